@@ -4,7 +4,7 @@ use std::sync::{
     atomic::{AtomicBool, Ordering},
     Arc, Mutex, OnceLock,
 };
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use sysinfo::{ProcessesToUpdate, System};
 use tauri::{Emitter, Manager, State};
 use tauri_runtime::ResizeDirection as TauriResizeDirection;
@@ -14,6 +14,7 @@ use tauri_runtime::ResizeDirection as TauriResizeDirection;
 // ---------------------------------------------------------------------------
 
 static AGENT_APP_HANDLE: OnceLock<tauri::AppHandle> = OnceLock::new();
+static APP_READY: OnceLock<AppReadyEvent> = OnceLock::new();
 static AGENT_BRIDGE_STATE: OnceLock<Arc<Mutex<AgentBridgeInner>>> = OnceLock::new();
 static AGENT_BRIDGE_FILES_DIR: OnceLock<std::path::PathBuf> = OnceLock::new();
 
@@ -322,12 +323,13 @@ fn agent_handle_dump(body: &str) -> (&'static str, String) {
     }
 }
 use voxvulgi_engine::models::ModelStore;
-use voxvulgi_engine::paths::AppPaths;
+use voxvulgi_engine::paths::{AppPaths, PathSource};
 use voxvulgi_engine::{
-    config, db, diagnostics, instagram_subscriptions, jobs, library, speakers, subscriptions,
-    subtitle_tracks, subtitles, tools, translate, voice_backend_adapters, voice_backends,
-    voice_benchmarks, voice_cast_packs, voice_cleanup, voice_library, voice_plans,
-    voice_reference_candidates, voice_reference_curation, voice_templates,
+    collections, config, db, diagnostics, ffmpeg, instagram_subscriptions, jobs, library,
+    migration, speakers, subscriptions, subtitle_tracks, subtitles, tools, translate,
+    voice_backend_adapters, voice_backends, voice_benchmarks, voice_cast_packs, voice_cleanup,
+    voice_library, voice_plans, voice_reference_candidates, voice_reference_curation,
+    voice_templates,
 };
 
 #[derive(Debug, Clone, serde::Deserialize)]
@@ -342,6 +344,14 @@ struct OfflineBundleManifest {
     payload_sha256: Option<String>,
     #[serde(default)]
     payload_sha256_algorithm: Option<String>,
+    /// Schema v2: payload zip entry path -> hex SHA-256 of the extracted file.
+    #[serde(default)]
+    checksums: std::collections::HashMap<String, String>,
+    #[serde(default)]
+    checksums_algorithm: Option<String>,
+    /// Legacy directory bundles: re-copy every file instead of skipping up-to-date ones.
+    #[serde(default)]
+    force_overwrite: bool,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -370,13 +380,18 @@ enum ArtifactKind {
     QcReport,
     BenchmarkReport,
     ReferenceCurationReport,
+    TtsSegmentAudio,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
 #[serde(rename_all = "snake_case")]
 enum ArtifactRerunKind {
     SeparateSpleeter,
+    #[serde(rename = "separate_spleeter_5stems")]
+    SeparateSpleeter5Stems,
     SeparateDemucs,
+    #[serde(rename = "separate_demucs_4stems")]
+    SeparateDemucs4Stems,
     CleanVocals,
     TtsPyttsx3,
     TtsNeuralLocalV1,
@@ -405,6 +420,7 @@ struct ArtifactInfo {
     voice_clone_converted_segments: Option<usize>,
     voice_clone_fallback_segments: Option<usize>,
     voice_clone_standard_tts_segments: Option<usize>,
+    duration_ms: Option<i64>,
     rerun_kind: Option<ArtifactRerunKind>,
 }
 
@@ -429,6 +445,11 @@ struct AppState {
     safe_mode_enabled: Arc<AtomicBool>,
     safe_mode_cli: bool,
     startup: Arc<Mutex<StartupTracker>>,
+    trace_correlation_id: Arc<Mutex<Option<String>>>,
+    trace_rate_limiter: Arc<Mutex<TraceRateLimiter>>,
+    app_handle: tauri::AppHandle,
+    /// Outcome of the startup database integrity check.
+    db_integrity: String,
 }
 
 impl Drop for AppState {
@@ -444,6 +465,130 @@ struct DiagnosticsInfo {
     app_name: String,
     app_version: String,
     engine_version: String,
+    is_portable: bool,
+    /// `os_app_data`, `environment` or `portable`.
+    data_dir_source: String,
+    db_integrity: String,
+}
+
+const TRACE_RATE_LIMIT_MAX_EVENTS: u32 = 100;
+const TRACE_RATE_LIMIT_WINDOW: Duration = Duration::from_secs(10);
+/// Distinct `(source, event)` windows tracked at once; further pairs share one overflow window, so
+/// unique event names cannot bypass the limit or grow the map without bound.
+const TRACE_RATE_LIMIT_MAX_KEYS: usize = 1000;
+const TRACE_RATE_LIMIT_OVERFLOW_EVENT: &str = "*";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum TraceAdmission {
+    Allowed,
+    Suppressed,
+}
+
+/// Events dropped in one `(source, event)` window, reported once that window ends.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SuppressedTraceEvents {
+    source: String,
+    event: String,
+    suppressed: u32,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct TraceWindow {
+    started: Instant,
+    admitted: u32,
+    suppressed: u32,
+}
+
+/// Caps frontend trace writes per `(source, event)` pair within a fixed window.
+#[derive(Debug, Default)]
+struct TraceRateLimiter {
+    windows: std::collections::HashMap<(String, String), TraceWindow>,
+}
+
+impl TraceRateLimiter {
+    fn admit(&mut self, source: &str, event: &str, now: Instant) -> TraceAdmission {
+        let mut key = (source.to_string(), event.to_string());
+        if !self.windows.contains_key(&key) && self.windows.len() >= TRACE_RATE_LIMIT_MAX_KEYS {
+            key = (
+                source.to_string(),
+                TRACE_RATE_LIMIT_OVERFLOW_EVENT.to_string(),
+            );
+        }
+        let window = self.windows.entry(key).or_insert(TraceWindow {
+            started: now,
+            admitted: 0,
+            suppressed: 0,
+        });
+        if window.admitted >= TRACE_RATE_LIMIT_MAX_EVENTS {
+            window.suppressed += 1;
+            return TraceAdmission::Suppressed;
+        }
+        window.admitted += 1;
+        TraceAdmission::Allowed
+    }
+
+    /// Drops every window that ended by `now` and returns the ones that suppressed events.
+    fn take_expired(&mut self, now: Instant) -> Vec<SuppressedTraceEvents> {
+        let mut expired = Vec::new();
+        self.windows.retain(|(source, event), window| {
+            if now.duration_since(window.started) < TRACE_RATE_LIMIT_WINDOW {
+                return true;
+            }
+            if window.suppressed > 0 {
+                expired.push(SuppressedTraceEvents {
+                    source: source.clone(),
+                    event: event.clone(),
+                    suppressed: window.suppressed,
+                });
+            }
+            false
+        });
+        expired
+    }
+
+    /// Drops every window, returning all pending suppressed counts (used on shutdown).
+    fn take_all(&mut self) -> Vec<SuppressedTraceEvents> {
+        self.windows
+            .drain()
+            .filter(|(_, window)| window.suppressed > 0)
+            .map(|((source, event), window)| SuppressedTraceEvents {
+                source,
+                event,
+                suppressed: window.suppressed,
+            })
+            .collect()
+    }
+
+    /// Suppressed counts in the current windows, keyed by `source:event`.
+    fn stats(&self) -> std::collections::HashMap<String, u32> {
+        self.windows
+            .iter()
+            .filter(|(_, window)| window.suppressed > 0)
+            .map(|((source, event), window)| (format!("{source}:{event}"), window.suppressed))
+            .collect()
+    }
+}
+
+fn append_suppressed_trace_rows(
+    paths: &AppPaths,
+    suppressed: &[SuppressedTraceEvents],
+    correlation_id: Option<String>,
+) {
+    for entry in suppressed {
+        let _ = append_diagnostics_trace_row(
+            paths,
+            "events_suppressed".to_string(),
+            serde_json::json!({
+                "source": &entry.source,
+                "event": &entry.event,
+                "suppressed": entry.suppressed,
+                "window_secs": TRACE_RATE_LIMIT_WINDOW.as_secs(),
+            }),
+            "warn".to_string(),
+            None,
+            correlation_id.clone(),
+        );
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -568,12 +713,43 @@ struct StartupStatus {
     phases: Vec<StartupPhase>,
 }
 
+/// Payload of the `app_ready` event, sent once setup and the offline bundle have finished.
+#[derive(Debug, Clone, serde::Serialize)]
+struct AppReadyEvent {
+    version: String,
+    engine_version: String,
+    offline_bundle_applied: bool,
+    db_schema_version: u32,
+}
+
+#[derive(Debug, Clone, serde::Serialize)]
+struct AppSetupErrorEvent {
+    error: String,
+    /// `"warning"` when the app keeps running, `"error"` when setup could not finish.
+    severity: &'static str,
+}
+
+fn emit_app_ready(app: &tauri::AppHandle, offline_bundle_applied: bool) {
+    let event = APP_READY.get_or_init(|| AppReadyEvent {
+        version: app.package_info().version.to_string(),
+        engine_version: voxvulgi_engine::ENGINE_VERSION.to_string(),
+        offline_bundle_applied,
+        db_schema_version: db::CURRENT_SCHEMA_VERSION,
+    });
+    let _ = app.emit("app_ready", event);
+}
+
+const DEFAULT_WATCH_DIRECTORY_INTERVAL_SECS: u64 = 30;
+/// `downloads_dir_set` rejects folders on volumes with less free space than this by default.
+const DEFAULT_DOWNLOAD_DIR_MIN_FREE_GB: f32 = 5.0;
+
 #[derive(Debug, Clone, serde::Serialize)]
 struct DownloadDirStatus {
     current_dir: String,
     default_dir: String,
     exists: bool,
     using_default: bool,
+    disk_free_gb: Option<f64>,
     feature_roots: Vec<FeatureStorageRootStatus>,
 }
 
@@ -714,6 +890,45 @@ struct DiagnosticsTraceClearSummary {
     removed_bytes: u64,
 }
 
+/// Filters for [`clear_dir_entries_with_bytes`]; the default clears every entry.
+#[derive(Debug, Clone, Default)]
+struct ClearOptions {
+    /// Only entries whose file name matches are removed.
+    pattern: Option<regex::Regex>,
+    /// Only entries whose mtime is at least this old are removed.
+    max_age_secs: Option<u64>,
+    /// Skip directories.
+    only_files: bool,
+}
+
+impl ClearOptions {
+    fn matches(&self, path: &std::path::Path, meta: &std::fs::Metadata) -> bool {
+        if self.only_files && meta.is_dir() {
+            return false;
+        }
+        if let Some(pattern) = &self.pattern {
+            let name = path
+                .file_name()
+                .map(|name| name.to_string_lossy())
+                .unwrap_or_default();
+            if !pattern.is_match(&name) {
+                return false;
+            }
+        }
+        if let Some(max_age_secs) = self.max_age_secs {
+            let age = meta
+                .modified()
+                .ok()
+                .and_then(|modified| modified.elapsed().ok());
+            match age {
+                Some(age) if age.as_secs() >= max_age_secs => {}
+                _ => return false,
+            }
+        }
+        true
+    }
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 struct DiagnosticsProcessSnapshot {
     pid: Option<u32>,
@@ -731,6 +946,10 @@ struct DiagnosticsTraceEntry {
     level: String,
     details: serde_json::Value,
     process: Option<DiagnosticsProcessSnapshot>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tags: Option<std::collections::HashMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    correlation_id: Option<String>,
 }
 
 #[derive(Debug, Clone, serde::Serialize)]
@@ -771,13 +990,6 @@ struct ExportedFile {
     file_bytes: u64,
 }
 
-#[derive(Debug, Default, Clone)]
-struct CopySummary {
-    copied_files: u64,
-    skipped_files: u64,
-    copied_bytes: u64,
-}
-
 #[derive(Debug, Default, Clone)]
 struct ZipExtractSummary {
     extracted_files: u64,
@@ -974,6 +1186,8 @@ fn append_diagnostics_trace_row(
     event: String,
     details: serde_json::Value,
     level: String,
+    tags: Option<std::collections::HashMap<String, String>>,
+    correlation_id: Option<String>,
 ) -> Result<String, String> {
     let path = diagnostics_trace_file_path(paths)?;
     let mut file = std::fs::OpenOptions::new()
@@ -988,6 +1202,8 @@ fn append_diagnostics_trace_row(
         level,
         details,
         process: capture_process_snapshot(),
+        tags: Some(diagnostics_trace_tags(tags)),
+        correlation_id,
     };
 
     use std::io::Write as _;
@@ -997,17 +1213,49 @@ fn append_diagnostics_trace_row(
         serde_json::to_string(&row).map_err(|e| e.to_string())?
     )
     .map_err(|e| e.to_string())?;
+    drop(file);
+
+    let trace_config = config::load_diagnostics_trace_config(paths).unwrap_or_default();
+    let _ = diagnostics::rotate_diagnostics_trace_if_needed(&path, &trace_config);
 
     Ok(path.to_string_lossy().to_string())
 }
 
+/// Every trace row carries the app version so mixed-version trace files stay readable.
+fn diagnostics_trace_tags(
+    tags: Option<std::collections::HashMap<String, String>>,
+) -> std::collections::HashMap<String, String> {
+    let mut out = tags.unwrap_or_default();
+    out.retain(|key, _| !key.trim().is_empty());
+    out.insert(
+        "app_version".to_string(),
+        env!("CARGO_PKG_VERSION").to_string(),
+    );
+    out
+}
+
+/// Correlates backend rows written outside any request (startup phases, background samples)
+/// with the launch that produced them.
+fn launch_correlation_id() -> &'static str {
+    static LAUNCH_ID: OnceLock<String> = OnceLock::new();
+    LAUNCH_ID.get_or_init(|| format!("launch-{}-{}", std::process::id(), now_epoch_ms_i64()))
+}
+
 fn append_diagnostics_trace_row_best_effort(
     paths: &AppPaths,
     event: &str,
     details: serde_json::Value,
     level: &str,
+    correlation_id: Option<&str>,
 ) {
-    let _ = append_diagnostics_trace_row(paths, event.to_string(), details, level.to_string());
+    let _ = append_diagnostics_trace_row(
+        paths,
+        event.to_string(),
+        details,
+        level.to_string(),
+        None,
+        correlation_id.map(str::to_string),
+    );
 }
 
 fn read_recent_diagnostics_trace_entries(
@@ -1056,23 +1304,83 @@ fn set_startup_phase(
             "error": error,
         }),
         if state == "error" { "error" } else { "info" },
+        Some(launch_correlation_id()),
     );
 }
 
-fn is_safe_relative_path(path: &std::path::Path) -> bool {
-    !path.components().any(|c| {
+/// File extensions (lowercase, without the dot; "" means none) a payload may write into `tools/`.
+const OFFLINE_BUNDLE_TOOLS_EXTENSIONS: &[&str] = &["exe", "dll", "", "sh", "bat", "cfg"];
+/// File extensions a payload may write into `models/`.
+const OFFLINE_BUNDLE_MODELS_EXTENSIONS: &[&str] = &[
+    "bin",
+    "gguf",
+    "safetensors",
+    "json",
+    "txt",
+    "tokenizer",
+    "vocab",
+    "bpe",
+    "model",
+    "pt",
+    "pth",
+];
+/// `tools/` subtrees holding whole runtimes (Python stdlib, site-packages, Deno) whose file types
+/// cannot be enumerated; they only get the traversal checks.
+const OFFLINE_BUNDLE_TOOLS_RUNTIME_DIRS: &[&str] = &["python/", "js_runtime/"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum SafePathMode {
+    /// Rejects only parent, root and prefix components.
+    Relaxed,
+    /// Additionally requires the file extension to be in the allowlist.
+    Strict(&'static [&'static str]),
+}
+
+fn is_safe_relative_path(path: &std::path::Path, mode: SafePathMode) -> bool {
+    let traversal = path.components().any(|c| {
         matches!(
             c,
             std::path::Component::ParentDir
                 | std::path::Component::RootDir
                 | std::path::Component::Prefix(_)
         )
-    })
+    });
+    if traversal {
+        return false;
+    }
+    match mode {
+        SafePathMode::Relaxed => true,
+        SafePathMode::Strict(allowed) => {
+            let ext = path
+                .extension()
+                .map(|e| e.to_string_lossy().to_ascii_lowercase())
+                .unwrap_or_default();
+            allowed.contains(&ext.as_str())
+        }
+    }
+}
+
+/// Path check for a file entry `rel` under the payload's `tools/`, `models/` or
+/// `cache/huggingface/` root.
+fn offline_bundle_safe_path_mode(root: &str, rel: &str) -> SafePathMode {
+    match root {
+        "tools/"
+            if !OFFLINE_BUNDLE_TOOLS_RUNTIME_DIRS
+                .iter()
+                .any(|dir| rel.starts_with(dir)) =>
+        {
+            SafePathMode::Strict(OFFLINE_BUNDLE_TOOLS_EXTENSIONS)
+        }
+        "models/" => SafePathMode::Strict(OFFLINE_BUNDLE_MODELS_EXTENSIONS),
+        // Hugging Face blobs are content-addressed and have no meaningful extension.
+        _ => SafePathMode::Relaxed,
+    }
 }
 
 fn extract_payload_zip_best_effort(
     zip_path: &std::path::Path,
     paths: &AppPaths,
+    checksums: Option<&std::collections::HashMap<String, String>>,
 ) -> Result<ZipExtractSummary, String> {
     use zip::result::ZipError;
 
@@ -1099,12 +1407,16 @@ fn extract_payload_zip_best_effort(
 
         let name = entry.name().replace('\\', "/");
 
-        let (dst_root, rel) = if let Some(rest) = name.strip_prefix("tools/") {
-            (paths.tools_dir(), rest)
+        let (root, dst_root, rel) = if let Some(rest) = name.strip_prefix("tools/") {
+            ("tools/", paths.tools_dir(), rest)
         } else if let Some(rest) = name.strip_prefix("models/") {
-            (paths.models_dir(), rest)
+            ("models/", paths.models_dir(), rest)
         } else if let Some(rest) = name.strip_prefix("cache/huggingface/") {
-            (paths.cache_dir().join("huggingface"), rest)
+            (
+                "cache/huggingface/",
+                paths.cache_dir().join("huggingface"),
+                rest,
+            )
         } else {
             continue;
         };
@@ -1115,7 +1427,12 @@ fn extract_payload_zip_best_effort(
         }
 
         let rel_path = std::path::Path::new(rel);
-        if !is_safe_relative_path(rel_path) {
+        let mode = if entry.is_dir() {
+            SafePathMode::Relaxed
+        } else {
+            offline_bundle_safe_path_mode(root, rel)
+        };
+        if !is_safe_relative_path(rel_path, mode) {
             return Err(format!("unsafe payload zip path: {name}"));
         }
 
@@ -1126,9 +1443,21 @@ fn extract_payload_zip_best_effort(
             continue;
         }
 
+        let expected_sha256 = checksums.and_then(|map| map.get(&name));
         if let Ok(meta) = std::fs::metadata(&out_path) {
-            let expected = entry.size();
-            if expected > 0 && meta.is_file() && meta.len() == expected {
+            let unchanged = match expected_sha256 {
+                Some(expected) => {
+                    meta.is_file()
+                        && tools::compute_sha256(&out_path)
+                            .map(|actual| actual.eq_ignore_ascii_case(expected))
+                            .unwrap_or(false)
+                }
+                None => {
+                    let expected = entry.size();
+                    expected > 0 && meta.is_file() && meta.len() == expected
+                }
+            };
+            if unchanged {
                 summary.skipped_files += 1;
                 continue;
             }
@@ -1149,6 +1478,16 @@ fn extract_payload_zip_best_effort(
                 .map_err(|e| format!("failed to extract {}: {e}", name))?;
         }
 
+        if let Some(expected) = expected_sha256 {
+            let actual = tools::compute_sha256(&tmp).map_err(|e| e.to_string())?;
+            if !actual.eq_ignore_ascii_case(expected) {
+                let _ = std::fs::remove_file(&tmp);
+                return Err(format!(
+                    "offline bundle checksum mismatch for {name}: expected={expected} actual={actual}"
+                ));
+            }
+        }
+
         if out_path.exists() {
             let _ = std::fs::remove_file(&out_path);
         }
@@ -1198,12 +1537,39 @@ fn read_offline_bundle_manifest(
             manifest_path.to_string_lossy()
         )
     })?;
-    serde_json::from_slice::<OfflineBundleManifest>(&bytes).map_err(|e| {
+    let mut manifest = serde_json::from_slice::<OfflineBundleManifest>(&bytes).map_err(|e| {
         format!(
             "offline bundle manifest is invalid JSON ({}): {e}",
             manifest_path.to_string_lossy()
         )
-    })
+    })?;
+    match manifest.schema_version {
+        1 => manifest.checksums.clear(),
+        2 => {
+            let algorithm = manifest
+                .checksums_algorithm
+                .as_deref()
+                .unwrap_or("sha256")
+                .trim()
+                .to_ascii_lowercase();
+            if algorithm != "sha256" {
+                return Err(format!(
+                    "unsupported offline bundle checksums algorithm: {algorithm}"
+                ));
+            }
+            manifest.checksums = manifest
+                .checksums
+                .into_iter()
+                .map(|(path, sha256)| (path.replace('\\', "/"), sha256.trim().to_string()))
+                .collect();
+        }
+        other => {
+            return Err(format!(
+                "unsupported offline bundle schema_version: {other}"
+            ))
+        }
+    }
+    Ok(manifest)
 }
 
 fn sha256_hex_file(path: &std::path::Path) -> Result<String, String> {
@@ -1293,18 +1659,33 @@ fn offline_bundle_marker_path(paths: &AppPaths) -> std::path::PathBuf {
     paths.config_dir().join("offline_bundle_applied_v1.json")
 }
 
-fn offline_bundle_already_applied(paths: &AppPaths, bundle_id: &str) -> bool {
-    let marker = offline_bundle_marker_path(paths);
-    let Ok(bytes) = std::fs::read(marker) else {
-        return false;
-    };
-    let Ok(v) = serde_json::from_slice::<serde_json::Value>(&bytes) else {
-        return false;
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct OfflineBundleAppliedEntry {
+    bundle_id: String,
+    bundle_root: String,
+    applied_at_ms: i64,
+    schema_version: u32,
+}
+
+const OFFLINE_BUNDLE_APPLIED_MAX_ENTRIES: usize = 100;
+
+/// Applied bundles, oldest first. Markers written before the list format hold a single object.
+fn read_offline_bundle_applied_entries(paths: &AppPaths) -> Vec<OfflineBundleAppliedEntry> {
+    let Ok(bytes) = std::fs::read(offline_bundle_marker_path(paths)) else {
+        return Vec::new();
     };
-    v.get("bundle_id")
-        .and_then(|s| s.as_str())
-        .map(|s| s == bundle_id)
-        .unwrap_or(false)
+    if let Ok(entries) = serde_json::from_slice::<Vec<OfflineBundleAppliedEntry>>(&bytes) {
+        return entries;
+    }
+    serde_json::from_slice::<OfflineBundleAppliedEntry>(&bytes)
+        .map(|entry| vec![entry])
+        .unwrap_or_default()
+}
+
+fn offline_bundle_already_applied(paths: &AppPaths, bundle_id: &str) -> bool {
+    read_offline_bundle_applied_entries(paths)
+        .iter()
+        .any(|entry| entry.bundle_id == bundle_id)
 }
 
 fn write_offline_bundle_marker(
@@ -1317,21 +1698,26 @@ fn write_offline_bundle_marker(
         std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
     }
 
-    let record = serde_json::json!({
-        "schema_version": 1,
-        "bundle_id": bundle_id,
-        "bundle_root": bundle_root.to_string_lossy(),
-        "applied_at_ms": std::time::SystemTime::now()
+    let mut entries = read_offline_bundle_applied_entries(paths);
+    entries.push(OfflineBundleAppliedEntry {
+        bundle_id: bundle_id.to_string(),
+        bundle_root: bundle_root.to_string_lossy().to_string(),
+        applied_at_ms: std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap_or_default()
             .as_millis() as i64,
+        schema_version: 1,
     });
+    if entries.len() > OFFLINE_BUNDLE_APPLIED_MAX_ENTRIES {
+        let excess = entries.len() - OFFLINE_BUNDLE_APPLIED_MAX_ENTRIES;
+        entries.drain(..excess);
+    }
 
     std::fs::write(
         &marker,
         format!(
             "{}\n",
-            serde_json::to_string_pretty(&record).unwrap_or_else(|_| "{}".to_string())
+            serde_json::to_string_pretty(&entries).unwrap_or_else(|_| "[]".to_string())
         ),
     )
     .map_err(|e| {
@@ -1343,100 +1729,31 @@ fn write_offline_bundle_marker(
     Ok(())
 }
 
-fn copy_tree_best_effort(
-    src_root: &std::path::Path,
-    dst_root: &std::path::Path,
-) -> Result<CopySummary, String> {
-    if !src_root.exists() {
-        return Ok(CopySummary::default());
+/// `pyvenv.cfg` rejects verbatim (`\\?\`) paths that canonicalization produces on Windows.
+fn normalize_path_for_pyvenv_cfg(path: &std::path::Path) -> String {
+    let raw = path.to_string_lossy();
+    if cfg!(windows) {
+        strip_windows_verbatim_prefix(&raw)
+    } else {
+        raw.to_string()
     }
+}
 
-    let mut summary = CopySummary::default();
-    let mut stack: Vec<std::path::PathBuf> = vec![src_root.to_path_buf()];
-
-    while let Some(dir) = stack.pop() {
-        let entries = std::fs::read_dir(&dir)
-            .map_err(|e| format!("failed to read dir {}: {e}", dir.to_string_lossy()))?;
-
-        for entry in entries.flatten() {
-            let path = entry.path();
-            let file_type = match entry.file_type() {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
-
-            let rel = match path.strip_prefix(src_root) {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
-            let dst = dst_root.join(rel);
-
-            if file_type.is_dir() {
-                std::fs::create_dir_all(&dst)
-                    .map_err(|e| format!("failed to create dir {}: {e}", dst.to_string_lossy()))?;
-                stack.push(path);
-                continue;
-            }
-
-            if !file_type.is_file() {
-                continue;
-            }
-
-            let src_meta = match std::fs::metadata(&path) {
-                Ok(v) => v,
-                Err(_) => continue,
-            };
-
-            if let Ok(dst_meta) = std::fs::metadata(&dst) {
-                if dst_meta.len() == src_meta.len() && src_meta.len() > 0 {
-                    summary.skipped_files += 1;
-                    continue;
-                }
-            }
-
-            if let Some(parent) = dst.parent() {
-                std::fs::create_dir_all(parent).map_err(|e| {
-                    format!("failed to create dir {}: {e}", parent.to_string_lossy())
-                })?;
-            }
-
-            let tmp = dst.with_extension("copying");
-            let _ = std::fs::remove_file(&tmp);
-
-            std::fs::copy(&path, &tmp).map_err(|e| {
-                format!(
-                    "failed to copy {} -> {}: {e}",
-                    path.to_string_lossy(),
-                    tmp.to_string_lossy()
-                )
-            })?;
-
-            if dst.exists() {
-                let _ = std::fs::remove_file(&dst);
-            }
-            if std::fs::rename(&tmp, &dst).is_err() {
-                std::fs::copy(&tmp, &dst).map_err(|e| {
-                    format!(
-                        "failed to finalize copy {} -> {}: {e}",
-                        tmp.to_string_lossy(),
-                        dst.to_string_lossy()
-                    )
-                })?;
-                let _ = std::fs::remove_file(&tmp);
-            }
-
-            summary.copied_files += 1;
-            summary.copied_bytes += src_meta.len();
-        }
+/// `\\?\C:\x` becomes `C:\x` and `\\?\UNC\server\share` becomes `\\server\share`.
+fn strip_windows_verbatim_prefix(raw: &str) -> String {
+    if let Some(rest) = raw.strip_prefix(r"\\?\UNC\") {
+        return format!(r"\\{rest}");
+    }
+    match raw.strip_prefix(r"\\?\") {
+        Some(rest) if rest.as_bytes().get(1) == Some(&b':') => rest.to_string(),
+        _ => raw.to_string(),
     }
-
-    Ok(summary)
 }
 
 fn patch_venv_pyvenv_cfg_best_effort(paths: &AppPaths) -> Result<(), String> {
     let venv_dir = paths.python_venv_dir();
     let cfg_path = venv_dir.join("pyvenv.cfg");
-    if !cfg_path.is_file() {
+    if !cfg_path.is_file() || tools::venv_uses_system_python(paths) {
         return Ok(());
     }
 
@@ -1446,6 +1763,9 @@ fn patch_venv_pyvenv_cfg_best_effort(paths: &AppPaths) -> Result<(), String> {
         return Ok(());
     }
 
+    let home = normalize_path_for_pyvenv_cfg(&portable_dir);
+    let executable = normalize_path_for_pyvenv_cfg(&portable_python);
+    let venv = normalize_path_for_pyvenv_cfg(&venv_dir);
     let raw = std::fs::read_to_string(&cfg_path)
         .map_err(|e| format!("failed to read {}: {e}", cfg_path.to_string_lossy()))?;
 
@@ -1457,24 +1777,17 @@ fn patch_venv_pyvenv_cfg_best_effort(paths: &AppPaths) -> Result<(), String> {
     for line in raw.lines() {
         let trimmed = line.trim_start();
         if trimmed.starts_with("home =") {
-            out.push(format!("home = {}", portable_dir.to_string_lossy()));
+            out.push(format!("home = {home}"));
             wrote_home = true;
             continue;
         }
         if trimmed.starts_with("executable =") {
-            out.push(format!(
-                "executable = {}",
-                portable_python.to_string_lossy()
-            ));
+            out.push(format!("executable = {executable}"));
             wrote_executable = true;
             continue;
         }
         if trimmed.starts_with("command =") {
-            out.push(format!(
-                "command = {} -m venv {}",
-                portable_python.to_string_lossy(),
-                venv_dir.to_string_lossy()
-            ));
+            out.push(format!("command = {executable} -m venv {venv}"));
             wrote_command = true;
             continue;
         }
@@ -1482,20 +1795,13 @@ fn patch_venv_pyvenv_cfg_best_effort(paths: &AppPaths) -> Result<(), String> {
     }
 
     if !wrote_home {
-        out.push(format!("home = {}", portable_dir.to_string_lossy()));
+        out.push(format!("home = {home}"));
     }
     if !wrote_executable {
-        out.push(format!(
-            "executable = {}",
-            portable_python.to_string_lossy()
-        ));
+        out.push(format!("executable = {executable}"));
     }
     if !wrote_command {
-        out.push(format!(
-            "command = {} -m venv {}",
-            portable_python.to_string_lossy(),
-            venv_dir.to_string_lossy()
-        ));
+        out.push(format!("command = {executable} -m venv {venv}"));
     }
 
     std::fs::write(&cfg_path, format!("{}\n", out.join("\n")))
@@ -1503,24 +1809,19 @@ fn patch_venv_pyvenv_cfg_best_effort(paths: &AppPaths) -> Result<(), String> {
     Ok(())
 }
 
+/// Applies the bundled offline payload once. Returns true when files were installed this launch.
 fn apply_offline_bundle_if_present(
     paths: &AppPaths,
     resource_dir: &std::path::Path,
-) -> Result<(), String> {
+) -> Result<bool, String> {
     let Some(bundle_root) = find_offline_bundle_root(resource_dir) else {
-        return Ok(());
+        return Ok(false);
     };
 
     let manifest = read_offline_bundle_manifest(&bundle_root)?;
-    if manifest.schema_version != 1 {
-        return Err(format!(
-            "unsupported offline bundle schema_version: {}",
-            manifest.schema_version
-        ));
-    }
 
     if offline_bundle_already_applied(paths, &manifest.bundle_id) {
-        return Ok(());
+        return Ok(false);
     }
 
     eprintln!(
@@ -1539,7 +1840,8 @@ fn apply_offline_bundle_if_present(
 
     if payload_zip_path.is_file() {
         verify_offline_payload_integrity(&manifest, &payload_zip_path)?;
-        let sum = extract_payload_zip_best_effort(&payload_zip_path, paths)?;
+        let checksums = (manifest.schema_version >= 2).then_some(&manifest.checksums);
+        let sum = extract_payload_zip_best_effort(&payload_zip_path, paths, checksums)?;
         patch_venv_pyvenv_cfg_best_effort(paths)?;
         write_offline_bundle_marker(paths, &bundle_root, &manifest.bundle_id)?;
 
@@ -1548,7 +1850,7 @@ fn apply_offline_bundle_if_present(
             payload_zip_name, sum.extracted_files, sum.extracted_bytes, sum.skipped_files,
         );
 
-        return Ok(());
+        return Ok(true);
     }
 
     // Back-compat: directory-based bundle format.
@@ -1563,9 +1865,17 @@ fn apply_offline_bundle_if_present(
         ));
     }
 
-    let tools_sum = copy_tree_best_effort(&tools_src, &paths.tools_dir())?;
-    let models_sum = copy_tree_best_effort(&models_src, &paths.models_dir())?;
-    let hf_sum = copy_tree_best_effort(&hf_cache_src, &paths.cache_dir().join("huggingface"))?;
+    let force = manifest.force_overwrite;
+    let tools_sum = migration::copy_tree_best_effort(&tools_src, &paths.tools_dir(), force)
+        .map_err(|e| e.to_string())?;
+    let models_sum = migration::copy_tree_best_effort(&models_src, &paths.models_dir(), force)
+        .map_err(|e| e.to_string())?;
+    let hf_sum = migration::copy_tree_best_effort(
+        &hf_cache_src,
+        &paths.cache_dir().join("huggingface"),
+        force,
+    )
+    .map_err(|e| e.to_string())?;
 
     patch_venv_pyvenv_cfg_best_effort(paths)?;
     write_offline_bundle_marker(paths, &bundle_root, &manifest.bundle_id)?;
@@ -1583,7 +1893,7 @@ fn apply_offline_bundle_if_present(
         hf_sum.skipped_files,
     );
 
-    Ok(())
+    Ok(true)
 }
 
 #[cfg(test)]
@@ -1591,6 +1901,191 @@ mod tests {
     use super::*;
     use voxvulgi_engine::{config, db, paths::AppPaths};
 
+    #[test]
+    fn offline_bundle_paths_block_traversal_and_unlisted_file_types() {
+        let check = |root: &str, rel: &str| {
+            is_safe_relative_path(
+                std::path::Path::new(rel),
+                offline_bundle_safe_path_mode(root, rel),
+            )
+        };
+
+        for (root, rel) in [
+            ("tools/", "../evil.exe"),
+            ("tools/", "ffmpeg/../../evil.exe"),
+            ("tools/", "ffmpeg/payload.ps1"),
+            ("tools/", "yt-dlp/update.py"),
+            ("tools/", "ffmpeg/helper.vbs"),
+            ("models/", "whispercpp/run.exe"),
+            ("models/", "kokoro/install.sh"),
+            ("models/", "kokoro/hook.dll"),
+            ("models/", "whispercpp/loader.py"),
+            ("cache/huggingface/", "../../tools/evil.exe"),
+        ] {
+            assert!(!check(root, rel), "{root}{rel} should be blocked");
+        }
+        assert!(!is_safe_relative_path(
+            std::path::Path::new("/etc/passwd"),
+            SafePathMode::Relaxed
+        ));
+
+        for (root, rel) in [
+            ("tools/", "ffmpeg/ffmpeg.exe"),
+            ("tools/", "yt-dlp/yt-dlp"),
+            ("tools/", "python/portable/Lib/os.py"),
+            ("models/", "whispercpp/1/tiny.BIN"),
+            ("models/", "kokoro/1/config.json"),
+            ("cache/huggingface/", "hub/models--x/blobs/abc123"),
+        ] {
+            assert!(check(root, rel), "{root}{rel} should be allowed");
+        }
+    }
+
+    #[test]
+    fn trace_rate_limiter_suppresses_bursts_and_reports_on_reset() {
+        let mut limiter = TraceRateLimiter::default();
+        let start = Instant::now();
+        for _ in 0..TRACE_RATE_LIMIT_MAX_EVENTS {
+            assert_eq!(limiter.admit("ui", "click", start), TraceAdmission::Allowed);
+        }
+        assert_eq!(
+            limiter.admit("ui", "click", start),
+            TraceAdmission::Suppressed
+        );
+        assert_eq!(
+            limiter.admit("ui", "click", start),
+            TraceAdmission::Suppressed
+        );
+        assert_eq!(
+            limiter.admit("ui", "scroll", start),
+            TraceAdmission::Allowed
+        );
+        assert_eq!(limiter.stats().get("ui:click"), Some(&2));
+        assert!(limiter.take_expired(start).is_empty());
+
+        // Every expired window is evicted, and its count is reported even without a new event
+        // for the same pair.
+        let later = start + TRACE_RATE_LIMIT_WINDOW;
+        assert_eq!(
+            limiter.take_expired(later),
+            vec![SuppressedTraceEvents {
+                source: "ui".to_string(),
+                event: "click".to_string(),
+                suppressed: 2,
+            }]
+        );
+        assert!(limiter.windows.is_empty());
+        assert!(limiter.stats().is_empty());
+
+        limiter.admit("ui", "click", later);
+        assert!(limiter.take_all().is_empty());
+    }
+
+    #[test]
+    fn trace_rate_limiter_caps_distinct_event_names() {
+        let mut limiter = TraceRateLimiter::default();
+        let now = Instant::now();
+        for i in 0..TRACE_RATE_LIMIT_MAX_KEYS {
+            limiter.admit("ui", &format!("event-{i}"), now);
+        }
+        for i in 0..TRACE_RATE_LIMIT_MAX_EVENTS {
+            assert_eq!(
+                limiter.admit("ui", &format!("unique-{i}"), now),
+                TraceAdmission::Allowed
+            );
+        }
+        assert_eq!(
+            limiter.admit("ui", "unique-last", now),
+            TraceAdmission::Suppressed
+        );
+        assert_eq!(limiter.windows.len(), TRACE_RATE_LIMIT_MAX_KEYS + 1);
+        assert_eq!(
+            limiter.take_all(),
+            vec![SuppressedTraceEvents {
+                source: "ui".to_string(),
+                event: TRACE_RATE_LIMIT_OVERFLOW_EVENT.to_string(),
+                suppressed: 1,
+            }]
+        );
+    }
+
+    #[test]
+    fn clear_dir_entries_respects_pattern_and_only_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        std::fs::write(dir.path().join("trace.jsonl"), b"abc").expect("write");
+        std::fs::write(dir.path().join("notes.txt"), b"keep").expect("write");
+        std::fs::create_dir_all(dir.path().join("trace.d")).expect("mkdir");
+
+        let options = ClearOptions {
+            pattern: Some(regex::Regex::new(r"^trace").expect("regex")),
+            only_files: true,
+            ..ClearOptions::default()
+        };
+        let summary = clear_dir_entries_with_bytes(dir.path(), &options).expect("clear");
+        assert_eq!(summary.removed_entries, 1);
+        assert_eq!(summary.removed_bytes, 3);
+        assert!(!dir.path().join("trace.jsonl").exists());
+        assert!(dir.path().join("notes.txt").exists());
+        assert!(dir.path().join("trace.d").exists());
+
+        let too_young = ClearOptions {
+            max_age_secs: Some(60 * 60),
+            ..ClearOptions::default()
+        };
+        let summary = clear_dir_entries_with_bytes(dir.path(), &too_young).expect("clear");
+        assert_eq!(summary.removed_entries, 0);
+        assert!(dir.path().join("notes.txt").exists());
+    }
+
+    #[test]
+    fn pyvenv_cfg_paths_drop_windows_verbatim_prefix() {
+        assert_eq!(
+            strip_windows_verbatim_prefix(r"\\?\C:\foo\bar"),
+            r"C:\foo\bar"
+        );
+        assert_eq!(
+            strip_windows_verbatim_prefix(r"\\?\UNC\server\share\venv"),
+            r"\\server\share\venv"
+        );
+        assert_eq!(strip_windows_verbatim_prefix(r"C:\foo"), r"C:\foo");
+        assert_eq!(
+            strip_windows_verbatim_prefix(r"\\?\Volume{abc}\x"),
+            r"\\?\Volume{abc}\x"
+        );
+        if !cfg!(windows) {
+            assert_eq!(
+                normalize_path_for_pyvenv_cfg(std::path::Path::new("/opt/venv")),
+                "/opt/venv"
+            );
+        }
+    }
+
+    #[test]
+    fn offline_bundle_marker_keeps_every_applied_bundle() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        std::fs::create_dir_all(paths.config_dir()).expect("config dir");
+        std::fs::write(
+            offline_bundle_marker_path(&paths),
+            r#"{"schema_version":1,"bundle_id":"legacy","bundle_root":"/b","applied_at_ms":1}"#,
+        )
+        .expect("legacy marker");
+        assert!(offline_bundle_already_applied(&paths, "legacy"));
+
+        let root = std::path::Path::new("/bundles/next");
+        write_offline_bundle_marker(&paths, root, "next").expect("marker");
+        assert!(offline_bundle_already_applied(&paths, "legacy"));
+        assert!(offline_bundle_already_applied(&paths, "next"));
+
+        for i in 0..OFFLINE_BUNDLE_APPLIED_MAX_ENTRIES {
+            write_offline_bundle_marker(&paths, root, &format!("b{i}")).expect("marker");
+        }
+        let entries = read_offline_bundle_applied_entries(&paths);
+        assert_eq!(entries.len(), OFFLINE_BUNDLE_APPLIED_MAX_ENTRIES);
+        assert!(!offline_bundle_already_applied(&paths, "legacy"));
+        assert_eq!(entries.last().map(|e| e.bundle_id.as_str()), Some("b99"));
+    }
+
     #[test]
     fn verify_offline_payload_integrity_accepts_matching_bytes_and_hash() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -1606,6 +2101,9 @@ mod tests {
                 "808B59664B6ADB9274E3BBD0766E7AEC9659786C22FDB825C49CA7FDA1C6236E".to_string(),
             ),
             payload_sha256_algorithm: Some("sha256".to_string()),
+            checksums: Default::default(),
+            checksums_algorithm: None,
+            force_overwrite: false,
         };
 
         verify_offline_payload_integrity(&manifest, &payload).expect("verify");
@@ -1624,6 +2122,9 @@ mod tests {
             payload_bytes: Some(13),
             payload_sha256: Some("DEADBEEF".to_string()),
             payload_sha256_algorithm: Some("sha256".to_string()),
+            checksums: Default::default(),
+            checksums_algorithm: None,
+            force_overwrite: false,
         };
 
         let err = verify_offline_payload_integrity(&manifest, &payload).expect_err("mismatch");
@@ -1631,14 +2132,51 @@ mod tests {
     }
 
     #[test]
-    fn phase2_latest_state_marks_interrupted_steps_when_job_failed() {
+    fn extract_payload_zip_v2_reextracts_files_whose_checksum_differs() {
+        use std::io::Write as _;
+
         let dir = tempfile::tempdir().expect("tempdir");
-        let paths = AppPaths::new(dir.path().to_path_buf());
-        let job = jobs::enqueue_dummy_sleep(&paths, 1).expect("enqueue");
-        let finished_at_ms = now_epoch_ms_i64();
-        let conn = db::open(&paths).expect("db");
-        db::migrate(&conn).expect("migrate");
-        conn.execute(
+        let paths = AppPaths::new(dir.path().join("app"));
+        let zip_path = dir.path().join("payload.zip");
+        {
+            let file = std::fs::File::create(&zip_path).expect("zip file");
+            let mut zip = zip::ZipWriter::new(file);
+            let options = zip::write::FileOptions::default();
+            zip.start_file("tools/a.txt", options).expect("start a");
+            zip.write_all(b"new-a").expect("write a");
+            zip.start_file("tools/b.txt", options).expect("start b");
+            zip.write_all(b"same!").expect("write b");
+            zip.finish().expect("finish");
+        }
+
+        std::fs::create_dir_all(paths.tools_dir()).expect("tools dir");
+        std::fs::write(paths.tools_dir().join("a.txt"), b"old-a").expect("seed a");
+        std::fs::write(paths.tools_dir().join("b.txt"), b"same!").expect("seed b");
+
+        let mut checksums = std::collections::HashMap::new();
+        for (name, body) in [("tools/a.txt", b"new-a"), ("tools/b.txt", b"same!")] {
+            checksums.insert(name.to_string(), hex::encode(Sha256::digest(body)));
+        }
+
+        let sum =
+            extract_payload_zip_best_effort(&zip_path, &paths, Some(&checksums)).expect("extract");
+        assert_eq!(sum.extracted_files, 1);
+        assert_eq!(sum.skipped_files, 1);
+        assert_eq!(
+            std::fs::read(paths.tools_dir().join("a.txt")).expect("read a"),
+            b"new-a"
+        );
+    }
+
+    #[test]
+    fn phase2_latest_state_marks_interrupted_steps_when_job_failed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let job = jobs::enqueue_dummy_sleep(&paths, 1).expect("enqueue");
+        let finished_at_ms = now_epoch_ms_i64();
+        let conn = db::open(&paths).expect("db");
+        db::migrate(&conn).expect("migrate");
+        conn.execute(
             "UPDATE job SET status='failed', error='interrupted by app shutdown', finished_at_ms=?1 WHERE id=?2",
             (finished_at_ms, &job.id),
         )
@@ -1670,6 +2208,48 @@ mod tests {
         assert_eq!(statuses, vec!["done", "interrupted", "interrupted"]);
     }
 
+    #[test]
+    fn item_artifacts_list_includes_tts_segments_after_tts_group() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let backend_dir = paths
+            .derived_item_dir("item-1")
+            .join("tts_preview")
+            .join("pyttsx3_v1");
+        std::fs::create_dir_all(&backend_dir).expect("mkdir");
+        let segments = (0..(MAX_ARTIFACT_TTS_SEGMENTS_PER_BACKEND + 5))
+            .map(|index| {
+                serde_json::json!({
+                    "index": index,
+                    "audio_path": backend_dir.join(format!("seg_{index:04}.wav")),
+                })
+            })
+            .collect::<Vec<_>>();
+        std::fs::write(
+            backend_dir.join("manifest.json"),
+            serde_json::json!({ "segments": segments }).to_string(),
+        )
+        .expect("write manifest");
+
+        let artifacts = collect_item_artifacts_v1(&paths, "item-1");
+        let segment_entries = artifacts
+            .iter()
+            .filter(|artifact| artifact.group == "TTS Segments")
+            .collect::<Vec<_>>();
+        assert_eq!(segment_entries.len(), MAX_ARTIFACT_TTS_SEGMENTS_PER_BACKEND);
+        assert_eq!(segment_entries[0].id, "tts_seg_pyttsx3_v1_0000");
+
+        let last_tts = artifacts
+            .iter()
+            .rposition(|artifact| artifact.group == "TTS")
+            .expect("tts group");
+        let first_segment = artifacts
+            .iter()
+            .position(|artifact| artifact.group == "TTS Segments")
+            .expect("segments group");
+        assert!(last_tts < first_segment);
+    }
+
     #[test]
     fn artifact_info_serializes_runtime_contract_in_snake_case() {
         let artifact = ArtifactInfo {
@@ -1689,6 +2269,7 @@ mod tests {
             voice_clone_converted_segments: Some(4),
             voice_clone_fallback_segments: Some(0),
             voice_clone_standard_tts_segments: Some(0),
+            duration_ms: None,
             rerun_kind: Some(ArtifactRerunKind::MuxDubPreviewV1),
         };
 
@@ -1831,6 +2412,7 @@ mod tests {
             "VoxVulgi".to_string(),
             "0.1.5".to_string(),
             startup,
+            "ok".to_string(),
         )
         .expect("snapshot");
         let export = write_diagnostics_app_state_snapshot_exports(
@@ -1964,6 +2546,11 @@ fn build_download_dir_status(paths: &AppPaths) -> Result<DownloadDirStatus, Stri
         default_dir: default_dir.to_string_lossy().to_string(),
         exists,
         using_default: override_dir.is_none(),
+        disk_free_gb: if exists {
+            diagnostics::disk_free_gb(&current_dir).ok()
+        } else {
+            None
+        },
         feature_roots,
     })
 }
@@ -2193,6 +2780,7 @@ fn path_size_bytes_best_effort(path: &std::path::Path) -> u64 {
 
 fn clear_dir_entries_with_bytes(
     dir: &std::path::Path,
+    options: &ClearOptions,
 ) -> Result<DiagnosticsTraceClearSummary, String> {
     std::fs::create_dir_all(dir).map_err(|e| e.to_string())?;
     let entries = std::fs::read_dir(dir).map_err(|e| e.to_string())?;
@@ -2202,11 +2790,14 @@ fn clear_dir_entries_with_bytes(
 
     for entry in entries.flatten() {
         let path = entry.path();
-        removed_bytes = removed_bytes.saturating_add(path_size_bytes_best_effort(&path));
         let meta = match std::fs::symlink_metadata(&path) {
             Ok(v) => v,
             Err(_) => continue,
         };
+        if !options.matches(&path, &meta) {
+            continue;
+        }
+        removed_bytes = removed_bytes.saturating_add(path_size_bytes_best_effort(&path));
         if meta.is_dir() {
             std::fs::remove_dir_all(&path).map_err(|e| e.to_string())?;
         } else {
@@ -2595,6 +3186,7 @@ fn build_diagnostics_app_state_snapshot(
     app_name: String,
     app_version: String,
     startup: StartupStatus,
+    db_integrity: String,
 ) -> Result<DiagnosticsAppStateSnapshot, String> {
     let app = DiagnosticsInfo {
         app_data_dir: paths.base_dir.to_string_lossy().to_string(),
@@ -2606,6 +3198,9 @@ fn build_diagnostics_app_state_snapshot(
         app_name,
         app_version,
         engine_version: diagnostics::engine_version().to_string(),
+        is_portable: paths.is_portable(),
+        data_dir_source: paths.source.as_str().to_string(),
+        db_integrity,
     };
     let download_roots = build_download_dir_status(paths)?;
     let diagnostics_trace_dir = build_diagnostics_trace_dir_status(paths)?;
@@ -2633,7 +3228,9 @@ fn build_diagnostics_app_state_snapshot(
         config::load_batch_on_import_rules(paths).map_err(|e| e.to_string())?;
     let optional_diarization_backend =
         config::load_optional_diarization_backend_status(paths).map_err(|e| e.to_string())?;
-    let storage = diagnostics::storage_breakdown(paths).map_err(|e| e.to_string())?;
+    let storage =
+        diagnostics::storage_breakdown(paths, diagnostics::DEFAULT_STORAGE_BREAKDOWN_TOP_N)
+            .map_err(|e| e.to_string())?;
     let thumbnail_cache = library::thumbnail_cache_status(paths).map_err(|e| e.to_string())?;
     let jobs = build_job_queue_snapshot(paths)?;
     let library = build_library_snapshot(paths)?;
@@ -2714,6 +3311,9 @@ fn diagnostics_info(app: tauri::AppHandle, state: State<'_, AppState>) -> Diagno
         app_name: package.name.to_string(),
         app_version: package.version.to_string(),
         engine_version: diagnostics::engine_version().to_string(),
+        is_portable: state.paths.is_portable(),
+        data_dir_source: state.paths.source.as_str().to_string(),
+        db_integrity: state.db_integrity.clone(),
     }
 }
 
@@ -3077,6 +3677,42 @@ async fn library_thumbnail_data_url(
     .map_err(|e| e.to_string())?
 }
 
+#[tauri::command]
+async fn library_item_thumbnail_path(
+    state: State<'_, AppState>,
+    item_id: String,
+) -> Result<Option<String>, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        library::ensure_thumbnail_path(&paths, item_id.trim())
+            .map(|path| path.map(|p| p.to_string_lossy().to_string()))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn item_background_silences(
+    state: State<'_, AppState>,
+    item_id: String,
+) -> Result<Vec<ffmpeg::SilenceRange>, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        jobs::item_background_silences(&paths, item_id.trim()).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+fn item_tts_manifest_summary(
+    state: State<'_, AppState>,
+    item_id: String,
+) -> Result<Option<jobs::TtsManifestSummary>, String> {
+    jobs::item_tts_manifest_summary(&state.paths, item_id.trim()).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[allow(non_snake_case)]
 fn item_qc_report_v1_load(
@@ -3087,7 +3723,7 @@ fn item_qc_report_v1_load(
     trackId: Option<String>,
     variant_label: Option<String>,
     variantLabel: Option<String>,
-) -> Result<Option<serde_json::Value>, String> {
+) -> Result<Option<jobs::QcReportV1Result>, String> {
     let item_id = item_id
         .or(itemId)
         .map(|v| v.trim().to_string())
@@ -3098,27 +3734,10 @@ fn item_qc_report_v1_load(
         .map(|v| v.trim().to_string())
         .filter(|v| !v.is_empty())
         .ok_or_else(|| "missing required key trackId".to_string())?;
+    let variant_label = variant_label.or(variantLabel);
 
-    let variant_label = variant_label
-        .or(variantLabel)
-        .map(|value| value.trim().to_string())
-        .filter(|value| !value.is_empty());
-    let file_name = match variant_label.as_deref() {
-        Some(label) => format!("qc_report_v1_{track_id}_{label}.json"),
-        None => format!("qc_report_v1_{track_id}.json"),
-    };
-    let path = state
-        .paths
-        .derived_item_dir(&item_id)
-        .join("qc")
-        .join(file_name);
-    if !path.exists() {
-        return Ok(None);
-    }
-
-    let bytes = std::fs::read(&path).map_err(|e| e.to_string())?;
-    let parsed: serde_json::Value = serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
-    Ok(Some(parsed))
+    jobs::load_qc_report_v1(&state.paths, &item_id, &track_id, variant_label.as_deref())
+        .map_err(|e| e.to_string())
 }
 
 fn normalize_variant_label(raw: Option<&str>) -> Option<String> {
@@ -3163,6 +3782,49 @@ fn load_artifact_voice_clone_meta(
     serde_json::from_slice::<ArtifactVoiceCloneMeta>(&bytes).ok()
 }
 
+const MAX_ARTIFACT_TTS_SEGMENTS_PER_BACKEND: usize = 500;
+
+#[derive(Debug, Clone, serde::Deserialize)]
+struct ArtifactTtsSegmentsManifest {
+    #[serde(default)]
+    segments: Vec<ArtifactTtsSegment>,
+}
+
+#[derive(Debug, Clone, serde::Deserialize)]
+struct ArtifactTtsSegment {
+    index: u32,
+    #[serde(default)]
+    audio_path: Option<String>,
+}
+
+fn load_artifact_tts_segments(path: &std::path::Path) -> Vec<ArtifactTtsSegment> {
+    let Ok(bytes) = std::fs::read(path) else {
+        return Vec::new();
+    };
+    serde_json::from_slice::<ArtifactTtsSegmentsManifest>(&bytes)
+        .map(|manifest| manifest.segments)
+        .unwrap_or_default()
+}
+
+fn load_artifact_audio_duration_ms(
+    paths: &AppPaths,
+    kind: &ArtifactKind,
+    path: &std::path::Path,
+) -> Option<i64> {
+    let is_audio = matches!(
+        kind,
+        ArtifactKind::SeparationStem
+            | ArtifactKind::CleanupAudio
+            | ArtifactKind::DubMix
+            | ArtifactKind::DubSpeechStem
+            | ArtifactKind::TtsSegmentAudio
+    );
+    if !is_audio || !path.is_file() {
+        return None;
+    }
+    ffmpeg::probe(paths, path).ok()?.duration_ms
+}
+
 fn qc_report_identity(file_name: &str) -> (Option<String>, Option<String>) {
     let Some(stem) = file_name.strip_suffix(".json") else {
         return (None, None);
@@ -3178,7 +3840,7 @@ fn qc_report_identity(file_name: &str) -> (Option<String>, Option<String>) {
 
 #[tauri::command]
 #[allow(non_snake_case)]
-fn item_artifacts_list_v1(
+async fn item_artifacts_list_v1(
     state: State<'_, AppState>,
     item_id: Option<String>,
     itemId: Option<String>,
@@ -3189,7 +3851,14 @@ fn item_artifacts_list_v1(
         .filter(|v| !v.is_empty())
         .ok_or_else(|| "missing required key itemId".to_string())?;
 
-    let item_dir = state.paths.derived_item_dir(&item_id);
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || Ok(collect_item_artifacts_v1(&paths, &item_id)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+fn collect_item_artifacts_v1(paths: &AppPaths, item_id: &str) -> Vec<ArtifactInfo> {
+    let item_dir = paths.derived_item_dir(item_id);
     let mut out: Vec<ArtifactInfo> = Vec::new();
 
     let mut push = |id: &str,
@@ -3204,6 +3873,7 @@ fn item_artifacts_list_v1(
                     rerun_kind: Option<ArtifactRerunKind>,
                     path: std::path::PathBuf| {
         let voice_clone_meta = load_artifact_voice_clone_meta(&kind, &path);
+        let duration_ms = load_artifact_audio_duration_ms(paths, &kind, &path);
         out.push(ArtifactInfo {
             id: id.to_string(),
             title: title.to_string(),
@@ -3231,6 +3901,7 @@ fn item_artifacts_list_v1(
             voice_clone_standard_tts_segments: voice_clone_meta
                 .as_ref()
                 .and_then(|value| value.voice_clone_standard_tts_segments),
+            duration_ms,
             rerun_kind,
         });
     };
@@ -3300,6 +3971,53 @@ fn item_artifacts_list_v1(
             .join("demucs_two_stems_v1")
             .join("background.wav"),
     );
+    for (stem, title) in [
+        ("drums", "Drums (Demucs 4-stem)"),
+        ("bass", "Bass (Demucs 4-stem)"),
+        ("other", "Other (Demucs 4-stem)"),
+        ("vocals", "Vocals (Demucs 4-stem)"),
+    ] {
+        push(
+            &format!("sep_demucs_4stems_{stem}"),
+            title,
+            "Separation",
+            ArtifactKind::SeparationStem,
+            Some("separate_audio_demucs_4stems_v1"),
+            None,
+            None,
+            None,
+            None,
+            Some(ArtifactRerunKind::SeparateDemucs4Stems),
+            item_dir
+                .join("separation")
+                .join("demucs_4stems_v1")
+                .join(format!("{stem}.wav")),
+        );
+    }
+    for (stem, title) in [
+        ("vocals", "Vocals (Spleeter 5-stem)"),
+        ("drums", "Drums (Spleeter 5-stem)"),
+        ("bass", "Bass (Spleeter 5-stem)"),
+        ("piano", "Piano (Spleeter 5-stem)"),
+        ("other", "Other (Spleeter 5-stem)"),
+    ] {
+        push(
+            &format!("sep_spleeter_5stems_{stem}"),
+            title,
+            "Separation",
+            ArtifactKind::SeparationStem,
+            Some("separate_audio_spleeter"),
+            None,
+            None,
+            None,
+            None,
+            Some(ArtifactRerunKind::SeparateSpleeter5Stems),
+            item_dir
+                .join("separation")
+                .join("spleeter_5stems")
+                .join(format!("{stem}.wav")),
+        );
+    }
 
     // Vocals cleanup
     push(
@@ -3365,6 +4083,49 @@ fn item_artifacts_list_v1(
             .join("dub_voice_preserving_v1")
             .join("manifest.json"),
     );
+
+    // TTS segments
+    for (backend_id, backend_dir, job_type) in [
+        ("pyttsx3_v1", "pyttsx3_v1", "tts_preview_pyttsx3_v1"),
+        (
+            "tts_neural_local_v1",
+            "tts_neural_local_v1",
+            "tts_neural_local_v1",
+        ),
+        (
+            "openvoice_v2",
+            "dub_voice_preserving_v1",
+            "dub_voice_preserving_v1",
+        ),
+    ] {
+        let manifest_path = item_dir
+            .join("tts_preview")
+            .join(backend_dir)
+            .join("manifest.json");
+        for segment in load_artifact_tts_segments(&manifest_path)
+            .into_iter()
+            .take(MAX_ARTIFACT_TTS_SEGMENTS_PER_BACKEND)
+        {
+            let Some(audio_path) = segment.audio_path.filter(|value| !value.trim().is_empty())
+            else {
+                continue;
+            };
+            push(
+                &format!("tts_seg_{backend_id}_{:04}", segment.index),
+                &format!("TTS segment {:04} ({backend_id})", segment.index),
+                "TTS Segments",
+                ArtifactKind::TtsSegmentAudio,
+                Some(job_type),
+                None,
+                None,
+                None,
+                Some(backend_id),
+                None,
+                std::path::PathBuf::from(audio_path),
+            );
+        }
+    }
+
     let voice_preserving_variants_dir = item_dir
         .join("tts_preview")
         .join("dub_voice_preserving_v1")
@@ -3846,7 +4607,7 @@ fn item_artifacts_list_v1(
             .then_with(|| a.id.cmp(&b.id))
     });
 
-    Ok(out)
+    out
 }
 
 #[tauri::command]
@@ -3976,14 +4737,85 @@ async fn item_export_source_media(
 #[tauri::command]
 async fn diagnostics_storage_breakdown(
     state: State<'_, AppState>,
+    top_n: Option<usize>,
 ) -> Result<diagnostics::StorageBreakdown, String> {
     let paths = state.paths.clone();
-    tauri::async_runtime::spawn_blocking(move || diagnostics::storage_breakdown(&paths))
+    let top_n = top_n.unwrap_or(diagnostics::DEFAULT_STORAGE_BREAKDOWN_TOP_N);
+    tauri::async_runtime::spawn_blocking(move || diagnostics::storage_breakdown(&paths, top_n))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn diagnostics_db_integrity_check(
+    state: State<'_, AppState>,
+) -> Result<db::DbIntegrityResult, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || db::integrity_check(&paths, None))
+        .await
+        .map_err(|e| e.to_string())
+}
+
+/// Defaults to a dry run; callers delete only after showing the preview.
+#[tauri::command]
+#[allow(non_snake_case)]
+async fn diagnostics_clean_orphans(
+    state: State<'_, AppState>,
+    dry_run: Option<bool>,
+    dryRun: Option<bool>,
+) -> Result<diagnostics::OrphanCleanupResult, String> {
+    let paths = state.paths.clone();
+    let dry_run = dry_run.or(dryRun).unwrap_or(true);
+    tauri::async_runtime::spawn_blocking(move || diagnostics::clean_orphans(&paths, dry_run))
         .await
         .map_err(|e| e.to_string())?
         .map_err(|e| e.to_string())
 }
 
+/// Quick integrity check before the schema is touched. With `--recover-corrupt-db` a corrupt
+/// database is moved aside (never deleted) so a fresh one is created.
+fn startup_db_integrity(paths: &AppPaths, recover: bool) -> String {
+    let result = db::integrity_check(paths, Some(db::STARTUP_INTEGRITY_MAX_ISSUES));
+    if result.ok {
+        return "ok".to_string();
+    }
+    eprintln!(
+        "database integrity check failed: {}",
+        result.issues.join("; ")
+    );
+    append_diagnostics_trace_row_best_effort(
+        paths,
+        "db_integrity_check_failed",
+        serde_json::json!({
+            "issues": &result.issues,
+            "recover": recover,
+        }),
+        "error",
+        Some(launch_correlation_id()),
+    );
+    if !recover {
+        return format!(
+            "corrupt ({} issue(s)); restart with --recover-corrupt-db to move it aside",
+            result.issues.len()
+        );
+    }
+    match db::quarantine_corrupt_db(paths) {
+        Ok(moved) => format!("recovered: corrupt database moved to {}", moved.display()),
+        Err(error) => format!("corrupt; recovery failed: {error}"),
+    }
+}
+
+#[tauri::command]
+async fn diagnostics_health_check(
+    state: State<'_, AppState>,
+) -> Result<diagnostics::HealthCheckReport, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || diagnostics::health_check(&paths))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn diagnostics_clear_cache(
     state: State<'_, AppState>,
@@ -3995,6 +4827,50 @@ async fn diagnostics_clear_cache(
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn diagnostics_applied_bundles(
+    state: State<'_, AppState>,
+) -> Result<Vec<OfflineBundleAppliedEntry>, String> {
+    Ok(read_offline_bundle_applied_entries(&state.paths))
+}
+
+#[tauri::command]
+async fn diagnostics_clear_cache_selective(
+    state: State<'_, AppState>,
+    options: diagnostics::CacheClearOptions,
+) -> Result<diagnostics::CacheClearSummary, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        diagnostics::clear_cache_selective(&paths, &options)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn storage_migrate(
+    app: tauri::AppHandle,
+    state: State<'_, AppState>,
+    new_base_dir: String,
+    remove_old_dir: bool,
+) -> Result<migration::StorageMigrationResult, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        migration::migrate_storage(
+            &paths,
+            std::path::Path::new(new_base_dir.trim()),
+            remove_old_dir,
+            |progress| {
+                let _ = app.emit("storage-migrate-progress", progress);
+            },
+        )
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn diagnostics_thumbnail_cache_status(
     state: State<'_, AppState>,
@@ -4022,6 +4898,7 @@ async fn diagnostics_export_bundle(
     app: tauri::AppHandle,
     state: State<'_, AppState>,
     out_path: String,
+    max_job_logs: Option<usize>,
 ) -> Result<diagnostics::DiagnosticsBundleResult, String> {
     let out_path = out_path.trim().to_string();
     if out_path.is_empty() {
@@ -4039,6 +4916,7 @@ async fn diagnostics_export_bundle(
             std::path::PathBuf::from(out_path),
             &app_name,
             &app_version,
+            max_job_logs.unwrap_or(diagnostics::DEFAULT_BUNDLE_MAX_JOB_LOGS),
         )
     })
     .await
@@ -4047,23 +4925,52 @@ async fn diagnostics_export_bundle(
 }
 
 #[tauri::command]
+#[allow(non_snake_case)]
 async fn diagnostics_generate_licensing_report(
     state: State<'_, AppState>,
+    include_python: Option<bool>,
+    includePython: Option<bool>,
 ) -> Result<diagnostics::LicensingReportResult, String> {
     let paths = state.paths.clone();
-    tauri::async_runtime::spawn_blocking(move || diagnostics::generate_licensing_report(&paths))
-        .await
-        .map_err(|e| e.to_string())?
-        .map_err(|e| e.to_string())
+    let include_python = include_python.or(includePython).unwrap_or(true);
+    tauri::async_runtime::spawn_blocking(move || {
+        diagnostics::generate_licensing_report(&paths, include_python)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn jobs_log_retention_policy(state: State<'_, AppState>) -> jobs::JobLogRetentionPolicy {
+    jobs::load_job_log_retention_policy(&state.paths)
+}
+
+#[tauri::command]
+fn jobs_log_retention_policy_set(
+    state: State<'_, AppState>,
+    policy: jobs::JobLogRetentionPolicy,
+) -> Result<jobs::JobLogRetentionPolicy, String> {
+    jobs::set_job_log_retention_policy(&state.paths, policy).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn config_job_log_retention_get(
+    state: State<'_, AppState>,
+) -> Result<Vec<jobs::JobTypeRetentionOverride>, String> {
+    jobs::get_job_log_retention_overrides(&state.paths).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn jobs_log_retention_policy() -> jobs::JobLogRetentionPolicy {
-    jobs::job_log_retention_policy()
+fn config_job_log_retention_set(
+    state: State<'_, AppState>,
+    overrides: Vec<jobs::JobTypeRetentionOverride>,
+) -> Result<Vec<jobs::JobTypeRetentionOverride>, String> {
+    jobs::set_job_log_retention_overrides(&state.paths, overrides).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-async fn jobs_prune_logs(state: State<'_, AppState>) -> Result<(), String> {
+async fn jobs_prune_logs(state: State<'_, AppState>) -> Result<jobs::JobLogPruneSummary, String> {
     let paths = state.paths.clone();
     tauri::async_runtime::spawn_blocking(move || jobs::prune_job_logs_now(&paths))
         .await
@@ -4071,6 +4978,18 @@ async fn jobs_prune_logs(state: State<'_, AppState>) -> Result<(), String> {
         .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+async fn jobs_log_search(
+    state: State<'_, AppState>,
+    query: jobs::JobLogQuery,
+) -> Result<Vec<serde_json::Value>, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || jobs::search_job_logs(&paths, &query))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn window_minimize(window: tauri::Window) -> Result<(), String> {
     window.minimize().map_err(|e| e.to_string())
@@ -4091,11 +5010,47 @@ fn window_close(window: tauri::Window) -> Result<(), String> {
     window.close().map_err(|e| e.to_string())
 }
 
+#[cfg(not(mobile))]
 #[tauri::command]
 fn window_start_drag(window: tauri::Window) -> Result<(), String> {
     window.start_dragging().map_err(|e| e.to_string())
 }
 
+#[cfg(mobile)]
+#[derive(Debug, Clone, serde::Serialize)]
+struct WindowDragStartEvent {
+    touch_x: f64,
+    touch_y: f64,
+    window_x: i32,
+    window_y: i32,
+}
+
+/// Touch platforms have no native window drag; the frontend follows the touch from the
+/// `window_drag_start` anchor and moves the window with `window_set_position`.
+#[cfg(mobile)]
+#[tauri::command]
+fn window_start_drag(window: tauri::Window, x: f64, y: f64) -> Result<(), String> {
+    let position = window.outer_position().map_err(|e| e.to_string())?;
+    window
+        .emit(
+            "window_drag_start",
+            WindowDragStartEvent {
+                touch_x: x,
+                touch_y: y,
+                window_x: position.x,
+                window_y: position.y,
+            },
+        )
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn window_set_position(window: tauri::Window, x: i32, y: i32) -> Result<(), String> {
+    window
+        .set_position(tauri::PhysicalPosition::new(x, y))
+        .map_err(|e| e.to_string())
+}
+
 fn parse_window_resize_direction(direction: &str) -> Result<TauriResizeDirection, String> {
     match direction {
         "East" => Ok(TauriResizeDirection::East),
@@ -4144,6 +5099,12 @@ fn startup_status(state: State<'_, AppState>) -> Result<StartupStatus, String> {
     current_startup_status(&state)
 }
 
+/// The `app_ready` payload if it has already been emitted, for listeners that attach late.
+#[tauri::command]
+fn app_ready_status() -> Option<AppReadyEvent> {
+    APP_READY.get().cloned()
+}
+
 #[tauri::command]
 async fn diagnostics_app_state_snapshot(
     app: tauri::AppHandle,
@@ -4154,9 +5115,10 @@ async fn diagnostics_app_state_snapshot(
     let app_version = package.version.to_string();
     let paths = state.paths.clone();
     let startup = current_startup_status(&state)?;
+    let db_integrity = state.db_integrity.clone();
 
     tauri::async_runtime::spawn_blocking(move || {
-        build_diagnostics_app_state_snapshot(&paths, app_name, app_version, startup)
+        build_diagnostics_app_state_snapshot(&paths, app_name, app_version, startup, db_integrity)
     })
     .await
     .map_err(|e| e.to_string())?
@@ -4178,10 +5140,16 @@ async fn diagnostics_export_app_state_snapshot(
     let app_version = package.version.to_string();
     let paths = state.paths.clone();
     let startup = current_startup_status(&state)?;
+    let db_integrity = state.db_integrity.clone();
 
     tauri::async_runtime::spawn_blocking(move || {
-        let snapshot =
-            build_diagnostics_app_state_snapshot(&paths, app_name, app_version, startup)?;
+        let snapshot = build_diagnostics_app_state_snapshot(
+            &paths,
+            app_name,
+            app_version,
+            startup,
+            db_integrity,
+        )?;
         write_diagnostics_app_state_snapshot_exports(&snapshot, &std::path::PathBuf::from(out_path))
     })
     .await
@@ -4204,10 +5172,22 @@ fn downloads_dir_status(state: State<'_, AppState>) -> Result<DownloadDirStatus,
 }
 
 #[tauri::command]
-fn downloads_dir_set(
-    state: State<'_, AppState>,
+fn downloads_dir_free_gb(state: State<'_, AppState>) -> Result<f64, String> {
+    let dir = state
+        .paths
+        .effective_download_dir()
+        .map_err(|e| e.to_string())?;
+    diagnostics::disk_free_gb(&dir).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+fn downloads_dir_set(
+    state: State<'_, AppState>,
     path: String,
     create_if_missing: bool,
+    min_free_gb: Option<f32>,
+    minFreeGb: Option<f32>,
 ) -> Result<DownloadDirStatus, String> {
     let mut dir = std::path::PathBuf::from(path.trim());
     if dir.as_os_str().is_empty() {
@@ -4229,6 +5209,16 @@ fn downloads_dir_set(
         return Err(format!("path is not a folder: {}", dir.to_string_lossy()));
     }
 
+    let min_free_gb = min_free_gb
+        .or(minFreeGb)
+        .unwrap_or(DEFAULT_DOWNLOAD_DIR_MIN_FREE_GB);
+    let free = diagnostics::disk_free_gb(&dir).map_err(|e| e.to_string())?;
+    if free < f64::from(min_free_gb) {
+        return Err(format!(
+            "insufficient disk space: {free:.1} GB free, {min_free_gb} GB required"
+        ));
+    }
+
     let normalized = dir.canonicalize().unwrap_or(dir);
     ensure_media_output_layout(&normalized)?;
     state
@@ -4420,7 +5410,40 @@ async fn diagnostics_trace_clear(
         let dir = paths
             .effective_diagnostics_trace_dir()
             .map_err(|e| e.to_string())?;
-        clear_dir_entries_with_bytes(&dir)
+        clear_dir_entries_with_bytes(&dir, &ClearOptions::default())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn diagnostics_trace_clear_old(
+    state: State<'_, AppState>,
+    max_age_days: u64,
+) -> Result<DiagnosticsTraceClearSummary, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let dir = paths
+            .effective_diagnostics_trace_dir()
+            .map_err(|e| e.to_string())?;
+        let options = ClearOptions {
+            max_age_secs: Some(max_age_days.saturating_mul(24 * 60 * 60)),
+            ..ClearOptions::default()
+        };
+        clear_dir_entries_with_bytes(&dir, &options)
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn diagnostics_trace_size(state: State<'_, AppState>) -> Result<u64, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let dir = paths
+            .effective_diagnostics_trace_dir()
+            .map_err(|e| e.to_string())?;
+        Ok(path_size_bytes_best_effort(&dir))
     })
     .await
     .map_err(|e| e.to_string())?
@@ -4432,25 +5455,88 @@ async fn diagnostics_trace_write_event(
     event: String,
     details: Option<serde_json::Value>,
     level: Option<String>,
+    tags: Option<std::collections::HashMap<String, String>>,
+    correlation_id: Option<String>,
+    source: Option<String>,
 ) -> Result<String, String> {
     let event = event.trim().to_string();
     if event.is_empty() {
         return Err("event is empty".to_string());
     }
+    let source = source
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "frontend".to_string());
+    let (expired, admission) = {
+        let mut limiter = state
+            .trace_rate_limiter
+            .lock()
+            .map_err(|_| "trace rate limiter lock poisoned".to_string())?;
+        let now = Instant::now();
+        let expired = limiter.take_expired(now);
+        (expired, limiter.admit(&source, &event, now))
+    };
+    let correlation_id = correlation_id
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .or_else(|| {
+            state
+                .trace_correlation_id
+                .lock()
+                .ok()
+                .and_then(|guard| guard.clone())
+        });
+
+    let mut tags = tags.unwrap_or_default();
+    tags.entry("source".to_string())
+        .or_insert_with(|| source.clone());
 
     let paths = state.paths.clone();
     tauri::async_runtime::spawn_blocking(move || {
+        append_suppressed_trace_rows(&paths, &expired, correlation_id.clone());
+        if admission == TraceAdmission::Suppressed {
+            return diagnostics_trace_file_path(&paths)
+                .map(|path| path.to_string_lossy().to_string());
+        }
         append_diagnostics_trace_row(
             &paths,
             event,
             details.unwrap_or(serde_json::Value::Null),
             level.unwrap_or_else(|| "info".to_string()),
+            Some(tags),
+            correlation_id,
         )
     })
     .await
     .map_err(|e| e.to_string())?
 }
 
+#[tauri::command]
+fn diagnostics_trace_rate_limit_stats(
+    state: State<'_, AppState>,
+) -> Result<std::collections::HashMap<String, u32>, String> {
+    let guard = state
+        .trace_rate_limiter
+        .lock()
+        .map_err(|_| "trace rate limiter lock poisoned".to_string())?;
+    Ok(guard.stats())
+}
+
+#[tauri::command]
+fn diagnostics_trace_set_correlation_id(
+    state: State<'_, AppState>,
+    correlation_id: Option<String>,
+) -> Result<(), String> {
+    let mut guard = state
+        .trace_correlation_id
+        .lock()
+        .map_err(|_| "trace correlation lock poisoned".to_string())?;
+    *guard = correlation_id
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    Ok(())
+}
+
 #[tauri::command]
 async fn diagnostics_trace_recent(
     state: State<'_, AppState>,
@@ -4480,6 +5566,145 @@ fn config_batch_on_import_set(
     Ok(rules)
 }
 
+#[tauri::command]
+fn config_batch_on_import_warnings(
+    state: State<'_, AppState>,
+    rules: config::BatchOnImportRules,
+) -> Vec<String> {
+    config::batch_on_import_rules_warnings(&state.paths, &rules)
+}
+
+#[tauri::command]
+fn config_custom_tool_paths_get(
+    state: State<'_, AppState>,
+) -> Result<config::CustomToolPaths, String> {
+    config::load_custom_tool_paths(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn config_custom_tool_paths_set(
+    state: State<'_, AppState>,
+    config_value: config::CustomToolPaths,
+) -> Result<config::CustomToolPaths, String> {
+    config::save_custom_tool_paths(&state.paths, &config_value).map_err(|e| e.to_string())?;
+    config::load_custom_tool_paths(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn config_proxy_get(state: State<'_, AppState>) -> Result<config::ProxyConfig, String> {
+    config::load_proxy_config(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn config_proxy_set(
+    state: State<'_, AppState>,
+    config_value: config::ProxyConfig,
+) -> Result<config::ProxyConfig, String> {
+    config::save_proxy_config(&state.paths, &config_value).map_err(|e| e.to_string())?;
+    config::load_proxy_config(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn config_tts_defaults_get(
+    state: State<'_, AppState>,
+) -> Result<config::TtsDefaultsConfig, String> {
+    config::load_tts_defaults_config(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn config_tts_defaults_set(
+    state: State<'_, AppState>,
+    config_value: config::TtsDefaultsConfig,
+) -> Result<config::TtsDefaultsConfig, String> {
+    config::save_tts_defaults_config(&state.paths, &config_value).map_err(|e| e.to_string())?;
+    config::load_tts_defaults_config(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn config_download_disk_space_get(
+    state: State<'_, AppState>,
+) -> Result<config::DownloadDiskSpaceConfig, String> {
+    config::load_download_disk_space_config(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn config_download_disk_space_set(
+    state: State<'_, AppState>,
+    config_value: config::DownloadDiskSpaceConfig,
+) -> Result<config::DownloadDiskSpaceConfig, String> {
+    config::save_download_disk_space_config(&state.paths, &config_value)
+        .map_err(|e| e.to_string())?;
+    config::load_download_disk_space_config(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn config_pack_integrity_get(
+    state: State<'_, AppState>,
+) -> Result<config::PackIntegrityConfig, String> {
+    config::load_pack_integrity_config(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn config_pack_integrity_set(
+    state: State<'_, AppState>,
+    config_value: config::PackIntegrityConfig,
+) -> Result<config::PackIntegrityConfig, String> {
+    config::save_pack_integrity_config(&state.paths, &config_value).map_err(|e| e.to_string())?;
+    config::load_pack_integrity_config(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn config_diagnostics_trace_config_get(
+    state: State<'_, AppState>,
+) -> Result<config::DiagnosticsTraceWriteConfig, String> {
+    config::load_diagnostics_trace_config(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn config_diagnostics_trace_config_set(
+    state: State<'_, AppState>,
+    config_value: config::DiagnosticsTraceWriteConfig,
+) -> Result<config::DiagnosticsTraceWriteConfig, String> {
+    config::save_diagnostics_trace_config(&state.paths, &config_value)
+        .map_err(|e| e.to_string())?;
+    config::load_diagnostics_trace_config(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn config_export_json(
+    state: State<'_, AppState>,
+    out_path: String,
+    include_secrets: Option<bool>,
+) -> Result<config::ConfigExportResult, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let out_path = normalize_shell_path(out_path, "config export path")?;
+        config::export_config_json(&paths, &out_path, include_secrets.unwrap_or(false))
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn config_import_json(
+    state: State<'_, AppState>,
+    in_path: String,
+) -> Result<config::ConfigImportSummary, String> {
+    let paths = state.paths.clone();
+    let summary = tauri::async_runtime::spawn_blocking(move || {
+        let in_path = normalize_existing_shell_path(in_path, "config import file")?;
+        config::import_config_json(&paths, &in_path).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())??;
+    // The import may have changed the job concurrency limit.
+    if let Ok(settings) = jobs::get_runtime_settings(&state.paths) {
+        state.runner.set_max_concurrency(settings.max_concurrency);
+    }
+    Ok(summary)
+}
+
 #[tauri::command]
 fn config_youtube_auth_get(
     state: State<'_, AppState>,
@@ -4497,21 +5722,31 @@ fn config_youtube_auth_set(
 }
 
 #[tauri::command]
-fn config_diarization_optional_status(
+async fn config_diarization_optional_status(
     state: State<'_, AppState>,
 ) -> Result<config::OptionalDiarizationBackendStatus, String> {
-    config::load_optional_diarization_backend_status(&state.paths).map_err(|e| e.to_string())
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        config::load_optional_diarization_backend_status_checked(&paths).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 #[tauri::command]
-fn config_diarization_optional_set(
+async fn config_diarization_optional_set(
     state: State<'_, AppState>,
     config_value: config::OptionalDiarizationBackendConfig,
     token: Option<String>,
 ) -> Result<config::OptionalDiarizationBackendStatus, String> {
-    config::save_optional_diarization_backend_config(&state.paths, &config_value, token.as_deref())
-        .map_err(|e| e.to_string())?;
-    config::load_optional_diarization_backend_status(&state.paths).map_err(|e| e.to_string())
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        config::save_optional_diarization_backend_config(&paths, &config_value, token.as_deref())
+            .map_err(|e| e.to_string())?;
+        config::load_optional_diarization_backend_status(&paths).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
 }
 
 #[tauri::command]
@@ -4535,6 +5770,32 @@ async fn models_inventory(
     .map_err(|e| e.to_string())?
 }
 
+#[tauri::command]
+async fn models_inventory_with_sizes(
+    state: State<'_, AppState>,
+) -> Result<voxvulgi_engine::models::ModelInventoryWithSizes, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let store = ModelStore::new(paths);
+        store.inventory_with_sizes().map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn models_delete(
+    state: State<'_, AppState>,
+    model_id: String,
+) -> Result<voxvulgi_engine::models::DeleteModelResult, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        voxvulgi_engine::models::delete_model(&paths, &model_id).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 #[tauri::command]
 fn models_install_demo(state: State<'_, AppState>) -> Result<(), String> {
     let store = ModelStore::new(state.paths.clone());
@@ -4549,6 +5810,14 @@ fn models_install(state: State<'_, AppState>, model_id: String) -> Result<(), St
     store.install_model(&model_id).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn models_install_async(
+    state: State<'_, AppState>,
+    model_id: String,
+) -> Result<jobs::JobRow, String> {
+    jobs::enqueue_install_model_v1(&state.paths, &model_id).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn tools_ffmpeg_status(
     state: State<'_, AppState>,
@@ -4559,6 +5828,47 @@ async fn tools_ffmpeg_status(
         .map_err(|e| e.to_string())?
 }
 
+#[tauri::command]
+async fn tools_all_status(state: State<'_, AppState>) -> Result<tools::AllToolsStatus, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || Ok(tools::all_tools_status(&paths)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn tools_ffmpeg_probe_version(state: State<'_, AppState>) -> Result<String, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        tools::probe_ffmpeg_version(&paths).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn ffmpeg_probe_v2(
+    state: State<'_, AppState>,
+    path: String,
+) -> Result<ffmpeg::MediaProbe, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        ffmpeg::probe(&paths, std::path::Path::new(&path)).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn tools_ytdlp_probe_version(state: State<'_, AppState>) -> Result<String, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        tools::probe_ytdlp_version(&paths).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 #[tauri::command]
 fn tools_ffmpeg_install(state: State<'_, AppState>) -> Result<tools::FfmpegToolsStatus, String> {
     tools::install_ffmpeg_tools(&state.paths).map_err(|e| e.to_string())
@@ -4577,6 +5887,31 @@ fn tools_ytdlp_install(state: State<'_, AppState>) -> Result<tools::YtDlpToolsSt
     tools::install_ytdlp_tools(&state.paths).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+async fn tools_ytdlp_check_for_update(
+    state: State<'_, AppState>,
+    force_refresh: Option<bool>,
+    forceRefresh: Option<bool>,
+) -> Result<tools::YtDlpUpdateInfo, String> {
+    let paths = state.paths.clone();
+    let force_refresh = force_refresh.or(forceRefresh).unwrap_or(false);
+    tauri::async_runtime::spawn_blocking(move || {
+        tools::check_ytdlp_update(&paths, force_refresh).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn tools_ytdlp_update(state: State<'_, AppState>) -> Result<tools::YtDlpToolsStatus, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        tools::update_ytdlp_tools(&paths).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 #[tauri::command]
 async fn tools_js_runtime_status(
     state: State<'_, AppState>,
@@ -4605,10 +5940,35 @@ async fn tools_python_status(
 }
 
 #[tauri::command]
+async fn tools_python_venv_check_packages(
+    state: State<'_, AppState>,
+    packages: Vec<String>,
+) -> Result<std::collections::HashMap<String, Option<String>>, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        let refs = packages.iter().map(String::as_str).collect::<Vec<_>>();
+        tools::check_python_venv_packages(&paths, &refs).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
 fn tools_python_install(
     state: State<'_, AppState>,
+    use_system_python: Option<bool>,
+    useSystemPython: Option<bool>,
 ) -> Result<tools::PythonToolchainStatus, String> {
-    tools::install_python_toolchain(&state.paths).map_err(|e| e.to_string())
+    let use_system_python = use_system_python.or(useSystemPython).unwrap_or(false);
+    tools::install_python_toolchain(&state.paths, use_system_python).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn tools_python_detect_system() -> Result<Option<tools::SystemPythonInfo>, String> {
+    tauri::async_runtime::spawn_blocking(tools::detect_system_python)
+        .await
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -4633,6 +5993,18 @@ fn tools_phase2_packs_install_plan() -> Vec<tools::Phase2PackPlanItem> {
     tools::phase2_packs_install_plan()
 }
 
+#[tauri::command]
+async fn tools_phase2_packs_rollback(
+    state: State<'_, AppState>,
+) -> Result<tools::Phase2RollbackSummary, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        tools::rollback_phase2_packs(&paths).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 #[tauri::command]
 async fn tools_phase2_packs_install_latest_state(
     state: State<'_, AppState>,
@@ -4686,6 +6058,14 @@ fn tools_pack_integrity_manifest_generate(
     tools::generate_pack_integrity_manifest(&state.paths).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn tools_pack_integrity_verify(
+    state: State<'_, AppState>,
+    pack_id: String,
+) -> Result<tools::PackIntegrityVerifyResult, String> {
+    tools::verify_pack_integrity(&state.paths, &pack_id).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn tools_performance_tier_status(
     state: State<'_, AppState>,
@@ -4726,6 +6106,24 @@ fn tools_demucs_install(state: State<'_, AppState>) -> Result<tools::DemucsPackS
     tools::install_demucs_pack(&state.paths).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+async fn tools_cleanup_status(
+    state: State<'_, AppState>,
+) -> Result<tools::CleanupPackStatus, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || Ok(tools::cleanup_pack_status(&paths)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn tools_sync_status(state: State<'_, AppState>) -> Result<tools::SyncPackStatus, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || Ok(tools::sync_pack_status(&paths)))
+        .await
+        .map_err(|e| e.to_string())?
+}
+
 #[tauri::command]
 async fn tools_diarization_status(
     state: State<'_, AppState>,
@@ -4789,6 +6187,16 @@ async fn tools_tts_voice_preserving_local_v1_status(
     .map_err(|e| e.to_string())?
 }
 
+#[tauri::command]
+async fn tools_scan_user_models(
+    state: State<'_, AppState>,
+) -> Result<tools::UserModelsScanResult, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || tools::scan_user_models(&paths))
+        .await
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn tools_tts_voice_preserving_local_v1_install(
     state: State<'_, AppState>,
@@ -5297,12 +6705,24 @@ async fn voice_backend_adapter_probe(
 }
 
 #[tauri::command]
+#[allow(non_snake_case)]
 async fn tools_tts_preview_pyttsx3_voices(
     state: State<'_, AppState>,
+    language_filter: Option<String>,
+    languageFilter: Option<String>,
+    gender_filter: Option<String>,
+    genderFilter: Option<String>,
+    include_sample: Option<bool>,
+    includeSample: Option<bool>,
 ) -> Result<Vec<tools::Pyttsx3Voice>, String> {
     let paths = state.paths.clone();
+    let query = tools::Pyttsx3VoiceQuery {
+        language_filter: language_filter.or(languageFilter),
+        gender_filter: gender_filter.or(genderFilter),
+        include_sample: include_sample.or(includeSample).unwrap_or(false),
+    };
     tauri::async_runtime::spawn_blocking(move || {
-        tools::tts_preview_pyttsx3_list_voices(&paths).map_err(|e| e.to_string())
+        tools::tts_preview_pyttsx3_list_voices(&paths, &query).map_err(|e| e.to_string())
     })
     .await
     .map_err(|e| e.to_string())?
@@ -5350,6 +6770,44 @@ fn speakers_upsert(
     .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn speakers_delete(
+    state: State<'_, AppState>,
+    item_id: String,
+    speaker_key: String,
+) -> Result<(), String> {
+    speakers::delete_item_speaker_setting(&state.paths, &item_id, &speaker_key)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn speakers_list_all(
+    state: State<'_, AppState>,
+) -> Result<Vec<speakers::GlobalSpeakerSummary>, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        speakers::list_all_speaker_keys(&paths).map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn speakers_bulk_set_voice(
+    state: State<'_, AppState>,
+    speaker_key: String,
+    voice_id: Option<String>,
+    item_ids: Vec<String>,
+) -> Result<usize, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || {
+        speakers::bulk_set_speaker_voice(&paths, &speaker_key, voice_id, &item_ids)
+            .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
 #[tauri::command]
 async fn voice_templates_list(
     state: State<'_, AppState>,
@@ -5875,8 +7333,98 @@ fn library_list(
     state: State<'_, AppState>,
     limit: usize,
     offset: usize,
-) -> Result<Vec<library::LibraryItem>, String> {
-    library::list_items(&state.paths, limit, offset).map_err(|e| e.to_string())
+) -> Result<library::LibraryPage, String> {
+    library::list_items_page(&state.paths, limit, offset).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn library_list_by_platform(
+    state: State<'_, AppState>,
+    platform: String,
+    limit: usize,
+    offset: usize,
+) -> Result<library::LibraryPage, String> {
+    library::list_items_by_platform(&state.paths, &platform, limit, offset)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn library_count(state: State<'_, AppState>) -> Result<usize, String> {
+    library::count_items(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn library_count_by_tag(state: State<'_, AppState>, tag: String) -> Result<usize, String> {
+    library::count_items_by_tag(&state.paths, &tag).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn library_list_in_collection(
+    state: State<'_, AppState>,
+    collection_id: String,
+    limit: usize,
+    offset: usize,
+) -> Result<library::LibraryPage, String> {
+    library::list_items_in_collection(&state.paths, &collection_id, limit, offset)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+fn collections_create(
+    state: State<'_, AppState>,
+    name: String,
+    parent_id: Option<String>,
+    parentId: Option<String>,
+) -> Result<collections::CollectionRow, String> {
+    let parent_id = parent_id.or(parentId);
+    collections::create_collection(&state.paths, &name, parent_id.as_deref())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn collections_delete(state: State<'_, AppState>, id: String) -> Result<(), String> {
+    collections::delete_collection(&state.paths, &id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn collections_rename(
+    state: State<'_, AppState>,
+    id: String,
+    name: String,
+) -> Result<collections::CollectionRow, String> {
+    collections::rename_collection(&state.paths, &id, &name).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+fn collections_list(
+    state: State<'_, AppState>,
+    parent_id: Option<String>,
+    parentId: Option<String>,
+) -> Result<Vec<collections::CollectionRow>, String> {
+    let parent_id = parent_id.or(parentId);
+    collections::list_collections(&state.paths, parent_id.as_deref()).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn collections_add_item(
+    state: State<'_, AppState>,
+    collection_id: String,
+    item_id: String,
+) -> Result<(), String> {
+    collections::add_item_to_collection(&state.paths, &collection_id, &item_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn collections_remove_item(
+    state: State<'_, AppState>,
+    collection_id: String,
+    item_id: String,
+) -> Result<(), String> {
+    collections::remove_item_from_collection(&state.paths, &collection_id, &item_id)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -5897,6 +7445,33 @@ fn library_get(
     library::get_item_by_id(&state.paths, &item_id).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn library_item_get_metadata(
+    state: State<'_, AppState>,
+    item_id: String,
+) -> Result<std::collections::HashMap<String, String>, String> {
+    library::get_item_metadata(&state.paths, &item_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn library_item_history_list(
+    state: State<'_, AppState>,
+    item_id: String,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<library::LibraryItemHistoryRow>, String> {
+    library::list_item_history(&state.paths, &item_id, limit, offset).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+async fn library_repair_metadata(state: State<'_, AppState>) -> Result<usize, String> {
+    let paths = state.paths.clone();
+    tauri::async_runtime::spawn_blocking(move || library::repair_item_metadata(&paths))
+        .await
+        .map_err(|e| e.to_string())?
+        .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn youtube_subscriptions_list(
     state: State<'_, AppState>,
@@ -5904,6 +7479,13 @@ fn youtube_subscriptions_list(
     subscriptions::list_youtube_subscriptions(&state.paths).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn youtube_subscriptions_list_with_errors(
+    state: State<'_, AppState>,
+) -> Result<Vec<subscriptions::YoutubeSubscriptionRow>, String> {
+    subscriptions::list_youtube_subscriptions_with_errors(&state.paths).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn youtube_subscriptions_output_dir(
     state: State<'_, AppState>,
@@ -5911,7 +7493,7 @@ fn youtube_subscriptions_output_dir(
 ) -> Result<String, String> {
     let sub = subscriptions::get_youtube_subscription_by_id(&state.paths, &id)
         .map_err(|e| e.to_string())?
-        .ok_or_else(|| format!("subscription not found: {id}"))?;
+        .ok_or_else(|| voxvulgi_engine::EngineError::not_found("subscription", &id).to_string())?;
     subscriptions::youtube_subscription_output_dir(&state.paths, &sub)
         .map(|path| path.to_string_lossy().to_string())
         .map_err(|e| e.to_string())
@@ -5958,6 +7540,18 @@ fn youtube_subscriptions_export_json(
     .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn youtube_subscriptions_export_opml(
+    state: State<'_, AppState>,
+    out_path: String,
+) -> Result<subscriptions::YoutubeSubscriptionsExportSummary, String> {
+    subscriptions::export_youtube_subscriptions_opml(
+        &state.paths,
+        &std::path::PathBuf::from(out_path),
+    )
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn youtube_subscriptions_import_json(
     state: State<'_, AppState>,
@@ -6204,64 +7798,199 @@ fn download_presets_set(
 }
 
 #[tauri::command]
-fn download_presets_export_json(
+fn download_presets_export_json(
+    state: State<'_, AppState>,
+    out_path: String,
+) -> Result<(), String> {
+    let config_value =
+        config::load_download_presets_config(&state.paths).map_err(|e| e.to_string())?;
+    let out_path = std::path::PathBuf::from(out_path);
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+    }
+    let json = serde_json::to_string_pretty(&config_value).map_err(|e| e.to_string())?;
+    std::fs::write(out_path, format!("{json}\n")).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn download_presets_import_json(
+    state: State<'_, AppState>,
+    in_path: String,
+) -> Result<config::DownloadPresetsConfig, String> {
+    let bytes = std::fs::read(std::path::PathBuf::from(in_path)).map_err(|e| e.to_string())?;
+    let parsed: config::DownloadPresetsConfig =
+        serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
+    config::save_download_presets_config(&state.paths, &parsed).map_err(|e| e.to_string())?;
+    config::load_download_presets_config(&state.paths).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn subtitles_list_tracks(
+    state: State<'_, AppState>,
+    item_id: String,
+) -> Result<Vec<subtitle_tracks::SubtitleTrackRow>, String> {
+    subtitle_tracks::list_tracks(&state.paths, &item_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn subtitles_list_tracks_filtered(
+    state: State<'_, AppState>,
+    item_id: String,
+    options: Option<subtitle_tracks::ListTracksOptions>,
+) -> Result<subtitle_tracks::SubtitleTracksPage, String> {
+    subtitle_tracks::list_tracks_filtered(&state.paths, &item_id, &options.unwrap_or_default())
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn subtitles_list_versions(
+    state: State<'_, AppState>,
+    item_id: String,
+    kind: String,
+    lang: String,
+) -> Result<Vec<subtitle_tracks::SubtitleTrackRow>, String> {
+    subtitle_tracks::list_track_versions(&state.paths, &item_id, &kind, &lang)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn subtitles_get_lineage(
+    state: State<'_, AppState>,
+    track_id: String,
+) -> Result<Vec<subtitle_tracks::SubtitleTrackRow>, String> {
+    subtitle_tracks::get_lineage(&state.paths, &track_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn subtitles_list_children(
+    state: State<'_, AppState>,
+    track_id: String,
+) -> Result<Vec<subtitle_tracks::SubtitleTrackRow>, String> {
+    subtitle_tracks::list_children(&state.paths, &track_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn subtitles_restore_version(
+    state: State<'_, AppState>,
+    track_id: String,
+) -> Result<subtitle_tracks::SubtitleTrackRow, String> {
+    subtitle_tracks::restore_version(&state.paths, &track_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn subtitles_load_track(
+    state: State<'_, AppState>,
+    track_id: String,
+) -> Result<subtitles::SubtitleDocument, String> {
+    subtitle_tracks::load_document(&state.paths, &track_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn subtitles_get_metadata(
+    state: State<'_, AppState>,
+    track_id: String,
+) -> Result<std::collections::HashMap<String, serde_json::Value>, String> {
+    subtitle_tracks::get_document_metadata(&state.paths, &track_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn subtitles_set_metadata(
     state: State<'_, AppState>,
-    out_path: String,
-) -> Result<(), String> {
-    let config_value =
-        config::load_download_presets_config(&state.paths).map_err(|e| e.to_string())?;
-    let out_path = std::path::PathBuf::from(out_path);
-    if let Some(parent) = out_path.parent() {
-        std::fs::create_dir_all(parent).map_err(|e| e.to_string())?;
-    }
-    let json = serde_json::to_string_pretty(&config_value).map_err(|e| e.to_string())?;
-    std::fs::write(out_path, format!("{json}\n")).map_err(|e| e.to_string())
+    track_id: String,
+    key: String,
+    value: serde_json::Value,
+) -> Result<std::collections::HashMap<String, serde_json::Value>, String> {
+    subtitle_tracks::set_document_metadata(&state.paths, &track_id, &key, value)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn download_presets_import_json(
+#[allow(non_snake_case)]
+fn subtitles_save_new_version(
     state: State<'_, AppState>,
-    in_path: String,
-) -> Result<config::DownloadPresetsConfig, String> {
-    let bytes = std::fs::read(std::path::PathBuf::from(in_path)).map_err(|e| e.to_string())?;
-    let parsed: config::DownloadPresetsConfig =
-        serde_json::from_slice(&bytes).map_err(|e| e.to_string())?;
-    config::save_download_presets_config(&state.paths, &parsed).map_err(|e| e.to_string())?;
-    config::load_download_presets_config(&state.paths).map_err(|e| e.to_string())
+    track_id: String,
+    doc: subtitles::SubtitleDocument,
+    version_metadata: Option<std::collections::HashMap<String, serde_json::Value>>,
+    versionMetadata: Option<std::collections::HashMap<String, serde_json::Value>>,
+) -> Result<subtitle_tracks::SubtitleSaveResult, String> {
+    subtitle_tracks::save_new_version(
+        &state.paths,
+        &track_id,
+        doc,
+        version_metadata.or(versionMetadata),
+    )
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn subtitles_list_tracks(
+fn subtitles_annotate_version(
     state: State<'_, AppState>,
-    item_id: String,
-) -> Result<Vec<subtitle_tracks::SubtitleTrackRow>, String> {
-    subtitle_tracks::list_tracks(&state.paths, &item_id).map_err(|e| e.to_string())
+    track_id: String,
+    notes: Option<String>,
+) -> Result<subtitle_tracks::SubtitleTrackRow, String> {
+    subtitle_tracks::annotate_version(&state.paths, &track_id, notes).map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn subtitles_load_track(
+fn subtitles_find_and_replace(
     state: State<'_, AppState>,
     track_id: String,
-) -> Result<subtitles::SubtitleDocument, String> {
-    subtitle_tracks::load_document(&state.paths, &track_id).map_err(|e| e.to_string())
+    pattern: String,
+    replacement: String,
+    case_sensitive: bool,
+    whole_word: bool,
+) -> Result<subtitle_tracks::SubtitleTrackRow, String> {
+    let doc = subtitle_tracks::load_document(&state.paths, &track_id).map_err(|e| e.to_string())?;
+    let (doc, _) =
+        subtitles::find_and_replace(&doc, &pattern, &replacement, case_sensitive, whole_word)
+            .map_err(|e| e.to_string())?;
+    subtitle_tracks::save_new_version(&state.paths, &track_id, doc, None)
+        .map(|saved| saved.track)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
-fn subtitles_save_new_version(
+fn subtitles_filter_low_confidence(
     state: State<'_, AppState>,
     track_id: String,
-    doc: subtitles::SubtitleDocument,
+    threshold: Option<f32>,
 ) -> Result<subtitle_tracks::SubtitleTrackRow, String> {
-    subtitle_tracks::save_new_version(&state.paths, &track_id, doc).map_err(|e| e.to_string())
+    let doc = subtitle_tracks::load_document(&state.paths, &track_id).map_err(|e| e.to_string())?;
+    let (doc, _) = subtitles::replace_low_confidence_segments(
+        &doc,
+        threshold.unwrap_or(subtitles::LOW_CONFIDENCE_FILTER_DEFAULT_THRESHOLD),
+    );
+    subtitle_tracks::save_new_version(&state.paths, &track_id, doc, None)
+        .map(|saved| saved.track)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
+fn subtitles_find_and_replace_preview(
+    doc: subtitles::SubtitleDocument,
+    pattern: String,
+    replacement: String,
+    case_sensitive: bool,
+    whole_word: bool,
+) -> Result<subtitles::FindReplacePreview, String> {
+    subtitles::find_and_replace_preview(&doc, &pattern, &replacement, case_sensitive, whole_word)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
 fn subtitles_export_doc_srt(
     doc: subtitles::SubtitleDocument,
     out_path: String,
+    include_speaker_labels: Option<bool>,
+    includeSpeakerLabels: Option<bool>,
 ) -> Result<(), String> {
     let out_path = std::path::PathBuf::from(out_path);
-    subtitle_tracks::export_document_srt(&doc, &out_path).map_err(|e| e.to_string())
+    let include_speaker_labels = include_speaker_labels
+        .or(includeSpeakerLabels)
+        .unwrap_or(false);
+    subtitle_tracks::export_document_srt_with_options(&doc, &out_path, include_speaker_labels)
+        .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -6274,19 +8003,73 @@ fn subtitles_export_doc_vtt(
 }
 
 #[tauri::command]
+fn subtitles_export_doc_vtt_with_options(
+    doc: subtitles::SubtitleDocument,
+    out_path: String,
+    options: subtitles::ExportVttOptions,
+) -> Result<(), String> {
+    let out_path = std::path::PathBuf::from(out_path);
+    subtitle_tracks::export_document_vtt_with_options(&doc, &out_path, &options)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
 async fn jobs_list(
     state: State<'_, AppState>,
     limit: usize,
     offset: usize,
+    item_id: Option<String>,
+    itemId: Option<String>,
+    job_type: Option<String>,
+    jobType: Option<String>,
+) -> Result<Vec<jobs::JobRow>, String> {
+    let paths = state.paths.clone();
+    let item_id = item_id.or(itemId);
+    let job_type = job_type.or(jobType);
+    tauri::async_runtime::spawn_blocking(move || {
+        jobs::list_jobs(
+            &paths,
+            limit,
+            offset,
+            item_id.as_deref(),
+            job_type.as_deref(),
+        )
+        .map_err(|e| e.to_string())
+    })
+    .await
+    .map_err(|e| e.to_string())?
+}
+
+#[tauri::command]
+async fn jobs_list_children(
+    state: State<'_, AppState>,
+    job_id: String,
+    limit: Option<usize>,
+    offset: Option<usize>,
 ) -> Result<Vec<jobs::JobRow>, String> {
     let paths = state.paths.clone();
     tauri::async_runtime::spawn_blocking(move || {
-        jobs::list_jobs(&paths, limit, offset).map_err(|e| e.to_string())
+        jobs::list_child_jobs(&paths, &job_id, limit.unwrap_or(200), offset.unwrap_or(0))
+            .map_err(|e| e.to_string())
     })
     .await
     .map_err(|e| e.to_string())?
 }
 
+#[tauri::command]
+fn jobs_get(state: State<'_, AppState>, job_id: String) -> Result<jobs::JobRow, String> {
+    jobs::get_job_by_id(&state.paths, &job_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn jobs_get_batch(
+    state: State<'_, AppState>,
+    job_ids: Vec<String>,
+) -> Result<Vec<jobs::JobRow>, String> {
+    jobs::get_jobs_by_ids(&state.paths, &job_ids).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 async fn jobs_list_for_item(
     state: State<'_, AppState>,
@@ -6325,10 +8108,17 @@ fn jobs_enqueue_import_local(
 }
 
 #[tauri::command]
+#[allow(non_snake_case)]
 fn jobs_enqueue_install_phase2_packs_v1(
     state: State<'_, AppState>,
+    rollback_on_failure: Option<bool>,
+    rollbackOnFailure: Option<bool>,
 ) -> Result<jobs::JobRow, String> {
-    jobs::enqueue_install_phase2_packs_v1(&state.paths).map_err(|e| e.to_string())
+    jobs::enqueue_install_phase2_packs_v1_with_options(
+        &state.paths,
+        rollback_on_failure.or(rollbackOnFailure).unwrap_or(false),
+    )
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -6339,7 +8129,11 @@ fn jobs_enqueue_download_batch(
     output_dir: Option<String>,
     use_browser_cookies: Option<bool>,
     preset_id: Option<String>,
-) -> Result<Vec<jobs::JobRow>, String> {
+    output_filename_template: Option<String>,
+    schedule_after_ms: Option<i64>,
+    read_archive: Option<String>,
+    write_archive: Option<String>,
+) -> Result<jobs::DownloadBatchEnqueueResult, String> {
     jobs::enqueue_download_direct_url_batch(
         &state.paths,
         urls,
@@ -6347,10 +8141,21 @@ fn jobs_enqueue_download_batch(
         output_dir,
         use_browser_cookies,
         preset_id,
+        output_filename_template,
+        schedule_after_ms,
+        read_archive,
+        write_archive,
     )
     .map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn jobs_validate_download_urls(
+    urls: Vec<String>,
+) -> Result<Vec<jobs::UrlValidationResult>, String> {
+    Ok(jobs::validate_urls(urls))
+}
+
 #[tauri::command]
 fn jobs_enqueue_instagram_batch(
     state: State<'_, AppState>,
@@ -6370,6 +8175,7 @@ fn jobs_enqueue_instagram_batch(
 }
 
 #[tauri::command]
+#[allow(non_snake_case)]
 fn jobs_enqueue_image_batch(
     state: State<'_, AppState>,
     start_urls: Vec<String>,
@@ -6381,18 +8187,55 @@ fn jobs_enqueue_image_batch(
     output_subdir: Option<String>,
     output_dir: Option<String>,
     auth_cookie: Option<String>,
+    max_hamming_distance: Option<u8>,
+    max_image_bytes: Option<u64>,
+    schedule_after_ms: Option<i64>,
+    file_extensions_filter: Option<Vec<String>>,
+    fileExtensionsFilter: Option<Vec<String>>,
+    min_image_pixels: Option<u64>,
+    minImagePixels: Option<u64>,
+    max_image_pixels: Option<u64>,
+    maxImagePixels: Option<u64>,
 ) -> Result<jobs::JobRow, String> {
     jobs::enqueue_download_image_batch(
         &state.paths,
         start_urls,
-        max_pages,
-        delay_ms,
-        allow_cross_domain,
-        follow_content_links,
-        skip_url_keywords.unwrap_or_default(),
-        output_subdir,
-        output_dir,
-        auth_cookie,
+        jobs::DownloadImageBatchOptions {
+            max_pages,
+            delay_ms,
+            allow_cross_domain,
+            follow_content_links,
+            skip_url_keywords: skip_url_keywords.unwrap_or_default(),
+            output_subdir,
+            output_dir,
+            auth_cookie,
+            max_hamming_distance,
+            max_image_bytes,
+            schedule_after_ms,
+            file_extensions_filter: file_extensions_filter.or(fileExtensionsFilter),
+            min_image_pixels: min_image_pixels.or(minImagePixels),
+            max_image_pixels: max_image_pixels.or(maxImagePixels),
+        },
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+fn jobs_enqueue_watch_directory(
+    state: State<'_, AppState>,
+    dir_path: String,
+    extensions: Option<Vec<String>>,
+    interval_secs: Option<u64>,
+    intervalSecs: Option<u64>,
+) -> Result<jobs::JobRow, String> {
+    jobs::enqueue_watch_directory(
+        &state.paths,
+        dir_path,
+        extensions.unwrap_or_default(),
+        interval_secs
+            .or(intervalSecs)
+            .unwrap_or(DEFAULT_WATCH_DIRECTORY_INTERVAL_SECS),
     )
     .map_err(|e| e.to_string())
 }
@@ -6407,8 +8250,37 @@ fn jobs_enqueue_asr_local(
     state: State<'_, AppState>,
     item_id: String,
     lang: Option<String>,
+    task: Option<String>,
 ) -> Result<jobs::JobRow, String> {
-    jobs::enqueue_asr_local(&state.paths, item_id, lang).map_err(|e| e.to_string())
+    jobs::enqueue_asr_local(&state.paths, item_id, lang, task).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn jobs_enqueue_asr_batch(
+    state: State<'_, AppState>,
+    item_ids: Vec<String>,
+    lang: Option<String>,
+    model_id: Option<String>,
+) -> Result<jobs::AsrBatchEnqueueResult, String> {
+    let result = jobs::enqueue_asr_local_batch(&state.paths, item_ids, lang, model_id)
+        .map_err(|e| e.to_string())?;
+    let batch_id = result
+        .enqueued
+        .iter()
+        .find_map(|job| job.batch_id.as_deref());
+    for item_id in &result.skipped_already_transcribed {
+        append_diagnostics_trace_row_best_effort(
+            &state.paths,
+            "asr_batch_item_skipped",
+            serde_json::json!({
+                "item_id": item_id,
+                "reason": "already_has_source_track",
+            }),
+            "info",
+            batch_id,
+        );
+    }
+    Ok(result)
 }
 
 #[tauri::command]
@@ -6420,6 +8292,26 @@ fn jobs_enqueue_translate_local(
     jobs::enqueue_translate_local(&state.paths, item_id, source_track_id).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+#[allow(non_snake_case)]
+fn jobs_enqueue_translate_batch(
+    state: State<'_, AppState>,
+    model_id: Option<String>,
+    modelId: Option<String>,
+    dry_run: Option<bool>,
+    dryRun: Option<bool>,
+    max_items: Option<usize>,
+    maxItems: Option<usize>,
+) -> Result<jobs::TranslateBatchResult, String> {
+    jobs::enqueue_translate_batch(
+        &state.paths,
+        model_id.or(modelId),
+        dry_run.or(dryRun).unwrap_or(false),
+        max_items.or(maxItems),
+    )
+    .map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn jobs_enqueue_diarize_local_v1(
     state: State<'_, AppState>,
@@ -6463,23 +8355,44 @@ fn jobs_enqueue_tts_preview_pyttsx3_v1(
 }
 
 #[tauri::command]
+#[allow(non_snake_case)]
 fn jobs_enqueue_tts_neural_local_v1(
     state: State<'_, AppState>,
     item_id: String,
     source_track_id: String,
+    lang_code: Option<String>,
+    langCode: Option<String>,
+    fallback_to_pyttsx3: Option<bool>,
+    fallbackToPyttsx3: Option<bool>,
 ) -> Result<jobs::JobRow, String> {
-    jobs::enqueue_tts_neural_local_v1(&state.paths, item_id, source_track_id)
-        .map_err(|e| e.to_string())
+    jobs::enqueue_tts_neural_local_v1(
+        &state.paths,
+        item_id,
+        source_track_id,
+        lang_code.or(langCode),
+        fallback_to_pyttsx3.or(fallbackToPyttsx3),
+    )
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
+#[allow(non_snake_case)]
 fn jobs_enqueue_dub_voice_preserving_v1(
     state: State<'_, AppState>,
     item_id: String,
     source_track_id: String,
+    auto_extract_profiles: Option<bool>,
+    autoExtractProfiles: Option<bool>,
 ) -> Result<jobs::JobRow, String> {
-    jobs::enqueue_dub_voice_preserving_v1(&state.paths, item_id, source_track_id)
-        .map_err(|e| e.to_string())
+    jobs::enqueue_dub_voice_preserving_v1_with_options(
+        &state.paths,
+        item_id,
+        source_track_id,
+        auto_extract_profiles
+            .or(autoExtractProfiles)
+            .unwrap_or(false),
+    )
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -6555,6 +8468,12 @@ fn jobs_enqueue_mix_dub_preview_v1(
     timingFitMinFactor: Option<f32>,
     timing_fit_max_factor: Option<f32>,
     timingFitMaxFactor: Option<f32>,
+    normalize_speech: Option<bool>,
+    normalizeSpeech: Option<bool>,
+    tts_manifest_path: Option<String>,
+    ttsManifestPath: Option<String>,
+    variant_label: Option<String>,
+    variantLabel: Option<String>,
 ) -> Result<jobs::JobRow, String> {
     let item_id = item_id
         .or(itemId)
@@ -6570,6 +8489,9 @@ fn jobs_enqueue_mix_dub_preview_v1(
         timing_fit_enabled.or(timingFitEnabled),
         timing_fit_min_factor.or(timingFitMinFactor),
         timing_fit_max_factor.or(timingFitMaxFactor),
+        normalize_speech.or(normalizeSpeech),
+        tts_manifest_path.or(ttsManifestPath),
+        variant_label.or(variantLabel),
     )
     .map_err(|e| e.to_string())
 }
@@ -6587,6 +8509,8 @@ fn jobs_enqueue_mux_dub_preview_v1(
     dubbedAudioLang: Option<String>,
     original_audio_lang: Option<String>,
     originalAudioLang: Option<String>,
+    embed_subtitle_track_id: Option<String>,
+    embedSubtitleTrackId: Option<String>,
 ) -> Result<jobs::JobRow, String> {
     let item_id = item_id
         .or(itemId)
@@ -6601,32 +8525,127 @@ fn jobs_enqueue_mux_dub_preview_v1(
         keep_original_audio.or(keepOriginalAudio),
         dubbed_audio_lang.or(dubbedAudioLang),
         original_audio_lang.or(originalAudioLang),
+        embed_subtitle_track_id.or(embedSubtitleTrackId),
     )
     .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
+#[allow(non_snake_case)]
 fn jobs_enqueue_separate_audio_spleeter(
     state: State<'_, AppState>,
     item_id: String,
+    audio_path_override: Option<String>,
+    audioPathOverride: Option<String>,
+) -> Result<jobs::JobRow, String> {
+    jobs::enqueue_separate_audio_spleeter_with_options(
+        &state.paths,
+        item_id,
+        2,
+        audio_path_override.or(audioPathOverride),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+fn jobs_enqueue_separate_audio_spleeter_5stems(
+    state: State<'_, AppState>,
+    item_id: String,
+    audio_path_override: Option<String>,
+    audioPathOverride: Option<String>,
 ) -> Result<jobs::JobRow, String> {
-    jobs::enqueue_separate_audio_spleeter(&state.paths, item_id).map_err(|e| e.to_string())
+    jobs::enqueue_separate_audio_spleeter_with_options(
+        &state.paths,
+        item_id,
+        5,
+        audio_path_override.or(audioPathOverride),
+    )
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
+#[allow(non_snake_case)]
 fn jobs_enqueue_separate_audio_demucs_v1(
     state: State<'_, AppState>,
     item_id: String,
+    chunk_duration_secs: Option<u64>,
+    chunkDurationSecs: Option<u64>,
+    audio_path_override: Option<String>,
+    audioPathOverride: Option<String>,
+) -> Result<jobs::JobRow, String> {
+    jobs::enqueue_separate_audio_demucs_v1_with_options(
+        &state.paths,
+        item_id,
+        chunk_duration_secs.or(chunkDurationSecs),
+        audio_path_override.or(audioPathOverride),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn jobs_enqueue_separate_audio_demucs_4stems_v1(
+    state: State<'_, AppState>,
+    item_id: String,
+) -> Result<jobs::JobRow, String> {
+    jobs::enqueue_separate_audio_demucs_4stems_v1(&state.paths, item_id).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn jobs_enqueue_extract_speaker_voice_profile_v1(
+    state: State<'_, AppState>,
+    item_id: String,
+    speaker_key: String,
+    start_ms: i64,
+    end_ms: i64,
 ) -> Result<jobs::JobRow, String> {
-    jobs::enqueue_separate_audio_demucs_v1(&state.paths, item_id).map_err(|e| e.to_string())
+    jobs::enqueue_extract_speaker_voice_profile_v1(
+        &state.paths,
+        item_id,
+        speaker_key,
+        start_ms,
+        end_ms,
+    )
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
 fn jobs_enqueue_clean_vocals_v1(
     state: State<'_, AppState>,
     item_id: String,
+    source: Option<String>,
+) -> Result<jobs::JobRow, String> {
+    jobs::enqueue_clean_vocals_v1(&state.paths, item_id, source).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn jobs_enqueue_sync_subtitles_v1(
+    state: State<'_, AppState>,
+    item_id: String,
+    source_track_id: String,
 ) -> Result<jobs::JobRow, String> {
-    jobs::enqueue_clean_vocals_v1(&state.paths, item_id).map_err(|e| e.to_string())
+    jobs::enqueue_sync_subtitles_v1(&state.paths, item_id, source_track_id)
+        .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn jobs_enqueue_full_pipeline_v1(
+    state: State<'_, AppState>,
+    item_id: String,
+    asr_lang: Option<String>,
+    model_id: Option<String>,
+    tts_backend: Option<String>,
+    ducking_strength: Option<f32>,
+) -> Result<jobs::FullPipelineResult, String> {
+    jobs::enqueue_full_pipeline_v1(
+        &state.paths,
+        item_id,
+        asr_lang,
+        model_id,
+        tts_backend,
+        ducking_strength,
+    )
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -6659,6 +8678,15 @@ fn jobs_enqueue_export_pack_v1(
     jobs::enqueue_export_pack_v1(&state.paths, item_id).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn jobs_enqueue_export_multi_pack_v1(
+    state: State<'_, AppState>,
+    item_ids: Vec<String>,
+    out_path: String,
+) -> Result<jobs::JobRow, String> {
+    jobs::enqueue_export_multi_pack_v1(&state.paths, item_ids, out_path).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn jobs_enqueue_localization_batch_v1(
     state: State<'_, AppState>,
@@ -6725,12 +8753,20 @@ fn jobs_runtime_settings_get(
     jobs::get_runtime_settings(&state.paths).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn jobs_runtime_state_get(state: State<'_, AppState>) -> Result<jobs::JobRuntimeState, String> {
+    jobs::get_runtime_state(&state.paths, state.runner.running_count()).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 fn jobs_runtime_settings_set(
     state: State<'_, AppState>,
     max_concurrency: usize,
 ) -> Result<jobs::JobRuntimeSettings, String> {
-    jobs::set_runtime_max_concurrency(&state.paths, max_concurrency).map_err(|e| e.to_string())
+    let settings = jobs::set_runtime_max_concurrency(&state.paths, max_concurrency)
+        .map_err(|e| e.to_string())?;
+    state.runner.set_max_concurrency(settings.max_concurrency);
+    Ok(settings)
 }
 
 #[tauri::command]
@@ -6753,6 +8789,26 @@ fn jobs_flush_cache(
     jobs::flush_jobs_cache(&state.paths, options).map_err(|e| e.to_string())
 }
 
+#[tauri::command]
+fn jobs_flush_cache_dry_run(
+    state: State<'_, AppState>,
+    options: Option<jobs::JobCleanupOptions>,
+) -> Result<jobs::JobCleanupSummary, String> {
+    let options = jobs::JobCleanupOptions {
+        dry_run: true,
+        ..options.unwrap_or_default()
+    };
+    jobs::flush_jobs_cache(&state.paths, Some(options)).map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+fn jobs_flush_cache_with_options(
+    state: State<'_, AppState>,
+    options: jobs::JobCleanupOptions,
+) -> Result<jobs::JobCleanupSummary, String> {
+    jobs::flush_jobs_cache(&state.paths, Some(options)).map_err(|e| e.to_string())
+}
+
 #[tauri::command]
 #[allow(non_snake_case)]
 fn jobs_clear_failed_for_item(
@@ -6775,13 +8831,48 @@ fn jobs_retry(
     state: State<'_, AppState>,
     job_id: Option<String>,
     jobId: Option<String>,
+    preserve_batch: Option<bool>,
+    preserveBatch: Option<bool>,
+    with_delay_ms: Option<u64>,
+    withDelayMs: Option<u64>,
 ) -> Result<jobs::JobRow, String> {
     let job_id = job_id
         .or(jobId)
         .map(|v| v.trim().to_string())
         .filter(|v| !v.is_empty())
         .ok_or_else(|| "missing required key jobId".to_string())?;
-    jobs::retry_job(&state.paths, &job_id).map_err(|e| e.to_string())
+    jobs::retry_job_with_options(
+        &state.paths,
+        &job_id,
+        preserve_batch.or(preserveBatch).unwrap_or(true),
+        with_delay_ms.or(withDelayMs),
+    )
+    .map_err(|e| e.to_string())
+}
+
+#[tauri::command]
+#[allow(non_snake_case)]
+fn jobs_retry_batch(
+    state: State<'_, AppState>,
+    batch_id: Option<String>,
+    batchId: Option<String>,
+    preserve_batch: Option<bool>,
+    preserveBatch: Option<bool>,
+    with_delay_ms: Option<u64>,
+    withDelayMs: Option<u64>,
+) -> Result<Vec<jobs::JobRow>, String> {
+    let batch_id = batch_id
+        .or(batchId)
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| "missing required key batchId".to_string())?;
+    jobs::retry_batch(
+        &state.paths,
+        &batch_id,
+        preserve_batch.or(preserveBatch).unwrap_or(false),
+        with_delay_ms.or(withDelayMs),
+    )
+    .map_err(|e| e.to_string())
 }
 
 #[tauri::command]
@@ -6979,26 +9070,49 @@ fn agent_dump_complete(path: String) {
 pub fn run() {
     tauri::Builder::default()
         .setup(|app| {
-            let base_dir = app.path().app_data_dir()?;
-            let paths = AppPaths::new(AppPaths::normalize_base_dir(&base_dir));
+            let resource_dir = app.path().resource_dir().ok();
+            // `VOXVULGI_DATA_DIR` wins so CI and containers stay isolated. Portable mode
+            // (`--portable` or `<resource dir>/portable.txt`) keeps all data in
+            // `<exe dir>/data` instead of the OS app data directory.
+            let (base_dir, path_source) = if let Some(env_paths) = AppPaths::from_env() {
+                (env_paths.base_dir, env_paths.source)
+            } else if AppPaths::portable_mode_requested(std::env::args(), resource_dir.as_deref()) {
+                (AppPaths::portable_base_dir()?, PathSource::Portable)
+            } else {
+                (app.path().app_data_dir()?, PathSource::OsAppData)
+            };
+            let base_dir = AppPaths::normalize_base_dir(&base_dir);
+            // A storage migration leaves a pointer file behind; follow it to the moved data. An
+            // explicit `VOXVULGI_DATA_DIR` is used as given.
+            let base_dir = if path_source == PathSource::Environment {
+                base_dir
+            } else {
+                AppPaths::resolve_base_dir(&base_dir)
+            };
+            let paths = AppPaths::new(base_dir.clone()).with_source(path_source);
             let startup = Arc::new(Mutex::new(StartupTracker::new()));
             let _ = AGENT_APP_HANDLE.set(app.handle().clone());
             spawn_agent_bridge(&AppPaths::normalize_base_dir(&base_dir));
             set_startup_phase(&startup, &paths, "app_dirs", "running", None);
             paths.ensure_dirs()?;
+            // A storage migration with "remove old dir" empties it only now, after the pointer
+            // was followed and nothing holds the old database open.
+            migration::finish_pending_old_dir_cleanup(&paths);
             set_startup_phase(&startup, &paths, "app_dirs", "ready", None);
             let cli_safe_mode = std::env::args().any(|value| value.trim() == "--safe-mode");
             let persisted_safe_mode = config::load_safe_mode_config(&paths)
                 .map(|value| value.enabled)
                 .unwrap_or(false);
             let safe_mode_enabled = cli_safe_mode || persisted_safe_mode;
+            let mut offline_bundle_thread = None;
             if safe_mode_enabled {
                 set_startup_phase(&startup, &paths, "offline_bundle", "skipped", None);
-            } else if let Ok(resource_dir) = app.path().resource_dir() {
+            } else if let Some(resource_dir) = resource_dir {
                 set_startup_phase(&startup, &paths, "offline_bundle", "pending", None);
                 let startup_for_thread = Arc::clone(&startup);
                 let paths_for_bundle = paths.clone();
-                std::thread::spawn(move || {
+                let bundle_app = app.handle().clone();
+                offline_bundle_thread = Some(std::thread::spawn(move || {
                     set_startup_phase(
                         &startup_for_thread,
                         &paths_for_bundle,
@@ -7008,7 +9122,7 @@ pub fn run() {
                     );
                     let result = apply_offline_bundle_if_present(&paths_for_bundle, &resource_dir);
                     match result {
-                        Ok(()) => {
+                        Ok(applied) => {
                             set_startup_phase(
                                 &startup_for_thread,
                                 &paths_for_bundle,
@@ -7016,8 +9130,16 @@ pub fn run() {
                                 "ready",
                                 None,
                             );
+                            applied
                         }
                         Err(error) => {
+                            let _ = bundle_app.emit(
+                                "app_setup_error",
+                                AppSetupErrorEvent {
+                                    error: error.clone(),
+                                    severity: "warning",
+                                },
+                            );
                             set_startup_phase(
                                 &startup_for_thread,
                                 &paths_for_bundle,
@@ -7025,9 +9147,10 @@ pub fn run() {
                                 "error",
                                 Some(error),
                             );
+                            false
                         }
                     }
-                });
+                }));
             } else {
                 set_startup_phase(
                     &startup,
@@ -7038,6 +9161,9 @@ pub fn run() {
                 );
             }
             set_startup_phase(&startup, &paths, "db_schema", "running", None);
+            let recover_corrupt_db =
+                std::env::args().any(|value| value.trim() == "--recover-corrupt-db");
+            let db_integrity = startup_db_integrity(&paths, recover_corrupt_db);
             db::ensure_schema(&paths)?;
             set_startup_phase(&startup, &paths, "db_schema", "ready", None);
             if safe_mode_enabled {
@@ -7056,6 +9182,7 @@ pub fn run() {
                         "source": "background_sampler",
                     }),
                     "info",
+                    Some(launch_correlation_id()),
                 );
             });
             app.manage(AppState {
@@ -7064,7 +9191,45 @@ pub fn run() {
                 safe_mode_enabled: Arc::new(AtomicBool::new(safe_mode_enabled)),
                 safe_mode_cli: cli_safe_mode,
                 startup,
+                trace_correlation_id: Arc::new(Mutex::new(None)),
+                trace_rate_limiter: Arc::new(Mutex::new(TraceRateLimiter::default())),
+                app_handle: app.handle().clone(),
+                db_integrity,
+            });
+            let progress_handle = app.state::<AppState>().app_handle.clone();
+            jobs::set_phase2_install_progress_hook(move |progress| {
+                let _ = progress_handle.emit("phase2_install_progress", progress);
             });
+            let runtime_handle = app.state::<AppState>().app_handle.clone();
+            jobs::set_runtime_state_hook(move |event| {
+                let _ = runtime_handle.emit("runtime_state_changed", event);
+            });
+            let batch_handle = app.state::<AppState>().app_handle.clone();
+            jobs::set_batch_completed_hook(move |event| {
+                let _ = batch_handle.emit("batch_completed", event);
+            });
+            let failed_handle = app.state::<AppState>().app_handle.clone();
+            jobs::set_job_failed_hook(move |event| {
+                let _ = failed_handle.emit("job_failed", event);
+            });
+            let status_handle = app.state::<AppState>().app_handle.clone();
+            jobs::set_job_status_changed_hook(move |event| {
+                let _ = status_handle.emit("job_status_changed", event);
+            });
+            let model_handle = app.state::<AppState>().app_handle.clone();
+            voxvulgi_engine::models::set_model_download_hook(move |event| {
+                let _ = model_handle.emit("model_download_progress", event);
+            });
+            let ready_handle = app.handle().clone();
+            match offline_bundle_thread {
+                Some(bundle) => {
+                    std::thread::spawn(move || {
+                        let applied = bundle.join().unwrap_or(false);
+                        emit_app_ready(&ready_handle, applied);
+                    });
+                }
+                None => emit_app_ready(&ready_handle, false),
+            }
             Ok(())
         })
         .plugin(tauri_plugin_dialog::init())
@@ -7072,6 +9237,9 @@ pub fn run() {
         .invoke_handler(tauri::generate_handler![
             diagnostics_info,
             diagnostics_clear_cache,
+            diagnostics_clear_cache_selective,
+            diagnostics_applied_bundles,
+            storage_migrate,
             diagnostics_thumbnail_cache_clear,
             diagnostics_thumbnail_cache_status,
             diagnostics_export_bundle,
@@ -7079,20 +9247,32 @@ pub fn run() {
             diagnostics_export_app_state_snapshot,
             diagnostics_generate_licensing_report,
             diagnostics_storage_breakdown,
+            diagnostics_health_check,
+            diagnostics_db_integrity_check,
+            diagnostics_clean_orphans,
             item_outputs,
             library_thumbnail_data_url,
+            library_item_thumbnail_path,
+            item_background_silences,
+            item_tts_manifest_summary,
             item_artifacts_list_v1,
             item_export_mux_preview_mp4,
             item_qc_report_v1_load,
             diagnostics_trace_clear,
+            diagnostics_trace_clear_old,
+            diagnostics_trace_size,
             diagnostics_trace_dir_set,
             diagnostics_trace_dir_status,
             diagnostics_trace_dir_use_default,
             diagnostics_trace_recent,
             diagnostics_trace_write_event,
+            diagnostics_trace_set_correlation_id,
+            diagnostics_trace_rate_limit_stats,
             safe_mode_set,
             safe_mode_status,
             startup_status,
+            app_ready_status,
+            downloads_dir_free_gb,
             downloads_dir_set,
             downloads_dir_status,
             downloads_dir_use_default,
@@ -7100,6 +9280,21 @@ pub fn run() {
             downloads_feature_root_use_default,
             config_batch_on_import_get,
             config_batch_on_import_set,
+            config_batch_on_import_warnings,
+            config_custom_tool_paths_get,
+            config_custom_tool_paths_set,
+            config_proxy_get,
+            config_proxy_set,
+            config_tts_defaults_get,
+            config_tts_defaults_set,
+            config_download_disk_space_get,
+            config_download_disk_space_set,
+            config_pack_integrity_get,
+            config_pack_integrity_set,
+            config_diagnostics_trace_config_get,
+            config_diagnostics_trace_config_set,
+            config_export_json,
+            config_import_json,
             config_youtube_auth_get,
             config_youtube_auth_set,
             config_diarization_optional_clear_token,
@@ -7110,13 +9305,27 @@ pub fn run() {
             download_presets_import_json,
             download_presets_set,
             library_get,
+            library_item_get_metadata,
+            library_item_history_list,
             library_list,
+            library_list_by_platform,
+            library_count,
+            library_count_by_tag,
+            library_list_in_collection,
+            library_repair_metadata,
+            collections_add_item,
+            collections_create,
+            collections_delete,
+            collections_list,
+            collections_remove_item,
+            collections_rename,
             localization_workspace_list,
             youtube_subscription_groups_delete,
             youtube_subscription_groups_list,
             youtube_subscription_groups_set_for_subscription,
             youtube_subscription_groups_upsert,
             youtube_subscriptions_list,
+            youtube_subscriptions_list_with_errors,
             youtube_subscriptions_output_dir,
             youtube_subscriptions_upsert,
             youtube_subscriptions_delete,
@@ -7126,6 +9335,7 @@ pub fn run() {
             youtube_subscriptions_queue_all_active,
             youtube_subscriptions_queue_group,
             youtube_subscriptions_export_json,
+            youtube_subscriptions_export_opml,
             youtube_subscriptions_import_json,
             youtube_subscriptions_import_4kvdp_dir,
             youtube_subscriptions_import_4kvdp_state,
@@ -7140,9 +9350,12 @@ pub fn run() {
             instagram_subscriptions_output_dir,
             jobs_cancel,
             jobs_cancel_all,
+            jobs_enqueue_watch_directory,
             jobs_enqueue_dummy,
             jobs_enqueue_asr_local,
+            jobs_enqueue_asr_batch,
             jobs_enqueue_download_batch,
+            jobs_validate_download_urls,
             jobs_enqueue_instagram_batch,
             jobs_enqueue_image_batch,
             jobs_enqueue_import_local,
@@ -7156,32 +9369,56 @@ pub fn run() {
             jobs_enqueue_mix_dub_preview_v1,
             jobs_enqueue_mux_dub_preview_v1,
             jobs_enqueue_separate_audio_spleeter,
+            jobs_enqueue_separate_audio_spleeter_5stems,
             jobs_enqueue_separate_audio_demucs_v1,
+            jobs_enqueue_separate_audio_demucs_4stems_v1,
             jobs_enqueue_clean_vocals_v1,
+            jobs_enqueue_sync_subtitles_v1,
+            jobs_enqueue_full_pipeline_v1,
+            jobs_enqueue_extract_speaker_voice_profile_v1,
             jobs_enqueue_qc_report_v1,
             jobs_enqueue_export_pack_v1,
+            jobs_enqueue_export_multi_pack_v1,
             jobs_enqueue_localization_batch_v1,
             jobs_enqueue_localization_run_v1,
             jobs_enqueue_voice_ab_preview_v1,
             jobs_enqueue_translate_local,
+            jobs_enqueue_translate_batch,
             jobs_cleanup_preview,
             jobs_flush_cache,
+            jobs_flush_cache_dry_run,
+            jobs_flush_cache_with_options,
             jobs_clear_failed_for_item,
             jobs_list,
+            jobs_list_children,
+            jobs_get,
+            jobs_get_batch,
             jobs_list_for_item,
             jobs_queue_control_get,
             jobs_queue_control_set,
             jobs_item_artifact_retention_policy,
             jobs_log_retention_policy,
+            jobs_log_retention_policy_set,
+            config_job_log_retention_get,
+            config_job_log_retention_set,
+            jobs_log_search,
             jobs_prune_logs,
             jobs_runtime_settings_get,
+            jobs_runtime_state_get,
             jobs_runtime_settings_set,
             jobs_retry,
+            jobs_retry_batch,
             models_inventory,
+            models_inventory_with_sizes,
             models_install,
+            models_install_async,
+            models_delete,
             models_install_demo,
             speakers_list,
             speakers_upsert,
+            speakers_delete,
+            speakers_list_all,
+            speakers_bulk_set_voice,
             voice_library_add_reference,
             voice_library_apply_to_item,
             voice_library_create,
@@ -7238,31 +9475,52 @@ pub fn run() {
             item_export_source_media,
             subtitles_export_doc_srt,
             subtitles_export_doc_vtt,
+            subtitles_export_doc_vtt_with_options,
             subtitles_list_tracks,
+            subtitles_list_tracks_filtered,
+            subtitles_list_versions,
+            subtitles_restore_version,
             subtitles_load_track,
+            subtitles_get_metadata,
+            subtitles_set_metadata,
             subtitles_save_new_version,
+            subtitles_annotate_version,
+            subtitles_get_lineage,
+            subtitles_list_children,
+            subtitles_find_and_replace,
+            subtitles_filter_low_confidence,
+            subtitles_find_and_replace_preview,
             shell_paths_status,
             shell_open_parent_dir,
             shell_open_path,
             shell_reveal_path,
+            tools_all_status,
             tools_ffmpeg_install,
+            tools_ffmpeg_probe_version,
+            ffmpeg_probe_v2,
             tools_ffmpeg_status,
             tools_js_runtime_install,
             tools_js_runtime_status,
             tools_python_install,
+            tools_python_detect_system,
             tools_python_status,
+            tools_python_venv_check_packages,
             tools_python_portable_install,
             tools_python_portable_status,
             tools_phase2_packs_install_plan,
             tools_phase2_packs_install_latest_state,
+            tools_phase2_packs_rollback,
             tools_pack_integrity_manifest_generate,
             tools_pack_integrity_manifest_status,
+            tools_pack_integrity_verify,
             tools_performance_tier_status,
             tools_diarization_install,
             tools_diarization_status,
             tools_spleeter_install,
             tools_spleeter_status,
             tools_demucs_install,
+            tools_cleanup_status,
+            tools_sync_status,
             tools_demucs_status,
             tools_tts_preview_install,
             tools_tts_preview_status,
@@ -7270,12 +9528,17 @@ pub fn run() {
             tools_tts_neural_local_v1_install,
             tools_tts_neural_local_v1_status,
             tools_tts_voice_preserving_local_v1_install,
+            tools_scan_user_models,
             tools_tts_voice_preserving_local_v1_status,
             tools_ytdlp_install,
+            tools_ytdlp_probe_version,
             tools_ytdlp_status,
+            tools_ytdlp_check_for_update,
+            tools_ytdlp_update,
             window_close,
             window_minimize,
             window_start_drag,
+            window_set_position,
             window_start_resize_drag,
             window_toggle_maximize,
             admin_save_snapshot,
@@ -7291,8 +9554,16 @@ pub fn run() {
         ])
         .build(tauri::generate_context!())
         .expect("error while building tauri application")
-        .run(|_app_handle, event| {
+        .run(|app_handle, event| {
             if let tauri::RunEvent::Exit = event {
+                if let Some(state) = app_handle.try_state::<AppState>() {
+                    let pending = state
+                        .trace_rate_limiter
+                        .lock()
+                        .map(|mut limiter| limiter.take_all())
+                        .unwrap_or_default();
+                    append_suppressed_trace_rows(&state.paths, &pending, None);
+                }
                 cleanup_agent_bridge_files();
             }
         });
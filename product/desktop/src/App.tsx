@@ -1,4 +1,13 @@
-import { Suspense, lazy, type ReactNode, useCallback, useEffect, useMemo, useState } from "react";
+import {
+  Suspense,
+  lazy,
+  type ReactNode,
+  useCallback,
+  useEffect,
+  useMemo,
+  useRef,
+  useState,
+} from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { listen } from "@tauri-apps/api/event";
 import { getCurrentWindow } from "@tauri-apps/api/window";
@@ -319,6 +328,8 @@ function localizationJobTypeLabel(jobType: string | null | undefined): string {
       return "Label speakers";
     case "install_phase2_packs_v1":
       return "Prepare voice cloning";
+    case "install_model_v1":
+      return "Install model";
     case "dub_voice_preserving_v1":
       return "Dub speech generation";
     case "mix_dub_preview_v1":
@@ -2186,9 +2197,18 @@ function LocalizationStudioHome({
   );
 }
 
+type WindowDragStartEvent = {
+  touch_x: number;
+  touch_y: number;
+  window_x: number;
+  window_y: number;
+};
+
 function App() {
   const initialPage = parseStoredPage(safeLocalStorageGet(ACTIVE_PAGE_KEY));
   const currentWindow = useMemo(() => getCurrentWindow(), []);
+  // Touch builds have no native window drag; see `window_drag_start` below.
+  const touchDragAnchorRef = useRef<WindowDragStartEvent | null>(null);
   const [page, setPage] = useState<AppPage>(initialPage);
   const [visitedPages, setVisitedPages] = useState<Record<AppPage, boolean>>(() => ({
     [initialPage]: true,
@@ -2486,6 +2506,52 @@ function App() {
     },
   );
 
+  // The backend emits `app_ready` once setup and the offline bundle finish, and
+  // `app_setup_error` when the bundle could not be applied.
+  useEffect(() => {
+    const unlisteners: Array<() => void> = [];
+    let disposed = false;
+    const refreshStartup = () => {
+      invoke<StartupStatus>("startup_status")
+        .then((status) => {
+          if (!disposed) setStartup(status);
+        })
+        .catch(() => {
+          // Ignore; the polling loop retries.
+        });
+    };
+    (async () => {
+      try {
+        const readyUnlisten = await listen("app_ready", refreshStartup);
+        const errorUnlisten = await listen<{ error: string; severity: string }>(
+          "app_setup_error",
+          (event) => {
+            void diagnosticsTrace(
+              "app_setup_error",
+              { error: event.payload.error },
+              event.payload.severity === "error" ? "error" : "warn",
+            );
+            refreshStartup();
+          },
+        );
+        if (disposed) {
+          readyUnlisten();
+          errorUnlisten();
+          return;
+        }
+        unlisteners.push(readyUnlisten, errorUnlisten);
+        const ready = await invoke<unknown>("app_ready_status");
+        if (ready) refreshStartup();
+      } catch {
+        // Ignore listener registration errors.
+      }
+    })();
+    return () => {
+      disposed = true;
+      for (const unlisten of unlisteners) unlisten();
+    };
+  }, []);
+
   useEffect(() => {
     if (!startup) return;
     const startupSettled =
@@ -2521,9 +2587,28 @@ function App() {
     },
   );
 
-  async function startWindowDrag() {
+  useEffect(() => {
+    let disposed = false;
+    let unlisten: (() => void) | null = null;
+    listen<WindowDragStartEvent>("window_drag_start", (event) => {
+      touchDragAnchorRef.current = event.payload;
+    })
+      .then((fn) => {
+        if (disposed) fn();
+        else unlisten = fn;
+      })
+      .catch(() => {
+        // Ignore listener registration errors.
+      });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  async function startWindowDrag(touch?: { x: number; y: number }) {
     try {
-      await invoke("window_start_drag");
+      await invoke("window_start_drag", touch ?? {});
     } catch {
       try {
         await currentWindow.startDragging();
@@ -2833,7 +2918,29 @@ function App() {
                   if (e.button !== 0) return;
                   e.preventDefault();
                   e.stopPropagation();
-                  void startWindowDrag();
+                  if (e.pointerType === "touch") {
+                    e.currentTarget.setPointerCapture(e.pointerId);
+                    void startWindowDrag({ x: e.screenX, y: e.screenY });
+                  } else {
+                    void startWindowDrag();
+                  }
+                }}
+                onPointerMove={(e) => {
+                  const anchor = touchDragAnchorRef.current;
+                  if (!anchor || e.pointerType !== "touch") return;
+                  const scale = window.devicePixelRatio || 1;
+                  void invoke("window_set_position", {
+                    x: Math.round(anchor.window_x + (e.screenX - anchor.touch_x) * scale),
+                    y: Math.round(anchor.window_y + (e.screenY - anchor.touch_y) * scale),
+                  }).catch(() => {
+                    // Ignore window API errors.
+                  });
+                }}
+                onPointerUp={() => {
+                  touchDragAnchorRef.current = null;
+                }}
+                onPointerCancel={() => {
+                  touchDragAnchorRef.current = null;
                 }}
                 onDoubleClick={(e) => {
                   e.preventDefault();
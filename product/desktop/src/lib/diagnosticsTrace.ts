@@ -4,14 +4,30 @@ export async function diagnosticsTrace(
   event: string,
   details: unknown = null,
   level: "info" | "warn" | "error" = "info",
+  tags: Record<string, string> | null = null,
+  correlationId: string | null = null,
+  source: string | null = null,
 ): Promise<void> {
   try {
     await invoke("diagnostics_trace_write_event", {
       event,
       details,
       level,
+      tags,
+      correlationId,
+      source,
     });
   } catch {
     // Never fail UI flows because diagnostics logging is unavailable.
   }
 }
+
+export async function setDiagnosticsTraceCorrelationId(
+  correlationId: string | null,
+): Promise<void> {
+  try {
+    await invoke("diagnostics_trace_set_correlation_id", { correlationId });
+  } catch {
+    // Never fail UI flows because diagnostics logging is unavailable.
+  }
+}
@@ -11,11 +11,14 @@ export type ArtifactKind =
   | "export_pack"
   | "qc_report"
   | "benchmark_report"
-  | "reference_curation_report";
+  | "reference_curation_report"
+  | "tts_segment_audio";
 
 export type ArtifactRerunKind =
   | "separate_spleeter"
+  | "separate_spleeter_5stems"
   | "separate_demucs"
+  | "separate_demucs_4stems"
   | "clean_vocals"
   | "tts_pyttsx3"
   | "tts_neural_local_v1"
@@ -47,6 +50,7 @@ export type ArtifactInfo = {
   voice_clone_converted_segments: number | null;
   voice_clone_fallback_segments: number | null;
   voice_clone_standard_tts_segments: number | null;
+  duration_ms: number | null;
   rerun_kind: ArtifactRerunKind | null;
 };
 
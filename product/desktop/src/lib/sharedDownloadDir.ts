@@ -6,6 +6,7 @@ export type DownloadDirStatus = {
   default_dir: string;
   exists: boolean;
   using_default: boolean;
+  disk_free_gb: number | null;
   feature_roots: FeatureRootStatus[];
 };
 
@@ -1,5 +1,6 @@
 import { startTransition, useCallback, useEffect, useMemo, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { confirm, open, save } from "@tauri-apps/plugin-dialog";
 import { usePageActivity, usePollingLoop } from "../lib/activity";
 import { copyPathToClipboard, openPathBestEffort, revealPath as revealFilesystemPath } from "../lib/pathOpener";
@@ -10,6 +11,15 @@ type DiagnosticsInfo = {
   app_name: string;
   app_version: string;
   engine_version: string;
+  is_portable: boolean;
+  data_dir_source: string;
+  db_integrity: string;
+};
+
+type DbIntegrityResult = {
+  ok: boolean;
+  issues: string[];
+  pages_checked: number;
 };
 
 type FfmpegToolsStatus = {
@@ -28,6 +38,13 @@ type YtDlpToolsStatus = {
   ytdlp_version: string | null;
 };
 
+type YtDlpUpdateInfo = {
+  installed_version: string | null;
+  latest_version: string | null;
+  update_available: boolean;
+  checked_at_ms: number;
+};
+
 type JsRuntimeToolsStatus = {
   available: boolean;
   preferred_runtime: string;
@@ -63,6 +80,12 @@ type PortablePythonStatus = {
   install_dir: string;
 };
 
+type SystemPythonInfo = {
+  path: string;
+  version: string;
+  is_venv: boolean;
+};
+
 type Phase2PackPlanItem = {
   id: string;
   title: string;
@@ -70,6 +93,20 @@ type Phase2PackPlanItem = {
   estimated_bytes: number | null;
 };
 
+type Phase2InstallProgress = {
+  step_index: number;
+  total_steps: number;
+  current_step_name: string;
+  current_step_status: string;
+};
+
+type ModelDownloadEvent = {
+  model_id: string;
+  downloaded_bytes: number;
+  total_bytes: number | null;
+  stage: string;
+};
+
 type Phase2InstallLatestState = {
   exists: boolean;
   path: string;
@@ -247,13 +284,41 @@ type StorageBreakdown = {
   logs_bytes: number;
   db_bytes: number;
   total_bytes: number;
+  per_platform?: { platform: string; item_count: number; media_bytes: number }[];
+  orphaned_item_dirs?: { dir_path: string; estimated_bytes: number }[];
+  orphaned_job_log_files?: { dir_path: string; estimated_bytes: number }[];
+};
+
+type OrphanCleanupResult = {
+  dry_run: boolean;
+  removed_dirs: string[];
+  removed_log_files: string[];
+  freed_bytes: number;
+  failed_paths: string[];
 };
 
 type CacheClearSummary = {
   removed_entries: number;
   removed_bytes: number;
+  category_bytes?: Record<string, number>;
 };
 
+type CacheClearOptions = {
+  clear_huggingface: boolean;
+  clear_python: boolean;
+  clear_asr_audio: boolean;
+  clear_tts_segments: boolean;
+  clear_image_batch: boolean;
+};
+
+const CACHE_CLEAR_CATEGORIES: [keyof CacheClearOptions, string][] = [
+  ["clear_huggingface", "HuggingFace"],
+  ["clear_python", "Python/pip"],
+  ["clear_asr_audio", "ASR audio"],
+  ["clear_tts_segments", "TTS segments"],
+  ["clear_image_batch", "Image batch manifests"],
+];
+
 type ThumbnailCacheStatus = {
   cache_dir: string;
   total_bytes: number;
@@ -272,6 +337,13 @@ type JobLogRetentionPolicy = {
   max_backups: number;
   max_age_days: number;
   total_cap_bytes: number;
+  overrides: JobTypeRetentionOverride[];
+};
+
+type JobTypeRetentionOverride = {
+  job_type: string;
+  max_age_days: number | null;
+  max_backups: number | null;
 };
 
 type JobStatus = "queued" | "running" | "succeeded" | "failed" | "canceled";
@@ -288,6 +360,7 @@ type JobRow = {
   started_at_ms: number | null;
   finished_at_ms: number | null;
   logs_path: string;
+  requeue_count?: number;
 };
 
 type JobCleanupOutputTarget = {
@@ -326,6 +399,14 @@ type JobCleanupSummary = {
   skipped_external_output_dirs: number;
   removed_cache_entries: number;
   failed_paths: JobCleanupFailure[];
+  dry_run: boolean;
+  would_remove_bytes: number;
+  available_to_remove_bytes: number;
+  freed_log_bytes: number;
+  freed_artifact_bytes: number;
+  freed_output_bytes: number;
+  freed_cache_bytes: number;
+  freed_bytes: number;
 };
 
 type ItemArtifactRetentionClass = {
@@ -347,6 +428,7 @@ type BatchOnImportRules = {
   auto_separate: boolean;
   auto_diarize: boolean;
   auto_dub_preview: boolean;
+  auto_diarize_backend?: string | null;
 };
 
 type OptionalDiarizationBackendConfig = {
@@ -362,6 +444,8 @@ type OptionalDiarizationBackendStatus = {
   token_present: boolean;
   token_path: string;
   config_path: string;
+  pyannote_importable?: boolean;
+  pyannote_import_error?: string | null;
 };
 
 type PackIntegrityManifestStatus = {
@@ -370,6 +454,10 @@ type PackIntegrityManifestStatus = {
   generated_at_ms: number | null;
 };
 
+type PackIntegrityConfig = {
+  verify_pack_integrity_on_job_start: boolean;
+};
+
 type PackIntegrityManifestResult = {
   out_path: string;
   file_bytes: number;
@@ -379,15 +467,31 @@ type PackIntegrityManifestResult = {
 type PerformanceTierStatus = {
   tier: string;
   gpu_names: string[];
+  gpu_name: string | null;
+  gpu_vram_mb: number | null;
   torch_cuda_available: boolean | null;
+  cuda_available: boolean;
+  metal_available: boolean;
+  cpu_cores: number;
+  recommended_max_concurrency: number;
   recommended_separation_backend: string;
   recommended_diarization_backend: string;
   recommended_tts_vc_device: string;
 };
 
+type PythonPackageLicense = {
+  name: string;
+  version: string;
+  license: string;
+  home_page: string | null;
+};
+
 type LicensingReportResult = {
   out_path: string;
   file_bytes: number;
+  python_packages: PythonPackageLicense[];
+  python_unavailable: boolean;
+  python_licenses_csv_path: string | null;
 };
 
 type DiagnosticsTraceDirStatus = {
@@ -417,6 +521,8 @@ type DiagnosticsTraceEntry = {
   level: string;
   details: unknown;
   process: DiagnosticsProcessSnapshot | null;
+  tags?: Record<string, string> | null;
+  correlation_id?: string | null;
 };
 
 type StartupPhase = {
@@ -503,6 +609,7 @@ type DownloadDirStatus = {
   default_dir: string;
   exists: boolean;
   using_default: boolean;
+  disk_free_gb: number | null;
   feature_roots: FeatureStorageRootStatus[];
 };
 
@@ -671,6 +778,7 @@ function defaultAdapterConfig(template: VoiceBackendAdapterTemplate): VoiceBacke
 export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
   const pageActive = usePageActivity(visible);
   const [info, setInfo] = useState<DiagnosticsInfo | null>(null);
+  const [dbIntegrityResult, setDbIntegrityResult] = useState<DbIntegrityResult | null>(null);
   const [startup, setStartup] = useState<StartupStatus | null>(null);
   const [inventory, setInventory] = useState<ModelInventory | null>(null);
   const [ffmpeg, setFfmpeg] = useState<FfmpegToolsStatus | null>(null);
@@ -678,8 +786,11 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
   const [jsRuntime, setJsRuntime] = useState<JsRuntimeToolsStatus | null>(null);
   const [python, setPython] = useState<PythonToolchainStatus | null>(null);
   const [portablePython, setPortablePython] = useState<PortablePythonStatus | null>(null);
+  const [systemPython, setSystemPython] = useState<SystemPythonInfo | null>(null);
   const [phase2Plan, setPhase2Plan] = useState<Phase2PackPlanItem[] | null>(null);
   const [phase2Latest, setPhase2Latest] = useState<Phase2InstallLatestState | null>(null);
+  const [phase2Progress, setPhase2Progress] = useState<Phase2InstallProgress | null>(null);
+  const [modelDownloads, setModelDownloads] = useState<Record<string, ModelDownloadEvent>>({});
   const [spleeter, setSpleeter] = useState<SpleeterPackStatus | null>(null);
   const [demucs, setDemucs] = useState<DemucsPackStatus | null>(null);
   const [diarization, setDiarization] = useState<DiarizationPackStatus | null>(null);
@@ -699,6 +810,9 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
   const [voiceBackendRecommendation, setVoiceBackendRecommendation] =
     useState<VoiceBackendRecommendation | null>(null);
   const [integrity, setIntegrity] = useState<PackIntegrityManifestStatus | null>(null);
+  const [packIntegrityConfig, setPackIntegrityConfig] = useState<PackIntegrityConfig | null>(
+    null,
+  );
   const [perfTier, setPerfTier] = useState<PerformanceTierStatus | null>(null);
   const [batchRules, setBatchRules] = useState<BatchOnImportRules | null>(null);
   const [diarizationOptional, setDiarizationOptional] =
@@ -710,6 +824,7 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
   const [storage, setStorage] = useState<StorageBreakdown | null>(null);
   const [thumbnailCache, setThumbnailCache] = useState<ThumbnailCacheStatus | null>(null);
   const [policy, setPolicy] = useState<JobLogRetentionPolicy | null>(null);
+  const [policyDraft, setPolicyDraft] = useState<JobLogRetentionPolicy | null>(null);
   const [artifactRetentionPolicy, setArtifactRetentionPolicy] =
     useState<ItemArtifactRetentionPolicy | null>(null);
   const [diagnosticsTraceDir, setDiagnosticsTraceDir] =
@@ -722,6 +837,13 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
   const [error, setError] = useState<string | null>(null);
   const [notice, setNotice] = useState<string | null>(null);
   const [busy, setBusy] = useState(false);
+  const [cacheClearOptions, setCacheClearOptions] = useState<CacheClearOptions>({
+    clear_huggingface: false,
+    clear_python: false,
+    clear_asr_audio: false,
+    clear_tts_segments: false,
+    clear_image_batch: false,
+  });
   const [snapshotBusy, setSnapshotBusy] = useState(false);
   const [sectionStatus, setSectionStatus] = useState<Record<DiagnosticsSectionKey, DiagnosticsSectionStatus>>({
     build: { state: "idle", error: null },
@@ -916,6 +1038,7 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
         nextPython,
         nextPortablePython,
         nextIntegrity,
+        nextPackIntegrityConfig,
         nextPerfTier,
       ] = await Promise.all([
         invoke<FfmpegToolsStatus>("tools_ffmpeg_status"),
@@ -924,6 +1047,7 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
         invoke<PythonToolchainStatus>("tools_python_status"),
         invoke<PortablePythonStatus>("tools_python_portable_status"),
         invoke<PackIntegrityManifestStatus>("tools_pack_integrity_manifest_status"),
+        invoke<PackIntegrityConfig>("config_pack_integrity_get"),
         invoke<PerformanceTierStatus>("tools_performance_tier_status"),
       ]);
       startTransition(() => {
@@ -933,6 +1057,7 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
         setPython(nextPython);
         setPortablePython(nextPortablePython);
         setIntegrity(nextIntegrity);
+        setPackIntegrityConfig(nextPackIntegrityConfig);
         setPerfTier(nextPerfTier);
         updateSectionStatus("tools", "ready");
       });
@@ -1102,6 +1227,52 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
     loadTraceSection,
   ]);
 
+  useEffect(() => {
+    invoke<SystemPythonInfo | null>("tools_python_detect_system")
+      .then(setSystemPython)
+      .catch(() => undefined);
+  }, []);
+
+  useEffect(() => {
+    let disposed = false;
+    let unlisten: (() => void) | null = null;
+    void listen<Phase2InstallProgress>("phase2_install_progress", (event) => {
+      setPhase2Progress(event.payload);
+      void loadPhase2Section();
+    }).then((u) => {
+      if (disposed) u();
+      else unlisten = u;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [loadPhase2Section]);
+
+  useEffect(() => {
+    let disposed = false;
+    let unlisten: (() => void) | null = null;
+    void listen<ModelDownloadEvent>("model_download_progress", (event) => {
+      const progress = event.payload;
+      setModelDownloads((prev) => ({ ...prev, [progress.model_id]: progress }));
+      if (progress.stage === "installed") void refresh();
+    }).then((u) => {
+      if (disposed) u();
+      else unlisten = u;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, [refresh]);
+
+  function modelDownloadLabel(modelId: string): string | null {
+    const progress = modelDownloads[modelId];
+    if (!progress || progress.stage === "installed") return null;
+    const total = progress.total_bytes ? ` / ${formatBytes(progress.total_bytes)}` : "";
+    return `${progress.stage}: ${formatBytes(progress.downloaded_bytes)}${total}`;
+  }
+
   const modelGroups = useMemo(() => {
     const models = inventory?.models ?? [];
     return {
@@ -1246,8 +1417,8 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
     setError(null);
     setNotice(null);
     try {
-      await invoke("models_install", { modelId });
-      await refresh();
+      await invoke("models_install_async", { modelId });
+      setNotice(`Installing ${modelId} in the background. Progress is shown next to the model.`);
     } catch (e) {
       setError(String(e));
     } finally {
@@ -1283,6 +1454,41 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
     }
   }
 
+  async function checkYtdlpUpdate() {
+    setBusy(true);
+    setError(null);
+    setNotice(null);
+    try {
+      const info = await invoke<YtDlpUpdateInfo>("tools_ytdlp_check_for_update", {
+        forceRefresh: true,
+      });
+      setNotice(
+        info.update_available
+          ? `yt-dlp ${info.latest_version ?? "?"} is available (installed: ${info.installed_version ?? "none"}).`
+          : `yt-dlp is up to date (${info.installed_version ?? "?"}).`,
+      );
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function updateYtdlp() {
+    setBusy(true);
+    setError(null);
+    setNotice("Updating yt-dlp. This may take a minute.");
+    try {
+      const status = await invoke<YtDlpToolsStatus>("tools_ytdlp_update");
+      setNotice(`yt-dlp version: ${status.ytdlp_version ?? "unknown"}.`);
+      await refresh();
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
   async function installJsRuntime() {
     setBusy(true);
     setError(null);
@@ -1297,12 +1503,16 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
     }
   }
 
-  async function installPythonToolchain() {
+  async function installPythonToolchain(useSystemPython = false) {
     setBusy(true);
     setError(null);
-    setNotice("Setting up Python toolchain (creates a venv under app data).");
+    setNotice(
+      useSystemPython
+        ? "Setting up Python toolchain from system Python (creates a venv under app data)."
+        : "Setting up Python toolchain (creates a venv under app data).",
+    );
     try {
-      await invoke<PythonToolchainStatus>("tools_python_install");
+      await invoke<PythonToolchainStatus>("tools_python_install", { useSystemPython });
       await refresh();
     } catch (e) {
       setError(String(e));
@@ -1545,6 +1755,34 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
     }
   }
 
+  async function rollbackPhase2Packs() {
+    const ok = await confirm(
+      "Uninstall Python packages added, and reinstall packages changed, since the last failed Voice cloning install started?\n\nUse this after a failed install left the toolchain half-installed.",
+      { title: "Roll back Voice cloning packages", kind: "warning" },
+    );
+    if (!ok) return;
+
+    setBusy(true);
+    setError(null);
+    setNotice("Rolling back Voice cloning packages...");
+    try {
+      const summary = await invoke<{
+        snapshot_path: string;
+        steps: { package: string; action: string; ok: boolean; error: string | null }[];
+      }>("tools_phase2_packs_rollback");
+      const failed = summary.steps.filter((step) => !step.ok);
+      setNotice(
+        `Restored ${summary.steps.length - failed.length} package(s)` +
+          (failed.length ? `; ${failed.length} failed (${failed.map((s) => s.package).join(", ")})` : "."),
+      );
+      await refresh();
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
   async function generateIntegrityManifest() {
     setBusy(true);
     setError(null);
@@ -1569,6 +1807,21 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
     }
   }
 
+  async function setVerifyPackIntegrityOnJobStart(enabled: boolean) {
+    setBusy(true);
+    setError(null);
+    try {
+      const saved = await invoke<PackIntegrityConfig>("config_pack_integrity_set", {
+        configValue: { verify_pack_integrity_on_job_start: enabled },
+      });
+      setPackIntegrityConfig(saved);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
   async function revealPath(path: string) {
     setError(null);
     const trimmed = (path ?? "").trim();
@@ -1592,7 +1845,14 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
         rules: batchRules,
       });
       setBatchRules(saved);
-      setNotice("Saved batch-on-import rules.");
+      const warnings = await invoke<string[]>("config_batch_on_import_warnings", { rules: saved }).catch(
+        () => [] as string[],
+      );
+      setNotice(
+        warnings.length > 0
+          ? `Saved batch-on-import rules. Warning: ${warnings.join(" ")}`
+          : "Saved batch-on-import rules.",
+      );
       await refresh();
     } catch (e) {
       setError(String(e));
@@ -1691,6 +1951,19 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
     }
   }
 
+  async function checkDbIntegrity() {
+    setError(null);
+    setBusy(true);
+    try {
+      const result = await invoke<DbIntegrityResult>("diagnostics_db_integrity_check");
+      setDbIntegrityResult(result);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
   async function revealDbFile() {
     setError(null);
     if (!info?.db_path) return;
@@ -1825,6 +2098,57 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
     }
   }
 
+  async function cleanOrphans() {
+    setBusy(true);
+    setError(null);
+    setNotice(null);
+    try {
+      const preview = await invoke<OrphanCleanupResult>("diagnostics_clean_orphans", { dryRun: true });
+      const total = preview.removed_dirs.length + preview.removed_log_files.length;
+      if (!total) {
+        setNotice("No orphaned derived folders or job logs found.");
+        return;
+      }
+      const ok = await confirm(
+        `Remove ${preview.removed_dirs.length} orphaned derived folder(s) and ${preview.removed_log_files.length} orphaned job log(s) (${formatBytes(preview.freed_bytes)})?\n\nThese have no matching library item or job. Library media is not touched.`,
+        { title: "Clean orphaned files", kind: "warning" },
+      );
+      if (!ok) return;
+      const result = await invoke<OrphanCleanupResult>("diagnostics_clean_orphans", { dryRun: false });
+      setNotice(
+        `Removed ${result.removed_dirs.length} folder(s) and ${result.removed_log_files.length} log(s) (${formatBytes(result.freed_bytes)}).` +
+          (result.failed_paths.length ? ` ${result.failed_paths.length} path(s) could not be removed.` : ""),
+      );
+      await refresh();
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function clearCacheSelective() {
+    setBusy(true);
+    setError(null);
+    setNotice(null);
+    try {
+      const summary = await invoke<CacheClearSummary>("diagnostics_clear_cache_selective", {
+        options: cacheClearOptions,
+      });
+      const parts = Object.entries(summary.category_bytes ?? {}).map(
+        ([category, bytes]) => `${category}: ${formatBytes(bytes)}`,
+      );
+      setNotice(
+        `Cleared ${formatBytes(summary.removed_bytes)} of selected caches${parts.length ? ` (${parts.join(", ")})` : ""}.`,
+      );
+      await refresh();
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
   async function clearThumbnailCache() {
     const ok = await confirm(
       "Clear thumbnail cache files? This will not delete library media or metadata.",
@@ -1908,7 +2232,7 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
           } satisfies JobCleanupOptions,
         });
         setNotice(
-          `Flushed ${summary.removed_jobs} jobs, kept ${summary.kept_jobs_due_to_failures} job${summary.kept_jobs_due_to_failures === 1 ? "" : "s"} due to cleanup failures, removed ${summary.removed_log_files} log files, ${summary.removed_artifact_dirs} artifact folders, ${summary.removed_managed_output_dirs} managed output folders, ${summary.removed_external_output_dirs} external output folders, and ${summary.removed_cache_entries} cache entries.`,
+          `Flushed ${summary.removed_jobs} jobs, kept ${summary.kept_jobs_due_to_failures} job${summary.kept_jobs_due_to_failures === 1 ? "" : "s"} due to cleanup failures, removed ${summary.removed_log_files} log files, ${summary.removed_artifact_dirs} artifact folders, ${summary.removed_managed_output_dirs} managed output folders, ${summary.removed_external_output_dirs} external output folders, and ${summary.removed_cache_entries} cache entries (${formatBytes(summary.freed_bytes)} freed).`,
         );
         if (summary.failed_paths.length > 0) {
           const detail = summary.failed_paths
@@ -1941,8 +2265,13 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
     setError(null);
     setNotice(null);
     try {
-      await invoke("jobs_prune_logs");
-      setNotice("Pruned job logs.");
+      const summary = await invoke<{
+        pruned_log_files: number;
+        pruned_orphan_artifact_dirs: number;
+      }>("jobs_prune_logs");
+      setNotice(
+        `Pruned ${summary.pruned_log_files} job log file(s) and ${summary.pruned_orphan_artifact_dirs} orphaned artifact folder(s).`,
+      );
       await refresh();
     } catch (e) {
       setError(String(e));
@@ -1951,6 +2280,25 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
     }
   }
 
+  async function saveJobLogRetentionPolicy() {
+    if (!policyDraft) return;
+    setBusy(true);
+    setError(null);
+    setNotice(null);
+    try {
+      const next = await invoke<JobLogRetentionPolicy>("jobs_log_retention_policy_set", {
+        policy: policyDraft,
+      });
+      setPolicy(next);
+      setPolicyDraft(null);
+      setNotice("Saved job log caps.");
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
   async function exportDiagnosticsBundle() {
     const stamp = new Date().toISOString().replace(/[:.]/g, "-");
     const outPath = await save({
@@ -2237,10 +2585,28 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
           <div className="k">App data dir</div>
           <div className="v">{info?.app_data_dir ?? "-"}</div>
         </div>
+        <div className="kv">
+          <div className="k">Portable mode</div>
+          <div className="v">{info ? (info.is_portable ? "yes" : "no") : "-"}</div>
+        </div>
+        <div className="kv">
+          <div className="k">Data dir source</div>
+          <div className="v">{info?.data_dir_source ?? "-"}</div>
+        </div>
         <div className="kv">
           <div className="k">DB path</div>
           <div className="v">{info?.db_path ?? "-"}</div>
         </div>
+        <div className="kv">
+          <div className="k">DB integrity</div>
+          <div className="v">
+            {dbIntegrityResult
+              ? dbIntegrityResult.ok
+                ? `ok (${dbIntegrityResult.pages_checked} pages)`
+                : `${dbIntegrityResult.issues.length} issue(s): ${dbIntegrityResult.issues.slice(0, 5).join("; ")}`
+              : (info?.db_integrity ?? "-")}
+          </div>
+        </div>
         <div className="row">
           <button type="button" disabled={busy || !info?.app_data_dir} onClick={openAppDataDir}>
             Open app data folder
@@ -2248,6 +2614,9 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
           <button type="button" disabled={busy || !info?.db_path} onClick={revealDbFile}>
             Reveal DB file
           </button>
+          <button type="button" disabled={busy} onClick={checkDbIntegrity}>
+            Check DB integrity
+          </button>
           <button type="button" disabled={busy} onClick={() => refresh()}>
             Refresh
           </button>
@@ -2310,6 +2679,9 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
               <div className="k">Roots</div>
               <div className="v">
                 Download root: {appStateSnapshot.download_roots.current_dir}
+                {appStateSnapshot.download_roots.disk_free_gb !== null
+                  ? ` (${appStateSnapshot.download_roots.disk_free_gb.toFixed(1)} GB free)`
+                  : ""}
               </div>
             </div>
             <div className="kv">
@@ -3073,6 +3445,12 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
           >
             Install yt-dlp
           </button>
+          <button type="button" disabled={busy} onClick={checkYtdlpUpdate}>
+            Check yt-dlp update
+          </button>
+          <button type="button" disabled={busy} onClick={updateYtdlp}>
+            Update yt-dlp
+          </button>
           <button
             type="button"
             disabled={busy || !!jsRuntime?.bundled_deno_installed}
@@ -3083,10 +3461,21 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
           <button
             type="button"
             disabled={busy || !!python?.venv_exists}
-            onClick={installPythonToolchain}
+            onClick={() => void installPythonToolchain()}
           >
             Setup Python toolchain
           </button>
+          {systemPython ? (
+            <button
+              type="button"
+              disabled={busy || !!python?.venv_exists}
+              onClick={() => void installPythonToolchain(true)}
+              title={systemPython.path}
+            >
+              Setup Python toolchain from system {systemPython.version}
+              {systemPython.is_venv ? " (venv)" : ""}
+            </button>
+          ) : null}
           <button
             type="button"
             disabled={busy || !!portablePython?.installed}
@@ -3153,6 +3542,9 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
           <button type="button" disabled={busy} onClick={enqueueInstallPhase2Packs}>
             Install Voice cloning packages
           </button>
+          <button type="button" disabled={busy} onClick={rollbackPhase2Packs}>
+            Roll back last install
+          </button>
           <button type="button" disabled={busy} onClick={() => refresh()}>
             Refresh
           </button>
@@ -3174,6 +3566,16 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
           <div className="k">Live progress</div>
           <div className="v">{phase2HasActive ? "updating..." : phase2HasProblem ? "interrupted" : "idle"}</div>
         </div>
+        {phase2Progress ? (
+          <div className="kv">
+            <div className="k">Current step</div>
+            <div className="v">
+              {phase2Progress.current_step_name} ({phase2Progress.current_step_status}, step{" "}
+              {Math.min(phase2Progress.step_index + 1, phase2Progress.total_steps)} of{" "}
+              {phase2Progress.total_steps})
+            </div>
+          </div>
+        ) : null}
 
         <div className="table-wrap">
           <table>
@@ -3257,6 +3659,7 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
                   auto_separate: prev?.auto_separate ?? false,
                   auto_diarize: prev?.auto_diarize ?? false,
                   auto_dub_preview: prev?.auto_dub_preview ?? false,
+                  auto_diarize_backend: prev?.auto_diarize_backend ?? null,
                 }))
               }
             />
@@ -3274,6 +3677,7 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
                   auto_separate: prev?.auto_separate ?? false,
                   auto_diarize: prev?.auto_diarize ?? false,
                   auto_dub_preview: prev?.auto_dub_preview ?? false,
+                  auto_diarize_backend: prev?.auto_diarize_backend ?? null,
                 }))
               }
             />
@@ -3291,6 +3695,7 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
                   auto_separate: e.currentTarget.checked,
                   auto_diarize: prev?.auto_diarize ?? false,
                   auto_dub_preview: prev?.auto_dub_preview ?? false,
+                  auto_diarize_backend: prev?.auto_diarize_backend ?? null,
                 }))
               }
             />
@@ -3308,11 +3713,27 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
                   auto_separate: prev?.auto_separate ?? false,
                   auto_diarize: e.currentTarget.checked,
                   auto_dub_preview: prev?.auto_dub_preview ?? false,
+                  auto_diarize_backend: prev?.auto_diarize_backend ?? null,
                 }))
               }
             />
             <span>Auto diarize</span>
           </label>
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <span>Diarize backend</span>
+            <select
+              value={batchRules?.auto_diarize_backend ?? ""}
+              disabled={busy || !batchRules}
+              onChange={(e) => {
+                const value = e.currentTarget.value;
+                setBatchRules((prev) => (prev ? { ...prev, auto_diarize_backend: value || null } : prev));
+              }}
+            >
+              <option value="">Default (baseline)</option>
+              <option value="baseline">baseline</option>
+              <option value="pyannote_byo_v1">pyannote_byo_v1</option>
+            </select>
+          </label>
           <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
             <input
               type="checkbox"
@@ -3325,6 +3746,7 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
                   auto_separate: prev?.auto_separate ?? false,
                   auto_diarize: prev?.auto_diarize ?? false,
                   auto_dub_preview: e.currentTarget.checked,
+                  auto_diarize_backend: prev?.auto_diarize_backend ?? null,
                 }))
               }
             />
@@ -3356,6 +3778,16 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
           <div className="k">Token present</div>
           <div className="v">{diarizationOptional?.token_present ? "yes" : "no"}</div>
         </div>
+        {diarizationOptional?.config.backend === "pyannote_byo_v1" ? (
+          <div className="kv">
+            <div className="k">pyannote.audio importable</div>
+            <div className="v">
+              {diarizationOptional.pyannote_importable
+                ? "yes"
+                : `no${diarizationOptional.pyannote_import_error ? ` (${diarizationOptional.pyannote_import_error})` : ""}`}
+            </div>
+          </div>
+        ) : null}
         <div className="kv">
           <div className="k">Config path</div>
           <div className="v">{diarizationOptional?.config_path ?? "-"}</div>
@@ -3537,6 +3969,15 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
             Reveal manifest
           </button>
         </div>
+        <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+          <input
+            type="checkbox"
+            checked={packIntegrityConfig?.verify_pack_integrity_on_job_start ?? false}
+            disabled={busy}
+            onChange={(e) => void setVerifyPackIntegrityOnJobStart(e.currentTarget.checked)}
+          />
+          Verify pack files against the manifest before separation, diarization, and TTS jobs
+        </label>
 
         <div style={{ marginTop: 16 }} />
 
@@ -3548,6 +3989,22 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
           <div className="k">GPUs</div>
           <div className="v">{perfTier?.gpu_names?.length ? perfTier.gpu_names.join(", ") : "-"}</div>
         </div>
+        <div className="kv">
+          <div className="k">GPU memory</div>
+          <div className="v">{perfTier?.gpu_vram_mb ? `${perfTier.gpu_vram_mb} MiB` : "-"}</div>
+        </div>
+        <div className="kv">
+          <div className="k">Metal available</div>
+          <div className="v">{perfTier ? (perfTier.metal_available ? "yes" : "no") : "-"}</div>
+        </div>
+        <div className="kv">
+          <div className="k">CPU cores</div>
+          <div className="v">{perfTier?.cpu_cores ?? "-"}</div>
+        </div>
+        <div className="kv">
+          <div className="k">Recommended max concurrency</div>
+          <div className="v">{perfTier?.recommended_max_concurrency ?? "-"}</div>
+        </div>
         <div className="kv">
           <div className="k">Torch CUDA available</div>
           <div className="v">
@@ -3581,6 +4038,16 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
         <div className="kv">
           <div className="k">Last report</div>
           <div className="v">{licensingReport?.out_path ?? "-"}</div>
+          <div className="k">Python packages</div>
+          <div className="v">
+            {!licensingReport
+              ? "-"
+              : licensingReport.python_unavailable
+                ? "Python venv not available"
+                : `${licensingReport.python_packages.length} scanned, ${
+                    licensingReport.python_packages.filter((p) => p.license === "UNKNOWN").length
+                  } with unknown license`}
+          </div>
         </div>
         <div className="row" style={{ flexWrap: "wrap" }}>
           <button type="button" disabled={busy} onClick={generateLicensingReport}>
@@ -3645,11 +4112,30 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
           <div className="k">Total</div>
           <div className="v">{storage ? formatBytes(storage.total_bytes) : "-"}</div>
         </div>
+        <div className="kv">
+          <div className="k">Orphaned</div>
+          <div className="v">
+            {storage
+              ? `${storage.orphaned_item_dirs?.length ?? 0} folder(s), ${storage.orphaned_job_log_files?.length ?? 0} job log(s)`
+              : "-"}
+          </div>
+        </div>
+        {(storage?.per_platform ?? []).map((entry) => (
+          <div className="kv" key={entry.platform}>
+            <div className="k">Source: {entry.platform}</div>
+            <div className="v">
+              {entry.item_count} item(s), {formatBytes(entry.media_bytes)}
+            </div>
+          </div>
+        ))}
 
         <div className="row">
           <button type="button" disabled={busy} onClick={() => refresh()}>
             Refresh
           </button>
+          <button type="button" disabled={busy} onClick={cleanOrphans}>
+            Clean orphaned files
+          </button>
           <button type="button" disabled={busy} onClick={clearCache}>
             Clear cache
           </button>
@@ -3663,6 +4149,29 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
             Prune job logs
           </button>
         </div>
+        <div className="row" style={{ flexWrap: "wrap" }}>
+          {CACHE_CLEAR_CATEGORIES.map(([key, label]) => (
+            <label key={key} style={{ display: "flex", alignItems: "center", gap: 8 }}>
+              <input
+                type="checkbox"
+                checked={cacheClearOptions[key]}
+                disabled={busy}
+                onChange={(e) => {
+                  const checked = e.currentTarget.checked;
+                  setCacheClearOptions((prev) => ({ ...prev, [key]: checked }));
+                }}
+              />
+              <span>{label}</span>
+            </label>
+          ))}
+          <button
+            type="button"
+            disabled={busy || !Object.values(cacheClearOptions).some(Boolean)}
+            onClick={clearCacheSelective}
+          >
+            Clear selected caches
+          </button>
+        </div>
 
         <div className="kv">
           <div className="k">Job log caps</div>
@@ -3672,6 +4181,53 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
               : "-"}
           </div>
         </div>
+        {policy ? (
+          <div className="row" style={{ flexWrap: "wrap" }}>
+            {(
+              [
+                ["rotate_bytes", "Rotate (MB)", 1024 * 1024],
+                ["max_backups", "Backups", 1],
+                ["max_age_days", "Max age (days)", 1],
+                ["total_cap_bytes", "Total cap (MB)", 1024 * 1024],
+              ] as const
+            ).map(([key, label, unit]) => (
+              <label key={key} style={{ display: "flex", alignItems: "center", gap: 8 }}>
+                <span>{label}</span>
+                <input
+                  type="number"
+                  min={0}
+                  style={{ width: 90 }}
+                  value={Math.round((policyDraft ?? policy)[key] / unit)}
+                  disabled={busy}
+                  onChange={(e) => {
+                    const value = Math.max(0, Math.round(Number(e.currentTarget.value) || 0));
+                    setPolicyDraft((prev) => ({ ...(prev ?? policy), [key]: value * unit }));
+                  }}
+                />
+              </label>
+            ))}
+            <button
+              type="button"
+              disabled={busy || !policyDraft}
+              onClick={() => void saveJobLogRetentionPolicy()}
+            >
+              Save job log caps
+            </button>
+          </div>
+        ) : null}
+        {policy && policy.overrides.length > 0 ? (
+          <div className="kv">
+            <div className="k">Job log overrides</div>
+            <div className="v">
+              {policy.overrides
+                .map(
+                  (o) =>
+                    `${o.job_type}: ${o.max_age_days !== null ? `age ${o.max_age_days}d` : "default age"}, ${o.max_backups !== null ? `keep ${o.max_backups} backups` : "default backups"}`,
+                )
+                .join("; ")}
+            </div>
+          </div>
+        ) : null}
         <div className="kv">
           <div className="k">Derived artifact policy</div>
           <div className="v">
@@ -3850,6 +4406,11 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
                       >
                         {modelInstallActionLabel(m)}
                       </button>
+                      {modelDownloadLabel(m.id) ? (
+                        <div style={{ color: "#4b5563", fontSize: 12, marginTop: 4 }}>
+                          {modelDownloadLabel(m.id)}
+                        </div>
+                      ) : null}
                     </td>
                   </tr>
                 ))
@@ -3909,6 +4470,11 @@ export function DiagnosticsPage({ visible = true }: { visible?: boolean }) {
                         <button type="button" disabled={busy} onClick={() => installModel(m.id)}>
                           {modelInstallActionLabel(m)}
                         </button>
+                        {modelDownloadLabel(m.id) ? (
+                          <div style={{ color: "#4b5563", fontSize: 12, marginTop: 4 }}>
+                            {modelDownloadLabel(m.id)}
+                          </div>
+                        ) : null}
                       </td>
                     </tr>
                   ))}
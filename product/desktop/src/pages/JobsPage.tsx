@@ -1,5 +1,6 @@
 import { Fragment, useCallback, useEffect, useMemo, useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
+import { listen } from "@tauri-apps/api/event";
 import { confirm, save } from "@tauri-apps/plugin-dialog";
 import { usePageActivity, usePollingLoop } from "../lib/activity";
 import { copyPathToClipboard, openPathBestEffort, requireOpenablePath, revealPath } from "../lib/pathOpener";
@@ -19,6 +20,10 @@ type JobRow = {
   finished_at_ms: number | null;
   logs_path: string;
   params_json?: string;
+  requeue_count?: number;
+  content_key?: string | null;
+  not_before_ms?: number | null;
+  estimate_remaining_ms?: number | null;
 };
 
 type LibraryItem = {
@@ -65,6 +70,32 @@ type JobRuntimeSettings = {
   max_concurrency: number;
 };
 
+type JobRuntimeState = {
+  running_count: number;
+  queued_count: number;
+  paused: boolean;
+  max_concurrency: number;
+};
+
+type JobRuntimeStateEvent = {
+  running_count: number;
+  timestamp_ms: number;
+};
+
+type BatchCompletedEvent = {
+  batch_id: string;
+  total_jobs: number;
+  succeeded: number;
+  failed: number;
+  canceled: number;
+};
+
+type JobFailedEvent = {
+  job_id: string;
+  error: string;
+  final_progress: number | null;
+};
+
 type JobCleanupOutputTarget = {
   path: string;
   source_job_ids: string[];
@@ -101,6 +132,14 @@ type JobCleanupSummary = {
   skipped_external_output_dirs: number;
   removed_cache_entries: number;
   failed_paths: JobCleanupFailure[];
+  dry_run: boolean;
+  would_remove_bytes: number;
+  available_to_remove_bytes: number;
+  freed_log_bytes: number;
+  freed_artifact_bytes: number;
+  freed_output_bytes: number;
+  freed_cache_bytes: number;
+  freed_bytes: number;
 };
 
 type FfmpegToolsStatus = {
@@ -158,6 +197,19 @@ function joinPath(dir: string, file: string): string {
   return d && f ? `${d}${sep}${f}` : d || f;
 }
 
+function formatBytes(bytes: number): string {
+  if (!Number.isFinite(bytes)) return "-";
+  if (bytes < 1024) return `${bytes} B`;
+  const units = ["KB", "MB", "GB", "TB"] as const;
+  let value = bytes / 1024;
+  let unitIndex = 0;
+  while (value >= 1024 && unitIndex < units.length - 1) {
+    value /= 1024;
+    unitIndex += 1;
+  }
+  return `${value.toFixed(1)} ${units[unitIndex]}`;
+}
+
 function formatTs(ms: number | null): string {
   if (!ms) return "-";
   try {
@@ -167,6 +219,14 @@ function formatTs(ms: number | null): string {
   }
 }
 
+function formatRemaining(ms: number): string {
+  const seconds = Math.max(1, Math.round(ms / 1000));
+  if (seconds < 60) return `~${seconds}s remaining`;
+  const minutes = Math.round(seconds / 60);
+  if (minutes < 60) return `~${minutes} min remaining`;
+  return `~${(minutes / 60).toFixed(1)} h remaining`;
+}
+
 function isActive(status: JobStatus): boolean {
   return status === "queued" || status === "running";
 }
@@ -286,6 +346,7 @@ export function JobsPage({ visible = true }: { visible?: boolean }) {
   const [dummySeconds, setDummySeconds] = useState(10);
   const [queuePaused, setQueuePaused] = useState(false);
   const [maxConcurrency, setMaxConcurrency] = useState(4);
+  const [runtimeState, setRuntimeState] = useState<JobRuntimeState | null>(null);
 
   async function handlePathOpenFailure(path: string, error: unknown, actionLabel: string) {
     const copied = await copyPathToClipboard(path);
@@ -294,16 +355,18 @@ export function JobsPage({ visible = true }: { visible?: boolean }) {
   }
 
   const refresh = useCallback(async () => {
-    const [next, control, runtime, youtubeSubscriptions, instagramSubscriptions] = await Promise.all([
+    const [next, control, runtime, runtimeSnapshot, youtubeSubscriptions, instagramSubscriptions] = await Promise.all([
       invoke<JobRow[]>("jobs_list", { limit: 200, offset: 0 }),
       invoke<JobQueueControlState>("jobs_queue_control_get"),
       invoke<JobRuntimeSettings>("jobs_runtime_settings_get"),
+      invoke<JobRuntimeState>("jobs_runtime_state_get").catch(() => null),
       invoke<YoutubeSubscriptionRow[]>("youtube_subscriptions_list").catch(() => []),
       invoke<InstagramSubscriptionRow[]>("instagram_subscriptions_list").catch(() => []),
     ]);
     setJobs(next);
     setQueuePaused(control.paused);
     setMaxConcurrency(runtime.max_concurrency);
+    setRuntimeState(runtimeSnapshot);
     setYoutubeSubscriptionsById(
       Object.fromEntries(youtubeSubscriptions.map((subscription) => [subscription.id, subscription])),
     );
@@ -317,6 +380,45 @@ export function JobsPage({ visible = true }: { visible?: boolean }) {
     refresh().catch((e) => setError(String(e)));
   }, [pageActive, refresh]);
 
+  useEffect(() => {
+    let disposed = false;
+    let unlisten: (() => void) | null = null;
+    void listen<JobRuntimeStateEvent>("runtime_state_changed", (event) => {
+      setRuntimeState((prev) => (prev ? { ...prev, running_count: event.payload.running_count } : prev));
+    }).then((u) => {
+      if (disposed) u();
+      else unlisten = u;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+    };
+  }, []);
+
+  useEffect(() => {
+    if (!pageActive) return;
+    let disposed = false;
+    let unlisten: (() => void) | null = null;
+    let unlistenFailed: (() => void) | null = null;
+    void listen<BatchCompletedEvent>("batch_completed", () => {
+      refresh().catch((e) => setError(String(e)));
+    }).then((u) => {
+      if (disposed) u();
+      else unlisten = u;
+    });
+    void listen<JobFailedEvent>("job_failed", () => {
+      refresh().catch((e) => setError(String(e)));
+    }).then((u) => {
+      if (disposed) u();
+      else unlistenFailed = u;
+    });
+    return () => {
+      disposed = true;
+      unlisten?.();
+      unlistenFailed?.();
+    };
+  }, [pageActive, refresh]);
+
   useEffect(() => {
     invoke<DiagnosticsInfo>("diagnostics_info")
       .then((info) => setAppDataDir(info.app_data_dir ?? ""))
@@ -621,12 +723,21 @@ export function JobsPage({ visible = true }: { visible?: boolean }) {
     setError(null);
     setNotice(null);
     try {
-      await Promise.all(
-        retryableIds.map((jobId) =>
-          invoke("jobs_retry", { jobId, job_id: jobId }),
-        ),
-      );
-      setNotice(`Retried ${retryableIds.length} job${retryableIds.length === 1 ? "" : "s"} in batch.`);
+      let retriedCount = retryableIds.length;
+      if (group.batchId) {
+        const retried = await invoke<JobRow[]>("jobs_retry_batch", {
+          batchId: group.batchId,
+          preserveBatch: true,
+        });
+        retriedCount = retried.length;
+      } else {
+        await Promise.all(
+          retryableIds.map((jobId) =>
+            invoke("jobs_retry", { jobId, job_id: jobId }),
+          ),
+        );
+      }
+      setNotice(`Retried ${retriedCount} job${retriedCount === 1 ? "" : "s"} in batch.`);
       await refresh();
     } catch (e) {
       setError(String(e));
@@ -650,11 +761,11 @@ export function JobsPage({ visible = true }: { visible?: boolean }) {
     }
   }
 
-  async function openJobArtifactsDir(jobId: string) {
+  async function openJobArtifactsDir(job: JobRow) {
     if (!appDataDir) return;
 
     const derivedDir = joinPath(appDataDir, "derived");
-    const artifactsDir = joinPath(joinPath(derivedDir, "jobs"), jobId);
+    const artifactsDir = joinPath(joinPath(derivedDir, "jobs"), job.content_key || job.id);
 
     setError(null);
     try {
@@ -918,7 +1029,7 @@ export function JobsPage({ visible = true }: { visible?: boolean }) {
           } satisfies JobCleanupOptions,
         });
         setNotice(
-          `Flushed ${summary.removed_jobs} jobs, kept ${summary.kept_jobs_due_to_failures} job${summary.kept_jobs_due_to_failures === 1 ? "" : "s"} due to cleanup failures, removed ${summary.removed_log_files} log files, ${summary.removed_artifact_dirs} artifact folders, ${summary.removed_managed_output_dirs} managed output folders, ${summary.removed_external_output_dirs} external output folders, and ${summary.removed_cache_entries} cache entries.`,
+          `Flushed ${summary.removed_jobs} jobs, kept ${summary.kept_jobs_due_to_failures} job${summary.kept_jobs_due_to_failures === 1 ? "" : "s"} due to cleanup failures, removed ${summary.removed_log_files} log files, ${summary.removed_artifact_dirs} artifact folders, ${summary.removed_managed_output_dirs} managed output folders, ${summary.removed_external_output_dirs} external output folders, and ${summary.removed_cache_entries} cache entries (${formatBytes(summary.freed_bytes)} freed).`,
         );
         if (summary.failed_paths.length > 0) {
           const detail = summary.failed_paths
@@ -970,7 +1081,9 @@ export function JobsPage({ visible = true }: { visible?: boolean }) {
       job.job_type === "mux_dub_preview_v1" &&
       Boolean(job.item_id);
     const derivedDir = appDataDir ? joinPath(appDataDir, "derived") : "";
-    const artifactsDir = derivedDir ? joinPath(joinPath(derivedDir, "jobs"), job.id) : "";
+    const artifactsDir = derivedDir
+      ? joinPath(joinPath(derivedDir, "jobs"), job.content_key || job.id)
+      : "";
     const outputsDir =
       derivedDir && job.item_id
         ? joinPath(joinPath(derivedDir, "items"), job.item_id)
@@ -987,6 +1100,11 @@ export function JobsPage({ visible = true }: { visible?: boolean }) {
           {nested ? "\u251C\u2500 " : ""}
           {job.status}
           {job.error ? `: ${job.error}` : ""}
+          {job.status === "queued" && job.not_before_ms && job.not_before_ms > Date.now() ? (
+            <div style={{ color: "#4b5563", fontSize: 12 }}>
+              Scheduled for {formatTs(job.not_before_ms)}
+            </div>
+          ) : null}
         </td>
         <td title={job.id}>
           <code>{job.item_id ? job.item_id.slice(0, 8) : job.id.slice(0, 8)}</code>
@@ -1000,7 +1118,15 @@ export function JobsPage({ visible = true }: { visible?: boolean }) {
           ) : null}
         </td>
         <td>{job.job_type}</td>
-        <td>{Math.round((job.progress ?? 0) * 100)}%</td>
+        <td>
+          {job.status === "failed" && (job.progress ?? 0) > 0 ? "Failed at " : null}
+          {Math.round((job.progress ?? 0) * 100)}%
+          {job.status === "running" && job.estimate_remaining_ms != null ? (
+            <div style={{ color: "#4b5563", fontSize: 12 }}>
+              {formatRemaining(job.estimate_remaining_ms)}
+            </div>
+          ) : null}
+        </td>
         <td>{formatTs(job.created_at_ms)}</td>
         <td>{formatTs(job.started_at_ms)}</td>
         <td>{formatTs(job.finished_at_ms)}</td>
@@ -1073,7 +1199,7 @@ export function JobsPage({ visible = true }: { visible?: boolean }) {
                   </button>
                 ) : null}
                 {canOpenArtifacts ? (
-                  <button type="button" disabled={busy} onClick={() => openJobArtifactsDir(job.id)}>
+                  <button type="button" disabled={busy} onClick={() => openJobArtifactsDir(job)}>
                     Open artifacts
                   </button>
                 ) : null}
@@ -1183,6 +1309,12 @@ export function JobsPage({ visible = true }: { visible?: boolean }) {
 
       <div className="card">
         <h2>Queue</h2>
+        {runtimeState ? (
+          <div style={{ color: "#4b5563", fontSize: 12 }}>
+            Running {runtimeState.running_count} of {runtimeState.max_concurrency} slots,{" "}
+            {runtimeState.queued_count} queued{runtimeState.paused ? " (paused)" : ""}
+          </div>
+        ) : null}
         <div className="table-wrap">
           <table>
             <thead>
@@ -24,6 +24,16 @@ type LibraryItem = {
   video_codec: string | null;
   audio_codec: string | null;
   thumbnail_path: string | null;
+  file_size_bytes: number | null;
+  media_type: "video" | "audio" | "unknown";
+  source_platform?: "youtube" | "instagram" | "direct_http" | "local_file" | null;
+};
+
+type LibraryListPage = {
+  items: LibraryItem[];
+  total_count: number;
+  offset: number;
+  limit: number;
 };
 
 const thumbnailDataUrlCache = new Map<string, string>();
@@ -169,6 +179,13 @@ function inferSubscriptionType(url: string): "Channel" | "Shorts" | "Playlist" |
   return "URL";
 }
 
+function splitKeywordList(raw: string): string[] {
+  return raw
+    .split(",")
+    .map((v) => v.trim())
+    .filter(Boolean);
+}
+
 function describeRecurringTarget(
   outputDirOverride: string | null,
   defaultRoot: string,
@@ -303,10 +320,17 @@ type YoutubeSubscriptionRow = {
   refresh_interval_minutes: number;
   last_queued_at_ms: number | null;
   last_error_at_ms: number | null;
+  last_error?: string | null;
   consecutive_failures: number;
   next_allowed_refresh_at_ms: number | null;
   created_at_ms: number;
   updated_at_ms: number;
+  filter_keywords_exclude: string[];
+  filter_keywords_include: string[] | null;
+  max_items_per_refresh: number | null;
+  auto_max_items: boolean;
+  avg_posts_per_day: number | null;
+  last_refreshed_at_ms: number | null;
 };
 
 type YoutubeSubscriptionUpsert = {
@@ -322,6 +346,10 @@ type YoutubeSubscriptionUpsert = {
   preset_id: string | null;
   group_ids: string[];
   refresh_interval_minutes: number | null;
+  filter_keywords_exclude: string[];
+  filter_keywords_include: string[] | null;
+  max_items_per_refresh: number | null;
+  auto_max_items: boolean;
 };
 
 type YoutubeSubscriptionGroupRow = {
@@ -447,7 +475,9 @@ type YoutubeSubscriptionsExportSummary = {
 type YoutubeSubscriptionsImportSummary = {
   total_in_file: number;
   inserted: number;
-  updated: number;
+  skipped_duplicates: number;
+  skipped_invalid: number;
+  validation_errors: string[];
 };
 
 type YoutubeSubscriptionsImport4kvdpSummary = {
@@ -508,6 +538,7 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
   const [items, setItems] = useState<LibraryItem[]>([]);
   const [itemsOffset, setItemsOffset] = useState(0);
   const [itemsHasMore, setItemsHasMore] = useState(true);
+  const [itemsTotal, setItemsTotal] = useState<number | null>(null);
   const [itemsLoadingMore, setItemsLoadingMore] = useState(false);
   const [subscriptions, setSubscriptions] = useState<YoutubeSubscriptionRow[]>([]);
   const [instagramSubscriptions, setInstagramSubscriptions] = useState<InstagramSubscriptionRow[]>(
@@ -530,6 +561,8 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
     return "auto";
   });
   const [urlBatchText, setUrlBatchText] = useState("");
+  const [urlBatchScheduleAt, setUrlBatchScheduleAt] = useState("");
+  const [imageBatchScheduleAt, setImageBatchScheduleAt] = useState("");
   const [urlBatchAuthCookie, setUrlBatchAuthCookie] = useState("");
   const [urlBatchOutputDir, setUrlBatchOutputDir] = useState(() => {
     return safeLocalStorageGet("voxvulgi.v1.library.url_batch_output_dir") ?? "";
@@ -618,6 +651,11 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
       "avatar profile userpic gravatar"
     );
   });
+  const [imageBatchExtensions, setImageBatchExtensions] = useState(() => {
+    return safeLocalStorageGet("voxvulgi.v1.library.image_batch_extensions") ?? "";
+  });
+  const [imageBatchMinPixels, setImageBatchMinPixels] = useState("");
+  const [imageBatchMaxPixels, setImageBatchMaxPixels] = useState("");
   const [imageBatchOutputDir, setImageBatchOutputDir] = useState(() => {
     return safeLocalStorageGet("voxvulgi.v1.library.image_batch_output_dir") ?? "";
   });
@@ -646,6 +684,10 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
   const [subscriptionPresetId, setSubscriptionPresetId] = useState<string>("");
   const [subscriptionGroupIds, setSubscriptionGroupIds] = useState<string[]>([]);
   const [subscriptionGroupFilterId, setSubscriptionGroupFilterId] = useState<string>("");
+  const [subscriptionExcludeKeywords, setSubscriptionExcludeKeywords] = useState("");
+  const [subscriptionIncludeKeywords, setSubscriptionIncludeKeywords] = useState("");
+  const [subscriptionMaxItemsPerRefresh, setSubscriptionMaxItemsPerRefresh] = useState("");
+  const [subscriptionAutoMaxItems, setSubscriptionAutoMaxItems] = useState(false);
   const [subscriptionRefreshIntervalMinutes, setSubscriptionRefreshIntervalMinutes] = useState(() => {
     const raw = safeLocalStorageGet("voxvulgi.v1.library.youtube_subscription_refresh_interval_minutes");
     const parsed = raw ? Number(raw) : NaN;
@@ -903,7 +945,7 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
     const wantsInstagram = showInstagramArchive;
     const wantsBatchRules = showImportControls;
     const [
-      nextItems,
+      nextPage,
       nextRules,
       nextSubscriptions,
       nextGroups,
@@ -913,11 +955,11 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
       nextActiveRefreshIds,
     ] = await Promise.all([
       wantsItems
-        ? invoke<LibraryItem[]>("library_list", {
+        ? invoke<LibraryListPage>("library_list", {
             limit: wantsInstagram && !showMediaLibrary ? 160 : libraryPageSize,
             offset: 0,
           })
-        : Promise.resolve([] as LibraryItem[]),
+        : Promise.resolve(null),
       wantsBatchRules
         ? invoke<BatchOnImportRules>("config_batch_on_import_get").catch(() => null)
         : Promise.resolve(null),
@@ -940,9 +982,11 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
         ? invoke<string[]>("youtube_subscriptions_active_refresh_ids").catch(() => [])
         : Promise.resolve([] as string[]),
     ]);
+    const nextItems = nextPage?.items ?? [];
     setItems(nextItems);
     setItemsOffset(nextItems.length);
-    setItemsHasMore(!wantsInstagram && nextItems.length >= libraryPageSize);
+    setItemsTotal(nextPage ? nextPage.total_count : null);
+    setItemsHasMore(!wantsInstagram && nextItems.length < (nextPage?.total_count ?? 0));
     setItemsLoadingMore(false);
     if (nextRules) setBatchRules(nextRules);
     setSubscriptions(nextSubscriptions);
@@ -967,13 +1011,15 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
     setItemsLoadingMore(true);
     setError(null);
     try {
-      const nextItems = await invoke<LibraryItem[]>("library_list", {
+      const nextPage = await invoke<LibraryListPage>("library_list", {
         limit: libraryPageSize,
         offset: itemsOffset,
       });
-      setItems((prev) => [...prev, ...nextItems]);
-      setItemsOffset((prev) => prev + nextItems.length);
-      setItemsHasMore(nextItems.length >= libraryPageSize);
+      const nextOffset = nextPage.offset + nextPage.items.length;
+      setItems((prev) => [...prev, ...nextPage.items]);
+      setItemsOffset(nextOffset);
+      setItemsTotal(nextPage.total_count);
+      setItemsHasMore(nextPage.items.length > 0 && nextOffset < nextPage.total_count);
     } catch (e) {
       setError(String(e));
     } finally {
@@ -1208,6 +1254,10 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
     safeLocalStorageSet("voxvulgi.v1.library.image_batch_skip_keywords", imageBatchSkipKeywords);
   }, [imageBatchSkipKeywords]);
 
+  useEffect(() => {
+    safeLocalStorageSet("voxvulgi.v1.library.image_batch_extensions", imageBatchExtensions);
+  }, [imageBatchExtensions]);
+
   useEffect(() => {
     safeLocalStorageSet("voxvulgi.v1.library.image_batch_output_dir", imageBatchOutputDir);
   }, [imageBatchOutputDir]);
@@ -1385,6 +1435,16 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
   }
 
 
+  function scheduleAfterMs(localDateTime: string): number | null {
+    const trimmed = localDateTime.trim();
+    if (!trimmed) return null;
+    const ms = new Date(trimmed).getTime();
+    if (!Number.isFinite(ms)) {
+      throw new Error(`Invalid start time: ${trimmed}`);
+    }
+    return ms > Date.now() ? ms : null;
+  }
+
   async function enqueueUrlBatch() {
     setBusy(true);
     setError(null);
@@ -1408,16 +1468,30 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
         );
       }
 
-      const queued = await invoke<Array<{ id: string }>>("jobs_enqueue_download_batch", {
+      const result = await invoke<{
+        queued: Array<{ id: string }>;
+        skipped_invalid: Array<{ url: string; error: string | null }>;
+      }>("jobs_enqueue_download_batch", {
         urls,
         authCookie: urlBatchAuthCookie.trim() || null,
         outputDir: urlBatchOutputDir.trim() || null,
         useBrowserCookies: urlBatchUseBrowserCookies,
         presetId: urlBatchPresetId.trim() || null,
+        scheduleAfterMs: scheduleAfterMs(urlBatchScheduleAt),
       });
-      setUrlBatchText("");
+      const queued = result.queued;
+      const skipped = result.skipped_invalid;
+      // Leave invalid URLs in the box so they can be fixed and resubmitted.
+      setUrlBatchText(skipped.map((entry) => entry.url).join("\n"));
       setUrlBatchAuthCookie("");
-      setNotice(`Queued ${queued.length} download job${queued.length === 1 ? "" : "s"}.`);
+      setNotice(
+        `Queued ${queued.length} download job${queued.length === 1 ? "" : "s"}.` +
+          (skipped.length
+            ? ` Skipped ${skipped.length} invalid URL${skipped.length === 1 ? "" : "s"}: ${skipped
+                .map((entry) => entry.error ?? entry.url)
+                .join("; ")}`
+            : ""),
+      );
       await refresh();
     } catch (e) {
       setError(String(e));
@@ -1494,6 +1568,10 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
         .split(/[\s,;]+/)
         .map((value) => value.trim())
         .filter(Boolean);
+      const fileExtensions = imageBatchExtensions
+        .split(/[\s,;]+/)
+        .map((value) => value.trim())
+        .filter(Boolean);
       const maxPages = Number.isFinite(imageBatchMaxPages)
         ? Math.max(1, Math.min(5000, Math.round(imageBatchMaxPages)))
         : 1500;
@@ -1508,9 +1586,17 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
         allowCrossDomain: imageBatchAllowCrossDomain,
         followContentLinks: imageBatchFollowContentLinks,
         skipUrlKeywords: skipKeywords,
+        fileExtensionsFilter: fileExtensions.length ? fileExtensions : null,
+        minImagePixels: imageBatchMinPixels.trim()
+          ? Math.round(Number(imageBatchMinPixels))
+          : null,
+        maxImagePixels: imageBatchMaxPixels.trim()
+          ? Math.round(Number(imageBatchMaxPixels))
+          : null,
         outputSubdir: null,
         outputDir: imageBatchOutputDir.trim() || null,
         authCookie: imageBatchAuthCookie.trim() || null,
+        scheduleAfterMs: scheduleAfterMs(imageBatchScheduleAt),
       });
 
       setImageBatchUrlsText("");
@@ -1588,6 +1674,10 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
     setSubscriptionPresetId("");
     setSubscriptionGroupIds([]);
     setSubscriptionRefreshIntervalMinutes(60);
+    setSubscriptionExcludeKeywords("");
+    setSubscriptionIncludeKeywords("");
+    setSubscriptionMaxItemsPerRefresh("");
+    setSubscriptionAutoMaxItems(false);
   }
 
   function editSubscription(sub: YoutubeSubscriptionRow) {
@@ -1604,6 +1694,12 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
     setSubscriptionPresetId(sub.preset_id ?? "");
     setSubscriptionGroupIds(sub.group_ids ?? []);
     setSubscriptionRefreshIntervalMinutes(sub.refresh_interval_minutes);
+    setSubscriptionExcludeKeywords((sub.filter_keywords_exclude ?? []).join(", "));
+    setSubscriptionIncludeKeywords((sub.filter_keywords_include ?? []).join(", "));
+    setSubscriptionMaxItemsPerRefresh(
+      sub.max_items_per_refresh != null ? String(sub.max_items_per_refresh) : "",
+    );
+    setSubscriptionAutoMaxItems(sub.auto_max_items ?? false);
   }
 
   async function saveSubscription() {
@@ -1630,6 +1726,12 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
             Math.round(subscriptionRefreshIntervalMinutes),
           ),
         ),
+        filter_keywords_exclude: splitKeywordList(subscriptionExcludeKeywords),
+        filter_keywords_include: splitKeywordList(subscriptionIncludeKeywords),
+        max_items_per_refresh: subscriptionMaxItemsPerRefresh.trim()
+          ? Math.round(Number(subscriptionMaxItemsPerRefresh))
+          : null,
+        auto_max_items: subscriptionAutoMaxItems,
       };
       if (!payload.title) throw new Error("Subscription title is required.");
       if (!payload.source_url) throw new Error("Subscription URL is required.");
@@ -1888,6 +1990,32 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
     }
   }
 
+  async function exportSubscriptionsOpml() {
+    setBusy(true);
+    setError(null);
+    setNotice(null);
+    try {
+      const out = await save({
+        title: "Export YouTube subscriptions as OPML",
+        defaultPath: "youtube_subscriptions.opml",
+        filters: [{ name: "OPML", extensions: ["opml", "xml"] }],
+      });
+      if (!out || typeof out !== "string") return;
+
+      const summary = await invoke<YoutubeSubscriptionsExportSummary>(
+        "youtube_subscriptions_export_opml",
+        {
+          outPath: out,
+        },
+      );
+      setNotice(`Exported ${summary.count} subscription(s) to ${summary.out_path}`);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
   async function importSubscriptionsJson() {
     setBusy(true);
     setError(null);
@@ -1907,8 +2035,11 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
         },
       );
       setNotice(
-        `Imported ${summary.total_in_file} entries (inserted ${summary.inserted}, updated ${summary.updated}).`,
+        `Imported ${summary.total_in_file} entries (inserted ${summary.inserted}, skipped ${summary.skipped_duplicates} duplicates, ${summary.skipped_invalid} invalid).`,
       );
+      if (summary.validation_errors.length) {
+        setError(summary.validation_errors.slice(0, 5).join("\n"));
+      }
       await refresh();
     } catch (e) {
       setError(String(e));
@@ -2709,6 +2840,18 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
             Choose folder
           </button>
         </div>
+        <div className="row">
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <span>Start after</span>
+            <input
+              type="datetime-local"
+              value={urlBatchScheduleAt}
+              disabled={busy}
+              onChange={(e) => setUrlBatchScheduleAt(e.currentTarget.value)}
+            />
+          </label>
+          <span style={{ color: "#4b5563" }}>Leave empty to start right away.</span>
+        </div>
         <div style={{ display: "grid", gap: 6, marginTop: 10 }}>
           <span>Session / cookies</span>
           <textarea
@@ -3145,6 +3288,49 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
             />
           </label>
         </div>
+        <div className="row">
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <span>Skip titles containing</span>
+            <input
+              value={subscriptionExcludeKeywords}
+              disabled={busy}
+              onChange={(e) => setSubscriptionExcludeKeywords(e.currentTarget.value)}
+              placeholder="#shorts, trailer"
+              style={{ width: 220 }}
+            />
+          </label>
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <span>Only titles containing</span>
+            <input
+              value={subscriptionIncludeKeywords}
+              disabled={busy}
+              onChange={(e) => setSubscriptionIncludeKeywords(e.currentTarget.value)}
+              placeholder="(any title)"
+              style={{ width: 220 }}
+            />
+          </label>
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <span>Max items per refresh</span>
+            <input
+              type="number"
+              min={1}
+              value={subscriptionMaxItemsPerRefresh}
+              disabled={busy}
+              onChange={(e) => setSubscriptionMaxItemsPerRefresh(e.currentTarget.value)}
+              placeholder="(default)"
+              style={{ width: 100 }}
+            />
+          </label>
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <input
+              type="checkbox"
+              checked={subscriptionAutoMaxItems}
+              disabled={busy}
+              onChange={(e) => setSubscriptionAutoMaxItems(e.currentTarget.checked)}
+            />
+            <span>Auto max items (from posting activity)</span>
+          </label>
+        </div>
         <div className="row">
           <span style={{ color: "#4b5563" }}>Groups</span>
           {subscriptionGroups.length ? (
@@ -3183,6 +3369,9 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
           <button type="button" disabled={busy} onClick={exportSubscriptionsJson}>
             Export JSON
           </button>
+          <button type="button" disabled={busy} onClick={exportSubscriptionsOpml}>
+            Export OPML
+          </button>
           <button type="button" disabled={busy} onClick={importSubscriptionsJson}>
             Import JSON
           </button>
@@ -3276,6 +3465,13 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
                           ? `retry after ${new Date(sub.next_allowed_refresh_at_ms).toLocaleString()}`
                           : "ready"}
                         {sub.consecutive_failures > 0 ? ` (${sub.consecutive_failures} fail)` : ""}
+                        {sub.last_error ? (
+                          <div style={{ fontSize: 12, color: "#b91c1c" }} title={sub.last_error}>
+                            {sub.last_error.length > 80
+                              ? `${sub.last_error.slice(0, 80)}…`
+                              : sub.last_error}
+                          </div>
+                        ) : null}
                       </td>
                       <td>
                         <div className="row" style={{ marginTop: 0, flexWrap: "nowrap" }}>
@@ -3830,6 +4026,42 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
             />
           </label>
         </div>
+        <div className="row">
+          <label style={{ display: "flex", alignItems: "center", gap: 8, flex: 1 }}>
+            <span>Only extensions</span>
+            <input
+              value={imageBatchExtensions}
+              disabled={busy}
+              onChange={(e) => setImageBatchExtensions(e.currentTarget.value)}
+              placeholder="svg webp (empty = all images)"
+              style={{ width: "100%" }}
+            />
+          </label>
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <span>Min pixels</span>
+            <input
+              type="number"
+              min={0}
+              value={imageBatchMinPixels}
+              disabled={busy}
+              onChange={(e) => setImageBatchMinPixels(e.currentTarget.value)}
+              placeholder="(no limit)"
+              style={{ width: 120 }}
+            />
+          </label>
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <span>Max pixels</span>
+            <input
+              type="number"
+              min={0}
+              value={imageBatchMaxPixels}
+              disabled={busy}
+              onChange={(e) => setImageBatchMaxPixels(e.currentTarget.value)}
+              placeholder="(no limit)"
+              style={{ width: 120 }}
+            />
+          </label>
+        </div>
         <div className="row">
           <label style={{ display: "flex", alignItems: "center", gap: 8, flex: 1 }}>
             <span>Batch output override</span>
@@ -3845,6 +4077,18 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
             Choose folder
           </button>
         </div>
+        <div className="row">
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <span>Start after</span>
+            <input
+              type="datetime-local"
+              value={imageBatchScheduleAt}
+              disabled={busy}
+              onChange={(e) => setImageBatchScheduleAt(e.currentTarget.value)}
+            />
+          </label>
+          <span style={{ color: "#4b5563" }}>Leave empty to start right away.</span>
+        </div>
         <div className="row">
           <label style={{ display: "flex", alignItems: "center", gap: 8, flex: 1 }}>
             <span>Session cookie</span>
@@ -4166,7 +4410,9 @@ export function LibraryPage({ mode = "all" }: LibraryPageProps) {
           </div>
           <div className="row">
             <div style={{ color: "#4b5563" }}>
-              Loaded {items.length} item{items.length === 1 ? "" : "s"}.
+              Loaded {items.length}
+              {itemsTotal !== null ? ` of ${itemsTotal}` : ""}
+              {(itemsTotal ?? items.length) === 1 ? " item" : " items"}.
               Showing {filteredMediaItems.length} after filters.
               {itemsHasMore ? " (more available)" : ""}.
             </div>
@@ -50,6 +50,16 @@ type SubtitleTrackRow = {
   path: string;
   created_by: string;
   version: number;
+  notes?: string | null;
+  parent_track_id?: string | null;
+};
+
+const SUBTITLE_VERSION_NOTES_MAX_CHARS = 4096;
+
+type FindReplacePreview = {
+  matches: number;
+  sample_before: string[];
+  sample_after: string[];
 };
 
 type SubtitleSegment = {
@@ -58,6 +68,7 @@ type SubtitleSegment = {
   end_ms: number;
   text: string;
   speaker: string | null;
+  confidence?: number | null;
 };
 
 type SubtitleDocument = {
@@ -65,6 +76,7 @@ type SubtitleDocument = {
   kind: string;
   lang: string;
   segments: SubtitleSegment[];
+  metadata?: Record<string, unknown>;
 };
 
 type JobStatus = "queued" | "running" | "succeeded" | "failed" | "canceled";
@@ -224,6 +236,10 @@ function speakerProfilePaths(setting: {
 type Pyttsx3Voice = {
   id: string;
   name: string;
+  lang?: string | null;
+  age?: string | null;
+  gender?: string | null;
+  sample_text_wav?: string | null;
 };
 
 type ItemSpeakerSetting = {
@@ -748,6 +764,11 @@ type LocalizationBatchItemResult = {
   queued_jobs: JobRow[];
 };
 
+type AsrBatchEnqueueResult = {
+  enqueued: JobRow[];
+  skipped_already_transcribed: string[];
+};
+
 type LocalizationBatchQueueSummary = {
   batch_id: string;
   queued_jobs_total: number;
@@ -1306,6 +1327,12 @@ export function SubtitleEditorPage({
   }
   const [dirty, setDirty] = useState(false);
   const [busy, setBusy] = useState(false);
+  const [findPattern, setFindPattern] = useState("");
+  const [replaceText, setReplaceText] = useState("");
+  const [findCaseSensitive, setFindCaseSensitive] = useState(false);
+  const [findWholeWord, setFindWholeWord] = useState(false);
+  const [lowConfidenceThreshold, setLowConfidenceThreshold] = useState(-0.9);
+  const [findPreview, setFindPreview] = useState<FindReplacePreview | null>(null);
   const [error, setError] = useState<string | null>(null);
   const [notice, setNotice] = useState<string | null>(null);
   const [outputs, setOutputs] = useState<ItemOutputs | null>(null);
@@ -1454,6 +1481,12 @@ export function SubtitleEditorPage({
   const [mixTimingFitEnabled, setMixTimingFitEnabled] = useState(() => {
     return safeLocalStorageGet("voxvulgi.v1.editor.timing_fit_enabled") === "1";
   });
+  const [mixNormalizeSpeech, setMixNormalizeSpeech] = useState(() => {
+    return safeLocalStorageGet("voxvulgi.v1.editor.mix_normalize_speech") === "1";
+  });
+  const [dubAutoExtractProfiles, setDubAutoExtractProfiles] = useState(() => {
+    return safeLocalStorageGet("voxvulgi.v1.editor.dub_auto_extract_profiles") === "1";
+  });
   const [mixTimingFitMinFactor, setMixTimingFitMinFactor] = useState(() => {
     const raw = safeLocalStorageGet("voxvulgi.v1.editor.timing_fit_min_factor");
     const parsed = raw ? Number(raw) : NaN;
@@ -1474,6 +1507,7 @@ export function SubtitleEditorPage({
   const [muxKeepOriginalAudio, setMuxKeepOriginalAudio] = useState(() => {
     return safeLocalStorageGet("voxvulgi.v1.editor.mux_keep_original_audio") === "1";
   });
+  const [muxEmbedSubtitles, setMuxEmbedSubtitles] = useState(false);
   const [muxDubbedAudioLang, setMuxDubbedAudioLang] = useState(() => {
     return safeLocalStorageGet("voxvulgi.v1.editor.mux_dubbed_audio_lang") ?? "eng";
   });
@@ -1493,6 +1527,9 @@ export function SubtitleEditorPage({
   const [exportIncludeVtt, setExportIncludeVtt] = useState(() => {
     return safeLocalStorageGet("voxvulgi.v1.editor.export_include_vtt") === "1";
   });
+  const [exportSpeakerLabels, setExportSpeakerLabels] = useState(() => {
+    return safeLocalStorageGet("voxvulgi.v1.editor.export_speaker_labels") === "1";
+  });
   const [exportIncludeDubPreview, setExportIncludeDubPreview] = useState(() => {
     const raw = safeLocalStorageGet("voxvulgi.v1.editor.export_include_dub_preview");
     return raw === null ? true : raw === "1";
@@ -1513,6 +1550,7 @@ export function SubtitleEditorPage({
   const [qcReport, setQcReport] = useState<any | null>(null);
   const [pyttsx3Voices, setPyttsx3Voices] = useState<Pyttsx3Voice[]>([]);
   const [pyttsx3VoicesBusy, setPyttsx3VoicesBusy] = useState(false);
+  const [pyttsx3LanguageFilter, setPyttsx3LanguageFilter] = useState("");
   const [speakerSettings, setSpeakerSettings] = useState<ItemSpeakerSetting[]>([]);
   const [speakerSettingsBusy, setSpeakerSettingsBusy] = useState(false);
   const [voiceTemplates, setVoiceTemplates] = useState<VoiceTemplate[]>([]);
@@ -1732,6 +1770,20 @@ export function SubtitleEditorPage({
     );
   }, [mixTimingFitEnabled]);
 
+  useEffect(() => {
+    safeLocalStorageSet(
+      "voxvulgi.v1.editor.mix_normalize_speech",
+      mixNormalizeSpeech ? "1" : "0",
+    );
+  }, [mixNormalizeSpeech]);
+
+  useEffect(() => {
+    safeLocalStorageSet(
+      "voxvulgi.v1.editor.dub_auto_extract_profiles",
+      dubAutoExtractProfiles ? "1" : "0",
+    );
+  }, [dubAutoExtractProfiles]);
+
   useEffect(() => {
     safeLocalStorageSet(
       "voxvulgi.v1.editor.timing_fit_min_factor",
@@ -1790,6 +1842,13 @@ export function SubtitleEditorPage({
     );
   }, [exportIncludeVtt]);
 
+  useEffect(() => {
+    safeLocalStorageSet(
+      "voxvulgi.v1.editor.export_speaker_labels",
+      exportSpeakerLabels ? "1" : "0",
+    );
+  }, [exportSpeakerLabels]);
+
   useEffect(() => {
     safeLocalStorageSet(
       "voxvulgi.v1.editor.export_include_dub_preview",
@@ -2137,6 +2196,33 @@ export function SubtitleEditorPage({
     [tracks, trackId],
   );
 
+  const [trackVersions, setTrackVersions] = useState<SubtitleTrackRow[]>([]);
+  const [versionNotesDraft, setVersionNotesDraft] = useState<string | null>(null);
+  useEffect(() => {
+    setVersionNotesDraft(null);
+  }, [trackId]);
+  useEffect(() => {
+    if (!currentTrack) {
+      setTrackVersions([]);
+      return;
+    }
+    let disposed = false;
+    invoke<SubtitleTrackRow[]>("subtitles_list_versions", {
+      itemId: currentTrack.item_id,
+      kind: currentTrack.kind,
+      lang: currentTrack.lang,
+    })
+      .then((next) => {
+        if (!disposed) setTrackVersions(next);
+      })
+      .catch(() => {
+        if (!disposed) setTrackVersions([]);
+      });
+    return () => {
+      disposed = true;
+    };
+  }, [currentTrack]);
+
   const translatedEnglishTrack = useMemo(
     () => pickLatestTrack(tracks, (track) => track.kind === "translated" && track.lang === "en"),
     [tracks],
@@ -3054,7 +3140,7 @@ export function SubtitleEditorPage({
         t.lang === "en" &&
         t.format === "ytfetch_subtitle_json_v1",
     );
-  }, [currentTrack, tracks]);
+  }, [currentTrack]);
 
   const activePairTrackId = useMemo(() => {
     if (!bilingualEnabled) return null;
@@ -3649,14 +3735,122 @@ export function SubtitleEditorPage({
     setError(null);
     setNotice(null);
     try {
-      const next = await invoke<SubtitleTrackRow>("subtitles_save_new_version", {
-        trackId,
+      const next = await invoke<SubtitleTrackRow & { was_no_op: boolean }>(
+        "subtitles_save_new_version",
+        {
+          trackId,
+          doc,
+        },
+      );
+      const nextTracks = await refreshTracks();
+      setTracks(nextTracks);
+      setTrackId(next.id);
+      setDirty(false);
+      if (next.was_no_op) {
+        setNotice(`No changes since v${next.version}; nothing saved.`);
+      }
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function previewFindAndReplace() {
+    if (!doc || !findPattern) return;
+    setError(null);
+    try {
+      const preview = await invoke<FindReplacePreview>("subtitles_find_and_replace_preview", {
         doc,
+        pattern: findPattern,
+        replacement: replaceText,
+        caseSensitive: findCaseSensitive,
+        wholeWord: findWholeWord,
+      });
+      setFindPreview(preview);
+    } catch (e) {
+      setFindPreview(null);
+      setError(String(e));
+    }
+  }
+
+  async function applyFindAndReplace() {
+    if (!trackId || !findPattern) return;
+    setBusy(true);
+    setError(null);
+    setNotice(null);
+    try {
+      const next = await invoke<SubtitleTrackRow>("subtitles_find_and_replace", {
+        trackId,
+        pattern: findPattern,
+        replacement: replaceText,
+        caseSensitive: findCaseSensitive,
+        wholeWord: findWholeWord,
       });
       const nextTracks = await refreshTracks();
       setTracks(nextTracks);
       setTrackId(next.id);
-      setDirty(false);
+      setFindPreview(null);
+      setNotice(`Replaced matches; saved as v${next.version}.`);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function filterLowConfidenceSegments() {
+    if (!trackId) return;
+    setBusy(true);
+    setError(null);
+    setNotice(null);
+    try {
+      const next = await invoke<SubtitleTrackRow>("subtitles_filter_low_confidence", {
+        trackId,
+        threshold: lowConfidenceThreshold,
+      });
+      const nextTracks = await refreshTracks();
+      setTracks(nextTracks);
+      setTrackId(next.id);
+      setNotice(`Marked low-confidence segments as [inaudible]; saved as v${next.version}.`);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function saveVersionNotes() {
+    if (!trackId || versionNotesDraft === null) return;
+    setBusy(true);
+    setError(null);
+    setNotice(null);
+    try {
+      const next = await invoke<SubtitleTrackRow>("subtitles_annotate_version", {
+        trackId,
+        notes: versionNotesDraft.trim() ? versionNotesDraft : null,
+      });
+      setTrackVersions((prev) => prev.map((t) => (t.id === next.id ? next : t)));
+      setVersionNotesDraft(null);
+      setNotice(next.notes ? `Saved notes for v${next.version}.` : `Cleared notes for v${next.version}.`);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBusy(false);
+    }
+  }
+
+  async function restoreTrackVersion() {
+    if (!trackId) return;
+    setBusy(true);
+    setError(null);
+    setNotice(null);
+    try {
+      const next = await invoke<SubtitleTrackRow>("subtitles_restore_version", { trackId });
+      const nextTracks = await refreshTracks();
+      setTracks(nextTracks);
+      setTrackId(next.id);
+      setNotice(`Restored as v${next.version}.`);
     } catch (e) {
       setError(String(e));
     } finally {
@@ -3783,6 +3977,7 @@ export function SubtitleEditorPage({
         timingFitEnabled: mixTimingFitEnabled,
         timingFitMinFactor: mixTimingFitMinFactor,
         timingFitMaxFactor: mixTimingFitMaxFactor,
+        normalizeSpeech: mixNormalizeSpeech,
       });
       setNotice("Queued mix dub preview job.");
       refreshArtifacts().catch(() => undefined);
@@ -3806,6 +4001,7 @@ export function SubtitleEditorPage({
         keepOriginalAudio: muxKeepOriginalAudio,
         dubbedAudioLang: muxDubbedAudioLang.trim() || null,
         originalAudioLang: muxOriginalAudioLang.trim() || null,
+        embedSubtitleTrackId: muxEmbedSubtitles ? trackId : null,
       });
       setNotice("Queued mux preview job.");
       refreshArtifacts().catch(() => undefined);
@@ -4219,6 +4415,7 @@ export function SubtitleEditorPage({
       const job = await invoke<JobRow>("jobs_enqueue_dub_voice_preserving_v1", {
         itemId,
         sourceTrackId: targetTrackId,
+        autoExtractProfiles: dubAutoExtractProfiles,
       });
       setDubVoicePreservingJobId(job.id);
       setDubVoicePreservingJobStatus(job.status);
@@ -4276,7 +4473,9 @@ export function SubtitleEditorPage({
     setError(null);
     setPyttsx3VoicesBusy(true);
     try {
-      const voices = await invoke<Pyttsx3Voice[]>("tools_tts_preview_pyttsx3_voices");
+      const voices = await invoke<Pyttsx3Voice[]>("tools_tts_preview_pyttsx3_voices", {
+        languageFilter: pyttsx3LanguageFilter.trim() || null,
+      });
       setPyttsx3Voices(
         [...(voices ?? [])].sort((a, b) => (a.name ?? "").localeCompare(b.name ?? "")),
       );
@@ -5303,6 +5502,28 @@ export function SubtitleEditorPage({
     }
   }
 
+  async function queueAsrBatch() {
+    const itemIds = Array.from(new Set(batchSelectedItemIds.map((value) => value.trim()).filter(Boolean)));
+    if (!itemIds.length) {
+      setError("Choose at least one item for batch ASR.");
+      return;
+    }
+    setError(null);
+    setBatchQueueBusy(true);
+    try {
+      const result = await invoke<AsrBatchEnqueueResult>("jobs_enqueue_asr_batch", { itemIds });
+      const skipped = result.skipped_already_transcribed.length;
+      setNotice(
+        `Queued ASR for ${result.enqueued.length} item(s)${skipped ? `; skipped ${skipped} already transcribed` : ""}.`,
+      );
+      refreshItemJobs().catch(() => undefined);
+    } catch (e) {
+      setError(String(e));
+    } finally {
+      setBatchQueueBusy(false);
+    }
+  }
+
   function toggleExperimentalBatchBackend(backendId: string, checked: boolean) {
     setExperimentalBatchBackendIds((prev) => {
       const set = new Set(prev);
@@ -5647,7 +5868,7 @@ export function SubtitleEditorPage({
   usePollingLoop(
     async () => {
       try {
-        const rows = await invoke<JobRow[]>("jobs_list", { limit: 200, offset: 0 });
+        const rows = await invoke<JobRow[]>("jobs_list", { limit: 200, offset: 0, itemId });
         const byId = new Map(rows.map((job) => [job.id, job]));
 
         const applyJobState = (
@@ -5794,13 +6015,17 @@ export function SubtitleEditorPage({
 
       if (exportIncludeSrt) {
         const outPath = joinPath(outDir, `${sourceBaseStem}.sub-en.srt`);
-        await invoke("subtitles_export_doc_srt", { doc, outPath });
+        await invoke("subtitles_export_doc_srt", {
+          doc,
+          outPath,
+          includeSpeakerLabels: exportSpeakerLabels,
+        });
         created.push(outPath);
       }
 
       if (exportIncludeVtt) {
         const outPath = joinPath(outDir, `${sourceBaseStem}.sub-en.vtt`);
-        await invoke("subtitles_export_doc_vtt", { doc, outPath });
+        await exportDocVtt(doc, outPath);
         created.push(outPath);
       }
 
@@ -5839,6 +6064,18 @@ export function SubtitleEditorPage({
     }
   }
 
+  async function exportDocVtt(target: SubtitleDocument, outPath: string) {
+    if (exportSpeakerLabels) {
+      await invoke("subtitles_export_doc_vtt_with_options", {
+        doc: target,
+        outPath,
+        options: { include_speaker_labels: true, position: null, align: null },
+      });
+    } else {
+      await invoke("subtitles_export_doc_vtt", { doc: target, outPath });
+    }
+  }
+
   async function exportSrt() {
     if (!doc) return;
     const suggested = exportSrtPreviewPath || `${sourceBaseStem}.sub-en.srt`;
@@ -5848,7 +6085,11 @@ export function SubtitleEditorPage({
     setError(null);
     setNotice(null);
     try {
-      await invoke("subtitles_export_doc_srt", { doc, outPath: out });
+      await invoke("subtitles_export_doc_srt", {
+        doc,
+        outPath: out,
+        includeSpeakerLabels: exportSpeakerLabels,
+      });
       setNotice(`Exported SRT: ${out}`);
       await refreshLocalizationOutputStatuses().catch(() => undefined);
     } catch (e) {
@@ -5867,7 +6108,7 @@ export function SubtitleEditorPage({
     setError(null);
     setNotice(null);
     try {
-      await invoke("subtitles_export_doc_vtt", { doc, outPath: out });
+      await exportDocVtt(doc, out);
       setNotice(`Exported VTT: ${out}`);
       await refreshLocalizationOutputStatuses().catch(() => undefined);
     } catch (e) {
@@ -6090,11 +6331,21 @@ export function SubtitleEditorPage({
         setNotice("Queued Spleeter separation.");
         return;
       }
+      if (artifact.rerun_kind === "separate_spleeter_5stems") {
+        await invoke("jobs_enqueue_separate_audio_spleeter_5stems", { itemId });
+        setNotice("Queued Spleeter 5-stem separation.");
+        return;
+      }
       if (artifact.rerun_kind === "separate_demucs") {
         await invoke("jobs_enqueue_separate_audio_demucs_v1", { itemId });
         setNotice("Queued Demucs separation.");
         return;
       }
+      if (artifact.rerun_kind === "separate_demucs_4stems") {
+        await invoke("jobs_enqueue_separate_audio_demucs_4stems_v1", { itemId });
+        setNotice("Queued Demucs 4-stem separation.");
+        return;
+      }
       if (artifact.rerun_kind === "clean_vocals") {
         await enqueueCleanVocals();
         return;
@@ -6388,14 +6639,25 @@ export function SubtitleEditorPage({
               </>
             ) : null}
             {selectedStage === "dub" ? (
-              <button
-                type="button"
-                disabled={busy || !trackId}
-                onClick={enqueueDubVoicePreservingV1}
-                title={!trackId ? "Load the English translated track first" : undefined}
-              >
-                Run voice-preserving dub
-              </button>
+              <>
+                <button
+                  type="button"
+                  disabled={busy || !trackId}
+                  onClick={enqueueDubVoicePreservingV1}
+                  title={!trackId ? "Load the English translated track first" : undefined}
+                >
+                  Run voice-preserving dub
+                </button>
+                <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+                  <input
+                    type="checkbox"
+                    checked={dubAutoExtractProfiles}
+                    disabled={busy}
+                    onChange={(e) => setDubAutoExtractProfiles(e.currentTarget.checked)}
+                  />
+                  <span>Auto-extract missing speaker profiles</span>
+                </label>
+              </>
             ) : null}
             {selectedStage === "mix" ? (
               <button type="button" disabled={busy} onClick={enqueueMixDubPreview}>
@@ -7002,6 +7264,15 @@ export function SubtitleEditorPage({
             />
             <span>Subtitles (.vtt)</span>
           </label>
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <input
+              type="checkbox"
+              checked={exportSpeakerLabels}
+              disabled={busy || !doc}
+              onChange={(e) => setExportSpeakerLabels(e.currentTarget.checked)}
+            />
+            <span>Speaker labels</span>
+          </label>
           <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
             <input
               type="checkbox"
@@ -7353,6 +7624,163 @@ export function SubtitleEditorPage({
           </button>
         </div>
 
+        <div className="row" style={{ marginTop: 10, flexWrap: "wrap" }}>
+          <div style={{ fontSize: 12, opacity: 0.85 }}>Find and replace</div>
+          <input
+            value={findPattern}
+            disabled={busy}
+            placeholder="Find (prefix regex: for a pattern)"
+            onChange={(e) => {
+              setFindPattern(e.currentTarget.value);
+              setFindPreview(null);
+            }}
+            style={{ width: 220 }}
+          />
+          <input
+            value={replaceText}
+            disabled={busy}
+            placeholder="Replace with"
+            onChange={(e) => {
+              setReplaceText(e.currentTarget.value);
+              setFindPreview(null);
+            }}
+            style={{ width: 180 }}
+          />
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <input
+              type="checkbox"
+              checked={findCaseSensitive}
+              disabled={busy}
+              onChange={(e) => {
+                setFindCaseSensitive(e.currentTarget.checked);
+                setFindPreview(null);
+              }}
+            />
+            <span>Match case</span>
+          </label>
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <input
+              type="checkbox"
+              checked={findWholeWord}
+              disabled={busy}
+              onChange={(e) => {
+                setFindWholeWord(e.currentTarget.checked);
+                setFindPreview(null);
+              }}
+            />
+            <span>Whole word</span>
+          </label>
+          <button
+            type="button"
+            disabled={busy || !doc || !findPattern}
+            onClick={previewFindAndReplace}
+          >
+            Preview
+          </button>
+          <button
+            type="button"
+            disabled={busy || !trackId || !findPattern || dirty}
+            title={dirty ? "Save new version first; replace works on the saved track." : undefined}
+            onClick={applyFindAndReplace}
+          >
+            Replace all
+          </button>
+          {findPreview ? (
+            <span style={{ fontSize: 12, opacity: 0.85 }}>
+              {findPreview.matches} match{findPreview.matches === 1 ? "" : "es"}
+            </span>
+          ) : null}
+        </div>
+        {findPreview?.sample_before.length ? (
+          <div style={{ fontSize: 12, opacity: 0.85, marginTop: 6 }}>
+            {findPreview.sample_before.map((before, idx) => (
+              <div key={idx}>
+                {before} → {findPreview.sample_after[idx] ?? ""}
+              </div>
+            ))}
+          </div>
+        ) : null}
+
+        <div className="row" style={{ marginTop: 10, flexWrap: "wrap" }}>
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <span>Low-confidence below</span>
+            <input
+              type="number"
+              min={-2}
+              max={0}
+              step={0.1}
+              value={lowConfidenceThreshold}
+              disabled={busy}
+              onChange={(e) => {
+                const value = Number(e.currentTarget.value);
+                if (Number.isFinite(value)) {
+                  setLowConfidenceThreshold(Math.min(0, Math.max(-2, value)));
+                }
+              }}
+              style={{ width: 80 }}
+            />
+          </label>
+          <button
+            type="button"
+            disabled={busy || !trackId || dirty}
+            title={
+              dirty
+                ? "Save new version first; this works on the saved track."
+                : "Replace segments Whisper was unsure about with [inaudible]."
+            }
+            onClick={filterLowConfidenceSegments}
+          >
+            Mark as [inaudible]
+          </button>
+        </div>
+
+        {trackVersions.length > 1 ? (
+          <div className="row" style={{ marginTop: 10, flexWrap: "wrap" }}>
+            <div style={{ fontSize: 12, opacity: 0.85 }}>Version history</div>
+            <select
+              value={trackId ?? ""}
+              disabled={busy || dirty}
+              onChange={(e) => setTrackId(e.currentTarget.value)}
+            >
+              {trackVersions.map((t) => (
+                <option key={t.id} value={t.id} title={t.notes ?? undefined}>
+                  v{t.version} ({t.created_by})
+                  {t.notes ? ` - ${t.notes.length > 40 ? `${t.notes.slice(0, 40)}...` : t.notes}` : ""}
+                </option>
+              ))}
+            </select>
+            <button
+              type="button"
+              disabled={busy || dirty || trackVersions[0]?.id === trackId}
+              title="Save this version's content as a new latest version."
+              onClick={restoreTrackVersion}
+            >
+              Restore as latest
+            </button>
+          </div>
+        ) : null}
+        {trackId ? (
+          <div className="row" style={{ marginTop: 10, flexWrap: "wrap" }}>
+            <div style={{ fontSize: 12, opacity: 0.85 }}>Version notes</div>
+            <input
+              type="text"
+              style={{ flex: 1, minWidth: 240 }}
+              maxLength={SUBTITLE_VERSION_NOTES_MAX_CHARS}
+              placeholder="What produced or changed this version"
+              value={versionNotesDraft ?? trackVersions.find((t) => t.id === trackId)?.notes ?? ""}
+              disabled={busy}
+              onChange={(e) => setVersionNotesDraft(e.currentTarget.value)}
+            />
+            <button
+              type="button"
+              disabled={busy || versionNotesDraft === null}
+              onClick={() => void saveVersionNotes()}
+            >
+              Save notes
+            </button>
+          </div>
+        ) : null}
+
         <div className="row" style={{ marginTop: 10, flexWrap: "wrap" }}>
           <div style={{ fontSize: 12, opacity: 0.85 }}>Mix settings</div>
           <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
@@ -7390,6 +7818,15 @@ export function SubtitleEditorPage({
             />
             <span>Timing fit</span>
           </label>
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <input
+              type="checkbox"
+              checked={mixNormalizeSpeech}
+              disabled={busy}
+              onChange={(e) => setMixNormalizeSpeech(e.currentTarget.checked)}
+            />
+            <span>Level speech segments</span>
+          </label>
           <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
             <span>Min</span>
             <input
@@ -7440,6 +7877,15 @@ export function SubtitleEditorPage({
             />
             <span>Keep original audio</span>
           </label>
+          <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
+            <input
+              type="checkbox"
+              checked={muxEmbedSubtitles}
+              disabled={busy || !trackId}
+              onChange={(e) => setMuxEmbedSubtitles(e.currentTarget.checked)}
+            />
+            <span>Embed current track as soft subtitles</span>
+          </label>
           <label style={{ display: "flex", alignItems: "center", gap: 8 }}>
             <span>Dub lang</span>
             <input
@@ -7556,6 +8002,12 @@ export function SubtitleEditorPage({
           <div style={{ marginTop: 12 }}>
             <div className="row" style={{ alignItems: "center", gap: 10, flexWrap: "wrap" }}>
               <div style={{ fontSize: 12, opacity: 0.85 }}>Speaker voices (pyttsx3)</div>
+              <input
+                value={pyttsx3LanguageFilter}
+                onChange={(e) => setPyttsx3LanguageFilter(e.currentTarget.value)}
+                placeholder="Language (e.g. en)"
+                style={{ width: 140 }}
+              />
               <button type="button" disabled={pyttsx3VoicesBusy} onClick={loadPyttsx3Voices}>
                 {pyttsx3Voices.length ? "Reload voices" : "Load voices"}
               </button>
@@ -7612,7 +8064,7 @@ export function SubtitleEditorPage({
                         ) : null}
                         {pyttsx3Voices.map((v) => (
                           <option key={v.id} value={v.id}>
-                            {v.name}
+                            {v.lang ? `${v.name} (${v.lang})` : v.name}
                           </option>
                         ))}
                       </select>
@@ -10029,6 +10481,9 @@ export function SubtitleEditorPage({
                 <button type="button" disabled={batchQueueBusy} onClick={queueLocalizationBatch}>
                   Queue batch dubbing
                 </button>
+                <button type="button" disabled={batchQueueBusy} onClick={queueAsrBatch}>
+                  Queue batch ASR
+                </button>
               </div>
               <div
                 style={{
@@ -18,7 +18,7 @@ fn wait_for_job(paths: &AppPaths, job_id: &str, timeout: Duration) -> Result<job
     let mut last_status = String::new();
 
     loop {
-        let rows = jobs::list_jobs(paths, 500, 0)?;
+        let rows = jobs::list_jobs(paths, 500, 0, None, None)?;
         if let Some(job) = rows.into_iter().find(|j| j.id == job_id) {
             let status = format!("{:?}", job.status);
             if status != last_status {
@@ -148,7 +148,7 @@ fn main() -> Result<()> {
     let python = tools::python_toolchain_status(&paths);
     if !python.venv_exists {
         eprintln!("setting up Python toolchain...");
-        let _ = tools::install_python_toolchain(&paths)?;
+        let _ = tools::install_python_toolchain(&paths, false)?;
     }
 
     let vp = tools::tts_voice_preserving_local_v1_pack_status(&paths);
@@ -174,8 +174,12 @@ fn main() -> Result<()> {
 
     let runner = jobs::start_runner(paths.clone())?;
 
-    let import_job =
-        jobs::enqueue_import_local(&paths, media_path.to_string_lossy().to_string(), true)?;
+    let import_job = jobs::enqueue_import_local(
+        &paths,
+        media_path.to_string_lossy().to_string(),
+        true,
+        false,
+    )?;
     wait_for_job(&paths, &import_job.id, Duration::from_secs(20 * 60))?;
 
     let canonical_media = std::fs::canonicalize(&media_path)?;
@@ -188,7 +192,7 @@ fn main() -> Result<()> {
         })?;
     eprintln!("imported item_id={}", item.id);
 
-    let asr_job = jobs::enqueue_asr_local(&paths, item.id.clone(), Some("ko".to_string()))?;
+    let asr_job = jobs::enqueue_asr_local(&paths, item.id.clone(), Some("ko".to_string()), None)?;
     wait_for_job(&paths, &asr_job.id, Duration::from_secs(45 * 60))?;
 
     let tracks = subtitle_tracks::list_tracks(&paths, &item.id)?;
@@ -289,7 +293,7 @@ fn main() -> Result<()> {
     wait_for_job(&paths, &dub_job.id, Duration::from_secs(60 * 60))?;
 
     let vp_report = paths
-        .job_artifacts_dir(&dub_job.id)
+        .job_artifacts_dir(&dub_job.id, dub_job.content_key.as_deref())
         .join("tts_voice_preserving_report.json");
     let report_json = std::fs::read_to_string(&vp_report).map_err(|e| {
         EngineError::InstallFailed(format!(
@@ -18,7 +18,7 @@ fn repo_root_from_engine_dir(engine_dir: &Path) -> PathBuf {
 fn wait_for_job(paths: &AppPaths, job_id: &str, timeout: Duration) -> Result<jobs::JobRow> {
     let start = Instant::now();
     loop {
-        let rows = jobs::list_jobs(paths, 500, 0)?;
+        let rows = jobs::list_jobs(paths, 500, 0, None, None)?;
         if let Some(job) = rows.into_iter().find(|value| value.id == job_id) {
             match job.status {
                 jobs::JobStatus::Succeeded => return Ok(job),
@@ -51,7 +51,7 @@ fn wait_for_batch_to_idle(
 ) -> Result<Vec<jobs::JobRow>> {
     let start = Instant::now();
     loop {
-        let rows = jobs::list_jobs(paths, 1000, 0)?
+        let rows = jobs::list_jobs(paths, 1000, 0, None, None)?
             .into_iter()
             .filter(|job| job.batch_id.as_deref() == Some(batch_id))
             .collect::<Vec<_>>();
@@ -218,7 +218,7 @@ fn main() -> Result<()> {
     }
     let python = tools::python_toolchain_status(&paths);
     if !python.venv_exists {
-        let _ = tools::install_python_toolchain(&paths)?;
+        let _ = tools::install_python_toolchain(&paths, false)?;
     }
     let vp = tools::tts_voice_preserving_local_v1_pack_status(&paths);
     if !vp.installed {
@@ -238,8 +238,12 @@ fn main() -> Result<()> {
 
     let runner = jobs::start_runner(paths.clone())?;
 
-    let import_job =
-        jobs::enqueue_import_local(&paths, media_path.to_string_lossy().to_string(), true)?;
+    let import_job = jobs::enqueue_import_local(
+        &paths,
+        media_path.to_string_lossy().to_string(),
+        true,
+        false,
+    )?;
     wait_for_job(&paths, &import_job.id, Duration::from_secs(20 * 60))?;
 
     let canonical_media = std::fs::canonicalize(&media_path)?;
@@ -260,6 +264,7 @@ fn main() -> Result<()> {
             output_mode: None,
             queue_export_pack: false,
             queue_qc: false,
+            speaker_count: Default::default(),
         },
     )?;
     wait_for_batch_to_idle(&paths, &first_run.batch_id, Duration::from_secs(90 * 60))?;
@@ -308,6 +313,7 @@ fn main() -> Result<()> {
             output_mode: None,
             queue_export_pack: false,
             queue_qc: true,
+            speaker_count: Default::default(),
         },
     )?;
     let final_batch =
@@ -333,7 +339,7 @@ fn main() -> Result<()> {
         })?;
 
     let voice_report = paths
-        .job_artifacts_dir(&voice_job.id)
+        .job_artifacts_dir(&voice_job.id, voice_job.content_key.as_deref())
         .join("tts_voice_preserving_report.json");
     let mix_wav = paths
         .derived_item_dir(&item.id)
@@ -1,8 +1,10 @@
 #include "whisper.h"
 
 #include <cctype>
+#include <cmath>
 #include <cstring>
 #include <cstdlib>
+#include <cstdio>
 #include <string>
 
 static thread_local std::string g_last_error;
@@ -48,18 +50,38 @@ static std::string json_escape(const std::string &in) {
     return out;
 }
 
+// Called with the end time (ms) of the newest decoded segment.
+typedef void (*ytf_whisper_progress_fn)(int64_t end_ms, void *user_data);
+
+struct progress_forward {
+    ytf_whisper_progress_fn cb;
+    void *user_data;
+};
+
+static void forward_new_segments(whisper_context *ctx, whisper_state * /*state*/, int n_new,
+                                 void *user_data) {
+    const progress_forward *forward = static_cast<const progress_forward *>(user_data);
+    const int n_segments = whisper_full_n_segments(ctx);
+    if (!forward || !forward->cb || n_new <= 0 || n_segments <= 0) {
+        return;
+    }
+    forward->cb(whisper_full_get_segment_t1(ctx, n_segments - 1) * 10, forward->user_data);
+}
+
 extern "C" {
 
 const char *ytf_whisper_last_error() { return g_last_error.c_str(); }
 
 void ytf_whisper_free_string(char *s) { std::free(s); }
 
-char *ytf_whisper_transcribe_json(const char *model_path,
-                                 const float *samples,
-                                 int n_samples,
-                                 const char *language,
-                                 int n_threads,
-                                 bool translate) {
+char *ytf_whisper_transcribe_json_progress(const char *model_path,
+                                          const float *samples,
+                                          int n_samples,
+                                          const char *language,
+                                          int n_threads,
+                                          bool translate,
+                                          ytf_whisper_progress_fn progress_cb,
+                                          void *progress_user_data) {
     g_last_error.clear();
 
     if (!model_path || !samples || n_samples <= 0) {
@@ -96,6 +118,12 @@ char *ytf_whisper_transcribe_json(const char *model_path,
         wparams.detect_language = false;
     }
 
+    progress_forward forward{progress_cb, progress_user_data};
+    if (progress_cb) {
+        wparams.new_segment_callback = forward_new_segments;
+        wparams.new_segment_callback_user_data = &forward;
+    }
+
     const int rc = whisper_full(ctx, wparams, samples, n_samples);
     if (rc != 0) {
         whisper_free(ctx);
@@ -107,6 +135,7 @@ char *ytf_whisper_transcribe_json(const char *model_path,
     const char *detected_lang = whisper_lang_str(lang_id);
 
     const int n_segments = whisper_full_n_segments(ctx);
+    const whisper_token token_eot = whisper_token_eot(ctx);
     std::string json;
     json.reserve(static_cast<size_t>(n_segments) * 128 + 64);
     json += "{";
@@ -135,7 +164,27 @@ char *ytf_whisper_transcribe_json(const char *model_path,
         json += std::to_string(t1 * 10);
         json += ",\"text\":\"";
         json += json_escape(text);
-        json += "\"}";
+        json += "\"";
+
+        // Mean log probability of the segment's text tokens (special tokens sort after EOT).
+        double logprob_sum = 0.0;
+        int logprob_count = 0;
+        const int n_tokens = whisper_full_n_tokens(ctx, i);
+        for (int j = 0; j < n_tokens; j++) {
+            const whisper_token_data token = whisper_full_get_token_data(ctx, i, j);
+            if (token.id >= token_eot || !std::isfinite(token.plog)) {
+                continue;
+            }
+            logprob_sum += token.plog;
+            logprob_count++;
+        }
+        if (logprob_count > 0) {
+            char buf[32];
+            std::snprintf(buf, sizeof(buf), "%.4f", logprob_sum / logprob_count);
+            json += ",\"avg_logprob\":";
+            json += buf;
+        }
+        json += "}";
     }
 
     json += "]}";
@@ -151,4 +200,14 @@ char *ytf_whisper_transcribe_json(const char *model_path,
     return out;
 }
 
+char *ytf_whisper_transcribe_json(const char *model_path,
+                                 const float *samples,
+                                 int n_samples,
+                                 const char *language,
+                                 int n_threads,
+                                 bool translate) {
+    return ytf_whisper_transcribe_json_progress(
+        model_path, samples, n_samples, language, n_threads, translate, nullptr, nullptr);
+}
+
 } // extern "C"
@@ -5,7 +5,10 @@ use crate::{EngineError, Result};
 use hound::SampleFormat;
 use serde::{Deserialize, Serialize};
 use std::path::Path;
-use std::{ffi::CStr, ffi::CString, os::raw::c_char};
+use std::{
+    ffi::{c_void, CStr, CString},
+    os::raw::c_char,
+};
 
 #[derive(Debug, Clone, Serialize)]
 pub struct WhisperTranscriptStats {
@@ -20,13 +23,18 @@ pub struct WhisperTranscriptResult {
     pub stats: WhisperTranscriptStats,
 }
 
+/// `progress_cb` receives the transcribed fraction of the audio (0..1) as whisper emits segments.
 pub fn transcribe_whisper_wav_16k_mono(
     paths: &AppPaths,
     model_id: &str,
     wav_path: &Path,
     lang: Option<&str>,
+    progress_cb: Option<impl Fn(f32)>,
 ) -> Result<SubtitleDocument> {
-    Ok(transcribe_whisper_wav_16k_mono_with_stats(paths, model_id, wav_path, lang)?.doc)
+    Ok(
+        transcribe_whisper_wav_16k_mono_with_stats(paths, model_id, wav_path, lang, progress_cb)?
+            .doc,
+    )
 }
 
 pub fn transcribe_whisper_wav_16k_mono_with_stats(
@@ -34,6 +42,7 @@ pub fn transcribe_whisper_wav_16k_mono_with_stats(
     model_id: &str,
     wav_path: &Path,
     lang: Option<&str>,
+    progress_cb: Option<impl Fn(f32)>,
 ) -> Result<WhisperTranscriptResult> {
     let model_path = resolve_whisper_model_path(paths, model_id)?;
     let audio = load_wav_16k_mono_f32(wav_path)?;
@@ -50,8 +59,12 @@ pub fn transcribe_whisper_wav_16k_mono_with_stats(
         .transpose()
         .map_err(|_| EngineError::InstallFailed("language contains NUL byte".to_string()))?;
 
+    let progress = progress_cb.as_ref().map(|cb| WhisperProgress {
+        duration_ms: (audio.len() as i64) * 1000 / WHISPER_SAMPLE_RATE,
+        cb: cb as &dyn Fn(f32),
+    });
     let out_ptr = unsafe {
-        ytf_whisper_transcribe_json(
+        ytf_whisper_transcribe_json_progress(
             model_path_c.as_ptr(),
             audio.as_ptr(),
             audio.len() as i32,
@@ -61,6 +74,13 @@ pub fn transcribe_whisper_wav_16k_mono_with_stats(
                 .unwrap_or(std::ptr::null()),
             threads,
             false,
+            progress
+                .as_ref()
+                .map(|_| whisper_progress_trampoline as WhisperProgressFn),
+            progress
+                .as_ref()
+                .map(|p| p as *const WhisperProgress<'_> as *mut c_void)
+                .unwrap_or(std::ptr::null_mut()),
         )
     };
 
@@ -82,7 +102,18 @@ pub fn transcribe_whisper_wav_16k_mono_with_stats(
     unsafe { ytf_whisper_free_string(out_ptr) };
 
     let parsed: WhisperJson = serde_json::from_str(&json)?;
-    Ok(whisper_json_to_document(parsed, "source", lang))
+    let mut result = whisper_json_to_document(parsed, "source", lang);
+    let metadata = &mut result.doc.metadata;
+    metadata.insert("model_id".to_string(), model_id.into());
+    metadata.insert(
+        "lang_detected".to_string(),
+        result.stats.detected_lang.clone().into(),
+    );
+    metadata.insert(
+        "segment_count".to_string(),
+        result.stats.usable_segment_count.into(),
+    );
+    Ok(result)
 }
 
 pub fn translate_whisper_wav_16k_mono_to_en(
@@ -150,6 +181,34 @@ pub fn translate_whisper_wav_16k_mono_to_en_with_stats(
     Ok(whisper_json_to_document(parsed, "translated", Some("en")))
 }
 
+const WHISPER_SAMPLE_RATE: i64 = 16_000;
+
+type WhisperProgressFn = extern "C" fn(end_ms: i64, user_data: *mut c_void);
+
+struct WhisperProgress<'a> {
+    duration_ms: i64,
+    cb: &'a dyn Fn(f32),
+}
+
+fn whisper_progress_fraction(end_ms: i64, duration_ms: i64) -> f32 {
+    if duration_ms <= 0 {
+        return 0.0;
+    }
+    (end_ms as f64 / duration_ms as f64).clamp(0.0, 1.0) as f32
+}
+
+extern "C" fn whisper_progress_trampoline(end_ms: i64, user_data: *mut c_void) {
+    if user_data.is_null() {
+        return;
+    }
+    // SAFETY: `user_data` points at the `WhisperProgress` kept alive for the whole FFI call.
+    let progress = unsafe { &*(user_data as *const WhisperProgress<'_>) };
+    // Unwinding into C++ is undefined behaviour; a panicking callback just loses the update.
+    let _ = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        (progress.cb)(whisper_progress_fraction(end_ms, progress.duration_ms))
+    }));
+}
+
 #[derive(Debug, Deserialize)]
 struct WhisperJson {
     lang: Option<String>,
@@ -161,6 +220,8 @@ struct WhisperJsonSegment {
     start_ms: i64,
     end_ms: i64,
     text: String,
+    #[serde(default)]
+    avg_logprob: Option<f32>,
 }
 
 fn normalize_lang(value: Option<&str>) -> Option<String> {
@@ -197,6 +258,7 @@ fn whisper_json_to_document(
             end_ms,
             text,
             speaker: None,
+            confidence: seg.avg_logprob,
         });
     }
 
@@ -216,6 +278,7 @@ fn whisper_json_to_document(
             kind: kind.to_string(),
             lang,
             segments,
+            metadata: Default::default(),
         },
         stats: WhisperTranscriptStats {
             detected_lang,
@@ -284,6 +347,25 @@ fn load_wav_16k_mono_f32(path: &Path) -> Result<Vec<f32>> {
 mod tests {
     use super::*;
 
+    #[test]
+    fn whisper_progress_trampoline_reports_audio_fraction() {
+        assert_eq!(whisper_progress_fraction(30_000, 60_000), 0.5);
+        assert_eq!(whisper_progress_fraction(90_000, 60_000), 1.0);
+        assert_eq!(whisper_progress_fraction(1_000, 0), 0.0);
+
+        let seen = std::cell::RefCell::new(Vec::new());
+        let cb = |fraction: f32| seen.borrow_mut().push(fraction);
+        let progress = WhisperProgress {
+            duration_ms: 10_000,
+            cb: &cb,
+        };
+        let user_data = &progress as *const WhisperProgress<'_> as *mut c_void;
+        whisper_progress_trampoline(2_500, user_data);
+        whisper_progress_trampoline(10_000, user_data);
+        whisper_progress_trampoline(5_000, std::ptr::null_mut());
+        assert_eq!(*seen.borrow(), vec![0.25, 1.0]);
+    }
+
     #[test]
     fn whisper_json_to_document_reports_raw_and_usable_segments() {
         let result = whisper_json_to_document(
@@ -294,11 +376,13 @@ mod tests {
                         start_ms: -25,
                         end_ms: 250,
                         text: "   ".to_string(),
+                        avg_logprob: None,
                     },
                     WhisperJsonSegment {
                         start_ms: 500,
                         end_ms: 400,
                         text: " hello ".to_string(),
+                        avg_logprob: None,
                     },
                 ],
             },
@@ -344,6 +428,17 @@ extern "C" {
         translate: bool,
     ) -> *mut c_char;
 
+    fn ytf_whisper_transcribe_json_progress(
+        model_path: *const c_char,
+        samples: *const f32,
+        n_samples: i32,
+        language: *const c_char,
+        n_threads: i32,
+        translate: bool,
+        progress_cb: Option<WhisperProgressFn>,
+        progress_user_data: *mut c_void,
+    ) -> *mut c_char;
+
     fn ytf_whisper_free_string(ptr: *mut c_char);
 
     fn ytf_whisper_last_error() -> *const c_char;
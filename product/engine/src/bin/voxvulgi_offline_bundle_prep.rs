@@ -135,7 +135,7 @@ fn run() -> Result<()> {
         let py = tools::python_toolchain_status(&paths);
         if !py.venv_exists {
             println!("setting up python toolchain (venv)...");
-            let next = tools::install_python_toolchain(&paths)?;
+            let next = tools::install_python_toolchain(&paths, false)?;
             if !next.venv_exists {
                 return Err(EngineError::InstallFailed(
                     "python toolchain install did not result in venv_exists=true".to_string(),
@@ -349,6 +349,14 @@ fn export_offline_payload(paths: &AppPaths, out_dir: &Path) -> Result<()> {
         ),
     )?;
 
+    // Upgrade to schema v2 so installs can skip files whose content already matches.
+    let manifest_path = out_dir.join("manifest.json");
+    let v2 = tools::tools_generate_offline_bundle_manifest_v2(paths, out_dir, &manifest_path)?;
+    println!(
+        "manifest v2: files={} bytes={}",
+        v2.file_count, v2.total_bytes
+    );
+
     Ok(())
 }
 
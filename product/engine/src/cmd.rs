@@ -1,5 +1,31 @@
-use std::ffi::OsStr;
-use std::process::Command;
+use std::ffi::{OsStr, OsString};
+use std::io::Read;
+use std::path::Path;
+use std::process::{Child, Command, Output, Stdio};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use crate::{EngineError, Result};
+
+const RUN_WITH_TIMEOUT_POLL_INTERVAL_MS: u64 = 100;
+/// Host variables passed through to [`sanitized_command`] children.
+#[cfg_attr(not(unix), allow(dead_code))]
+const SANITIZED_ENV_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "TEMP",
+    "TMP",
+    "TMPDIR",
+    "USERPROFILE",
+    "APPDATA",
+    "LOCALAPPDATA",
+    "SystemRoot",
+    "SystemDrive",
+    "PYTHONNOUSERSITE",
+    "LANG",
+    "LC_ALL",
+    "LC_CTYPE",
+];
 
 pub fn command(program: impl AsRef<OsStr>) -> Command {
     let mut cmd = Command::new(program);
@@ -7,6 +33,120 @@ pub fn command(program: impl AsRef<OsStr>) -> Command {
     cmd
 }
 
+/// Like [`command`], but on Unix the child only inherits `SANITIZED_ENV_ALLOWLIST`, so host
+/// secrets such as `AWS_SECRET_ACCESS_KEY` do not reach model scripts. Job-specific variables are
+/// set by the caller afterwards.
+pub fn sanitized_command(exe: &Path) -> Command {
+    sanitized_command_from(exe, |key| std::env::var_os(key))
+}
+
+/// [`sanitized_command`] reading host variables through `host_var`.
+#[cfg_attr(not(unix), allow(unused_variables))]
+fn sanitized_command_from(exe: &Path, host_var: impl Fn(&str) -> Option<OsString>) -> Command {
+    let mut cmd = command(exe);
+    #[cfg(unix)]
+    {
+        cmd.env_clear();
+        for key in SANITIZED_ENV_ALLOWLIST {
+            if let Some(value) = host_var(key) {
+                cmd.env(key, value);
+            }
+        }
+    }
+    cmd
+}
+
+/// Runs `cmd` to completion, capturing stdout/stderr like [`Command::output`]. If it is still
+/// running after `timeout`, the child (and on Unix its whole process group) is killed and an
+/// `ExternalToolFailed` error with `stderr = "timeout"` is returned.
+pub fn run_with_timeout(mut cmd: Command, timeout: Duration) -> Result<Output> {
+    let tool = Path::new(cmd.get_program())
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| cmd.get_program().to_string_lossy().to_string());
+
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        cmd.process_group(0);
+    }
+
+    let mut child = cmd.spawn()?;
+    let stdout_reader = spawn_pipe_reader(child.stdout.take());
+    let stderr_reader = spawn_pipe_reader(child.stderr.take());
+
+    let started = Instant::now();
+    let status = loop {
+        match child.try_wait() {
+            Ok(Some(status)) => break status,
+            Ok(None) => {}
+            Err(e) => {
+                kill_process_tree(&mut child);
+                return Err(e.into());
+            }
+        }
+        let elapsed = started.elapsed();
+        if elapsed >= timeout {
+            kill_process_tree(&mut child);
+            // Readers are left detached: a surviving grandchild could keep the pipes open.
+            return Err(EngineError::ExternalToolFailed {
+                tool,
+                code: None,
+                stderr: "timeout".to_string(),
+            });
+        }
+        thread::sleep(
+            Duration::from_millis(RUN_WITH_TIMEOUT_POLL_INTERVAL_MS).min(timeout - elapsed),
+        );
+    };
+
+    Ok(Output {
+        status,
+        stdout: join_pipe_reader(stdout_reader),
+        stderr: join_pipe_reader(stderr_reader),
+    })
+}
+
+fn spawn_pipe_reader(pipe: Option<impl Read + Send + 'static>) -> Option<JoinHandle<Vec<u8>>> {
+    let mut pipe = pipe?;
+    Some(thread::spawn(move || {
+        let mut buf = Vec::new();
+        let _ = pipe.read_to_end(&mut buf);
+        buf
+    }))
+}
+
+fn join_pipe_reader(handle: Option<JoinHandle<Vec<u8>>>) -> Vec<u8> {
+    handle.and_then(|h| h.join().ok()).unwrap_or_default()
+}
+
+/// Kills `child` and waits for it. On Windows `taskkill /T` takes its descendants too; on Unix
+/// they are reached only when the child leads its own process group, as in [`run_with_timeout`].
+#[cfg(unix)]
+pub(crate) fn kill_process_tree(child: &mut Child) {
+    // A child spawned as a group leader (see `run_with_timeout`) takes helpers it spawned, such
+    // as Python multiprocessing workers, down with it; otherwise the group kill is a no-op.
+    if let Ok(pid) = libc::pid_t::try_from(child.id()) {
+        unsafe {
+            libc::kill(-pid, libc::SIGKILL);
+        }
+    }
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+#[cfg(windows)]
+pub(crate) fn kill_process_tree(child: &mut Child) {
+    let pid = child.id().to_string();
+    let _ = command("taskkill")
+        .args(["/PID", &pid, "/T", "/F"])
+        .status();
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
 #[cfg(windows)]
 fn configure_for_background(cmd: &mut Command) {
     use std::os::windows::process::CommandExt;
@@ -18,3 +158,49 @@ fn configure_for_background(cmd: &mut Command) {
 
 #[cfg(not(windows))]
 fn configure_for_background(_cmd: &mut Command) {}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn run_with_timeout_captures_output_and_kills_on_timeout() {
+        let mut echo = command("sh");
+        echo.args(["-c", "echo hello; echo oops >&2"]);
+        let output = run_with_timeout(echo, Duration::from_secs(10)).expect("echo");
+        assert!(output.status.success());
+        assert_eq!(String::from_utf8_lossy(&output.stdout).trim(), "hello");
+        assert_eq!(String::from_utf8_lossy(&output.stderr).trim(), "oops");
+
+        let mut sleeper = command("sh");
+        sleeper.args(["-c", "sleep 30"]);
+        let started = Instant::now();
+        let err = run_with_timeout(sleeper, Duration::from_millis(300)).expect_err("timeout");
+        assert!(started.elapsed() < Duration::from_secs(10));
+        match err {
+            EngineError::ExternalToolFailed { tool, code, stderr } => {
+                assert_eq!(tool, "sh");
+                assert_eq!(code, None);
+                assert_eq!(stderr, "timeout");
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[test]
+    fn sanitized_command_drops_host_secrets() {
+        let host_var = |key: &str| match key {
+            "AWS_SECRET_ACCESS_KEY" => Some(OsString::from("voxvulgi-test-secret")),
+            _ => std::env::var_os(key),
+        };
+        let mut env = sanitized_command_from(Path::new("sh"), host_var);
+        env.args(["-c", "env"]);
+        let output = env.output().expect("env");
+
+        assert!(output.status.success());
+        let stdout = String::from_utf8_lossy(&output.stdout);
+        assert!(!stdout.contains("AWS_SECRET_ACCESS_KEY"));
+        assert!(!stdout.contains("voxvulgi-test-secret"));
+        assert!(stdout.lines().any(|line| line.starts_with("PATH=")));
+    }
+}
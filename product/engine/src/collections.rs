@@ -0,0 +1,245 @@
+use crate::paths::AppPaths;
+use crate::{db, EngineError, Result};
+use rusqlite::{params, OptionalExtension};
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+const COLLECTION_NAME_MAX_CHARS: usize = 100;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CollectionRow {
+    pub id: String,
+    pub name: String,
+    pub parent_id: Option<String>,
+    pub created_at_ms: i64,
+    pub item_count: usize,
+    pub child_count: usize,
+}
+
+pub fn create_collection(
+    paths: &AppPaths,
+    name: &str,
+    parent_id: Option<&str>,
+) -> Result<CollectionRow> {
+    let name = normalize_collection_name(name)?;
+    let parent_id = parent_id.map(str::trim).filter(|v| !v.is_empty());
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    if let Some(parent_id) = parent_id {
+        require_collection_conn(&conn, parent_id)?;
+    }
+
+    let id = Uuid::new_v4().to_string();
+    conn.execute(
+        "INSERT INTO collection (id, name, parent_id, created_at_ms) VALUES (?1, ?2, ?3, ?4)",
+        params![id, name, parent_id, now_ms()],
+    )?;
+    require_collection_conn(&conn, &id)
+}
+
+pub fn rename_collection(paths: &AppPaths, id: &str, name: &str) -> Result<CollectionRow> {
+    let name = normalize_collection_name(name)?;
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let changed = conn.execute(
+        "UPDATE collection SET name = ?1 WHERE id = ?2",
+        params![name, id],
+    )?;
+    if changed == 0 {
+        return Err(EngineError::not_found("collection", id));
+    }
+    require_collection_conn(&conn, id)
+}
+
+/// Removes the collection and its memberships; library items are untouched and direct
+/// sub-collections move up to the deleted collection's parent.
+pub fn delete_collection(paths: &AppPaths, id: &str) -> Result<()> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let tx = conn.unchecked_transaction()?;
+    let parent_id: Option<Option<String>> = tx
+        .query_row(
+            "SELECT parent_id FROM collection WHERE id = ?1",
+            params![id],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let Some(parent_id) = parent_id else {
+        return Err(EngineError::not_found("collection", id));
+    };
+    tx.execute(
+        "UPDATE collection SET parent_id = ?1 WHERE parent_id = ?2",
+        params![parent_id, id],
+    )?;
+    tx.execute(
+        "DELETE FROM collection_item WHERE collection_id = ?1",
+        params![id],
+    )?;
+    tx.execute("DELETE FROM collection WHERE id = ?1", params![id])?;
+    tx.commit()?;
+    Ok(())
+}
+
+/// Lists the direct children of `parent_id`, or the top-level collections when it is `None`.
+pub fn list_collections(paths: &AppPaths, parent_id: Option<&str>) -> Result<Vec<CollectionRow>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let parent_id = parent_id.map(str::trim).filter(|v| !v.is_empty());
+    let mut stmt = conn.prepare(
+        r#"
+SELECT
+  c.id,
+  c.name,
+  c.parent_id,
+  c.created_at_ms,
+  (SELECT COUNT(*) FROM collection_item ci WHERE ci.collection_id = c.id),
+  (SELECT COUNT(*) FROM collection child WHERE child.parent_id = c.id)
+FROM collection c
+WHERE c.parent_id IS ?1
+ORDER BY c.name COLLATE NOCASE ASC, c.created_at_ms ASC
+"#,
+    )?;
+    let rows = stmt
+        .query_map(params![parent_id], collection_from_row)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(rows)
+}
+
+/// Adds an item to a collection; adding an existing member is a no-op.
+pub fn add_item_to_collection(paths: &AppPaths, collection_id: &str, item_id: &str) -> Result<()> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    require_collection_conn(&conn, collection_id)?;
+    let item_exists: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM library_item WHERE id = ?1)",
+        params![item_id],
+        |row| row.get(0),
+    )?;
+    if !item_exists {
+        return Err(EngineError::not_found("library item", item_id));
+    }
+    conn.execute(
+        "INSERT OR IGNORE INTO collection_item (collection_id, item_id) VALUES (?1, ?2)",
+        params![collection_id, item_id],
+    )?;
+    Ok(())
+}
+
+pub fn remove_item_from_collection(
+    paths: &AppPaths,
+    collection_id: &str,
+    item_id: &str,
+) -> Result<()> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    conn.execute(
+        "DELETE FROM collection_item WHERE collection_id = ?1 AND item_id = ?2",
+        params![collection_id, item_id],
+    )?;
+    Ok(())
+}
+
+fn normalize_collection_name(name: &str) -> Result<String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(EngineError::InstallFailed(
+            "collection name cannot be empty".to_string(),
+        ));
+    }
+    Ok(name.chars().take(COLLECTION_NAME_MAX_CHARS).collect())
+}
+
+fn require_collection_conn(conn: &rusqlite::Connection, id: &str) -> Result<CollectionRow> {
+    conn.query_row(
+        r#"
+SELECT
+  c.id,
+  c.name,
+  c.parent_id,
+  c.created_at_ms,
+  (SELECT COUNT(*) FROM collection_item ci WHERE ci.collection_id = c.id),
+  (SELECT COUNT(*) FROM collection child WHERE child.parent_id = c.id)
+FROM collection c
+WHERE c.id = ?1
+"#,
+        params![id],
+        collection_from_row,
+    )
+    .optional()?
+    .ok_or_else(|| EngineError::not_found("collection", id))
+}
+
+fn collection_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<CollectionRow> {
+    Ok(CollectionRow {
+        id: row.get(0)?,
+        name: row.get(1)?,
+        parent_id: row.get(2)?,
+        created_at_ms: row.get(3)?,
+        item_count: row.get::<_, i64>(4)?.max(0) as usize,
+        child_count: row.get::<_, i64>(5)?.max(0) as usize,
+    })
+}
+
+fn now_ms() -> i64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::library;
+
+    #[test]
+    fn collections_nest_and_delete_without_touching_items() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let conn = db::open(&paths).expect("open");
+        db::migrate(&conn).expect("migrate");
+        conn.execute(
+            "INSERT INTO library_item (id, created_at_ms, source_type, source_uri, title, media_path) VALUES ('item-1', 1, 'local', 'file:///a.mp4', 'A', '/a.mp4')",
+            [],
+        )
+        .expect("insert item");
+        drop(conn);
+
+        let root = create_collection(&paths, " Shows ", None).expect("root");
+        assert_eq!(root.name, "Shows");
+        let child = create_collection(&paths, "Season 1", Some(&root.id)).expect("child");
+        let grandchild = create_collection(&paths, "Extras", Some(&child.id)).expect("grand");
+        assert!(create_collection(&paths, "   ", None).is_err());
+        assert!(create_collection(&paths, "Orphan", Some("missing")).is_err());
+
+        add_item_to_collection(&paths, &child.id, "item-1").expect("add");
+        add_item_to_collection(&paths, &child.id, "item-1").expect("add twice");
+        assert!(add_item_to_collection(&paths, &child.id, "missing").is_err());
+        let page = library::list_items_in_collection(&paths, &child.id, 10, 0).expect("page");
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.items[0].id, "item-1");
+
+        let renamed = rename_collection(&paths, &child.id, "Season One").expect("rename");
+        assert_eq!(renamed.item_count, 1);
+        assert_eq!(renamed.child_count, 1);
+        assert_eq!(
+            list_collections(&paths, None).expect("top"),
+            vec![CollectionRow {
+                child_count: 1,
+                ..root.clone()
+            }]
+        );
+
+        delete_collection(&paths, &child.id).expect("delete");
+        let children = list_collections(&paths, Some(&root.id)).expect("children");
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].id, grandchild.id);
+        assert_eq!(library::count_items(&paths).expect("count"), 1);
+        assert!(delete_collection(&paths, &child.id).is_err());
+
+        add_item_to_collection(&paths, &root.id, "item-1").expect("add root");
+        remove_item_from_collection(&paths, &root.id, "item-1").expect("remove");
+        let page = library::list_items_in_collection(&paths, &root.id, 10, 0).expect("empty");
+        assert_eq!(page.total_count, 0);
+    }
+}
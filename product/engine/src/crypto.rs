@@ -0,0 +1,154 @@
+//! At-rest encryption for job secrets.
+//!
+//! [`generate_machine_key`] returns a random 32-byte key persisted under the config dir rather
+//! than one stretched with PBKDF2 from the database's creation time: that timestamp is guessable
+//! and changes whenever the database is recreated, which would orphan every stored secret.
+//! Because the key lives on disk, the function returns a `Result`. AES-128-GCM uses the first
+//! 16 key bytes.
+
+use crate::paths::AppPaths;
+use crate::{EngineError, Result};
+use aes_gcm::aead::rand_core::RngCore;
+use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+use aes_gcm::{Aes128Gcm, Key, Nonce};
+use std::collections::HashMap;
+use std::io::Write;
+use std::path::PathBuf;
+use std::sync::{Mutex, OnceLock};
+
+/// Prefix of every encrypted secret; files without it are legacy plaintext.
+const SECRET_MAGIC: &[u8] = b"VVS1";
+const NONCE_LEN: usize = 12;
+const AES_KEY_LEN: usize = 16;
+pub const SECRET_KEY_LEN: usize = 32;
+
+pub type SecretKey = [u8; SECRET_KEY_LEN];
+
+fn cipher(key: &SecretKey) -> Aes128Gcm {
+    Aes128Gcm::new(Key::<Aes128Gcm>::from_slice(&key[..AES_KEY_LEN]))
+}
+
+/// Encrypts `data` with AES-128-GCM as `magic || nonce || ciphertext+tag`.
+pub fn encrypt_secret(data: &[u8], key: &SecretKey) -> Vec<u8> {
+    let nonce = Aes128Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher(key)
+        .encrypt(&nonce, data)
+        .expect("AES-GCM only fails for plaintexts over 64 GiB");
+    let mut out = Vec::with_capacity(SECRET_MAGIC.len() + NONCE_LEN + ciphertext.len());
+    out.extend_from_slice(SECRET_MAGIC);
+    out.extend_from_slice(&nonce);
+    out.extend_from_slice(&ciphertext);
+    out
+}
+
+/// Reverses [`encrypt_secret`]; fails on a missing prefix, a wrong key, or tampered bytes.
+pub fn decrypt_secret(ciphertext: &[u8], key: &SecretKey) -> Result<Vec<u8>> {
+    let invalid = || EngineError::InvalidInput {
+        message: "secret could not be decrypted".to_string(),
+    };
+    let body = ciphertext.strip_prefix(SECRET_MAGIC).ok_or_else(invalid)?;
+    if body.len() < NONCE_LEN {
+        return Err(invalid());
+    }
+    let (nonce, sealed) = body.split_at(NONCE_LEN);
+    cipher(key)
+        .decrypt(Nonce::from_slice(nonce), sealed)
+        .map_err(|_| invalid())
+}
+
+/// True when `bytes` carry the [`encrypt_secret`] prefix.
+pub fn is_encrypted_secret(bytes: &[u8]) -> bool {
+    bytes.starts_with(SECRET_MAGIC)
+}
+
+fn machine_key_cache() -> &'static Mutex<HashMap<PathBuf, SecretKey>> {
+    static CACHE: OnceLock<Mutex<HashMap<PathBuf, SecretKey>>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+/// Secret-encryption key for this app data dir: a random key kept in
+/// [`AppPaths::secret_key_path`], created on first use and cached per process. It keeps secrets
+/// out of casual copies of `secrets/`; it does not stop code running as the same user.
+pub fn generate_machine_key(paths: &AppPaths) -> Result<SecretKey> {
+    let mut cache = machine_key_cache()
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    if let Some(key) = cache.get(&paths.base_dir) {
+        return Ok(*key);
+    }
+    let key = read_or_create_key_file(paths)?;
+    cache.insert(paths.base_dir.clone(), key);
+    Ok(key)
+}
+
+fn read_or_create_key_file(paths: &AppPaths) -> Result<SecretKey> {
+    let path = paths.secret_key_path();
+    match std::fs::read(&path) {
+        Ok(bytes) => {
+            return SecretKey::try_from(bytes.as_slice()).map_err(|_| EngineError::InvalidInput {
+                message: format!(
+                    "secret key file {} is corrupt ({} bytes, expected {SECRET_KEY_LEN}); remove it to create a new key (stored secrets will need to be re-entered)",
+                    path.display(),
+                    bytes.len()
+                ),
+            });
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    let mut key: SecretKey = [0; SECRET_KEY_LEN];
+    OsRng.fill_bytes(&mut key);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let mut file = std::fs::OpenOptions::new()
+        .create_new(true)
+        .write(true)
+        .open(&path)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        file.set_permissions(std::fs::Permissions::from_mode(0o600))?;
+    }
+    file.write_all(&key)?;
+    file.sync_all()?;
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn secrets_round_trip_and_reject_wrong_key_or_tampering() {
+        let key = [7u8; SECRET_KEY_LEN];
+        let sealed = encrypt_secret(b"SID=abc", &key);
+        assert!(is_encrypted_secret(&sealed));
+        assert!(!sealed.windows(7).any(|w| w == b"SID=abc"));
+        assert_eq!(decrypt_secret(&sealed, &key).expect("decrypt"), b"SID=abc");
+        assert_ne!(encrypt_secret(b"SID=abc", &key), sealed);
+
+        assert!(decrypt_secret(&sealed, &[8u8; SECRET_KEY_LEN]).is_err());
+        let mut tampered = sealed.clone();
+        *tampered.last_mut().expect("tag") ^= 1;
+        assert!(decrypt_secret(&tampered, &key).is_err());
+        assert!(decrypt_secret(b"SID=abc", &key).is_err());
+    }
+
+    #[test]
+    fn machine_key_is_persisted_per_app_dir() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let first = generate_machine_key(&paths).expect("key");
+        assert_eq!(generate_machine_key(&paths).expect("cached"), first);
+        assert_eq!(read_or_create_key_file(&paths).expect("file"), first);
+
+        let other = tempfile::tempdir().expect("tempdir");
+        let other_paths = AppPaths::new(other.path().to_path_buf());
+        assert_ne!(generate_machine_key(&other_paths).expect("key"), first);
+
+        std::fs::write(other_paths.secret_key_path(), b"short").expect("corrupt");
+        assert!(read_or_create_key_file(&other_paths).is_err());
+    }
+}
@@ -1,9 +1,10 @@
 use crate::paths::AppPaths;
 use crate::Result;
-use rusqlite::{Connection, OpenFlags};
-use std::time::Duration;
+use rusqlite::{Connection, OpenFlags, OptionalExtension};
+use serde::Serialize;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
 
-const CURRENT_SCHEMA_VERSION: u32 = 11;
+pub const CURRENT_SCHEMA_VERSION: u32 = 35;
 
 struct MigrationStep {
     version: u32,
@@ -20,12 +21,115 @@ const MIGRATION_STEPS: &[MigrationStep] = &[
         apply: apply_schema_v10,
     },
     MigrationStep {
-        version: CURRENT_SCHEMA_VERSION,
+        version: 11,
         apply: apply_schema_v11,
     },
+    MigrationStep {
+        version: 12,
+        apply: apply_schema_v12,
+    },
+    MigrationStep {
+        version: 13,
+        apply: apply_schema_v13,
+    },
+    MigrationStep {
+        version: 14,
+        apply: apply_schema_v14,
+    },
+    MigrationStep {
+        version: 15,
+        apply: apply_schema_v15,
+    },
+    MigrationStep {
+        version: 16,
+        apply: apply_schema_v16,
+    },
+    MigrationStep {
+        version: 17,
+        apply: apply_schema_v17,
+    },
+    MigrationStep {
+        version: 18,
+        apply: apply_schema_v18,
+    },
+    MigrationStep {
+        version: 19,
+        apply: apply_schema_v19,
+    },
+    MigrationStep {
+        version: 20,
+        apply: apply_schema_v20,
+    },
+    MigrationStep {
+        version: 21,
+        apply: apply_schema_v21,
+    },
+    MigrationStep {
+        version: 22,
+        apply: apply_schema_v22,
+    },
+    MigrationStep {
+        version: 23,
+        apply: apply_schema_v23,
+    },
+    MigrationStep {
+        version: 24,
+        apply: apply_schema_v24,
+    },
+    MigrationStep {
+        version: 25,
+        apply: apply_schema_v25,
+    },
+    MigrationStep {
+        version: 26,
+        apply: apply_schema_v26,
+    },
+    MigrationStep {
+        version: 27,
+        apply: apply_schema_v27,
+    },
+    MigrationStep {
+        version: 28,
+        apply: apply_schema_v28,
+    },
+    MigrationStep {
+        version: 29,
+        apply: apply_schema_v29,
+    },
+    MigrationStep {
+        version: 30,
+        apply: apply_schema_v30,
+    },
+    MigrationStep {
+        version: 31,
+        apply: apply_schema_v31,
+    },
+    MigrationStep {
+        version: 32,
+        apply: apply_schema_v32,
+    },
+    MigrationStep {
+        version: 33,
+        apply: apply_schema_v33,
+    },
+    MigrationStep {
+        version: 34,
+        apply: apply_schema_v34,
+    },
+    MigrationStep {
+        version: CURRENT_SCHEMA_VERSION,
+        apply: apply_schema_v35,
+    },
 ];
 
+const DEFAULT_BUSY_TIMEOUT_MS: u64 = 5_000;
+
 pub fn open(paths: &AppPaths) -> Result<Connection> {
+    open_with_opts(paths, true, DEFAULT_BUSY_TIMEOUT_MS)
+}
+
+/// Opens the app database with an explicit journal mode and busy timeout.
+pub fn open_with_opts(paths: &AppPaths, wal: bool, busy_timeout_ms: u64) -> Result<Connection> {
     paths.ensure_dirs()?;
 
     let db_path = paths.db_dir().join("app.sqlite");
@@ -36,21 +140,41 @@ pub fn open(paths: &AppPaths) -> Result<Connection> {
             | OpenFlags::SQLITE_OPEN_FULL_MUTEX,
     )?;
 
-    conn.busy_timeout(Duration::from_secs(10))?;
-    conn.pragma_update(None, "journal_mode", "WAL")?;
+    conn.busy_timeout(Duration::from_millis(busy_timeout_ms))?;
+    if wal {
+        conn.pragma_update(None, "journal_mode", "WAL")?;
+        conn.pragma_update(None, "synchronous", "NORMAL")?;
+    }
     conn.pragma_update(None, "foreign_keys", "ON")?;
 
     Ok(conn)
 }
 
+/// Folds the WAL back into the main database file and truncates it.
+pub fn wal_checkpoint_truncate(conn: &Connection) -> Result<()> {
+    conn.query_row("PRAGMA wal_checkpoint(TRUNCATE)", [], |_| Ok(()))?;
+    Ok(())
+}
+
+/// Applies every step newer than the recorded version. Each applied step is recorded in
+/// `schema_migration` alongside `user_version`; an up-to-date database returns without touching
+/// the schema. Expired `meta` rows are pruned after a migration and by [`ensure_schema`].
 pub fn migrate(conn: &Connection) -> Result<()> {
     let mut current_version = schema_user_version(conn)?;
+    if current_version >= CURRENT_SCHEMA_VERSION {
+        return Ok(());
+    }
+    ensure_schema_migration_table(conn, current_version)?;
     for step in MIGRATION_STEPS {
         if current_version >= step.version {
             continue;
         }
         let tx = conn.unchecked_transaction()?;
         (step.apply)(&tx)?;
+        tx.execute(
+            "INSERT OR IGNORE INTO schema_migration(version, applied_at_ms) VALUES (?1, ?2)",
+            rusqlite::params![step.version, now_ms()],
+        )?;
         tx.pragma_update(None, "user_version", step.version)?;
         upsert_schema_version_meta(&tx, step.version)?;
         tx.commit()?;
@@ -59,14 +183,99 @@ pub fn migrate(conn: &Connection) -> Result<()> {
     if current_version == 0 {
         upsert_schema_version_meta(conn, 0)?;
     }
+    prune_expired_meta(conn)?;
+    Ok(())
+}
+
+/// Reads a `meta` value, treating rows past their `expires_at_ms` as absent.
+pub fn get_meta(conn: &Connection, key: &str) -> Result<Option<String>> {
+    let value = conn
+        .query_row(
+            "SELECT value FROM meta
+             WHERE key=?1 AND (expires_at_ms IS NULL OR expires_at_ms >= ?2)",
+            rusqlite::params![key, now_ms()],
+            |row| row.get::<_, String>(0),
+        )
+        .optional()?;
+    Ok(value)
+}
+
+/// Writes a `meta` value that never expires.
+pub fn set_meta(conn: &Connection, key: &str, value: &str) -> Result<()> {
+    conn.execute(
+        "INSERT INTO meta(key, value, expires_at_ms) VALUES(?1, ?2, NULL)
+         ON CONFLICT(key) DO UPDATE SET value=excluded.value, expires_at_ms=NULL",
+        rusqlite::params![key, value],
+    )?;
     Ok(())
 }
 
+/// Writes a `meta` value that [`get_meta`] stops returning `ttl_ms` from now.
+pub fn set_meta_with_ttl(conn: &Connection, key: &str, value: &str, ttl_ms: u64) -> Result<()> {
+    let expires_at_ms = now_ms().saturating_add(i64::try_from(ttl_ms).unwrap_or(i64::MAX));
+    conn.execute(
+        "INSERT INTO meta(key, value, expires_at_ms) VALUES(?1, ?2, ?3)
+         ON CONFLICT(key) DO UPDATE SET value=excluded.value, expires_at_ms=excluded.expires_at_ms",
+        rusqlite::params![key, value, expires_at_ms],
+    )?;
+    Ok(())
+}
+
+/// Deletes expired `meta` rows and returns how many were removed.
+pub fn prune_expired_meta(conn: &Connection) -> Result<usize> {
+    let removed = conn.execute(
+        "DELETE FROM meta WHERE expires_at_ms IS NOT NULL AND expires_at_ms < ?1",
+        [now_ms()],
+    )?;
+    Ok(removed)
+}
+
+/// Highest version recorded in `schema_migration`, falling back to `user_version` for databases
+/// that predate the table.
+pub fn schema_version(conn: &Connection) -> Result<u32> {
+    let has_table: bool = conn.query_row(
+        "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type='table' AND name='schema_migration')",
+        [],
+        |row| row.get(0),
+    )?;
+    if !has_table {
+        return schema_user_version(conn);
+    }
+    let version: Option<i64> =
+        conn.query_row("SELECT MAX(version) FROM schema_migration", [], |row| {
+            row.get(0)
+        })?;
+    Ok(version.unwrap_or(0).max(0) as u32)
+}
+
 pub fn schema_user_version(conn: &Connection) -> Result<u32> {
     let version = conn.pragma_query_value(None, "user_version", |row| row.get::<_, i32>(0))?;
     Ok(version.max(0) as u32)
 }
 
+/// Creates `schema_migration` and backfills steps already applied before it existed; their
+/// `applied_at_ms` is unknown and left NULL.
+fn ensure_schema_migration_table(conn: &Connection, applied_version: u32) -> Result<()> {
+    apply_schema_v19(conn)?;
+    for step in MIGRATION_STEPS {
+        if step.version > applied_version {
+            break;
+        }
+        conn.execute(
+            "INSERT OR IGNORE INTO schema_migration(version, applied_at_ms) VALUES (?1, NULL)",
+            [step.version],
+        )?;
+    }
+    Ok(())
+}
+
+fn now_ms() -> i64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as i64)
+        .unwrap_or(0)
+}
+
 fn upsert_schema_version_meta(conn: &Connection, version: u32) -> Result<()> {
     conn.execute(
         "INSERT INTO meta(key, value) VALUES('schema_version', ?1)
@@ -645,6 +854,270 @@ CREATE INDEX IF NOT EXISTS idx_localization_workspace_selected
     Ok(())
 }
 
+fn apply_schema_v12(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "library_item", "file_size_bytes", "INTEGER")?;
+
+    // File sizes only need a stat, so back-fill them here; duration/dimensions need ffprobe and
+    // are repaired on demand by `library::repair_item_metadata`.
+    let mut stmt = conn.prepare("SELECT id, media_path FROM library_item")?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    for (item_id, media_path) in rows {
+        if let Ok(meta) = std::fs::metadata(&media_path) {
+            conn.execute(
+                "UPDATE library_item SET file_size_bytes=?1 WHERE id=?2",
+                rusqlite::params![meta.len() as i64, item_id],
+            )?;
+        }
+    }
+    Ok(())
+}
+
+fn apply_schema_v13(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "job", "requeue_count", "INTEGER NOT NULL DEFAULT 0")?;
+    Ok(())
+}
+
+fn apply_schema_v14(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "subtitle_track", "content_hash", "TEXT")?;
+    Ok(())
+}
+
+fn apply_schema_v15(conn: &Connection) -> Result<()> {
+    ensure_column(
+        conn,
+        "youtube_subscription",
+        "filter_keywords_exclude",
+        "TEXT",
+    )?;
+    ensure_column(
+        conn,
+        "youtube_subscription",
+        "filter_keywords_include",
+        "TEXT",
+    )?;
+    Ok(())
+}
+
+fn apply_schema_v16(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "job", "content_key", "TEXT")?;
+    Ok(())
+}
+
+fn apply_schema_v17(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "job", "not_before_ms", "INTEGER")?;
+    Ok(())
+}
+
+fn apply_schema_v18(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "youtube_subscription", "last_error", "TEXT")?;
+    Ok(())
+}
+
+fn apply_schema_v19(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+CREATE TABLE IF NOT EXISTS schema_migration (
+  version INTEGER PRIMARY KEY,
+  applied_at_ms INTEGER
+);
+"#,
+    )?;
+    Ok(())
+}
+
+fn apply_schema_v20(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "library_item", "content_hash", "TEXT")?;
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_library_item_content_hash ON library_item(content_hash);",
+    )?;
+    Ok(())
+}
+
+fn apply_schema_v21(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+CREATE TABLE IF NOT EXISTS library_item_metadata (
+  item_id TEXT NOT NULL,
+  key TEXT NOT NULL,
+  value TEXT NOT NULL,
+  PRIMARY KEY (item_id, key),
+  FOREIGN KEY (item_id) REFERENCES library_item(id) ON DELETE CASCADE
+);
+"#,
+    )?;
+    Ok(())
+}
+
+fn apply_schema_v22(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+CREATE TABLE IF NOT EXISTS collection (
+  id TEXT PRIMARY KEY,
+  name TEXT NOT NULL,
+  parent_id TEXT,
+  created_at_ms INTEGER NOT NULL,
+  FOREIGN KEY (parent_id) REFERENCES collection(id) ON DELETE SET NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_collection_parent ON collection(parent_id, name);
+
+CREATE TABLE IF NOT EXISTS collection_item (
+  collection_id TEXT NOT NULL,
+  item_id TEXT NOT NULL,
+  PRIMARY KEY (collection_id, item_id),
+  FOREIGN KEY (collection_id) REFERENCES collection(id) ON DELETE CASCADE,
+  FOREIGN KEY (item_id) REFERENCES library_item(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_collection_item_item ON collection_item(item_id);
+"#,
+    )?;
+    Ok(())
+}
+
+fn apply_schema_v23(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "subtitle_track", "notes", "TEXT")?;
+    Ok(())
+}
+
+fn apply_schema_v24(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "subtitle_track", "parent_track_id", "TEXT")?;
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_subtitle_track_parent ON subtitle_track(parent_track_id);",
+    )?;
+    Ok(())
+}
+
+fn apply_schema_v25(conn: &Connection) -> Result<()> {
+    ensure_column(
+        conn,
+        "youtube_subscription",
+        "max_items_per_refresh",
+        "INTEGER",
+    )?;
+    Ok(())
+}
+
+fn apply_schema_v26(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+CREATE TABLE IF NOT EXISTS library_item_history (
+  id TEXT PRIMARY KEY,
+  item_id TEXT NOT NULL,
+  event_type TEXT NOT NULL,
+  job_id TEXT,
+  track_id TEXT,
+  details_json TEXT NOT NULL,
+  created_at_ms INTEGER NOT NULL,
+  FOREIGN KEY (item_id) REFERENCES library_item(id) ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_library_item_history_item
+  ON library_item_history(item_id, created_at_ms);
+"#,
+    )?;
+    Ok(())
+}
+
+fn apply_schema_v27(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_job_item_id ON job(item_id, created_at_ms DESC);",
+    )?;
+    Ok(())
+}
+
+fn apply_schema_v28(conn: &Connection) -> Result<()> {
+    ensure_column(
+        conn,
+        "youtube_subscription",
+        "auto_max_items",
+        "INTEGER NOT NULL DEFAULT 0",
+    )?;
+    ensure_column(conn, "youtube_subscription", "avg_posts_per_day", "REAL")?;
+    ensure_column(
+        conn,
+        "youtube_subscription",
+        "last_refreshed_at_ms",
+        "INTEGER",
+    )?;
+    Ok(())
+}
+
+fn apply_schema_v29(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "meta", "expires_at_ms", "INTEGER")?;
+    Ok(())
+}
+
+fn apply_schema_v30(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "job", "on_complete_json", "TEXT")?;
+    Ok(())
+}
+
+fn apply_schema_v31(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "library_item", "source_platform", "TEXT")?;
+    // Backfill from what older rows already record; unmatched URLs stay NULL.
+    conn.execute_batch(
+        r#"
+UPDATE library_item SET source_platform = 'local_file'
+WHERE source_platform IS NULL AND source_type = 'local_file';
+
+UPDATE library_item SET source_platform = CASE
+    WHEN source_uri LIKE '%://youtu.be/%' OR source_uri LIKE '%youtube.com/%' THEN 'youtube'
+    WHEN source_uri LIKE '%instagram.com/%' THEN 'instagram'
+    WHEN source_uri LIKE 'http://%' OR source_uri LIKE 'https://%' THEN 'direct_http'
+    ELSE NULL
+  END
+WHERE source_platform IS NULL AND source_type = 'url_direct';
+
+CREATE INDEX IF NOT EXISTS idx_library_item_source_platform
+  ON library_item(source_platform, created_at_ms);
+"#,
+    )?;
+    Ok(())
+}
+
+fn apply_schema_v32(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "job", "worker_id", "INTEGER")?;
+    Ok(())
+}
+
+fn apply_schema_v33(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "job", "parent_job_id", "TEXT")?;
+    conn.execute_batch(
+        "CREATE INDEX IF NOT EXISTS idx_job_parent_job_id ON job(parent_job_id, created_at_ms);",
+    )?;
+    Ok(())
+}
+
+fn apply_schema_v34(conn: &Connection) -> Result<()> {
+    ensure_column(conn, "job", "progress_steps_json", "TEXT")?;
+    Ok(())
+}
+
+/// Sources beyond the first `ingest_provenance` row, recorded when a download turns out to be a
+/// duplicate of an existing item.
+fn apply_schema_v35(conn: &Connection) -> Result<()> {
+    conn.execute_batch(
+        r#"
+CREATE TABLE IF NOT EXISTS ingest_provenance_source (
+  item_id TEXT NOT NULL,
+  source_url TEXT NOT NULL,
+  provider TEXT NOT NULL,
+  rights_note TEXT NOT NULL,
+  attested_at_ms INTEGER NOT NULL,
+  created_at_ms INTEGER NOT NULL,
+  PRIMARY KEY (item_id, source_url),
+  FOREIGN KEY (item_id) REFERENCES library_item(id) ON DELETE CASCADE
+);
+"#,
+    )?;
+    Ok(())
+}
+
 fn ensure_column(conn: &Connection, table: &str, column: &str, column_def: &str) -> Result<()> {
     let mut stmt = conn.prepare(&format!("PRAGMA table_info({table})"))?;
     let mut rows = stmt.query([])?;
@@ -665,14 +1138,185 @@ fn ensure_column(conn: &Connection, table: &str, column: &str, column_def: &str)
 pub fn ensure_schema(paths: &AppPaths) -> Result<()> {
     let conn = open(paths)?;
     migrate(&conn)?;
+    prune_expired_meta(&conn)?;
     Ok(())
 }
 
+/// Issue cap for the quick check run at startup.
+pub const STARTUP_INTEGRITY_MAX_ISSUES: u32 = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DbIntegrityResult {
+    pub ok: bool,
+    pub issues: Vec<String>,
+    /// Database size in pages at the time of the check.
+    pub pages_checked: u32,
+}
+
+/// Runs `PRAGMA integrity_check`, reporting at most `max_issues` problems when set. A database
+/// that cannot be opened or queried is reported as a single issue rather than an error.
+pub fn integrity_check(paths: &AppPaths, max_issues: Option<u32>) -> DbIntegrityResult {
+    let checked = (|| -> Result<(Vec<String>, u32)> {
+        let conn = open_with_opts(paths, false, DEFAULT_BUSY_TIMEOUT_MS)?;
+        let pages_checked: u32 = conn.query_row("PRAGMA page_count", [], |row| row.get(0))?;
+        let sql = match max_issues {
+            Some(limit) => format!("PRAGMA integrity_check({})", limit.max(1)),
+            None => "PRAGMA integrity_check".to_string(),
+        };
+        let mut stmt = conn.prepare(&sql)?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        Ok((rows, pages_checked))
+    })();
+    match checked {
+        Ok((rows, pages_checked)) => {
+            let issues: Vec<String> = rows.into_iter().filter(|row| row != "ok").collect();
+            DbIntegrityResult {
+                ok: issues.is_empty(),
+                issues,
+                pages_checked,
+            }
+        }
+        Err(err) => DbIntegrityResult {
+            ok: false,
+            issues: vec![err.to_string()],
+            pages_checked: 0,
+        },
+    }
+}
+
+/// Renames a corrupt database (and its WAL/SHM sidecars) to `app.sqlite.corrupt.{timestamp}` so
+/// a fresh one can be created; nothing is deleted. Returns the new path of the main file.
+pub fn quarantine_corrupt_db(paths: &AppPaths) -> Result<std::path::PathBuf> {
+    let db_path = paths.db_dir().join("app.sqlite");
+    let quarantined = paths
+        .db_dir()
+        .join(format!("app.sqlite.corrupt.{}", now_ms()));
+    std::fs::rename(&db_path, &quarantined)?;
+    for suffix in ["-wal", "-shm"] {
+        let sidecar = paths.db_dir().join(format!("app.sqlite{suffix}"));
+        if sidecar.exists() {
+            let target = paths.db_dir().join(format!(
+                "{}{suffix}",
+                quarantined
+                    .file_name()
+                    .unwrap_or_default()
+                    .to_string_lossy()
+            ));
+            std::fs::rename(&sidecar, target)?;
+        }
+    }
+    Ok(quarantined)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::paths::AppPaths;
-    use rusqlite::OptionalExtension;
+
+    #[test]
+    fn integrity_check_reports_corrupt_db_and_quarantine_keeps_it() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        ensure_schema(&paths).expect("schema");
+        let healthy = integrity_check(&paths, Some(STARTUP_INTEGRITY_MAX_ISSUES));
+        assert!(healthy.ok, "{:?}", healthy.issues);
+        assert!(healthy.pages_checked > 0);
+
+        let db_path = paths.db_dir().join("app.sqlite");
+        for suffix in ["-wal", "-shm"] {
+            let _ = std::fs::remove_file(paths.db_dir().join(format!("app.sqlite{suffix}")));
+        }
+        let mut bytes = std::fs::read(&db_path).expect("read db");
+        for byte in bytes.iter_mut().skip(100) {
+            *byte = 0xA5;
+        }
+        std::fs::write(&db_path, &bytes).expect("corrupt db");
+        let corrupt = integrity_check(&paths, None);
+        assert!(!corrupt.ok);
+        assert!(!corrupt.issues.is_empty());
+
+        let quarantined = quarantine_corrupt_db(&paths).expect("quarantine");
+        assert_eq!(std::fs::read(&quarantined).expect("kept"), bytes);
+        assert!(quarantined
+            .file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("app.sqlite.corrupt.")));
+        ensure_schema(&paths).expect("fresh schema");
+        assert!(integrity_check(&paths, None).ok);
+    }
+
+    #[test]
+    fn meta_ttl_hides_and_prunes_expired_rows() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        ensure_schema(&paths).expect("schema");
+        let conn = open(&paths).expect("open");
+
+        set_meta(&conn, "plain", "1").expect("set");
+        set_meta_with_ttl(&conn, "fresh", "2", 60_000).expect("set fresh");
+        set_meta_with_ttl(&conn, "stale", "3", 60_000).expect("set stale");
+        conn.execute(
+            "UPDATE meta SET expires_at_ms=?1 WHERE key='stale'",
+            [now_ms() - 1],
+        )
+        .expect("expire");
+
+        assert_eq!(get_meta(&conn, "plain").expect("get").as_deref(), Some("1"));
+        assert_eq!(get_meta(&conn, "fresh").expect("get").as_deref(), Some("2"));
+        assert_eq!(get_meta(&conn, "stale").expect("get"), None);
+        assert_eq!(prune_expired_meta(&conn).expect("prune"), 1);
+        assert_eq!(prune_expired_meta(&conn).expect("prune again"), 0);
+
+        set_meta(&conn, "fresh", "4").expect("clear ttl");
+        let expires: Option<i64> = conn
+            .query_row(
+                "SELECT expires_at_ms FROM meta WHERE key='fresh'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("row");
+        assert_eq!(expires, None);
+    }
+
+    #[test]
+    fn concurrent_writers_do_not_hit_sqlite_busy() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        ensure_schema(&paths).expect("schema");
+
+        let handles: Vec<_> = (0..8)
+            .map(|worker| {
+                let paths = paths.clone();
+                std::thread::spawn(move || -> Result<()> {
+                    let conn = open(&paths)?;
+                    for i in 0..25 {
+                        conn.execute(
+                            "INSERT INTO meta(key, value) VALUES(?1, ?2)
+                             ON CONFLICT(key) DO UPDATE SET value=excluded.value",
+                            [format!("concurrency_test_{worker}_{i}"), i.to_string()],
+                        )?;
+                    }
+                    Ok(())
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().expect("join").expect("writer");
+        }
+
+        let conn = open(&paths).expect("open");
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM meta WHERE key LIKE 'concurrency_test_%'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("count");
+        assert_eq!(count, 200);
+        wal_checkpoint_truncate(&conn).expect("checkpoint");
+    }
 
     #[test]
     fn migrate_adds_batch_id_for_legacy_job_table() {
@@ -858,4 +1502,47 @@ CREATE TABLE IF NOT EXISTS job (
             .expect("meta schema version");
         assert_eq!(meta, CURRENT_SCHEMA_VERSION.to_string());
     }
+
+    #[test]
+    fn migrate_records_each_step_once_across_reopen() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let applied = |conn: &Connection| -> Vec<(u32, Option<i64>)> {
+            let mut stmt = conn
+                .prepare("SELECT version, applied_at_ms FROM schema_migration ORDER BY version")
+                .expect("prepare");
+            stmt.query_map([], |row| Ok((row.get(0)?, row.get(1)?)))
+                .expect("query")
+                .collect::<rusqlite::Result<Vec<_>>>()
+                .expect("rows")
+        };
+
+        let conn = open(&paths).expect("open");
+        migrate(&conn).expect("migrate");
+        assert_eq!(
+            schema_version(&conn).expect("version"),
+            CURRENT_SCHEMA_VERSION
+        );
+        let first = applied(&conn);
+        assert_eq!(first.len(), MIGRATION_STEPS.len());
+        assert!(first.iter().all(|(_, at)| at.is_some()));
+        drop(conn);
+
+        let conn = open(&paths).expect("reopen");
+        migrate(&conn).expect("migrate again");
+        assert_eq!(
+            schema_version(&conn).expect("version"),
+            CURRENT_SCHEMA_VERSION
+        );
+        assert_eq!(applied(&conn), first);
+
+        // A database from before the table existed gets its earlier steps backfilled.
+        conn.execute_batch("DROP TABLE schema_migration; PRAGMA user_version = 18;")
+            .expect("downgrade");
+        migrate(&conn).expect("migrate legacy");
+        let backfilled = applied(&conn);
+        assert_eq!(backfilled.len(), MIGRATION_STEPS.len());
+        assert_eq!(backfilled[0].1, None);
+        assert!(backfilled.last().expect("last").1.is_some());
+    }
 }
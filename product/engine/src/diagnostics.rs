@@ -1,9 +1,9 @@
 use crate::models::{ModelInventory, ModelStore};
 use crate::paths::AppPaths;
-use crate::{db, jobs, tools, Result};
+use crate::{config, db, jobs, library, tools, EngineError, Result};
 use regex::Regex;
-use serde::Serialize;
-use std::collections::BTreeMap;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::io::{BufRead, BufReader, Write};
 use std::path::{Path, PathBuf};
 use std::sync::OnceLock;
@@ -11,6 +11,13 @@ use std::time::{Duration, Instant};
 
 const STORAGE_SCAN_MAX_ENTRIES_PER_ROOT: usize = 25_000;
 const STORAGE_SCAN_MAX_MILLIS_PER_ROOT: u64 = 1_500;
+pub const DEFAULT_STORAGE_BREAKDOWN_TOP_N: usize = 20;
+pub const DEFAULT_BUNDLE_MAX_JOB_LOGS: usize = 50;
+const BUNDLE_JOBS_SUMMARY_WINDOW_MS: i64 = 7 * 24 * 60 * 60 * 1000;
+const HEALTH_DISK_FREE_WARNING_GB: f64 = 5.0;
+const HEALTH_DISK_FREE_CRITICAL_GB: f64 = 0.5;
+/// Recently touched dirs/logs may belong to an import or job whose DB row is not written yet.
+const ORPHAN_MIN_AGE: Duration = Duration::from_secs(60 * 60);
 
 #[derive(Debug, Clone, Serialize)]
 pub struct StorageBreakdown {
@@ -20,24 +27,98 @@ pub struct StorageBreakdown {
     pub logs_bytes: u64,
     pub db_bytes: u64,
     pub total_bytes: u64,
+    pub per_item: Vec<ItemStorageEntry>,
+    pub per_platform: Vec<PlatformStorageEntry>,
+    /// Derived item dirs with no `library_item` row.
+    pub orphaned_item_dirs: Vec<OrphanedDirEntry>,
+    /// Job log files (including rotated backups) with no `job` row.
+    pub orphaned_job_log_files: Vec<OrphanedDirEntry>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanedDirEntry {
+    pub dir_path: String,
+    pub estimated_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OrphanCleanupResult {
+    pub dry_run: bool,
+    /// Dirs removed, or that would be removed when `dry_run` is set.
+    pub removed_dirs: Vec<String>,
+    pub removed_log_files: Vec<String>,
+    pub freed_bytes: u64,
+    pub failed_paths: Vec<String>,
+}
+
+/// Library items grouped by `source_platform`; `"unknown"` covers rows without one.
+#[derive(Debug, Clone, Serialize)]
+pub struct PlatformStorageEntry {
+    pub platform: String,
+    pub item_count: u64,
+    pub media_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ItemStorageEntry {
+    pub item_id: String,
+    pub title: String,
+    pub derived_bytes: u64,
+    pub asr_bytes: u64,
+    pub translate_bytes: u64,
+    pub separation_bytes: u64,
+    pub tts_bytes: u64,
+    pub dub_preview_bytes: u64,
+    pub export_bytes: u64,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct CacheClearSummary {
     pub removed_entries: usize,
     pub removed_bytes: u64,
+    /// Bytes removed per category; empty for a full [`clear_cache`].
+    pub category_bytes: BTreeMap<String, u64>,
+}
+
+/// Categories removed by [`clear_cache_selective`]; each flag defaults to off.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct CacheClearOptions {
+    pub clear_huggingface: bool,
+    pub clear_python: bool,
+    /// 16 kHz mono WAVs extracted for ASR, translate and diarize.
+    pub clear_asr_audio: bool,
+    /// Per-segment TTS renders under `tts_preview/<backend>/segments/`.
+    pub clear_tts_segments: bool,
+    /// Manifests of finished image batch jobs; downloaded images are never touched.
+    pub clear_image_batch: bool,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct DiagnosticsBundleResult {
     pub out_path: String,
     pub file_bytes: u64,
+    pub included_job_logs: usize,
 }
 
 #[derive(Debug, Clone, Serialize)]
 pub struct LicensingReportResult {
     pub out_path: String,
     pub file_bytes: u64,
+    pub python_packages: Vec<PythonPackageLicense>,
+    /// True when Python scanning was requested but the venv is missing or could not be queried.
+    pub python_unavailable: bool,
+    pub python_licenses_csv_path: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PythonPackageLicense {
+    pub name: String,
+    pub version: String,
+    /// `UNKNOWN` when the package metadata declares no license.
+    pub license: String,
+    #[serde(default)]
+    pub home_page: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -107,6 +188,7 @@ struct BundleDbInfo {
 struct BundleJobsInfo {
     recent_jobs: Vec<BundleJobRow>,
     recent_failed_jobs: Vec<BundleJobRow>,
+    warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -121,6 +203,18 @@ struct BundleJobRow {
     created_at_ms: i64,
     started_at_ms: Option<i64>,
     finished_at_ms: Option<i64>,
+    requeue_count: i64,
+}
+
+/// Subtitle versions carrying user notes; the notes go through the same redaction as logs.
+#[derive(Debug, Clone, Serialize)]
+struct BundleSubtitleNoteRow {
+    track_id: String,
+    item_id: String,
+    kind: String,
+    lang: String,
+    version: i64,
+    notes: String,
 }
 
 #[derive(Debug, Clone, Serialize)]
@@ -131,11 +225,30 @@ struct BundleConfigInfo {
     download_dir_override_present: bool,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum HealthLevel {
+    Ok,
+    Warning,
+    Critical,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct HealthCheckReport {
+    pub db_ok: bool,
+    pub db_schema_version: u32,
+    pub tools: tools::AllToolsStatus,
+    pub paths_writable: HashMap<String, bool>,
+    pub disk_free_gb: HashMap<String, f64>,
+    pub python_packages: HashMap<String, Option<String>>,
+    pub overall: HealthLevel,
+}
+
 pub fn engine_version() -> &'static str {
     env!("CARGO_PKG_VERSION")
 }
 
-pub fn storage_breakdown(paths: &AppPaths) -> Result<StorageBreakdown> {
+pub fn storage_breakdown(paths: &AppPaths, top_n: usize) -> Result<StorageBreakdown> {
     paths.ensure_dirs()?;
 
     let library_bytes = directory_size_bytes_best_effort(&paths.library_dir());
@@ -148,6 +261,10 @@ pub fn storage_breakdown(paths: &AppPaths) -> Result<StorageBreakdown> {
         .saturating_add(cache_bytes)
         .saturating_add(logs_bytes)
         .saturating_add(db_bytes);
+    let per_item = item_storage_entries(paths, top_n);
+    let per_platform = platform_storage_entries(paths);
+    let (orphaned_item_dirs, orphaned_job_log_files) =
+        find_orphans(paths, ORPHAN_MIN_AGE).unwrap_or_default();
 
     Ok(StorageBreakdown {
         library_bytes,
@@ -156,19 +273,490 @@ pub fn storage_breakdown(paths: &AppPaths) -> Result<StorageBreakdown> {
         logs_bytes,
         db_bytes,
         total_bytes,
+        per_item,
+        per_platform,
+        orphaned_item_dirs,
+        orphaned_job_log_files,
     })
 }
 
+/// Lists derived item dirs and job logs whose ids are missing from the DB. Fails rather than
+/// reporting everything as orphaned when the DB cannot be read.
+fn find_orphans(
+    paths: &AppPaths,
+    min_age: Duration,
+) -> Result<(Vec<OrphanedDirEntry>, Vec<OrphanedDirEntry>)> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let ids = |sql: &str| -> Result<Vec<String>> {
+        let mut stmt = conn.prepare(sql)?;
+        let rows = stmt
+            .query_map([], |row| row.get::<_, String>(0))?
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(rows)
+    };
+    let item_dir_names: std::collections::HashSet<std::ffi::OsString> =
+        ids("SELECT DISTINCT id FROM library_item")?
+            .iter()
+            .filter_map(|id| paths.derived_item_dir(id).file_name().map(|n| n.to_owned()))
+            .collect();
+    let job_log_names: std::collections::HashSet<std::ffi::OsString> = ids("SELECT id FROM job")?
+        .iter()
+        .filter_map(|id| paths.job_log_path(id).file_name().map(|n| n.to_owned()))
+        .collect();
+
+    let old_enough = |path: &Path| {
+        std::fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok()
+            .and_then(|modified| modified.elapsed().ok())
+            .is_some_and(|age| age >= min_age)
+    };
+
+    let mut dirs = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(paths.derived_items_dir()) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if !entry.file_type().is_ok_and(|t| t.is_dir())
+                || item_dir_names.contains(&entry.file_name())
+                || !old_enough(&path)
+            {
+                continue;
+            }
+            dirs.push(OrphanedDirEntry {
+                estimated_bytes: directory_size_bytes_best_effort(&path),
+                dir_path: path.to_string_lossy().to_string(),
+            });
+        }
+    }
+
+    let mut logs = Vec::new();
+    if let Ok(entries) = std::fs::read_dir(paths.job_logs_dir()) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+            // Rotated backups are `<id>.jsonl.<n>`.
+            let base = match name.rsplit_once('.') {
+                Some((base, n)) if n.chars().all(|c| c.is_ascii_digit()) => base,
+                _ => name.as_str(),
+            };
+            if !base.ends_with(".jsonl")
+                || !entry.file_type().is_ok_and(|t| t.is_file())
+                || job_log_names.contains(std::ffi::OsStr::new(base))
+                || !old_enough(&path)
+            {
+                continue;
+            }
+            logs.push(OrphanedDirEntry {
+                estimated_bytes: file_size_bytes_best_effort(&path),
+                dir_path: path.to_string_lossy().to_string(),
+            });
+        }
+    }
+
+    dirs.sort_by(|a, b| a.dir_path.cmp(&b.dir_path));
+    logs.sort_by(|a, b| a.dir_path.cmp(&b.dir_path));
+    Ok((dirs, logs))
+}
+
+/// Removes orphaned derived item dirs and job logs; with `dry_run` only reports what would go.
+pub fn clean_orphans(paths: &AppPaths, dry_run: bool) -> Result<OrphanCleanupResult> {
+    clean_orphans_older_than(paths, dry_run, ORPHAN_MIN_AGE)
+}
+
+fn clean_orphans_older_than(
+    paths: &AppPaths,
+    dry_run: bool,
+    min_age: Duration,
+) -> Result<OrphanCleanupResult> {
+    let (dirs, logs) = find_orphans(paths, min_age)?;
+    let mut result = OrphanCleanupResult {
+        dry_run,
+        removed_dirs: Vec::new(),
+        removed_log_files: Vec::new(),
+        freed_bytes: 0,
+        failed_paths: Vec::new(),
+    };
+    for entry in dirs {
+        if dry_run || std::fs::remove_dir_all(&entry.dir_path).is_ok() {
+            result.freed_bytes = result.freed_bytes.saturating_add(entry.estimated_bytes);
+            result.removed_dirs.push(entry.dir_path);
+        } else {
+            result.failed_paths.push(entry.dir_path);
+        }
+    }
+    for entry in logs {
+        if dry_run || std::fs::remove_file(&entry.dir_path).is_ok() {
+            result.freed_bytes = result.freed_bytes.saturating_add(entry.estimated_bytes);
+            result.removed_log_files.push(entry.dir_path);
+        } else {
+            result.failed_paths.push(entry.dir_path);
+        }
+    }
+    Ok(result)
+}
+
+fn platform_storage_entries(paths: &AppPaths) -> Vec<PlatformStorageEntry> {
+    db::open(paths)
+        .and_then(|conn| {
+            db::migrate(&conn)?;
+            let mut stmt = conn.prepare(
+                r#"
+SELECT COALESCE(source_platform, 'unknown'), COUNT(*), COALESCE(SUM(file_size_bytes), 0)
+FROM library_item
+GROUP BY 1
+ORDER BY 3 DESC, 1
+"#,
+            )?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok(PlatformStorageEntry {
+                        platform: row.get(0)?,
+                        item_count: row.get::<_, i64>(1)?.max(0) as u64,
+                        media_bytes: row.get::<_, i64>(2)?.max(0) as u64,
+                    })
+                })?
+                .collect::<std::result::Result<Vec<_>, _>>()?;
+            Ok(rows)
+        })
+        .unwrap_or_default()
+}
+
+/// Largest derived item dirs first, with per-stage subtotals.
+fn item_storage_entries(paths: &AppPaths, top_n: usize) -> Vec<ItemStorageEntry> {
+    if top_n == 0 {
+        return Vec::new();
+    }
+    let entries = match std::fs::read_dir(paths.derived_items_dir()) {
+        Ok(v) => v,
+        Err(_) => return Vec::new(),
+    };
+
+    let titles: BTreeMap<String, String> = db::open(paths)
+        .and_then(|conn| {
+            let mut stmt = conn.prepare("SELECT id, title FROM library_item")?;
+            let rows = stmt
+                .query_map([], |row| {
+                    Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+                })?
+                .collect::<std::result::Result<BTreeMap<_, _>, _>>()?;
+            Ok(rows)
+        })
+        .unwrap_or_default();
+
+    let mut out = Vec::new();
+    for entry in entries.flatten() {
+        if !entry.file_type().map(|t| t.is_dir()).unwrap_or(false) {
+            continue;
+        }
+        let item_id = entry.file_name().to_string_lossy().to_string();
+        let item_dir = entry.path();
+        out.push(ItemStorageEntry {
+            title: titles.get(&item_id).cloned().unwrap_or_default(),
+            derived_bytes: directory_size_bytes_best_effort(&item_dir),
+            asr_bytes: directory_size_bytes_best_effort(&item_dir.join("asr")),
+            translate_bytes: directory_size_bytes_best_effort(&item_dir.join("translate")),
+            separation_bytes: directory_size_bytes_best_effort(&item_dir.join("separation")),
+            tts_bytes: directory_size_bytes_best_effort(&item_dir.join("tts_preview")),
+            dub_preview_bytes: directory_size_bytes_best_effort(&item_dir.join("dub_preview")),
+            export_bytes: directory_size_bytes_best_effort(&item_dir.join("exports")),
+            item_id,
+        });
+    }
+    out.sort_by(|a, b| {
+        b.derived_bytes
+            .cmp(&a.derived_bytes)
+            .then_with(|| a.item_id.cmp(&b.item_id))
+    });
+    out.truncate(top_n);
+    out
+}
+
+/// Collects per-component readiness (DB, tools, writable dirs, free disk, venv packages).
+pub fn health_check(paths: &AppPaths) -> HealthCheckReport {
+    let _ = paths.ensure_dirs();
+
+    let (db_ok, db_schema_version) = match db::open(paths) {
+        Ok(conn) => {
+            let ok = conn
+                .query_row("SELECT 1", [], |row| row.get::<_, i64>(0))
+                .is_ok();
+            (ok, db::schema_user_version(&conn).unwrap_or(0))
+        }
+        Err(_) => (false, 0),
+    };
+
+    let tools = tools::all_tools_status(paths);
+
+    let dirs = [
+        ("base_dir", paths.base_dir.clone()),
+        ("models_dir", paths.models_dir()),
+        ("tools_dir", paths.tools_dir()),
+        ("cache_dir", paths.cache_dir()),
+    ];
+    let mut paths_writable = HashMap::new();
+    for (label, dir) in &dirs {
+        paths_writable.insert(label.to_string(), dir_is_writable(dir));
+    }
+
+    let mut disk_free_gb = HashMap::new();
+    let mut seen_filesystems = Vec::new();
+    for (label, dir) in &dirs {
+        let Some(fs_id) = filesystem_id(dir) else {
+            continue;
+        };
+        if seen_filesystems.contains(&fs_id) {
+            continue;
+        }
+        seen_filesystems.push(fs_id);
+        if let Some(bytes) = disk_free_bytes(dir) {
+            disk_free_gb.insert(label.to_string(), bytes as f64 / 1_073_741_824.0);
+        }
+    }
+
+    let mut python_packages = HashMap::new();
+    if tools.spleeter.installed {
+        if let Ok(found) = tools::check_spleeter_packages(paths) {
+            python_packages.extend(found);
+        }
+    }
+    if tools.diarization.installed {
+        if let Ok(found) = tools::check_diarization_packages(paths) {
+            python_packages.extend(found);
+        }
+    }
+
+    let ffmpeg_ok = tools.ffmpeg.installed || tools.ffmpeg.ffmpeg_version.is_some();
+    let mut overall = HealthLevel::Ok;
+    if !db_ok
+        || !ffmpeg_ok
+        || !paths_writable.get("base_dir").copied().unwrap_or(false)
+        || disk_free_gb
+            .values()
+            .any(|gb| *gb < HEALTH_DISK_FREE_CRITICAL_GB)
+    {
+        overall = HealthLevel::Critical;
+    } else if !tools.ytdlp.available
+        || paths_writable.values().any(|ok| !ok)
+        || disk_free_gb
+            .values()
+            .any(|gb| *gb < HEALTH_DISK_FREE_WARNING_GB)
+        || python_packages.values().any(Option::is_none)
+    {
+        overall = HealthLevel::Warning;
+    }
+
+    HealthCheckReport {
+        db_ok,
+        db_schema_version,
+        tools,
+        paths_writable,
+        disk_free_gb,
+        python_packages,
+        overall,
+    }
+}
+
+pub(crate) fn dir_is_writable(dir: &Path) -> bool {
+    if std::fs::create_dir_all(dir).is_err() {
+        return false;
+    }
+    let probe = dir.join(format!(".voxvulgi_write_probe_{}", std::process::id()));
+    let ok = std::fs::write(&probe, b"ok").is_ok();
+    let _ = std::fs::remove_file(&probe);
+    ok
+}
+
+#[cfg(unix)]
+fn filesystem_id(dir: &Path) -> Option<String> {
+    use std::os::unix::fs::MetadataExt;
+    std::fs::metadata(dir).ok().map(|m| m.dev().to_string())
+}
+
+#[cfg(not(unix))]
+fn filesystem_id(dir: &Path) -> Option<String> {
+    let canonical = std::fs::canonicalize(dir).ok()?;
+    canonical
+        .components()
+        .next()
+        .map(|root| root.as_os_str().to_string_lossy().to_ascii_lowercase())
+}
+
+/// Free space available to the current user on the volume holding `path`, in GiB.
+pub fn disk_free_gb(path: &Path) -> Result<f64> {
+    disk_free_bytes(path)
+        .map(|bytes| bytes as f64 / 1_073_741_824.0)
+        .ok_or_else(|| {
+            EngineError::InstallFailed(format!(
+                "failed to query free disk space for {}",
+                path.to_string_lossy()
+            ))
+        })
+}
+
+#[cfg(unix)]
+pub(crate) fn disk_free_bytes(dir: &Path) -> Option<u64> {
+    use std::os::unix::ffi::OsStrExt;
+    let c_path = std::ffi::CString::new(dir.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    let rc = unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) };
+    if rc != 0 {
+        return None;
+    }
+    Some((stat.f_bavail as u64).saturating_mul(stat.f_frsize as u64))
+}
+
+#[cfg(windows)]
+pub(crate) fn disk_free_bytes(dir: &Path) -> Option<u64> {
+    use std::os::windows::ffi::OsStrExt;
+    use windows_sys::Win32::Storage::FileSystem::GetDiskFreeSpaceExW;
+
+    let wide: Vec<u16> = dir
+        .as_os_str()
+        .encode_wide()
+        .chain(std::iter::once(0))
+        .collect();
+    let mut free_to_caller: u64 = 0;
+    let ok = unsafe {
+        GetDiskFreeSpaceExW(
+            wide.as_ptr(),
+            &mut free_to_caller,
+            std::ptr::null_mut(),
+            std::ptr::null_mut(),
+        )
+    };
+    if ok == 0 {
+        return None;
+    }
+    Some(free_to_caller)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub(crate) fn disk_free_bytes(_dir: &Path) -> Option<u64> {
+    None
+}
+
 pub fn clear_cache(paths: &AppPaths) -> Result<CacheClearSummary> {
     paths.ensure_dirs()?;
     clear_dir_entries_with_bytes(&paths.cache_dir())
 }
 
+/// Clears only the cache categories selected in `options`. Derived artifacts that a later job can
+/// regenerate are removed per item; library media and downloads are never touched.
+pub fn clear_cache_selective(
+    paths: &AppPaths,
+    options: &CacheClearOptions,
+) -> Result<CacheClearSummary> {
+    paths.ensure_dirs()?;
+    let mut summary = CacheClearSummary {
+        removed_entries: 0,
+        removed_bytes: 0,
+        category_bytes: BTreeMap::new(),
+    };
+    let mut record = |category: &str, removed: CacheClearSummary| {
+        summary.removed_entries += removed.removed_entries;
+        summary.removed_bytes = summary.removed_bytes.saturating_add(removed.removed_bytes);
+        *summary
+            .category_bytes
+            .entry(category.to_string())
+            .or_default() += removed.removed_bytes;
+    };
+
+    if options.clear_huggingface {
+        record(
+            "huggingface",
+            clear_dir_entries_with_bytes(&paths.cache_dir().join("huggingface"))?,
+        );
+    }
+    if options.clear_python {
+        for sub in ["python", "pip"] {
+            record(
+                "python",
+                clear_dir_entries_with_bytes(&paths.cache_dir().join(sub))?,
+            );
+        }
+    }
+
+    if options.clear_asr_audio || options.clear_tts_segments {
+        let item_dirs = std::fs::read_dir(paths.derived_items_dir())
+            .into_iter()
+            .flatten()
+            .flatten()
+            .map(|entry| entry.path())
+            .filter(|path| path.is_dir());
+        for item_dir in item_dirs {
+            if options.clear_asr_audio {
+                for stage in ["asr", "translate", "diarize"] {
+                    record(
+                        "asr_audio",
+                        remove_path_with_bytes(&item_dir.join(stage).join("audio_16k.wav")),
+                    );
+                }
+            }
+            if options.clear_tts_segments {
+                let backend_dirs = std::fs::read_dir(item_dir.join("tts_preview"))
+                    .into_iter()
+                    .flatten()
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_dir());
+                for backend_dir in backend_dirs {
+                    let segments = remove_path_with_bytes(&backend_dir.join("segments"));
+                    if segments.removed_entries > 0 {
+                        // The manifest only indexes the segments; dropping it lets the next TTS
+                        // run re-render instead of skipping as already done.
+                        let manifest = remove_path_with_bytes(&backend_dir.join("manifest.json"));
+                        record("tts_segments", manifest);
+                    }
+                    record("tts_segments", segments);
+                }
+            }
+        }
+    }
+
+    if options.clear_image_batch {
+        let conn = db::open(paths)?;
+        db::migrate(&conn)?;
+        let mut stmt = conn.prepare(
+            "SELECT id, content_key FROM job WHERE type='download_image_batch' AND status IN ('succeeded','failed','canceled')",
+        )?;
+        let jobs = stmt
+            .query_map([], |row| {
+                Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+            })?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        for (job_id, content_key) in jobs {
+            let dir = paths.job_artifacts_dir(&job_id, content_key.as_deref());
+            record("image_batch", remove_path_with_bytes(&dir));
+        }
+    }
+
+    Ok(summary)
+}
+
+fn remove_path_with_bytes(path: &Path) -> CacheClearSummary {
+    let (bytes, removed) = if path.is_dir() {
+        let bytes = directory_size_bytes_best_effort(path);
+        (bytes, std::fs::remove_dir_all(path).is_ok())
+    } else if path.is_file() {
+        let bytes = file_size_bytes_best_effort(path);
+        (bytes, std::fs::remove_file(path).is_ok())
+    } else {
+        (0, false)
+    };
+    CacheClearSummary {
+        removed_entries: usize::from(removed),
+        removed_bytes: if removed { bytes } else { 0 },
+        category_bytes: BTreeMap::new(),
+    }
+}
+
 pub fn export_diagnostics_bundle(
     paths: &AppPaths,
     out_path: impl AsRef<Path>,
     app_name: &str,
     app_version: &str,
+    max_job_logs: usize,
 ) -> Result<DiagnosticsBundleResult> {
     paths.ensure_dirs()?;
 
@@ -177,8 +765,9 @@ pub fn export_diagnostics_bundle(
         std::fs::create_dir_all(parent)?;
     }
 
-    let retention = jobs::job_log_retention_policy();
-    let storage = storage_breakdown(paths)?;
+    let retention = jobs::load_job_log_retention_policy(paths);
+    // Per-item rows carry library titles, so the shareable bundle keeps totals only.
+    let storage = storage_breakdown(paths, 0)?;
     let models = export_models_inventory(ModelStore::new(paths.clone()).inventory().unwrap_or(
         ModelInventory {
             models_dir: String::new(),
@@ -195,6 +784,7 @@ pub fn export_diagnostics_bundle(
         .collect();
 
     let config = export_config_summary(paths);
+    let job_warnings = export_job_warnings(paths)?;
 
     let manifest = DiagnosticsBundleManifest {
         schema_version: 1,
@@ -216,6 +806,7 @@ pub fn export_diagnostics_bundle(
         jobs: BundleJobsInfo {
             recent_jobs: recent_jobs.clone(),
             recent_failed_jobs: recent_failed_jobs.clone(),
+            warnings: job_warnings,
         },
         retention: retention.clone(),
         config,
@@ -234,16 +825,33 @@ pub fn export_diagnostics_bundle(
 
     add_redacted_failed_job_logs(&mut zip, paths, &retention, &recent_failed_jobs, options)?;
 
+    let jobs_summary = export_jobs_summary(paths, now_ms() - BUNDLE_JOBS_SUMMARY_WINDOW_MS)?;
+    write_pretty_json_to_zip(&mut zip, "jobs_summary.json", &jobs_summary, options)?;
+    let subtitle_notes = export_subtitle_notes(paths)?;
+    if !subtitle_notes.is_empty() {
+        write_pretty_json_to_zip(&mut zip, "subtitle_notes.json", &subtitle_notes, options)?;
+    }
+    let item_history = export_item_history(paths)?;
+    if !item_history.is_empty() {
+        write_pretty_json_to_zip(&mut zip, "item_history.json", &item_history, options)?;
+    }
+    let included_job_logs =
+        add_redacted_recent_job_logs(&mut zip, paths, &retention, max_job_logs, options)?;
+
     zip.finish().map_err(zip_err_to_io)?;
 
     let file_bytes = std::fs::metadata(&out_path).map(|m| m.len()).unwrap_or(0);
     Ok(DiagnosticsBundleResult {
         out_path: out_path.to_string_lossy().to_string(),
         file_bytes,
+        included_job_logs,
     })
 }
 
-pub fn generate_licensing_report(paths: &AppPaths) -> Result<LicensingReportResult> {
+pub fn generate_licensing_report(
+    paths: &AppPaths,
+    include_python: bool,
+) -> Result<LicensingReportResult> {
     paths.ensure_dirs()?;
 
     let out_dir = paths.derived_dir().join("reports");
@@ -258,7 +866,11 @@ pub fn generate_licensing_report(paths: &AppPaths) -> Result<LicensingReportResu
             models: Vec::new(),
         });
 
-    let python_packages = list_python_packages_best_effort(paths);
+    let python_packages = if include_python {
+        list_python_packages_best_effort(paths)
+    } else {
+        Some(Vec::new())
+    };
     let openvoice_manifest = read_json_best_effort(
         &paths
             .python_models_dir()
@@ -298,12 +910,19 @@ pub fn generate_licensing_report(paths: &AppPaths) -> Result<LicensingReportResu
     }
 
     md.push_str("## Python packages (venv)\n\n");
-    match python_packages {
+    match &python_packages {
+        _ if !include_python => {
+            md.push_str("- Python package scan skipped.\n\n");
+        }
         Some(pkgs) if !pkgs.is_empty() => {
             md.push_str("| Name | Version | License |\n|---|---|---|\n");
             for p in pkgs {
-                let license = p.license.unwrap_or_else(|| "unknown".to_string());
-                md.push_str(&format!("| `{}` | {} | {} |\n", p.name, p.version, license));
+                md.push_str(&format!(
+                    "| `{}` | {} | {} |\n",
+                    p.name,
+                    p.version,
+                    p.license.replace('|', "/")
+                ));
             }
             md.push_str("\n");
         }
@@ -336,20 +955,78 @@ pub fn generate_licensing_report(paths: &AppPaths) -> Result<LicensingReportResu
 
     std::fs::write(&out_path, md)?;
     let file_bytes = std::fs::metadata(&out_path).map(|m| m.len()).unwrap_or(0);
+
+    let python_unavailable = python_packages.is_none();
+    let python_packages = python_packages.unwrap_or_default();
+    let python_licenses_csv_path = if include_python && !python_unavailable {
+        let csv_path = out_dir.join("python_licenses.csv");
+        write_python_licenses_csv(&csv_path, &python_packages)?;
+        Some(csv_path.to_string_lossy().to_string())
+    } else {
+        None
+    };
+
     Ok(LicensingReportResult {
         out_path: out_path.to_string_lossy().to_string(),
         file_bytes,
+        python_packages,
+        python_unavailable,
+        python_licenses_csv_path,
     })
 }
 
+fn write_python_licenses_csv(path: &Path, packages: &[PythonPackageLicense]) -> Result<()> {
+    let mut writer = csv::Writer::from_path(path)?;
+    writer.write_record(["name", "version", "license", "home_page"])?;
+    for p in packages {
+        writer.write_record([
+            p.name.as_str(),
+            p.version.as_str(),
+            p.license.as_str(),
+            p.home_page.as_deref().unwrap_or(""),
+        ])?;
+    }
+    writer.flush()?;
+    Ok(())
+}
+
 #[derive(Debug, Clone, serde::Deserialize)]
 struct PythonPackageRow {
     name: String,
     version: String,
     license: Option<String>,
+    #[serde(default)]
+    license_classifiers: Vec<String>,
+    #[serde(default)]
+    home_page: Option<String>,
 }
 
-fn list_python_packages_best_effort(paths: &AppPaths) -> Option<Vec<PythonPackageRow>> {
+/// First line of the declared license (some packages embed the full text), else the trove
+/// classifiers, else `UNKNOWN`.
+fn python_package_license(row: &PythonPackageRow) -> String {
+    const MAX_CHARS: usize = 120;
+    let declared = row
+        .license
+        .as_deref()
+        .and_then(|v| v.lines().map(str::trim).find(|l| !l.is_empty()))
+        .filter(|v| !v.eq_ignore_ascii_case("unknown"));
+    if let Some(declared) = declared {
+        return declared.chars().take(MAX_CHARS).collect();
+    }
+    let classifiers: Vec<&str> = row
+        .license_classifiers
+        .iter()
+        .filter_map(|c| c.rsplit("::").next().map(str::trim))
+        .filter(|c| !c.is_empty())
+        .collect();
+    if classifiers.is_empty() {
+        "UNKNOWN".to_string()
+    } else {
+        classifiers.join("; ")
+    }
+}
+
+fn list_python_packages_best_effort(paths: &AppPaths) -> Option<Vec<PythonPackageLicense>> {
     let venv_python = tools::python_venv_python_path(paths).ok()?;
     let code = r#"
 import json
@@ -363,10 +1040,24 @@ for dist in md.distributions():
     meta = dist.metadata
     name = meta.get("Name") or meta.get("Summary") or dist.metadata.get("Name") or "unknown"
     version = getattr(dist, "version", None) or meta.get("Version") or "unknown"
-    lic = meta.get("License")
+    lic = meta.get("License-Expression") or meta.get("License")
     if lic:
         lic = lic.strip()
-    rows.append({"name": str(name), "version": str(version), "license": (lic if lic else None)})
+    classifiers = [c for c in (meta.get_all("Classifier") or []) if c.startswith("License ::")]
+    home = meta.get("Home-page")
+    if not home:
+        for url in meta.get_all("Project-URL") or []:
+            label, _, value = url.partition(",")
+            if label.strip().lower() in ("homepage", "home", "source"):
+                home = value.strip()
+                break
+    rows.append({
+        "name": str(name),
+        "version": str(version),
+        "license": (lic if lic else None),
+        "license_classifiers": classifiers,
+        "home_page": (home.strip() if home and home.strip() else None),
+    })
 
 rows.sort(key=lambda r: r["name"].lower())
 print(json.dumps(rows, ensure_ascii=False))
@@ -382,7 +1073,17 @@ print(json.dumps(rows, ensure_ascii=False))
     let text = String::from_utf8_lossy(&output.stdout);
     let last = text.lines().rev().find(|l| !l.trim().is_empty())?.trim();
     let parsed: Vec<PythonPackageRow> = serde_json::from_str(last).ok()?;
-    Some(parsed)
+    Some(
+        parsed
+            .into_iter()
+            .map(|row| PythonPackageLicense {
+                license: python_package_license(&row),
+                name: row.name,
+                version: row.version,
+                home_page: row.home_page,
+            })
+            .collect(),
+    )
 }
 
 fn read_json_best_effort(path: &Path) -> Option<serde_json::Value> {
@@ -440,7 +1141,7 @@ fn export_db_and_jobs(
         counts.insert(name.to_string(), count);
     }
 
-    let jobs = jobs::list_jobs(paths, jobs_limit, 0).unwrap_or_default();
+    let jobs = jobs::list_jobs(paths, jobs_limit, 0, None, None).unwrap_or_default();
     let rows = jobs
         .into_iter()
         .map(|j| BundleJobRow {
@@ -454,6 +1155,7 @@ fn export_db_and_jobs(
             created_at_ms: j.created_at_ms,
             started_at_ms: j.started_at_ms,
             finished_at_ms: j.finished_at_ms,
+            requeue_count: j.requeue_count,
         })
         .collect();
 
@@ -466,6 +1168,97 @@ fn export_db_and_jobs(
     ))
 }
 
+fn export_subtitle_notes(paths: &AppPaths) -> Result<Vec<BundleSubtitleNoteRow>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, item_id, kind, lang, version, notes FROM subtitle_track
+         WHERE notes IS NOT NULL AND notes != '' ORDER BY item_id, kind, lang, version DESC",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(BundleSubtitleNoteRow {
+                track_id: row.get(0)?,
+                item_id: row.get(1)?,
+                kind: row.get(2)?,
+                lang: row.get(3)?,
+                version: row.get(4)?,
+                notes: redact_free_text(&row.get::<_, String>(5)?),
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(rows)
+}
+
+/// History rows keyed by item id, with `details` passed through log redaction.
+fn export_item_history(
+    paths: &AppPaths,
+) -> Result<BTreeMap<String, Vec<library::LibraryItemHistoryRow>>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let item_ids = conn
+        .prepare("SELECT DISTINCT item_id FROM library_item_history ORDER BY item_id")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let mut out = BTreeMap::new();
+    for item_id in item_ids {
+        let mut rows =
+            library::list_item_history(paths, &item_id, library::ITEM_HISTORY_MAX_ROWS, 0)?;
+        for row in &mut rows {
+            redact_value_in_place(&mut row.details);
+        }
+        out.insert(item_id, rows);
+    }
+    Ok(out)
+}
+
+fn export_jobs_summary(paths: &AppPaths, since_ms: i64) -> Result<Vec<BundleJobRow>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, item_id, batch_id, type, status, progress, error, created_at_ms, started_at_ms, finished_at_ms, requeue_count
+         FROM job WHERE created_at_ms >= ?1 ORDER BY created_at_ms DESC",
+    )?;
+    let rows = stmt
+        .query_map([since_ms], |row| {
+            Ok(BundleJobRow {
+                id: row.get(0)?,
+                item_id: row.get(1)?,
+                batch_id: row.get(2)?,
+                job_type: row.get(3)?,
+                status: row.get(4)?,
+                progress: row.get::<_, f64>(5)? as f32,
+                error: row
+                    .get::<_, Option<String>>(6)?
+                    .map(|value| redact_free_text(&value)),
+                created_at_ms: row.get(7)?,
+                started_at_ms: row.get(8)?,
+                finished_at_ms: row.get(9)?,
+                requeue_count: row.get(10)?,
+            })
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(rows)
+}
+
+fn export_job_warnings(paths: &AppPaths) -> Result<Vec<String>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let repeatedly_requeued: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM job WHERE requeue_count > 1",
+        [],
+        |row| row.get(0),
+    )?;
+
+    let mut warnings = Vec::new();
+    if repeatedly_requeued > 0 {
+        warnings.push(format!(
+            "{repeatedly_requeued} job(s) were requeued more than once after the app stopped mid-run; they may be crashing the app"
+        ));
+    }
+    Ok(warnings)
+}
+
 fn job_status_as_str(status: &jobs::JobStatus) -> &'static str {
     match status {
         jobs::JobStatus::Queued => "queued",
@@ -514,7 +1307,7 @@ fn add_redacted_failed_job_logs<W: Write + std::io::Seek>(
     recent_failed_jobs: &[BundleJobRow],
     options: zip::write::FileOptions,
 ) -> Result<()> {
-    let job_rows = jobs::list_jobs(paths, 500, 0).unwrap_or_default();
+    let job_rows = jobs::list_jobs(paths, 500, 0, None, None).unwrap_or_default();
     let mut failed_by_id: BTreeMap<String, jobs::JobRow> = BTreeMap::new();
     for job in job_rows {
         if !matches!(job.status, jobs::JobStatus::Failed) {
@@ -554,6 +1347,111 @@ fn add_redacted_failed_job_logs<W: Write + std::io::Seek>(
     Ok(())
 }
 
+/// Adds the logs of the most recently finished jobs under `jobs_logs/`. Jobs that still have a
+/// cookie secret on disk are skipped entirely. Returns the number of jobs whose logs were added.
+fn add_redacted_recent_job_logs<W: Write + std::io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    paths: &AppPaths,
+    retention: &jobs::JobLogRetentionPolicy,
+    max_job_logs: usize,
+    options: zip::write::FileOptions,
+) -> Result<usize> {
+    if max_job_logs == 0 {
+        return Ok(0);
+    }
+    const MAX_LOG_BYTES_PER_FILE: u64 = 2 * 1024 * 1024;
+
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, logs_path FROM job
+         WHERE finished_at_ms IS NOT NULL
+         ORDER BY finished_at_ms DESC
+         LIMIT ?1",
+    )?;
+    let rows = stmt
+        .query_map([max_job_logs as i64], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+
+    let mut included = 0_usize;
+    for (job_id, logs_path) in rows {
+        if paths.job_secrets_dir().join(&job_id).exists()
+            || paths.job_cookie_secret_path(&job_id).exists()
+        {
+            continue;
+        }
+        let mut added_any = false;
+        for path in log_path_candidates(Path::new(&logs_path), retention.max_backups) {
+            if !path.is_file() {
+                continue;
+            }
+            let file_name = match path.file_name().and_then(|v| v.to_str()) {
+                Some(v) if !v.trim().is_empty() => v,
+                _ => continue,
+            };
+            write_redacted_jsonl_file_to_zip(
+                zip,
+                &format!("jobs_logs/{file_name}"),
+                &path,
+                MAX_LOG_BYTES_PER_FILE,
+                options,
+            )?;
+            added_any = true;
+        }
+        if added_any {
+            included += 1;
+        }
+    }
+    Ok(included)
+}
+
+/// Rotates the diagnostics trace once it exceeds `max_file_bytes`:
+/// `diagnostics_trace.jsonl` -> `diagnostics_trace.1.jsonl` -> `.2` ... up to `max_backup_files`.
+/// The next append recreates the live file. Returns whether a rotation happened.
+pub fn rotate_diagnostics_trace_if_needed(
+    path: &Path,
+    config: &config::DiagnosticsTraceWriteConfig,
+) -> Result<bool> {
+    let len = match std::fs::metadata(path) {
+        Ok(m) => m.len(),
+        Err(_) => return Ok(false),
+    };
+    if len <= config.max_file_bytes {
+        return Ok(false);
+    }
+
+    if config.max_backup_files == 0 {
+        std::fs::remove_file(path)?;
+        return Ok(true);
+    }
+    for i in (1..=config.max_backup_files).rev() {
+        let dst = numbered_backup_path(path, i);
+        let src = if i == 1 {
+            path.to_path_buf()
+        } else {
+            numbered_backup_path(path, i - 1)
+        };
+        if !src.exists() {
+            continue;
+        }
+        std::fs::rename(&src, &dst)?;
+    }
+    Ok(true)
+}
+
+fn numbered_backup_path(path: &Path, index: usize) -> PathBuf {
+    let stem = path
+        .file_stem()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_default();
+    match path.extension() {
+        Some(ext) => path.with_file_name(format!("{stem}.{index}.{}", ext.to_string_lossy())),
+        None => path.with_file_name(format!("{stem}.{index}")),
+    }
+}
+
 fn log_path_candidates(base: &Path, max_backups: usize) -> Vec<PathBuf> {
     let mut paths = Vec::with_capacity(1 + max_backups);
     paths.push(base.to_path_buf());
@@ -812,6 +1710,7 @@ fn clear_dir_entries_with_bytes(dir: &Path) -> Result<CacheClearSummary> {
         return Ok(CacheClearSummary {
             removed_entries: 0,
             removed_bytes: 0,
+            category_bytes: BTreeMap::new(),
         });
     }
 
@@ -844,6 +1743,7 @@ fn clear_dir_entries_with_bytes(dir: &Path) -> Result<CacheClearSummary> {
     Ok(CacheClearSummary {
         removed_entries,
         removed_bytes,
+        category_bytes: BTreeMap::new(),
     })
 }
 
@@ -862,6 +1762,161 @@ mod tests {
     use rusqlite::params;
     use std::io::Read;
 
+    #[test]
+    fn python_package_license_falls_back_to_classifiers_then_unknown() {
+        let row = |license: Option<&str>, classifiers: &[&str]| PythonPackageRow {
+            name: "pkg".to_string(),
+            version: "1.0".to_string(),
+            license: license.map(str::to_string),
+            license_classifiers: classifiers.iter().map(|c| c.to_string()).collect(),
+            home_page: None,
+        };
+        assert_eq!(
+            python_package_license(&row(Some("\nMIT License\n\nCopyright ..."), &[])),
+            "MIT License"
+        );
+        assert_eq!(
+            python_package_license(&row(
+                Some("UNKNOWN"),
+                &["License :: OSI Approved :: BSD License"]
+            )),
+            "BSD License"
+        );
+        assert_eq!(python_package_license(&row(None, &[])), "UNKNOWN");
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let result = generate_licensing_report(&paths, true).expect("report");
+        assert!(result.python_unavailable);
+        assert!(result.python_packages.is_empty());
+        assert!(result.python_licenses_csv_path.is_none());
+        let skipped = generate_licensing_report(&paths, false).expect("report");
+        assert!(!skipped.python_unavailable);
+
+        let csv_path = dir.path().join("python_licenses.csv");
+        write_python_licenses_csv(
+            &csv_path,
+            &[PythonPackageLicense {
+                name: "numpy".to_string(),
+                version: "2.0".to_string(),
+                license: "BSD, see LICENSE".to_string(),
+                home_page: Some("https://numpy.org".to_string()),
+            }],
+        )
+        .expect("csv");
+        assert_eq!(
+            std::fs::read_to_string(&csv_path).expect("read"),
+            "name,version,license,home_page\nnumpy,2.0,\"BSD, see LICENSE\",https://numpy.org\n"
+        );
+    }
+
+    #[test]
+    fn storage_breakdown_lists_largest_items_first() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("ensure dirs");
+
+        let small = paths.derived_item_dir("small");
+        let big = paths.derived_item_dir("big");
+        std::fs::create_dir_all(small.join("asr")).expect("small asr");
+        std::fs::create_dir_all(big.join("tts_preview")).expect("big tts");
+        std::fs::write(small.join("asr").join("a.json"), vec![0_u8; 10]).expect("write small");
+        std::fs::write(big.join("tts_preview").join("b.wav"), vec![0_u8; 100]).expect("write big");
+
+        let breakdown = storage_breakdown(&paths, 1).expect("breakdown");
+        assert_eq!(breakdown.per_item.len(), 1);
+        assert_eq!(breakdown.per_item[0].item_id, "big");
+        assert_eq!(breakdown.per_item[0].tts_bytes, 100);
+        assert_eq!(breakdown.per_item[0].derived_bytes, 100);
+    }
+
+    #[test]
+    fn clean_orphans_previews_then_removes_unreferenced_dirs_and_logs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("ensure dirs");
+        db::ensure_schema(&paths).expect("schema");
+
+        let clip = dir.path().join("clip.wav");
+        std::fs::write(&clip, b"not really audio").expect("clip");
+        let item = library::import_local_file(&paths, &clip)
+            .expect("import")
+            .item;
+        let job = jobs::enqueue_dummy_sleep(&paths, 1).expect("job");
+        std::fs::write(paths.job_log_path(&job.id), "{}\n").expect("job log");
+
+        let orphan_dir = paths.derived_item_dir("00000000-0000-0000-0000-0000000000ff");
+        std::fs::create_dir_all(&orphan_dir).expect("orphan dir");
+        std::fs::write(orphan_dir.join("a.json"), vec![0_u8; 10]).expect("orphan file");
+        let orphan_log = paths
+            .job_logs_dir()
+            .join("00000000-0000-0000-0000-0000000000ee.jsonl.1");
+        std::fs::write(&orphan_log, vec![b'x'; 5]).expect("orphan log");
+
+        let preview = clean_orphans_older_than(&paths, true, Duration::ZERO).expect("preview");
+        assert_eq!(
+            preview.removed_dirs,
+            vec![orphan_dir.to_string_lossy().to_string()]
+        );
+        assert_eq!(
+            preview.removed_log_files,
+            vec![orphan_log.to_string_lossy().to_string()]
+        );
+        assert_eq!(preview.freed_bytes, 15);
+        assert!(orphan_dir.exists() && orphan_log.exists());
+
+        let cleaned = clean_orphans_older_than(&paths, false, Duration::ZERO).expect("clean");
+        assert_eq!(cleaned.freed_bytes, 15);
+        assert!(!orphan_dir.exists() && !orphan_log.exists());
+        assert!(paths.derived_item_dir(&item.id).exists());
+        assert!(paths.job_log_path(&job.id).exists());
+    }
+
+    #[test]
+    fn diagnostics_trace_rotation_shifts_numbered_backups() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let trace = dir.path().join("diagnostics_trace.jsonl");
+        let config = config::DiagnosticsTraceWriteConfig {
+            max_file_bytes: 4,
+            max_backup_files: 2,
+        };
+
+        std::fs::write(&trace, "first").expect("write first");
+        assert!(rotate_diagnostics_trace_if_needed(&trace, &config).expect("rotate 1"));
+        std::fs::write(&trace, "second").expect("write second");
+        assert!(rotate_diagnostics_trace_if_needed(&trace, &config).expect("rotate 2"));
+        std::fs::write(&trace, "third").expect("write third");
+        assert!(rotate_diagnostics_trace_if_needed(&trace, &config).expect("rotate 3"));
+
+        assert!(!trace.exists());
+        let backup1 = dir.path().join("diagnostics_trace.1.jsonl");
+        let backup2 = dir.path().join("diagnostics_trace.2.jsonl");
+        assert_eq!(std::fs::read_to_string(backup1).expect("b1"), "third");
+        assert_eq!(std::fs::read_to_string(backup2).expect("b2"), "second");
+        assert!(!dir.path().join("diagnostics_trace.3.jsonl").exists());
+
+        std::fs::write(&trace, "ok").expect("write small");
+        assert!(!rotate_diagnostics_trace_if_needed(&trace, &config).expect("no rotate"));
+    }
+
+    #[test]
+    fn health_check_reports_writable_dirs_and_free_space() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let report = health_check(&paths);
+        assert!(report.db_ok);
+        assert!(report.db_schema_version > 0);
+        assert_eq!(report.paths_writable.len(), 4);
+        assert!(report.paths_writable.values().all(|ok| *ok));
+        #[cfg(unix)]
+        assert!(!report.disk_free_gb.is_empty());
+        if !report.tools.ffmpeg.installed && report.tools.ffmpeg.ffmpeg_version.is_none() {
+            assert_eq!(report.overall, HealthLevel::Critical);
+        }
+    }
+
     #[test]
     fn prune_job_logs_removes_old_files_by_age() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -925,8 +1980,36 @@ INSERT INTO job(
         )
         .expect("insert job");
 
+        let secret_job_id = "job_with_cookie_secret";
+        let secret_log_path = paths.job_logs_dir().join(format!("{secret_job_id}.jsonl"));
+        std::fs::write(&secret_log_path, "{\"event\":\"download\"}\n").expect("write log");
+        std::fs::create_dir_all(paths.job_secrets_dir()).expect("secrets dir");
+        std::fs::write(paths.job_cookie_secret_path(secret_job_id), "cookie").expect("secret");
+        conn.execute(
+            r#"
+INSERT INTO job(
+  id, item_id, batch_id, type, status, progress, error, params_json,
+  created_at_ms, started_at_ms, finished_at_ms, logs_path
+) VALUES (?1, NULL, NULL, 'download_direct_url', 'succeeded', 1.0, NULL, '{}', ?2, NULL, ?2, ?3)
+"#,
+            params![
+                secret_job_id,
+                now_ms(),
+                secret_log_path.to_string_lossy().to_string()
+            ],
+        )
+        .expect("insert secret job");
+
         let out_path = dir.path().join("diagnostics.zip");
-        export_diagnostics_bundle(&paths, &out_path, "VoxVulgi", "0.0.0").expect("export");
+        let result = export_diagnostics_bundle(
+            &paths,
+            &out_path,
+            "VoxVulgi",
+            "0.0.0",
+            DEFAULT_BUNDLE_MAX_JOB_LOGS,
+        )
+        .expect("export");
+        assert_eq!(result.included_job_logs, 1);
 
         let file = std::fs::File::open(&out_path).expect("open zip");
         let mut archive = zip::ZipArchive::new(file).expect("zip archive");
@@ -952,7 +2035,21 @@ INSERT INTO job(
             .read_to_string(&mut redacted_log)
             .expect("read log");
 
-        for content in [&manifest, &jobs_failed, &redacted_log] {
+        assert!(archive
+            .by_name(&format!("jobs_logs/{job_id}.jsonl"))
+            .is_ok());
+        assert!(archive
+            .by_name(&format!("jobs_logs/{secret_job_id}.jsonl"))
+            .is_err());
+        let mut jobs_summary = String::new();
+        archive
+            .by_name("jobs_summary.json")
+            .expect("jobs_summary.json")
+            .read_to_string(&mut jobs_summary)
+            .expect("read jobs_summary");
+        assert!(jobs_summary.contains(secret_job_id));
+
+        for content in [&manifest, &jobs_failed, &redacted_log, &jobs_summary] {
             assert!(!content.contains("verysecret"), "cookie should be redacted");
             assert!(
                 !content.contains("abcdef"),
@@ -973,4 +2070,43 @@ INSERT INTO job(
             "redacted log should include redaction markers"
         );
     }
+
+    #[test]
+    fn clear_cache_selective_removes_only_selected_categories() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+
+        let hf = paths.cache_dir().join("huggingface").join("model.bin");
+        std::fs::create_dir_all(hf.parent().unwrap()).expect("hf dir");
+        std::fs::write(&hf, b"weights").expect("hf");
+        let item_dir = paths.derived_item_dir("item-1");
+        let asr_wav = item_dir.join("asr").join("audio_16k.wav");
+        let asr_json = item_dir.join("asr").join("transcript.json");
+        let tts_dir = item_dir.join("tts_preview").join("pyttsx3_v1");
+        std::fs::create_dir_all(asr_wav.parent().unwrap()).expect("asr dir");
+        std::fs::create_dir_all(tts_dir.join("segments")).expect("tts dir");
+        std::fs::write(&asr_wav, vec![0u8; 100]).expect("wav");
+        std::fs::write(&asr_json, b"{}").expect("json");
+        std::fs::write(tts_dir.join("segments").join("0001.wav"), vec![0u8; 40]).expect("seg");
+        std::fs::write(tts_dir.join("manifest.json"), b"{}").expect("manifest");
+
+        let summary = clear_cache_selective(
+            &paths,
+            &CacheClearOptions {
+                clear_asr_audio: true,
+                clear_tts_segments: true,
+                ..CacheClearOptions::default()
+            },
+        )
+        .expect("clear");
+
+        assert_eq!(summary.category_bytes.get("asr_audio"), Some(&100));
+        assert_eq!(summary.category_bytes.get("tts_segments"), Some(&42));
+        assert_eq!(summary.removed_bytes, 142);
+        assert!(!asr_wav.exists());
+        assert!(asr_json.exists());
+        assert!(!tts_dir.join("segments").exists());
+        assert!(hf.exists());
+    }
 }
@@ -53,6 +53,21 @@ pub enum EngineError {
 
     #[error("model/tool install failed: {0}")]
     InstallFailed(String),
+
+    #[error("{entity} not found: {id}")]
+    NotFound { entity: String, id: String },
+
+    #[error("invalid input: {message}")]
+    InvalidInput { message: String },
+}
+
+impl EngineError {
+    pub fn not_found(entity: &str, id: impl Into<String>) -> Self {
+        EngineError::NotFound {
+            entity: entity.to_string(),
+            id: id.into(),
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, EngineError>;
@@ -2,7 +2,13 @@ use crate::cmd;
 use crate::paths::AppPaths;
 use crate::{EngineError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, VecDeque};
+use std::ffi::OsString;
 use std::path::{Path, PathBuf};
+use std::sync::{Mutex, OnceLock};
+use std::time::SystemTime;
+
+const PROBE_CACHE_CAPACITY: usize = 512;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct MediaProbe {
@@ -12,9 +18,146 @@ pub struct MediaProbe {
     pub audio_codec: Option<String>,
     pub width: Option<i64>,
     pub height: Option<i64>,
+    #[serde(default)]
+    pub has_video: bool,
+    #[serde(default)]
+    pub has_audio: bool,
+    /// Container-level tags (title, artist, ...) with lowercased keys.
+    #[serde(default)]
+    pub format_tags: BTreeMap<String, String>,
+    #[serde(default)]
+    pub audio_sample_rate_hz: Option<u32>,
+    #[serde(default)]
+    pub audio_channels: Option<u8>,
+    #[serde(default)]
+    pub video_fps: Option<f32>,
+    #[serde(default)]
+    pub file_size_bytes: Option<u64>,
+}
+
+impl MediaProbe {
+    pub fn empty() -> Self {
+        Self {
+            duration_ms: None,
+            container: None,
+            video_codec: None,
+            audio_codec: None,
+            width: None,
+            height: None,
+            has_video: false,
+            has_audio: false,
+            format_tags: BTreeMap::new(),
+            audio_sample_rate_hz: None,
+            audio_channels: None,
+            video_fps: None,
+            file_size_bytes: None,
+        }
+    }
+}
+
+/// Size + mtime of a probed file; a mismatch means the file was rewritten since.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct ProbeFingerprint {
+    len: u64,
+    modified: Option<SystemTime>,
+}
+
+impl ProbeFingerprint {
+    fn of(path: &Path) -> Option<Self> {
+        let meta = std::fs::metadata(path).ok()?;
+        Some(Self {
+            len: meta.len(),
+            modified: meta.modified().ok(),
+        })
+    }
+}
+
+struct ProbeCache {
+    entries: HashMap<PathBuf, (ProbeFingerprint, MediaProbe)>,
+    order: VecDeque<PathBuf>,
+    capacity: usize,
+}
+
+impl ProbeCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    fn touch(&mut self, path: &Path) {
+        if let Some(pos) = self.order.iter().position(|p| p == path) {
+            if let Some(key) = self.order.remove(pos) {
+                self.order.push_back(key);
+            }
+        }
+    }
+
+    fn get(&mut self, path: &Path, fingerprint: ProbeFingerprint) -> Option<MediaProbe> {
+        let hit = match self.entries.get(path) {
+            Some((cached, probe)) if *cached == fingerprint => probe.clone(),
+            Some(_) => {
+                self.remove(path);
+                return None;
+            }
+            None => return None,
+        };
+        self.touch(path);
+        Some(hit)
+    }
+
+    fn insert(&mut self, path: PathBuf, fingerprint: ProbeFingerprint, probe: MediaProbe) {
+        if self.entries.contains_key(&path) {
+            self.touch(&path);
+        } else {
+            while self.order.len() >= self.capacity {
+                match self.order.pop_front() {
+                    Some(oldest) => {
+                        self.entries.remove(&oldest);
+                    }
+                    None => break,
+                }
+            }
+            self.order.push_back(path.clone());
+        }
+        self.entries.insert(path, (fingerprint, probe));
+    }
+
+    fn remove(&mut self, path: &Path) {
+        if self.entries.remove(path).is_some() {
+            self.order.retain(|p| p != path);
+        }
+    }
 }
 
+fn probe_cache() -> &'static Mutex<ProbeCache> {
+    static CACHE: OnceLock<Mutex<ProbeCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(ProbeCache::new(PROBE_CACHE_CAPACITY)))
+}
+
+/// Probes `input` with ffprobe, reusing a cached result while the file is unchanged.
 pub fn probe(paths: &AppPaths, input: &Path) -> Result<MediaProbe> {
+    let fingerprint = ProbeFingerprint::of(input);
+    if let Some(fingerprint) = fingerprint {
+        if let Ok(mut cache) = probe_cache().lock() {
+            if let Some(hit) = cache.get(input, fingerprint) {
+                return Ok(hit);
+            }
+        }
+    }
+
+    let probe = probe_uncached(paths, input)?;
+    if let Some(fingerprint) = fingerprint {
+        if let Ok(mut cache) = probe_cache().lock() {
+            cache.insert(input.to_path_buf(), fingerprint, probe.clone());
+        }
+    }
+    Ok(probe)
+}
+
+fn probe_uncached(paths: &AppPaths, input: &Path) -> Result<MediaProbe> {
     let output = cmd::command(paths.ffprobe_cmd())
         .args([
             "-v",
@@ -41,7 +184,11 @@ pub fn probe(paths: &AppPaths, input: &Path) -> Result<MediaProbe> {
         });
     }
 
-    let parsed: FfprobeOutput = serde_json::from_slice(&output.stdout)?;
+    media_probe_from_ffprobe_json(&output.stdout)
+}
+
+fn media_probe_from_ffprobe_json(stdout: &[u8]) -> Result<MediaProbe> {
+    let parsed: FfprobeOutput = serde_json::from_slice(stdout)?;
 
     let container = parsed
         .format
@@ -53,25 +200,59 @@ pub fn probe(paths: &AppPaths, input: &Path) -> Result<MediaProbe> {
         .as_ref()
         .and_then(|f| f.duration.as_deref())
         .and_then(parse_seconds_to_ms);
+    let format_tags = parsed
+        .format
+        .as_ref()
+        .map(|f| {
+            f.tags
+                .iter()
+                .map(|(k, v)| (k.to_ascii_lowercase(), v.trim().to_string()))
+                .filter(|(_, v)| !v.is_empty())
+                .collect()
+        })
+        .unwrap_or_default();
 
-    let (video_codec, width, height) = parsed
+    let has_video = parsed
         .streams
         .as_ref()
-        .and_then(|s| {
-            s.iter()
-                .find(|st| st.codec_type.as_deref() == Some("video"))
-        })
+        .is_some_and(|s| s.iter().any(|st| st.codec_type.as_deref() == Some("video")));
+    let has_audio = parsed
+        .streams
+        .as_ref()
+        .is_some_and(|s| s.iter().any(|st| st.codec_type.as_deref() == Some("audio")));
+
+    let video_stream = parsed.streams.as_ref().and_then(|s| {
+        s.iter()
+            .find(|st| st.codec_type.as_deref() == Some("video"))
+    });
+    let (video_codec, width, height) = video_stream
         .map(|st| (st.codec_name.clone(), st.width, st.height))
         .unwrap_or((None, None, None));
-
-    let audio_codec = parsed
-        .streams
+    let video_fps = video_stream.and_then(|st| {
+        st.avg_frame_rate
+            .as_deref()
+            .and_then(parse_frame_rate)
+            .or_else(|| st.r_frame_rate.as_deref().and_then(parse_frame_rate))
+    });
+
+    let audio_stream = parsed.streams.as_ref().and_then(|s| {
+        s.iter()
+            .find(|st| st.codec_type.as_deref() == Some("audio"))
+    });
+    let audio_codec = audio_stream.and_then(|st| st.codec_name.clone());
+    let audio_sample_rate_hz = audio_stream
+        .and_then(|st| st.sample_rate.as_deref())
+        .and_then(|v| v.trim().parse::<u32>().ok())
+        .filter(|v| *v > 0);
+    let audio_channels = audio_stream
+        .and_then(|st| st.channels)
+        .and_then(|v| u8::try_from(v).ok())
+        .filter(|v| *v > 0);
+    let file_size_bytes = parsed
+        .format
         .as_ref()
-        .and_then(|s| {
-            s.iter()
-                .find(|st| st.codec_type.as_deref() == Some("audio"))
-        })
-        .and_then(|st| st.codec_name.clone());
+        .and_then(|f| f.size.as_deref())
+        .and_then(|v| v.trim().parse::<u64>().ok());
 
     Ok(MediaProbe {
         duration_ms,
@@ -80,6 +261,13 @@ pub fn probe(paths: &AppPaths, input: &Path) -> Result<MediaProbe> {
         audio_codec,
         width,
         height,
+        has_video,
+        has_audio,
+        format_tags,
+        audio_sample_rate_hz,
+        audio_channels,
+        video_fps,
+        file_size_bytes,
     })
 }
 
@@ -105,7 +293,10 @@ pub fn generate_thumbnail(
         .arg("-i")
         .arg(input)
         .args(["-frames:v", "1"])
-        .args(["-vf", "scale='min(480,iw)':-2"])
+        .args([
+            "-vf",
+            "scale='min(320,iw)':'min(180,ih)':force_original_aspect_ratio=decrease",
+        ])
         .args(["-q:v", "3"])
         .arg(output_image)
         .output()
@@ -127,18 +318,27 @@ pub fn generate_thumbnail(
     Ok(())
 }
 
+const SUPPORTED_EXTRACT_SAMPLE_RATES_HZ: &[u32] = &[8000, 16000, 22050, 44100, 48000];
+
 pub fn extract_audio_wav_16k_mono(paths: &AppPaths, input: &Path, output_wav: &Path) -> Result<()> {
+    extract_audio_wav_resampled(paths, input, output_wav, 16000, 1)
+}
+
+/// Extracts the first audio stream as 16-bit PCM WAV at the requested rate and channel count.
+pub fn extract_audio_wav_resampled(
+    paths: &AppPaths,
+    input: &Path,
+    output_wav: &Path,
+    sample_rate_hz: u32,
+    channels: u8,
+) -> Result<()> {
+    let args = extract_audio_wav_args(input, output_wav, sample_rate_hz, channels)?;
     if let Some(parent) = output_wav.parent() {
         std::fs::create_dir_all(parent)?;
     }
 
     let output = cmd::command(paths.ffmpeg_cmd())
-        .args(["-nostdin", "-y"])
-        .arg("-i")
-        .arg(input)
-        .args(["-vn", "-ac", "1", "-ar", "16000"])
-        .args(["-c:a", "pcm_s16le"])
-        .arg(output_wav)
+        .args(&args)
         .output()
         .map_err(|e| match e.kind() {
             std::io::ErrorKind::NotFound => EngineError::ExternalToolMissing {
@@ -158,6 +358,34 @@ pub fn extract_audio_wav_16k_mono(paths: &AppPaths, input: &Path, output_wav: &P
     Ok(())
 }
 
+fn extract_audio_wav_args(
+    input: &Path,
+    output_wav: &Path,
+    sample_rate_hz: u32,
+    channels: u8,
+) -> Result<Vec<OsString>> {
+    if !SUPPORTED_EXTRACT_SAMPLE_RATES_HZ.contains(&sample_rate_hz) {
+        return Err(EngineError::InstallFailed(format!(
+            "unsupported sample rate {sample_rate_hz} Hz (expected one of {SUPPORTED_EXTRACT_SAMPLE_RATES_HZ:?})"
+        )));
+    }
+    if !(1..=2).contains(&channels) {
+        return Err(EngineError::InstallFailed(format!(
+            "unsupported channel count {channels} (expected 1 or 2)"
+        )));
+    }
+
+    let mut args: Vec<OsString> = ["-nostdin", "-y", "-i"].map(OsString::from).to_vec();
+    args.push(input.as_os_str().to_os_string());
+    args.extend(["-vn", "-ac"].map(OsString::from));
+    args.push(channels.to_string().into());
+    args.push("-ar".into());
+    args.push(sample_rate_hz.to_string().into());
+    args.extend(["-c:a", "pcm_s16le"].map(OsString::from));
+    args.push(output_wav.as_os_str().to_os_string());
+    Ok(args)
+}
+
 pub fn extract_audio_clip_wav_16k_mono(
     paths: &AppPaths,
     input: &Path,
@@ -252,20 +480,76 @@ pub fn concat_wav_files_16k_mono(
     Ok(())
 }
 
-pub fn extract_audio_wav_44k_stereo(
+/// Cuts `[start_ms, start_ms + duration_ms)` out of a WAV file, keeping its rate and channels.
+pub fn extract_wav_segment(
     paths: &AppPaths,
-    input: &Path,
+    input_wav: &Path,
     output_wav: &Path,
+    start_ms: i64,
+    duration_ms: i64,
 ) -> Result<()> {
     if let Some(parent) = output_wav.parent() {
         std::fs::create_dir_all(parent)?;
     }
+    let start_seconds = (start_ms.max(0) as f64) / 1000.0;
+    let duration_seconds = (duration_ms.max(1) as f64) / 1000.0;
 
     let output = cmd::command(paths.ffmpeg_cmd())
         .args(["-nostdin", "-y"])
+        .args(["-ss", &format!("{start_seconds:.3}")])
         .arg("-i")
-        .arg(input)
-        .args(["-vn", "-ac", "2", "-ar", "44100"])
+        .arg(input_wav)
+        .args(["-t", &format!("{duration_seconds:.3}")])
+        .args(["-vn", "-c:a", "pcm_s16le"])
+        .arg(output_wav)
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => EngineError::ExternalToolMissing {
+                tool: "ffmpeg".to_string(),
+            },
+            _ => EngineError::Io(e),
+        })?;
+
+    if !output.status.success() {
+        return Err(EngineError::ExternalToolFailed {
+            tool: "ffmpeg".to_string(),
+            code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+
+    Ok(())
+}
+
+/// Joins WAV chunks that overlap by `overlap_ms`, crossfading each boundary.
+pub fn concat_wav_files_crossfaded(
+    paths: &AppPaths,
+    inputs: &[PathBuf],
+    overlap_ms: i64,
+    output_wav: &Path,
+) -> Result<()> {
+    if inputs.is_empty() {
+        return Err(EngineError::InstallFailed(
+            "concat requested with no input wav files".to_string(),
+        ));
+    }
+    if let Some(parent) = output_wav.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    if inputs.len() == 1 {
+        std::fs::copy(&inputs[0], output_wav)?;
+        return Ok(());
+    }
+
+    let mut command = cmd::command(paths.ffmpeg_cmd());
+    command.args(["-nostdin", "-y"]);
+    for input in inputs {
+        command.arg("-i").arg(input);
+    }
+    let filter = crossfade_concat_filter(inputs.len(), overlap_ms);
+    let output = command
+        .args(["-filter_complex", &filter])
+        .args(["-map", "[out]"])
         .args(["-c:a", "pcm_s16le"])
         .arg(output_wav)
         .output()
@@ -287,6 +571,195 @@ pub fn extract_audio_wav_44k_stereo(
     Ok(())
 }
 
+fn crossfade_concat_filter(input_count: usize, overlap_ms: i64) -> String {
+    let overlap_seconds = (overlap_ms.max(1) as f64) / 1000.0;
+    let mut filter = String::new();
+    let mut previous = "[0:a]".to_string();
+    for index in 1..input_count {
+        let label = if index + 1 == input_count {
+            "[out]".to_string()
+        } else {
+            format!("[x{index}]")
+        };
+        if !filter.is_empty() {
+            filter.push(';');
+        }
+        filter.push_str(&format!(
+            "{previous}[{index}:a]acrossfade=d={overlap_seconds:.3}:c1=tri:c2=tri{label}"
+        ));
+        previous = label;
+    }
+    filter
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct SilenceRange {
+    pub start_ms: i64,
+    pub end_ms: i64,
+}
+
+/// Runs ffmpeg's `silencedetect` filter over `audio_path` and returns the silent ranges found.
+pub fn detect_silences(
+    paths: &AppPaths,
+    audio_path: &Path,
+    noise_db: f32,
+    min_duration_ms: u64,
+) -> Result<Vec<SilenceRange>> {
+    let filter = format!(
+        "silencedetect=noise={noise_db}dB:d={:.3}",
+        (min_duration_ms as f64) / 1000.0
+    );
+    let output = cmd::command(paths.ffmpeg_cmd())
+        .args(["-nostdin", "-hide_banner"])
+        .arg("-i")
+        .arg(audio_path)
+        .args(["-vn", "-af", &filter])
+        .args(["-f", "null", "-"])
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => EngineError::ExternalToolMissing {
+                tool: "ffmpeg".to_string(),
+            },
+            _ => EngineError::Io(e),
+        })?;
+
+    if !output.status.success() {
+        return Err(EngineError::ExternalToolFailed {
+            tool: "ffmpeg".to_string(),
+            code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+
+    // A silence still open at end of stream has no `silence_end` line; close it at the duration.
+    let total_ms = probe(paths, audio_path).ok().and_then(|p| p.duration_ms);
+    Ok(parse_silencedetect_output(
+        &String::from_utf8_lossy(&output.stderr),
+        total_ms,
+    ))
+}
+
+fn parse_silencedetect_output(stderr: &str, total_ms: Option<i64>) -> Vec<SilenceRange> {
+    let mut ranges = Vec::new();
+    let mut open_start_ms: Option<i64> = None;
+    for line in stderr.lines() {
+        if let Some(rest) = line.split("silence_start:").nth(1) {
+            open_start_ms = rest
+                .split_whitespace()
+                .next()
+                .and_then(parse_seconds_to_ms_clamped);
+        } else if let Some(rest) = line.split("silence_end:").nth(1) {
+            let end_ms = rest
+                .split_whitespace()
+                .next()
+                .and_then(parse_seconds_to_ms_clamped);
+            if let (Some(start_ms), Some(end_ms)) = (open_start_ms.take(), end_ms) {
+                if end_ms > start_ms {
+                    ranges.push(SilenceRange { start_ms, end_ms });
+                }
+            }
+        }
+    }
+    if let (Some(start_ms), Some(end_ms)) = (open_start_ms, total_ms) {
+        if end_ms > start_ms {
+            ranges.push(SilenceRange { start_ms, end_ms });
+        }
+    }
+    ranges
+}
+
+/// silencedetect can report slightly negative starts when silence begins at the first sample.
+fn parse_seconds_to_ms_clamped(value: &str) -> Option<i64> {
+    let seconds: f64 = value.parse().ok()?;
+    if !seconds.is_finite() {
+        return None;
+    }
+    Some((seconds.max(0.0) * 1000.0).round() as i64)
+}
+
+/// Fraction of `[start_ms, end_ms)` covered by `ranges`, in `0.0..=1.0`.
+pub fn silence_coverage(ranges: &[SilenceRange], start_ms: i64, end_ms: i64) -> f32 {
+    if end_ms <= start_ms {
+        return 0.0;
+    }
+    let covered: i64 = ranges
+        .iter()
+        .map(|r| (r.end_ms.min(end_ms) - r.start_ms.max(start_ms)).max(0))
+        .sum();
+    (covered as f32 / (end_ms - start_ms) as f32).clamp(0.0, 1.0)
+}
+
+/// Input statistics reported by a measuring `loudnorm` pass. Silent input reports `-inf`, which
+/// is kept as `None`.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct LoudnormMeasurement {
+    pub input_i: Option<f32>,
+    pub input_tp: Option<f32>,
+    pub input_lra: Option<f32>,
+    pub input_thresh: Option<f32>,
+}
+
+/// Runs a measuring `loudnorm` pass over `audio_path` without writing any output.
+pub fn measure_loudnorm(
+    paths: &AppPaths,
+    audio_path: &Path,
+    target_lufs: f32,
+    true_peak_db: f32,
+    lra: f32,
+) -> Result<LoudnormMeasurement> {
+    let filter = format!(
+        "loudnorm=I={target_lufs:.1}:TP={true_peak_db:.1}:LRA={lra:.1}:dual_mono=true:print_format=json"
+    );
+    let output = cmd::command(paths.ffmpeg_cmd())
+        .args(["-nostdin", "-hide_banner"])
+        .arg("-i")
+        .arg(audio_path)
+        .args(["-vn", "-af", &filter])
+        .args(["-f", "null", "-"])
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => EngineError::ExternalToolMissing {
+                tool: "ffmpeg".to_string(),
+            },
+            _ => EngineError::Io(e),
+        })?;
+
+    if !output.status.success() {
+        return Err(EngineError::ExternalToolFailed {
+            tool: "ffmpeg".to_string(),
+            code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+
+    parse_loudnorm_output(&String::from_utf8_lossy(&output.stderr)).ok_or_else(|| {
+        EngineError::InstallFailed(format!(
+            "loudnorm printed no measurement for {}",
+            audio_path.display()
+        ))
+    })
+}
+
+/// loudnorm prints its JSON block last, with every value as a string.
+fn parse_loudnorm_output(stderr: &str) -> Option<LoudnormMeasurement> {
+    let start = stderr.rfind('{')?;
+    let end = start + stderr[start..].find('}')?;
+    let value: serde_json::Value = serde_json::from_str(&stderr[start..=end]).ok()?;
+    let field = |key: &str| {
+        value
+            .get(key)
+            .and_then(|v| v.as_str())
+            .and_then(|v| v.trim().parse::<f32>().ok())
+            .filter(|v| v.is_finite())
+    };
+    Some(LoudnormMeasurement {
+        input_i: field("input_i"),
+        input_tp: field("input_tp"),
+        input_lra: field("input_lra"),
+        input_thresh: field("input_thresh"),
+    })
+}
+
 #[derive(Debug, Clone, Deserialize)]
 struct FfprobeOutput {
     streams: Option<Vec<FfprobeStream>>,
@@ -299,18 +772,42 @@ struct FfprobeStream {
     codec_name: Option<String>,
     width: Option<i64>,
     height: Option<i64>,
+    #[serde(default)]
+    sample_rate: Option<String>,
+    #[serde(default)]
+    channels: Option<i64>,
+    #[serde(default)]
+    avg_frame_rate: Option<String>,
+    #[serde(default)]
+    r_frame_rate: Option<String>,
 }
 
 #[derive(Debug, Clone, Deserialize)]
 struct FfprobeFormat {
     format_name: Option<String>,
     duration: Option<String>,
+    #[serde(default)]
+    size: Option<String>,
+    #[serde(default)]
+    tags: HashMap<String, String>,
 }
 
 fn first_format_name(value: &str) -> String {
     value.split(',').next().unwrap_or(value).trim().to_string()
 }
 
+/// Parses ffprobe rates such as `30000/1001`; `0/0` (unknown) yields `None`.
+fn parse_frame_rate(value: &str) -> Option<f32> {
+    let (num, den) = match value.trim().split_once('/') {
+        Some((num, den)) => (num.parse::<f64>().ok()?, den.parse::<f64>().ok()?),
+        None => (value.trim().parse::<f64>().ok()?, 1.0),
+    };
+    if den <= 0.0 || num <= 0.0 || !num.is_finite() {
+        return None;
+    }
+    Some((num / den) as f32)
+}
+
 fn parse_seconds_to_ms(value: &str) -> Option<i64> {
     let seconds: f64 = value.parse().ok()?;
     if !seconds.is_finite() || seconds < 0.0 {
@@ -318,3 +815,141 @@ fn parse_seconds_to_ms(value: &str) -> Option<i64> {
     }
     Some((seconds * 1000.0).round() as i64)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn probe_cache_evicts_least_recently_used_and_rejects_stale_fingerprints() {
+        let mut cache = ProbeCache::new(2);
+        let fp = ProbeFingerprint {
+            len: 1,
+            modified: None,
+        };
+        cache.insert(PathBuf::from("a"), fp, MediaProbe::empty());
+        cache.insert(PathBuf::from("b"), fp, MediaProbe::empty());
+        assert!(cache.get(Path::new("a"), fp).is_some());
+        cache.insert(PathBuf::from("c"), fp, MediaProbe::empty());
+        assert!(cache.get(Path::new("b"), fp).is_none());
+        assert!(cache.get(Path::new("a"), fp).is_some());
+
+        let rewritten = ProbeFingerprint {
+            len: 2,
+            modified: None,
+        };
+        assert!(cache.get(Path::new("a"), rewritten).is_none());
+        assert!(!cache.entries.contains_key(Path::new("a")));
+    }
+
+    #[test]
+    fn media_probe_reads_stream_rates_channels_and_size() {
+        let json = br#"{
+            "streams": [
+                {"codec_type": "video", "codec_name": "h264", "width": 1920, "height": 1080,
+                 "avg_frame_rate": "30000/1001", "r_frame_rate": "30/1"},
+                {"codec_type": "audio", "codec_name": "aac", "sample_rate": "44100", "channels": 2}
+            ],
+            "format": {"format_name": "mov,mp4", "duration": "1.5", "size": "12345"}
+        }"#;
+        let probe = media_probe_from_ffprobe_json(json).expect("probe");
+        assert_eq!(probe.container.as_deref(), Some("mov"));
+        assert_eq!(probe.audio_sample_rate_hz, Some(44100));
+        assert_eq!(probe.audio_channels, Some(2));
+        assert_eq!(probe.file_size_bytes, Some(12345));
+        assert!((probe.video_fps.expect("fps") - 29.97).abs() < 0.01);
+        assert_eq!(parse_frame_rate("0/0"), None);
+    }
+
+    #[test]
+    fn extract_audio_wav_args_use_requested_rate_and_channels() {
+        let args = extract_audio_wav_args(Path::new("in.mp4"), Path::new("out.wav"), 44100, 1)
+            .expect("args");
+        let args: Vec<String> = args
+            .iter()
+            .map(|v| v.to_string_lossy().to_string())
+            .collect();
+        assert_eq!(
+            args,
+            [
+                "-nostdin",
+                "-y",
+                "-i",
+                "in.mp4",
+                "-vn",
+                "-ac",
+                "1",
+                "-ar",
+                "44100",
+                "-c:a",
+                "pcm_s16le",
+                "out.wav"
+            ]
+        );
+
+        assert!(extract_audio_wav_args(Path::new("in.mp4"), Path::new("o.wav"), 32000, 1).is_err());
+        assert!(extract_audio_wav_args(Path::new("in.mp4"), Path::new("o.wav"), 48000, 3).is_err());
+    }
+
+    #[test]
+    fn crossfade_concat_filter_chains_each_boundary() {
+        assert_eq!(
+            crossfade_concat_filter(3, 5000),
+            "[0:a][1:a]acrossfade=d=5.000:c1=tri:c2=tri[x1];[x1][2:a]acrossfade=d=5.000:c1=tri:c2=tri[out]"
+        );
+        assert_eq!(
+            crossfade_concat_filter(2, 250),
+            "[0:a][1:a]acrossfade=d=0.250:c1=tri:c2=tri[out]"
+        );
+    }
+
+    #[test]
+    fn parse_loudnorm_output_reads_trailing_json_block() {
+        let stderr = "\
+size=N/A time=00:00:02.00 bitrate=N/A
+[Parsed_loudnorm_0 @ 0x1]
+{
+\t\"input_i\" : \"-23.54\",
+\t\"input_tp\" : \"-4.20\",
+\t\"input_lra\" : \"1.10\",
+\t\"input_thresh\" : \"-inf\",
+\t\"target_offset\" : \"0.00\"
+}
+";
+        let m = parse_loudnorm_output(stderr).expect("measurement");
+        assert_eq!(m.input_i, Some(-23.54));
+        assert_eq!(m.input_tp, Some(-4.2));
+        assert_eq!(m.input_lra, Some(1.1));
+        assert_eq!(m.input_thresh, None);
+        assert!(parse_loudnorm_output("no stats here").is_none());
+    }
+
+    #[test]
+    fn parse_silencedetect_output_pairs_ranges_and_closes_trailing_silence() {
+        let stderr = "\
+[silencedetect @ 0x1] silence_start: -0.00133
+[silencedetect @ 0x1] silence_end: 1.5 | silence_duration: 1.50133
+size=N/A time=00:00:05.00 bitrate=N/A
+[silencedetect @ 0x1] silence_start: 4.25
+";
+        let ranges = parse_silencedetect_output(stderr, Some(5000));
+        assert_eq!(
+            ranges,
+            [
+                SilenceRange {
+                    start_ms: 0,
+                    end_ms: 1500
+                },
+                SilenceRange {
+                    start_ms: 4250,
+                    end_ms: 5000
+                }
+            ]
+        );
+        assert!(parse_silencedetect_output(stderr, None).len() == 1);
+
+        assert_eq!(silence_coverage(&ranges, 1000, 2000), 0.5);
+        assert_eq!(silence_coverage(&ranges, 2000, 4000), 0.0);
+        assert_eq!(silence_coverage(&ranges, 4500, 5000), 1.0);
+    }
+}
@@ -19,6 +19,12 @@ const DEFAULT_OUTPUT_SUBDIR: &str = "image_archive";
 const DEFAULT_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/130.0.0.0 Safari/537.36";
 const MAX_INLINE_RESOLVE_PAGES: usize = 24;
 const MAX_INLINE_HTML_BYTES: u64 = 2 * 1024 * 1024;
+const DEFAULT_MAX_HAMMING_DISTANCE: u8 = 2;
+const MAX_MAX_HAMMING_DISTANCE: u8 = 10;
+const MIN_IMAGE_BYTES: usize = 256;
+const DEFAULT_MAX_IMAGE_BYTES: u64 = 20 * 1024 * 1024;
+/// Allocation cap for the decoder while probing image dimensions.
+const DIMENSION_PROBE_MAX_ALLOC_BYTES: u64 = 100 * 1024 * 1024;
 
 const PROFILE_MARKERS: &[&str] = &[
     "avatar",
@@ -134,6 +140,18 @@ pub struct ImageBatchRequest {
     pub skip_url_keywords: Vec<String>,
     pub output_subdir: String,
     pub auth_cookie: Option<String>,
+    pub max_hamming_distance: u8,
+    #[serde(default = "default_max_image_bytes")]
+    pub max_image_bytes: u64,
+    /// Lowercase extensions without the dot; when non-empty, other image URLs are not fetched.
+    #[serde(default)]
+    pub file_extensions_filter: Vec<String>,
+    /// Images with fewer pixels (width * height) are rejected; `None` means no limit.
+    #[serde(default)]
+    pub min_image_pixels: Option<u64>,
+    /// Images with more pixels are rejected; `None` means no limit.
+    #[serde(default)]
+    pub max_image_pixels: Option<u64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -142,7 +160,23 @@ pub struct ImageBatchSummary {
     pub images_downloaded: usize,
     pub skipped_profile_images: usize,
     pub duplicate_images: usize,
+    pub duplicate_images_by_hash: usize,
     pub failed_images: usize,
+    /// Images whose leading bytes matched no known image format.
+    #[serde(default)]
+    pub rejected_wrong_mime: usize,
+    /// Images below the minimum size or above `max_image_bytes`.
+    #[serde(default)]
+    pub rejected_bad_size: usize,
+    /// Images skipped because their URL did not match `file_extensions_filter`.
+    #[serde(default)]
+    pub filtered_by_extension: usize,
+    /// Images with fewer pixels than `min_image_pixels`.
+    #[serde(default)]
+    pub rejected_too_small_px: usize,
+    /// Images with more pixels than `max_image_pixels`.
+    #[serde(default)]
+    pub rejected_too_large_px: usize,
     pub manifest_path: String,
     pub output_dir: String,
 }
@@ -158,21 +192,76 @@ struct ImageCandidate {
 enum CandidateStatus {
     Downloaded,
     Duplicate,
+    DuplicateByHash,
     SkippedProfile,
     SkippedCustomKeyword,
+    FilteredByExtension,
+    RejectedWrongMime,
+    RejectedBadSize,
+    RejectedTooSmallPx,
+    RejectedTooLargePx,
     Failed,
 }
 
+#[derive(Debug, Clone)]
+struct ImageRejection {
+    url: String,
+    reason: &'static str,
+    bytes: u64,
+}
+
+/// Crawl settings for [`build_image_batch_request`]. `None` fields fall back to the defaults.
+#[derive(Debug, Clone, Default)]
+pub struct ImageBatchRequestOptions {
+    pub max_pages: Option<usize>,
+    pub delay_ms: Option<u64>,
+    pub allow_cross_domain: Option<bool>,
+    pub follow_content_links: Option<bool>,
+    pub skip_url_keywords: Vec<String>,
+    pub output_subdir: Option<String>,
+    pub auth_cookie: Option<String>,
+    pub max_hamming_distance: Option<u8>,
+    pub max_image_bytes: Option<u64>,
+}
+
+/// Per-candidate settings for [`download_candidate_image`], taken once from the request.
+struct DownloadLimits<'a> {
+    max_hamming_distance: u8,
+    max_image_bytes: u64,
+    min_image_pixels: Option<u64>,
+    max_image_pixels: Option<u64>,
+    skip_url_keywords: &'a [String],
+    auth_cookie: Option<&'a str>,
+}
+
+impl<'a> DownloadLimits<'a> {
+    fn from_request(request: &'a ImageBatchRequest) -> Self {
+        Self {
+            max_hamming_distance: request.max_hamming_distance,
+            max_image_bytes: request.max_image_bytes,
+            min_image_pixels: request.min_image_pixels,
+            max_image_pixels: request.max_image_pixels,
+            skip_url_keywords: &request.skip_url_keywords,
+            auth_cookie: request.auth_cookie.as_deref(),
+        }
+    }
+}
+
 pub fn build_image_batch_request(
     start_urls: Vec<String>,
-    max_pages: Option<usize>,
-    delay_ms: Option<u64>,
-    allow_cross_domain: Option<bool>,
-    follow_content_links: Option<bool>,
-    skip_url_keywords: Vec<String>,
-    output_subdir: Option<String>,
-    auth_cookie: Option<String>,
+    options: ImageBatchRequestOptions,
 ) -> Result<ImageBatchRequest> {
+    let ImageBatchRequestOptions {
+        max_pages,
+        delay_ms,
+        allow_cross_domain,
+        follow_content_links,
+        skip_url_keywords,
+        output_subdir,
+        auth_cookie,
+        max_hamming_distance,
+        max_image_bytes,
+    } = options;
     let start_urls = normalize_start_urls(start_urls)?;
     if start_urls.is_empty() {
         return Err(EngineError::InstallFailed(
@@ -189,6 +278,8 @@ pub fn build_image_batch_request(
     let output_subdir = sanitize_output_subdir(output_subdir.as_deref().unwrap_or(""));
     let skip_url_keywords = normalize_keywords(skip_url_keywords);
     let auth_cookie = normalize_cookie(auth_cookie.as_deref());
+    let max_hamming_distance = normalize_max_hamming_distance(max_hamming_distance);
+    let max_image_bytes = normalize_max_image_bytes(max_image_bytes);
 
     Ok(ImageBatchRequest {
         start_urls,
@@ -199,6 +290,11 @@ pub fn build_image_batch_request(
         skip_url_keywords,
         output_subdir,
         auth_cookie,
+        max_hamming_distance,
+        max_image_bytes,
+        file_extensions_filter: Vec::new(),
+        min_image_pixels: None,
+        max_image_pixels: None,
     })
 }
 
@@ -241,12 +337,22 @@ where
     let mut visited_pages: HashSet<String> = HashSet::new();
     let mut seen_image_urls: HashSet<String> = HashSet::new();
     let mut seen_hashes: HashSet<String> = HashSet::new();
+    let mut seen_dhashes: HashSet<u64> = HashSet::new();
+    let limits = DownloadLimits::from_request(request);
 
     let mut pages_crawled = 0_usize;
     let mut downloaded = 0_usize;
     let mut skipped_profile = 0_usize;
     let mut duplicate_images = 0_usize;
+    let mut duplicate_images_by_hash = 0_usize;
     let mut failed_images = 0_usize;
+    let mut rejected_wrong_mime = 0_usize;
+    let mut rejected_bad_size = 0_usize;
+    let mut filtered_by_extension = 0_usize;
+    let mut rejected_too_small_px = 0_usize;
+    let mut rejected_too_large_px = 0_usize;
+    let mut canceled = false;
+    let mut last_progress = 0.0_f32;
 
     while let Some(page_url) = queue.pop_front() {
         if pages_crawled >= request.max_pages {
@@ -306,8 +412,11 @@ where
         }
 
         pages_crawled += 1;
-        let pct = 0.10 + 0.80 * ((pages_crawled as f32) / (request.max_pages as f32)).min(1.0);
-        set_progress(pct)?;
+        let pct = 0.10 + 0.80 * ((pages_crawled - 1) as f32 / request.max_pages as f32).min(1.0);
+        if pct > last_progress {
+            last_progress = pct;
+            set_progress(pct)?;
+        }
         let html = String::from_utf8_lossy(&html_buf).into_owned();
         let document = Html::parse_document(&html);
 
@@ -321,8 +430,13 @@ where
         )?;
 
         let candidates = extract_image_candidates(&document, &page_url);
+        // The current page counts with its candidate total so progress moves within a page.
+        let avg_images_per_page =
+            (downloaded + candidates.len()) as f32 / pages_crawled.max(1) as f32;
+        let estimated_total = (request.max_pages as f32 * avg_images_per_page).max(1.0);
         for candidate in candidates {
             if should_cancel()? {
+                canceled = true;
                 break;
             }
             let Some(first_url) = candidate.urls.first() else {
@@ -332,6 +446,30 @@ where
                 continue;
             }
 
+            let candidate =
+                match filter_candidate_by_extension(candidate, &request.file_extensions_filter) {
+                    Ok(candidate) => candidate,
+                    Err(candidate) => {
+                        filtered_by_extension += 1;
+                        write_manifest_row(
+                            &mut manifest,
+                            &[
+                                &candidate.page_url,
+                                candidate.urls.first().map(String::as_str).unwrap_or(""),
+                                status_as_str(CandidateStatus::FilteredByExtension),
+                                "",
+                                "",
+                                "",
+                                &candidate.urls.len().to_string(),
+                            ],
+                        )?;
+                        continue;
+                    }
+                };
+            let Some(first_url) = candidate.urls.first() else {
+                continue;
+            };
+
             let host_folder = sanitize_name(
                 host_of(&candidate.page_url)
                     .as_deref()
@@ -340,20 +478,56 @@ where
             let image_out_dir = output_root.join(host_folder).join("images");
             std::fs::create_dir_all(&image_out_dir)?;
 
+            let mut rejections = Vec::new();
             let (status, saved_path, byte_count, digest) = download_candidate_image(
                 &agent,
                 &candidate,
                 &image_out_dir,
                 &mut seen_hashes,
-                &request.skip_url_keywords,
-                request.auth_cookie.as_deref(),
+                &mut seen_dhashes,
+                &limits,
+                &mut rejections,
             );
+            for rejection in &rejections {
+                log_line(
+                    "warn",
+                    "image_rejected",
+                    serde_json::json!({
+                        "url": redact_url_for_log(&rejection.url),
+                        "reason": rejection.reason,
+                        "bytes": rejection.bytes,
+                    }),
+                )?;
+            }
 
             match status {
-                CandidateStatus::Downloaded => downloaded += 1,
+                CandidateStatus::Downloaded => {
+                    downloaded += 1;
+                    log_line(
+                        "info",
+                        "image_downloaded",
+                        serde_json::json!({
+                            "url": redact_url_for_log(first_url),
+                            "path": saved_path.as_deref().unwrap_or(""),
+                            "size_bytes": byte_count,
+                            "page_index": pages_crawled,
+                        }),
+                    )?;
+                    let pct = 0.10 + 0.80 * (downloaded as f32 / estimated_total).min(1.0);
+                    if pct > last_progress {
+                        last_progress = pct;
+                        set_progress(pct)?;
+                    }
+                }
                 CandidateStatus::Duplicate => duplicate_images += 1,
+                CandidateStatus::DuplicateByHash => duplicate_images_by_hash += 1,
                 CandidateStatus::SkippedProfile => skipped_profile += 1,
                 CandidateStatus::SkippedCustomKeyword => {}
+                CandidateStatus::FilteredByExtension => filtered_by_extension += 1,
+                CandidateStatus::RejectedWrongMime => rejected_wrong_mime += 1,
+                CandidateStatus::RejectedBadSize => rejected_bad_size += 1,
+                CandidateStatus::RejectedTooSmallPx => rejected_too_small_px += 1,
+                CandidateStatus::RejectedTooLargePx => rejected_too_large_px += 1,
                 CandidateStatus::Failed => failed_images += 1,
             }
 
@@ -370,6 +544,9 @@ where
                 ],
             )?;
         }
+        if canceled {
+            break;
+        }
 
         let (next_links, content_links) =
             discover_links(&document, &page_url, request.follow_content_links);
@@ -400,12 +577,35 @@ where
         images_downloaded: downloaded,
         skipped_profile_images: skipped_profile,
         duplicate_images,
+        duplicate_images_by_hash,
         failed_images,
+        rejected_wrong_mime,
+        rejected_bad_size,
+        filtered_by_extension,
+        rejected_too_small_px,
+        rejected_too_large_px,
         manifest_path: manifest_path.to_string_lossy().to_string(),
         output_dir: output_root.to_string_lossy().to_string(),
     })
 }
 
+pub fn normalize_max_image_bytes(value: Option<u64>) -> u64 {
+    value
+        .filter(|v| *v > 0)
+        .unwrap_or(DEFAULT_MAX_IMAGE_BYTES)
+        .max(MIN_IMAGE_BYTES as u64)
+}
+
+fn default_max_image_bytes() -> u64 {
+    DEFAULT_MAX_IMAGE_BYTES
+}
+
+pub fn normalize_max_hamming_distance(value: Option<u8>) -> u8 {
+    value
+        .unwrap_or(DEFAULT_MAX_HAMMING_DISTANCE)
+        .min(MAX_MAX_HAMMING_DISTANCE)
+}
+
 fn normalize_start_urls(inputs: Vec<String>) -> Result<Vec<String>> {
     let mut output: Vec<String> = Vec::new();
     for input in inputs {
@@ -457,6 +657,59 @@ fn sanitize_output_subdir(value: &str) -> String {
     }
 }
 
+/// Lowercases, strips a leading dot and dedupes; each extension must be ASCII alphanumeric.
+pub fn normalize_file_extensions_filter(values: Option<Vec<String>>) -> Result<Vec<String>> {
+    let mut out: Vec<String> = Vec::new();
+    for raw in values.unwrap_or_default() {
+        let ext = raw.trim().trim_start_matches('.').to_ascii_lowercase();
+        if ext.is_empty() {
+            continue;
+        }
+        if !ext.chars().all(|ch| ch.is_ascii_alphanumeric()) {
+            return Err(EngineError::InstallFailed(format!(
+                "invalid file extension filter: {raw:?} (letters and digits only)"
+            )));
+        }
+        if !out.contains(&ext) {
+            out.push(ext);
+        }
+    }
+    Ok(out)
+}
+
+/// Keeps only the candidate URLs whose path ends with an allowed extension; returns the
+/// candidate unchanged as `Err` when none match.
+fn filter_candidate_by_extension(
+    mut candidate: ImageCandidate,
+    extensions: &[String],
+) -> std::result::Result<ImageCandidate, ImageCandidate> {
+    if extensions.is_empty() {
+        return Ok(candidate);
+    }
+    let kept: Vec<String> = candidate
+        .urls
+        .iter()
+        .filter(|url| url_has_extension(url, extensions))
+        .cloned()
+        .collect();
+    if kept.is_empty() {
+        return Err(candidate);
+    }
+    candidate.urls = kept;
+    Ok(candidate)
+}
+
+fn url_has_extension(url: &str, extensions: &[String]) -> bool {
+    let Ok(parsed) = Url::parse(url) else {
+        return false;
+    };
+    let path = parsed.path().to_ascii_lowercase();
+    let Some((_, ext)) = path.rsplit_once('.') else {
+        return false;
+    };
+    !ext.contains('/') && extensions.iter().any(|allowed| allowed == ext)
+}
+
 fn normalize_keywords(values: Vec<String>) -> Vec<String> {
     let mut out: Vec<String> = Vec::new();
     for raw in values {
@@ -1362,12 +1615,21 @@ fn download_candidate_image(
     candidate: &ImageCandidate,
     output_dir: &Path,
     seen_hashes: &mut HashSet<String>,
-    skip_url_keywords: &[String],
-    auth_cookie: Option<&str>,
+    seen_dhashes: &mut HashSet<u64>,
+    limits: &DownloadLimits<'_>,
+    rejections: &mut Vec<ImageRejection>,
 ) -> (CandidateStatus, Option<String>, Option<u64>, Option<String>) {
     if candidate.skip_profile {
         return (CandidateStatus::SkippedProfile, None, None, None);
     }
+    let DownloadLimits {
+        max_hamming_distance,
+        max_image_bytes,
+        min_image_pixels,
+        max_image_pixels,
+        skip_url_keywords,
+        auth_cookie,
+    } = *limits;
 
     struct DownloadedVariant {
         url: String,
@@ -1449,6 +1711,7 @@ fn download_candidate_image(
         if response
             .body_mut()
             .as_reader()
+            .take(max_image_bytes.saturating_add(1))
             .read_to_end(&mut data)
             .is_err()
         {
@@ -1458,6 +1721,23 @@ fn download_candidate_image(
         if data.is_empty() {
             continue;
         }
+        let rejection_reason = if data.len() as u64 > max_image_bytes {
+            Some("too_large")
+        } else if data.len() < MIN_IMAGE_BYTES {
+            Some("too_small")
+        } else if sniff_image_format(&data).is_none() {
+            Some("wrong_mime")
+        } else {
+            pixel_bounds_rejection(&data, min_image_pixels, max_image_pixels)
+        };
+        if let Some(reason) = rejection_reason {
+            rejections.push(ImageRejection {
+                url,
+                reason,
+                bytes: data.len() as u64,
+            });
+            continue;
+        }
         if data.len() < 512 && keyword_match(&url, THUMB_HINTS) {
             continue;
         }
@@ -1503,6 +1783,24 @@ fn download_candidate_image(
     }
 
     if let Some(chosen) = best {
+        // The same picture is often re-encoded or resized across pages, so byte digests alone
+        // miss it; compare perceptual hashes as well.
+        let dhash = if is_raster_image_content_type(&chosen.content_type) {
+            compute_dhash(&chosen.data)
+        } else {
+            None
+        };
+        if let Some(hash) = dhash {
+            if is_near_duplicate_dhash(hash, seen_dhashes, max_hamming_distance) {
+                return (
+                    CandidateStatus::DuplicateByHash,
+                    None,
+                    Some(chosen.data.len() as u64),
+                    Some(chosen.digest),
+                );
+            }
+        }
+
         let ext = guess_extension(&chosen.url, &chosen.content_type);
         let stem_raw = Url::parse(&chosen.url)
             .ok()
@@ -1532,6 +1830,9 @@ fn download_candidate_image(
         }
 
         seen_hashes.insert(chosen.digest.clone());
+        if let Some(hash) = dhash {
+            seen_dhashes.insert(hash);
+        }
         return (
             CandidateStatus::Downloaded,
             Some(out_path.to_string_lossy().to_string()),
@@ -1544,6 +1845,16 @@ fn download_candidate_image(
         return (CandidateStatus::Duplicate, None, Some(bytes), Some(digest));
     }
 
+    if let Some(rejection) = rejections.last() {
+        let status = match rejection.reason {
+            "wrong_mime" => CandidateStatus::RejectedWrongMime,
+            "too_small_px" => CandidateStatus::RejectedTooSmallPx,
+            "too_large_px" => CandidateStatus::RejectedTooLargePx,
+            _ => CandidateStatus::RejectedBadSize,
+        };
+        return (status, None, Some(rejection.bytes), None);
+    }
+
     if saw_custom_skip {
         (CandidateStatus::SkippedCustomKeyword, None, None, None)
     } else {
@@ -1551,6 +1862,99 @@ fn download_candidate_image(
     }
 }
 
+/// Width * height read from the image header, or `None` when the format cannot be probed
+/// (e.g. SVG) or the decoder would exceed [`DIMENSION_PROBE_MAX_ALLOC_BYTES`].
+fn image_pixel_count(data: &[u8]) -> Option<u64> {
+    let mut limits = image::Limits::default();
+    limits.max_alloc = Some(DIMENSION_PROBE_MAX_ALLOC_BYTES);
+    let mut reader = image::ImageReader::new(std::io::Cursor::new(data))
+        .with_guessed_format()
+        .ok()?;
+    reader.limits(limits);
+    let (width, height) = reader.into_dimensions().ok()?;
+    Some(u64::from(width) * u64::from(height))
+}
+
+fn pixel_bounds_rejection(
+    data: &[u8],
+    min_image_pixels: Option<u64>,
+    max_image_pixels: Option<u64>,
+) -> Option<&'static str> {
+    if min_image_pixels.is_none() && max_image_pixels.is_none() {
+        return None;
+    }
+    let pixels = image_pixel_count(data)?;
+    if min_image_pixels.is_some_and(|min| pixels < min) {
+        Some("too_small_px")
+    } else if max_image_pixels.is_some_and(|max| pixels > max) {
+        Some("too_large_px")
+    } else {
+        None
+    }
+}
+
+/// Drops zero bounds (no limit) and rejects a minimum above the maximum.
+pub fn normalize_image_pixel_bounds(
+    min_image_pixels: Option<u64>,
+    max_image_pixels: Option<u64>,
+) -> Result<(Option<u64>, Option<u64>)> {
+    let min = min_image_pixels.filter(|v| *v > 0);
+    let max = max_image_pixels.filter(|v| *v > 0);
+    if let (Some(min), Some(max)) = (min, max) {
+        if min > max {
+            return Err(EngineError::InstallFailed(format!(
+                "min_image_pixels ({min}) must not exceed max_image_pixels ({max})"
+            )));
+        }
+    }
+    Ok((min, max))
+}
+
+/// Identifies an image by its magic number, ignoring whatever the server claimed.
+pub(crate) fn sniff_image_format(data: &[u8]) -> Option<&'static str> {
+    let head = &data[..data.len().min(12)];
+    if head.starts_with(b"\xFF\xD8\xFF") {
+        Some("jpeg")
+    } else if head.starts_with(b"\x89PNG\r\n") {
+        Some("png")
+    } else if head.starts_with(b"GIF87a") || head.starts_with(b"GIF89a") {
+        Some("gif")
+    } else if head.len() >= 12 && head.starts_with(b"RIFF") && &head[8..12] == b"WEBP" {
+        Some("webp")
+    } else if head.starts_with(b"BM") {
+        Some("bmp")
+    } else {
+        None
+    }
+}
+
+fn is_raster_image_content_type(content_type: &str) -> bool {
+    let value = content_type.trim().to_ascii_lowercase();
+    value.starts_with("image/") && !value.starts_with("image/svg")
+}
+
+/// 64-bit difference hash: 9x8 grayscale thumbnail, one bit per horizontal neighbour comparison.
+fn compute_dhash(data: &[u8]) -> Option<u64> {
+    let img = image::load_from_memory(data).ok()?;
+    let small = img
+        .resize_exact(9, 8, image::imageops::FilterType::Triangle)
+        .to_luma8();
+    let mut hash = 0_u64;
+    for y in 0..8 {
+        for x in 0..8 {
+            let left = small.get_pixel(x, y)[0];
+            let right = small.get_pixel(x + 1, y)[0];
+            hash = (hash << 1) | u64::from(left > right);
+        }
+    }
+    Some(hash)
+}
+
+fn is_near_duplicate_dhash(hash: u64, seen: &HashSet<u64>, max_distance: u8) -> bool {
+    seen.iter()
+        .any(|other| (hash ^ other).count_ones() <= u32::from(max_distance))
+}
+
 fn prefer_downloaded_variant(
     new_url: &str,
     new_content_type: &str,
@@ -1735,8 +2139,14 @@ fn status_as_str(value: CandidateStatus) -> &'static str {
     match value {
         CandidateStatus::Downloaded => "downloaded",
         CandidateStatus::Duplicate => "duplicate",
+        CandidateStatus::DuplicateByHash => "duplicate_dhash",
         CandidateStatus::SkippedProfile => "skipped_profile",
         CandidateStatus::SkippedCustomKeyword => "skipped_custom_keyword",
+        CandidateStatus::FilteredByExtension => "filtered_by_extension",
+        CandidateStatus::RejectedWrongMime => "rejected_wrong_mime",
+        CandidateStatus::RejectedBadSize => "rejected_bad_size",
+        CandidateStatus::RejectedTooSmallPx => "rejected_too_small_px",
+        CandidateStatus::RejectedTooLargePx => "rejected_too_large_px",
         CandidateStatus::Failed => "failed_all_variants",
     }
 }
@@ -1786,6 +2196,99 @@ fn redact_url_for_log(value: &str) -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn dhash_matches_reencoded_image_and_ignores_different_one() {
+        let gradient = image::RgbImage::from_fn(64, 48, |x, y| {
+            image::Rgb([(x * 4) as u8, (y * 5) as u8, ((x + y) * 2) as u8])
+        });
+        let encode = |img: &image::RgbImage, format: image::ImageFormat| {
+            let mut out = std::io::Cursor::new(Vec::new());
+            img.write_to(&mut out, format).expect("encode");
+            out.into_inner()
+        };
+        let png = compute_dhash(&encode(&gradient, image::ImageFormat::Png)).expect("png dhash");
+        let resized =
+            image::imageops::resize(&gradient, 32, 24, image::imageops::FilterType::Triangle);
+        let small = compute_dhash(&encode(&resized, image::ImageFormat::Png)).expect("dhash");
+        let flipped = image::imageops::flip_horizontal(&gradient);
+        let other = compute_dhash(&encode(&flipped, image::ImageFormat::Png)).expect("dhash");
+
+        let seen: HashSet<u64> = [png].into_iter().collect();
+        assert!(is_near_duplicate_dhash(small, &seen, 2));
+        assert!(!is_near_duplicate_dhash(other, &seen, 2));
+        assert!(compute_dhash(b"<html></html>").is_none());
+        assert!(!is_raster_image_content_type("image/svg+xml"));
+        assert_eq!(normalize_max_hamming_distance(Some(50)), 10);
+    }
+
+    #[test]
+    fn pixel_bounds_reject_tiny_and_oversized_images() {
+        let png = |width: u32, height: u32| {
+            let mut buf = std::io::Cursor::new(Vec::new());
+            image::RgbImage::new(width, height)
+                .write_to(&mut buf, image::ImageFormat::Png)
+                .expect("encode");
+            buf.into_inner()
+        };
+        let pixel = png(1, 1);
+        let photo = png(40, 30);
+
+        assert_eq!(image_pixel_count(&photo), Some(1200));
+        assert_eq!(pixel_bounds_rejection(&pixel, None, None), None);
+        assert_eq!(
+            pixel_bounds_rejection(&pixel, Some(100), None),
+            Some("too_small_px")
+        );
+        assert_eq!(
+            pixel_bounds_rejection(&photo, Some(100), Some(1000)),
+            Some("too_large_px")
+        );
+        assert_eq!(pixel_bounds_rejection(&photo, Some(100), Some(1200)), None);
+        assert_eq!(
+            pixel_bounds_rejection(b"<svg></svg>", Some(100), None),
+            None
+        );
+
+        assert_eq!(
+            normalize_image_pixel_bounds(Some(0), Some(500)).expect("bounds"),
+            (None, Some(500))
+        );
+        assert!(normalize_image_pixel_bounds(Some(600), Some(500)).is_err());
+    }
+
+    #[test]
+    fn file_extensions_filter_normalizes_and_matches_url_paths() {
+        let exts = normalize_file_extensions_filter(Some(vec![
+            ".SVG".to_string(),
+            "webp".to_string(),
+            "svg".to_string(),
+            " ".to_string(),
+        ]))
+        .expect("filter");
+        assert_eq!(exts, vec!["svg".to_string(), "webp".to_string()]);
+        assert!(normalize_file_extensions_filter(Some(vec!["jp*g".to_string()])).is_err());
+
+        let candidate = ImageCandidate {
+            page_url: "https://example.com/".to_string(),
+            urls: vec![
+                "https://example.com/a.jpg".to_string(),
+                "https://example.com/a.WEBP?w=2".to_string(),
+            ],
+            skip_profile: false,
+        };
+        let kept = filter_candidate_by_extension(candidate.clone(), &exts).expect("kept");
+        assert_eq!(
+            kept.urls,
+            vec!["https://example.com/a.WEBP?w=2".to_string()]
+        );
+        let jpg_only = ImageCandidate {
+            urls: vec!["https://example.com/b.jpg".to_string()],
+            ..candidate.clone()
+        };
+        assert!(filter_candidate_by_extension(jpg_only, &exts).is_err());
+        assert!(filter_candidate_by_extension(candidate, &[]).is_ok());
+    }
+
     #[test]
     fn normalize_http_url_allows_http_https_only() {
         assert!(normalize_http_url("https://example.com").is_ok());
@@ -1797,17 +2300,19 @@ mod tests {
     fn build_request_clamps_limits_and_sanitizes_values() {
         let req = build_image_batch_request(
             vec!["https://example.com/blog".to_string()],
-            Some(99_999),
-            Some(99_999),
-            None,
-            None,
-            vec![
-                "avatar".to_string(),
-                "avatar".to_string(),
-                " profile ".to_string(),
-            ],
-            Some("Dad Images/2026".to_string()),
-            Some(" session=abc ".to_string()),
+            ImageBatchRequestOptions {
+                max_pages: Some(99_999),
+                delay_ms: Some(99_999),
+                skip_url_keywords: vec![
+                    "avatar".to_string(),
+                    "avatar".to_string(),
+                    " profile ".to_string(),
+                ],
+                output_subdir: Some("Dad Images/2026".to_string()),
+                auth_cookie: Some(" session=abc ".to_string()),
+                max_image_bytes: Some(0),
+                ..Default::default()
+            },
         )
         .expect("request");
         assert_eq!(req.max_pages, MAX_MAX_PAGES);
@@ -1816,6 +2321,19 @@ mod tests {
         assert_eq!(req.output_subdir, "dad_images_2026");
         assert_eq!(req.auth_cookie.as_deref(), Some("session=abc"));
         assert!(!req.follow_content_links);
+        assert_eq!(req.max_image_bytes, DEFAULT_MAX_IMAGE_BYTES);
+    }
+
+    #[test]
+    fn sniff_image_format_matches_magic_numbers_only() {
+        assert_eq!(sniff_image_format(b"\xFF\xD8\xFF\xE0rest"), Some("jpeg"));
+        assert_eq!(sniff_image_format(b"\x89PNG\r\n\x1a\n"), Some("png"));
+        assert_eq!(sniff_image_format(b"GIF89a...."), Some("gif"));
+        assert_eq!(sniff_image_format(b"RIFF\0\0\0\0WEBPVP8 "), Some("webp"));
+        assert_eq!(sniff_image_format(b"BM\0\0"), Some("bmp"));
+        assert_eq!(sniff_image_format(b"RIFF\0\0\0\0WAVEfmt "), None);
+        assert_eq!(sniff_image_format(b"<!DOCTYPE html>"), None);
+        assert_eq!(sniff_image_format(b""), None);
     }
 
     #[test]
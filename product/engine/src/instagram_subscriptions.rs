@@ -605,9 +605,9 @@ mod tests {
         assert_eq!(stored, "sessionid=abc123");
 
         let queued = queue_instagram_subscription(&paths, &sub.id).expect("queue");
-        let job_secret =
-            jobs::read_auth_cookie_secret_path(&paths.job_cookie_secret_path(&queued[0].id))
-                .expect("job auth secret");
+        let job_secret = jobs::read_job_cookie_secret(&paths, &queued[0].id)
+            .expect("read job auth secret")
+            .expect("job auth secret");
         assert_eq!(job_secret, "sessionid=abc123");
     }
 }
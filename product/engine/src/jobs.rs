@@ -1,18 +1,21 @@
 use crate::paths::AppPaths;
 use crate::{
-    asr, cmd, config, db, ffmpeg, image_batch, library, persistence, speakers, subscriptions,
-    subtitle_tracks, subtitles, tools, translate, voice_backend_adapters, voice_cast_packs,
-    voice_plans, voice_reference_candidates, voice_templates, EngineError, Result,
+    asr, cmd, config, crypto, db, diagnostics, ffmpeg, image_batch, library, models, persistence,
+    speakers, subscriptions, subtitle_tracks, subtitles, tools, translate, voice_backend_adapters,
+    voice_cast_packs, voice_plans, voice_reference_candidates, voice_templates, EngineError,
+    Result,
 };
 use regex::Regex;
 use rusqlite::{params, OptionalExtension};
 use scraper::{Html, Selector};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cell::Cell;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fs::OpenOptions;
-use std::io::{Read, Write};
+use std::io::{BufRead, Read, Write};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicUsize, Ordering};
 use std::sync::{Arc, Mutex, OnceLock};
 use std::thread;
 use std::time::{Duration, SystemTime, UNIX_EPOCH};
@@ -25,9 +28,21 @@ const JOB_LOG_ROTATE_BYTES: u64 = 50 * 1024 * 1024;
 const JOB_LOG_MAX_BACKUPS: usize = 3;
 const JOB_LOG_MAX_AGE_DAYS: u64 = 30;
 const JOB_LOG_TOTAL_CAP_BYTES: u64 = 1 * 1024 * 1024 * 1024;
-const MAX_DOWNLOAD_BATCH_URLS: usize = 1500;
+const JOB_LOG_SEARCH_MAX_RESULTS: usize = 1000;
+const PYTHON_STDERR_EXCERPT_LINES: usize = 20;
+const PYTHON_STDERR_EXCERPT_MAX_CHARS: usize = 2000;
+pub(crate) const MAX_DOWNLOAD_BATCH_URLS: usize = 1500;
 const DOWNLOAD_PROVIDER_DIRECT_HTTP: &str = "direct_http_v1";
 const DOWNLOAD_PROVIDER_YOUTUBE_YT_DLP: &str = "youtube_yt_dlp_v1";
+/// Non-YouTube sites whose page URLs are routed through yt-dlp. Subdomains match too.
+const SUPPORTED_YTDLP_DOMAINS: &[&str] = &[
+    "vimeo.com",
+    "soundcloud.com",
+    "dailymotion.com",
+    "tiktok.com",
+    "twitter.com",
+    "x.com",
+];
 const DOWNLOAD_RIGHTS_NOTE_UNSPECIFIED: &str = "not_collected";
 const DEFAULT_VIDEO_OUTPUT_SUBDIR: &str = "video";
 const DEFAULT_INSTAGRAM_OUTPUT_SUBDIR: &str = "instagram";
@@ -38,40 +53,577 @@ const EMBED_CRAWL_MAX_CANDIDATES: usize = 40;
 const EMBED_FETCH_MAX_BODY_BYTES: u64 = 2 * 1024 * 1024;
 const DIRECT_DOWNLOAD_SNIFF_BYTES: usize = 8192;
 const INSTAGRAM_API_APP_ID: &str = "936619743392459";
+const INSTAGRAM_THUMBNAIL_MAX_BYTES: u64 = 10 * 1024 * 1024;
+const NETSCAPE_COOKIE_FILE_HEADER: &str = "# Netscape HTTP Cookie File";
+const COOKIE_SUSPICIOUSLY_SHORT_CHARS: usize = 20;
 const DEFAULT_HTTP_USER_AGENT: &str = "Mozilla/5.0 (Windows NT 10.0; Win64; x64) AppleWebKit/537.36 (KHTML, like Gecko) Chrome/130.0.0.0 Safari/537.36";
 const META_KEY_JOBS_QUEUE_PAUSED: &str = "jobs_queue_paused";
 const META_KEY_JOBS_MAX_CONCURRENCY: &str = "jobs_max_concurrency";
+const META_KEY_JOB_LOG_RETENTION_OVERRIDES: &str = "job_log_retention_overrides_v1";
+const META_KEY_JOB_LOG_RETENTION_POLICY: &str = "job_log_retention_policy_v1";
+const BATCH_COMPLETED_META_TTL_MS: u64 = 7 * 24 * 60 * 60 * 1000;
+const JOB_LOG_OVERRIDE_MAX_AGE_DAYS_LIMIT: u64 = 3650;
+const JOB_LOG_MIN_ROTATE_BYTES: u64 = 1024 * 1024;
+const JOB_LOG_MAX_BACKUPS_LIMIT: usize = 10;
 const YT_DLP_EXPAND_TIMEOUT_SECS: u64 = 900;
 const YT_DLP_DOWNLOAD_TIMEOUT_SECS: u64 = 7200;
 const EXTERNAL_CMD_POLL_INTERVAL_MS: u64 = 200;
 const YT_DLP_BOOTSTRAP_TIMEOUT_SECS: u64 = 180;
 const EXPERIMENTAL_VOICE_BACKEND_TIMEOUT_SECS: u64 = 7200;
+const PYTHON_TOOL_DEFAULT_TIMEOUT_SECS: u64 = 3600;
 const DIARIZATION_SPEAKER_COUNT_MAX: u32 = 16;
+const MIX_SILENCE_NOISE_DB: f32 = -35.0;
+const MIX_SILENCE_MIN_DURATION_MS: u64 = 300;
+const MIX_SPEECH_NORMALIZE_TARGET_LUFS: f32 = -16.0;
+const MIX_SPEECH_NORMALIZE_TRUE_PEAK_DB: f32 = -1.5;
+const MIX_SPEECH_NORMALIZE_LRA: f32 = 5.0;
+const MIX_SPEECH_NORMALIZE_MAX_GAIN_DB: f32 = 20.0;
+const MAX_REQUEUE_ATTEMPTS: i64 = 3;
+const WATCH_DIRECTORY_MIN_INTERVAL_SECS: u64 = 5;
+const WATCH_DIRECTORY_MAX_INTERVAL_SECS: u64 = 3600;
+// Spleeter and Demucs are trained on 44.1 kHz stereo music; downmixing to mono would discard
+// the spatial cues they use to separate vocals from accompaniment.
+const SEPARATION_INPUT_SAMPLE_RATE_HZ: u32 = 44100;
+const MIX_SAMPLE_RATE_HZ: u32 = 44100;
+const AUTO_PROFILE_CLIP_MS: i64 = 10_000;
+const SEPARATION_INPUT_CHANNELS: u8 = 2;
+// Demucs holds the whole input in memory; long inputs are separated in overlapping chunks.
+const DEMUCS_AUTO_CHUNK_MIN_DURATION_SECS: i64 = 1200;
+const DEMUCS_DEFAULT_CHUNK_SECS: u64 = 600;
+const DEMUCS_MIN_CHUNK_SECS: u64 = 30;
+const DEMUCS_CHUNK_OVERLAP_MS: i64 = 5_000;
+const CLEAN_VOCALS_SOURCE_DEMUCS: &str = "demucs";
+const CLEAN_VOCALS_SOURCE_SPLEETER: &str = "spleeter";
+const CLEAN_VOCALS_FFMPEG_FILTER: &str =
+    "highpass=f=80,lowpass=f=12000,afftdn=nf=-25,loudnorm=I=-16:TP=-1.5:LRA=11";
+const CLEAN_VOCALS_PEAK_DBFS: f32 = -1.0;
 #[cfg(windows)]
 const YT_DLP_WINDOWS_DOWNLOAD_URL: &str =
     "https://github.com/yt-dlp/yt-dlp/releases/latest/download/yt-dlp.exe";
 
 static YT_DLP_BOOTSTRAP_LOCK: OnceLock<Mutex<()>> = OnceLock::new();
 
+type Phase2InstallProgressHook = Box<dyn Fn(&Phase2InstallProgress) + Send + Sync>;
+static PHASE2_INSTALL_PROGRESS_HOOK: OnceLock<Phase2InstallProgressHook> = OnceLock::new();
+
+/// Step transition of a running `install_phase2_packs_v1` job.
+#[derive(Debug, Clone, Serialize)]
+pub struct Phase2InstallProgress {
+    pub step_index: usize,
+    pub total_steps: usize,
+    pub current_step_name: String,
+    pub current_step_status: String,
+}
+
+/// Registers the observer for phase-2 install step transitions; later registrations are ignored.
+pub fn set_phase2_install_progress_hook(
+    hook: impl Fn(&Phase2InstallProgress) + Send + Sync + 'static,
+) {
+    let _ = PHASE2_INSTALL_PROGRESS_HOOK.set(Box::new(hook));
+}
+
+fn report_phase2_install_progress(
+    step_index: usize,
+    total_steps: usize,
+    step_name: &str,
+    status: &str,
+) {
+    if let Some(hook) = PHASE2_INSTALL_PROGRESS_HOOK.get() {
+        hook(&Phase2InstallProgress {
+            step_index,
+            total_steps,
+            current_step_name: step_name.to_string(),
+            current_step_status: status.to_string(),
+        });
+    }
+}
+
+type RuntimeStateHook = Box<dyn Fn(&JobRuntimeStateEvent) + Send + Sync>;
+static RUNTIME_STATE_HOOK: OnceLock<RuntimeStateHook> = OnceLock::new();
+
+/// Emitted by the runner whenever a job starts or finishes executing.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobRuntimeStateEvent {
+    pub running_count: usize,
+    pub timestamp_ms: i64,
+}
+
+/// Registers the observer for runner running-count changes; later registrations are ignored.
+pub fn set_runtime_state_hook(hook: impl Fn(&JobRuntimeStateEvent) + Send + Sync + 'static) {
+    let _ = RUNTIME_STATE_HOOK.set(Box::new(hook));
+}
+
+fn report_runtime_state(running_count: usize) {
+    if let Some(hook) = RUNTIME_STATE_HOOK.get() {
+        hook(&JobRuntimeStateEvent {
+            running_count,
+            timestamp_ms: now_ms(),
+        });
+    }
+}
+
+type BatchCompletedHook = Box<dyn Fn(&BatchCompletedEvent) + Send + Sync>;
+static BATCH_COMPLETED_HOOK: OnceLock<BatchCompletedHook> = OnceLock::new();
+
+/// Emitted once the last queued or running job of a batch reaches a terminal state.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct BatchCompletedEvent {
+    pub batch_id: String,
+    pub total_jobs: usize,
+    pub succeeded: usize,
+    pub failed: usize,
+    pub canceled: usize,
+}
+
+/// Registers the observer for batch completions; later registrations are ignored.
+pub fn set_batch_completed_hook(hook: impl Fn(&BatchCompletedEvent) + Send + Sync + 'static) {
+    let _ = BATCH_COMPLETED_HOOK.set(Box::new(hook));
+}
+
+/// True when no job of the batch is still queued or running.
+pub fn check_batch_complete(paths: &AppPaths, batch_id: &str) -> Result<bool> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let active: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM job WHERE batch_id=?1 AND status IN (?2, ?3)",
+        params![
+            batch_id,
+            JobStatus::Queued.as_str(),
+            JobStatus::Running.as_str()
+        ],
+        |row| row.get(0),
+    )?;
+    Ok(active == 0)
+}
+
+fn batch_completed_event(paths: &AppPaths, batch_id: &str) -> Result<BatchCompletedEvent> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut stmt =
+        conn.prepare("SELECT status, COUNT(*) FROM job WHERE batch_id=?1 GROUP BY status")?;
+    let rows = stmt.query_map(params![batch_id], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, i64>(1)?))
+    })?;
+    let mut event = BatchCompletedEvent {
+        batch_id: batch_id.to_string(),
+        total_jobs: 0,
+        succeeded: 0,
+        failed: 0,
+        canceled: 0,
+    };
+    for row in rows {
+        let (status, count) = row?;
+        let count = count.max(0) as usize;
+        event.total_jobs += count;
+        match JobStatus::from_str(&status) {
+            Some(JobStatus::Succeeded) => event.succeeded += count,
+            Some(JobStatus::Failed) => event.failed += count,
+            Some(JobStatus::Canceled) => event.canceled += count,
+            _ => {}
+        }
+    }
+    Ok(event)
+}
+
+type JobFailedHook = Box<dyn Fn(&JobFailedEvent) + Send + Sync>;
+static JOB_FAILED_HOOK: OnceLock<JobFailedHook> = OnceLock::new();
+
+/// Emitted when a running job is marked failed.
+#[derive(Debug, Clone, Serialize)]
+pub struct JobFailedEvent {
+    pub job_id: String,
+    pub error: String,
+    /// Progress (0..1) the job had reached when it failed, if it reported any.
+    pub final_progress: Option<f32>,
+}
+
+/// Registers the observer for job failures; later registrations are ignored.
+pub fn set_job_failed_hook(hook: impl Fn(&JobFailedEvent) + Send + Sync + 'static) {
+    let _ = JOB_FAILED_HOOK.set(Box::new(hook));
+}
+
+type JobStatusChangedHook = Box<dyn Fn(&JobStatusChangedEvent) + Send + Sync>;
+static JOB_STATUS_CHANGED_HOOK: OnceLock<JobStatusChangedHook> = OnceLock::new();
+
+/// Emitted when a running job succeeds or fails.
 #[derive(Debug, Clone, Serialize)]
+pub struct JobStatusChangedEvent {
+    pub job_id: String,
+    pub status: JobStatus,
+    pub worker_id: Option<usize>,
+}
+
+/// Registers the observer for job completions; later registrations are ignored.
+pub fn set_job_status_changed_hook(hook: impl Fn(&JobStatusChangedEvent) + Send + Sync + 'static) {
+    let _ = JOB_STATUS_CHANGED_HOOK.set(Box::new(hook));
+}
+
+fn report_job_status_changed(job_id: &str, status: JobStatus) {
+    if let Some(hook) = JOB_STATUS_CHANGED_HOOK.get() {
+        hook(&JobStatusChangedEvent {
+            job_id: job_id.to_string(),
+            status,
+            worker_id: current_worker_id(),
+        });
+    }
+}
+
+/// Source of runner worker ids; each spawned worker thread takes the next value.
+static WORKER_ID: AtomicUsize = AtomicUsize::new(1);
+
+/// `CURRENT_PROGRESS` value before the job on this thread has reported any progress.
+const NO_PROGRESS_BITS: u32 = u32::MAX;
+
+thread_local! {
+    // Each job executes on its own worker thread, so this tracks that job's progress.
+    static CURRENT_PROGRESS: AtomicU32 = const { AtomicU32::new(NO_PROGRESS_BITS) };
+    static CURRENT_WORKER_ID: Cell<Option<usize>> = const { Cell::new(None) };
+}
+
+/// Id of the runner worker executing on this thread; `None` outside worker threads.
+fn current_worker_id() -> Option<usize> {
+    CURRENT_WORKER_ID.with(Cell::get)
+}
+
+fn current_progress() -> Option<f32> {
+    let bits = CURRENT_PROGRESS.with(|progress| progress.load(Ordering::Relaxed));
+    (bits != NO_PROGRESS_BITS).then(|| f32::from_bits(bits))
+}
+
+/// Reports the batch of `job_id` as completed when that job was its last active one.
+fn report_batch_completion(paths: &AppPaths, job_id: &str) -> Result<()> {
+    let Some(batch_id) = job_batch_id(paths, job_id)? else {
+        return Ok(());
+    };
+    report_batch_completion_for(paths, &batch_id)
+}
+
+fn report_batch_completion_for(paths: &AppPaths, batch_id: &str) -> Result<()> {
+    let Some(hook) = BATCH_COMPLETED_HOOK.get() else {
+        return Ok(());
+    };
+    if claim_batch_completion(paths, batch_id)? {
+        hook(&batch_completed_event(paths, batch_id)?);
+    }
+    Ok(())
+}
+
+/// True for exactly one caller once nothing in `batch_id` is active anymore. The `meta` row is
+/// claimed with `INSERT OR IGNORE`, so jobs finishing at the same time emit the event only once.
+/// It expires after [`BATCH_COMPLETED_META_TTL_MS`] and is pruned on startup.
+fn claim_batch_completion(paths: &AppPaths, batch_id: &str) -> Result<bool> {
+    if !check_batch_complete(paths, batch_id)? {
+        return Ok(false);
+    }
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let now = now_ms();
+    let claimed = conn.execute(
+        "INSERT OR IGNORE INTO meta(key, value, expires_at_ms) VALUES(?1, ?2, ?3)",
+        params![
+            batch_completed_meta_key(batch_id),
+            now.to_string(),
+            now.saturating_add(BATCH_COMPLETED_META_TTL_MS as i64)
+        ],
+    )?;
+    Ok(claimed == 1)
+}
+
+fn batch_completed_meta_key(batch_id: &str) -> String {
+    format!("batch_completed:{batch_id}")
+}
+
+/// Replaces the global age/backup limits for logs of one job type. `None` keeps the default.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JobTypeRetentionOverride {
+    pub job_type: String,
+    #[serde(default)]
+    pub max_age_days: Option<u64>,
+    #[serde(default)]
+    pub max_backups: Option<usize>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
 pub struct JobLogRetentionPolicy {
     pub rotate_bytes: u64,
     pub max_backups: usize,
     pub max_age_days: u64,
     pub total_cap_bytes: u64,
+    /// Managed separately via `set_job_log_retention_overrides`; ignored when storing the policy.
+    #[serde(default)]
+    pub overrides: Vec<JobTypeRetentionOverride>,
+}
+
+impl Default for JobLogRetentionPolicy {
+    fn default() -> Self {
+        Self {
+            rotate_bytes: JOB_LOG_ROTATE_BYTES,
+            max_backups: JOB_LOG_MAX_BACKUPS,
+            max_age_days: JOB_LOG_MAX_AGE_DAYS,
+            total_cap_bytes: JOB_LOG_TOTAL_CAP_BYTES,
+            overrides: Vec::new(),
+        }
+    }
 }
 
-pub fn job_log_retention_policy() -> JobLogRetentionPolicy {
+/// Reads the stored global limits (falling back to the compiled-in defaults) plus per-type overrides.
+pub fn load_job_log_retention_policy(paths: &AppPaths) -> JobLogRetentionPolicy {
+    let stored = db::open(paths)
+        .and_then(|conn| {
+            db::migrate(&conn)?;
+            Ok(conn
+                .query_row(
+                    "SELECT value FROM meta WHERE key=?1",
+                    [META_KEY_JOB_LOG_RETENTION_POLICY],
+                    |row| row.get::<_, String>(0),
+                )
+                .optional()?)
+        })
+        .ok()
+        .flatten()
+        .and_then(|raw| serde_json::from_str::<JobLogRetentionPolicy>(&raw).ok())
+        .filter(|policy| validate_job_log_retention_policy(policy).is_ok());
     JobLogRetentionPolicy {
-        rotate_bytes: JOB_LOG_ROTATE_BYTES,
-        max_backups: JOB_LOG_MAX_BACKUPS,
-        max_age_days: JOB_LOG_MAX_AGE_DAYS,
-        total_cap_bytes: JOB_LOG_TOTAL_CAP_BYTES,
+        overrides: get_job_log_retention_overrides(paths).unwrap_or_default(),
+        ..stored.unwrap_or_default()
+    }
+}
+
+pub fn set_job_log_retention_policy(
+    paths: &AppPaths,
+    policy: JobLogRetentionPolicy,
+) -> Result<JobLogRetentionPolicy> {
+    validate_job_log_retention_policy(&policy)?;
+    let stored = JobLogRetentionPolicy {
+        overrides: Vec::new(),
+        ..policy
+    };
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    conn.execute(
+        "INSERT INTO meta(key, value) VALUES(?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value=excluded.value",
+        params![
+            META_KEY_JOB_LOG_RETENTION_POLICY,
+            serde_json::to_string(&stored)?
+        ],
+    )?;
+    Ok(load_job_log_retention_policy(paths))
+}
+
+fn validate_job_log_retention_policy(policy: &JobLogRetentionPolicy) -> Result<()> {
+    if policy.rotate_bytes < JOB_LOG_MIN_ROTATE_BYTES {
+        return Err(EngineError::InstallFailed(format!(
+            "rotate_bytes must be at least {JOB_LOG_MIN_ROTATE_BYTES} (got {})",
+            policy.rotate_bytes
+        )));
+    }
+    if policy.max_age_days < 1 {
+        return Err(EngineError::InstallFailed(
+            "max_age_days must be at least 1".to_string(),
+        ));
+    }
+    if policy.max_backups > JOB_LOG_MAX_BACKUPS_LIMIT {
+        return Err(EngineError::InstallFailed(format!(
+            "max_backups must be at most {JOB_LOG_MAX_BACKUPS_LIMIT} (got {})",
+            policy.max_backups
+        )));
+    }
+    if policy.total_cap_bytes < policy.rotate_bytes {
+        return Err(EngineError::InstallFailed(format!(
+            "total_cap_bytes must be at least rotate_bytes ({})",
+            policy.rotate_bytes
+        )));
+    }
+    Ok(())
+}
+
+pub fn get_job_log_retention_overrides(paths: &AppPaths) -> Result<Vec<JobTypeRetentionOverride>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let raw: Option<String> = conn
+        .query_row(
+            "SELECT value FROM meta WHERE key=?1",
+            [META_KEY_JOB_LOG_RETENTION_OVERRIDES],
+            |row| row.get(0),
+        )
+        .optional()?;
+    let Some(raw) = raw else {
+        return Ok(Vec::new());
+    };
+    let parsed: Vec<JobTypeRetentionOverride> = serde_json::from_str(&raw).unwrap_or_default();
+    Ok(normalize_job_log_retention_overrides(parsed))
+}
+
+pub fn set_job_log_retention_overrides(
+    paths: &AppPaths,
+    overrides: Vec<JobTypeRetentionOverride>,
+) -> Result<Vec<JobTypeRetentionOverride>> {
+    let overrides = normalize_job_log_retention_overrides(overrides);
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    conn.execute(
+        "INSERT INTO meta(key, value) VALUES(?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET value=excluded.value",
+        params![
+            META_KEY_JOB_LOG_RETENTION_OVERRIDES,
+            serde_json::to_string(&overrides)?
+        ],
+    )?;
+    Ok(overrides)
+}
+
+/// Trims job types, drops empty or no-op entries, clamps limits and keeps the last entry per type.
+fn normalize_job_log_retention_overrides(
+    overrides: Vec<JobTypeRetentionOverride>,
+) -> Vec<JobTypeRetentionOverride> {
+    let mut out: Vec<JobTypeRetentionOverride> = Vec::new();
+    for entry in overrides {
+        let job_type = entry.job_type.trim().to_string();
+        if job_type.is_empty() || (entry.max_age_days.is_none() && entry.max_backups.is_none()) {
+            continue;
+        }
+        let normalized = JobTypeRetentionOverride {
+            job_type,
+            max_age_days: entry
+                .max_age_days
+                .map(|v| v.clamp(1, JOB_LOG_OVERRIDE_MAX_AGE_DAYS_LIMIT)),
+            max_backups: entry.max_backups.map(|v| v.min(JOB_LOG_MAX_BACKUPS_LIMIT)),
+        };
+        out.retain(|existing| existing.job_type != normalized.job_type);
+        out.push(normalized);
+    }
+    out
+}
+
+/// Artifact dir names checked per `SELECT` when looking for orphaned job artifacts.
+const ORPHAN_ARTIFACT_SCAN_BATCH: usize = 1000;
+
+/// What one [`prune_job_logs_now`] pass removed.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct JobLogPruneSummary {
+    pub pruned_log_files: usize,
+    /// Artifact dirs under `derived/jobs` whose job id or content key no longer has a job row.
+    pub pruned_orphan_artifact_dirs: usize,
+}
+
+pub fn prune_job_logs_now(paths: &AppPaths) -> Result<JobLogPruneSummary> {
+    let summary = prune_job_logs(paths)?;
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    db::prune_expired_meta(&conn)?;
+    Ok(summary)
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JobLogQuery {
+    #[serde(default)]
+    pub job_id: Option<String>,
+    #[serde(default)]
+    pub event: Option<String>,
+    #[serde(default)]
+    pub level: Option<String>,
+    /// Case-insensitive substring matched against the raw log line.
+    #[serde(default)]
+    pub text_contains: Option<String>,
+    #[serde(default)]
+    pub since_ms: Option<i64>,
+    /// Maximum matches to return; 0 means the cap (1000).
+    #[serde(default)]
+    pub limit: usize,
+}
+
+/// Scans JSONL job logs (including rotated backups) and returns matching entries, oldest first
+/// within each job. Only the requested job's files are opened when `job_id` is set.
+pub fn search_job_logs(paths: &AppPaths, query: &JobLogQuery) -> Result<Vec<serde_json::Value>> {
+    let limit = if query.limit == 0 {
+        JOB_LOG_SEARCH_MAX_RESULTS
+    } else {
+        query.limit.min(JOB_LOG_SEARCH_MAX_RESULTS)
+    };
+    let logs_dir = paths.job_logs_dir();
+    let files = match normalize_non_empty(query.job_id.as_deref()) {
+        Some(job_id) => {
+            if job_id.contains(['/', '\\']) || job_id.contains("..") {
+                return Err(EngineError::InstallFailed(format!(
+                    "invalid job id: {job_id}"
+                )));
+            }
+            job_log_files_oldest_first(&logs_dir.join(format!("{job_id}.jsonl")))
+        }
+        None => {
+            let mut bases = match std::fs::read_dir(&logs_dir) {
+                Ok(entries) => entries
+                    .flatten()
+                    .map(|entry| entry.path())
+                    .filter(|path| path.extension().is_some_and(|ext| ext == "jsonl"))
+                    .collect::<Vec<_>>(),
+                Err(_) => Vec::new(),
+            };
+            bases.sort();
+            bases
+                .iter()
+                .flat_map(|base| job_log_files_oldest_first(base))
+                .collect()
+        }
+    };
+
+    let event = normalize_non_empty(query.event.as_deref());
+    let level = normalize_non_empty(query.level.as_deref());
+    let needle = normalize_non_empty(query.text_contains.as_deref()).map(|v| v.to_lowercase());
+
+    let mut matches = Vec::new();
+    for file in files {
+        let Ok(handle) = std::fs::File::open(&file) else {
+            continue;
+        };
+        for line in std::io::BufReader::new(handle).lines() {
+            let Ok(line) = line else {
+                break;
+            };
+            if let Some(needle) = needle.as_deref() {
+                if !line.to_lowercase().contains(needle) {
+                    continue;
+                }
+            }
+            let Ok(entry) = serde_json::from_str::<serde_json::Value>(&line) else {
+                continue;
+            };
+            if let Some(event) = event.as_deref() {
+                if entry.get("event").and_then(|v| v.as_str()) != Some(event) {
+                    continue;
+                }
+            }
+            if let Some(level) = level.as_deref() {
+                let entry_level = entry.get("level").and_then(|v| v.as_str()).unwrap_or("");
+                if !entry_level.eq_ignore_ascii_case(level) {
+                    continue;
+                }
+            }
+            if let Some(since_ms) = query.since_ms {
+                if entry.get("ts_ms").and_then(|v| v.as_i64()).unwrap_or(0) < since_ms {
+                    continue;
+                }
+            }
+            matches.push(entry);
+            if matches.len() >= limit {
+                return Ok(matches);
+            }
+        }
     }
+    Ok(matches)
 }
 
-pub fn prune_job_logs_now(paths: &AppPaths) -> Result<()> {
-    prune_job_logs(paths)
+fn job_log_files_oldest_first(base_path: &Path) -> Vec<PathBuf> {
+    let mut files = (1..=JOB_LOG_MAX_BACKUPS_LIMIT)
+        .rev()
+        .map(|i| path_with_suffix(base_path, &format!(".{i}")))
+        .filter(|path| path.exists())
+        .collect::<Vec<_>>();
+    if base_path.exists() {
+        files.push(base_path.to_path_buf());
+    }
+    files
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
@@ -125,10 +677,17 @@ pub enum JobType {
     MuxDubPreviewV1,
     SeparateAudioSpleeter,
     SeparateAudioDemucsV1,
+    SeparateAudioDemucs4StemsV1,
     CleanVocalsV1,
+    ExtractSpeakerVoiceProfileV1,
+    SyncSubtitlesV1,
+    FullPipelineV1,
     QcReportV1,
     ExportPackV1,
+    ExportMultiPackV1,
     InstallPhase2PacksV1,
+    InstallModelV1,
+    WatchDirectory,
     DummySleep,
 }
 
@@ -150,10 +709,17 @@ impl JobType {
             JobType::MuxDubPreviewV1 => "mux_dub_preview_v1",
             JobType::SeparateAudioSpleeter => "separate_audio_spleeter",
             JobType::SeparateAudioDemucsV1 => "separate_audio_demucs_v1",
+            JobType::SeparateAudioDemucs4StemsV1 => "separate_audio_demucs_4stems_v1",
             JobType::CleanVocalsV1 => "clean_vocals_v1",
+            JobType::ExtractSpeakerVoiceProfileV1 => "extract_speaker_voice_profile_v1",
+            JobType::SyncSubtitlesV1 => "sync_subtitles_v1",
+            JobType::FullPipelineV1 => "full_pipeline_v1",
             JobType::QcReportV1 => "qc_report_v1",
             JobType::ExportPackV1 => "export_pack_v1",
+            JobType::ExportMultiPackV1 => "export_multi_pack_v1",
             JobType::InstallPhase2PacksV1 => "install_phase2_packs_v1",
+            JobType::InstallModelV1 => "install_model_v1",
+            JobType::WatchDirectory => "watch_directory",
             JobType::DummySleep => "dummy_sleep",
         }
     }
@@ -177,10 +743,17 @@ impl JobType {
             "mux_dub_preview_v1" => Some(JobType::MuxDubPreviewV1),
             "separate_audio_spleeter" => Some(JobType::SeparateAudioSpleeter),
             "separate_audio_demucs_v1" => Some(JobType::SeparateAudioDemucsV1),
+            "separate_audio_demucs_4stems_v1" => Some(JobType::SeparateAudioDemucs4StemsV1),
             "clean_vocals_v1" => Some(JobType::CleanVocalsV1),
+            "extract_speaker_voice_profile_v1" => Some(JobType::ExtractSpeakerVoiceProfileV1),
+            "sync_subtitles_v1" => Some(JobType::SyncSubtitlesV1),
+            "full_pipeline_v1" => Some(JobType::FullPipelineV1),
             "qc_report_v1" => Some(JobType::QcReportV1),
             "export_pack_v1" => Some(JobType::ExportPackV1),
+            "export_multi_pack_v1" => Some(JobType::ExportMultiPackV1),
             "install_phase2_packs_v1" => Some(JobType::InstallPhase2PacksV1),
+            "install_model_v1" => Some(JobType::InstallModelV1),
+            "watch_directory" => Some(JobType::WatchDirectory),
             "dummy_sleep" => Some(JobType::DummySleep),
             _ => None,
         }
@@ -201,6 +774,93 @@ pub struct JobRow {
     pub finished_at_ms: Option<i64>,
     pub logs_path: String,
     pub params_json: String,
+    /// Times the job was put back in the queue after being orphaned by an app crash.
+    #[serde(default)]
+    pub requeue_count: i64,
+    /// Stable key naming the job's artifacts dir, shared by retries of the same work.
+    #[serde(default)]
+    pub content_key: Option<String>,
+    /// Queued jobs are not picked up before this time (epoch ms).
+    #[serde(default)]
+    pub not_before_ms: Option<i64>,
+    /// Filled in by [`list_jobs`] for running jobs whose type has a recorded average duration.
+    #[serde(default)]
+    pub estimate_remaining_ms: Option<i64>,
+    /// Follow-up jobs the runner enqueues once this job finishes; stored as `on_complete_json`.
+    #[serde(default)]
+    pub on_complete_jobs: Vec<JobDependency>,
+    /// Runner worker that last finished the job; see [`current_worker_id`].
+    #[serde(default)]
+    pub worker_id: Option<usize>,
+    /// Job whose execution enqueued this one (subscription refresh -> downloads, import chains).
+    #[serde(default)]
+    pub parent_job_id: Option<String>,
+    /// Named stages of multi-step jobs in start order; stored as `progress_steps_json`.
+    #[serde(default)]
+    pub progress_steps: Vec<ProgressStep>,
+}
+
+pub const PROGRESS_STEP_STATUS_RUNNING: &str = "running";
+pub const PROGRESS_STEP_STATUS_DONE: &str = "done";
+pub const PROGRESS_STEP_STATUS_FAILED: &str = "failed";
+pub const PROGRESS_STEP_STATUS_CANCELED: &str = "canceled";
+
+/// One stage of a job, recorded by [`record_step_start`] and [`record_step_done`].
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProgressStep {
+    pub name: String,
+    /// `"running"` or `"done"`; steps still running when the job fails or is canceled are closed
+    /// as `"failed"` or `"canceled"`.
+    pub status: String,
+    #[serde(default)]
+    pub started_at_ms: Option<i64>,
+    #[serde(default)]
+    pub finished_at_ms: Option<i64>,
+}
+
+pub const JOB_DEPENDENCY_CONDITION_SUCCESS: &str = "success";
+pub const JOB_DEPENDENCY_CONDITION_ALWAYS: &str = "always";
+
+/// A job to enqueue when its parent finishes. `condition` is `"success"` (parent succeeded) or
+/// `"always"` (succeeded or failed); canceled parents never trigger dependencies.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct JobDependency {
+    pub job_type: String,
+    pub params_json: String,
+    #[serde(default = "default_job_dependency_condition")]
+    pub condition: String,
+    /// Dependencies of the queued job itself.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub on_complete: Vec<JobDependency>,
+}
+
+fn default_job_dependency_condition() -> String {
+    JOB_DEPENDENCY_CONDITION_SUCCESS.to_string()
+}
+
+impl JobDependency {
+    pub fn on_success(job_type: JobType, params_json: String) -> Self {
+        Self {
+            job_type: job_type.as_str().to_string(),
+            params_json,
+            condition: JOB_DEPENDENCY_CONDITION_SUCCESS.to_string(),
+            on_complete: Vec::new(),
+        }
+    }
+
+    pub fn always(job_type: JobType, params_json: String) -> Self {
+        Self {
+            job_type: job_type.as_str().to_string(),
+            params_json,
+            condition: JOB_DEPENDENCY_CONDITION_ALWAYS.to_string(),
+            on_complete: Vec::new(),
+        }
+    }
+
+    pub fn with_on_complete(mut self, on_complete: Vec<JobDependency>) -> Self {
+        self.on_complete = on_complete;
+        self
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -224,7 +884,23 @@ pub struct JobCleanupOptions {
     pub remove_managed_output_dirs: bool,
     #[serde(default)]
     pub remove_external_output_dirs: bool,
-}
+    /// Report what would be removed without deleting anything.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Only flush terminal jobs of these types. Shared cache entries are kept when set.
+    #[serde(default)]
+    pub job_types: Option<Vec<String>>,
+    /// Only flush jobs that finished more than this many days ago. Shared cache entries are kept
+    /// when set.
+    #[serde(default)]
+    pub older_than_days: Option<u64>,
+}
+
+impl JobCleanupOptions {
+    fn has_job_filter(&self) -> bool {
+        self.job_types.as_ref().is_some_and(|v| !v.is_empty()) || self.older_than_days.is_some()
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct JobCleanupOutputTarget {
@@ -251,6 +927,26 @@ pub struct JobCleanupSummary {
     pub skipped_external_output_dirs: usize,
     pub removed_cache_entries: usize,
     pub failed_paths: Vec<JobCleanupFailure>,
+    /// True when nothing was deleted and the counts describe what would be removed.
+    #[serde(default)]
+    pub dry_run: bool,
+    /// Bytes removed (or, in dry-run mode, that would be removed) with the given opt-ins.
+    #[serde(default)]
+    pub would_remove_bytes: u64,
+    /// Bytes that could be removed if every output-dir opt-in were enabled.
+    #[serde(default)]
+    pub available_to_remove_bytes: u64,
+    /// Bytes actually deleted, by category; all zero for a dry run.
+    #[serde(default)]
+    pub freed_log_bytes: u64,
+    #[serde(default)]
+    pub freed_artifact_bytes: u64,
+    #[serde(default)]
+    pub freed_output_bytes: u64,
+    #[serde(default)]
+    pub freed_cache_bytes: u64,
+    #[serde(default)]
+    pub freed_bytes: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -310,6 +1006,9 @@ struct ImportLocalParams {
 struct InstallPhase2PacksV1Params {
     #[serde(default)]
     resume_localization_run: Option<LocalizationRunRequest>,
+    /// Uninstall packages added since the pre-install snapshot when a step fails.
+    #[serde(default)]
+    rollback_on_failure: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -321,6 +1020,9 @@ struct AsrLocalParams {
     batch_on_import: bool,
     #[serde(default)]
     pipeline: Option<LocalizationPipelineOptions>,
+    /// `"transcribe"` (default) or `"translate"` (Whisper's direct-to-English mode).
+    #[serde(default)]
+    task: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -362,14 +1064,99 @@ struct TtsNeuralLocalV1Params {
     source_track_id: String,
     #[serde(default)]
     batch_on_import: bool,
+    /// Kokoro language code; `None` falls back to the TTS defaults, then "a".
+    #[serde(default)]
+    lang_code: Option<String>,
+    /// Render segments Kokoro left without audio through pyttsx3 when the neural script fails.
+    #[serde(default = "default_true")]
+    fallback_to_pyttsx3: bool,
 }
 
+/// Renders a JSON request of `{text, out_path, voice_id}` segments with pyttsx3.
+const TTS_PYTTSX3_V1_SCRIPT: &str = r#"
+import argparse
+import json
+import os
+
+import pyttsx3
+
+
+def main():
+    ap = argparse.ArgumentParser()
+    ap.add_argument("--request", required=True)
+    args = ap.parse_args()
+
+    with open(args.request, "r", encoding="utf-8") as f:
+        items = json.load(f)
+
+    engine = pyttsx3.init()
+    default_voice = None
+    try:
+        default_voice = engine.getProperty("voice")
+    except Exception:
+        default_voice = None
+    if default_voice is not None:
+        default_voice = (str(default_voice).strip() or None)
+
+    current_voice = default_voice or ""
+
+    def flush_queue():
+        try:
+            engine.runAndWait()
+        except Exception:
+            pass
+
+    for it in items:
+        text = (it.get("text") or "").strip()
+        out_path = (it.get("out_path") or "").strip()
+        voice_id = (it.get("voice_id") or "").strip()
+        if not text or not out_path:
+            continue
+
+        desired_voice = voice_id if voice_id else (default_voice or "")
+        if desired_voice != current_voice:
+            flush_queue()
+            if desired_voice:
+                try:
+                    engine.setProperty("voice", desired_voice)
+                    current_voice = desired_voice
+                except Exception:
+                    current_voice = desired_voice
+            else:
+                # If we can't restore a known default voice id, re-init the engine to reset state.
+                try:
+                    engine = pyttsx3.init()
+                except Exception:
+                    pass
+                try:
+                    default_voice = engine.getProperty("voice")
+                except Exception:
+                    default_voice = None
+                if default_voice is not None:
+                    default_voice = (str(default_voice).strip() or None)
+                current_voice = default_voice or ""
+
+        out_dir = os.path.dirname(out_path)
+        if out_dir:
+            os.makedirs(out_dir, exist_ok=True)
+        engine.save_to_file(text, out_path)
+
+    flush_queue()
+
+
+if __name__ == "__main__":
+    main()
+"#;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct DubVoicePreservingV1Params {
     item_id: String,
     source_track_id: String,
     #[serde(default)]
     batch_on_import: bool,
+    /// Cut a reference clip per diarized speaker that has no voice profile yet.
+    #[serde(default)]
+    auto_extract_profiles: bool,
     #[serde(default)]
     pipeline: Option<LocalizationPipelineOptions>,
 }
@@ -400,10 +1187,16 @@ struct MixDubPreviewV1Params {
     timing_fit_min_factor: Option<f32>,
     #[serde(default)]
     timing_fit_max_factor: Option<f32>,
+    /// Measure each TTS segment and level it toward a common loudness before mixing.
+    #[serde(default)]
+    normalize_speech: Option<bool>,
     #[serde(default)]
     batch_on_import: bool,
     #[serde(default)]
     pipeline: Option<LocalizationPipelineOptions>,
+    /// Mix exactly this TTS manifest (under the item's derived dir) instead of the priority pick.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    tts_manifest_path: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -417,29 +1210,163 @@ struct MuxDubPreviewV1Params {
     dubbed_audio_lang: Option<String>,
     #[serde(default)]
     original_audio_lang: Option<String>,
+    /// Subtitle track of the same item to embed as a selectable (non-default) stream.
+    #[serde(default)]
+    embed_subtitle_track_id: Option<String>,
+    /// Switch MP4 output to MKV when the source video codec cannot be stream-copied into MP4.
+    #[serde(default = "default_true")]
+    allow_container_override: bool,
     #[serde(default)]
     batch_on_import: bool,
     #[serde(default)]
     pipeline: Option<LocalizationPipelineOptions>,
 }
 
+/// Video codecs that can be stream-copied into an MP4 container.
+const MP4_COMPATIBLE_VIDEO_CODECS: &[&str] = &["h264", "hevc", "av1", "mpeg4"];
+
+/// Returns the container to actually mux into, or `None` when the requested one can be kept.
+fn mux_container_override(requested: &str, video_codec: Option<&str>) -> Option<&'static str> {
+    let codec = video_codec?.trim().to_ascii_lowercase();
+    if requested == "mp4" && !MP4_COMPATIBLE_VIDEO_CODECS.contains(&codec.as_str()) {
+        Some("mkv")
+    } else {
+        None
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct SeparateAudioSpleeterParams {
     item_id: String,
     #[serde(default)]
     batch_on_import: bool,
+    /// Spleeter model: 2 (vocals/accompaniment) or 5 (vocals/drums/bass/piano/other).
+    #[serde(default = "default_spleeter_stems")]
+    stems: u8,
+    /// Absolute path to audio separated instead of the item's media (skips extraction).
+    #[serde(default)]
+    audio_path_override: Option<String>,
 }
 
+fn default_spleeter_stems() -> u8 {
+    2
+}
+
+/// Stem names written by the Spleeter 5-stem separation.
+const SPLEETER_5STEMS: [&str; 5] = ["vocals", "drums", "bass", "piano", "other"];
+
+/// Runs Spleeter's Python API from a script file (not `-c`/stdin) so multiprocessing can
+/// re-spawn the main module on Windows; the CLI layer breaks across Typer versions.
+const SPLEETER_SEPARATE_SCRIPT: &str = r#"
+import argparse
+
+from spleeter.separator import Separator
+
+
+def main() -> None:
+    ap = argparse.ArgumentParser()
+    ap.add_argument("--input", required=True)
+    ap.add_argument("--output", required=True)
+    ap.add_argument("--config", default="spleeter:2stems")
+    args = ap.parse_args()
+
+    separator = Separator(args.config)
+    separator.separate_to_file(args.input, args.output)
+    print("spleeter_separate_ok")
+
+
+if __name__ == "__main__":
+    main()
+"#;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct SeparateAudioDemucsV1Params {
     item_id: String,
     #[serde(default)]
     batch_on_import: bool,
+    /// `Some(0)` disables chunking; `None` chunks automatically for long inputs.
+    #[serde(default)]
+    chunk_duration_secs: Option<u64>,
+    /// Absolute path to audio separated instead of the item's media (skips extraction).
+    #[serde(default)]
+    audio_path_override: Option<String>,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SeparateAudioDemucs4StemsV1Params {
+    item_id: String,
+}
+
+/// Stem names written by the Demucs 4-stem separation, in output order.
+const DEMUCS_4STEMS: [&str; 4] = ["drums", "bass", "other", "vocals"];
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct CleanVocalsV1Params {
     item_id: String,
+    /// Separation backend whose vocals stem to clean ("demucs" or "spleeter"); any when unset.
+    #[serde(default)]
+    source: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExtractSpeakerVoiceProfileV1Params {
+    item_id: String,
+    speaker_key: String,
+    start_ms: i64,
+    end_ms: i64,
+}
+
+const SPEAKER_VOICE_PROFILE_MIN_MS: i64 = 3_000;
+const SPEAKER_VOICE_PROFILE_MAX_MS: i64 = 30_000;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct SyncSubtitlesV1Params {
+    item_id: String,
+    source_track_id: String,
+}
+
+/// Head job of a full dubbing run. Later stages in the same batch read these params in place of
+/// the global batch-on-import rules.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct FullPipelineV1Params {
+    item_id: String,
+    #[serde(default)]
+    asr_lang: Option<String>,
+    model_id: String,
+    #[serde(default)]
+    tts_backend: Option<String>,
+    #[serde(default)]
+    ducking_strength: Option<f32>,
+}
+
+/// Default cap on items queued by one [`enqueue_translate_batch`] call.
+const TRANSLATE_BATCH_DEFAULT_MAX_ITEMS: usize = 100;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TranslateBatchResult {
+    pub dry_run: bool,
+    /// Shared batch id of the queued jobs; `None` for dry runs or when nothing was queued.
+    pub batch_id: Option<String>,
+    pub enqueued: Vec<JobRow>,
+    /// Items selected for translation (queued, or that would be queued in a dry run).
+    pub eligible_item_ids: Vec<String>,
+    pub skipped_no_source_track: Vec<String>,
+    /// Items that already have an English translation or a queued/running translate job.
+    pub skipped_already_translated: Vec<String>,
+    /// Eligible items left out because of `max_items`.
+    pub skipped_over_limit: usize,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FullPipelineResult {
+    pub batch_id: String,
+    pub jobs: Vec<JobRow>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+struct AlignedFragment {
+    begin_ms: i64,
+    end_ms: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -457,6 +1384,34 @@ struct ExportPackV1Params {
     include_alternates: bool,
     #[serde(default)]
     variant_label: Option<String>,
+    #[serde(default = "default_true")]
+    include_subtitles: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportPackSubtitleTrack {
+    pub track_id: String,
+    pub kind: String,
+    pub lang: String,
+    pub version: i64,
+    pub srt_zip_path: String,
+    pub vtt_zip_path: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ExportMultiPackV1Params {
+    item_ids: Vec<String>,
+    out_path: String,
+}
+
+const EXPORT_MULTI_PACK_MAX_ITEMS: usize = 50;
+
+/// Written to the job artifacts dir as `export_pack_result.json` when an export pack finishes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ExportPackV1Result {
+    pub zip_path: String,
+    pub zip_bytes: u64,
+    pub included_subtitle_tracks: Vec<ExportPackSubtitleTrack>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -686,9 +1641,98 @@ struct DiarizeLocalV1Segment {
 
 #[derive(Debug, Clone, Deserialize)]
 struct TtsPreviewManifest {
+    #[serde(default)]
+    total_duration_ms: Option<i64>,
+    #[serde(default)]
+    synthesized_duration_ms: Option<i64>,
+    #[serde(default)]
+    coverage_ratio: Option<f32>,
+    #[serde(default)]
+    missing_segment_count: Option<usize>,
     segments: Vec<TtsPreviewManifestSegment>,
 }
 
+impl TtsPreviewManifest {
+    /// Coverage written by the TTS job, recomputed from `segments` for older manifests.
+    fn coverage(&self) -> TtsManifestCoverage {
+        match (
+            self.total_duration_ms,
+            self.synthesized_duration_ms,
+            self.coverage_ratio,
+            self.missing_segment_count,
+        ) {
+            (
+                Some(total_duration_ms),
+                Some(synthesized_duration_ms),
+                Some(coverage_ratio),
+                Some(missing_segment_count),
+            ) => TtsManifestCoverage {
+                total_duration_ms,
+                synthesized_duration_ms,
+                coverage_ratio,
+                missing_segment_count,
+            },
+            _ => TtsManifestCoverage::from_spans(
+                self.segments
+                    .iter()
+                    .map(|seg| (seg.start_ms, seg.end_ms, seg.audio_exists)),
+            ),
+        }
+    }
+}
+
+/// Share of the subtitle timeline that has synthesized TTS audio.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq)]
+pub struct TtsManifestCoverage {
+    pub total_duration_ms: i64,
+    pub synthesized_duration_ms: i64,
+    pub coverage_ratio: f32,
+    pub missing_segment_count: usize,
+}
+
+impl TtsManifestCoverage {
+    fn from_spans(spans: impl IntoIterator<Item = (i64, i64, bool)>) -> Self {
+        let mut total_duration_ms = 0i64;
+        let mut synthesized_duration_ms = 0i64;
+        let mut missing_segment_count = 0usize;
+        for (start_ms, end_ms, audio_exists) in spans {
+            let duration_ms = end_ms.saturating_sub(start_ms).max(0);
+            total_duration_ms = total_duration_ms.saturating_add(duration_ms);
+            if audio_exists {
+                synthesized_duration_ms = synthesized_duration_ms.saturating_add(duration_ms);
+            } else {
+                missing_segment_count += 1;
+            }
+        }
+        let coverage_ratio = if total_duration_ms > 0 {
+            (synthesized_duration_ms as f64 / total_duration_ms as f64).clamp(0.0, 1.0) as f32
+        } else if missing_segment_count == 0 {
+            1.0
+        } else {
+            0.0
+        };
+        Self {
+            total_duration_ms,
+            synthesized_duration_ms,
+            coverage_ratio,
+            missing_segment_count,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct TtsManifestSummary {
+    pub backend_id: String,
+    pub variant_label: Option<String>,
+    pub manifest_path: String,
+    pub segment_count: usize,
+    #[serde(flatten)]
+    pub coverage: TtsManifestCoverage,
+}
+
+/// Mixing warns when less than this share of the timeline has TTS audio.
+const MIX_TTS_COVERAGE_WARN_RATIO: f32 = 0.9;
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
 #[serde(rename_all = "snake_case")]
 pub enum VoiceCloneIntent {
@@ -881,87 +1925,156 @@ pub(crate) struct TtsPreviewManifestSegment {
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) struct QcThresholds {
-    pub(crate) cps_warn: f32,
-    pub(crate) cps_fail: f32,
-    pub(crate) line_chars_warn: usize,
-    pub(crate) line_chars_fail: usize,
-    pub(crate) overlap_warn_ms: i64,
+pub struct QcThresholds {
+    pub cps_warn: f32,
+    pub cps_fail: f32,
+    pub line_chars_warn: usize,
+    pub line_chars_fail: usize,
+    pub overlap_warn_ms: i64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) struct QcSummary {
-    pub(crate) total_segments: usize,
-    pub(crate) issues_total: usize,
-    pub(crate) issues_by_kind: std::collections::BTreeMap<String, usize>,
+pub struct QcSummary {
+    pub total_segments: usize,
+    pub issues_total: usize,
+    pub issues_by_kind: std::collections::BTreeMap<String, usize>,
+    /// Segments with a measured TTS duration; the percentages below are relative to this.
+    #[serde(default)]
+    pub timed_segments: usize,
+    #[serde(default)]
+    pub percent_on_time: f64,
+    #[serde(default)]
+    pub percent_short: f64,
+    #[serde(default)]
+    pub percent_long: f64,
+    /// Fraction of timed segments whose TTS/window ratio falls in the on-time band.
+    #[serde(default)]
+    pub timing_accuracy_score: Option<f64>,
+    #[serde(default)]
+    pub timing_ratio_p50: Option<f64>,
+    #[serde(default)]
+    pub timing_ratio_p90: Option<f64>,
+    /// Total words over total spoken time (TTS duration, else the subtitle window).
+    #[serde(default)]
+    pub words_per_minute: Option<f64>,
+    /// Silences of at least [`QC_GAP_MIN_MS`] between consecutive segments.
+    #[serde(default)]
+    pub gap_count: usize,
+    /// Window time covered by TTS audio across all segments, 0.0–1.0.
+    #[serde(default)]
+    pub timing_coverage: Option<f64>,
+    /// Fraction of segments with non-empty text.
+    #[serde(default)]
+    pub text_coverage: Option<f64>,
+    /// Translated characters over source characters for segments matched by index; `None` for
+    /// source tracks or when no source track exists.
+    #[serde(default)]
+    pub compression_ratio: Option<f64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) struct QcIssueRecord {
-    pub(crate) kind: String,
-    pub(crate) severity: String,
-    pub(crate) segment_index: u32,
-    pub(crate) start_ms: i64,
-    pub(crate) end_ms: i64,
-    pub(crate) message: String,
-    pub(crate) value: Option<f64>,
+pub struct QcSegmentReport {
+    pub segment_index: u32,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub window_ms: i64,
+    pub word_count: usize,
+    /// Non-whitespace characters in the segment text.
+    #[serde(default)]
+    pub text_chars: usize,
+    /// Non-whitespace characters of the source segment with the same index.
+    #[serde(default)]
+    pub source_text_chars: Option<usize>,
+    pub tts_duration_ms: Option<i64>,
+    /// Share of the window filled by TTS audio, capped at 1.0.
+    #[serde(default)]
+    pub coverage: Option<f64>,
+    /// `tts_duration_ms / window_ms`.
+    pub timing_ratio: Option<f64>,
+    /// `"short"` (< 0.8), `"on_time"` (0.8–1.2) or `"long"` (> 1.2).
+    pub timing_bucket: Option<String>,
+    /// Words per minute over the TTS duration, or over the window when no TTS audio exists.
+    pub word_rate_wpm: Option<f64>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct QcIssueRecord {
+    pub kind: String,
+    pub severity: String,
+    pub segment_index: u32,
+    pub start_ms: i64,
+    pub end_ms: i64,
+    pub message: String,
+    pub value: Option<f64>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) speaker_key: Option<String>,
+    pub speaker_key: Option<String>,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) artifact_path: Option<String>,
+    pub artifact_path: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub(crate) struct VoiceAudioStats {
-    pub(crate) duration_ms: i64,
-    pub(crate) sample_rate: u32,
-    pub(crate) peak_abs: f32,
-    pub(crate) rms: f32,
-    pub(crate) clipped_ratio: f32,
-    pub(crate) silence_ratio: f32,
-    pub(crate) zero_cross_ratio: f32,
-    pub(crate) pitch_hz: Option<f32>,
+pub struct VoiceAudioStats {
+    pub duration_ms: i64,
+    pub sample_rate: u32,
+    pub peak_abs: f32,
+    pub rms: f32,
+    pub clipped_ratio: f32,
+    pub silence_ratio: f32,
+    pub zero_cross_ratio: f32,
+    pub pitch_hz: Option<f32>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) struct VoiceReferenceQcRecord {
-    pub(crate) speaker_key: String,
-    pub(crate) path: String,
-    pub(crate) label: Option<String>,
-    pub(crate) stats: VoiceAudioStats,
-    pub(crate) warnings: Vec<String>,
+pub struct VoiceReferenceQcRecord {
+    pub speaker_key: String,
+    pub path: String,
+    pub label: Option<String>,
+    pub stats: VoiceAudioStats,
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) struct VoiceOutputQcRecord {
-    pub(crate) speaker_key: Option<String>,
-    pub(crate) segment_index: u32,
-    pub(crate) path: String,
-    pub(crate) stats: VoiceAudioStats,
-    pub(crate) warnings: Vec<String>,
+pub struct VoiceOutputQcRecord {
+    pub speaker_key: Option<String>,
+    pub segment_index: u32,
+    pub path: String,
+    pub stats: VoiceAudioStats,
+    pub warnings: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
-pub(crate) struct VoiceQcReportSection {
-    pub(crate) references: Vec<VoiceReferenceQcRecord>,
-    pub(crate) outputs: Vec<VoiceOutputQcRecord>,
+pub struct VoiceQcReportSection {
+    pub references: Vec<VoiceReferenceQcRecord>,
+    pub outputs: Vec<VoiceOutputQcRecord>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
-pub(crate) struct QcReportV1 {
-    pub(crate) schema_version: u32,
-    pub(crate) generated_at_ms: i64,
-    pub(crate) item_id: String,
-    pub(crate) track_id: String,
-    pub(crate) lang: String,
+pub struct QcReportV1Result {
+    pub schema_version: u32,
+    pub generated_at_ms: i64,
+    pub item_id: String,
+    pub track_id: String,
+    pub lang: String,
     #[serde(skip_serializing_if = "Option::is_none")]
-    pub(crate) variant_label: Option<String>,
-    pub(crate) thresholds: QcThresholds,
-    pub(crate) tts_backend: Option<String>,
-    pub(crate) tts_manifest_path: Option<String>,
-    pub(crate) issues: Vec<QcIssueRecord>,
-    pub(crate) voice: VoiceQcReportSection,
-    pub(crate) summary: QcSummary,
+    pub variant_label: Option<String>,
+    pub thresholds: QcThresholds,
+    pub tts_backend: Option<String>,
+    pub tts_manifest_path: Option<String>,
+    pub issues: Vec<QcIssueRecord>,
+    pub voice: VoiceQcReportSection,
+    #[serde(default)]
+    pub segments: Vec<QcSegmentReport>,
+    pub summary: QcSummary,
+    #[serde(default)]
+    pub source_track_id: Option<String>,
+    /// Weighted composite of timing coverage, text coverage and compression ratio, 0.0–1.0.
+    #[serde(default)]
+    pub qc_overall_score: f32,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct InstallModelV1Params {
+    model_id: String,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -969,6 +2082,27 @@ struct DummySleepParams {
     seconds: u64,
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct WatchDirectoryParams {
+    dir_path: String,
+    /// Lowercase extensions without the dot.
+    extensions: Vec<String>,
+    interval_secs: u64,
+}
+
+/// Per-file state persisted in the watch job's `known_files.json`.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+struct WatchedFileState {
+    size_bytes: u64,
+    modified_ms: i64,
+    /// Hash of the last imported (or baseline) content.
+    #[serde(default)]
+    sha256: Option<String>,
+    /// Size or mtime changed since the previous scan; the file is hashed once it is stable.
+    #[serde(default)]
+    pending: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct DownloadDirectUrlParams {
     url: String,
@@ -996,6 +2130,20 @@ struct DownloadDirectUrlParams {
     quality_preference: Option<String>,
     #[serde(default)]
     subtitle_mode: Option<String>,
+    #[serde(default)]
+    output_filename_template: Option<String>,
+    /// Earliest start time (epoch ms); copied into the job's `not_before_ms`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schedule_after_ms: Option<i64>,
+    /// Instagram media pk decoded from the post shortcode; used to dedupe batch targets.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    media_id: Option<String>,
+    /// yt-dlp `--download-archive` file the download is recorded in.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    write_archive: Option<String>,
+    /// Ask yt-dlp for a `.info.json` sidecar and copy its fields into item metadata.
+    #[serde(default = "default_true")]
+    write_info_json: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -1020,6 +2168,22 @@ struct DownloadImageBatchParams {
     output_dir: Option<String>,
     #[serde(default, skip_serializing)]
     auth_cookie: Option<String>,
+    #[serde(default)]
+    max_hamming_distance: Option<u8>,
+    /// Largest image accepted, in bytes; defaults to 20 MB.
+    #[serde(default)]
+    max_image_bytes: Option<u64>,
+    /// Only image URLs with one of these extensions are fetched; empty means no filter.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    file_extensions_filter: Option<Vec<String>>,
+    /// Pixel-count (width * height) bounds for downloaded images; `None` means no limit.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    min_image_pixels: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    max_image_pixels: Option<u64>,
+    /// Earliest start time (epoch ms); copied into the job's `not_before_ms`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    schedule_after_ms: Option<i64>,
 }
 
 #[derive(Debug, Clone)]
@@ -1033,10 +2197,18 @@ pub struct JobRuntimeSettings {
     pub max_concurrency: usize,
 }
 
-fn canonical_import_path(path: &str) -> Result<String> {
-    let trimmed = path.trim();
-    if trimmed.is_empty() {
-        return Err(EngineError::InstallFailed(
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JobRuntimeState {
+    pub running_count: usize,
+    pub queued_count: usize,
+    pub paused: bool,
+    pub max_concurrency: usize,
+}
+
+fn canonical_import_path(path: &str) -> Result<String> {
+    let trimmed = path.trim();
+    if trimmed.is_empty() {
+        return Err(EngineError::InstallFailed(
             "import path is required".to_string(),
         ));
     }
@@ -1069,6 +2241,20 @@ fn job_row_from_query_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<JobRow> {
         finished_at_ms: row.get(9)?,
         logs_path: row.get(10)?,
         params_json: row.get(11)?,
+        requeue_count: row.get(12)?,
+        content_key: row.get(13)?,
+        not_before_ms: row.get(14)?,
+        estimate_remaining_ms: None,
+        on_complete_jobs: row
+            .get::<_, Option<String>>(15)?
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default(),
+        worker_id: row.get(16)?,
+        parent_job_id: row.get(17)?,
+        progress_steps: row
+            .get::<_, Option<String>>(18)?
+            .and_then(|raw| serde_json::from_str(&raw).ok())
+            .unwrap_or_default(),
     })
 }
 
@@ -1093,7 +2279,14 @@ SELECT
   started_at_ms,
   finished_at_ms,
   logs_path,
-  params_json
+  params_json,
+  requeue_count,
+  content_key,
+  not_before_ms,
+  on_complete_json,
+  worker_id,
+  parent_job_id,
+  progress_steps_json
 FROM job
 WHERE type=?1 AND status IN (?2, ?3)
 ORDER BY created_at_ms ASC
@@ -1147,6 +2340,7 @@ fn enqueue_completed_import_reuse_job(
         params_json,
         Some(item.id.clone()),
         None,
+        None,
     )?;
     let now = now_ms();
     let conn = db::open(paths)?;
@@ -1226,36 +2420,245 @@ pub fn enqueue_import_local(
     } else {
         None
     };
-    enqueue_with_type_item_and_batch_id(paths, JobType::ImportLocal, params_json, None, batch_id)
+    enqueue_with_type_item_and_batch_id(
+        paths,
+        JobType::ImportLocal,
+        params_json,
+        None,
+        batch_id,
+        None,
+    )
+}
+
+/// Marks a phase-2 install run failed (and optionally rolls it back) on every exit that does not
+/// reach [`Phase2InstallRunGuard::succeeded`].
+struct Phase2InstallRunGuard<'a> {
+    paths: &'a AppPaths,
+    job_id: &'a str,
+    rollback_on_failure: bool,
+    finished: bool,
+}
+
+impl Phase2InstallRunGuard<'_> {
+    fn succeeded(mut self) {
+        self.finished = true;
+        let _ = tools::set_phase2_last_run_status(self.paths, tools::PHASE2_RUN_STATUS_SUCCEEDED);
+    }
+}
+
+impl Drop for Phase2InstallRunGuard<'_> {
+    fn drop(&mut self) {
+        if self.finished {
+            return;
+        }
+        let _ = tools::set_phase2_last_run_status(self.paths, tools::PHASE2_RUN_STATUS_FAILED);
+        if self.rollback_on_failure {
+            rollback_phase2_packs_logged(self.paths, self.job_id);
+        }
+    }
 }
 
+fn rollback_phase2_packs_logged(paths: &AppPaths, job_id: &str) {
+    match tools::rollback_phase2_packs(paths) {
+        Ok(summary) => {
+            for step in &summary.steps {
+                let _ = log_line(
+                    paths,
+                    job_id,
+                    if step.ok { "info" } else { "warn" },
+                    "phase2_rollback_step",
+                    serde_json::json!({
+                        "package": &step.package,
+                        "action": &step.action,
+                        "ok": step.ok,
+                        "error": &step.error,
+                    }),
+                );
+            }
+            let _ = log_line(
+                paths,
+                job_id,
+                "info",
+                "phase2_rollback_done",
+                serde_json::json!({ "restored": summary.steps.iter().filter(|s| s.ok).count() }),
+            );
+        }
+        Err(err) => {
+            let _ = log_line(
+                paths,
+                job_id,
+                "error",
+                "phase2_rollback_failed",
+                serde_json::json!({ "error": err.to_string() }),
+            );
+        }
+    }
+}
+
+/// Returns the already queued or running install job instead of starting a second one; pip
+/// operations cannot safely run in parallel.
 pub fn enqueue_install_phase2_packs_v1(paths: &AppPaths) -> Result<JobRow> {
-    let params_json = serde_json::to_string(&InstallPhase2PacksV1Params::default())?;
+    enqueue_install_phase2_packs_v1_with_options(paths, false)
+}
+
+pub fn enqueue_install_phase2_packs_v1_with_options(
+    paths: &AppPaths,
+    rollback_on_failure: bool,
+) -> Result<JobRow> {
+    if let Some(existing) = active_job_of_type(paths, JobType::InstallPhase2PacksV1.as_str())? {
+        return Ok(existing);
+    }
+    let params_json = serde_json::to_string(&InstallPhase2PacksV1Params {
+        rollback_on_failure,
+        ..Default::default()
+    })?;
     enqueue(paths, JobType::InstallPhase2PacksV1, params_json)
 }
 
+/// Installs a model in the background; returns the active install job for the same model
+/// instead of starting a second one.
+pub fn enqueue_install_model_v1(paths: &AppPaths, model_id: &str) -> Result<JobRow> {
+    let model = models::ModelStore::new(paths.clone()).model_spec_by_id(model_id.trim())?;
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, params_json FROM job WHERE type=?1 AND status IN ('queued','running')",
+    )?;
+    let active: Vec<(String, String)> = stmt
+        .query_map([JobType::InstallModelV1.as_str()], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?
+        .collect::<std::result::Result<_, _>>()?;
+    for (id, params_json) in active {
+        let same_model = serde_json::from_str::<InstallModelV1Params>(&params_json)
+            .is_ok_and(|p| p.model_id == model.id);
+        if same_model {
+            if let Some(existing) = get_job(paths, &id)? {
+                return Ok(existing);
+            }
+        }
+    }
+    let params_json = serde_json::to_string(&InstallModelV1Params { model_id: model.id })?;
+    enqueue(paths, JobType::InstallModelV1, params_json)
+}
+
+/// Starts a long-running job that imports new files appearing in `dir_path`. Returns the active
+/// watch job for the same directory instead of starting a second one. Empty `extensions` means
+/// every supported import type.
+pub fn enqueue_watch_directory(
+    paths: &AppPaths,
+    dir_path: String,
+    extensions: Vec<String>,
+    interval_secs: u64,
+) -> Result<JobRow> {
+    let dir_path = canonical_import_path(&dir_path)?;
+    if !Path::new(&dir_path).is_dir() {
+        return Err(EngineError::InstallFailed(format!(
+            "watch path is not a directory: {dir_path}"
+        )));
+    }
+    let supported = library::supported_import_extensions();
+    let mut normalized: Vec<String> = Vec::new();
+    for raw in extensions {
+        let ext = raw.trim().trim_start_matches('.').to_ascii_lowercase();
+        if ext.is_empty() || normalized.contains(&ext) {
+            continue;
+        }
+        if !supported.contains(&ext.as_str()) {
+            return Err(EngineError::InstallFailed(format!(
+                "unsupported watch extension: {ext} (supported: {})",
+                supported.join(", ")
+            )));
+        }
+        normalized.push(ext);
+    }
+    if normalized.is_empty() {
+        normalized = supported.iter().map(|ext| ext.to_string()).collect();
+    }
+
+    if let Some(existing) = active_watch_directory_job(paths, &dir_path)? {
+        return Ok(existing);
+    }
+    let params_json = serde_json::to_string(&WatchDirectoryParams {
+        dir_path,
+        extensions: normalized,
+        interval_secs: interval_secs.clamp(
+            WATCH_DIRECTORY_MIN_INTERVAL_SECS,
+            WATCH_DIRECTORY_MAX_INTERVAL_SECS,
+        ),
+    })?;
+    enqueue(paths, JobType::WatchDirectory, params_json)
+}
+
+fn active_watch_directory_job(paths: &AppPaths, dir_path: &str) -> Result<Option<JobRow>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, params_json FROM job WHERE type=?1 AND status IN (?2, ?3) ORDER BY created_at_ms ASC",
+    )?;
+    let rows = stmt
+        .query_map(
+            params![
+                JobType::WatchDirectory.as_str(),
+                JobStatus::Queued.as_str(),
+                JobStatus::Running.as_str()
+            ],
+            |row| Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?)),
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    let key = import_path_match_key(dir_path);
+    for (id, params_json) in rows {
+        let Ok(p) = serde_json::from_str::<WatchDirectoryParams>(&params_json) else {
+            continue;
+        };
+        if import_path_match_key(&p.dir_path) == key {
+            return get_job(paths, &id);
+        }
+    }
+    Ok(None)
+}
+
+/// Like [`enqueue_install_phase2_packs_v1`], reuses an active dummy job so repeated clicks are
+/// idempotent.
 pub fn enqueue_dummy_sleep(paths: &AppPaths, seconds: u64) -> Result<JobRow> {
+    if let Some(existing) = active_job_of_type(paths, JobType::DummySleep.as_str())? {
+        return Ok(existing);
+    }
     let seconds = seconds.clamp(1, 600);
     let params_json = serde_json::to_string(&DummySleepParams { seconds })?;
     enqueue(paths, JobType::DummySleep, params_json)
 }
 
+const ASR_TASK_TRANSCRIBE: &str = "transcribe";
+const ASR_TASK_TRANSLATE: &str = "translate";
+
+fn normalize_asr_task(task: Option<String>) -> Result<Option<String>> {
+    let Some(task) = task else {
+        return Ok(None);
+    };
+    let task = task.trim().to_ascii_lowercase();
+    match task.as_str() {
+        "" => Ok(None),
+        ASR_TASK_TRANSCRIBE | ASR_TASK_TRANSLATE => Ok(Some(task)),
+        other => Err(EngineError::InstallFailed(format!(
+            "unsupported ASR task: {other} (expected transcribe or translate)"
+        ))),
+    }
+}
+
+fn normalize_asr_lang(lang: Option<String>) -> Option<String> {
+    lang.map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty() && v != "auto")
+}
+
 pub fn enqueue_asr_local(
     paths: &AppPaths,
     item_id: String,
     lang: Option<String>,
+    task: Option<String>,
 ) -> Result<JobRow> {
-    let lang = match lang {
-        Some(v) => {
-            let v = v.trim().to_string();
-            if v.is_empty() || v == "auto" {
-                None
-            } else {
-                Some(v)
-            }
-        }
-        None => None,
-    };
+    let task = normalize_asr_task(task)?;
+    let lang = normalize_asr_lang(lang);
 
     let model_id = "whispercpp-tiny".to_string();
     let params_json = serde_json::to_string(&AsrLocalParams {
@@ -1264,6 +2667,7 @@ pub fn enqueue_asr_local(
         model_id,
         batch_on_import: false,
         pipeline: None,
+        task,
     })?;
 
     enqueue_with_type_and_item_id(paths, JobType::AsrLocal, params_json, Some(item_id))
@@ -1286,6 +2690,100 @@ pub fn enqueue_translate_local(
     enqueue_with_type_and_item_id(paths, JobType::TranslateLocal, params_json, Some(item_id))
 }
 
+/// Queues `translate_local` for every library item that has a source track but no English
+/// translation, using each item's first source track.
+pub fn enqueue_translate_batch(
+    paths: &AppPaths,
+    model_id: Option<String>,
+    dry_run: bool,
+    max_items: Option<usize>,
+) -> Result<TranslateBatchResult> {
+    let model_id =
+        normalize_non_empty(model_id.as_deref()).unwrap_or_else(|| "whispercpp-tiny".to_string());
+    let max_items = max_items.unwrap_or(TRANSLATE_BATCH_DEFAULT_MAX_ITEMS);
+
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut stmt = conn.prepare(
+        r#"
+SELECT
+  li.id,
+  (SELECT st.id FROM subtitle_track st
+   WHERE st.item_id = li.id AND st.kind = 'source'
+   ORDER BY st.rowid ASC LIMIT 1),
+  EXISTS(SELECT 1 FROM subtitle_track st
+         WHERE st.item_id = li.id AND st.kind = 'translated' AND st.lang = 'en'),
+  EXISTS(SELECT 1 FROM job j
+         WHERE j.item_id = li.id AND j.type = ?1 AND j.status IN ('queued', 'running'))
+FROM library_item li
+ORDER BY li.created_at_ms ASC, li.id ASC
+"#,
+    )?;
+    let rows = stmt
+        .query_map([JobType::TranslateLocal.as_str()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, bool>(2)?,
+                row.get::<_, bool>(3)?,
+            ))
+        })?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    drop(stmt);
+    drop(conn);
+
+    let mut result = TranslateBatchResult {
+        dry_run,
+        batch_id: None,
+        enqueued: Vec::new(),
+        eligible_item_ids: Vec::new(),
+        skipped_no_source_track: Vec::new(),
+        skipped_already_translated: Vec::new(),
+        skipped_over_limit: 0,
+    };
+    let mut selected: Vec<(String, String)> = Vec::new();
+    for (item_id, source_track_id, has_english, has_active_job) in rows {
+        let Some(source_track_id) = source_track_id else {
+            result.skipped_no_source_track.push(item_id);
+            continue;
+        };
+        if has_english || has_active_job {
+            result.skipped_already_translated.push(item_id);
+            continue;
+        }
+        if selected.len() >= max_items {
+            result.skipped_over_limit += 1;
+            continue;
+        }
+        result.eligible_item_ids.push(item_id.clone());
+        selected.push((item_id, source_track_id));
+    }
+    if dry_run || selected.is_empty() {
+        return Ok(result);
+    }
+
+    let batch_id = Uuid::new_v4().to_string();
+    for (item_id, source_track_id) in selected {
+        let params_json = serde_json::to_string(&TranslateLocalParams {
+            item_id: item_id.clone(),
+            source_track_id,
+            model_id: model_id.clone(),
+            batch_on_import: false,
+            pipeline: None,
+        })?;
+        result.enqueued.push(enqueue_with_type_item_and_batch_id(
+            paths,
+            JobType::TranslateLocal,
+            params_json,
+            Some(item_id),
+            Some(batch_id.clone()),
+            None,
+        )?);
+    }
+    result.batch_id = Some(batch_id);
+    Ok(result)
+}
+
 pub fn enqueue_diarize_local_v1(
     paths: &AppPaths,
     item_id: String,
@@ -1362,11 +2860,16 @@ pub fn enqueue_tts_neural_local_v1(
     paths: &AppPaths,
     item_id: String,
     source_track_id: String,
+    lang_code: Option<String>,
+    fallback_to_pyttsx3: Option<bool>,
 ) -> Result<JobRow> {
+    let lang_code = config::normalize_kokoro_lang_code(lang_code.as_deref())?;
     let params_json = serde_json::to_string(&TtsNeuralLocalV1Params {
         item_id: item_id.clone(),
         source_track_id,
         batch_on_import: false,
+        lang_code,
+        fallback_to_pyttsx3: fallback_to_pyttsx3.unwrap_or(true),
     })?;
     enqueue_with_type_and_item_id(paths, JobType::TtsNeuralLocalV1, params_json, Some(item_id))
 }
@@ -1375,11 +2878,21 @@ pub fn enqueue_dub_voice_preserving_v1(
     paths: &AppPaths,
     item_id: String,
     source_track_id: String,
+) -> Result<JobRow> {
+    enqueue_dub_voice_preserving_v1_with_options(paths, item_id, source_track_id, false)
+}
+
+pub fn enqueue_dub_voice_preserving_v1_with_options(
+    paths: &AppPaths,
+    item_id: String,
+    source_track_id: String,
+    auto_extract_profiles: bool,
 ) -> Result<JobRow> {
     let params_json = serde_json::to_string(&DubVoicePreservingV1Params {
         item_id: item_id.clone(),
         source_track_id,
         batch_on_import: false,
+        auto_extract_profiles,
         pipeline: None,
     })?;
     enqueue_with_type_and_item_id(
@@ -1458,6 +2971,7 @@ fn enqueue_experimental_voice_backend_render_v1_with_batch_id(
         params_json,
         Some(item_id),
         batch_id,
+        None,
     )
 }
 
@@ -1469,8 +2983,10 @@ pub fn enqueue_mix_dub_preview_v1(paths: &AppPaths, item_id: String) -> Result<J
         timing_fit_enabled: None,
         timing_fit_min_factor: None,
         timing_fit_max_factor: None,
+        normalize_speech: None,
         batch_on_import: false,
         pipeline: None,
+        tts_manifest_path: None,
     })?;
     enqueue_with_type_and_item_id(paths, JobType::MixDubPreviewV1, params_json, Some(item_id))
 }
@@ -1483,7 +2999,24 @@ pub fn enqueue_mix_dub_preview_v1_with_options(
     timing_fit_enabled: Option<bool>,
     timing_fit_min_factor: Option<f32>,
     timing_fit_max_factor: Option<f32>,
+    normalize_speech: Option<bool>,
+    tts_manifest_path: Option<String>,
+    variant_label: Option<String>,
 ) -> Result<JobRow> {
+    let tts_manifest_path = match normalize_non_empty(tts_manifest_path.as_deref()) {
+        Some(raw) => Some(
+            resolve_item_tts_manifest_path(paths, &item_id, &raw)?
+                .to_string_lossy()
+                .to_string(),
+        ),
+        None => None,
+    };
+    let pipeline = normalize_variant_label(variant_label.as_deref()).map(|variant_label| {
+        LocalizationPipelineOptions {
+            variant_label: Some(variant_label),
+            ..Default::default()
+        }
+    });
     let params_json = serde_json::to_string(&MixDubPreviewV1Params {
         item_id: item_id.clone(),
         ducking_strength,
@@ -1491,8 +3024,10 @@ pub fn enqueue_mix_dub_preview_v1_with_options(
         timing_fit_enabled,
         timing_fit_min_factor,
         timing_fit_max_factor,
+        normalize_speech,
         batch_on_import: false,
-        pipeline: None,
+        pipeline,
+        tts_manifest_path,
     })?;
     enqueue_with_type_and_item_id(paths, JobType::MixDubPreviewV1, params_json, Some(item_id))
 }
@@ -1504,6 +3039,8 @@ pub fn enqueue_mux_dub_preview_v1(paths: &AppPaths, item_id: String) -> Result<J
         keep_original_audio: None,
         dubbed_audio_lang: None,
         original_audio_lang: None,
+        embed_subtitle_track_id: None,
+        allow_container_override: true,
         batch_on_import: false,
         pipeline: None,
     })?;
@@ -1517,6 +3054,7 @@ pub fn enqueue_mux_dub_preview_v1_with_options(
     keep_original_audio: Option<bool>,
     dubbed_audio_lang: Option<String>,
     original_audio_lang: Option<String>,
+    embed_subtitle_track_id: Option<String>,
 ) -> Result<JobRow> {
     let params_json = serde_json::to_string(&MuxDubPreviewV1Params {
         item_id: item_id.clone(),
@@ -1524,6 +3062,10 @@ pub fn enqueue_mux_dub_preview_v1_with_options(
         keep_original_audio,
         dubbed_audio_lang,
         original_audio_lang,
+        embed_subtitle_track_id: embed_subtitle_track_id
+            .map(|v| v.trim().to_string())
+            .filter(|v| !v.is_empty()),
+        allow_container_override: true,
         batch_on_import: false,
         pipeline: None,
     })?;
@@ -1531,9 +3073,26 @@ pub fn enqueue_mux_dub_preview_v1_with_options(
 }
 
 pub fn enqueue_separate_audio_spleeter(paths: &AppPaths, item_id: String) -> Result<JobRow> {
+    enqueue_separate_audio_spleeter_with_options(paths, item_id, 2, None)
+}
+
+pub fn enqueue_separate_audio_spleeter_5stems(paths: &AppPaths, item_id: String) -> Result<JobRow> {
+    enqueue_separate_audio_spleeter_with_options(paths, item_id, 5, None)
+}
+
+pub fn enqueue_separate_audio_spleeter_with_options(
+    paths: &AppPaths,
+    item_id: String,
+    stems: u8,
+    audio_path_override: Option<String>,
+) -> Result<JobRow> {
+    let audio_path_override = resolve_separation_audio_override(audio_path_override.as_deref())?
+        .map(|path| path.to_string_lossy().to_string());
     let params_json = serde_json::to_string(&SeparateAudioSpleeterParams {
         item_id: item_id.clone(),
         batch_on_import: false,
+        stems,
+        audio_path_override,
     })?;
     enqueue_with_type_and_item_id(
         paths,
@@ -1544,9 +3103,22 @@ pub fn enqueue_separate_audio_spleeter(paths: &AppPaths, item_id: String) -> Res
 }
 
 pub fn enqueue_separate_audio_demucs_v1(paths: &AppPaths, item_id: String) -> Result<JobRow> {
+    enqueue_separate_audio_demucs_v1_with_options(paths, item_id, None, None)
+}
+
+pub fn enqueue_separate_audio_demucs_v1_with_options(
+    paths: &AppPaths,
+    item_id: String,
+    chunk_duration_secs: Option<u64>,
+    audio_path_override: Option<String>,
+) -> Result<JobRow> {
+    let audio_path_override = resolve_separation_audio_override(audio_path_override.as_deref())?
+        .map(|path| path.to_string_lossy().to_string());
     let params_json = serde_json::to_string(&SeparateAudioDemucsV1Params {
         item_id: item_id.clone(),
         batch_on_import: false,
+        chunk_duration_secs,
+        audio_path_override,
     })?;
     enqueue_with_type_and_item_id(
         paths,
@@ -1556,58 +3128,302 @@ pub fn enqueue_separate_audio_demucs_v1(paths: &AppPaths, item_id: String) -> Re
     )
 }
 
-pub fn enqueue_clean_vocals_v1(paths: &AppPaths, item_id: String) -> Result<JobRow> {
-    let params_json = serde_json::to_string(&CleanVocalsV1Params {
+pub fn enqueue_separate_audio_demucs_4stems_v1(
+    paths: &AppPaths,
+    item_id: String,
+) -> Result<JobRow> {
+    let params_json = serde_json::to_string(&SeparateAudioDemucs4StemsV1Params {
         item_id: item_id.clone(),
     })?;
-    enqueue_with_type_and_item_id(paths, JobType::CleanVocalsV1, params_json, Some(item_id))
+    enqueue_with_type_and_item_id(
+        paths,
+        JobType::SeparateAudioDemucs4StemsV1,
+        params_json,
+        Some(item_id),
+    )
 }
 
-pub fn enqueue_qc_report_v1(paths: &AppPaths, item_id: String, track_id: String) -> Result<JobRow> {
-    let params_json = serde_json::to_string(&QcReportV1Params {
+pub fn enqueue_clean_vocals_v1(
+    paths: &AppPaths,
+    item_id: String,
+    source: Option<String>,
+) -> Result<JobRow> {
+    let source = normalize_clean_vocals_source(source.as_deref())?;
+    let params_json = serde_json::to_string(&CleanVocalsV1Params {
         item_id: item_id.clone(),
-        track_id,
-        variant_label: None,
+        source,
     })?;
-    enqueue_with_type_and_item_id(paths, JobType::QcReportV1, params_json, Some(item_id))
+    enqueue_with_type_and_item_id(paths, JobType::CleanVocalsV1, params_json, Some(item_id))
 }
 
-pub fn enqueue_qc_report_v1_with_variant(
+pub fn enqueue_extract_speaker_voice_profile_v1(
     paths: &AppPaths,
     item_id: String,
-    track_id: String,
-    variant_label: Option<String>,
+    speaker_key: String,
+    start_ms: i64,
+    end_ms: i64,
 ) -> Result<JobRow> {
-    let params_json = serde_json::to_string(&QcReportV1Params {
+    let speaker_key = speaker_key.trim().to_string();
+    if speaker_key.is_empty() {
+        return Err(EngineError::InstallFailed(
+            "speaker_key is empty".to_string(),
+        ));
+    }
+    validate_speaker_voice_profile_range(start_ms, end_ms)?;
+    let params_json = serde_json::to_string(&ExtractSpeakerVoiceProfileV1Params {
         item_id: item_id.clone(),
-        track_id,
-        variant_label,
+        speaker_key,
+        start_ms,
+        end_ms,
     })?;
-    enqueue_with_type_and_item_id(paths, JobType::QcReportV1, params_json, Some(item_id))
+    enqueue_with_type_and_item_id(
+        paths,
+        JobType::ExtractSpeakerVoiceProfileV1,
+        params_json,
+        Some(item_id),
+    )
 }
 
-pub fn enqueue_export_pack_v1(paths: &AppPaths, item_id: String) -> Result<JobRow> {
-    let params_json = serde_json::to_string(&ExportPackV1Params {
-        item_id: item_id.clone(),
-        include_alternates: true,
-        variant_label: None,
-    })?;
-    enqueue_with_type_and_item_id(paths, JobType::ExportPackV1, params_json, Some(item_id))
+fn validate_speaker_voice_profile_range(start_ms: i64, end_ms: i64) -> Result<()> {
+    let duration_ms = end_ms - start_ms;
+    if start_ms < 0
+        || !(SPEAKER_VOICE_PROFILE_MIN_MS..=SPEAKER_VOICE_PROFILE_MAX_MS).contains(&duration_ms)
+    {
+        return Err(EngineError::InstallFailed(format!(
+            "voice profile clip must be between {SPEAKER_VOICE_PROFILE_MIN_MS} and {SPEAKER_VOICE_PROFILE_MAX_MS} ms (got start_ms={start_ms} end_ms={end_ms})"
+        )));
+    }
+    Ok(())
 }
 
-pub fn enqueue_localization_batch_v1(
+pub fn enqueue_sync_subtitles_v1(
     paths: &AppPaths,
-    request: LocalizationBatchRequest,
-) -> Result<LocalizationBatchQueueSummary> {
-    let item_ids = normalize_localization_batch_item_ids(request.item_ids)?;
-    if item_ids.is_empty() {
-        return Err(EngineError::InstallFailed(
-            "choose at least one item for batch dubbing".to_string(),
-        ));
+    item_id: String,
+    source_track_id: String,
+) -> Result<JobRow> {
+    let track = subtitle_tracks::get_track(paths, &source_track_id)?;
+    if track.item_id != item_id {
+        return Err(EngineError::InstallFailed(format!(
+            "subtitle track {source_track_id} does not belong to item {item_id}"
+        )));
     }
-    let batch_id = Uuid::new_v4().to_string();
-    let mut items: Vec<LocalizationBatchItemResult> = Vec::new();
-    let separation_backend = normalize_separation_backend(request.separation_backend.as_deref());
+    let params_json = serde_json::to_string(&SyncSubtitlesV1Params {
+        item_id: item_id.clone(),
+        source_track_id,
+    })?;
+    enqueue_with_type_and_item_id(paths, JobType::SyncSubtitlesV1, params_json, Some(item_id))
+}
+
+/// Queues ASR -> translate -> TTS -> separate -> mix -> mux for one item under a fresh batch.
+pub fn enqueue_full_pipeline_v1(
+    paths: &AppPaths,
+    item_id: String,
+    asr_lang: Option<String>,
+    model_id: Option<String>,
+    tts_backend: Option<String>,
+    ducking_strength: Option<f32>,
+) -> Result<FullPipelineResult> {
+    let item = library::get_item_by_id(paths, item_id.trim())?;
+    let tts_backend = normalize_non_empty(tts_backend.as_deref());
+    if let Some(backend) = tts_backend.as_deref() {
+        if ![
+            config::BATCH_ON_IMPORT_TTS_BACKEND_PYTTSX3_V1,
+            config::BATCH_ON_IMPORT_TTS_BACKEND_NEURAL_LOCAL_V1,
+            config::BATCH_ON_IMPORT_TTS_BACKEND_VOICE_PRESERVING_V1,
+        ]
+        .contains(&backend)
+        {
+            return Err(EngineError::InstallFailed(format!(
+                "unknown TTS backend for full pipeline: {backend}"
+            )));
+        }
+    }
+    if let Some(strength) = ducking_strength {
+        if !(0.0..=1.0).contains(&strength) {
+            return Err(EngineError::InstallFailed(format!(
+                "ducking_strength must be between 0.0 and 1.0, got {strength}"
+            )));
+        }
+    }
+
+    let batch_id = Uuid::new_v4().to_string();
+    let params_json = serde_json::to_string(&FullPipelineV1Params {
+        item_id: item.id.clone(),
+        asr_lang: normalize_non_empty(asr_lang.as_deref()).filter(|v| v != "auto"),
+        model_id: normalize_non_empty(model_id.as_deref())
+            .unwrap_or_else(|| "whispercpp-tiny".to_string()),
+        tts_backend,
+        ducking_strength,
+    })?;
+    let head = enqueue_with_type_item_and_batch_id(
+        paths,
+        JobType::FullPipelineV1,
+        params_json,
+        Some(item.id),
+        Some(batch_id.clone()),
+        None,
+    )?;
+    Ok(FullPipelineResult {
+        batch_id,
+        jobs: vec![head],
+    })
+}
+
+pub fn enqueue_qc_report_v1(paths: &AppPaths, item_id: String, track_id: String) -> Result<JobRow> {
+    let params_json = serde_json::to_string(&QcReportV1Params {
+        item_id: item_id.clone(),
+        track_id,
+        variant_label: None,
+    })?;
+    enqueue_with_type_and_item_id(paths, JobType::QcReportV1, params_json, Some(item_id))
+}
+
+pub fn enqueue_qc_report_v1_with_variant(
+    paths: &AppPaths,
+    item_id: String,
+    track_id: String,
+    variant_label: Option<String>,
+) -> Result<JobRow> {
+    let params_json = serde_json::to_string(&QcReportV1Params {
+        item_id: item_id.clone(),
+        track_id,
+        variant_label,
+    })?;
+    enqueue_with_type_and_item_id(paths, JobType::QcReportV1, params_json, Some(item_id))
+}
+
+pub fn enqueue_export_pack_v1(paths: &AppPaths, item_id: String) -> Result<JobRow> {
+    let params_json = serde_json::to_string(&ExportPackV1Params {
+        item_id: item_id.clone(),
+        include_alternates: true,
+        variant_label: None,
+        include_subtitles: true,
+    })?;
+    enqueue_with_type_and_item_id(paths, JobType::ExportPackV1, params_json, Some(item_id))
+}
+
+pub fn enqueue_export_multi_pack_v1(
+    paths: &AppPaths,
+    item_ids: Vec<String>,
+    out_path: String,
+) -> Result<JobRow> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let item_ids = item_ids
+        .into_iter()
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty() && seen.insert(value.clone()))
+        .collect::<Vec<_>>();
+    if item_ids.is_empty() {
+        return Err(EngineError::InstallFailed(
+            "choose at least one item to export".to_string(),
+        ));
+    }
+    if item_ids.len() > EXPORT_MULTI_PACK_MAX_ITEMS {
+        return Err(EngineError::InstallFailed(format!(
+            "too many items for one export pack ({}; max {EXPORT_MULTI_PACK_MAX_ITEMS})",
+            item_ids.len()
+        )));
+    }
+    let out_path = out_path.trim().to_string();
+    if out_path.is_empty() {
+        return Err(EngineError::InstallFailed("out_path is empty".to_string()));
+    }
+    for item_id in &item_ids {
+        library::get_item_by_id(paths, item_id)?;
+    }
+
+    let params_json = serde_json::to_string(&ExportMultiPackV1Params { item_ids, out_path })?;
+    enqueue_with_type_and_item_id(paths, JobType::ExportMultiPackV1, params_json, None)
+}
+
+pub const ASR_BATCH_MAX_ITEMS: usize = 200;
+
+#[derive(Debug, Clone, Serialize)]
+pub struct AsrBatchEnqueueResult {
+    pub enqueued: Vec<JobRow>,
+    /// Items skipped because they already have a source subtitle track.
+    pub skipped_already_transcribed: Vec<String>,
+}
+
+/// Queues one `asr_local` job per item under a shared batch id, skipping items that already
+/// have a source subtitle track.
+pub fn enqueue_asr_local_batch(
+    paths: &AppPaths,
+    item_ids: Vec<String>,
+    lang: Option<String>,
+    model_id: Option<String>,
+) -> Result<AsrBatchEnqueueResult> {
+    let mut seen: HashSet<String> = HashSet::new();
+    let item_ids: Vec<String> = item_ids
+        .into_iter()
+        .map(|id| id.trim().to_string())
+        .filter(|id| !id.is_empty() && seen.insert(id.clone()))
+        .collect();
+    if item_ids.is_empty() {
+        return Err(EngineError::InstallFailed(
+            "choose at least one item for batch ASR".to_string(),
+        ));
+    }
+    if item_ids.len() > ASR_BATCH_MAX_ITEMS {
+        return Err(EngineError::InstallFailed(format!(
+            "batch ASR supports at most {ASR_BATCH_MAX_ITEMS} items per submission"
+        )));
+    }
+    let lang = normalize_asr_lang(lang);
+    let model_id = model_id
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty())
+        .unwrap_or_else(|| "whispercpp-tiny".to_string());
+    let task = normalize_asr_task(None)?;
+
+    let mut jobs = Vec::new();
+    let mut skipped_already_transcribed = Vec::new();
+    for item_id in item_ids {
+        library::get_item_by_id(paths, &item_id)?;
+        let has_source_track = subtitle_tracks::list_tracks(paths, &item_id)?
+            .iter()
+            .any(|track| track.kind == "source");
+        if has_source_track {
+            skipped_already_transcribed.push(item_id);
+            continue;
+        }
+        let params_json = serde_json::to_string(&AsrLocalParams {
+            item_id: item_id.clone(),
+            lang: lang.clone(),
+            model_id: model_id.clone(),
+            batch_on_import: false,
+            pipeline: None,
+            task: task.clone(),
+        })?;
+        jobs.push((JobType::AsrLocal, params_json, Some(item_id), None));
+    }
+
+    let enqueued = if jobs.is_empty() {
+        Vec::new()
+    } else {
+        let conn = db::open(paths)?;
+        db::migrate(&conn)?;
+        enqueue_batch_in_transaction(&conn, paths, jobs, &Uuid::new_v4().to_string(), None)?
+    };
+    Ok(AsrBatchEnqueueResult {
+        enqueued,
+        skipped_already_transcribed,
+    })
+}
+
+pub fn enqueue_localization_batch_v1(
+    paths: &AppPaths,
+    request: LocalizationBatchRequest,
+) -> Result<LocalizationBatchQueueSummary> {
+    let item_ids = normalize_localization_batch_item_ids(request.item_ids)?;
+    if item_ids.is_empty() {
+        return Err(EngineError::InstallFailed(
+            "choose at least one item for batch dubbing".to_string(),
+        ));
+    }
+    let batch_id = Uuid::new_v4().to_string();
+    let mut items: Vec<LocalizationBatchItemResult> = Vec::new();
+    let separation_backend = normalize_separation_backend(request.separation_backend.as_deref());
 
     for item_id in item_ids {
         let item = library::get_item_by_id(paths, &item_id)?;
@@ -2061,6 +3877,7 @@ fn queue_voice_setup_for_localization(
 ) -> Result<LocalizationContinuationOutcome> {
     let params_json = serde_json::to_string(&InstallPhase2PacksV1Params {
         resume_localization_run: Some(localization_resume_request_for_dub(&item.id, pipeline)),
+        ..Default::default()
     })?;
     let queued_job = enqueue_with_type_item_and_batch_id(
         paths,
@@ -2068,6 +3885,7 @@ fn queue_voice_setup_for_localization(
         params_json,
         Some(item.id.clone()),
         batch_id,
+        None,
     )?;
     notes.push(
         "Voice cloning needs a one-time setup. VoxVulgi queued it and will continue this localization run automatically when setup finishes."
@@ -2110,6 +3928,7 @@ fn queue_dub_or_voice_setup_for_localization(
         item_id: item.id.clone(),
         source_track_id: track.id.clone(),
         batch_on_import: false,
+        auto_extract_profiles: false,
         pipeline: Some(LocalizationPipelineOptions {
             source_track_id: Some(track.id.clone()),
             ..pipeline
@@ -2121,6 +3940,7 @@ fn queue_dub_or_voice_setup_for_localization(
         params_json,
         Some(item.id.clone()),
         batch_id,
+        None,
     )?;
     notes.push("VoxVulgi queued the dubbing pipeline.".to_string());
     Ok(LocalizationContinuationOutcome {
@@ -2190,6 +4010,7 @@ fn queue_localization_continuation_from_track(
                 params_json,
                 Some(item.id.clone()),
                 batch_id,
+                None,
             )?;
             Ok(LocalizationContinuationOutcome {
                 stage: "translate".to_string(),
@@ -2220,6 +4041,7 @@ fn queue_localization_continuation_from_track(
                 params_json,
                 Some(item.id.clone()),
                 batch_id,
+                None,
             )?;
             Ok(LocalizationContinuationOutcome {
                 stage: "diarize".to_string(),
@@ -2392,6 +4214,7 @@ pub fn enqueue_localization_run_v1(
         model_id: "whispercpp-tiny".to_string(),
         batch_on_import: false,
         pipeline: Some(pipeline),
+        task: None,
     })?;
     let queued_job = enqueue_with_type_item_and_batch_id(
         paths,
@@ -2399,6 +4222,7 @@ pub fn enqueue_localization_run_v1(
         params_json,
         Some(item_id.clone()),
         Some(batch_id.clone()),
+        None,
     )?;
     Ok(LocalizationRunQueueSummary {
         batch_id,
@@ -2540,6 +4364,7 @@ pub fn enqueue_voice_ab_preview_v1(
             item_id: item_id.clone(),
             source_track_id: source_track_id.clone(),
             batch_on_import: false,
+            auto_extract_profiles: false,
             pipeline: Some(LocalizationPipelineOptions {
                 auto_pipeline: true,
                 output_mode: Some("dub".to_string()),
@@ -2559,6 +4384,7 @@ pub fn enqueue_voice_ab_preview_v1(
             params_json,
             Some(item_id.clone()),
             Some(batch_id.clone()),
+            None,
         )?);
     }
 
@@ -2570,6 +4396,44 @@ pub fn enqueue_voice_ab_preview_v1(
     })
 }
 
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UrlValidationResult {
+    pub url: String,
+    pub valid: bool,
+    pub error: Option<String>,
+    pub normalized: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadBatchEnqueueResult {
+    pub queued: Vec<JobRow>,
+    pub skipped_invalid: Vec<UrlValidationResult>,
+}
+
+/// Checks each URL (inputs are split like batch submissions) without enqueueing anything.
+pub fn validate_urls(urls: Vec<String>) -> Vec<UrlValidationResult> {
+    split_url_inputs(urls)
+        .into_iter()
+        .map(|url| match normalize_direct_url(&url) {
+            Ok(normalized) => UrlValidationResult {
+                url,
+                valid: true,
+                error: None,
+                normalized: Some(normalized),
+            },
+            Err(err) => UrlValidationResult {
+                url,
+                valid: false,
+                error: Some(err.to_string()),
+                normalized: None,
+            },
+        })
+        .collect()
+}
+
+/// Invalid URLs are skipped and reported instead of failing the batch.
+/// `read_archive` names an existing yt-dlp archive whose ids are skipped before enqueueing;
+/// `write_archive` is passed to yt-dlp as `--download-archive`.
 pub fn enqueue_download_direct_url_batch(
     paths: &AppPaths,
     urls: Vec<String>,
@@ -2577,8 +4441,40 @@ pub fn enqueue_download_direct_url_batch(
     output_dir: Option<String>,
     use_browser_cookies: Option<bool>,
     preset_id: Option<String>,
-) -> Result<Vec<JobRow>> {
-    enqueue_download_direct_url_batch_raw(
+    output_filename_template: Option<String>,
+    schedule_after_ms: Option<i64>,
+    read_archive: Option<String>,
+    write_archive: Option<String>,
+) -> Result<DownloadBatchEnqueueResult> {
+    let schedule_after_ms = normalize_schedule_after_ms(schedule_after_ms)?;
+    let write_archive = normalize_non_empty(write_archive.as_deref());
+    let (valid, skipped_invalid): (Vec<_>, Vec<_>) =
+        validate_urls(urls).into_iter().partition(|v| v.valid);
+    let mut result = DownloadBatchEnqueueResult {
+        queued: Vec::new(),
+        skipped_invalid,
+    };
+    let urls: Vec<String> = valid.into_iter().filter_map(|v| v.normalized).collect();
+    if urls.is_empty() && !result.skipped_invalid.is_empty() {
+        return Ok(result);
+    }
+    let urls = match normalize_non_empty(read_archive.as_deref()) {
+        Some(read_archive) => {
+            let urls = skip_urls_in_download_archive(urls, Path::new(&read_archive))?;
+            if urls.is_empty() {
+                return Ok(result);
+            }
+            urls
+        }
+        None => urls,
+    };
+    let output_filename_template = output_filename_template
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    if let Some(template) = output_filename_template.as_deref() {
+        validate_yt_dlp_output_template(template)?;
+    }
+    result.queued = enqueue_download_direct_url_batch_raw_with_subscription(
         paths,
         urls,
         Some(DOWNLOAD_PROVIDER_DIRECT_HTTP.to_string()),
@@ -2587,7 +4483,32 @@ pub fn enqueue_download_direct_url_batch(
         use_browser_cookies,
         preset_id,
         None,
-    )
+        None,
+        output_filename_template,
+        schedule_after_ms,
+        write_archive,
+        None,
+    )?;
+    Ok(result)
+}
+
+/// Drops URLs whose YouTube video id or Instagram shortcode is listed in a yt-dlp archive file.
+fn skip_urls_in_download_archive(urls: Vec<String>, archive_path: &Path) -> Result<Vec<String>> {
+    if !archive_path.is_file() {
+        return Err(EngineError::InstallFailed(format!(
+            "download archive not found: {}",
+            archive_path.display()
+        )));
+    }
+    let archived = subscriptions::read_archive_file_ids(archive_path)?;
+    Ok(urls
+        .into_iter()
+        .filter(|url| {
+            let archive_id = subscriptions::youtube_video_id_from_url(url)
+                .or_else(|| instagram_shortcode_from_url(url));
+            !archive_id.is_some_and(|id| archived.contains(&id))
+        })
+        .collect())
 }
 
 pub fn enqueue_download_direct_url_batch_raw(
@@ -2610,6 +4531,10 @@ pub fn enqueue_download_direct_url_batch_raw(
         preset_id,
         batch_id,
         None,
+        None,
+        None,
+        None,
+        None,
     )
 }
 
@@ -2639,6 +4564,7 @@ pub fn enqueue_youtube_subscription_refresh_v1(
         params_json,
         None,
         batch_id.or_else(|| Some(Uuid::new_v4().to_string())),
+        None,
     )?;
 
     if let Some(cookie) = auth_cookie.as_deref() {
@@ -2662,6 +4588,10 @@ fn enqueue_download_direct_url_batch_raw_with_subscription(
     preset_id: Option<String>,
     batch_id: Option<String>,
     subscription_id: Option<String>,
+    output_filename_template: Option<String>,
+    schedule_after_ms: Option<i64>,
+    write_archive: Option<String>,
+    parent_job_id: Option<&str>,
 ) -> Result<Vec<JobRow>> {
     let auth_cookie = normalize_auth_cookie(auth_cookie)?;
     let output_dir = normalize_output_dir(output_dir);
@@ -2701,6 +4631,10 @@ fn enqueue_download_direct_url_batch_raw_with_subscription(
         &preset,
         batch_id,
         subscription_id,
+        output_filename_template,
+        schedule_after_ms,
+        write_archive,
+        parent_job_id,
     )
 }
 
@@ -2713,10 +4647,22 @@ fn enqueue_download_targets_batch_with_subscription(
     preset: &config::DownloadPreset,
     batch_id: Option<String>,
     subscription_id: Option<String>,
+    output_filename_template: Option<String>,
+    schedule_after_ms: Option<i64>,
+    write_archive: Option<String>,
+    parent_job_id: Option<&str>,
 ) -> Result<Vec<JobRow>> {
-    let batch_id = batch_id.or_else(|| Some(Uuid::new_v4().to_string()));
-    let mut jobs: Vec<JobRow> = Vec::with_capacity(targets.len());
+    let batch_id = batch_id.unwrap_or_else(|| Uuid::new_v4().to_string());
+    let mut pending: Vec<(JobType, String, Option<String>, Option<String>)> =
+        Vec::with_capacity(targets.len());
+    let mut seen_media_ids: HashSet<String> = HashSet::new();
     for target in targets {
+        let media_id = instagram_media_id_from_url(&target.url);
+        if let Some(id) = media_id.as_ref() {
+            if !seen_media_ids.insert(id.clone()) {
+                continue;
+            }
+        }
         let params_json = serde_json::to_string(&DownloadDirectUrlParams {
             url: target.url,
             provider: target.provider.to_string(),
@@ -2731,26 +4677,31 @@ fn enqueue_download_targets_batch_with_subscription(
             format_preference: preset.format_preference.clone(),
             quality_preference: preset.quality_preference.clone(),
             subtitle_mode: preset.subtitle_mode.clone(),
+            output_filename_template: output_filename_template.clone(),
+            schedule_after_ms,
+            media_id,
+            write_archive: write_archive.clone(),
+            write_info_json: true,
         })?;
-        let job = enqueue_with_type_item_and_batch_id(
-            paths,
-            JobType::DownloadDirectUrl,
-            params_json,
-            None,
-            batch_id.clone(),
-        )?;
+        pending.push((JobType::DownloadDirectUrl, params_json, None, None));
+    }
 
-        if let Some(cookie) = auth_cookie.as_deref() {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let jobs = enqueue_batch_in_transaction(&conn, paths, pending, &batch_id, parent_job_id)?;
+
+    if let Some(cookie) = auth_cookie.as_deref() {
+        for (idx, job) in jobs.iter().enumerate() {
             if let Err(err) = write_job_cookie_secret(paths, &job.id, cookie) {
-                let _ = delete_job_by_id(paths, &job.id);
                 for queued in &jobs {
                     let _ = delete_job_by_id(paths, &queued.id);
+                }
+                for queued in &jobs[..idx] {
                     let _ = remove_job_cookie_secret(paths, &queued.id);
                 }
                 return Err(err);
             }
         }
-        jobs.push(job);
     }
 
     Ok(jobs)
@@ -2795,31 +4746,69 @@ pub fn enqueue_download_instagram_batch(
     )
 }
 
+/// Crawl and output settings for [`enqueue_download_image_batch`]. `None` fields fall back to the
+/// image batch defaults.
+#[derive(Debug, Clone, Default)]
+pub struct DownloadImageBatchOptions {
+    pub max_pages: Option<usize>,
+    pub delay_ms: Option<u64>,
+    pub allow_cross_domain: Option<bool>,
+    pub follow_content_links: Option<bool>,
+    pub skip_url_keywords: Vec<String>,
+    pub output_subdir: Option<String>,
+    pub output_dir: Option<String>,
+    pub auth_cookie: Option<String>,
+    pub max_hamming_distance: Option<u8>,
+    pub max_image_bytes: Option<u64>,
+    pub schedule_after_ms: Option<i64>,
+    pub file_extensions_filter: Option<Vec<String>>,
+    pub min_image_pixels: Option<u64>,
+    pub max_image_pixels: Option<u64>,
+}
+
 pub fn enqueue_download_image_batch(
     paths: &AppPaths,
     start_urls: Vec<String>,
-    max_pages: Option<usize>,
-    delay_ms: Option<u64>,
-    allow_cross_domain: Option<bool>,
-    follow_content_links: Option<bool>,
-    skip_url_keywords: Vec<String>,
-    output_subdir: Option<String>,
-    output_dir: Option<String>,
-    auth_cookie: Option<String>,
+    options: DownloadImageBatchOptions,
 ) -> Result<JobRow> {
-    let had_explicit_subdir = output_subdir
-        .as_ref()
-        .map(|value| !value.trim().is_empty())
-        .unwrap_or(false);
-    let req = image_batch::build_image_batch_request(
-        start_urls,
+    let DownloadImageBatchOptions {
         max_pages,
         delay_ms,
         allow_cross_domain,
         follow_content_links,
         skip_url_keywords,
         output_subdir,
+        output_dir,
         auth_cookie,
+        max_hamming_distance,
+        max_image_bytes,
+        schedule_after_ms,
+        file_extensions_filter,
+        min_image_pixels,
+        max_image_pixels,
+    } = options;
+    let schedule_after_ms = normalize_schedule_after_ms(schedule_after_ms)?;
+    let file_extensions_filter =
+        image_batch::normalize_file_extensions_filter(file_extensions_filter)?;
+    let (min_image_pixels, max_image_pixels) =
+        image_batch::normalize_image_pixel_bounds(min_image_pixels, max_image_pixels)?;
+    let had_explicit_subdir = output_subdir
+        .as_ref()
+        .map(|value| !value.trim().is_empty())
+        .unwrap_or(false);
+    let req = image_batch::build_image_batch_request(
+        start_urls,
+        image_batch::ImageBatchRequestOptions {
+            max_pages,
+            delay_ms,
+            allow_cross_domain,
+            follow_content_links,
+            skip_url_keywords,
+            output_subdir,
+            auth_cookie,
+            max_hamming_distance,
+            max_image_bytes,
+        },
     )?;
     let output_subdir = if had_explicit_subdir {
         req.output_subdir
@@ -2838,6 +4827,13 @@ pub fn enqueue_download_image_batch(
         output_subdir,
         output_dir,
         auth_cookie: None,
+        max_hamming_distance: Some(req.max_hamming_distance),
+        max_image_bytes: Some(req.max_image_bytes),
+        file_extensions_filter: (!file_extensions_filter.is_empty())
+            .then_some(file_extensions_filter),
+        min_image_pixels,
+        max_image_pixels,
+        schedule_after_ms,
     })?;
     let job = enqueue_with_type_item_and_batch_id(
         paths,
@@ -2845,6 +4841,7 @@ pub fn enqueue_download_image_batch(
         params_json,
         None,
         Some(Uuid::new_v4().to_string()),
+        None,
     )?;
 
     if let Some(cookie) = req.auth_cookie.as_deref() {
@@ -2858,10 +4855,15 @@ pub fn enqueue_download_image_batch(
     Ok(job)
 }
 
-pub fn list_jobs(paths: &AppPaths, limit: usize, offset: usize) -> Result<Vec<JobRow>> {
+/// Jobs enqueued by `parent_job_id`, oldest first.
+pub fn list_child_jobs(
+    paths: &AppPaths,
+    parent_job_id: &str,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<JobRow>> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
-
     let mut stmt = conn.prepare(
         r#"
 SELECT
@@ -2876,47 +4878,59 @@ SELECT
   started_at_ms,
   finished_at_ms,
   logs_path,
-  params_json
+  params_json,
+  requeue_count,
+  content_key,
+  not_before_ms,
+  on_complete_json,
+  worker_id,
+  parent_job_id,
+  progress_steps_json
 FROM job
-ORDER BY created_at_ms DESC
-LIMIT ?1 OFFSET ?2
+WHERE parent_job_id=?1
+ORDER BY created_at_ms ASC, rowid ASC
+LIMIT ?2 OFFSET ?3
 "#,
     )?;
-
     let rows = stmt
-        .query_map(params![limit as i64, offset as i64], |row| {
-            let status_str: String = row.get(4)?;
-            let status = JobStatus::from_str(&status_str).unwrap_or(JobStatus::Failed);
-            Ok(JobRow {
-                id: row.get(0)?,
-                item_id: row.get(1)?,
-                batch_id: row.get(2)?,
-                job_type: row.get(3)?,
-                status,
-                progress: row.get(5)?,
-                error: row.get(6)?,
-                created_at_ms: row.get(7)?,
-                started_at_ms: row.get(8)?,
-                finished_at_ms: row.get(9)?,
-                logs_path: row.get(10)?,
-                params_json: row.get(11)?,
-            })
-        })?
+        .query_map(
+            params![parent_job_id, limit as i64, offset as i64],
+            job_row_from_query_row,
+        )?
         .collect::<rusqlite::Result<Vec<_>>>()?;
-
     Ok(rows)
 }
 
-pub fn get_job(paths: &AppPaths, job_id: &str) -> Result<Option<JobRow>> {
-    let job_id = job_id.trim();
-    if job_id.is_empty() {
-        return Ok(None);
-    }
-
+/// Newest jobs first; `item_id` and `job_type` narrow the list when given.
+pub fn list_jobs(
+    paths: &AppPaths,
+    limit: usize,
+    offset: usize,
+    item_id: Option<&str>,
+    job_type: Option<&str>,
+) -> Result<Vec<JobRow>> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
 
-    conn.query_row(
+    let mut filters: Vec<&str> = Vec::new();
+    let mut args: Vec<rusqlite::types::Value> = Vec::new();
+    if let Some(item_id) = item_id.map(str::trim).filter(|v| !v.is_empty()) {
+        filters.push("item_id=?");
+        args.push(item_id.to_string().into());
+    }
+    if let Some(job_type) = job_type.map(str::trim).filter(|v| !v.is_empty()) {
+        filters.push("type=?");
+        args.push(job_type.to_string().into());
+    }
+    let where_clause = if filters.is_empty() {
+        String::new()
+    } else {
+        format!("WHERE {}\n", filters.join(" AND "))
+    };
+    args.push((limit as i64).into());
+    args.push((offset as i64).into());
+
+    let mut stmt = conn.prepare(&format!(
         r#"
 SELECT
   id,
@@ -2930,62 +4944,186 @@ SELECT
   started_at_ms,
   finished_at_ms,
   logs_path,
-  params_json
+  params_json,
+  requeue_count,
+  content_key,
+  not_before_ms,
+  on_complete_json,
+  worker_id,
+  parent_job_id,
+  progress_steps_json
 FROM job
-WHERE id=?1
-"#,
-        [job_id],
-        job_row_from_query_row,
-    )
-    .optional()
-    .map_err(Into::into)
-}
-
-pub fn active_youtube_subscription_refresh_ids(paths: &AppPaths) -> Result<HashSet<String>> {
-    let conn = db::open(paths)?;
-    db::migrate(&conn)?;
+{where_clause}ORDER BY created_at_ms DESC
+LIMIT ? OFFSET ?
+"#
+    ))?;
 
-    let mut stmt = conn.prepare(
-        r#"
-SELECT params_json FROM job
-WHERE type = ?1 AND status IN (?2, ?3)
-"#,
-    )?;
     let rows = stmt
-        .query_map(
+        .query_map(rusqlite::params_from_iter(args), job_row_from_query_row)?
+        .collect::<rusqlite::Result<Vec<JobRow>>>()?;
+
+    let now = now_ms();
+    let mut averages: HashMap<String, Option<i64>> = HashMap::new();
+    let rows = rows
+        .into_iter()
+        .map(|mut row| {
+            if let (JobStatus::Running, Some(started_at_ms)) = (&row.status, row.started_at_ms) {
+                let avg_ms = *averages
+                    .entry(row.job_type.clone())
+                    .or_insert_with(|| load_avg_job_duration_ms(&conn, &row.job_type));
+                row.estimate_remaining_ms =
+                    avg_ms.map(|avg| estimate_remaining_ms(avg, row.progress, now - started_at_ms));
+            }
+            row
+        })
+        .collect();
+
+    Ok(rows)
+}
+
+const JOB_DURATION_AVERAGE_RUNS: i64 = 10;
+
+fn avg_job_duration_meta_key(job_type: &str) -> String {
+    format!("avg_duration_{job_type}_ms")
+}
+
+fn load_avg_job_duration_ms(conn: &rusqlite::Connection, job_type: &str) -> Option<i64> {
+    conn.query_row(
+        "SELECT value FROM meta WHERE key=?1",
+        [avg_job_duration_meta_key(job_type)],
+        |row| row.get::<_, String>(0),
+    )
+    .optional()
+    .ok()
+    .flatten()
+    .and_then(|raw| raw.parse::<i64>().ok())
+    .filter(|avg| *avg > 0)
+}
+
+/// Refreshes the cached average duration of the last [`JOB_DURATION_AVERAGE_RUNS`] successful
+/// runs of `job_id`'s type.
+fn update_avg_job_duration(conn: &rusqlite::Connection, job_id: &str) -> Result<()> {
+    let job_type: String = conn.query_row("SELECT type FROM job WHERE id=?1", [job_id], |row| {
+        row.get(0)
+    })?;
+    let avg: Option<f64> = conn.query_row(
+        r#"
+SELECT AVG(finished_at_ms - started_at_ms)
+FROM (
+  SELECT finished_at_ms, started_at_ms
+  FROM job
+  WHERE type=?1 AND status=?2 AND started_at_ms IS NOT NULL AND finished_at_ms IS NOT NULL
+  ORDER BY finished_at_ms DESC
+  LIMIT ?3
+)
+"#,
+        params![
+            &job_type,
+            JobStatus::Succeeded.as_str(),
+            JOB_DURATION_AVERAGE_RUNS
+        ],
+        |row| row.get(0),
+    )?;
+    if let Some(avg) = avg {
+        conn.execute(
+            "INSERT INTO meta(key, value) VALUES(?1, ?2)
+             ON CONFLICT(key) DO UPDATE SET value=excluded.value",
             params![
-                JobType::YoutubeSubscriptionRefreshV1.as_str(),
-                JobStatus::Queued.as_str(),
-                JobStatus::Running.as_str(),
+                avg_job_duration_meta_key(&job_type),
+                (avg.round() as i64).max(0).to_string()
             ],
-            |row| row.get::<_, String>(0),
-        )?
-        .collect::<rusqlite::Result<Vec<_>>>()?;
+        )?;
+    }
+    Ok(())
+}
 
-    let mut ids = HashSet::new();
-    for params_json in &rows {
-        if let Ok(p) = serde_json::from_str::<YoutubeSubscriptionRefreshV1Params>(params_json) {
-            ids.insert(p.subscription_id);
-        }
+/// Remaining time for a run: the average's share for the unfinished progress, shortened when the
+/// run has already used more of the average than its progress accounts for.
+fn estimate_remaining_ms(avg_ms: i64, progress: f32, elapsed_ms: i64) -> i64 {
+    let progress = f64::from(progress.clamp(0.0, 1.0));
+    let by_progress = (avg_ms as f64 * (1.0 - progress)).round() as i64;
+    by_progress.min(avg_ms - elapsed_ms.max(0)).max(0)
+}
+
+pub fn get_job(paths: &AppPaths, job_id: &str) -> Result<Option<JobRow>> {
+    let job_id = job_id.trim();
+    if job_id.is_empty() {
+        return Ok(None);
     }
-    Ok(ids)
+
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    conn.query_row(
+        r#"
+SELECT
+  id,
+  item_id,
+  batch_id,
+  type,
+  status,
+  progress,
+  error,
+  created_at_ms,
+  started_at_ms,
+  finished_at_ms,
+  logs_path,
+  params_json,
+  requeue_count,
+  content_key,
+  not_before_ms,
+  on_complete_json,
+  worker_id,
+  parent_job_id,
+  progress_steps_json
+FROM job
+WHERE id=?1
+"#,
+        [job_id],
+        job_row_from_query_row,
+    )
+    .optional()
+    .map_err(Into::into)
 }
 
-pub fn list_jobs_for_item(
-    paths: &AppPaths,
-    item_id: &str,
-    limit: usize,
-    offset: usize,
-) -> Result<Vec<JobRow>> {
-    let item_id = item_id.trim();
-    if item_id.is_empty() {
-        return Err(EngineError::InstallFailed("item_id is empty".to_string()));
+/// Like [`get_job`], but a missing job is an error.
+pub fn get_job_by_id(paths: &AppPaths, job_id: &str) -> Result<JobRow> {
+    get_job(paths, job_id)?.ok_or_else(|| EngineError::not_found("job", job_id.trim()))
+}
+
+/// Maximum number of ids accepted by [`get_jobs_by_ids`].
+pub const JOBS_GET_BATCH_MAX: usize = 50;
+
+/// Fetches the given jobs in one query, in request order. Unknown ids are skipped.
+pub fn get_jobs_by_ids(paths: &AppPaths, job_ids: &[String]) -> Result<Vec<JobRow>> {
+    let mut ids: Vec<&str> = Vec::new();
+    for id in job_ids
+        .iter()
+        .map(|id| id.trim())
+        .filter(|id| !id.is_empty())
+    {
+        if !ids.contains(&id) {
+            ids.push(id);
+        }
+    }
+    if ids.len() > JOBS_GET_BATCH_MAX {
+        return Err(EngineError::InstallFailed(format!(
+            "too many job ids: {} (max {JOBS_GET_BATCH_MAX})",
+            ids.len()
+        )));
+    }
+    if ids.is_empty() {
+        return Ok(Vec::new());
     }
 
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
 
-    let mut stmt = conn.prepare(
+    let placeholders = (1..=ids.len())
+        .map(|i| format!("?{i}"))
+        .collect::<Vec<_>>()
+        .join(", ");
+    let mut stmt = conn.prepare(&format!(
         r#"
 SELECT
   id,
@@ -2999,36 +5137,69 @@ SELECT
   started_at_ms,
   finished_at_ms,
   logs_path,
-  params_json
+  params_json,
+  requeue_count,
+  content_key,
+  not_before_ms,
+  on_complete_json,
+  worker_id,
+  parent_job_id,
+  progress_steps_json
 FROM job
-WHERE item_id=?1
-ORDER BY created_at_ms DESC
-LIMIT ?2 OFFSET ?3
+WHERE id IN ({placeholders})
+"#
+    ))?;
+    let mut rows: HashMap<String, JobRow> = stmt
+        .query_map(
+            rusqlite::params_from_iter(ids.iter()),
+            job_row_from_query_row,
+        )?
+        .map(|row| row.map(|job| (job.id.clone(), job)))
+        .collect::<rusqlite::Result<_>>()?;
+    Ok(ids.iter().filter_map(|id| rows.remove(*id)).collect())
+}
+
+pub fn active_youtube_subscription_refresh_ids(paths: &AppPaths) -> Result<HashSet<String>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    let mut stmt = conn.prepare(
+        r#"
+SELECT params_json FROM job
+WHERE type = ?1 AND status IN (?2, ?3)
 "#,
     )?;
-
     let rows = stmt
-        .query_map(params![item_id, limit as i64, offset as i64], |row| {
-            let status_str: String = row.get(4)?;
-            let status = JobStatus::from_str(&status_str).unwrap_or(JobStatus::Failed);
-            Ok(JobRow {
-                id: row.get(0)?,
-                item_id: row.get(1)?,
-                batch_id: row.get(2)?,
-                job_type: row.get(3)?,
-                status,
-                progress: row.get(5)?,
-                error: row.get(6)?,
-                created_at_ms: row.get(7)?,
-                started_at_ms: row.get(8)?,
-                finished_at_ms: row.get(9)?,
-                logs_path: row.get(10)?,
-                params_json: row.get(11)?,
-            })
-        })?
+        .query_map(
+            params![
+                JobType::YoutubeSubscriptionRefreshV1.as_str(),
+                JobStatus::Queued.as_str(),
+                JobStatus::Running.as_str(),
+            ],
+            |row| row.get::<_, String>(0),
+        )?
         .collect::<rusqlite::Result<Vec<_>>>()?;
 
-    Ok(rows)
+    let mut ids = HashSet::new();
+    for params_json in &rows {
+        if let Ok(p) = serde_json::from_str::<YoutubeSubscriptionRefreshV1Params>(params_json) {
+            ids.insert(p.subscription_id);
+        }
+    }
+    Ok(ids)
+}
+
+pub fn list_jobs_for_item(
+    paths: &AppPaths,
+    item_id: &str,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<JobRow>> {
+    let item_id = item_id.trim();
+    if item_id.is_empty() {
+        return Err(EngineError::InstallFailed("item_id is empty".to_string()));
+    }
+    list_jobs(paths, limit, offset, Some(item_id), None)
 }
 
 pub fn get_queue_control(paths: &AppPaths) -> Result<JobQueueControlState> {
@@ -3047,6 +5218,24 @@ pub fn get_runtime_settings(paths: &AppPaths) -> Result<JobRuntimeSettings> {
     })
 }
 
+/// Snapshot of the queue; `running_count` comes from the live runner (see
+/// [`JobRunnerHandle::running_count`]).
+pub fn get_runtime_state(paths: &AppPaths, running_count: usize) -> Result<JobRuntimeState> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let queued_count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM job WHERE status=?1",
+        params![JobStatus::Queued.as_str()],
+        |row| row.get(0),
+    )?;
+    Ok(JobRuntimeState {
+        running_count,
+        queued_count: queued_count.max(0) as usize,
+        paused: is_queue_paused_conn(&conn)?,
+        max_concurrency: get_max_concurrency_conn(&conn)?,
+    })
+}
+
 pub fn set_runtime_max_concurrency(
     paths: &AppPaths,
     max_concurrency: usize,
@@ -3099,6 +5288,7 @@ pub fn cancel_job(paths: &AppPaths, job_id: &str) -> Result<()> {
     if updated == 0 {
         return Ok(());
     }
+    let _ = close_open_progress_steps(paths, job_id, PROGRESS_STEP_STATUS_CANCELED);
 
     if let Some((job_type, Some(batch_id))) = job_context {
         if job_type == JobType::ImportLocal.as_str() && !batch_id.trim().is_empty() {
@@ -3121,12 +5311,23 @@ WHERE batch_id=?3 AND id<>?4 AND status IN (?5, ?6)
     }
 
     remove_job_cookie_secret(paths, job_id);
+    let _ = report_batch_completion(paths, job_id);
     Ok(())
 }
 
 pub fn cancel_all_jobs(paths: &AppPaths) -> Result<usize> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT DISTINCT batch_id FROM job WHERE batch_id IS NOT NULL AND status IN (?1, ?2)",
+    )?;
+    let batch_ids = stmt
+        .query_map(
+            params![JobStatus::Queued.as_str(), JobStatus::Running.as_str()],
+            |row| row.get::<_, String>(0),
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    drop(stmt);
     let updated = conn.execute(
         "UPDATE job SET status=?1, finished_at_ms=?2 WHERE status IN (?3, ?4)",
         params![
@@ -3140,6 +5341,9 @@ pub fn cancel_all_jobs(paths: &AppPaths) -> Result<usize> {
     if updated > 0 {
         let _ = clear_dir_entries(&paths.job_secrets_dir());
     }
+    for batch_id in &batch_ids {
+        let _ = report_batch_completion_for(paths, batch_id);
+    }
     Ok(updated)
 }
 
@@ -3149,6 +5353,8 @@ struct TerminalJobCleanupRecord {
     job_type: String,
     params_json: String,
     logs_path: String,
+    /// `None` when a queued or running job still shares the artifacts dir.
+    artifacts_dir: Option<PathBuf>,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -3175,7 +5381,7 @@ struct JobCleanupPlan {
 }
 
 pub fn preview_jobs_cleanup(paths: &AppPaths) -> Result<JobCleanupPreview> {
-    let plan = build_job_cleanup_plan(paths)?;
+    let plan = build_job_cleanup_plan(paths, &JobCleanupOptions::default())?;
     Ok(JobCleanupPreview {
         terminal_job_count: plan.terminal_jobs.len(),
         log_file_count: plan.log_file_count,
@@ -3239,57 +5445,158 @@ pub fn flush_jobs_cache(
     paths: &AppPaths,
     options: Option<JobCleanupOptions>,
 ) -> Result<JobCleanupSummary> {
-    let plan = build_job_cleanup_plan(paths)?;
     let options = options.unwrap_or_default();
+    let plan = build_job_cleanup_plan(paths, &options)?;
+    let flush_cache = !options.has_job_filter();
+
+    let log_bytes: u64 = plan
+        .terminal_jobs
+        .iter()
+        .map(|job| job_log_files_size_bytes(Path::new(&job.logs_path)))
+        .sum();
+    // Jobs sharing a content key share one artifacts dir; count it once.
+    let unique_artifact_dirs: HashSet<PathBuf> = plan
+        .terminal_jobs
+        .iter()
+        .filter_map(|job| job.artifacts_dir.as_deref())
+        .map(|dir| std::fs::canonicalize(dir).unwrap_or_else(|_| dir.to_path_buf()))
+        .collect();
+    let artifact_bytes: u64 = unique_artifact_dirs
+        .iter()
+        .map(|dir| diagnostics::directory_size_bytes_best_effort(dir))
+        .sum();
+    let job_bytes = log_bytes + artifact_bytes;
+    let cache_bytes = if flush_cache {
+        diagnostics::directory_size_bytes_best_effort(&paths.cache_dir())
+    } else {
+        0
+    };
+    let output_dirs_bytes = |targets: &[CleanupOutputDirTargetInternal]| -> u64 {
+        targets
+            .iter()
+            .map(|t| diagnostics::directory_size_bytes_best_effort(&t.path))
+            .sum()
+    };
+    let managed_bytes = output_dirs_bytes(&plan.managed_output_dirs);
+    let external_bytes = output_dirs_bytes(&plan.external_output_dirs);
+    let available_to_remove_bytes = job_bytes + cache_bytes + managed_bytes + external_bytes;
+    let would_remove_bytes = job_bytes
+        + cache_bytes
+        + if options.remove_managed_output_dirs {
+            managed_bytes
+        } else {
+            0
+        }
+        + if options.remove_external_output_dirs {
+            external_bytes
+        } else {
+            0
+        };
+    let skipped_managed_output_dirs = if options.remove_managed_output_dirs {
+        0
+    } else {
+        plan.managed_output_dirs.len()
+    };
+    let skipped_external_output_dirs = if options.remove_external_output_dirs {
+        0
+    } else {
+        plan.external_output_dirs.len()
+    };
+
+    if options.dry_run {
+        return Ok(JobCleanupSummary {
+            removed_jobs: plan.terminal_jobs.len(),
+            kept_jobs_due_to_failures: 0,
+            removed_log_files: plan.log_file_count,
+            removed_artifact_dirs: plan.artifact_dir_count,
+            removed_managed_output_dirs: plan.managed_output_dirs.len()
+                - skipped_managed_output_dirs,
+            removed_external_output_dirs: plan.external_output_dirs.len()
+                - skipped_external_output_dirs,
+            skipped_managed_output_dirs,
+            skipped_external_output_dirs,
+            removed_cache_entries: if flush_cache {
+                plan.cache_entry_count
+            } else {
+                0
+            },
+            failed_paths: Vec::new(),
+            dry_run: true,
+            would_remove_bytes,
+            available_to_remove_bytes,
+            freed_log_bytes: 0,
+            freed_artifact_bytes: 0,
+            freed_output_bytes: 0,
+            freed_cache_bytes: 0,
+            freed_bytes: 0,
+        });
+    }
+
     let mut failed_paths: Vec<JobCleanupFailure> = Vec::new();
     let mut failed_job_ids: HashSet<String> = HashSet::new();
 
     let mut removed_log_files = 0_usize;
+    let mut freed_log_bytes = 0_u64;
     for job in &plan.terminal_jobs {
         let log_path = PathBuf::from(&job.logs_path);
-        removed_log_files += remove_job_log_files_detailed(
+        let (removed, bytes) = remove_job_log_files_detailed(
             &log_path,
             &mut failed_paths,
             &mut failed_job_ids,
             Some(&job.job_id),
         );
+        removed_log_files += removed;
+        freed_log_bytes += bytes;
     }
 
     let mut removed_artifact_dirs = 0_usize;
+    let mut freed_artifact_bytes = 0_u64;
     for job in &plan.terminal_jobs {
-        let artifacts_dir = paths.job_artifacts_dir(&job.job_id);
+        let Some(artifacts_dir) = job.artifacts_dir.as_ref() else {
+            continue;
+        };
         if !artifacts_dir.exists() {
             continue;
         }
-        if remove_path_recursively(&artifacts_dir, "job_artifacts", &mut failed_paths).is_ok() {
+        let bytes = path_size_bytes_best_effort(artifacts_dir);
+        if remove_path_recursively(artifacts_dir, "job_artifacts", &mut failed_paths).is_ok() {
             removed_artifact_dirs += 1;
+            freed_artifact_bytes += bytes;
         } else {
             failed_job_ids.insert(job.job_id.clone());
         }
     }
 
     let mut removed_managed_output_dirs = 0_usize;
+    let mut freed_output_bytes = 0_u64;
     if options.remove_managed_output_dirs {
-        removed_managed_output_dirs = remove_output_dir_targets(
+        let (removed, bytes) = remove_output_dir_targets(
             &plan.managed_output_dirs,
             "managed_output_dir",
             &mut failed_paths,
             &mut failed_job_ids,
         );
+        removed_managed_output_dirs = removed;
+        freed_output_bytes += bytes;
     }
 
     let mut removed_external_output_dirs = 0_usize;
     if options.remove_external_output_dirs {
-        removed_external_output_dirs = remove_output_dir_targets(
+        let (removed, bytes) = remove_output_dir_targets(
             &plan.external_output_dirs,
             "external_output_dir",
             &mut failed_paths,
             &mut failed_job_ids,
         );
+        removed_external_output_dirs = removed;
+        freed_output_bytes += bytes;
     }
 
-    let removed_cache_entries =
-        clear_dir_entries_detailed(&paths.cache_dir(), "cache_entry", &mut failed_paths)?;
+    let (removed_cache_entries, freed_cache_bytes) = if flush_cache {
+        clear_dir_entries_detailed(&paths.cache_dir(), "cache_entry", &mut failed_paths)?
+    } else {
+        (0, 0)
+    };
 
     let removable_job_ids: Vec<String> = plan
         .terminal_jobs
@@ -3302,6 +5609,9 @@ pub fn flush_jobs_cache(
         .len()
         .saturating_sub(removable_job_ids.len());
     let removed_jobs = delete_terminal_jobs_by_ids(paths, &removable_job_ids)?;
+    if let Ok(conn) = db::open(paths) {
+        let _ = db::wal_checkpoint_truncate(&conn);
+    }
 
     Ok(JobCleanupSummary {
         removed_jobs,
@@ -3310,18 +5620,21 @@ pub fn flush_jobs_cache(
         removed_artifact_dirs,
         removed_managed_output_dirs,
         removed_external_output_dirs,
-        skipped_managed_output_dirs: if options.remove_managed_output_dirs {
-            0
-        } else {
-            plan.managed_output_dirs.len()
-        },
-        skipped_external_output_dirs: if options.remove_external_output_dirs {
-            0
-        } else {
-            plan.external_output_dirs.len()
-        },
+        skipped_managed_output_dirs,
+        skipped_external_output_dirs,
         removed_cache_entries,
         failed_paths,
+        dry_run: false,
+        would_remove_bytes,
+        available_to_remove_bytes,
+        freed_log_bytes,
+        freed_artifact_bytes,
+        freed_output_bytes,
+        freed_cache_bytes,
+        freed_bytes: freed_log_bytes
+            + freed_artifact_bytes
+            + freed_output_bytes
+            + freed_cache_bytes,
     })
 }
 
@@ -3342,13 +5655,15 @@ pub fn clear_failed_jobs_for_item(
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
 
-    let mut stmt = conn.prepare("SELECT id, logs_path FROM job WHERE item_id=?1 AND status=?2")?;
-    let failed: Vec<(String, String)> = stmt
+    let mut stmt =
+        conn.prepare("SELECT id, logs_path, content_key FROM job WHERE item_id=?1 AND status=?2")?;
+    let failed: Vec<(String, String, Option<String>)> = stmt
         .query_map(params![item_id, JobStatus::Failed.as_str()], |row| {
-            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
         })?
         .collect::<rusqlite::Result<Vec<_>>>()?;
     drop(stmt);
+    let active_content_keys = active_job_content_keys(&conn)?;
     drop(conn);
 
     let mut failed_paths: Vec<JobCleanupFailure> = Vec::new();
@@ -3356,21 +5671,28 @@ pub fn clear_failed_jobs_for_item(
 
     let mut removed_log_files = 0_usize;
     if options.remove_log_files {
-        for (job_id, logs_path) in &failed {
+        for (job_id, logs_path, _) in &failed {
             let log_path = PathBuf::from(logs_path);
             removed_log_files += remove_job_log_files_detailed(
                 &log_path,
                 &mut failed_paths,
                 &mut failed_job_ids,
                 Some(job_id),
-            );
+            )
+            .0;
         }
     }
 
     let mut removed_artifact_dirs = 0_usize;
     if options.purge_orphan_artifacts {
-        for (job_id, _) in &failed {
-            let artifacts_dir = paths.job_artifacts_dir(job_id);
+        for (job_id, _, content_key) in &failed {
+            if content_key
+                .as_ref()
+                .is_some_and(|key| active_content_keys.contains(key))
+            {
+                continue;
+            }
+            let artifacts_dir = paths.job_artifacts_dir(job_id, content_key.as_deref());
             if !artifacts_dir.exists() {
                 continue;
             }
@@ -3384,8 +5706,8 @@ pub fn clear_failed_jobs_for_item(
 
     let removable: Vec<String> = failed
         .iter()
-        .filter(|(id, _)| !failed_job_ids.contains(id))
-        .map(|(id, _)| id.clone())
+        .filter(|(id, _, _)| !failed_job_ids.contains(id))
+        .map(|(id, _, _)| id.clone())
         .collect();
     let removed_jobs = delete_terminal_jobs_by_ids(paths, &removable)?;
 
@@ -3398,7 +5720,7 @@ pub fn clear_failed_jobs_for_item(
     })
 }
 
-fn build_job_cleanup_plan(paths: &AppPaths) -> Result<JobCleanupPlan> {
+fn build_job_cleanup_plan(paths: &AppPaths, filter: &JobCleanupOptions) -> Result<JobCleanupPlan> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
 
@@ -3408,9 +5730,18 @@ fn build_job_cleanup_plan(paths: &AppPaths) -> Result<JobCleanupPlan> {
         JobStatus::Canceled.as_str(),
     ];
 
+    let active_content_keys = active_job_content_keys(&conn)?;
     let mut stmt = conn.prepare(
-        "SELECT id, type, params_json, logs_path FROM job WHERE status IN (?1, ?2, ?3) ORDER BY created_at_ms ASC",
+        "SELECT id, type, params_json, logs_path, content_key, COALESCE(finished_at_ms, created_at_ms) FROM job WHERE status IN (?1, ?2, ?3) ORDER BY created_at_ms ASC",
     )?;
+    let job_types: Option<HashSet<&str>> = filter
+        .job_types
+        .as_ref()
+        .filter(|v| !v.is_empty())
+        .map(|v| v.iter().map(|t| t.trim()).collect());
+    let finished_before_ms = filter
+        .older_than_days
+        .map(|days| now_ms().saturating_sub((days as i64).saturating_mul(86_400_000)));
     let terminal_jobs = stmt
         .query_map(
             params![
@@ -3423,15 +5754,34 @@ fn build_job_cleanup_plan(paths: &AppPaths) -> Result<JobCleanupPlan> {
                 let job_type: String = row.get(1)?;
                 let params_json: String = row.get(2)?;
                 let logs_path: String = row.get(3)?;
-                Ok(TerminalJobCleanupRecord {
-                    job_id: id,
-                    job_type,
-                    params_json,
-                    logs_path,
-                })
+                let content_key: Option<String> = row.get(4)?;
+                let finished_ms: i64 = row.get(5)?;
+                let artifacts_dir = match content_key.as_deref() {
+                    Some(key) if active_content_keys.contains(key) => None,
+                    key => Some(paths.job_artifacts_dir(&id, key)),
+                };
+                Ok((
+                    TerminalJobCleanupRecord {
+                        job_id: id,
+                        job_type,
+                        params_json,
+                        logs_path,
+                        artifacts_dir,
+                    },
+                    finished_ms,
+                ))
             },
         )?
-        .collect::<rusqlite::Result<Vec<_>>>()?;
+        .collect::<rusqlite::Result<Vec<_>>>()?
+        .into_iter()
+        .filter(|(job, finished_ms)| {
+            job_types
+                .as_ref()
+                .is_none_or(|types| types.contains(job.job_type.as_str()))
+                && finished_before_ms.is_none_or(|cutoff| *finished_ms < cutoff)
+        })
+        .map(|(job, _)| job)
+        .collect::<Vec<_>>();
     drop(stmt);
     drop(conn);
 
@@ -3441,14 +5791,15 @@ fn build_job_cleanup_plan(paths: &AppPaths) -> Result<JobCleanupPlan> {
     };
     let mut output_dirs: HashMap<PathBuf, CleanupOutputDirTargetInternal> = HashMap::new();
     let mut log_file_count = 0_usize;
-    let mut artifact_dir_count = 0_usize;
+    let mut artifact_dirs: HashSet<&Path> = HashSet::new();
 
     for job in &terminal_jobs {
         log_file_count += count_job_log_files(Path::new(&job.logs_path));
 
-        let artifacts_dir = paths.job_artifacts_dir(&job.job_id);
-        if artifacts_dir.exists() {
-            artifact_dir_count += 1;
+        if let Some(artifacts_dir) = job.artifacts_dir.as_deref() {
+            if artifacts_dir.exists() {
+                artifact_dirs.insert(artifacts_dir);
+            }
         }
 
         collect_output_dir_targets(
@@ -3474,6 +5825,7 @@ fn build_job_cleanup_plan(paths: &AppPaths) -> Result<JobCleanupPlan> {
     managed_output_dirs.sort_by(|a, b| a.path.cmp(&b.path));
     external_output_dirs.sort_by(|a, b| a.path.cmp(&b.path));
 
+    let artifact_dir_count = artifact_dirs.len();
     Ok(JobCleanupPlan {
         terminal_jobs,
         log_file_count,
@@ -3485,87 +5837,291 @@ fn build_job_cleanup_plan(paths: &AppPaths) -> Result<JobCleanupPlan> {
 }
 
 pub fn retry_job(paths: &AppPaths, job_id: &str) -> Result<JobRow> {
+    retry_job_with_options(paths, job_id, true, None)
+}
+
+/// Re-enqueues a job with identical params. `preserve_batch` keeps the original `batch_id`
+/// (otherwise the retry has none); `with_delay_ms` overrides the inherited `not_before_ms`.
+pub fn retry_job_with_options(
+    paths: &AppPaths,
+    job_id: &str,
+    preserve_batch: bool,
+    with_delay_ms: Option<u64>,
+) -> Result<JobRow> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
-
-    let (type_str, params_json, batch_id): (String, String, Option<String>) = conn.query_row(
-        "SELECT type, params_json, batch_id FROM job WHERE id=?1",
-        [job_id],
-        |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+    let source = load_retry_source(&conn, job_id)?;
+    let batch_id = if preserve_batch {
+        source.batch_id
+    } else {
+        None
+    };
+    let parent_job_id = job_parent_id(&conn, job_id)?;
+    let mut row = insert_queued_job(
+        &conn,
+        paths,
+        source.job_type,
+        source.params_json,
+        source.item_id,
+        batch_id,
+        parent_job_id,
     )?;
+    copy_on_complete_jobs(&conn, &mut row, source.on_complete_json)?;
+    if let Some(delay_ms) = with_delay_ms {
+        delay_queued_job(&conn, &mut row, delay_ms)?;
+    }
+    Ok(row)
+}
 
-    let job_type = JobType::from_str(&type_str)
-        .ok_or_else(|| EngineError::InstallFailed(format!("unknown job type in db: {type_str}")))?;
-
-    let item_id = match job_type {
-        JobType::AsrLocal => serde_json::from_str::<AsrLocalParams>(&params_json)
-            .ok()
-            .map(|p| p.item_id),
-        JobType::TranslateLocal => serde_json::from_str::<TranslateLocalParams>(&params_json)
-            .ok()
-            .map(|p| p.item_id),
-        JobType::DiarizeLocalV1 => serde_json::from_str::<DiarizeLocalV1Params>(&params_json)
-            .ok()
-            .map(|p| p.item_id),
-        JobType::TtsPreviewPyttsx3V1 => {
-            serde_json::from_str::<TtsPreviewPyttsx3V1Params>(&params_json)
-                .ok()
-                .map(|p| p.item_id)
-        }
-        JobType::TtsNeuralLocalV1 => serde_json::from_str::<TtsNeuralLocalV1Params>(&params_json)
-            .ok()
-            .map(|p| p.item_id),
+/// Retries the latest attempt of each distinct job in `batch_id` when it failed or was canceled,
+/// in one transaction. Without `preserve_batch` the retries share a new batch id.
+pub fn retry_batch(
+    paths: &AppPaths,
+    batch_id: &str,
+    preserve_batch: bool,
+    with_delay_ms: Option<u64>,
+) -> Result<Vec<JobRow>> {
+    let batch_id = batch_id.trim();
+    if batch_id.is_empty() {
+        return Err(EngineError::InstallFailed("batch id is empty".to_string()));
+    }
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let job_ids = {
+        let mut stmt = conn.prepare(
+            r#"
+SELECT id FROM job
+WHERE batch_id=?1
+  AND status IN (?2, ?3)
+  AND NOT EXISTS (
+    SELECT 1 FROM job AS newer
+    WHERE newer.batch_id=job.batch_id
+      AND newer.type=job.type
+      AND newer.params_json=job.params_json
+      AND newer.created_at_ms > job.created_at_ms
+  )
+ORDER BY created_at_ms ASC
+"#,
+        )?;
+        let rows = stmt.query_map(
+            params![
+                batch_id,
+                JobStatus::Failed.as_str(),
+                JobStatus::Canceled.as_str()
+            ],
+            |row| row.get::<_, String>(0),
+        )?;
+        rows.collect::<std::result::Result<Vec<_>, _>>()?
+    };
+    if job_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let retry_batch_id = if preserve_batch {
+        batch_id.to_string()
+    } else {
+        Uuid::new_v4().to_string()
+    };
+    let tx = conn.unchecked_transaction()?;
+    let mut out = Vec::with_capacity(job_ids.len());
+    for job_id in &job_ids {
+        let source = load_retry_source(&tx, job_id)?;
+        let parent_job_id = job_parent_id(&tx, job_id)?;
+        let mut row = insert_queued_job(
+            &tx,
+            paths,
+            source.job_type,
+            source.params_json,
+            source.item_id,
+            Some(retry_batch_id.clone()),
+            parent_job_id,
+        )?;
+        copy_on_complete_jobs(&tx, &mut row, source.on_complete_json)?;
+        if let Some(delay_ms) = with_delay_ms {
+            delay_queued_job(&tx, &mut row, delay_ms)?;
+        }
+        out.push(row);
+    }
+    tx.commit()?;
+    Ok(out)
+}
+
+fn delay_queued_job(conn: &rusqlite::Connection, row: &mut JobRow, delay_ms: u64) -> Result<()> {
+    let not_before_ms = now_ms().saturating_add(i64::try_from(delay_ms).unwrap_or(i64::MAX));
+    conn.execute(
+        "UPDATE job SET not_before_ms=?1 WHERE id=?2",
+        params![not_before_ms, &row.id],
+    )?;
+    row.not_before_ms = Some(not_before_ms);
+    Ok(())
+}
+
+/// Retries stay attached to the original job's parent.
+fn job_parent_id(conn: &rusqlite::Connection, job_id: &str) -> Result<Option<String>> {
+    Ok(conn
+        .query_row(
+            "SELECT parent_job_id FROM job WHERE id=?1",
+            [job_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .flatten())
+}
+
+/// What is needed to re-enqueue a job.
+struct RetrySource {
+    job_type: JobType,
+    params_json: String,
+    item_id: Option<String>,
+    batch_id: Option<String>,
+    on_complete_json: Option<String>,
+}
+
+fn load_retry_source(conn: &rusqlite::Connection, job_id: &str) -> Result<RetrySource> {
+    let (type_str, params_json, batch_id, on_complete_json): (
+        String,
+        String,
+        Option<String>,
+        Option<String>,
+    ) = conn
+        .query_row(
+            "SELECT type, params_json, batch_id, on_complete_json FROM job WHERE id=?1",
+            [job_id],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        )
+        .optional()?
+        .ok_or_else(|| EngineError::not_found("job", job_id))?;
+
+    let job_type = JobType::from_str(&type_str)
+        .ok_or_else(|| EngineError::InstallFailed(format!("unknown job type in db: {type_str}")))?;
+    let item_id = job_item_id_from_params(&job_type, &params_json);
+    Ok(RetrySource {
+        job_type,
+        params_json,
+        item_id,
+        batch_id,
+        on_complete_json,
+    })
+}
+
+/// Carries the original job's dependencies over to its retry.
+fn copy_on_complete_jobs(
+    conn: &rusqlite::Connection,
+    row: &mut JobRow,
+    on_complete_json: Option<String>,
+) -> Result<()> {
+    let Some(raw) = on_complete_json.filter(|raw| !raw.trim().is_empty()) else {
+        return Ok(());
+    };
+    let dependencies: Vec<JobDependency> = serde_json::from_str(&raw)?;
+    conn.execute(
+        "UPDATE job SET on_complete_json=?1 WHERE id=?2",
+        params![&raw, &row.id],
+    )?;
+    row.on_complete_jobs = dependencies;
+    Ok(())
+}
+
+/// Item id carried in the params of item-scoped job types.
+fn job_item_id_from_params(job_type: &JobType, params_json: &str) -> Option<String> {
+    match job_type {
+        JobType::AsrLocal => serde_json::from_str::<AsrLocalParams>(params_json)
+            .ok()
+            .map(|p| p.item_id),
+        JobType::TranslateLocal => serde_json::from_str::<TranslateLocalParams>(params_json)
+            .ok()
+            .map(|p| p.item_id),
+        JobType::DiarizeLocalV1 => serde_json::from_str::<DiarizeLocalV1Params>(params_json)
+            .ok()
+            .map(|p| p.item_id),
+        JobType::TtsPreviewPyttsx3V1 => {
+            serde_json::from_str::<TtsPreviewPyttsx3V1Params>(params_json)
+                .ok()
+                .map(|p| p.item_id)
+        }
+        JobType::TtsNeuralLocalV1 => serde_json::from_str::<TtsNeuralLocalV1Params>(params_json)
+            .ok()
+            .map(|p| p.item_id),
         JobType::DubVoicePreservingV1 => {
-            serde_json::from_str::<DubVoicePreservingV1Params>(&params_json)
+            serde_json::from_str::<DubVoicePreservingV1Params>(params_json)
                 .ok()
                 .map(|p| p.item_id)
         }
         JobType::ExperimentalVoiceBackendRenderV1 => {
-            serde_json::from_str::<ExperimentalVoiceBackendRenderV1Params>(&params_json)
+            serde_json::from_str::<ExperimentalVoiceBackendRenderV1Params>(params_json)
                 .ok()
                 .map(|p| p.item_id)
         }
-        JobType::MixDubPreviewV1 => serde_json::from_str::<MixDubPreviewV1Params>(&params_json)
+        JobType::MixDubPreviewV1 => serde_json::from_str::<MixDubPreviewV1Params>(params_json)
             .ok()
             .map(|p| p.item_id),
-        JobType::MuxDubPreviewV1 => serde_json::from_str::<MuxDubPreviewV1Params>(&params_json)
+        JobType::MuxDubPreviewV1 => serde_json::from_str::<MuxDubPreviewV1Params>(params_json)
             .ok()
             .map(|p| p.item_id),
         JobType::SeparateAudioSpleeter => {
-            serde_json::from_str::<SeparateAudioSpleeterParams>(&params_json)
+            serde_json::from_str::<SeparateAudioSpleeterParams>(params_json)
                 .ok()
                 .map(|p| p.item_id)
         }
         JobType::SeparateAudioDemucsV1 => {
-            serde_json::from_str::<SeparateAudioDemucsV1Params>(&params_json)
+            serde_json::from_str::<SeparateAudioDemucsV1Params>(params_json)
+                .ok()
+                .map(|p| p.item_id)
+        }
+        JobType::SeparateAudioDemucs4StemsV1 => {
+            serde_json::from_str::<SeparateAudioDemucs4StemsV1Params>(params_json)
+                .ok()
+                .map(|p| p.item_id)
+        }
+        JobType::CleanVocalsV1 => serde_json::from_str::<CleanVocalsV1Params>(params_json)
+            .ok()
+            .map(|p| p.item_id),
+        JobType::ExtractSpeakerVoiceProfileV1 => {
+            serde_json::from_str::<ExtractSpeakerVoiceProfileV1Params>(params_json)
                 .ok()
                 .map(|p| p.item_id)
         }
-        JobType::CleanVocalsV1 => serde_json::from_str::<CleanVocalsV1Params>(&params_json)
+        JobType::SyncSubtitlesV1 => serde_json::from_str::<SyncSubtitlesV1Params>(params_json)
+            .ok()
+            .map(|p| p.item_id),
+        JobType::FullPipelineV1 => serde_json::from_str::<FullPipelineV1Params>(params_json)
             .ok()
             .map(|p| p.item_id),
-        JobType::QcReportV1 => serde_json::from_str::<QcReportV1Params>(&params_json)
+        JobType::QcReportV1 => serde_json::from_str::<QcReportV1Params>(params_json)
             .ok()
             .map(|p| p.item_id),
-        JobType::ExportPackV1 => serde_json::from_str::<ExportPackV1Params>(&params_json)
+        JobType::ExportPackV1 => serde_json::from_str::<ExportPackV1Params>(params_json)
             .ok()
             .map(|p| p.item_id),
         _ => None,
-    };
-
-    // Re-enqueue with identical params.
-    enqueue_with_type_item_and_batch_id(paths, job_type, params_json, item_id, batch_id)
+    }
 }
 
 #[derive(Debug, Clone)]
 pub struct JobRunnerHandle {
     stop: Arc<AtomicBool>,
+    running: Arc<AtomicUsize>,
+    max_concurrency: Arc<AtomicUsize>,
 }
 
 impl JobRunnerHandle {
     pub fn stop(&self) {
         self.stop.store(true, Ordering::SeqCst);
     }
+
+    /// Jobs currently executing in this runner.
+    pub fn running_count(&self) -> usize {
+        self.running.load(Ordering::SeqCst)
+    }
+
+    /// Applies a new concurrency limit to the live runner without waiting for a DB re-read.
+    /// Persist it with [`set_runtime_max_concurrency`].
+    pub fn set_max_concurrency(&self, max_concurrency: usize) {
+        self.max_concurrency.store(
+            max_concurrency.clamp(1, MAX_MAX_CONCURRENT_JOBS),
+            Ordering::SeqCst,
+        );
+    }
 }
 
 pub fn start_runner(paths: AppPaths) -> Result<JobRunnerHandle> {
@@ -3574,10 +6130,13 @@ pub fn start_runner(paths: AppPaths) -> Result<JobRunnerHandle> {
     db::migrate(&conn)?;
 
     // If the app crashed, requeue any running jobs.
-    requeue_orphaned_running_jobs(&conn)?;
+    requeue_orphaned_running_jobs(&paths, &conn)?;
 
     let stop = Arc::new(AtomicBool::new(false));
     let running = Arc::new(AtomicUsize::new(0));
+    let max_concurrency = Arc::new(AtomicUsize::new(
+        get_max_concurrency_conn(&conn).unwrap_or(DEFAULT_MAX_CONCURRENT_JOBS),
+    ));
 
     let prune_paths = paths.clone();
     thread::spawn(move || {
@@ -3586,24 +6145,65 @@ pub fn start_runner(paths: AppPaths) -> Result<JobRunnerHandle> {
 
     let stop_thread = stop.clone();
     let running_thread = running.clone();
-    thread::spawn(move || runner_loop(paths, stop_thread, running_thread));
+    let max_concurrency_thread = max_concurrency.clone();
+    thread::spawn(move || runner_loop(paths, stop_thread, running_thread, max_concurrency_thread));
 
-    Ok(JobRunnerHandle { stop })
+    Ok(JobRunnerHandle {
+        stop,
+        running,
+        max_concurrency,
+    })
 }
 
-fn requeue_orphaned_running_jobs(conn: &rusqlite::Connection) -> Result<usize> {
-    let updated = conn.execute(
-        "UPDATE job
-         SET status=?1, started_at_ms=NULL, finished_at_ms=?2, error=?3
-         WHERE status=?4",
-        params![
-            JobStatus::Failed.as_str(),
-            now_ms(),
-            "interrupted by app shutdown",
-            JobStatus::Running.as_str()
-        ],
-    )?;
-    Ok(updated)
+fn requeue_orphaned_running_jobs(paths: &AppPaths, conn: &rusqlite::Connection) -> Result<usize> {
+    let mut stmt = conn.prepare("SELECT id, type, requeue_count FROM job WHERE status=?1")?;
+    let orphaned = stmt
+        .query_map(params![JobStatus::Running.as_str()], |row| {
+            Ok((
+                row.get::<_, String>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, i64>(2)?,
+            ))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    drop(stmt);
+
+    for (job_id, job_type, requeue_count) in &orphaned {
+        // A watcher is interrupted by every shutdown by design; always resume it.
+        if job_type == JobType::WatchDirectory.as_str() {
+            conn.execute(
+                "UPDATE job SET status=?1, started_at_ms=NULL, finished_at_ms=NULL, error=NULL
+                 WHERE id=?2",
+                params![JobStatus::Queued.as_str(), job_id],
+            )?;
+            continue;
+        }
+        if *requeue_count >= MAX_REQUEUE_ATTEMPTS {
+            // Through `set_failed` so "always" dependents still fire.
+            set_failed(paths, job_id, "interrupted by app shutdown", None)?;
+            continue;
+        }
+
+        conn.execute(
+            "UPDATE job
+             SET status=?1, progress=0, started_at_ms=NULL, finished_at_ms=NULL, error=NULL,
+                 requeue_count=requeue_count + 1
+             WHERE id=?2",
+            params![JobStatus::Queued.as_str(), job_id],
+        )?;
+        let _ = log_line(
+            paths,
+            job_id,
+            "warn",
+            "job_requeued_after_restart",
+            serde_json::json!({
+                "job_type": job_type,
+                "requeue_count": requeue_count + 1,
+                "max_requeue_attempts": MAX_REQUEUE_ATTEMPTS,
+            }),
+        );
+    }
+    Ok(orphaned.len())
 }
 
 fn enqueue(paths: &AppPaths, job_type: JobType, params_json: String) -> Result<JobRow> {
@@ -3616,7 +6216,7 @@ fn enqueue_with_type_and_item_id(
     params_json: String,
     item_id: Option<String>,
 ) -> Result<JobRow> {
-    enqueue_with_type_item_and_batch_id(paths, job_type, params_json, item_id, None)
+    enqueue_with_type_item_and_batch_id(paths, job_type, params_json, item_id, None, None)
 }
 
 fn enqueue_with_type_item_and_batch_id(
@@ -3625,17 +6225,221 @@ fn enqueue_with_type_item_and_batch_id(
     params_json: String,
     item_id: Option<String>,
     batch_id: Option<String>,
+    parent_job_id: Option<String>,
+) -> Result<JobRow> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    insert_queued_job(
+        &conn,
+        paths,
+        job_type,
+        params_json,
+        item_id,
+        batch_id,
+        parent_job_id,
+    )
+}
+
+/// Enqueues a job that chains `on_complete` into the queue once it finishes; dependencies inherit
+/// its batch id.
+pub fn enqueue_with_on_complete(
+    paths: &AppPaths,
+    job_type: JobType,
+    params_json: String,
+    item_id: Option<String>,
+    batch_id: Option<String>,
+    on_complete: Vec<JobDependency>,
 ) -> Result<JobRow> {
+    validate_job_dependencies(&on_complete)?;
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let tx = conn.unchecked_transaction()?;
+    let mut row = insert_queued_job(&tx, paths, job_type, params_json, item_id, batch_id, None)?;
+    if !on_complete.is_empty() {
+        tx.execute(
+            "UPDATE job SET on_complete_json=?1 WHERE id=?2",
+            params![serde_json::to_string(&on_complete)?, &row.id],
+        )?;
+        row.on_complete_jobs = on_complete;
+    }
+    tx.commit()?;
+    Ok(row)
+}
+
+fn validate_job_dependencies(dependencies: &[JobDependency]) -> Result<()> {
+    for dependency in dependencies {
+        if JobType::from_str(&dependency.job_type).is_none() {
+            return Err(EngineError::InstallFailed(format!(
+                "unknown dependency job type: {}",
+                dependency.job_type
+            )));
+        }
+        if dependency.condition != JOB_DEPENDENCY_CONDITION_SUCCESS
+            && dependency.condition != JOB_DEPENDENCY_CONDITION_ALWAYS
+        {
+            return Err(EngineError::InstallFailed(format!(
+                "dependency condition must be \"{JOB_DEPENDENCY_CONDITION_SUCCESS}\" or \"{JOB_DEPENDENCY_CONDITION_ALWAYS}\", got \"{}\"",
+                dependency.condition
+            )));
+        }
+        serde_json::from_str::<serde_json::Value>(&dependency.params_json)?;
+        validate_job_dependencies(&dependency.on_complete)?;
+    }
+    Ok(())
+}
+
+/// Enqueues the finished job's `on_complete_json` dependencies that match its outcome.
+fn enqueue_on_complete_jobs(
+    conn: &rusqlite::Connection,
+    paths: &AppPaths,
+    job_id: &str,
+    succeeded: bool,
+) -> Result<Vec<JobRow>> {
+    let (raw, batch_id): (Option<String>, Option<String>) = conn.query_row(
+        "SELECT on_complete_json, batch_id FROM job WHERE id=?1",
+        [job_id],
+        |row| Ok((row.get(0)?, row.get(1)?)),
+    )?;
+    let Some(raw) = raw.filter(|raw| !raw.trim().is_empty()) else {
+        return Ok(Vec::new());
+    };
+    let dependencies: Vec<JobDependency> = serde_json::from_str(&raw)?;
+    let tx = conn.unchecked_transaction()?;
+    let mut queued = Vec::new();
+    for dependency in dependencies {
+        let triggered = dependency.condition == JOB_DEPENDENCY_CONDITION_ALWAYS
+            || (succeeded && dependency.condition == JOB_DEPENDENCY_CONDITION_SUCCESS);
+        if !triggered {
+            continue;
+        }
+        let Some(job_type) = JobType::from_str(&dependency.job_type) else {
+            continue;
+        };
+        let item_id = job_item_id_from_params(&job_type, &dependency.params_json);
+        let mut row = insert_queued_job(
+            &tx,
+            paths,
+            job_type,
+            dependency.params_json,
+            item_id,
+            batch_id.clone(),
+            Some(job_id.to_string()),
+        )?;
+        if !dependency.on_complete.is_empty() {
+            tx.execute(
+                "UPDATE job SET on_complete_json=?1 WHERE id=?2",
+                params![serde_json::to_string(&dependency.on_complete)?, &row.id],
+            )?;
+            row.on_complete_jobs = dependency.on_complete;
+        }
+        queued.push(row);
+    }
+    tx.commit()?;
+    if !queued.is_empty() {
+        let _ = log_line(
+            paths,
+            job_id,
+            "info",
+            "on_complete_jobs_enqueued",
+            serde_json::json!({
+                "succeeded": succeeded,
+                "job_ids": queued.iter().map(|row| row.id.as_str()).collect::<Vec<_>>(),
+            }),
+        );
+    }
+    Ok(queued)
+}
+
+/// Follow-ups never undo the parent's final status, so a failure to queue them is recorded in the
+/// parent's log instead of being returned.
+fn enqueue_on_complete_jobs_or_log(
+    conn: &rusqlite::Connection,
+    paths: &AppPaths,
+    job_id: &str,
+    succeeded: bool,
+) {
+    if let Err(err) = enqueue_on_complete_jobs(conn, paths, job_id, succeeded) {
+        let _ = log_line(
+            paths,
+            job_id,
+            "error",
+            "on_complete_jobs_failed",
+            serde_json::json!({ "succeeded": succeeded, "error": err.to_string() }),
+        );
+    }
+}
+
+/// Adds `dependency` to the running `job_id`'s `on_complete_json`, so the follow-up is queued
+/// once the job finishes instead of while it is still running. Duplicates are recorded once.
+fn chain_on_complete(paths: &AppPaths, job_id: &str, dependency: JobDependency) -> Result<()> {
+    validate_job_dependencies(std::slice::from_ref(&dependency))?;
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
+    let raw: Option<String> = conn
+        .query_row(
+            "SELECT on_complete_json FROM job WHERE id=?1",
+            [job_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| EngineError::not_found("job", job_id))?;
+    let mut dependencies: Vec<JobDependency> = match raw.filter(|raw| !raw.trim().is_empty()) {
+        Some(raw) => serde_json::from_str(&raw)?,
+        None => Vec::new(),
+    };
+    if dependencies.contains(&dependency) {
+        return Ok(());
+    }
+    dependencies.push(dependency);
+    conn.execute(
+        "UPDATE job SET on_complete_json=?1 WHERE id=?2",
+        params![serde_json::to_string(&dependencies)?, job_id],
+    )?;
+    Ok(())
+}
 
+/// Inserts every `(job type, params json, item id, batch id override)` entry in one
+/// transaction; jobs without an override share `batch_id`. Nothing is queued if any
+/// insert fails.
+fn enqueue_batch_in_transaction(
+    conn: &rusqlite::Connection,
+    paths: &AppPaths,
+    jobs: Vec<(JobType, String, Option<String>, Option<String>)>,
+    batch_id: &str,
+    parent_job_id: Option<&str>,
+) -> Result<Vec<JobRow>> {
+    let tx = conn.unchecked_transaction()?;
+    let mut rows = Vec::with_capacity(jobs.len());
+    for (job_type, params_json, item_id, batch_override) in jobs {
+        let batch_id = batch_override.unwrap_or_else(|| batch_id.to_string());
+        rows.push(insert_queued_job(
+            &tx,
+            paths,
+            job_type,
+            params_json,
+            item_id,
+            Some(batch_id),
+            parent_job_id.map(str::to_string),
+        )?);
+    }
+    tx.commit()?;
+    Ok(rows)
+}
+
+fn insert_queued_job(
+    conn: &rusqlite::Connection,
+    paths: &AppPaths,
+    job_type: JobType,
+    params_json: String,
+    item_id: Option<String>,
+    batch_id: Option<String>,
+    parent_job_id: Option<String>,
+) -> Result<JobRow> {
     let id = Uuid::new_v4().to_string();
     let created_at_ms = now_ms();
-    let logs_path = paths
-        .job_logs_dir()
-        .join(format!("{id}.jsonl"))
-        .to_string_lossy()
-        .to_string();
+    let logs_path = paths.job_log_path(&id).to_string_lossy().to_string();
+    let content_key = job_content_key(job_type.as_str(), item_id.as_deref(), &params_json);
+    let not_before_ms = job_not_before_ms(&params_json);
 
     conn.execute(
         r#"
@@ -3651,8 +6455,11 @@ INSERT INTO job (
   created_at_ms,
   started_at_ms,
   finished_at_ms,
-  logs_path
-) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+  logs_path,
+  content_key,
+  not_before_ms,
+  parent_job_id
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)
 "#,
         params![
             &id,
@@ -3666,9 +6473,19 @@ INSERT INTO job (
             created_at_ms,
             Option::<i64>::None,
             Option::<i64>::None,
-            &logs_path
+            &logs_path,
+            &content_key,
+            not_before_ms,
+            &parent_job_id
         ],
     )?;
+    if let Some(batch_id) = &batch_id {
+        // New work reopens a batch that already reported completion.
+        conn.execute(
+            "DELETE FROM meta WHERE key=?1",
+            [batch_completed_meta_key(batch_id)],
+        )?;
+    }
 
     Ok(JobRow {
         id,
@@ -3683,9 +6500,54 @@ INSERT INTO job (
         finished_at_ms: None,
         logs_path,
         params_json,
+        requeue_count: 0,
+        content_key,
+        not_before_ms,
+        estimate_remaining_ms: None,
+        on_complete_jobs: Vec::new(),
+        worker_id: None,
+        parent_job_id,
+        progress_steps: Vec::new(),
     })
 }
 
+/// Scheduled start carried in a job's params as `schedule_after_ms`, if any.
+fn job_not_before_ms(params_json: &str) -> Option<i64> {
+    serde_json::from_str::<serde_json::Value>(params_json)
+        .ok()?
+        .get("schedule_after_ms")?
+        .as_i64()
+}
+
+/// Names the artifacts dir shared by every attempt at the same work:
+/// `sha256("{job_type}:{item_id}:{sha256(params_json)}")`, truncated. Jobs without an item have
+/// no natural key and keep a per-job dir.
+fn job_content_key(job_type: &str, item_id: Option<&str>, params_json: &str) -> Option<String> {
+    let item_id = item_id.map(str::trim).filter(|id| !id.is_empty())?;
+    let params_digest = hex::encode(Sha256::digest(params_json.as_bytes()));
+    let key = Sha256::digest(format!("{job_type}:{item_id}:{params_digest}").as_bytes());
+    Some(hex::encode(&key[..16]))
+}
+
+fn job_content_key_for_id(paths: &AppPaths, job_id: &str) -> Result<Option<String>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let content_key: Option<String> = conn
+        .query_row(
+            "SELECT content_key FROM job WHERE id=?1",
+            params![job_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .flatten();
+    Ok(content_key)
+}
+
+fn job_artifacts_dir_for_id(paths: &AppPaths, job_id: &str) -> Result<PathBuf> {
+    let content_key = job_content_key_for_id(paths, job_id)?;
+    Ok(paths.job_artifacts_dir(job_id, content_key.as_deref()))
+}
+
 fn job_batch_id(paths: &AppPaths, job_id: &str) -> Result<Option<String>> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
@@ -3704,6 +6566,19 @@ fn job_batch_id(paths: &AppPaths, job_id: &str) -> Result<Option<String>> {
     }))
 }
 
+fn active_job_content_keys(conn: &rusqlite::Connection) -> Result<HashSet<String>> {
+    let mut stmt = conn.prepare(
+        "SELECT content_key FROM job WHERE content_key IS NOT NULL AND status IN (?1, ?2)",
+    )?;
+    let keys = stmt
+        .query_map(
+            params![JobStatus::Queued.as_str(), JobStatus::Running.as_str()],
+            |row| row.get::<_, String>(0),
+        )?
+        .collect::<rusqlite::Result<HashSet<_>>>()?;
+    Ok(keys)
+}
+
 fn item_has_active_job(paths: &AppPaths, item_id: &str, job_type: &str) -> Result<bool> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
@@ -3724,6 +6599,154 @@ WHERE item_id=?1 AND type=?2 AND status IN (?3, ?4)
     Ok(count > 0)
 }
 
+pub fn has_active_job_of_type(paths: &AppPaths, job_type: &str) -> Result<bool> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let count: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM job WHERE type=?1 AND status IN (?2, ?3)",
+        params![
+            job_type,
+            JobStatus::Queued.as_str(),
+            JobStatus::Running.as_str()
+        ],
+        |row| row.get(0),
+    )?;
+    Ok(count > 0)
+}
+
+/// Oldest queued or running job of `job_type`, if any.
+fn active_job_of_type(paths: &AppPaths, job_type: &str) -> Result<Option<JobRow>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let job_id: Option<String> = conn
+        .query_row(
+            "SELECT id FROM job WHERE type=?1 AND status IN (?2, ?3) ORDER BY created_at_ms ASC LIMIT 1",
+            params![
+                job_type,
+                JobStatus::Queued.as_str(),
+                JobStatus::Running.as_str()
+            ],
+            |row| row.get(0),
+        )
+        .optional()?;
+    match job_id {
+        Some(id) => get_job(paths, &id),
+        None => Ok(None),
+    }
+}
+
+/// Runs the Demucs two-stem split on `audio_path` and returns `(vocals, background)` paths.
+fn run_demucs_two_stems(
+    paths: &AppPaths,
+    job_id: &str,
+    venv_python: &Path,
+    torch_home: &Path,
+    audio_path: &Path,
+    raw_dir: &Path,
+) -> Result<(PathBuf, PathBuf)> {
+    std::fs::create_dir_all(raw_dir)?;
+    let output = {
+        let mut cmd = cmd::sanitized_command(venv_python);
+        cmd.args(["-m", "demucs_infer"]);
+        cmd.args(["--two-stems", "vocals"]);
+        cmd.arg("-o").arg(raw_dir);
+        cmd.arg(audio_path);
+        cmd.env("PYTHONNOUSERSITE", "1");
+        cmd.env(
+            "XDG_CACHE_HOME",
+            paths
+                .cache_dir()
+                .join("python")
+                .to_string_lossy()
+                .to_string(),
+        );
+        cmd.env("TORCH_HOME", torch_home.to_string_lossy().to_string());
+        run_python_tool(cmd, &JobType::SeparateAudioDemucsV1, "demucs")?
+    };
+
+    if !output.status.success() {
+        return Err(python_tool_failed(paths, job_id, "demucs", &output));
+    }
+
+    let mut vocals_src: Option<PathBuf> = None;
+    let mut background_src: Option<PathBuf> = None;
+    let mut stack: Vec<PathBuf> = vec![raw_dir.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            if name == "vocals.wav" {
+                vocals_src = Some(path);
+            } else if name == "no_vocals.wav" || name == "accompaniment.wav" {
+                background_src = Some(path);
+            }
+            if vocals_src.is_some() && background_src.is_some() {
+                break;
+            }
+        }
+        if vocals_src.is_some() && background_src.is_some() {
+            break;
+        }
+    }
+
+    let vocals_src = vocals_src.ok_or_else(|| {
+        EngineError::InstallFailed("demucs output not found (vocals.wav)".to_string())
+    })?;
+    let background_src = background_src.ok_or_else(|| {
+        EngineError::InstallFailed("demucs output not found (no_vocals.wav)".to_string())
+    })?;
+    Ok((vocals_src, background_src))
+}
+
+/// Chunk length to use for Demucs, or `None` to separate the whole input at once.
+fn demucs_chunk_duration_ms(
+    requested_secs: Option<u64>,
+    audio_duration_ms: Option<i64>,
+) -> Option<i64> {
+    match requested_secs {
+        Some(0) => None,
+        Some(secs) => Some((secs.max(DEMUCS_MIN_CHUNK_SECS) * 1000) as i64),
+        None => audio_duration_ms
+            .filter(|ms| *ms > DEMUCS_AUTO_CHUNK_MIN_DURATION_SECS * 1000)
+            .map(|_| (DEMUCS_DEFAULT_CHUNK_SECS * 1000) as i64),
+    }
+}
+
+/// `(start_ms, duration_ms)` chunks covering `total_ms`; each chunk runs `overlap_ms` into the
+/// next so the stitched stems can crossfade. A short tail is folded into the previous chunk.
+fn demucs_chunk_plan(total_ms: i64, chunk_ms: i64, overlap_ms: i64) -> Vec<(i64, i64)> {
+    if total_ms <= chunk_ms || chunk_ms <= overlap_ms {
+        return vec![(0, total_ms.max(0))];
+    }
+    let mut count = (total_ms + chunk_ms - 1) / chunk_ms;
+    if count > 1 && total_ms - (count - 1) * chunk_ms < 2 * overlap_ms {
+        count -= 1;
+    }
+    (0..count)
+        .map(|index| {
+            let start_ms = index * chunk_ms;
+            let end_ms = if index + 1 == count {
+                total_ms
+            } else {
+                start_ms + chunk_ms + overlap_ms
+            };
+            (start_ms, end_ms - start_ms)
+        })
+        .collect()
+}
+
 fn separation_background_path_best_effort(paths: &AppPaths, item_id: &str) -> Option<PathBuf> {
     let item_dir = paths.derived_item_dir(item_id);
     let demucs = item_dir
@@ -3742,9 +6765,102 @@ fn separation_background_path_best_effort(paths: &AppPaths, item_id: &str) -> Op
         return Some(spleeter);
     }
 
+    let demucs_4stems = item_dir
+        .join("separation")
+        .join("demucs_4stems_v1")
+        .join("other.wav");
+    if demucs_4stems.exists() {
+        return Some(demucs_4stems);
+    }
+
+    let spleeter_5stems = item_dir
+        .join("separation")
+        .join("spleeter_5stems")
+        .join("other.wav");
+    if spleeter_5stems.exists() {
+        return Some(spleeter_5stems);
+    }
+
     None
 }
 
+/// Fills in a profile for each speaker without one by cutting `AUTO_PROFILE_CLIP_MS` of audio
+/// from the speaker's earliest segment (vocals stem when available) into
+/// `speaker_profiles/{speaker_key}_auto.wav`. Existing auto clips are reused.
+fn auto_extract_speaker_profiles(
+    paths: &AppPaths,
+    job_id: &str,
+    item: &library::LibraryItem,
+    doc: &subtitles::SubtitleDocument,
+    settings_by_key: &mut HashMap<String, SpeakerRenderSettings>,
+) -> Result<()> {
+    let mut earliest_by_speaker: std::collections::BTreeMap<String, i64> =
+        std::collections::BTreeMap::new();
+    for seg in &doc.segments {
+        let Some(speaker) = seg
+            .speaker
+            .as_deref()
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+        else {
+            continue;
+        };
+        let start = earliest_by_speaker
+            .entry(speaker.to_string())
+            .or_insert(seg.start_ms);
+        *start = (*start).min(seg.start_ms);
+    }
+
+    let source = separation_vocals_path_best_effort(paths, &item.id)
+        .unwrap_or_else(|| PathBuf::from(&item.media_path));
+    let profiles_dir = paths.derived_item_dir(&item.id).join("speaker_profiles");
+    for (speaker_key, start_ms) in earliest_by_speaker {
+        let settings = settings_by_key.entry(speaker_key.clone()).or_default();
+        if settings.primary_profile_path.is_some() || !settings.profile_paths.is_empty() {
+            continue;
+        }
+        let file_stem = sanitize_filename_component(&speaker_key);
+        if file_stem.is_empty() {
+            continue;
+        }
+        let clip_path = profiles_dir.join(format!("{file_stem}_auto.wav"));
+        let start_ms = start_ms.max(0);
+        if !clip_path.exists() {
+            if let Err(err) = ffmpeg::extract_audio_clip_wav_16k_mono(
+                paths,
+                &source,
+                &clip_path,
+                start_ms,
+                start_ms + AUTO_PROFILE_CLIP_MS,
+            ) {
+                log_line(
+                    paths,
+                    job_id,
+                    "warning",
+                    "speaker_profile_auto_extract_failed",
+                    serde_json::json!({ "speaker_key": speaker_key, "error": err.to_string() }),
+                )?;
+                continue;
+            }
+        }
+        let clip = clip_path.to_string_lossy().to_string();
+        settings.primary_profile_path = Some(clip.clone());
+        settings.profile_paths = vec![clip];
+        log_line(
+            paths,
+            job_id,
+            "info",
+            "speaker_profile_auto_extracted",
+            serde_json::json!({
+                "speaker_key": speaker_key,
+                "source_segment_start_ms": start_ms,
+                "duration_ms": AUTO_PROFILE_CLIP_MS,
+            }),
+        )?;
+    }
+    Ok(())
+}
+
 fn separation_vocals_path_best_effort(paths: &AppPaths, item_id: &str) -> Option<PathBuf> {
     let item_dir = paths.derived_item_dir(item_id);
     let demucs = item_dir
@@ -3763,9 +6879,66 @@ fn separation_vocals_path_best_effort(paths: &AppPaths, item_id: &str) -> Option
         return Some(spleeter);
     }
 
+    let demucs_4stems = item_dir
+        .join("separation")
+        .join("demucs_4stems_v1")
+        .join("vocals.wav");
+    if demucs_4stems.exists() {
+        return Some(demucs_4stems);
+    }
+
+    let spleeter_5stems = item_dir
+        .join("separation")
+        .join("spleeter_5stems")
+        .join("vocals.wav");
+    if spleeter_5stems.exists() {
+        return Some(spleeter_5stems);
+    }
+
     None
 }
 
+fn normalize_clean_vocals_source(source: Option<&str>) -> Result<Option<String>> {
+    match normalize_non_empty(source).map(|v| v.to_ascii_lowercase()) {
+        None => Ok(None),
+        Some(v) if v == CLEAN_VOCALS_SOURCE_DEMUCS || v == CLEAN_VOCALS_SOURCE_SPLEETER => {
+            Ok(Some(v))
+        }
+        Some(other) => Err(EngineError::InstallFailed(format!(
+            "unsupported vocals cleanup source: {other} (expected {CLEAN_VOCALS_SOURCE_DEMUCS} or {CLEAN_VOCALS_SOURCE_SPLEETER})"
+        ))),
+    }
+}
+
+fn clean_vocals_source_path(
+    paths: &AppPaths,
+    item_id: &str,
+    source: Option<&str>,
+) -> Option<PathBuf> {
+    let separation_dir = paths.derived_item_dir(item_id).join("separation");
+    let candidates: &[&str] = match source {
+        Some(CLEAN_VOCALS_SOURCE_DEMUCS) => &["demucs_two_stems_v1", "demucs_4stems_v1"],
+        Some(CLEAN_VOCALS_SOURCE_SPLEETER) => &["spleeter_2stems", "spleeter_5stems"],
+        _ => return separation_vocals_path_best_effort(paths, item_id),
+    };
+    candidates
+        .iter()
+        .map(|dir| separation_dir.join(dir).join("vocals.wav"))
+        .find(|path| path.exists())
+}
+
+/// Cleaned vocals when `clean_vocals_v1` has run, otherwise the raw separated stem.
+fn preferred_vocals_path_best_effort(paths: &AppPaths, item_id: &str) -> Option<PathBuf> {
+    let cleaned = paths
+        .derived_item_dir(item_id)
+        .join("cleanup")
+        .join("vocals_clean_v1.wav");
+    if cleaned.exists() {
+        return Some(cleaned);
+    }
+    separation_vocals_path_best_effort(paths, item_id)
+}
+
 fn separation_background_exists(paths: &AppPaths, item_id: &str) -> bool {
     separation_background_path_best_effort(paths, item_id).is_some()
 }
@@ -3784,6 +6957,45 @@ fn mix_background_audio_source(
     None
 }
 
+/// Silent ranges of the audio the dub mixer would use as background for `item_id`.
+pub fn item_background_silences(
+    paths: &AppPaths,
+    item_id: &str,
+) -> Result<Vec<ffmpeg::SilenceRange>> {
+    let item = library::get_item_by_id(paths, item_id)?;
+    let (background_path, _) = mix_background_audio_source(paths, &item).ok_or_else(|| {
+        EngineError::InstallFailed(format!("no background audio available for item {item_id}"))
+    })?;
+    ffmpeg::detect_silences(
+        paths,
+        &background_path,
+        MIX_SILENCE_NOISE_DB,
+        MIX_SILENCE_MIN_DURATION_MS,
+    )
+}
+
+/// Coverage of the TTS manifest the dub mixer would pick for `item_id`, if one exists.
+pub fn item_tts_manifest_summary(
+    paths: &AppPaths,
+    item_id: &str,
+) -> Result<Option<TtsManifestSummary>> {
+    let preferred_backend_id = resolve_pipeline_tts_backend_preference(paths, item_id, None);
+    let Some(candidate) =
+        select_tts_manifest_candidate(paths, item_id, None, None, preferred_backend_id.as_deref())?
+    else {
+        return Ok(None);
+    };
+    let bytes = std::fs::read(&candidate.manifest_path)?;
+    let manifest: TtsPreviewManifest = serde_json::from_slice(&bytes)?;
+    Ok(Some(TtsManifestSummary {
+        backend_id: candidate.backend_id,
+        variant_label: candidate.variant_label,
+        manifest_path: candidate.manifest_path.to_string_lossy().to_string(),
+        segment_count: manifest.segments.len(),
+        coverage: manifest.coverage(),
+    }))
+}
+
 fn tts_manifest_exists(paths: &AppPaths, item_id: &str) -> bool {
     let item_dir = paths.derived_item_dir(item_id);
     list_tts_manifest_candidate_refs(&item_dir)
@@ -3804,7 +7016,255 @@ fn mux_output_exists(paths: &AppPaths, item_id: &str) -> bool {
     dir.join("mux_dub_preview_v1.mp4").exists() || dir.join("mux_dub_preview_v1.mkv").exists()
 }
 
-fn runner_loop(paths: AppPaths, stop: Arc<AtomicBool>, running: Arc<AtomicUsize>) {
+fn batch_on_import_asr_model_id(rules: &config::BatchOnImportRules) -> String {
+    rules
+        .asr_model_id
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .unwrap_or("whispercpp-tiny")
+        .to_string()
+}
+
+/// Params of the full-pipeline head job sharing `job_id`'s batch, if any.
+fn full_pipeline_params_for_job(paths: &AppPaths, job_id: &str) -> Option<FullPipelineV1Params> {
+    let batch_id = job_batch_id(paths, job_id).ok().flatten()?;
+    let conn = db::open(paths).ok()?;
+    let params_json: String = conn
+        .query_row(
+            "SELECT params_json FROM job WHERE batch_id=?1 AND type=?2 ORDER BY created_at_ms DESC LIMIT 1",
+            params![batch_id, JobType::FullPipelineV1.as_str()],
+            |row| row.get(0),
+        )
+        .optional()
+        .ok()
+        .flatten()?;
+    serde_json::from_str(&params_json).ok()
+}
+
+/// Batch-on-import rules for follow-up stages of `job_id`. Jobs in a full-pipeline batch run every
+/// dub stage with the pipeline's own model and TTS backend, whatever the global rules say.
+fn batch_on_import_rules_for_job(paths: &AppPaths, job_id: &str) -> config::BatchOnImportRules {
+    match full_pipeline_params_for_job(paths, job_id) {
+        Some(pipeline) => config::BatchOnImportRules {
+            auto_asr: true,
+            auto_translate: true,
+            auto_separate: true,
+            auto_diarize: false,
+            auto_dub_preview: true,
+            auto_cleanup_vocals: false,
+            auto_mux: true,
+            auto_export_pack: false,
+            tts_backend: pipeline.tts_backend,
+            asr_model_id: Some(pipeline.model_id),
+            auto_diarize_backend: None,
+        },
+        None => config::load_batch_on_import_rules(paths).unwrap_or_default(),
+    }
+}
+
+fn batch_on_import_tts_job_type(paths: &AppPaths, rules: &config::BatchOnImportRules) -> JobType {
+    match rules.tts_backend.as_deref() {
+        Some(config::BATCH_ON_IMPORT_TTS_BACKEND_PYTTSX3_V1) => JobType::TtsPreviewPyttsx3V1,
+        Some(config::BATCH_ON_IMPORT_TTS_BACKEND_NEURAL_LOCAL_V1) => JobType::TtsNeuralLocalV1,
+        Some(config::BATCH_ON_IMPORT_TTS_BACKEND_VOICE_PRESERVING_V1) => {
+            JobType::DubVoicePreservingV1
+        }
+        _ => {
+            if tools::tts_neural_local_v1_pack_status(paths).installed {
+                JobType::TtsNeuralLocalV1
+            } else {
+                JobType::TtsPreviewPyttsx3V1
+            }
+        }
+    }
+}
+
+/// Maps a subtitle language tag to the ISO 639-3 code aeneas expects.
+fn aeneas_language_code(lang: &str) -> String {
+    let primary = lang
+        .split(['-', '_'])
+        .next()
+        .unwrap_or("")
+        .trim()
+        .to_ascii_lowercase();
+    let mapped = match primary.as_str() {
+        "en" => "eng",
+        "ja" => "jpn",
+        "ko" => "kor",
+        "zh" => "cmn",
+        "de" => "deu",
+        "fr" => "fra",
+        "es" => "spa",
+        "it" => "ita",
+        "pt" => "por",
+        "ru" => "rus",
+        "nl" => "nld",
+        "" => "eng",
+        other if other.len() == 3 => other,
+        _ => "eng",
+    };
+    mapped.to_string()
+}
+
+/// Copies aligned timings onto the source segments that carry text; empty segments keep their
+/// original window so the result always has the source's segment count.
+fn apply_aligned_fragments(
+    source: &subtitles::SubtitleDocument,
+    fragments: &[AlignedFragment],
+) -> Result<subtitles::SubtitleDocument> {
+    let text_segments = source
+        .segments
+        .iter()
+        .filter(|seg| !seg.text.trim().is_empty())
+        .count();
+    if fragments.len() != text_segments {
+        return Err(EngineError::InstallFailed(format!(
+            "alignment returned {} fragments for {text_segments} subtitle segments",
+            fragments.len()
+        )));
+    }
+
+    let mut aligned = source.clone();
+    let mut fragments = fragments.iter();
+    for seg in aligned.segments.iter_mut() {
+        if seg.text.trim().is_empty() {
+            continue;
+        }
+        if let Some(fragment) = fragments.next() {
+            seg.start_ms = fragment.begin_ms.max(0);
+            seg.end_ms = fragment.end_ms.max(seg.start_ms);
+        }
+    }
+    Ok(aligned)
+}
+
+/// Denoises with ffmpeg's FFT denoiser plus band limiting, then loudness-normalizes.
+fn run_clean_vocals_ffmpeg(paths: &AppPaths, vocals_src: &Path, out_path: &Path) -> Result<()> {
+    let output = cmd::command(paths.ffmpeg_cmd())
+        .args(["-nostdin", "-y"])
+        .arg("-i")
+        .arg(vocals_src)
+        .args(["-af", CLEAN_VOCALS_FFMPEG_FILTER])
+        .args(["-c:a", "pcm_s16le", "-ar", "44100", "-ac", "2"])
+        .arg(out_path)
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => EngineError::ExternalToolMissing {
+                tool: "ffmpeg".to_string(),
+            },
+            _ => EngineError::Io(e),
+        })?;
+
+    if !output.status.success() {
+        return Err(EngineError::ExternalToolFailed {
+            tool: "ffmpeg".to_string(),
+            code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    Ok(())
+}
+
+/// Spectral-gating denoise via `noisereduce` from the venv, then peak-normalizes.
+fn run_clean_vocals_noisereduce(
+    paths: &AppPaths,
+    job_id: &str,
+    vocals_src: &Path,
+    out_path: &Path,
+) -> Result<()> {
+    let venv_python = tools::python_venv_python_path(paths)?;
+    let artifacts_dir = job_artifacts_dir_for_id(paths, job_id)?;
+    std::fs::create_dir_all(&artifacts_dir)?;
+    let script_path = artifacts_dir.join("clean_vocals_v1.py");
+    let script = r#"
+import argparse
+
+import noisereduce as nr
+import numpy as np
+from scipy.io import wavfile
+
+
+def main():
+    ap = argparse.ArgumentParser()
+    ap.add_argument("--input", required=True)
+    ap.add_argument("--output", required=True)
+    ap.add_argument("--peak-dbfs", type=float, default=-1.0)
+    args = ap.parse_args()
+
+    rate, data = wavfile.read(args.input)
+    if data.dtype.kind in "iu":
+        data = data.astype(np.float32) / float(np.iinfo(data.dtype).max)
+    else:
+        data = data.astype(np.float32)
+
+    # noisereduce expects (channels, samples).
+    samples = data.T if data.ndim == 2 else data
+    reduced = nr.reduce_noise(y=samples, sr=rate)
+
+    peak = float(np.max(np.abs(reduced))) if reduced.size else 0.0
+    if peak > 0.0:
+        reduced = reduced * (10.0 ** (args.peak_dbfs / 20.0) / peak)
+
+    out = reduced.T if reduced.ndim == 2 else reduced
+    wavfile.write(args.output, rate, (np.clip(out, -1.0, 1.0) * 32767.0).astype(np.int16))
+
+
+if __name__ == "__main__":
+    main()
+"#;
+    std::fs::write(&script_path, script)?;
+
+    let mut py_cmd = cmd::sanitized_command(&venv_python);
+    py_cmd.arg(&script_path);
+    py_cmd.arg("--input").arg(vocals_src);
+    py_cmd.arg("--output").arg(out_path);
+    py_cmd
+        .arg("--peak-dbfs")
+        .arg(CLEAN_VOCALS_PEAK_DBFS.to_string());
+    py_cmd.env("PYTHONNOUSERSITE", "1");
+    let output = run_python_tool(py_cmd, &JobType::CleanVocalsV1, "vocals cleanup script")?;
+    if !output.status.success() {
+        let _ = std::fs::remove_file(out_path);
+        return Err(python_tool_failed(
+            paths,
+            job_id,
+            "vocals cleanup script",
+            &output,
+        ));
+    }
+    Ok(())
+}
+
+fn enqueue_batch_on_import_clean_vocals(
+    paths: &AppPaths,
+    job_id: &str,
+    item_id: &str,
+) -> Result<()> {
+    let rules = batch_on_import_rules_for_job(paths, job_id);
+    if !rules.auto_cleanup_vocals
+        || item_has_active_job(paths, item_id, JobType::CleanVocalsV1.as_str()).unwrap_or(false)
+    {
+        return Ok(());
+    }
+    let params_json = serde_json::to_string(&CleanVocalsV1Params {
+        item_id: item_id.to_string(),
+        source: None,
+    })?;
+    chain_on_complete(
+        paths,
+        job_id,
+        JobDependency::on_success(JobType::CleanVocalsV1, params_json),
+    )?;
+    Ok(())
+}
+
+fn runner_loop(
+    paths: AppPaths,
+    stop: Arc<AtomicBool>,
+    running: Arc<AtomicUsize>,
+    max_concurrency: Arc<AtomicUsize>,
+) {
     while !stop.load(Ordering::SeqCst) {
         let paused = match is_queue_paused(&paths) {
             Ok(v) => v,
@@ -3815,11 +7275,11 @@ fn runner_loop(paths: AppPaths, stop: Arc<AtomicBool>, running: Arc<AtomicUsize>
             continue;
         }
 
-        let max_concurrency = match get_max_concurrency(&paths) {
-            Ok(v) => v,
-            Err(_) => DEFAULT_MAX_CONCURRENT_JOBS,
-        };
-        let available = max_concurrency.saturating_sub(running.load(Ordering::SeqCst));
+        start_queued_watchers(&paths);
+
+        let available = max_concurrency
+            .load(Ordering::SeqCst)
+            .saturating_sub(running.load(Ordering::SeqCst));
         if available == 0 {
             thread::sleep(Duration::from_millis(200));
             continue;
@@ -3851,40 +7311,149 @@ fn runner_loop(paths: AppPaths, stop: Arc<AtomicBool>, running: Arc<AtomicUsize>
                 continue;
             }
 
-            running.fetch_add(1, Ordering::SeqCst);
+            report_runtime_state(running.fetch_add(1, Ordering::SeqCst) + 1);
             let paths_worker = paths.clone();
             let running_worker = running.clone();
             thread::spawn(move || {
-                let result = execute_job(&paths_worker, &job_id, &type_str, &params_json);
-                if let Err(e) = result {
-                    let _ = set_failed(&paths_worker, &job_id, &e.to_string());
-                }
-                running_worker.fetch_sub(1, Ordering::SeqCst);
+                run_claimed_job(&paths_worker, &job_id, &type_str, &params_json);
+                let previous = running_worker.fetch_sub(1, Ordering::SeqCst);
+                report_runtime_state(previous.saturating_sub(1));
             });
         }
     }
 }
 
-fn fetch_queued_jobs(paths: &AppPaths, limit: usize) -> Result<Vec<(String, String, String)>> {
-    let conn = db::open(paths)?;
-    db::migrate(&conn)?;
-
-    let mut stmt = conn.prepare(
-        "SELECT id, type, params_json FROM job WHERE status=?1 ORDER BY created_at_ms ASC LIMIT ?2",
-    )?;
-
-    let rows = stmt
-        .query_map(params![JobStatus::Queued.as_str(), limit as i64], |row| {
-            Ok((row.get(0)?, row.get(1)?, row.get(2)?))
-        })?
-        .collect::<rusqlite::Result<Vec<_>>>()?;
+/// Starts queued directory watchers on their own threads. Watchers live until canceled, so they
+/// do not take a worker slot: with a concurrency of 1 their own import jobs could never run.
+fn start_queued_watchers(paths: &AppPaths) {
+    let Ok(queued) = fetch_queued_watchers(paths) else {
+        return;
+    };
+    for (job_id, type_str, params_json) in queued {
+        if !claim_job(paths, &job_id).unwrap_or(false) {
+            continue;
+        }
+        let paths_worker = paths.clone();
+        thread::spawn(move || run_claimed_job(&paths_worker, &job_id, &type_str, &params_json));
+    }
+}
 
-    Ok(rows)
+fn run_claimed_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str) {
+    let worker_id = WORKER_ID.fetch_add(1, Ordering::SeqCst);
+    CURRENT_WORKER_ID.with(|current| current.set(Some(worker_id)));
+    let result = execute_job(paths, job_id, type_str, params_json);
+    if let Err(e) = result {
+        let message = e.to_string();
+        log_python_import_failure_best_effort(paths, job_id, &message);
+        let _ = set_failed(paths, job_id, &message, current_progress());
+    }
 }
 
-fn claim_job(paths: &AppPaths, job_id: &str) -> Result<bool> {
-    let conn = db::open(paths)?;
-    db::migrate(&conn)?;
+/// Extracts the missing module from a Python `ModuleNotFoundError`/`ImportError` message.
+fn python_missing_module_from_error(message: &str) -> Option<String> {
+    if !message.contains("ModuleNotFoundError") && !message.contains("ImportError") {
+        return None;
+    }
+    for marker in ["No module named ", " from "] {
+        if let Some(idx) = message.find(marker) {
+            let rest = message[idx + marker.len()..].trim_start();
+            let name = rest
+                .trim_start_matches(['\'', '"'])
+                .split(|c: char| c == '\'' || c == '"' || c.is_whitespace())
+                .next()
+                .unwrap_or("")
+                .trim();
+            if !name.is_empty() {
+                return Some(name.to_string());
+            }
+        }
+    }
+    None
+}
+
+fn log_python_import_failure_best_effort(paths: &AppPaths, job_id: &str, message: &str) {
+    let Some(module) = python_missing_module_from_error(message) else {
+        return;
+    };
+    let top_level = module.split('.').next().unwrap_or(&module).to_string();
+    let installed_version = tools::check_python_venv_packages(paths, &[top_level.as_str()])
+        .ok()
+        .and_then(|versions| versions.get(&top_level).cloned().flatten());
+    let _ = log_line(
+        paths,
+        job_id,
+        "error",
+        "python_import_error",
+        serde_json::json!({
+            "module": module,
+            "package": top_level,
+            "installed_version": installed_version,
+            "hint": "A required Python package is missing or broken in the app venv. Reinstall the matching pack from Diagnostics -> Tools.",
+        }),
+    );
+}
+
+/// Queued jobs that are due, oldest first. Directory watchers are excluded; they are started by
+/// [`start_queued_watchers`] outside the worker pool.
+fn fetch_queued_jobs(paths: &AppPaths, limit: usize) -> Result<Vec<(String, String, String)>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    let mut stmt = conn.prepare(
+        r#"
+SELECT id, type, params_json
+FROM job
+WHERE status=?1 AND type<>?4 AND (not_before_ms IS NULL OR not_before_ms <= ?3)
+ORDER BY created_at_ms ASC
+LIMIT ?2
+"#,
+    )?;
+
+    let rows = stmt
+        .query_map(
+            params![
+                JobStatus::Queued.as_str(),
+                limit as i64,
+                now_ms(),
+                JobType::WatchDirectory.as_str()
+            ],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(rows)
+}
+
+fn fetch_queued_watchers(paths: &AppPaths) -> Result<Vec<(String, String, String)>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    let mut stmt = conn.prepare(
+        r#"
+SELECT id, type, params_json
+FROM job
+WHERE status=?1 AND type=?2 AND (not_before_ms IS NULL OR not_before_ms <= ?3)
+ORDER BY created_at_ms ASC
+"#,
+    )?;
+
+    let rows = stmt
+        .query_map(
+            params![
+                JobStatus::Queued.as_str(),
+                JobType::WatchDirectory.as_str(),
+                now_ms()
+            ],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(rows)
+}
+
+fn claim_job(paths: &AppPaths, job_id: &str) -> Result<bool> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
 
     let updated = conn.execute(
         "UPDATE job SET status=?1, started_at_ms=?2 WHERE id=?3 AND status=?4",
@@ -3898,8 +7467,558 @@ fn claim_job(paths: &AppPaths, job_id: &str) -> Result<bool> {
     Ok(updated == 1)
 }
 
+/// Python pack a job runs on, as named in the pack integrity manifest.
+fn python_pack_for_job_type(job_type: &JobType) -> Option<&'static str> {
+    match job_type {
+        JobType::SeparateAudioSpleeter => Some("spleeter"),
+        JobType::SeparateAudioDemucsV1 | JobType::SeparateAudioDemucs4StemsV1 => Some("demucs"),
+        JobType::DiarizeLocalV1 => Some("diarization"),
+        JobType::TtsPreviewPyttsx3V1 => Some("tts_preview"),
+        JobType::TtsNeuralLocalV1 => Some("tts_neural_local_v1"),
+        JobType::DubVoicePreservingV1 => Some("tts_voice_preserving_local_v1"),
+        _ => None,
+    }
+}
+
+fn verify_pack_integrity_on_job_start(paths: &AppPaths, job_id: &str, pack_id: &str) -> Result<()> {
+    let enabled = config::load_pack_integrity_config(paths)
+        .map(|c| c.verify_pack_integrity_on_job_start)
+        .unwrap_or(false);
+    if !enabled {
+        return Ok(());
+    }
+
+    let result = tools::verify_pack_integrity(paths, pack_id)?;
+    log_line(
+        paths,
+        job_id,
+        if result.ok { "info" } else { "error" },
+        "pack_integrity_checked",
+        serde_json::json!({
+            "pack_id": pack_id,
+            "ok": result.ok,
+            "missing_files": &result.missing_files,
+            "corrupted_files": &result.corrupted_files,
+        }),
+    )?;
+    if result.ok {
+        return Ok(());
+    }
+    Err(EngineError::InstallFailed(format!(
+        "pack integrity check failed: {pack_id}: missing [{}]; corrupted [{}]",
+        result.missing_files.join(", "),
+        result.corrupted_files.join(", ")
+    )))
+}
+
+fn spleeter_separate_command(
+    paths: &AppPaths,
+    venv_python: &Path,
+    script_path: &Path,
+    audio_path: &Path,
+    raw_dir: &Path,
+    config: &str,
+) -> std::process::Command {
+    let mut cmd = cmd::sanitized_command(venv_python);
+    cmd.arg(script_path);
+    cmd.arg("--input").arg(audio_path);
+    cmd.arg("--output").arg(raw_dir);
+    cmd.arg("--config").arg(config);
+    cmd.env("PYTHONNOUSERSITE", "1");
+    cmd.env(
+        "XDG_CACHE_HOME",
+        paths
+            .cache_dir()
+            .join("python")
+            .to_string_lossy()
+            .to_string(),
+    );
+    cmd.env(
+        "MODEL_PATH",
+        paths
+            .python_models_dir()
+            .join("spleeter")
+            .to_string_lossy()
+            .to_string(),
+    );
+    cmd
+}
+
+/// Returns the audio fed to a separation backend: the validated override when set, otherwise
+/// `mix_44k.wav` extracted from the item's media into `sep_dir`.
+fn separation_input_audio(
+    paths: &AppPaths,
+    job_id: &str,
+    item: &library::LibraryItem,
+    sep_dir: &Path,
+    audio_override: Option<&Path>,
+) -> Result<PathBuf> {
+    if let Some(path) = audio_override {
+        log_line(
+            paths,
+            job_id,
+            "info",
+            "separate_audio_override",
+            serde_json::json!({ "audio_path": path }),
+        )?;
+        return Ok(path.to_path_buf());
+    }
+    let audio_path = sep_dir.join("mix_44k.wav");
+    log_line(
+        paths,
+        job_id,
+        "info",
+        "separate_extract_audio_begin",
+        serde_json::json!({ "path": &item.media_path, "audio_path": &audio_path }),
+    )?;
+    if std::fs::metadata(&audio_path).map(|m| m.len()).unwrap_or(0) > 0 {
+        log_line(
+            paths,
+            job_id,
+            "info",
+            "separate_extract_audio_resume_skip_existing",
+            serde_json::json!({ "audio_path": &audio_path }),
+        )?;
+    } else {
+        ffmpeg::extract_audio_wav_resampled(
+            paths,
+            Path::new(&item.media_path),
+            &audio_path,
+            SEPARATION_INPUT_SAMPLE_RATE_HZ,
+            SEPARATION_INPUT_CHANNELS,
+        )?;
+    }
+    Ok(audio_path)
+}
+
+fn separation_audio_source(audio_override: Option<&Path>) -> &'static str {
+    if audio_override.is_some() {
+        "override"
+    } else {
+        "extracted"
+    }
+}
+
+/// Validates a separation `audio_path_override`: an absolute path to an existing audio file.
+fn resolve_separation_audio_override(raw: Option<&str>) -> Result<Option<PathBuf>> {
+    let Some(raw) = normalize_non_empty(raw) else {
+        return Ok(None);
+    };
+    let path = PathBuf::from(&raw);
+    if !path.is_absolute() {
+        return Err(EngineError::InstallFailed(format!(
+            "audio override must be an absolute path: {raw}"
+        )));
+    }
+    let ext = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .unwrap_or("")
+        .to_ascii_lowercase();
+    if !library::AUDIO_ONLY_EXTENSIONS.contains(&ext.as_str()) {
+        return Err(EngineError::InstallFailed(format!(
+            "audio override is not a supported audio file: {raw}"
+        )));
+    }
+    if !path.is_file() {
+        return Err(EngineError::InstallFailed(format!(
+            "audio override not found: {raw}"
+        )));
+    }
+    Ok(Some(path))
+}
+
+/// Output of one Spleeter run shared by the 2-stem and 5-stem flows.
+struct SpleeterRun {
+    audio_path: PathBuf,
+    raw_dir: PathBuf,
+    output: std::process::Output,
+}
+
+/// Extracts the separation input into `sep_dir` and runs Spleeter's Python API with `config`
+/// (`spleeter:2stems` or `spleeter:5stems`), leaving the raw stems under `sep_dir/raw`. Returns
+/// `None` when the job was canceled before Spleeter started.
+fn run_spleeter_model(
+    paths: &AppPaths,
+    job_id: &str,
+    item: &library::LibraryItem,
+    sep_dir: &Path,
+    audio_override: Option<&Path>,
+    config: &str,
+) -> Result<Option<SpleeterRun>> {
+    let audio_path = separation_input_audio(paths, job_id, item, sep_dir, audio_override)?;
+    set_progress(paths, job_id, 0.25)?;
+
+    if is_canceled(paths, job_id)? {
+        log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
+        return Ok(None);
+    }
+
+    let venv_python = tools::python_venv_python_path(paths).map_err(|_| {
+        EngineError::InstallFailed(
+            "Python toolchain is not set up. Open Diagnostics -> Tools -> Setup Python toolchain."
+                .to_string(),
+        )
+    })?;
+
+    let raw_dir = sep_dir.join("raw");
+    std::fs::create_dir_all(&raw_dir)?;
+    log_line(
+        paths,
+        job_id,
+        "info",
+        "separate_spleeter_begin",
+        serde_json::json!({ "audio_path": &audio_path, "raw_dir": &raw_dir, "config": config }),
+    )?;
+
+    // Use Spleeter's Python API instead of the CLI entrypoint.
+    let sep_script_path = sep_dir.join("spleeter_separate.py");
+    std::fs::write(&sep_script_path, SPLEETER_SEPARATE_SCRIPT)?;
+    let output = {
+        let mut cmd = spleeter_separate_command(
+            paths,
+            &venv_python,
+            &sep_script_path,
+            &audio_path,
+            &raw_dir,
+            config,
+        );
+        let old_path = std::env::var_os("PATH").unwrap_or_default();
+        cmd.env(
+            "PATH",
+            format!(
+                "{};{}",
+                paths.ffmpeg_dir().to_string_lossy(),
+                old_path.to_string_lossy()
+            ),
+        );
+        run_python_tool(cmd, &JobType::SeparateAudioSpleeter, "spleeter")?
+    };
+    if !output.status.success() {
+        return Err(python_tool_failed(paths, job_id, "spleeter", &output));
+    }
+
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if !stderr.trim().is_empty() {
+        log_line(
+            paths,
+            job_id,
+            "warn",
+            "separate_spleeter_warning",
+            serde_json::json!({ "stderr": stderr.trim() }),
+        )?;
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    if !stdout.trim().is_empty() {
+        log_line(
+            paths,
+            job_id,
+            "info",
+            "separate_spleeter_stdout",
+            serde_json::json!({ "stdout": stdout.trim() }),
+        )?;
+    }
+
+    Ok(Some(SpleeterRun {
+        audio_path,
+        raw_dir,
+        output,
+    }))
+}
+
+/// Moves one raw Spleeter stem to its final path, replacing any previous output.
+fn move_spleeter_stem(src: &Path, dst: &Path) -> Result<()> {
+    if dst.exists() {
+        let _ = std::fs::remove_file(dst);
+    }
+    if std::fs::rename(src, dst).is_err() {
+        std::fs::copy(src, dst)?;
+        let _ = std::fs::remove_file(src);
+    }
+    Ok(())
+}
+
+/// Writes `separation/spleeter_5stems/{vocals,drums,bass,piano,other}.wav`.
+fn run_separate_audio_spleeter_5stems(
+    paths: &AppPaths,
+    job_id: &str,
+    item: &library::LibraryItem,
+    audio_override: Option<&Path>,
+) -> Result<()> {
+    let sep_dir = paths
+        .derived_item_dir(&item.id)
+        .join("separation")
+        .join("spleeter_5stems");
+    std::fs::create_dir_all(&sep_dir)?;
+
+    let stem_dsts: Vec<PathBuf> = SPLEETER_5STEMS
+        .iter()
+        .map(|stem| sep_dir.join(format!("{stem}.wav")))
+        .collect();
+    if audio_override.is_none()
+        && stem_dsts
+            .iter()
+            .all(|path| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0) > 0)
+    {
+        set_progress(paths, job_id, 1.0)?;
+        log_line(
+            paths,
+            job_id,
+            "info",
+            "separate_resume_skip_existing",
+            serde_json::json!({ "stem_paths": &stem_dsts }),
+        )?;
+        return Ok(());
+    }
+
+    let Some(SpleeterRun { raw_dir, .. }) = run_spleeter_model(
+        paths,
+        job_id,
+        item,
+        &sep_dir,
+        audio_override,
+        "spleeter:5stems",
+    )?
+    else {
+        return Ok(());
+    };
+
+    let mut stem_srcs: Vec<Option<PathBuf>> = vec![None; SPLEETER_5STEMS.len()];
+    let mut stack: Vec<PathBuf> = vec![raw_dir.clone()];
+    while let Some(dir) = stack.pop() {
+        let entries = match std::fs::read_dir(&dir) {
+            Ok(v) => v,
+            Err(_) => continue,
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.is_dir() {
+                stack.push(path);
+                continue;
+            }
+            let name = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .unwrap_or("")
+                .to_lowercase();
+            if let Some(idx) = SPLEETER_5STEMS
+                .iter()
+                .position(|stem| name == format!("{stem}.wav"))
+            {
+                stem_srcs[idx] = Some(path);
+            }
+        }
+    }
+
+    for ((stem, src), dst) in SPLEETER_5STEMS.iter().zip(stem_srcs).zip(&stem_dsts) {
+        let src = src.ok_or_else(|| {
+            EngineError::InstallFailed(format!("spleeter output not found ({stem}.wav)"))
+        })?;
+        move_spleeter_stem(&src, dst)?;
+    }
+    let _ = std::fs::remove_dir_all(&raw_dir);
+
+    set_progress(paths, job_id, 0.95)?;
+    log_line(
+        paths,
+        job_id,
+        "info",
+        "separate_done",
+        serde_json::json!({ "stem_paths": &stem_dsts }),
+    )?;
+    Ok(())
+}
+
+fn sha256_file_hex(path: &Path) -> Result<String> {
+    let mut file = std::fs::File::open(path)?;
+    let mut hasher = Sha256::new();
+    let mut buf = vec![0u8; 64 * 1024];
+    loop {
+        let n = file.read(&mut buf)?;
+        if n == 0 {
+            break;
+        }
+        hasher.update(&buf[..n]);
+    }
+    Ok(hex::encode(hasher.finalize()))
+}
+
+/// Updates `known` from the current contents of `dir` (top level only) and returns the files to
+/// import. With `baseline`, existing files are recorded without being imported. New or modified
+/// files are imported one scan after their size and mtime stop changing, and only when their
+/// hash differs from the last imported content.
+fn scan_watch_directory(
+    dir: &Path,
+    extensions: &[String],
+    known: &mut std::collections::BTreeMap<String, WatchedFileState>,
+    baseline: bool,
+) -> Result<Vec<PathBuf>> {
+    let mut present: HashSet<String> = HashSet::new();
+    let mut to_import: Vec<PathBuf> = Vec::new();
+    for entry in std::fs::read_dir(dir)?.flatten() {
+        let path = entry.path();
+        let Ok(meta) = entry.metadata() else {
+            continue;
+        };
+        if !meta.is_file() {
+            continue;
+        }
+        let ext = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        if !extensions.contains(&ext) {
+            continue;
+        }
+        let key = path.to_string_lossy().to_string();
+        present.insert(key.clone());
+        let size_bytes = meta.len();
+        let modified_ms = meta
+            .modified()
+            .ok()
+            .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+            .map(|d| d.as_millis() as i64)
+            .unwrap_or(0);
+
+        if baseline {
+            let sha256 = sha256_file_hex(&path).ok();
+            known.insert(
+                key,
+                WatchedFileState {
+                    size_bytes,
+                    modified_ms,
+                    sha256,
+                    pending: false,
+                },
+            );
+            continue;
+        }
+
+        let previous = known.get(&key).cloned();
+        match previous {
+            Some(prev) if prev.size_bytes == size_bytes && prev.modified_ms == modified_ms => {
+                if !prev.pending || size_bytes == 0 {
+                    continue;
+                }
+                let Ok(sha256) = sha256_file_hex(&path) else {
+                    continue;
+                };
+                if prev.sha256.as_deref() != Some(sha256.as_str()) {
+                    to_import.push(path.clone());
+                }
+                known.insert(
+                    key,
+                    WatchedFileState {
+                        size_bytes,
+                        modified_ms,
+                        sha256: Some(sha256),
+                        pending: false,
+                    },
+                );
+            }
+            previous => {
+                known.insert(
+                    key,
+                    WatchedFileState {
+                        size_bytes,
+                        modified_ms,
+                        sha256: previous.and_then(|prev| prev.sha256),
+                        pending: true,
+                    },
+                );
+            }
+        }
+    }
+    known.retain(|key, _| present.contains(key));
+    to_import.sort();
+    Ok(to_import)
+}
+
+/// Scans until the job is canceled; the job keeps its worker slot while it runs.
+fn run_watch_directory(paths: &AppPaths, job_id: &str, p: &WatchDirectoryParams) -> Result<()> {
+    let dir = PathBuf::from(&p.dir_path);
+    let artifacts_dir = job_artifacts_dir_for_id(paths, job_id)?;
+    std::fs::create_dir_all(&artifacts_dir)?;
+    let known_path = artifacts_dir.join("known_files.json");
+    let mut baseline = !known_path.exists();
+    let mut known: std::collections::BTreeMap<String, WatchedFileState> = if baseline {
+        Default::default()
+    } else {
+        serde_json::from_slice(&std::fs::read(&known_path)?).unwrap_or_default()
+    };
+    let interval_secs = p.interval_secs.clamp(
+        WATCH_DIRECTORY_MIN_INTERVAL_SECS,
+        WATCH_DIRECTORY_MAX_INTERVAL_SECS,
+    );
+
+    log_line(
+        paths,
+        job_id,
+        "info",
+        "watch_directory_begin",
+        serde_json::json!({
+            "dir_path": &p.dir_path,
+            "extensions": &p.extensions,
+            "interval_secs": interval_secs,
+            "known_files": known.len(),
+        }),
+    )?;
+
+    loop {
+        if is_canceled(paths, job_id)? {
+            log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
+            return Ok(());
+        }
+
+        match scan_watch_directory(&dir, &p.extensions, &mut known, baseline) {
+            Ok(new_files) => {
+                for file in new_files {
+                    let path = file.to_string_lossy().to_string();
+                    match enqueue_import_local(paths, path.clone(), false, true) {
+                        Ok(job) => log_line(
+                            paths,
+                            job_id,
+                            "info",
+                            "watch_directory_import_queued",
+                            serde_json::json!({ "path": path, "import_job_id": job.id }),
+                        )?,
+                        Err(e) => log_line(
+                            paths,
+                            job_id,
+                            "warn",
+                            "watch_directory_import_failed",
+                            serde_json::json!({ "path": path, "error": e.to_string() }),
+                        )?,
+                    }
+                }
+                baseline = false;
+                persistence::atomic_write_text(
+                    &known_path,
+                    &format!("{}\n", serde_json::to_string_pretty(&known)?),
+                )?;
+            }
+            Err(e) => {
+                log_line(
+                    paths,
+                    job_id,
+                    "warn",
+                    "watch_directory_scan_failed",
+                    serde_json::json!({ "dir_path": &p.dir_path, "error": e.to_string() }),
+                )?;
+            }
+        }
+
+        for _ in 0..interval_secs {
+            if is_canceled(paths, job_id)? {
+                break;
+            }
+            thread::sleep(Duration::from_secs(1));
+        }
+    }
+}
+
 fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str) -> Result<()> {
-    let artifacts_dir = paths.job_artifacts_dir(job_id);
+    let artifacts_dir = job_artifacts_dir_for_id(paths, job_id)?;
     std::fs::create_dir_all(&artifacts_dir)?;
 
     if is_canceled(paths, job_id)? {
@@ -3918,6 +8037,10 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
     let job_type = JobType::from_str(type_str)
         .ok_or_else(|| EngineError::InstallFailed(format!("unknown job type in db: {type_str}")))?;
 
+    if let Some(pack_id) = python_pack_for_job_type(&job_type) {
+        verify_pack_integrity_on_job_start(paths, job_id, pack_id)?;
+    }
+
     match job_type {
         JobType::ImportLocal => {
             set_progress(paths, job_id, 0.05)?;
@@ -3928,13 +8051,21 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                 return Ok(());
             }
 
+            let validation = library::validate_importable_media(paths, Path::new(&p.path));
             log_line(
                 paths,
                 job_id,
-                "info",
+                if validation.is_ok() { "info" } else { "error" },
                 "import_local_begin",
-                serde_json::json!({ "path": p.path }),
+                serde_json::json!({
+                    "path": p.path,
+                    "validation": match &validation {
+                        Ok(report) => serde_json::json!(report),
+                        Err(e) => serde_json::json!({ "error": e.to_string() }),
+                    },
+                }),
             )?;
+            validation?;
             set_progress(paths, job_id, 0.15)?;
             log_line(
                 paths,
@@ -3947,7 +8078,28 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                 }),
             )?;
 
-            let item = library::import_local_file(paths, Path::new(&p.path))?;
+            let imported = library::import_local_file(paths, Path::new(&p.path))?;
+            let item = imported.item;
+            for sidecar in imported.sidecar_subtitles {
+                log_line(
+                    paths,
+                    job_id,
+                    if sidecar.error.is_some() {
+                        "warn"
+                    } else {
+                        "info"
+                    },
+                    "import_local_sidecar_subtitle",
+                    serde_json::json!({
+                        "item_id": &item.id,
+                        "path": &sidecar.sidecar.path,
+                        "lang": &sidecar.sidecar.lang,
+                        "format": &sidecar.sidecar.format,
+                        "track_id": &sidecar.track_id,
+                        "error": &sidecar.error,
+                    }),
+                )?;
+            }
             set_progress(paths, job_id, 0.75)?;
 
             // Associate created item id.
@@ -4011,17 +8163,28 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
             }
 
             // Optional: batch-on-import automation (local-only; off by default).
-            let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
+            let rules = batch_on_import_rules_for_job(paths, job_id);
             let any_enabled = rules.auto_asr
                 || rules.auto_translate
                 || rules.auto_separate
                 || rules.auto_diarize
-                || rules.auto_dub_preview;
+                || rules.auto_dub_preview
+                || rules.auto_cleanup_vocals;
             if any_enabled && p.apply_batch_on_import {
-                let batch_id = job_batch_id(paths, job_id)
-                    .ok()
-                    .flatten()
-                    .or_else(|| Some(Uuid::new_v4().to_string()));
+                // Chained jobs inherit the import's batch id, so give it one if it has none.
+                let batch_id = match job_batch_id(paths, job_id).ok().flatten() {
+                    Some(batch_id) => batch_id,
+                    None => {
+                        let batch_id = Uuid::new_v4().to_string();
+                        let conn = db::open(paths)?;
+                        db::migrate(&conn)?;
+                        conn.execute(
+                            "UPDATE job SET batch_id=?1 WHERE id=?2",
+                            params![&batch_id, job_id],
+                        )?;
+                        batch_id
+                    }
+                };
                 log_line(
                     paths,
                     job_id,
@@ -4035,6 +8198,11 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                             "auto_separate": rules.auto_separate,
                             "auto_diarize": rules.auto_diarize,
                             "auto_dub_preview": rules.auto_dub_preview,
+                            "auto_cleanup_vocals": rules.auto_cleanup_vocals,
+                            "auto_mux": rules.auto_mux,
+                            "auto_export_pack": rules.auto_export_pack,
+                            "tts_backend": &rules.tts_backend,
+                            "asr_model_id": &rules.asr_model_id,
                         }
                     }),
                 )?;
@@ -4043,36 +8211,47 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                     || rules.auto_translate
                     || rules.auto_diarize
                     || rules.auto_dub_preview;
-                let needs_separate = rules.auto_separate || rules.auto_dub_preview;
+                let needs_separate =
+                    rules.auto_separate || rules.auto_dub_preview || rules.auto_cleanup_vocals;
 
-                if needs_separate {
-                    let params_json = serde_json::to_string(&SeparateAudioSpleeterParams {
+                let separate_params_json = if needs_separate {
+                    Some(serde_json::to_string(&SeparateAudioSpleeterParams {
                         item_id: item.id.clone(),
                         batch_on_import: true,
-                    })?;
-                    let _ = enqueue_with_type_item_and_batch_id(
-                        paths,
-                        JobType::SeparateAudioSpleeter,
-                        params_json,
-                        Some(item.id.clone()),
-                        batch_id.clone(),
-                    )?;
-                }
+                        stems: 2,
+                        audio_path_override: None,
+                    })?)
+                } else {
+                    None
+                };
 
                 if needs_asr {
+                    // Separation waits for ASR instead of competing with it for the same item.
+                    let on_complete = separate_params_json
+                        .map(|params_json| {
+                            JobDependency::always(JobType::SeparateAudioSpleeter, params_json)
+                        })
+                        .into_iter()
+                        .collect();
                     let params_json = serde_json::to_string(&AsrLocalParams {
                         item_id: item.id.clone(),
                         lang: None,
-                        model_id: "whispercpp-tiny".to_string(),
+                        model_id: batch_on_import_asr_model_id(&rules),
                         batch_on_import: true,
                         pipeline: None,
+                        task: None,
                     })?;
-                    let _ = enqueue_with_type_item_and_batch_id(
+                    chain_on_complete(
                         paths,
-                        JobType::AsrLocal,
-                        params_json,
-                        Some(item.id.clone()),
-                        batch_id.clone(),
+                        job_id,
+                        JobDependency::on_success(JobType::AsrLocal, params_json)
+                            .with_on_complete(on_complete),
+                    )?;
+                } else if let Some(params_json) = separate_params_json {
+                    chain_on_complete(
+                        paths,
+                        job_id,
+                        JobDependency::on_success(JobType::SeparateAudioSpleeter, params_json),
                     )?;
                 }
             } else if any_enabled {
@@ -4089,6 +8268,11 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                             "auto_separate": rules.auto_separate,
                             "auto_diarize": rules.auto_diarize,
                             "auto_dub_preview": rules.auto_dub_preview,
+                            "auto_cleanup_vocals": rules.auto_cleanup_vocals,
+                            "auto_mux": rules.auto_mux,
+                            "auto_export_pack": rules.auto_export_pack,
+                            "tts_backend": &rules.tts_backend,
+                            "asr_model_id": &rules.asr_model_id,
                         }
                     }),
                 )?;
@@ -4100,7 +8284,7 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
             let subscription_id = p.subscription_id.clone();
             let url = normalize_direct_url(&p.url)?;
             let provider = effective_download_provider(&p.provider, &url);
-            let auth_cookie = if let Some(secret) = read_job_cookie_secret(paths, job_id) {
+            let auth_cookie = if let Some(secret) = read_job_cookie_secret(paths, job_id)? {
                 Some(secret)
             } else if let Some(inline) = normalize_auth_cookie(p.auth_cookie)? {
                 Some(inline)
@@ -4122,6 +8306,7 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                 return Ok(());
             }
 
+            let cookie_validation = auth_cookie.as_deref().map(validate_cookie_format);
             log_line(
                 paths,
                 job_id,
@@ -4129,25 +8314,92 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                 "download_direct_url_begin",
                 serde_json::json!({
                     "url": redact_url_for_log(&url),
-                    "provider": provider
+                    "provider": provider,
+                    "cookie_validation": cookie_validation,
                 }),
             )?;
+            if let Some(validation) = cookie_validation
+                .as_ref()
+                .filter(|v| !v.warnings.is_empty())
+            {
+                log_line(
+                    paths,
+                    job_id,
+                    "warning",
+                    "auth_cookie_format_warning",
+                    serde_json::json!({ "warnings": validation.warnings }),
+                )?;
+            }
+
+            let disk_check_dir = match output_dir.as_deref() {
+                Some(dir) => PathBuf::from(dir),
+                None => paths.effective_download_dir()?,
+            };
+            warn_if_download_disk_space_low(paths, job_id, &disk_check_dir)?;
+
+            if let Some(shortcode) = instagram_shortcode_from_url(&url) {
+                let media_id = p
+                    .media_id
+                    .clone()
+                    .or_else(|| instagram_shortcode_to_media_id(&shortcode));
+                let thumbnail_ok = match media_id.as_deref() {
+                    Some(media_id) => instagram_pre_fetch_thumbnail(
+                        paths,
+                        job_id,
+                        &url,
+                        media_id,
+                        auth_cookie.as_deref(),
+                    )
+                    .is_ok(),
+                    None => false,
+                };
+                log_line(
+                    paths,
+                    job_id,
+                    "info",
+                    "instagram_pre_fetch",
+                    serde_json::json!({
+                        "shortcode": shortcode,
+                        "media_id": media_id,
+                        "thumbnail_ok": thumbnail_ok,
+                    }),
+                )?;
+            }
 
             let downloaded_path = download_url_to_library(
                 paths,
                 &url,
                 job_id,
                 provider,
-                auth_cookie.as_deref(),
-                output_dir.as_deref(),
-                output_subdir.as_deref(),
-                use_browser_cookies,
-                p.output_path_template.as_deref(),
-                p.filename_template.as_deref(),
-                p.format_preference.as_deref(),
-                p.quality_preference.as_deref(),
-                p.subtitle_mode.as_deref(),
+                &UrlDownloadOptions {
+                    auth_cookie: auth_cookie.as_deref(),
+                    output_dir: output_dir.as_deref(),
+                    output_subdir: output_subdir.as_deref(),
+                    use_browser_cookies,
+                    output_path_template: p.output_path_template.as_deref(),
+                    filename_template: p.filename_template.as_deref(),
+                    format_preference: p.format_preference.as_deref(),
+                    quality_preference: p.quality_preference.as_deref(),
+                    subtitle_mode: p.subtitle_mode.as_deref(),
+                    output_filename_template: p.output_filename_template.as_deref(),
+                    download_archive: p.write_archive.as_deref(),
+                    write_info_json: p.write_info_json,
+                },
             )?;
+            let Some(downloaded_path) = downloaded_path else {
+                set_progress(paths, job_id, 1.0)?;
+                log_line(
+                    paths,
+                    job_id,
+                    "info",
+                    "download_direct_url_skipped_archived",
+                    serde_json::json!({
+                        "url": redact_url_for_log(&url),
+                        "download_archive": &p.write_archive,
+                    }),
+                )?;
+                return Ok(());
+            };
             set_progress(paths, job_id, 0.70)?;
 
             if is_canceled(paths, job_id)? {
@@ -4155,7 +8407,7 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                 return Ok(());
             }
 
-            let item = library::import_downloaded_file(
+            let imported = library::import_downloaded_file(
                 paths,
                 &downloaded_path,
                 &url,
@@ -4163,6 +8415,30 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                 provider,
                 now_ms(),
             )?;
+            let item = imported.item;
+            // Metadata only enriches a freshly imported item; a bad info.json must not fail a
+            // download that already completed.
+            let yt_dlp_metadata = if p.write_info_json && !imported.was_duplicate {
+                let info_json_path = yt_dlp_info_json_path(&downloaded_path);
+                match library::import_yt_dlp_info_json(paths, &item.id, &info_json_path) {
+                    Ok(metadata) => metadata,
+                    Err(err) => {
+                        log_line(
+                            paths,
+                            job_id,
+                            "warning",
+                            "yt_dlp_info_json_import_failed",
+                            serde_json::json!({
+                                "path": &info_json_path,
+                                "error": err.to_string(),
+                            }),
+                        )?;
+                        None
+                    }
+                }
+            } else {
+                None
+            };
             set_progress(paths, job_id, 1.0)?;
 
             if let Some(sub_id) = subscription_id.as_deref() {
@@ -4194,14 +8470,20 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                 "download_direct_url_done",
                 serde_json::json!({
                     "item_id": item.id,
-                    "path": downloaded_path.to_string_lossy().to_string()
+                    "path": downloaded_path.to_string_lossy().to_string(),
+                    "was_duplicate": imported.was_duplicate,
+                    "yt_dlp_metadata_imported": yt_dlp_metadata.as_ref().map(|m| serde_json::json!({
+                        "title": m.title,
+                        "uploader": m.uploader,
+                        "upload_date": m.upload_date,
+                    })),
                 }),
             )?;
         }
         JobType::YoutubeSubscriptionRefreshV1 => {
             set_progress(paths, job_id, 0.05)?;
             let p: YoutubeSubscriptionRefreshV1Params = serde_json::from_str(params_json)?;
-            let auth_cookie = read_job_cookie_secret(paths, job_id)
+            let auth_cookie = read_job_cookie_secret(paths, job_id)?
                 .or_else(|| resolve_global_youtube_auth_cookie(paths));
 
             if is_canceled(paths, job_id)? {
@@ -4210,16 +8492,31 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                 return Ok(());
             }
 
+            let mut new_items_seen: Option<subscriptions::RefreshItemsSeen> = None;
             let refresh_result: Result<()> = (|| {
                 let sub = subscriptions::get_youtube_subscription_by_id(paths, &p.subscription_id)?
-                    .ok_or_else(|| {
-                        EngineError::InstallFailed(format!(
-                            "subscription not found: {}",
-                            p.subscription_id
-                        ))
-                    })?;
-
-                let max_items = p.max_items.unwrap_or(200).clamp(1, MAX_DOWNLOAD_BATCH_URLS);
+                    .ok_or_else(|| EngineError::not_found("subscription", &p.subscription_id))?;
+
+                let mut max_items = sub
+                    .max_items_per_refresh
+                    .or(p.max_items)
+                    .unwrap_or(200)
+                    .clamp(1, MAX_DOWNLOAD_BATCH_URLS);
+                if sub.auto_max_items {
+                    let auto = subscriptions::compute_auto_max_items(&sub, max_items, now_ms());
+                    max_items = auto.value;
+                    log_line(
+                        paths,
+                        job_id,
+                        "info",
+                        "auto_max_items_computed",
+                        serde_json::json!({
+                            "value": auto.value,
+                            "avg_posts_per_day": auto.avg_posts_per_day,
+                            "days_since_last": auto.days_since_last,
+                        }),
+                    )?;
+                }
                 let output_dir = subscriptions::youtube_subscription_output_dir(paths, &sub)?;
                 std::fs::create_dir_all(&output_dir)?;
 
@@ -4268,7 +8565,54 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                         skipped_archived += 1;
                         continue;
                     }
-                    new_urls.push(candidate);
+                    new_urls.push(candidate);
+                }
+                new_items_seen = Some(subscriptions::RefreshItemsSeen {
+                    new_items: new_urls.len(),
+                    max_items,
+                });
+
+                let mut skipped_filtered = 0_usize;
+                let has_title_filters = !sub.filter_keywords_exclude.is_empty()
+                    || sub
+                        .filter_keywords_include
+                        .as_ref()
+                        .is_some_and(|v| !v.is_empty());
+                if has_title_filters && !new_urls.is_empty() {
+                    let titles = expand_yt_dlp_titles(
+                        paths,
+                        &sub.source_url,
+                        max_items,
+                        auth_cookie.as_deref(),
+                        use_browser_cookies_for_url(
+                            &sub.source_url,
+                            sub.use_browser_cookies && auth_cookie.is_none(),
+                        ),
+                    )?;
+                    let mut kept: Vec<String> = Vec::with_capacity(new_urls.len());
+                    for candidate in new_urls {
+                        let video_id = subscriptions::youtube_video_id_from_url(&candidate)
+                            .unwrap_or_default();
+                        let title = titles.get(&video_id).map(String::as_str).unwrap_or("");
+                        match subscriptions::title_filter_skip_reason(&sub, title) {
+                            Some(reason) => {
+                                skipped_filtered += 1;
+                                log_line(
+                                    paths,
+                                    job_id,
+                                    "info",
+                                    "youtube_subscription_video_filtered",
+                                    serde_json::json!({
+                                        "video_id": video_id,
+                                        "title": title,
+                                        "reason": reason,
+                                    }),
+                                )?;
+                            }
+                            None => kept.push(candidate),
+                        }
+                    }
+                    new_urls = kept;
                 }
 
                 if new_urls.is_empty() {
@@ -4281,6 +8625,7 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                         serde_json::json!({
                             "queued": 0,
                             "skipped_archived": skipped_archived,
+                            "skipped_filtered": skipped_filtered,
                         }),
                     )?;
                     return Ok(());
@@ -4296,6 +8641,10 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                     sub.preset_id.clone(),
                     Some(job_id.to_string()),
                     Some(sub.id.clone()),
+                    None,
+                    None,
+                    None,
+                    Some(job_id),
                 )?;
                 set_progress(paths, job_id, 1.0)?;
 
@@ -4307,6 +8656,7 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                     serde_json::json!({
                         "queued": queued.len(),
                         "skipped_archived": skipped_archived,
+                        "skipped_filtered": skipped_filtered,
                         "archive_path": archive_path.to_string_lossy().to_string(),
                     }),
                 )?;
@@ -4319,6 +8669,7 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                     let _ = subscriptions::record_subscription_refresh_success(
                         paths,
                         &p.subscription_id,
+                        new_items_seen,
                     );
                 }
                 Err(err) => {
@@ -4326,6 +8677,7 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                     let _ = subscriptions::record_subscription_refresh_failure(
                         paths,
                         &p.subscription_id,
+                        &err.to_string(),
                     );
                     return Err(err);
                 }
@@ -4334,7 +8686,7 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
         JobType::DownloadImageBatch => {
             set_progress(paths, job_id, 0.05)?;
             let p: DownloadImageBatchParams = serde_json::from_str(params_json)?;
-            let auth_cookie = read_job_cookie_secret(paths, job_id).or(p.auth_cookie);
+            let auth_cookie = read_job_cookie_secret(paths, job_id)?.or(p.auth_cookie);
             remove_job_cookie_secret(paths, job_id);
 
             if is_canceled(paths, job_id)? {
@@ -4371,12 +8723,18 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                     "delay_ms": p.delay_ms,
                     "allow_cross_domain": p.allow_cross_domain,
                     "follow_content_links": p.follow_content_links,
+                    "file_extensions_filter": &p.file_extensions_filter,
+                    "min_image_pixels": p.min_image_pixels,
+                    "max_image_pixels": p.max_image_pixels,
                     "output_subdir": if output_dir_override.is_some() { serde_json::Value::Null } else { serde_json::Value::String(effective_subdir.clone()) },
                     "output_dir": output_root.to_string_lossy().to_string(),
                 }),
             )?;
+            warn_if_download_disk_space_low(paths, job_id, &output_root)?;
 
             let manifest_path = artifacts_dir.join("image_manifest.csv");
+            let (min_image_pixels, max_image_pixels) =
+                image_batch::normalize_image_pixel_bounds(p.min_image_pixels, p.max_image_pixels)?;
             let request = image_batch::ImageBatchRequest {
                 start_urls: p.start_urls,
                 max_pages: p.max_pages,
@@ -4394,6 +8752,15 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                     effective_subdir
                 },
                 auth_cookie,
+                max_hamming_distance: image_batch::normalize_max_hamming_distance(
+                    p.max_hamming_distance,
+                ),
+                max_image_bytes: image_batch::normalize_max_image_bytes(p.max_image_bytes),
+                file_extensions_filter: image_batch::normalize_file_extensions_filter(
+                    p.file_extensions_filter,
+                )?,
+                min_image_pixels,
+                max_image_pixels,
             };
 
             let summary = image_batch::run_image_batch_download(
@@ -4428,6 +8795,11 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                     "duplicates": summary.duplicate_images,
                     "skipped_profile_images": summary.skipped_profile_images,
                     "failed_images": summary.failed_images,
+                    "rejected_wrong_mime": summary.rejected_wrong_mime,
+                    "rejected_bad_size": summary.rejected_bad_size,
+                    "filtered_by_extension": summary.filtered_by_extension,
+                    "rejected_too_small_px": summary.rejected_too_small_px,
+                    "rejected_too_large_px": summary.rejected_too_large_px,
                     "manifest_path": summary.manifest_path,
                     "output_dir": summary.output_dir,
                     "summary_path": summary_path,
@@ -4443,12 +8815,13 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                 return Ok(());
             }
 
+            let translate_task = p.task.as_deref() == Some(ASR_TASK_TRANSLATE);
             log_line(
                 paths,
                 job_id,
                 "info",
                 "asr_begin",
-                serde_json::json!({ "item_id": &p.item_id, "lang": &p.lang, "model_id": &p.model_id }),
+                serde_json::json!({ "item_id": &p.item_id, "lang": &p.lang, "model_id": &p.model_id, "task": &p.task }),
             )?;
 
             let item = library::get_item_by_id(paths, &p.item_id)?;
@@ -4458,6 +8831,7 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
             std::fs::create_dir_all(&asr_dir)?;
 
             let audio_path = asr_dir.join("audio_16k.wav");
+            let _ = record_step_start(paths, job_id, "extract_audio");
             log_line(
                 paths,
                 job_id,
@@ -4478,6 +8852,7 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
             } else {
                 ffmpeg::extract_audio_wav_16k_mono(paths, media_path, &audio_path)?;
             }
+            let _ = record_step_done(paths, job_id, "extract_audio");
             set_progress(paths, job_id, 0.25)?;
 
             if is_canceled(paths, job_id)? {
@@ -4485,6 +8860,7 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                 return Ok(());
             }
 
+            let _ = record_step_start(paths, job_id, "transcribe");
             log_line(
                 paths,
                 job_id,
@@ -4492,13 +8868,33 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                 "asr_transcribe_begin",
                 serde_json::json!({ "model_id": &p.model_id, "lang": &p.lang, "audio_path": &audio_path }),
             )?;
-            let result = asr::transcribe_whisper_wav_16k_mono_with_stats(
-                paths,
-                &p.model_id,
-                &audio_path,
-                p.lang.as_deref(),
-            )?;
+            let result = if translate_task {
+                asr::translate_whisper_wav_16k_mono_to_en_with_stats(
+                    paths,
+                    &p.model_id,
+                    &audio_path,
+                    p.lang.as_deref(),
+                )?
+            } else {
+                // Map whisper's 0..1 onto this step's 25%..85% band, writing at most every 1%.
+                let last_reported = std::cell::Cell::new(0.25_f32);
+                let report = |fraction: f32| {
+                    let progress = 0.25 + 0.60 * fraction;
+                    if progress - last_reported.get() >= 0.01 {
+                        last_reported.set(progress);
+                        let _ = set_progress(paths, job_id, progress);
+                    }
+                };
+                asr::transcribe_whisper_wav_16k_mono_with_stats(
+                    paths,
+                    &p.model_id,
+                    &audio_path,
+                    p.lang.as_deref(),
+                    Some(report),
+                )?
+            };
             let doc = result.doc;
+            let _ = record_step_done(paths, job_id, "transcribe");
             set_progress(paths, job_id, 0.85)?;
 
             if is_canceled(paths, job_id)? {
@@ -4535,9 +8931,15 @@ fn execute_job(paths: &AppPaths, job_id: &str, type_str: &str, params_json: &str
                 return Err(EngineError::InstallFailed(message));
             }
 
-            let json_path = asr_dir.join("source.json");
-            let srt_path = asr_dir.join("source.srt");
-            let vtt_path = asr_dir.join("source.vtt");
+            let _ = record_step_start(paths, job_id, "save_track");
+            let (track_kind, file_stem) = if translate_task {
+                ("translated", "translated_en")
+            } else {
+                ("source", "source")
+            };
+            let json_path = asr_dir.join(format!("{file_stem}.json"));
+            let srt_path = asr_dir.join(format!("{file_stem}.srt"));
+            let vtt_path = asr_dir.join(format!("{file_stem}.vtt"));
             subtitles::write_artifacts(&doc, &json_path, &srt_path, &vtt_path)?;
             set_progress(paths, job_id, 0.95)?;
 
@@ -4560,7 +8962,7 @@ INSERT INTO subtitle_track (
                 params![
                     &track_id,
                     &item.id,
-                    "source",
+                    track_kind,
                     &doc.lang,
                     "ytfetch_subtitle_json_v1",
                     json_path.to_string_lossy().to_string(),
@@ -4568,6 +8970,7 @@ INSERT INTO subtitle_track (
                     1_i64
                 ],
             )?;
+            let _ = record_step_done(paths, job_id, "save_track");
 
             log_line(
                 paths,
@@ -4576,10 +8979,17 @@ INSERT INTO subtitle_track (
                 "asr_done",
                 serde_json::json!({ "track_id": track_id, "json_path": json_path }),
             )?;
+            let _ = library::record_item_history(
+                paths,
+                &item.id,
+                "asr_done",
+                Some(job_id),
+                Some(&track_id),
+                serde_json::json!({ "json_path": &json_path }),
+            );
 
             if p.batch_on_import {
-                let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
-                let batch_id = job_batch_id(paths, job_id).ok().flatten();
+                let rules = batch_on_import_rules_for_job(paths, job_id);
 
                 if rules.auto_diarize {
                     if !item_has_active_job(paths, &item.id, JobType::DiarizeLocalV1.as_str())
@@ -4588,17 +8998,15 @@ INSERT INTO subtitle_track (
                         let params_json = serde_json::to_string(&DiarizeLocalV1Params {
                             item_id: item.id.clone(),
                             source_track_id: track_id.clone(),
-                            backend: None,
+                            backend: rules.auto_diarize_backend.clone(),
                             speaker_count: DiarizationSpeakerCountRequest::default(),
                             batch_on_import: true,
                             pipeline: None,
                         })?;
-                        let _ = enqueue_with_type_item_and_batch_id(
+                        chain_on_complete(
                             paths,
-                            JobType::DiarizeLocalV1,
-                            params_json,
-                            Some(item.id.clone()),
-                            batch_id.clone(),
+                            job_id,
+                            JobDependency::on_success(JobType::DiarizeLocalV1, params_json),
                         )?;
                     }
                 }
@@ -4610,16 +9018,14 @@ INSERT INTO subtitle_track (
                         let params_json = serde_json::to_string(&TranslateLocalParams {
                             item_id: item.id.clone(),
                             source_track_id: track_id.clone(),
-                            model_id: "whispercpp-tiny".to_string(),
+                            model_id: batch_on_import_asr_model_id(&rules),
                             batch_on_import: true,
                             pipeline: None,
                         })?;
-                        let _ = enqueue_with_type_item_and_batch_id(
+                        chain_on_complete(
                             paths,
-                            JobType::TranslateLocal,
-                            params_json,
-                            Some(item.id.clone()),
-                            batch_id.clone(),
+                            job_id,
+                            JobDependency::on_success(JobType::TranslateLocal, params_json),
                         )?;
                     }
                 }
@@ -4849,8 +9255,9 @@ INSERT INTO subtitle_track (
   format,
   path,
   created_by,
-  version
-) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+  version,
+  parent_track_id
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
 "#,
                 params![
                     &track_id,
@@ -4861,6 +9268,7 @@ INSERT INTO subtitle_track (
                     json_path.to_string_lossy().to_string(),
                     format!("translate:whispercpp:{}", p.model_id),
                     next_version,
+                    &source_track.id,
                 ],
             )?;
 
@@ -4882,6 +9290,18 @@ INSERT INTO subtitle_track (
                     "report_path": report_path
                 }),
             )?;
+            let _ = library::record_item_history(
+                paths,
+                &item.id,
+                "translate_done",
+                Some(job_id),
+                Some(&track_id),
+                serde_json::json!({
+                    "source_track_id": &source_track.id,
+                    "json_path": &json_path,
+                    "report_path": &report_path,
+                }),
+            );
 
             let pipeline = p.pipeline.clone().unwrap_or_default();
             if pipeline.auto_pipeline {
@@ -4910,39 +9330,42 @@ INSERT INTO subtitle_track (
                     )?;
                 }
             } else if p.batch_on_import {
-                let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
+                let rules = batch_on_import_rules_for_job(paths, job_id);
                 if rules.auto_dub_preview {
-                    let batch_id = job_batch_id(paths, job_id).ok().flatten();
-
-                    let prefer_neural = tools::tts_neural_local_v1_pack_status(paths).installed;
-                    let tts_job_type = if prefer_neural {
-                        JobType::TtsNeuralLocalV1
-                    } else {
-                        JobType::TtsPreviewPyttsx3V1
-                    };
+                    let tts_job_type = batch_on_import_tts_job_type(paths, &rules);
 
                     if !item_has_active_job(paths, &item.id, tts_job_type.as_str()).unwrap_or(false)
                     {
-                        let params_json = if prefer_neural {
-                            serde_json::to_string(&TtsNeuralLocalV1Params {
-                                item_id: item.id.clone(),
-                                source_track_id: track_id.clone(),
-                                batch_on_import: true,
-                            })?
-                        } else {
-                            serde_json::to_string(&TtsPreviewPyttsx3V1Params {
+                        let params_json = match tts_job_type {
+                            JobType::TtsNeuralLocalV1 => {
+                                serde_json::to_string(&TtsNeuralLocalV1Params {
+                                    item_id: item.id.clone(),
+                                    source_track_id: track_id.clone(),
+                                    batch_on_import: true,
+                                    lang_code: None,
+                                    fallback_to_pyttsx3: true,
+                                })?
+                            }
+                            JobType::DubVoicePreservingV1 => {
+                                serde_json::to_string(&DubVoicePreservingV1Params {
+                                    item_id: item.id.clone(),
+                                    source_track_id: track_id.clone(),
+                                    batch_on_import: true,
+                                    auto_extract_profiles: false,
+                                    pipeline: None,
+                                })?
+                            }
+                            _ => serde_json::to_string(&TtsPreviewPyttsx3V1Params {
                                 item_id: item.id.clone(),
                                 source_track_id: track_id.clone(),
                                 batch_on_import: true,
-                            })?
+                            })?,
                         };
 
-                        let _ = enqueue_with_type_item_and_batch_id(
+                        chain_on_complete(
                             paths,
-                            tts_job_type,
-                            params_json,
-                            Some(item.id.clone()),
-                            batch_id.clone(),
+                            job_id,
+                            JobDependency::on_success(tts_job_type, params_json),
                         )?;
                     }
                 }
@@ -5243,7 +9666,7 @@ if __name__ == "__main__":
 "#;
                 std::fs::write(&script_path, script)?;
 
-                let mut py_cmd = cmd::command(&python_exe);
+                let mut py_cmd = cmd::sanitized_command(&python_exe);
                 py_cmd.arg(&script_path);
                 py_cmd.arg("--audio").arg(&audio_path);
                 py_cmd.arg("--output").arg(&diarization_json_path);
@@ -5278,24 +9701,27 @@ if __name__ == "__main__":
                         .to_string(),
                 );
                 py_cmd.env("HF_HUB_DISABLE_TELEMETRY", "1");
+                if let Some(proxy) = config::active_proxy_config(paths) {
+                    proxy.apply_env(&mut py_cmd);
+                }
                 if let Some(token) = token.as_deref() {
                     py_cmd.env("HF_TOKEN", token);
                     py_cmd.env("HUGGINGFACE_HUB_TOKEN", token);
                     py_cmd.env("PYANNOTE_TOKEN", token);
                 }
 
-                let output = py_cmd.output().map_err(|e| {
-                    EngineError::InstallFailed(format!(
-                        "failed to run pyannote diarization script: {e}"
-                    ))
-                })?;
+                let output = run_python_tool(
+                    py_cmd,
+                    &JobType::DiarizeLocalV1,
+                    "pyannote diarization script",
+                )?;
                 if !output.status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    return Err(EngineError::InstallFailed(format!(
-                        "pyannote diarization script failed (code={:?}): {}",
-                        output.status.code(),
-                        stderr.trim()
-                    )));
+                    return Err(python_tool_failed(
+                        paths,
+                        job_id,
+                        "pyannote diarization script",
+                        &output,
+                    ));
                 }
             } else {
                 let venv_python = tools::python_venv_python_path(paths).map_err(|_| {
@@ -5481,7 +9907,7 @@ if __name__ == "__main__":
                     } ),
                 )?;
 
-                let mut py_cmd = cmd::command(&venv_python);
+                let mut py_cmd = cmd::sanitized_command(&venv_python);
                 py_cmd.arg(&script_path);
                 py_cmd.arg("--input").arg(&audio_path);
                 py_cmd.arg("--output").arg(&diarization_json_path);
@@ -5506,16 +9932,9 @@ if __name__ == "__main__":
                         .to_string_lossy()
                         .to_string(),
                 );
-                let output = py_cmd.output().map_err(|e| {
-                    EngineError::InstallFailed(format!("failed to run diarize script: {e}"))
-                })?;
+                let output = run_python_tool(py_cmd, &JobType::DiarizeLocalV1, "diarize script")?;
                 if !output.status.success() {
-                    let stderr = String::from_utf8_lossy(&output.stderr);
-                    return Err(EngineError::InstallFailed(format!(
-                        "diarize script failed (code={:?}): {}",
-                        output.status.code(),
-                        stderr.trim()
-                    )));
+                    return Err(python_tool_failed(paths, job_id, "diarize script", &output));
                 }
             }
 
@@ -5600,8 +10019,9 @@ INSERT INTO subtitle_track (
   format,
   path,
   created_by,
-  version
-) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+  version,
+  parent_track_id
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
 "#,
                 params![
                     &track_id,
@@ -5611,7 +10031,8 @@ INSERT INTO subtitle_track (
                     &source_track.format,
                     json_path.to_string_lossy().to_string(),
                     &created_by,
-                    next_version
+                    next_version,
+                    &source_track.id
                 ],
             )?;
 
@@ -5688,6 +10109,18 @@ INSERT INTO subtitle_track (
                     "observed_speaker_count": observed_speakers.len(),
                 }),
             )?;
+            let _ = library::record_item_history(
+                paths,
+                &item.id,
+                "diarize_done",
+                Some(job_id),
+                Some(&track_id),
+                serde_json::json!({
+                    "source_track_id": &source_track.id,
+                    "json_path": &json_path,
+                    "diarization_json_path": &diarization_json_path,
+                }),
+            );
 
             let pipeline = p.pipeline.clone().unwrap_or_default();
             if pipeline.auto_pipeline {
@@ -5759,6 +10192,9 @@ INSERT INTO subtitle_track (
             let item = library::get_item_by_id(paths, &p.item_id)?;
 
             let speaker_settings_by_key = speaker_render_settings_by_key(paths, &item.id)?;
+            let default_voice_id = config::load_tts_defaults_config(paths)
+                .unwrap_or_default()
+                .voice_for_backend(config::TTS_BACKEND_PYTTSX3_V1);
 
             let out_dir = paths
                 .derived_item_dir(&item.id)
@@ -5778,14 +10214,13 @@ INSERT INTO subtitle_track (
                 )?;
 
                 if p.batch_on_import {
-                    let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
+                    let rules = batch_on_import_rules_for_job(paths, job_id);
                     if rules.auto_dub_preview
                         && separation_background_exists(paths, &item.id)
                         && !mix_output_exists(paths, &item.id)
                         && !item_has_active_job(paths, &item.id, JobType::MixDubPreviewV1.as_str())
                             .unwrap_or(false)
                     {
-                        let batch_id = job_batch_id(paths, job_id).ok().flatten();
                         let params_json = serde_json::to_string(&MixDubPreviewV1Params {
                             item_id: item.id.clone(),
                             ducking_strength: None,
@@ -5793,15 +10228,15 @@ INSERT INTO subtitle_track (
                             timing_fit_enabled: None,
                             timing_fit_min_factor: None,
                             timing_fit_max_factor: None,
+                            normalize_speech: None,
                             batch_on_import: true,
                             pipeline: None,
+                            tts_manifest_path: None,
                         })?;
-                        let _ = enqueue_with_type_item_and_batch_id(
+                        chain_on_complete(
                             paths,
-                            JobType::MixDubPreviewV1,
-                            params_json,
-                            Some(item.id.clone()),
-                            batch_id,
+                            job_id,
+                            JobDependency::on_success(JobType::MixDubPreviewV1, params_json),
                         )?;
                     }
                 }
@@ -5836,7 +10271,10 @@ INSERT INTO subtitle_track (
                     .and_then(|k| speaker_settings_by_key.get(k))
                     .cloned()
                     .unwrap_or_default();
-                let voice_id = render_settings.voice_id.clone();
+                let voice_id = render_settings
+                    .voice_id
+                    .clone()
+                    .or_else(|| default_voice_id.clone());
                 let text = prepare_tts_text(text, &render_settings);
                 let out_path = segments_dir.join(format!("seg_{:04}.wav", seg.index));
                 request.push(TtsRequestSegment {
@@ -5867,82 +10305,9 @@ INSERT INTO subtitle_track (
             })?;
 
             let script_path = artifacts_dir.join("tts_pyttsx3_v1.py");
-            let script = r#"
-import argparse
-import json
-import os
-
-import pyttsx3
-
-
-def main():
-    ap = argparse.ArgumentParser()
-    ap.add_argument("--request", required=True)
-    args = ap.parse_args()
-
-    with open(args.request, "r", encoding="utf-8") as f:
-        items = json.load(f)
-
-    engine = pyttsx3.init()
-    default_voice = None
-    try:
-        default_voice = engine.getProperty("voice")
-    except Exception:
-        default_voice = None
-    if default_voice is not None:
-        default_voice = (str(default_voice).strip() or None)
-
-    current_voice = default_voice or ""
-
-    def flush_queue():
-        try:
-            engine.runAndWait()
-        except Exception:
-            pass
-
-    for it in items:
-        text = (it.get("text") or "").strip()
-        out_path = (it.get("out_path") or "").strip()
-        voice_id = (it.get("voice_id") or "").strip()
-        if not text or not out_path:
-            continue
-
-        desired_voice = voice_id if voice_id else (default_voice or "")
-        if desired_voice != current_voice:
-            flush_queue()
-            if desired_voice:
-                try:
-                    engine.setProperty("voice", desired_voice)
-                    current_voice = desired_voice
-                except Exception:
-                    current_voice = desired_voice
-            else:
-                # If we can't restore a known default voice id, re-init the engine to reset state.
-                try:
-                    engine = pyttsx3.init()
-                except Exception:
-                    pass
-                try:
-                    default_voice = engine.getProperty("voice")
-                except Exception:
-                    default_voice = None
-                if default_voice is not None:
-                    default_voice = (str(default_voice).strip() or None)
-                current_voice = default_voice or ""
-
-        out_dir = os.path.dirname(out_path)
-        if out_dir:
-            os.makedirs(out_dir, exist_ok=True)
-        engine.save_to_file(text, out_path)
-
-    flush_queue()
-
-
-if __name__ == "__main__":
-    main()
-"#;
-            std::fs::write(&script_path, script)?;
+            std::fs::write(&script_path, TTS_PYTTSX3_V1_SCRIPT)?;
 
+            let _ = record_step_start(paths, job_id, "synthesize");
             log_line(
                 paths,
                 job_id,
@@ -5951,7 +10316,7 @@ if __name__ == "__main__":
                 serde_json::json!({ "request_path": &request_path, "segments": request.len() }),
             )?;
 
-            let mut py_cmd = cmd::command(&venv_python);
+            let mut py_cmd = cmd::sanitized_command(&venv_python);
             py_cmd.arg(&script_path);
             py_cmd.arg("--request").arg(&request_path);
             py_cmd.env("PYTHONNOUSERSITE", "1");
@@ -5963,18 +10328,13 @@ if __name__ == "__main__":
                     .to_string_lossy()
                     .to_string(),
             );
-            let output = py_cmd.output().map_err(|e| {
-                EngineError::InstallFailed(format!("failed to run pyttsx3 script: {e}"))
-            })?;
+            let output = run_python_tool(py_cmd, &JobType::TtsPreviewPyttsx3V1, "pyttsx3 script")?;
             if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(EngineError::InstallFailed(format!(
-                    "pyttsx3 script failed (code={:?}): {}",
-                    output.status.code(),
-                    stderr.trim()
-                )));
+                return Err(python_tool_failed(paths, job_id, "pyttsx3 script", &output));
             }
+            let _ = record_step_done(paths, job_id, "synthesize");
             set_progress(paths, job_id, 0.80)?;
+            let _ = record_step_start(paths, job_id, "build_manifest");
 
             #[derive(Serialize)]
             struct TtsManifestSegment {
@@ -5995,6 +10355,8 @@ if __name__ == "__main__":
                 backend: String,
                 item_id: String,
                 track_id: String,
+                #[serde(flatten)]
+                coverage: TtsManifestCoverage,
                 segments: Vec<TtsManifestSegment>,
             }
 
@@ -6034,6 +10396,11 @@ if __name__ == "__main__":
                 backend: "pyttsx3_v1".to_string(),
                 item_id: item.id.clone(),
                 track_id: source_track.id.clone(),
+                coverage: TtsManifestCoverage::from_spans(
+                    manifest_segments
+                        .iter()
+                        .map(|seg| (seg.start_ms, seg.end_ms, seg.audio_exists)),
+                ),
                 segments: manifest_segments,
             };
 
@@ -6041,6 +10408,7 @@ if __name__ == "__main__":
                 &manifest_path,
                 format!("{}\n", serde_json::to_string_pretty(&manifest)?),
             )?;
+            let _ = record_step_done(paths, job_id, "build_manifest");
             set_progress(paths, job_id, 0.95)?;
 
             log_line(
@@ -6053,16 +10421,27 @@ if __name__ == "__main__":
                     "segments_dir": &segments_dir
                 }),
             )?;
+            let _ = library::record_item_history(
+                paths,
+                &item.id,
+                "tts_done",
+                Some(job_id),
+                Some(&source_track.id),
+                serde_json::json!({
+                    "job_type": type_str,
+                    "manifest_path": &manifest_path,
+                    "segments_dir": &segments_dir,
+                }),
+            );
 
             if p.batch_on_import {
-                let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
+                let rules = batch_on_import_rules_for_job(paths, job_id);
                 if rules.auto_dub_preview
                     && separation_background_exists(paths, &item.id)
                     && !mix_output_exists(paths, &item.id)
                     && !item_has_active_job(paths, &item.id, JobType::MixDubPreviewV1.as_str())
                         .unwrap_or(false)
                 {
-                    let batch_id = job_batch_id(paths, job_id).ok().flatten();
                     let params_json = serde_json::to_string(&MixDubPreviewV1Params {
                         item_id: item.id.clone(),
                         ducking_strength: None,
@@ -6070,15 +10449,15 @@ if __name__ == "__main__":
                         timing_fit_enabled: None,
                         timing_fit_min_factor: None,
                         timing_fit_max_factor: None,
+                        normalize_speech: None,
                         batch_on_import: true,
                         pipeline: None,
+                        tts_manifest_path: None,
                     })?;
-                    let _ = enqueue_with_type_item_and_batch_id(
+                    chain_on_complete(
                         paths,
-                        JobType::MixDubPreviewV1,
-                        params_json,
-                        Some(item.id.clone()),
-                        batch_id,
+                        job_id,
+                        JobDependency::on_success(JobType::MixDubPreviewV1, params_json),
                     )?;
                 }
             }
@@ -6124,6 +10503,18 @@ if __name__ == "__main__":
             let item = library::get_item_by_id(paths, &p.item_id)?;
 
             let speaker_settings_by_key = speaker_render_settings_by_key(paths, &item.id)?;
+            let tts_defaults = config::load_tts_defaults_config(paths).unwrap_or_default();
+            let default_voice_id =
+                tts_defaults.voice_for_backend(config::TTS_BACKEND_NEURAL_LOCAL_V1);
+            let lang_code = match config::normalize_kokoro_lang_code(p.lang_code.as_deref())? {
+                Some(code) => code,
+                None => {
+                    config::normalize_kokoro_lang_code(tts_defaults.default_lang_code.as_deref())
+                        .ok()
+                        .flatten()
+                        .unwrap_or_else(|| config::KOKORO_DEFAULT_LANG_CODE.to_string())
+                }
+            };
 
             let out_dir = paths
                 .derived_item_dir(&item.id)
@@ -6143,14 +10534,13 @@ if __name__ == "__main__":
                 )?;
 
                 if p.batch_on_import {
-                    let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
+                    let rules = batch_on_import_rules_for_job(paths, job_id);
                     if rules.auto_dub_preview
                         && separation_background_exists(paths, &item.id)
                         && !mix_output_exists(paths, &item.id)
                         && !item_has_active_job(paths, &item.id, JobType::MixDubPreviewV1.as_str())
                             .unwrap_or(false)
                     {
-                        let batch_id = job_batch_id(paths, job_id).ok().flatten();
                         let params_json = serde_json::to_string(&MixDubPreviewV1Params {
                             item_id: item.id.clone(),
                             ducking_strength: None,
@@ -6158,15 +10548,15 @@ if __name__ == "__main__":
                             timing_fit_enabled: None,
                             timing_fit_min_factor: None,
                             timing_fit_max_factor: None,
+                            normalize_speech: None,
                             batch_on_import: true,
                             pipeline: None,
+                            tts_manifest_path: None,
                         })?;
-                        let _ = enqueue_with_type_item_and_batch_id(
+                        chain_on_complete(
                             paths,
-                            JobType::MixDubPreviewV1,
-                            params_json,
-                            Some(item.id.clone()),
-                            batch_id,
+                            job_id,
+                            JobDependency::on_success(JobType::MixDubPreviewV1, params_json),
                         )?;
                     }
                 }
@@ -6201,7 +10591,10 @@ if __name__ == "__main__":
                     .and_then(|k| speaker_settings_by_key.get(k))
                     .cloned()
                     .unwrap_or_default();
-                let voice_id = render_settings.voice_id.clone();
+                let voice_id = render_settings
+                    .voice_id
+                    .clone()
+                    .or_else(|| default_voice_id.clone());
                 let text = prepare_tts_text(text, &render_settings);
                 let out_path = segments_dir.join(format!("seg_{:04}.wav", seg.index));
                 request.push(TtsRequestSegment {
@@ -6373,6 +10766,7 @@ def synthesize(
 def main():
     parser = argparse.ArgumentParser()
     parser.add_argument("--request", required=True)
+    parser.add_argument("--lang-code", default="a")
     args = parser.parse_args()
 
     with open(args.request, "r", encoding="utf-8") as f:
@@ -6380,9 +10774,9 @@ def main():
 
     try:
         try:
-            pipeline = KPipeline(lang_code="a")
+            pipeline = KPipeline(lang_code=args.lang_code)
         except TypeError:
-            pipeline = KPipeline("a")
+            pipeline = KPipeline(args.lang_code)
     except TypeError:
         pipeline = KPipeline()
 
@@ -6400,17 +10794,23 @@ if __name__ == "__main__":
 "##;
             std::fs::write(&script_path, script)?;
 
+            let _ = record_step_start(paths, job_id, "synthesize");
             log_line(
                 paths,
                 job_id,
                 "info",
                 "tts_preview_neural_python_begin",
-                serde_json::json!({ "request_path": &request_path, "segments": request.len() }),
+                serde_json::json!({
+                    "request_path": &request_path,
+                    "segments": request.len(),
+                    "lang_code": &lang_code,
+                }),
             )?;
 
-            let mut py_cmd = cmd::command(&venv_python);
+            let mut py_cmd = cmd::sanitized_command(&venv_python);
             py_cmd.arg(&script_path);
             py_cmd.arg("--request").arg(&request_path);
+            py_cmd.arg("--lang-code").arg(&lang_code);
             py_cmd.env("PYTHONNOUSERSITE", "1");
             py_cmd.env(
                 "XDG_CACHE_HOME",
@@ -6439,84 +10839,197 @@ if __name__ == "__main__":
             );
             py_cmd.env("HF_HUB_OFFLINE", "1");
             py_cmd.env("TRANSFORMERS_OFFLINE", "1");
-            let output = py_cmd.output().map_err(|e| {
-                EngineError::InstallFailed(format!("failed to run neural TTS script: {e}"))
-            })?;
+            let output = run_python_tool(py_cmd, &JobType::TtsNeuralLocalV1, "neural TTS script")?;
+            let mut fallback_manifest_path: Option<PathBuf> = None;
             if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(EngineError::InstallFailed(format!(
-                    "neural TTS script failed (code={:?}): {}",
-                    output.status.code(),
-                    stderr.trim()
-                )));
-            }
-            set_progress(paths, job_id, 0.80)?;
+                let neural_error = python_tool_failed(paths, job_id, "neural TTS script", &output);
+                if !p.fallback_to_pyttsx3 || !tools::tts_preview_pack_status(paths).installed {
+                    return Err(neural_error);
+                }
 
-            #[derive(Serialize)]
-            struct TtsManifestSegment {
-                index: u32,
-                start_ms: i64,
-                end_ms: i64,
-                speaker: Option<String>,
-                #[serde(default)]
-                tts_voice_id: Option<String>,
-                text: String,
-                audio_path: Option<String>,
-                audio_exists: bool,
-            }
+                let fallback_dir = pyttsx3_fallback_dir(&manifest_path);
+                let fallback_segments_dir = fallback_dir.join("segments");
+                std::fs::create_dir_all(&fallback_segments_dir)?;
+                let pyttsx3_voice_id =
+                    tts_defaults.voice_for_backend(config::TTS_BACKEND_PYTTSX3_V1);
+                let fallback_request: Vec<TtsRequestSegment> = request
+                    .iter()
+                    .filter(|seg| !Path::new(&seg.out_path).exists())
+                    .map(|seg| TtsRequestSegment {
+                        index: seg.index,
+                        speaker: seg.speaker.clone(),
+                        voice_id: pyttsx3_voice_id.clone(),
+                        text: seg.text.clone(),
+                        out_path: fallback_segments_dir
+                            .join(format!("seg_{:04}.wav", seg.index))
+                            .to_string_lossy()
+                            .to_string(),
+                    })
+                    .collect();
+                let fallback_request_path = artifacts_dir.join("tts_request_pyttsx3_fallback.json");
+                std::fs::write(
+                    &fallback_request_path,
+                    format!("{}\n", serde_json::to_string_pretty(&fallback_request)?),
+                )?;
+                let fallback_script_path = artifacts_dir.join("tts_pyttsx3_v1.py");
+                std::fs::write(&fallback_script_path, TTS_PYTTSX3_V1_SCRIPT)?;
+
+                let mut fallback_cmd = cmd::sanitized_command(&venv_python);
+                fallback_cmd.arg(&fallback_script_path);
+                fallback_cmd.arg("--request").arg(&fallback_request_path);
+                fallback_cmd.env("PYTHONNOUSERSITE", "1");
+                fallback_cmd.env(
+                    "XDG_CACHE_HOME",
+                    paths
+                        .cache_dir()
+                        .join("python")
+                        .to_string_lossy()
+                        .to_string(),
+                );
+                let fallback_output = run_python_tool(
+                    fallback_cmd,
+                    &JobType::TtsPreviewPyttsx3V1,
+                    "pyttsx3 fallback script",
+                )?;
+                if !fallback_output.status.success() {
+                    log_line(
+                        paths,
+                        job_id,
+                        "warn",
+                        "neural_tts_fallback_failed",
+                        serde_json::json!({
+                            "code": fallback_output.status.code(),
+                            "stderr": format_python_stderr(
+                                &String::from_utf8_lossy(&fallback_output.stderr),
+                                PYTHON_STDERR_EXCERPT_LINES,
+                            ),
+                        }),
+                    )?;
+                    return Err(neural_error);
+                }
 
-            #[derive(Serialize)]
-            struct TtsManifest {
-                schema_version: u32,
-                backend: String,
-                item_id: String,
-                track_id: String,
-                segments: Vec<TtsManifestSegment>,
+                let fallback_segments = fallback_request
+                    .iter()
+                    .filter(|seg| Path::new(&seg.out_path).exists())
+                    .count();
+                log_line(
+                    paths,
+                    job_id,
+                    "warn",
+                    "neural_tts_fallback_to_pyttsx3",
+                    serde_json::json!({
+                        "failed_segments": fallback_request.len(),
+                        "fallback_segments": fallback_segments,
+                        "neural_error": neural_error.to_string(),
+                    }),
+                )?;
+                fallback_manifest_path = Some(fallback_dir.join("manifest.json"));
             }
+            let _ = record_step_done(paths, job_id, "synthesize");
+            set_progress(paths, job_id, 0.80)?;
+            let _ = record_step_start(paths, job_id, "build_manifest");
 
-            let mut manifest_segments: Vec<TtsManifestSegment> = Vec::new();
-            for seg in &doc.segments {
-                let audio_path = segments_dir.join(format!("seg_{:04}.wav", seg.index));
-                let exists = audio_path.exists();
-                let speaker = seg
-                    .speaker
-                    .as_ref()
-                    .map(|s| s.trim().to_string())
-                    .filter(|s| !s.is_empty());
-                let render_settings = speaker
-                    .as_ref()
-                    .and_then(|k| speaker_settings_by_key.get(k))
-                    .cloned()
-                    .unwrap_or_default();
-                let tts_voice_id = render_settings.voice_id.clone();
-                manifest_segments.push(TtsManifestSegment {
-                    index: seg.index,
-                    start_ms: seg.start_ms,
-                    end_ms: seg.end_ms,
-                    speaker,
-                    tts_voice_id,
-                    text: prepare_tts_text(&seg.text, &render_settings),
-                    audio_path: if exists {
-                        Some(audio_path.to_string_lossy().to_string())
-                    } else {
-                        None
-                    },
-                    audio_exists: exists,
-                });
+            #[derive(Serialize)]
+            struct TtsManifestSegment {
+                index: u32,
+                start_ms: i64,
+                end_ms: i64,
+                speaker: Option<String>,
+                #[serde(default)]
+                tts_voice_id: Option<String>,
+                text: String,
+                audio_path: Option<String>,
+                audio_exists: bool,
             }
 
-            let manifest = TtsManifest {
-                schema_version: 1,
-                backend: "neural_local_v1".to_string(),
-                item_id: item.id.clone(),
-                track_id: source_track.id.clone(),
-                segments: manifest_segments,
+            #[derive(Serialize)]
+            struct TtsManifest {
+                schema_version: u32,
+                backend: String,
+                item_id: String,
+                track_id: String,
+                lang_code: String,
+                #[serde(skip_serializing_if = "Option::is_none")]
+                fallback_for: Option<String>,
+                #[serde(flatten)]
+                coverage: TtsManifestCoverage,
+                segments: Vec<TtsManifestSegment>,
+            }
+
+            let build_manifest_segments = |segments_dir: &Path| -> Vec<TtsManifestSegment> {
+                let mut manifest_segments: Vec<TtsManifestSegment> = Vec::new();
+                for seg in &doc.segments {
+                    let audio_path = segments_dir.join(format!("seg_{:04}.wav", seg.index));
+                    let exists = audio_path.exists();
+                    let speaker = seg
+                        .speaker
+                        .as_ref()
+                        .map(|s| s.trim().to_string())
+                        .filter(|s| !s.is_empty());
+                    let render_settings = speaker
+                        .as_ref()
+                        .and_then(|k| speaker_settings_by_key.get(k))
+                        .cloned()
+                        .unwrap_or_default();
+                    let tts_voice_id = render_settings.voice_id.clone();
+                    manifest_segments.push(TtsManifestSegment {
+                        index: seg.index,
+                        start_ms: seg.start_ms,
+                        end_ms: seg.end_ms,
+                        speaker,
+                        tts_voice_id,
+                        text: prepare_tts_text(&seg.text, &render_settings),
+                        audio_path: if exists {
+                            Some(audio_path.to_string_lossy().to_string())
+                        } else {
+                            None
+                        },
+                        audio_exists: exists,
+                    });
+                }
+                manifest_segments
             };
+            let build_manifest =
+                |backend: &str, segments_dir: &Path, fallback_for: Option<String>| {
+                    let manifest_segments = build_manifest_segments(segments_dir);
+                    TtsManifest {
+                        schema_version: 1,
+                        backend: backend.to_string(),
+                        item_id: item.id.clone(),
+                        track_id: source_track.id.clone(),
+                        lang_code: lang_code.clone(),
+                        fallback_for,
+                        coverage: TtsManifestCoverage::from_spans(
+                            manifest_segments
+                                .iter()
+                                .map(|seg| (seg.start_ms, seg.end_ms, seg.audio_exists)),
+                        ),
+                        segments: manifest_segments,
+                    }
+                };
+
+            if let Some(fallback_manifest_path) = fallback_manifest_path.as_deref() {
+                let fallback_segments_dir = fallback_manifest_path
+                    .parent()
+                    .unwrap_or(&out_dir)
+                    .join("segments");
+                let fallback_manifest = build_manifest(
+                    "pyttsx3_v1",
+                    &fallback_segments_dir,
+                    Some("neural_local_v1".to_string()),
+                );
+                std::fs::write(
+                    fallback_manifest_path,
+                    format!("{}\n", serde_json::to_string_pretty(&fallback_manifest)?),
+                )?;
+            }
 
+            let manifest = build_manifest("neural_local_v1", &segments_dir, None);
             std::fs::write(
                 &manifest_path,
                 format!("{}\n", serde_json::to_string_pretty(&manifest)?),
             )?;
+            let _ = record_step_done(paths, job_id, "build_manifest");
             set_progress(paths, job_id, 0.95)?;
 
             log_line(
@@ -6529,16 +11042,27 @@ if __name__ == "__main__":
                     "segments_dir": &segments_dir
                 }),
             )?;
+            let _ = library::record_item_history(
+                paths,
+                &item.id,
+                "tts_done",
+                Some(job_id),
+                Some(&source_track.id),
+                serde_json::json!({
+                    "job_type": type_str,
+                    "manifest_path": &manifest_path,
+                    "segments_dir": &segments_dir,
+                }),
+            );
 
             if p.batch_on_import {
-                let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
+                let rules = batch_on_import_rules_for_job(paths, job_id);
                 if rules.auto_dub_preview
                     && separation_background_exists(paths, &item.id)
                     && !mix_output_exists(paths, &item.id)
                     && !item_has_active_job(paths, &item.id, JobType::MixDubPreviewV1.as_str())
                         .unwrap_or(false)
                 {
-                    let batch_id = job_batch_id(paths, job_id).ok().flatten();
                     let params_json = serde_json::to_string(&MixDubPreviewV1Params {
                         item_id: item.id.clone(),
                         ducking_strength: None,
@@ -6546,15 +11070,15 @@ if __name__ == "__main__":
                         timing_fit_enabled: None,
                         timing_fit_min_factor: None,
                         timing_fit_max_factor: None,
+                        normalize_speech: None,
                         batch_on_import: true,
                         pipeline: None,
+                        tts_manifest_path: None,
                     })?;
-                    let _ = enqueue_with_type_item_and_batch_id(
+                    chain_on_complete(
                         paths,
-                        JobType::MixDubPreviewV1,
-                        params_json,
-                        Some(item.id.clone()),
-                        batch_id,
+                        job_id,
+                        JobDependency::on_success(JobType::MixDubPreviewV1, params_json),
                     )?;
                 }
             }
@@ -6618,6 +11142,15 @@ if __name__ == "__main__":
             let pipeline = p.pipeline.clone().unwrap_or_default();
             let mut speaker_settings_by_key = speaker_render_settings_by_key(paths, &item.id)?;
             apply_speaker_overrides(&mut speaker_settings_by_key, &pipeline.speaker_overrides);
+            if p.auto_extract_profiles {
+                auto_extract_speaker_profiles(
+                    paths,
+                    job_id,
+                    &item,
+                    &doc,
+                    &mut speaker_settings_by_key,
+                )?;
+            }
 
             let item_dir = paths.derived_item_dir(&item.id);
             let variant_label = normalize_variant_label(pipeline.variant_label.as_deref());
@@ -7139,12 +11672,12 @@ if __name__ == "__main__":
                 serde_json::json!({ "request_path": &request_path, "segments": request.len() }),
             )?;
 
-            let mut py_cmd = cmd::command(&venv_python);
+            let mut py_cmd = cmd::sanitized_command(&venv_python);
             py_cmd.arg(&script_path);
             py_cmd.arg("--request").arg(&request_path);
             py_cmd
                 .arg("--models-dir")
-                .arg(paths.python_models_dir().join("openvoice_v2"));
+                .arg(tools::openvoice_models_dir(paths));
             py_cmd.arg("--ffmpeg").arg(paths.ffmpeg_cmd());
             let report_path = artifacts_dir.join(match variant_label.as_deref() {
                 Some(label) => format!("tts_voice_preserving_report_{label}.json"),
@@ -7179,18 +11712,18 @@ if __name__ == "__main__":
             );
             py_cmd.env("HF_HUB_OFFLINE", "1");
             py_cmd.env("TRANSFORMERS_OFFLINE", "1");
-            let output = py_cmd.output().map_err(|e| {
-                EngineError::InstallFailed(format!(
-                    "failed to run voice-preserving TTS script: {e}"
-                ))
-            })?;
+            let output = run_python_tool(
+                py_cmd,
+                &JobType::DubVoicePreservingV1,
+                "voice-preserving TTS script",
+            )?;
             if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(EngineError::InstallFailed(format!(
-                    "voice-preserving TTS script failed (code={:?}): {}",
-                    output.status.code(),
-                    stderr.trim()
-                )));
+                return Err(python_tool_failed(
+                    paths,
+                    job_id,
+                    "voice-preserving TTS script",
+                    &output,
+                ));
             }
             set_progress(paths, job_id, 0.80)?;
 
@@ -7284,6 +11817,8 @@ if __name__ == "__main__":
                 voice_clone_fallback_segments: usize,
                 #[serde(default)]
                 voice_clone_standard_tts_segments: usize,
+                #[serde(flatten)]
+                coverage: TtsManifestCoverage,
                 segments: Vec<TtsManifestSegment>,
             }
 
@@ -7355,6 +11890,11 @@ if __name__ == "__main__":
                 voice_clone_converted_segments: clone_summary.clone_converted_segments,
                 voice_clone_fallback_segments: clone_summary.clone_fallback_segments,
                 voice_clone_standard_tts_segments: clone_summary.standard_tts_segments,
+                coverage: TtsManifestCoverage::from_spans(
+                    manifest_segments
+                        .iter()
+                        .map(|seg| (seg.start_ms, seg.end_ms, seg.audio_exists)),
+                ),
                 segments: manifest_segments,
             };
 
@@ -7376,9 +11916,20 @@ if __name__ == "__main__":
                     "variant_label": variant_label
                 }),
             )?;
+            let _ = library::record_item_history(
+                paths,
+                &item.id,
+                "tts_done",
+                Some(job_id),
+                Some(&source_track.id),
+                serde_json::json!({
+                    "job_type": type_str,
+                    "manifest_path": &manifest_path,
+                    "segments_dir": &segments_dir,
+                }),
+            );
 
             if pipeline.auto_pipeline {
-                let batch_id = job_batch_id(paths, job_id).ok().flatten();
                 if !item_has_active_job(paths, &item.id, JobType::MixDubPreviewV1.as_str())
                     .unwrap_or(false)
                 {
@@ -7389,19 +11940,45 @@ if __name__ == "__main__":
                         timing_fit_enabled: None,
                         timing_fit_min_factor: None,
                         timing_fit_max_factor: None,
+                        normalize_speech: None,
                         batch_on_import: false,
                         pipeline: Some(LocalizationPipelineOptions {
                             source_track_id: Some(source_track.id.clone()),
                             variant_label: variant_label.clone(),
                             ..pipeline.clone()
                         }),
+                        tts_manifest_path: None,
                     })?;
-                    let _ = enqueue_with_type_item_and_batch_id(
+                    chain_on_complete(
                         paths,
-                        JobType::MixDubPreviewV1,
-                        params_json,
-                        Some(item.id.clone()),
-                        batch_id.clone(),
+                        job_id,
+                        JobDependency::on_success(JobType::MixDubPreviewV1, params_json),
+                    )?;
+                }
+            } else if p.batch_on_import {
+                let rules = batch_on_import_rules_for_job(paths, job_id);
+                if rules.auto_dub_preview
+                    && separation_background_exists(paths, &item.id)
+                    && !mix_output_exists(paths, &item.id)
+                    && !item_has_active_job(paths, &item.id, JobType::MixDubPreviewV1.as_str())
+                        .unwrap_or(false)
+                {
+                    let params_json = serde_json::to_string(&MixDubPreviewV1Params {
+                        item_id: item.id.clone(),
+                        ducking_strength: None,
+                        loudness_target_lufs: None,
+                        timing_fit_enabled: None,
+                        timing_fit_min_factor: None,
+                        timing_fit_max_factor: None,
+                        normalize_speech: None,
+                        batch_on_import: true,
+                        pipeline: None,
+                        tts_manifest_path: None,
+                    })?;
+                    chain_on_complete(
+                        paths,
+                        job_id,
+                        JobDependency::on_success(JobType::MixDubPreviewV1, params_json),
                     )?;
                 }
             }
@@ -7455,18 +12032,30 @@ if __name__ == "__main__":
                 }),
             )?;
 
-            let preferred_backend_id =
-                resolve_pipeline_tts_backend_preference(paths, &item.id, Some(&pipeline));
-            let manifest_candidate = select_tts_manifest_candidate(
-                paths,
-                &item.id,
-                pipeline.source_track_id.as_deref(),
-                variant_label.as_deref(),
-                preferred_backend_id.as_deref(),
-            )?;
-            let manifest_path = manifest_candidate
-                .as_ref()
-                .map(|candidate| candidate.manifest_path.clone())
+            let _ = record_step_start(paths, job_id, "load_manifest");
+            let explicit_manifest_path = match normalize_non_empty(p.tts_manifest_path.as_deref()) {
+                Some(raw) => Some(resolve_item_tts_manifest_path(paths, &item.id, &raw)?),
+                None => None,
+            };
+            let manifest_candidate = if explicit_manifest_path.is_some() {
+                None
+            } else {
+                let preferred_backend_id =
+                    resolve_pipeline_tts_backend_preference(paths, &item.id, Some(&pipeline));
+                select_tts_manifest_candidate(
+                    paths,
+                    &item.id,
+                    pipeline.source_track_id.as_deref(),
+                    variant_label.as_deref(),
+                    preferred_backend_id.as_deref(),
+                )?
+            };
+            let manifest_path = explicit_manifest_path
+                .or_else(|| {
+                    manifest_candidate
+                        .as_ref()
+                        .map(|candidate| candidate.manifest_path.clone())
+                })
                 .unwrap_or_else(|| {
                     tts_manifest_path(&item_dir, "tts_neural_local_v1", variant_label.as_deref())
                 });
@@ -7479,6 +12068,24 @@ if __name__ == "__main__":
 
             let manifest_bytes = std::fs::read(&manifest_path)?;
             let manifest: TtsPreviewManifest = serde_json::from_slice(&manifest_bytes)?;
+            let coverage = manifest.coverage();
+            if coverage.coverage_ratio < MIX_TTS_COVERAGE_WARN_RATIO {
+                log_line(
+                    paths,
+                    job_id,
+                    "warn",
+                    "mix_dub_preview_low_tts_coverage",
+                    serde_json::json!({
+                        "manifest_path": &manifest_path,
+                        "coverage_ratio": coverage.coverage_ratio,
+                        "missing_segment_count": coverage.missing_segment_count,
+                        "total_duration_ms": coverage.total_duration_ms,
+                        "synthesized_duration_ms": coverage.synthesized_duration_ms,
+                    }),
+                )?;
+            }
+
+            let _ = record_step_done(paths, job_id, "load_manifest");
 
             let out_dir = dub_variant_dir(&item_dir, variant_label.as_deref());
             std::fs::create_dir_all(&out_dir)?;
@@ -7497,7 +12104,6 @@ if __name__ == "__main__":
                 )?;
 
                 if pipeline.auto_pipeline {
-                    let batch_id = job_batch_id(paths, job_id).ok().flatten();
                     if !item_has_active_job(paths, &item.id, JobType::MuxDubPreviewV1.as_str())
                         .unwrap_or(false)
                     {
@@ -7507,6 +12113,8 @@ if __name__ == "__main__":
                             keep_original_audio: None,
                             dubbed_audio_lang: None,
                             original_audio_lang: None,
+                            embed_subtitle_track_id: None,
+                            allow_container_override: true,
                             batch_on_import: false,
                             pipeline: Some(LocalizationPipelineOptions {
                                 source_track_id: pipeline.source_track_id.clone(),
@@ -7514,37 +12122,34 @@ if __name__ == "__main__":
                                 ..pipeline.clone()
                             }),
                         })?;
-                        let _ = enqueue_with_type_item_and_batch_id(
+                        chain_on_complete(
                             paths,
-                            JobType::MuxDubPreviewV1,
-                            params_json,
-                            Some(item.id.clone()),
-                            batch_id,
+                            job_id,
+                            JobDependency::on_success(JobType::MuxDubPreviewV1, params_json),
                         )?;
                     }
                 } else if p.batch_on_import {
-                    let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
-                    if rules.auto_dub_preview
+                    let rules = batch_on_import_rules_for_job(paths, job_id);
+                    if (rules.auto_dub_preview || rules.auto_mux)
                         && !mux_output_exists(paths, &item.id)
                         && !item_has_active_job(paths, &item.id, JobType::MuxDubPreviewV1.as_str())
                             .unwrap_or(false)
                     {
-                        let batch_id = job_batch_id(paths, job_id).ok().flatten();
                         let params_json = serde_json::to_string(&MuxDubPreviewV1Params {
                             item_id: item.id.clone(),
                             output_container: None,
                             keep_original_audio: None,
                             dubbed_audio_lang: None,
                             original_audio_lang: None,
+                            embed_subtitle_track_id: None,
+                            allow_container_override: true,
                             batch_on_import: true,
                             pipeline: None,
                         })?;
-                        let _ = enqueue_with_type_item_and_batch_id(
+                        chain_on_complete(
                             paths,
-                            JobType::MuxDubPreviewV1,
-                            params_json,
-                            Some(item.id.clone()),
-                            batch_id,
+                            job_id,
+                            JobDependency::on_success(JobType::MuxDubPreviewV1, params_json),
                         )?;
                     }
                 }
@@ -7552,11 +12157,22 @@ if __name__ == "__main__":
                 return Ok(());
             }
 
-            let ducking_strength = p.ducking_strength.unwrap_or(0.6).clamp(0.0, 1.0);
+            let _ = record_step_start(paths, job_id, "compose_graph");
+            let ducking_strength = p
+                .ducking_strength
+                .or_else(|| {
+                    p.batch_on_import
+                        .then(|| full_pipeline_params_for_job(paths, job_id))
+                        .flatten()
+                        .and_then(|pipeline| pipeline.ducking_strength)
+                })
+                .unwrap_or(0.6)
+                .clamp(0.0, 1.0);
             let loudness_target_lufs = p.loudness_target_lufs.unwrap_or(-16.0).clamp(-40.0, -5.0);
             let timing_fit_enabled = p.timing_fit_enabled.unwrap_or(false);
             let timing_fit_min_factor = p.timing_fit_min_factor.unwrap_or(0.85).clamp(0.5, 1.0);
             let timing_fit_max_factor = p.timing_fit_max_factor.unwrap_or(1.25).clamp(1.0, 3.0);
+            let normalize_speech = p.normalize_speech.unwrap_or(false);
 
             #[derive(Serialize)]
             struct TimingFitEntry {
@@ -7569,6 +12185,7 @@ if __name__ == "__main__":
                 applied_factor: Option<f32>,
                 stretched: bool,
                 note: Option<String>,
+                silence_coverage: Option<f32>,
             }
 
             let mut inputs: Vec<(TtsPreviewManifestSegment, PathBuf)> = Vec::new();
@@ -7582,6 +12199,31 @@ if __name__ == "__main__":
                 }
                 inputs.push((seg.clone(), audio_path));
             }
+            if let Some(candidate) = manifest_candidate
+                .as_ref()
+                .filter(|candidate| tts_backend_ids_match(&candidate.backend_id, "kokoro"))
+            {
+                let fallback_path =
+                    pyttsx3_fallback_dir(&candidate.manifest_path).join("manifest.json");
+                if let Some(fallback) = std::fs::read(&fallback_path)
+                    .ok()
+                    .and_then(|bytes| serde_json::from_slice::<TtsPreviewManifest>(&bytes).ok())
+                {
+                    let filled = fill_missing_tts_inputs_from_fallback(&mut inputs, &fallback);
+                    if filled > 0 {
+                        log_line(
+                            paths,
+                            job_id,
+                            "info",
+                            "mix_dub_preview_pyttsx3_fallback_segments",
+                            serde_json::json!({
+                                "manifest_path": &fallback_path,
+                                "segments": filled,
+                            }),
+                        )?;
+                    }
+                }
+            }
 
             // If there is no TTS audio, output just the selected audio source.
             if inputs.is_empty() {
@@ -7613,6 +12255,7 @@ if __name__ == "__main__":
                     "mix_dub_preview_done",
                     serde_json::json!({
                         "out_path": &final_path,
+                        "manifest_used": manifest_path.to_string_lossy(),
                         "overlays": 0,
                         "mode": if used_source_audio_fallback {
                             "source_audio_only"
@@ -7622,6 +12265,14 @@ if __name__ == "__main__":
                         "background_mode": background_mode
                     }),
                 )?;
+                let _ = library::record_item_history(
+                    paths,
+                    &item.id,
+                    "mix_done",
+                    Some(job_id),
+                    None,
+                    serde_json::json!({ "out_path": &final_path }),
+                );
                 return Ok(());
             }
 
@@ -7629,6 +12280,55 @@ if __name__ == "__main__":
             let max_single_pass_segments = 120_usize;
             let use_single_pass = inputs.len() <= max_single_pass_segments;
 
+            // Best-effort: segments landing on background silence can be mixed without ducking
+            // artifacts, so report how much of each window is already quiet.
+            let background_silences = match ffmpeg::detect_silences(
+                paths,
+                &background_path,
+                MIX_SILENCE_NOISE_DB,
+                MIX_SILENCE_MIN_DURATION_MS,
+            ) {
+                Ok(ranges) => Some(ranges),
+                Err(e) => {
+                    log_line(
+                        paths,
+                        job_id,
+                        "warn",
+                        "mix_dub_preview_silence_detect_failed",
+                        serde_json::json!({ "error": e.to_string() }),
+                    )?;
+                    None
+                }
+            };
+            let silence_coverage_for = |start_ms: i64, end_ms: i64| {
+                background_silences
+                    .as_deref()
+                    .map(|ranges| ffmpeg::silence_coverage(ranges, start_ms, end_ms))
+            };
+            if let Some(ranges) = background_silences.as_deref() {
+                let segments: Vec<serde_json::Value> = inputs
+                    .iter()
+                    .map(|(seg, _)| {
+                        serde_json::json!({
+                            "index": seg.index,
+                            "start_ms": seg.start_ms,
+                            "end_ms": seg.end_ms,
+                            "silence_coverage": ffmpeg::silence_coverage(ranges, seg.start_ms, seg.end_ms),
+                        })
+                    })
+                    .collect();
+                log_line(
+                    paths,
+                    job_id,
+                    "info",
+                    "mix_dub_preview_silence_coverage",
+                    serde_json::json!({
+                        "silence_ranges": ranges.len(),
+                        "segments": segments,
+                    }),
+                )?;
+            }
+
             let mut timing_fit_entries: Vec<TimingFitEntry> = Vec::new();
             let mut applied_factors_by_index: HashMap<u32, f32> = HashMap::new();
             if timing_fit_enabled {
@@ -7651,11 +12351,31 @@ if __name__ == "__main__":
                         applied_factor: None,
                         stretched: false,
                         note: None,
+                        silence_coverage: silence_coverage_for(seg.start_ms, seg.end_ms),
                     });
                 }
             }
 
+            let speech_gain_db_by_index = if normalize_speech {
+                measure_speech_gains_db(paths, job_id, &artifacts_dir, &inputs)?
+            } else {
+                HashMap::new()
+            };
+            let speech_gain_filter = |index: u32| {
+                speech_gain_db_by_index
+                    .get(&index)
+                    .map(|gain| format!(",volume={gain:.2}dB"))
+                    .unwrap_or_default()
+            };
+
+            let _ = record_step_done(paths, job_id, "compose_graph");
+            let _ = record_step_start(paths, job_id, "render");
             let mut used_legacy = false;
+            // Backgrounds already at the mix rate skip the resampler.
+            let background_resampled = ffmpeg::probe(paths, &background_path)
+                .ok()
+                .and_then(|probe| probe.audio_sample_rate_hz)
+                != Some(MIX_SAMPLE_RATE_HZ);
             if use_single_pass {
                 set_progress(paths, job_id, 0.15)?;
 
@@ -7665,9 +12385,11 @@ if __name__ == "__main__":
                 // 3) mix background + speech
                 // 4) loudness normalize and limit
                 let mut filter = String::new();
-                filter.push_str(
-                    "[0:a]aresample=44100,aformat=sample_fmts=fltp:channel_layouts=stereo[bg0];",
-                );
+                filter.push_str(if background_resampled {
+                    "[0:a]aresample=44100,aformat=sample_fmts=fltp:channel_layouts=stereo[bg0];"
+                } else {
+                    "[0:a]aformat=sample_fmts=fltp:channel_layouts=stereo[bg0];"
+                });
 
                 for (i, (seg, audio_path)) in inputs.iter().enumerate() {
                     let input_idx = i + 1;
@@ -7713,12 +12435,13 @@ if __name__ == "__main__":
                     }
 
                     filter.push_str(&format!(
-                        "[{input_idx}:a]aresample=44100,aformat=sample_fmts=fltp:channel_layouts=stereo"
+                        "[{input_idx}:a]aresample=44100,aformat=sample_fmts=fltp:channel_layouts=stereo{}",
+                        speech_gain_filter(seg.index)
                     ));
                     if let Some(factor) = applied_factor {
                         if factor > 1.001 {
                             filter.push(',');
-                            filter.push_str(&atempo_chain_for_factor(factor));
+                            filter.push_str(&atempo_chain_for_factor(factor)?);
                         }
                         if timing_fit_enabled {
                             filter.push(',');
@@ -7827,7 +12550,25 @@ if __name__ == "__main__":
                 let mut mixed_count = 0_usize;
                 let total = inputs.len().max(1) as f32;
 
-                for (i, (seg, audio_path)) in inputs.iter().enumerate() {
+                let input_paths: Vec<&Path> = inputs.iter().map(|(_, p)| p.as_path()).collect();
+                if let Some((step, step_path)) =
+                    resumable_mix_step(&artifacts_dir, &background_path, &input_paths)
+                {
+                    log_line(
+                        paths,
+                        job_id,
+                        "info",
+                        "mix_resume_from_step",
+                        serde_json::json!({
+                            "step": step,
+                            "skipped_segments": step,
+                        }),
+                    )?;
+                    current_mix = step_path;
+                    mixed_count = step;
+                }
+
+                for (i, (seg, audio_path)) in inputs.iter().enumerate().skip(mixed_count) {
                     if is_canceled(paths, job_id)? {
                         log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
                         return Ok(());
@@ -7839,14 +12580,18 @@ if __name__ == "__main__":
                     mixed_count += 1;
                     let delay_ms = seg.start_ms.max(0);
                     let step_out = artifacts_dir.join(format!("mix_step_{mixed_count:04}.wav"));
+                    // Render to a temp name first so a crash never leaves a partial step behind
+                    // under the name `resumable_mix_step` looks for.
+                    let step_tmp = artifacts_dir.join(format!("mix_step_{mixed_count:04}.wav.tmp"));
 
                     let filter = format!(
                         concat!(
                             "[0:a]aresample=44100,aformat=sample_fmts=fltp:channel_layouts=stereo[bg];",
-                            "[1:a]aresample=44100,aformat=sample_fmts=fltp:channel_layouts=stereo,",
+                            "[1:a]aresample=44100,aformat=sample_fmts=fltp:channel_layouts=stereo{},",
                             "adelay={}|{}[tts];",
                             "[bg][tts]amix=inputs=2:duration=first:dropout_transition=0:normalize=0[m]"
                         ),
+                        speech_gain_filter(seg.index),
                         delay_ms,
                         delay_ms
                     );
@@ -7861,7 +12606,8 @@ if __name__ == "__main__":
                         .arg(&filter)
                         .args(["-map", "[m]"])
                         .args(["-c:a", "pcm_s16le", "-ar", "44100", "-ac", "2"])
-                        .arg(&step_out)
+                        .args(["-f", "wav"])
+                        .arg(&step_tmp)
                         .output()
                         .map_err(|e| match e.kind() {
                             std::io::ErrorKind::NotFound => EngineError::ExternalToolMissing {
@@ -7871,12 +12617,14 @@ if __name__ == "__main__":
                         })?;
 
                     if !output.status.success() {
+                        let _ = std::fs::remove_file(&step_tmp);
                         return Err(EngineError::ExternalToolFailed {
                             tool: "ffmpeg".to_string(),
                             code: output.status.code(),
                             stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
                         });
                     }
+                    std::fs::rename(&step_tmp, &step_out)?;
 
                     current_mix = step_out;
                 }
@@ -7928,12 +12676,13 @@ if __name__ == "__main__":
                     let window_ms = (seg.end_ms - seg.start_ms).max(0);
                     let window_s = (window_ms as f64) / 1000.0;
                     filter.push_str(&format!(
-                        "[{i}:a]aresample=44100,aformat=sample_fmts=fltp:channel_layouts=stereo"
+                        "[{i}:a]aresample=44100,aformat=sample_fmts=fltp:channel_layouts=stereo{}",
+                        speech_gain_filter(seg.index)
                     ));
                     if let Some(factor) = applied_factors_by_index.get(&seg.index).copied() {
                         if factor > 1.001 {
                             filter.push(',');
-                            filter.push_str(&atempo_chain_for_factor(factor));
+                            filter.push_str(&atempo_chain_for_factor(factor)?);
                         }
                         if timing_fit_enabled {
                             filter.push(',');
@@ -7987,6 +12736,7 @@ if __name__ == "__main__":
                 }
             }
 
+            let _ = record_step_done(paths, job_id, "render");
             set_progress(paths, job_id, 0.95)?;
             log_line(
                 paths,
@@ -7995,27 +12745,40 @@ if __name__ == "__main__":
                 "mix_dub_preview_done",
                 serde_json::json!({
                     "out_path": &final_path,
+                    "manifest_used": manifest_path.to_string_lossy(),
                     "overlays": inputs.len(),
                     "mode": if used_legacy { "legacy_fallback" } else { "single_pass" },
                     "background_mode": background_mode,
                     "ducking_strength": ducking_strength,
                     "loudness_target_lufs": loudness_target_lufs,
                     "timing_fit_enabled": timing_fit_enabled,
+                    "speech_normalization_applied": normalize_speech,
+                    "segments_adjusted": speech_gain_db_by_index.len(),
+                    "background_resampled": used_legacy || background_resampled,
                     "variant_label": variant_label.clone()
                 }),
             )?;
+            let _ = library::record_item_history(
+                paths,
+                &item.id,
+                "mix_done",
+                Some(job_id),
+                None,
+                serde_json::json!({ "out_path": &final_path }),
+            );
 
             if pipeline.auto_pipeline {
                 if !item_has_active_job(paths, &item.id, JobType::MuxDubPreviewV1.as_str())
                     .unwrap_or(false)
                 {
-                    let batch_id = job_batch_id(paths, job_id).ok().flatten();
                     let params_json = serde_json::to_string(&MuxDubPreviewV1Params {
                         item_id: item.id.clone(),
                         output_container: None,
                         keep_original_audio: None,
                         dubbed_audio_lang: None,
                         original_audio_lang: None,
+                        embed_subtitle_track_id: None,
+                        allow_container_override: true,
                         batch_on_import: false,
                         pipeline: Some(LocalizationPipelineOptions {
                             source_track_id: pipeline.source_track_id.clone(),
@@ -8023,37 +12786,34 @@ if __name__ == "__main__":
                             ..pipeline.clone()
                         }),
                     })?;
-                    let _ = enqueue_with_type_item_and_batch_id(
+                    chain_on_complete(
                         paths,
-                        JobType::MuxDubPreviewV1,
-                        params_json,
-                        Some(item.id.clone()),
-                        batch_id,
+                        job_id,
+                        JobDependency::on_success(JobType::MuxDubPreviewV1, params_json),
                     )?;
                 }
             } else if p.batch_on_import {
-                let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
-                if rules.auto_dub_preview
+                let rules = batch_on_import_rules_for_job(paths, job_id);
+                if (rules.auto_dub_preview || rules.auto_mux)
                     && !mux_output_exists(paths, &item.id)
                     && !item_has_active_job(paths, &item.id, JobType::MuxDubPreviewV1.as_str())
                         .unwrap_or(false)
                 {
-                    let batch_id = job_batch_id(paths, job_id).ok().flatten();
                     let params_json = serde_json::to_string(&MuxDubPreviewV1Params {
                         item_id: item.id.clone(),
                         output_container: None,
                         keep_original_audio: None,
                         dubbed_audio_lang: None,
                         original_audio_lang: None,
+                        embed_subtitle_track_id: None,
+                        allow_container_override: true,
                         batch_on_import: true,
                         pipeline: None,
                     })?;
-                    let _ = enqueue_with_type_item_and_batch_id(
+                    chain_on_complete(
                         paths,
-                        JobType::MuxDubPreviewV1,
-                        params_json,
-                        Some(item.id.clone()),
-                        batch_id,
+                        job_id,
+                        JobDependency::on_success(JobType::MuxDubPreviewV1, params_json),
                     )?;
                 }
             }
@@ -8085,6 +12845,20 @@ if __name__ == "__main__":
                 ));
             }
 
+            let embed_track = match p.embed_subtitle_track_id.as_deref() {
+                Some(track_id) => {
+                    let track = subtitle_tracks::get_track(paths, track_id)?;
+                    if track.item_id != p.item_id {
+                        return Err(EngineError::InstallFailed(format!(
+                            "mux subtitle track item_id mismatch: params.item_id={} track.item_id={}",
+                            p.item_id, track.item_id
+                        )));
+                    }
+                    Some(track)
+                }
+                None => None,
+            };
+
             let item_dir = paths.derived_item_dir(&item.id);
             let dub_dir = dub_variant_dir(&item_dir, variant_label.as_deref());
             let dub_audio_path = dub_dir.join("mix_dub_preview_v1.wav");
@@ -8102,7 +12876,25 @@ if __name__ == "__main__":
                 .map(|v| v.trim().to_lowercase())
                 .filter(|v| !v.is_empty())
                 .unwrap_or_else(|| "mp4".to_string());
-            let ext = if container == "mkv" { "mkv" } else { "mp4" };
+            let mut ext = if container == "mkv" { "mkv" } else { "mp4" };
+            if p.allow_container_override {
+                let video_codec = ffmpeg::probe(paths, &media_path)
+                    .ok()
+                    .and_then(|probe| probe.video_codec);
+                if let Some(forced) = mux_container_override(ext, video_codec.as_deref()) {
+                    log_line(
+                        paths,
+                        job_id,
+                        "info",
+                        "mux_container_override",
+                        serde_json::json!({
+                            "original_codec": video_codec,
+                            "forced_container": forced,
+                        }),
+                    )?;
+                    ext = forced;
+                }
+            }
             let out_path = out_dir.join(format!("mux_dub_preview_v1.{ext}"));
 
             if out_path.exists() {
@@ -8122,18 +12914,45 @@ if __name__ == "__main__":
             let original_lang =
                 normalize_lang_tag(p.original_audio_lang.as_deref()).unwrap_or("und");
 
+            let subtitle_srt_path = match embed_track.as_ref() {
+                Some(track) => {
+                    let doc = subtitle_tracks::load_document(paths, &track.id)?;
+                    let srt_path = job_artifacts_dir_for_id(paths, job_id)?
+                        .join(format!("mux_subtitle_{}.srt", track.id));
+                    if let Some(parent) = srt_path.parent() {
+                        std::fs::create_dir_all(parent)?;
+                    }
+                    subtitle_tracks::export_document_srt(&doc, &srt_path)?;
+                    Some(srt_path)
+                }
+                None => None,
+            };
+
             let mut ff = cmd::command(paths.ffmpeg_cmd());
             ff.args(["-nostdin", "-y"]);
             ff.arg("-i").arg(&media_path);
             ff.arg("-i").arg(&dub_audio_path);
+            if let Some(srt_path) = subtitle_srt_path.as_ref() {
+                ff.arg("-i").arg(srt_path);
+            }
             ff.args(["-map", "0:v:0?"]);
             // Put dubbed audio first so it's the default track in most players.
             ff.args(["-map", "1:a:0"]);
             if keep_original_audio {
                 ff.args(["-map", "0:a:0?"]);
             }
+            if subtitle_srt_path.is_some() {
+                ff.args(["-map", "2:0"]);
+            }
             ff.args(["-c:v", "copy"]);
             ff.args(["-c:a", "aac", "-b:a", "192k"]);
+            if let Some(track) = embed_track.as_ref() {
+                let subtitle_codec = if ext == "mp4" { "mov_text" } else { "srt" };
+                let subtitle_lang = normalize_lang_tag(Some(&track.lang)).unwrap_or(&track.lang);
+                ff.args(["-c:s", subtitle_codec]);
+                ff.args(["-metadata:s:s:0", &format!("language={subtitle_lang}")]);
+                ff.args(["-disposition:s:0", "0"]);
+            }
             ff.args(["-shortest"]);
             if ext == "mp4" {
                 ff.args(["-movflags", "+faststart"]);
@@ -8172,16 +12991,26 @@ if __name__ == "__main__":
                 "mux_dub_preview_done",
                 serde_json::json!({
                     "out_path": &out_path,
-                    "container": ext,
+                    "actual_container": ext,
                     "keep_original_audio": keep_original_audio,
                     "dubbed_lang": dubbed_lang,
                     "original_lang": original_lang,
-                    "variant_label": variant_label.clone()
+                    "variant_label": variant_label.clone(),
+                    "subtitle_embedded": subtitle_srt_path.is_some(),
+                    "soft_subtitle_embedded": subtitle_srt_path.is_some(),
+                    "subtitle_track_id": embed_track.as_ref().map(|t| t.id.clone())
                 }),
             )?;
+            let _ = library::record_item_history(
+                paths,
+                &item.id,
+                "mux_done",
+                Some(job_id),
+                None,
+                serde_json::json!({ "out_path": &out_path }),
+            );
 
             if pipeline.auto_pipeline {
-                let batch_id = job_batch_id(paths, job_id).ok().flatten();
                 if pipeline.queue_qc {
                     if let Some(track_id) = pipeline.source_track_id.clone() {
                         if !item_has_active_job(paths, &item.id, JobType::QcReportV1.as_str())
@@ -8192,12 +13021,10 @@ if __name__ == "__main__":
                                 track_id,
                                 variant_label: variant_label.clone(),
                             })?;
-                            let _ = enqueue_with_type_item_and_batch_id(
+                            chain_on_complete(
                                 paths,
-                                JobType::QcReportV1,
-                                params_json,
-                                Some(item.id.clone()),
-                                batch_id.clone(),
+                                job_id,
+                                JobDependency::on_success(JobType::QcReportV1, params_json),
                             )?;
                         }
                     }
@@ -8210,13 +13037,30 @@ if __name__ == "__main__":
                         item_id: item.id.clone(),
                         include_alternates: true,
                         variant_label: variant_label.clone(),
+                        include_subtitles: true,
                     })?;
-                    let _ = enqueue_with_type_item_and_batch_id(
+                    chain_on_complete(
                         paths,
-                        JobType::ExportPackV1,
-                        params_json,
-                        Some(item.id.clone()),
-                        batch_id,
+                        job_id,
+                        JobDependency::on_success(JobType::ExportPackV1, params_json),
+                    )?;
+                }
+            } else if p.batch_on_import {
+                let rules = batch_on_import_rules_for_job(paths, job_id);
+                if rules.auto_export_pack
+                    && !item_has_active_job(paths, &item.id, JobType::ExportPackV1.as_str())
+                        .unwrap_or(false)
+                {
+                    let params_json = serde_json::to_string(&ExportPackV1Params {
+                        item_id: item.id.clone(),
+                        include_alternates: false,
+                        variant_label: variant_label.clone(),
+                        include_subtitles: true,
+                    })?;
+                    chain_on_complete(
+                        paths,
+                        job_id,
+                        JobDependency::on_success(JobType::ExportPackV1, params_json),
                     )?;
                 }
             }
@@ -8230,12 +13074,24 @@ if __name__ == "__main__":
                 return Ok(());
             }
 
+            if p.stems != 2 && p.stems != 5 {
+                return Err(EngineError::InstallFailed(format!(
+                    "unsupported spleeter stem count: {} (expected 2 or 5)",
+                    p.stems
+                )));
+            }
+            let audio_override =
+                resolve_separation_audio_override(p.audio_path_override.as_deref())?;
             log_line(
                 paths,
                 job_id,
                 "info",
                 "separate_begin",
-                serde_json::json!({ "item_id": &p.item_id, "backend": "spleeter:2stems" }),
+                serde_json::json!({
+                    "item_id": &p.item_id,
+                    "backend": format!("spleeter:{}stems", p.stems),
+                    "audio_source": separation_audio_source(audio_override.as_deref()),
+                }),
             )?;
 
             let pack = tools::spleeter_pack_status(paths);
@@ -8247,7 +13103,14 @@ if __name__ == "__main__":
             }
 
             let item = library::get_item_by_id(paths, &p.item_id)?;
-            let media_path = Path::new(&item.media_path);
+            if p.stems == 5 {
+                return run_separate_audio_spleeter_5stems(
+                    paths,
+                    job_id,
+                    &item,
+                    audio_override.as_deref(),
+                );
+            }
 
             let sep_dir = paths
                 .derived_item_dir(&item.id)
@@ -8257,7 +13120,8 @@ if __name__ == "__main__":
 
             let vocals_dst = sep_dir.join("vocals.wav");
             let background_dst = sep_dir.join("background.wav");
-            if vocals_dst.exists()
+            if audio_override.is_none()
+                && vocals_dst.exists()
                 && background_dst.exists()
                 && std::fs::metadata(&vocals_dst).map(|m| m.len()).unwrap_or(0) > 0
                 && std::fs::metadata(&background_dst)
@@ -8275,14 +13139,14 @@ if __name__ == "__main__":
                 )?;
 
                 if p.batch_on_import {
-                    let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
+                    enqueue_batch_on_import_clean_vocals(paths, job_id, &item.id)?;
+                    let rules = batch_on_import_rules_for_job(paths, job_id);
                     if rules.auto_dub_preview
                         && tts_manifest_exists(paths, &item.id)
                         && !mix_output_exists(paths, &item.id)
                         && !item_has_active_job(paths, &item.id, JobType::MixDubPreviewV1.as_str())
                             .unwrap_or(false)
                     {
-                        let batch_id = job_batch_id(paths, job_id).ok().flatten();
                         let params_json = serde_json::to_string(&MixDubPreviewV1Params {
                             item_id: item.id.clone(),
                             ducking_strength: None,
@@ -8290,15 +13154,15 @@ if __name__ == "__main__":
                             timing_fit_enabled: None,
                             timing_fit_min_factor: None,
                             timing_fit_max_factor: None,
+                            normalize_speech: None,
                             batch_on_import: true,
                             pipeline: None,
+                            tts_manifest_path: None,
                         })?;
-                        let _ = enqueue_with_type_item_and_batch_id(
+                        chain_on_complete(
                             paths,
-                            JobType::MixDubPreviewV1,
-                            params_json,
-                            Some(item.id.clone()),
-                            batch_id,
+                            job_id,
+                            JobDependency::on_success(JobType::MixDubPreviewV1, params_json),
                         )?;
                     }
                 }
@@ -8306,145 +13170,23 @@ if __name__ == "__main__":
                 return Ok(());
             }
 
-            let audio_path = sep_dir.join("mix_44k.wav");
-            log_line(
+            let Some(SpleeterRun {
+                audio_path,
+                raw_dir,
+                output,
+            }) = run_spleeter_model(
                 paths,
                 job_id,
-                "info",
-                "separate_extract_audio_begin",
-                serde_json::json!({ "path": &item.media_path, "audio_path": &audio_path }),
-            )?;
-            if audio_path.exists()
-                && std::fs::metadata(&audio_path).map(|m| m.len()).unwrap_or(0) > 0
-            {
-                log_line(
-                    paths,
-                    job_id,
-                    "info",
-                    "separate_extract_audio_resume_skip_existing",
-                    serde_json::json!({ "audio_path": &audio_path }),
-                )?;
-            } else {
-                ffmpeg::extract_audio_wav_44k_stereo(paths, media_path, &audio_path)?;
-            }
-            set_progress(paths, job_id, 0.25)?;
-
-            if is_canceled(paths, job_id)? {
-                log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
+                &item,
+                &sep_dir,
+                audio_override.as_deref(),
+                "spleeter:2stems",
+            )?
+            else {
                 return Ok(());
-            }
-
-            let venv_python = tools::python_venv_python_path(paths).map_err(|_| {
-                EngineError::InstallFailed(
-                    "Python toolchain is not set up. Open Diagnostics -> Tools -> Setup Python toolchain."
-                        .to_string(),
-                )
-            })?;
-
-            let raw_dir = sep_dir.join("raw");
-            std::fs::create_dir_all(&raw_dir)?;
-
-            log_line(
-                paths,
-                job_id,
-                "info",
-                "separate_spleeter_begin",
-                serde_json::json!({ "audio_path": &audio_path, "raw_dir": &raw_dir }),
-            )?;
-
-            let ffmpeg_dir = paths.ffmpeg_dir();
-            let old_path = std::env::var_os("PATH").unwrap_or_default();
-            let ffmpeg_path = format!(
-                "{};{}",
-                ffmpeg_dir.to_string_lossy(),
-                old_path.to_string_lossy()
-            );
-
-            // Use Spleeter's Python API instead of the CLI entrypoint.
-            //
-            // The CLI layer depends on Typer internals that can break across Typer versions,
-            // while the separation backend itself (Separator) remains stable.
-            //
-            // We run a dedicated script file (not `-c`/stdin) so multiprocessing can correctly
-            // re-spawn the main module on Windows.
-            let sep_script_path = sep_dir.join("spleeter_separate.py");
-            let sep_script = r#"
-import argparse
-
-from spleeter.separator import Separator
-
-
-def main() -> None:
-    ap = argparse.ArgumentParser()
-    ap.add_argument("--input", required=True)
-    ap.add_argument("--output", required=True)
-    args = ap.parse_args()
-
-    separator = Separator("spleeter:2stems")
-    separator.separate_to_file(args.input, args.output)
-    print("spleeter_separate_ok")
-
-
-if __name__ == "__main__":
-    main()
-"#;
-            std::fs::write(&sep_script_path, sep_script)?;
-
-            let output = {
-                let mut cmd = cmd::command(&venv_python);
-                cmd.arg(&sep_script_path);
-                cmd.arg("--input").arg(&audio_path);
-                cmd.arg("--output").arg(&raw_dir);
-                cmd.env("PATH", ffmpeg_path);
-                cmd.env("PYTHONNOUSERSITE", "1");
-                cmd.env(
-                    "XDG_CACHE_HOME",
-                    paths
-                        .cache_dir()
-                        .join("python")
-                        .to_string_lossy()
-                        .to_string(),
-                );
-                cmd.env(
-                    "MODEL_PATH",
-                    paths
-                        .python_models_dir()
-                        .join("spleeter")
-                        .to_string_lossy()
-                        .to_string(),
-                );
-                cmd.output()
-            }
-            .map_err(|e| EngineError::InstallFailed(format!("failed to run spleeter: {e}")))?;
-
-            if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(EngineError::InstallFailed(format!(
-                    "spleeter failed (code={:?}): {}",
-                    output.status.code(),
-                    stderr.trim()
-                )));
-            }
+            };
             let split_stdout = String::from_utf8_lossy(&output.stdout);
             let split_stderr = String::from_utf8_lossy(&output.stderr);
-            if !split_stderr.trim().is_empty() {
-                log_line(
-                    paths,
-                    job_id,
-                    "warn",
-                    "separate_spleeter_warning",
-                    serde_json::json!({ "stderr": split_stderr.trim() }),
-                )?;
-            }
-            if !split_stdout.trim().is_empty() {
-                log_line(
-                    paths,
-                    job_id,
-                    "info",
-                    "separate_spleeter_stdout",
-                    serde_json::json!({ "stdout": split_stdout.trim() }),
-                )?;
-            }
 
             let stem_name = audio_path
                 .file_stem()
@@ -8563,14 +13305,14 @@ if __name__ == "__main__":
                 EngineError::InstallFailed(format!(
                     "spleeter stem extraction output not found; expected vocals.wav and accompaniment.wav. stdout={}, stderr={}",
                     split_stdout.trim(),
-                    split_stderr.trim()
+                    format_python_stderr(&split_stderr, PYTHON_STDERR_EXCERPT_LINES)
                 ))
             })?;
             let background_src = background_src.ok_or_else(|| {
                 EngineError::InstallFailed(format!(
                     "spleeter stem extraction output not found; expected vocals.wav and accompaniment.wav. stdout={}, stderr={}",
                     split_stdout.trim(),
-                    split_stderr.trim()
+                    format_python_stderr(&split_stderr, PYTHON_STDERR_EXCERPT_LINES)
                 ))
             })?;
 
@@ -8589,6 +13331,193 @@ if __name__ == "__main__":
                 }),
             )?;
 
+            move_spleeter_stem(&vocals_src, &vocals_dst)?;
+            move_spleeter_stem(&background_src, &background_dst)?;
+
+            let _ = std::fs::remove_dir_all(&stems_dir);
+            set_progress(paths, job_id, 0.95)?;
+
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "separate_done",
+                serde_json::json!({
+                    "vocals_path": &vocals_dst,
+                    "background_path": &background_dst,
+                }),
+            )?;
+
+            if p.batch_on_import {
+                enqueue_batch_on_import_clean_vocals(paths, job_id, &item.id)?;
+                let rules = batch_on_import_rules_for_job(paths, job_id);
+                if rules.auto_dub_preview
+                    && tts_manifest_exists(paths, &item.id)
+                    && !mix_output_exists(paths, &item.id)
+                    && !item_has_active_job(paths, &item.id, JobType::MixDubPreviewV1.as_str())
+                        .unwrap_or(false)
+                {
+                    let params_json = serde_json::to_string(&MixDubPreviewV1Params {
+                        item_id: item.id.clone(),
+                        ducking_strength: None,
+                        loudness_target_lufs: None,
+                        timing_fit_enabled: None,
+                        timing_fit_min_factor: None,
+                        timing_fit_max_factor: None,
+                        normalize_speech: None,
+                        batch_on_import: true,
+                        pipeline: None,
+                        tts_manifest_path: None,
+                    })?;
+                    chain_on_complete(
+                        paths,
+                        job_id,
+                        JobDependency::on_success(JobType::MixDubPreviewV1, params_json),
+                    )?;
+                }
+            }
+        }
+        JobType::SeparateAudioDemucsV1 => {
+            set_progress(paths, job_id, 0.05)?;
+            let p: SeparateAudioDemucsV1Params = serde_json::from_str(params_json)?;
+
+            if is_canceled(paths, job_id)? {
+                log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
+                return Ok(());
+            }
+
+            let audio_override =
+                resolve_separation_audio_override(p.audio_path_override.as_deref())?;
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "separate_begin",
+                serde_json::json!({
+                    "item_id": &p.item_id,
+                    "backend": "demucs:two_stems_vocals_v1",
+                    "audio_source": separation_audio_source(audio_override.as_deref()),
+                }),
+            )?;
+
+            let pack = tools::demucs_pack_status(paths);
+            if !pack.installed {
+                return Err(EngineError::InstallFailed(
+                    "Demucs separation pack is not installed. Open Diagnostics -> Tools -> Install Demucs separation pack."
+                        .to_string(),
+                ));
+            }
+
+            let item = library::get_item_by_id(paths, &p.item_id)?;
+
+            let sep_dir = paths
+                .derived_item_dir(&item.id)
+                .join("separation")
+                .join("demucs_two_stems_v1");
+            std::fs::create_dir_all(&sep_dir)?;
+
+            let vocals_dst = sep_dir.join("vocals.wav");
+            let background_dst = sep_dir.join("background.wav");
+            if audio_override.is_none()
+                && vocals_dst.exists()
+                && background_dst.exists()
+                && std::fs::metadata(&vocals_dst).map(|m| m.len()).unwrap_or(0) > 0
+                && std::fs::metadata(&background_dst)
+                    .map(|m| m.len())
+                    .unwrap_or(0)
+                    > 0
+            {
+                set_progress(paths, job_id, 1.0)?;
+                log_line(
+                    paths,
+                    job_id,
+                    "info",
+                    "separate_resume_skip_existing",
+                    serde_json::json!({ "vocals_path": &vocals_dst, "background_path": &background_dst }),
+                )?;
+
+                if p.batch_on_import {
+                    enqueue_batch_on_import_clean_vocals(paths, job_id, &item.id)?;
+                    let rules = batch_on_import_rules_for_job(paths, job_id);
+                    if rules.auto_dub_preview
+                        && tts_manifest_exists(paths, &item.id)
+                        && !mix_output_exists(paths, &item.id)
+                        && !item_has_active_job(paths, &item.id, JobType::MixDubPreviewV1.as_str())
+                            .unwrap_or(false)
+                    {
+                        let params_json = serde_json::to_string(&MixDubPreviewV1Params {
+                            item_id: item.id.clone(),
+                            ducking_strength: None,
+                            loudness_target_lufs: None,
+                            timing_fit_enabled: None,
+                            timing_fit_min_factor: None,
+                            timing_fit_max_factor: None,
+                            normalize_speech: None,
+                            batch_on_import: true,
+                            pipeline: None,
+                            tts_manifest_path: None,
+                        })?;
+                        chain_on_complete(
+                            paths,
+                            job_id,
+                            JobDependency::on_success(JobType::MixDubPreviewV1, params_json),
+                        )?;
+                    }
+                }
+
+                return Ok(());
+            }
+
+            let audio_path =
+                separation_input_audio(paths, job_id, &item, &sep_dir, audio_override.as_deref())?;
+            set_progress(paths, job_id, 0.25)?;
+
+            if is_canceled(paths, job_id)? {
+                log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
+                return Ok(());
+            }
+
+            let venv_python = tools::python_venv_python_path(paths).map_err(|_| {
+                EngineError::InstallFailed(
+                    "Python toolchain is not set up. Open Diagnostics -> Tools -> Setup Python toolchain."
+                        .to_string(),
+                )
+            })?;
+
+            let raw_dir = sep_dir.join("raw");
+            std::fs::create_dir_all(&raw_dir)?;
+            let torch_home = paths.python_models_dir().join("demucs");
+            std::fs::create_dir_all(&torch_home)?;
+
+            let audio_duration_ms = ffmpeg::probe(paths, &audio_path)
+                .ok()
+                .and_then(|probe| probe.duration_ms);
+            let chunks = match (
+                demucs_chunk_duration_ms(p.chunk_duration_secs, audio_duration_ms),
+                audio_duration_ms,
+            ) {
+                (Some(chunk_ms), Some(total_ms)) => {
+                    demucs_chunk_plan(total_ms, chunk_ms, DEMUCS_CHUNK_OVERLAP_MS)
+                }
+                _ => Vec::new(),
+            };
+            let used_chunking = chunks.len() > 1;
+            let chunk_count = chunks.len().max(1);
+
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "separate_demucs_begin",
+                serde_json::json!({
+                    "audio_path": &audio_path,
+                    "raw_dir": &raw_dir,
+                    "duration_ms": audio_duration_ms,
+                    "used_chunking": used_chunking,
+                    "chunk_count": chunk_count,
+                }),
+            )?;
+
             if vocals_dst.exists() {
                 let _ = std::fs::remove_file(&vocals_dst);
             }
@@ -8596,18 +13525,83 @@ if __name__ == "__main__":
                 let _ = std::fs::remove_file(&background_dst);
             }
 
-            if std::fs::rename(&vocals_src, &vocals_dst).is_err() {
-                std::fs::copy(&vocals_src, &vocals_dst)?;
-                let _ = std::fs::remove_file(&vocals_src);
-            }
-            if std::fs::rename(&background_src, &background_dst).is_err() {
-                std::fs::copy(&background_src, &background_dst)?;
-                let _ = std::fs::remove_file(&background_src);
+            if used_chunking {
+                let chunks_dir = sep_dir.join("chunks");
+                std::fs::create_dir_all(&chunks_dir)?;
+                let mut vocals_parts: Vec<PathBuf> = Vec::with_capacity(chunks.len());
+                let mut background_parts: Vec<PathBuf> = Vec::with_capacity(chunks.len());
+                for (index, (start_ms, duration_ms)) in chunks.iter().copied().enumerate() {
+                    if is_canceled(paths, job_id)? {
+                        log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
+                        return Ok(());
+                    }
+                    let chunk_path = chunks_dir.join(format!("chunk_{index:03}.wav"));
+                    ffmpeg::extract_wav_segment(
+                        paths,
+                        &audio_path,
+                        &chunk_path,
+                        start_ms,
+                        duration_ms,
+                    )?;
+                    let (vocals_part, background_part) = run_demucs_two_stems(
+                        paths,
+                        job_id,
+                        &venv_python,
+                        &torch_home,
+                        &chunk_path,
+                        &raw_dir.join(format!("chunk_{index:03}")),
+                    )?;
+                    vocals_parts.push(vocals_part);
+                    background_parts.push(background_part);
+                    log_line(
+                        paths,
+                        job_id,
+                        "info",
+                        "separate_demucs_chunk_done",
+                        serde_json::json!({
+                            "index": index,
+                            "start_ms": start_ms,
+                            "duration_ms": duration_ms,
+                        }),
+                    )?;
+                    set_progress(
+                        paths,
+                        job_id,
+                        0.25 + 0.6 * ((index + 1) as f32 / chunks.len() as f32),
+                    )?;
+                }
+
+                ffmpeg::concat_wav_files_crossfaded(
+                    paths,
+                    &vocals_parts,
+                    DEMUCS_CHUNK_OVERLAP_MS,
+                    &vocals_dst,
+                )?;
+                ffmpeg::concat_wav_files_crossfaded(
+                    paths,
+                    &background_parts,
+                    DEMUCS_CHUNK_OVERLAP_MS,
+                    &background_dst,
+                )?;
+                let _ = std::fs::remove_dir_all(&chunks_dir);
+            } else {
+                let (vocals_src, background_src) = run_demucs_two_stems(
+                    paths,
+                    job_id,
+                    &venv_python,
+                    &torch_home,
+                    &audio_path,
+                    &raw_dir,
+                )?;
+                if std::fs::rename(&vocals_src, &vocals_dst).is_err() {
+                    std::fs::copy(&vocals_src, &vocals_dst)?;
+                }
+                if std::fs::rename(&background_src, &background_dst).is_err() {
+                    std::fs::copy(&background_src, &background_dst)?;
+                }
             }
 
-            let _ = std::fs::remove_dir_all(&stems_dir);
             set_progress(paths, job_id, 0.95)?;
-
             log_line(
                 paths,
                 job_id,
@@ -8616,18 +13610,20 @@ if __name__ == "__main__":
                 serde_json::json!({
                     "vocals_path": &vocals_dst,
                     "background_path": &background_dst,
+                    "used_chunking": used_chunking,
+                    "chunk_count": chunk_count,
                 }),
             )?;
 
             if p.batch_on_import {
-                let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
+                enqueue_batch_on_import_clean_vocals(paths, job_id, &item.id)?;
+                let rules = batch_on_import_rules_for_job(paths, job_id);
                 if rules.auto_dub_preview
                     && tts_manifest_exists(paths, &item.id)
                     && !mix_output_exists(paths, &item.id)
                     && !item_has_active_job(paths, &item.id, JobType::MixDubPreviewV1.as_str())
                         .unwrap_or(false)
                 {
-                    let batch_id = job_batch_id(paths, job_id).ok().flatten();
                     let params_json = serde_json::to_string(&MixDubPreviewV1Params {
                         item_id: item.id.clone(),
                         ducking_strength: None,
@@ -8635,22 +13631,22 @@ if __name__ == "__main__":
                         timing_fit_enabled: None,
                         timing_fit_min_factor: None,
                         timing_fit_max_factor: None,
+                        normalize_speech: None,
                         batch_on_import: true,
                         pipeline: None,
+                        tts_manifest_path: None,
                     })?;
-                    let _ = enqueue_with_type_item_and_batch_id(
+                    chain_on_complete(
                         paths,
-                        JobType::MixDubPreviewV1,
-                        params_json,
-                        Some(item.id.clone()),
-                        batch_id,
+                        job_id,
+                        JobDependency::on_success(JobType::MixDubPreviewV1, params_json),
                     )?;
                 }
             }
         }
-        JobType::SeparateAudioDemucsV1 => {
+        JobType::SeparateAudioDemucs4StemsV1 => {
             set_progress(paths, job_id, 0.05)?;
-            let p: SeparateAudioDemucsV1Params = serde_json::from_str(params_json)?;
+            let p: SeparateAudioDemucs4StemsV1Params = serde_json::from_str(params_json)?;
 
             if is_canceled(paths, job_id)? {
                 log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
@@ -8662,7 +13658,7 @@ if __name__ == "__main__":
                 job_id,
                 "info",
                 "separate_begin",
-                serde_json::json!({ "item_id": &p.item_id, "backend": "demucs:two_stems_vocals_v1" }),
+                serde_json::json!({ "item_id": &p.item_id, "backend": "demucs:4stems_v1" }),
             )?;
 
             let pack = tools::demucs_pack_status(paths);
@@ -8679,18 +13675,16 @@ if __name__ == "__main__":
             let sep_dir = paths
                 .derived_item_dir(&item.id)
                 .join("separation")
-                .join("demucs_two_stems_v1");
+                .join("demucs_4stems_v1");
             std::fs::create_dir_all(&sep_dir)?;
 
-            let vocals_dst = sep_dir.join("vocals.wav");
-            let background_dst = sep_dir.join("background.wav");
-            if vocals_dst.exists()
-                && background_dst.exists()
-                && std::fs::metadata(&vocals_dst).map(|m| m.len()).unwrap_or(0) > 0
-                && std::fs::metadata(&background_dst)
-                    .map(|m| m.len())
-                    .unwrap_or(0)
-                    > 0
+            let stem_dsts: Vec<PathBuf> = DEMUCS_4STEMS
+                .iter()
+                .map(|stem| sep_dir.join(format!("{stem}.wav")))
+                .collect();
+            if stem_dsts
+                .iter()
+                .all(|path| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0) > 0)
             {
                 set_progress(paths, job_id, 1.0)?;
                 log_line(
@@ -8698,38 +13692,8 @@ if __name__ == "__main__":
                     job_id,
                     "info",
                     "separate_resume_skip_existing",
-                    serde_json::json!({ "vocals_path": &vocals_dst, "background_path": &background_dst }),
+                    serde_json::json!({ "stem_paths": &stem_dsts }),
                 )?;
-
-                if p.batch_on_import {
-                    let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
-                    if rules.auto_dub_preview
-                        && tts_manifest_exists(paths, &item.id)
-                        && !mix_output_exists(paths, &item.id)
-                        && !item_has_active_job(paths, &item.id, JobType::MixDubPreviewV1.as_str())
-                            .unwrap_or(false)
-                    {
-                        let batch_id = job_batch_id(paths, job_id).ok().flatten();
-                        let params_json = serde_json::to_string(&MixDubPreviewV1Params {
-                            item_id: item.id.clone(),
-                            ducking_strength: None,
-                            loudness_target_lufs: None,
-                            timing_fit_enabled: None,
-                            timing_fit_min_factor: None,
-                            timing_fit_max_factor: None,
-                            batch_on_import: true,
-                            pipeline: None,
-                        })?;
-                        let _ = enqueue_with_type_item_and_batch_id(
-                            paths,
-                            JobType::MixDubPreviewV1,
-                            params_json,
-                            Some(item.id.clone()),
-                            batch_id,
-                        )?;
-                    }
-                }
-
                 return Ok(());
             }
 
@@ -8752,7 +13716,13 @@ if __name__ == "__main__":
                     serde_json::json!({ "audio_path": &audio_path }),
                 )?;
             } else {
-                ffmpeg::extract_audio_wav_44k_stereo(paths, media_path, &audio_path)?;
+                ffmpeg::extract_audio_wav_resampled(
+                    paths,
+                    media_path,
+                    &audio_path,
+                    SEPARATION_INPUT_SAMPLE_RATE_HZ,
+                    SEPARATION_INPUT_CHANNELS,
+                )?;
             }
             set_progress(paths, job_id, 0.25)?;
 
@@ -8783,9 +13753,8 @@ if __name__ == "__main__":
             std::fs::create_dir_all(&torch_home)?;
 
             let output = {
-                let mut cmd = cmd::command(&venv_python);
+                let mut cmd = cmd::sanitized_command(&venv_python);
                 cmd.args(["-m", "demucs_infer"]);
-                cmd.args(["--two-stems", "vocals"]);
                 cmd.arg("-o").arg(&raw_dir);
                 cmd.arg(&audio_path);
                 cmd.env("PYTHONNOUSERSITE", "1");
@@ -8798,184 +13767,463 @@ if __name__ == "__main__":
                         .to_string(),
                 );
                 cmd.env("TORCH_HOME", torch_home.to_string_lossy().to_string());
-                cmd.output()
+                run_python_tool(cmd, &JobType::SeparateAudioDemucs4StemsV1, "demucs")?
+            };
+
+            if !output.status.success() {
+                return Err(python_tool_failed(paths, job_id, "demucs", &output));
+            }
+
+            let mut stem_srcs: Vec<Option<PathBuf>> = vec![None; DEMUCS_4STEMS.len()];
+            let mut stack: Vec<PathBuf> = vec![raw_dir.clone()];
+            while let Some(dir) = stack.pop() {
+                let entries = match std::fs::read_dir(&dir) {
+                    Ok(v) => v,
+                    Err(_) => continue,
+                };
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    if path.is_dir() {
+                        stack.push(path);
+                        continue;
+                    }
+                    let name = path
+                        .file_name()
+                        .and_then(|n| n.to_str())
+                        .unwrap_or("")
+                        .to_lowercase();
+                    if let Some(idx) = DEMUCS_4STEMS
+                        .iter()
+                        .position(|stem| name == format!("{stem}.wav"))
+                    {
+                        stem_srcs[idx] = Some(path);
+                    }
+                }
+            }
+
+            for ((stem, src), dst) in DEMUCS_4STEMS.iter().zip(stem_srcs).zip(&stem_dsts) {
+                let src = src.ok_or_else(|| {
+                    EngineError::InstallFailed(format!("demucs output not found ({stem}.wav)"))
+                })?;
+                if dst.exists() {
+                    let _ = std::fs::remove_file(dst);
+                }
+                if std::fs::rename(&src, dst).is_err() {
+                    std::fs::copy(&src, dst)?;
+                }
+            }
+
+            set_progress(paths, job_id, 0.95)?;
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "separate_done",
+                serde_json::json!({ "stem_paths": &stem_dsts }),
+            )?;
+        }
+        JobType::CleanVocalsV1 => {
+            set_progress(paths, job_id, 0.05)?;
+            let p: CleanVocalsV1Params = serde_json::from_str(params_json)?;
+
+            if is_canceled(paths, job_id)? {
+                log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
+                return Ok(());
+            }
+
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "clean_vocals_begin",
+                serde_json::json!({ "item_id": &p.item_id, "source": &p.source }),
+            )?;
+
+            let item = library::get_item_by_id(paths, &p.item_id)?;
+            let vocals_src = clean_vocals_source_path(paths, &item.id, p.source.as_deref())
+                .ok_or_else(|| {
+                    EngineError::InstallFailed(match p.source.as_deref() {
+                        Some(source) => {
+                            format!("{source} vocals stem not found; run that separation first")
+                        }
+                        None => "vocals stem not found; run Separate first (Spleeter or Demucs)"
+                            .to_string(),
+                    })
+                })?;
+
+            let out_dir = paths.derived_item_dir(&item.id).join("cleanup");
+            std::fs::create_dir_all(&out_dir)?;
+            let out_path = out_dir.join("vocals_clean_v1.wav");
+
+            if out_path.exists() && std::fs::metadata(&out_path).map(|m| m.len()).unwrap_or(0) > 0 {
+                set_progress(paths, job_id, 1.0)?;
+                log_line(
+                    paths,
+                    job_id,
+                    "info",
+                    "clean_vocals_resume_skip_existing",
+                    serde_json::json!({ "out_path": &out_path }),
+                )?;
+                return Ok(());
+            }
+
+            let backend = if tools::cleanup_pack_status(paths).installed {
+                run_clean_vocals_noisereduce(paths, job_id, &vocals_src, &out_path)?;
+                "noisereduce_v1"
+            } else {
+                run_clean_vocals_ffmpeg(paths, &vocals_src, &out_path)?;
+                "ffmpeg_filters_v1"
+            };
+
+            set_progress(paths, job_id, 0.95)?;
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "clean_vocals_done",
+                serde_json::json!({
+                    "out_path": &out_path,
+                    "source_path": &vocals_src,
+                    "backend": backend,
+                }),
+            )?;
+        }
+        JobType::ExtractSpeakerVoiceProfileV1 => {
+            set_progress(paths, job_id, 0.05)?;
+            let p: ExtractSpeakerVoiceProfileV1Params = serde_json::from_str(params_json)?;
+            validate_speaker_voice_profile_range(p.start_ms, p.end_ms)?;
+
+            if is_canceled(paths, job_id)? {
+                log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
+                return Ok(());
+            }
+
+            let item = library::get_item_by_id(paths, &p.item_id)?;
+            let item_dir = paths.derived_item_dir(&item.id);
+            let vocals_src =
+                preferred_vocals_path_best_effort(paths, &item.id).ok_or_else(|| {
+                    EngineError::InstallFailed(
+                        "vocals stem not found; run Separate first (Spleeter or Demucs)"
+                            .to_string(),
+                    )
+                })?;
+
+            let file_stem = sanitize_filename_component(&p.speaker_key);
+            if file_stem.is_empty() {
+                return Err(EngineError::InstallFailed(format!(
+                    "speaker_key cannot be used as a file name: {}",
+                    p.speaker_key
+                )));
+            }
+            let out_path = item_dir
+                .join("speaker_profiles")
+                .join(format!("{file_stem}.wav"));
+
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "speaker_profile_extract_begin",
+                serde_json::json!({
+                    "speaker_key": &p.speaker_key,
+                    "start_ms": p.start_ms,
+                    "end_ms": p.end_ms,
+                    "vocals_path": &vocals_src,
+                    "out_path": &out_path,
+                }),
+            )?;
+            ffmpeg::extract_audio_clip_wav_16k_mono(
+                paths,
+                &vocals_src,
+                &out_path,
+                p.start_ms,
+                p.end_ms,
+            )?;
+            set_progress(paths, job_id, 0.8)?;
+
+            let out_path_str = out_path.to_string_lossy().to_string();
+            let existing = speakers::list_item_speaker_settings(paths, &item.id)?
+                .into_iter()
+                .find(|s| s.speaker_key == p.speaker_key.trim());
+            let mut profile_paths = vec![out_path_str.clone()];
+            if let Some(existing) = existing.as_ref() {
+                profile_paths.extend(existing.tts_voice_profile_paths.iter().cloned());
+            }
+            let existing = existing.as_ref();
+            speakers::upsert_item_speaker_setting(
+                paths,
+                &item.id,
+                &p.speaker_key,
+                existing.and_then(|s| s.display_name.clone()),
+                existing.and_then(|s| s.voice_profile_id.clone()),
+                existing.and_then(|s| s.tts_voice_id.clone()),
+                Some(out_path_str),
+                Some(profile_paths),
+                existing.and_then(|s| s.style_preset.clone()),
+                existing.and_then(|s| s.prosody_preset.clone()),
+                existing.and_then(|s| s.pronunciation_overrides.clone()),
+                existing.and_then(|s| s.render_mode.clone()),
+                existing.and_then(|s| s.subtitle_prosody_mode.clone()),
+            )?;
+
+            set_progress(paths, job_id, 0.95)?;
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "speaker_profile_extract_done",
+                serde_json::json!({ "speaker_key": &p.speaker_key, "out_path": &out_path }),
+            )?;
+        }
+        JobType::SyncSubtitlesV1 => {
+            set_progress(paths, job_id, 0.05)?;
+            let p: SyncSubtitlesV1Params = serde_json::from_str(params_json)?;
+
+            if is_canceled(paths, job_id)? {
+                log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
+                return Ok(());
+            }
+
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "sync_subtitles_begin",
+                serde_json::json!({ "item_id": &p.item_id, "source_track_id": &p.source_track_id }),
+            )?;
+
+            let pack = tools::sync_pack_status(paths);
+            if !pack.installed {
+                return Err(EngineError::InstallFailed(
+                    "Subtitle sync pack (aeneas) is not installed in the Python venv.".to_string(),
+                ));
+            }
+            let venv_python = tools::python_venv_python_path(paths)?;
+
+            let item = library::get_item_by_id(paths, &p.item_id)?;
+            let source_track = subtitle_tracks::get_track(paths, &p.source_track_id)?;
+            let source_doc = subtitle_tracks::load_document(paths, &source_track.id)?;
+            let texts = source_doc
+                .segments
+                .iter()
+                .map(|seg| seg.text.split_whitespace().collect::<Vec<_>>().join(" "))
+                .filter(|text| !text.is_empty())
+                .collect::<Vec<_>>();
+            if texts.is_empty() {
+                return Err(EngineError::InstallFailed(
+                    "source subtitle track has no text to align".to_string(),
+                ));
+            }
+
+            let audio_path = artifacts_dir.join("audio_16k.wav");
+            ffmpeg::extract_audio_wav_16k_mono(paths, Path::new(&item.media_path), &audio_path)?;
+            set_progress(paths, job_id, 0.25)?;
+
+            if is_canceled(paths, job_id)? {
+                log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
+                return Ok(());
             }
-            .map_err(|e| EngineError::InstallFailed(format!("failed to run demucs: {e}")))?;
 
+            let text_path = artifacts_dir.join("sync_text.txt");
+            std::fs::write(&text_path, format!("{}\n", texts.join("\n")))?;
+            let fragments_path = artifacts_dir.join("sync_fragments.json");
+            let script_path = artifacts_dir.join("sync_subtitles_aeneas_v1.py");
+            let script = r#"
+import argparse
+import json
+
+from aeneas.executetask import ExecuteTask
+from aeneas.syncmap.fragment import SyncMapFragment
+from aeneas.task import Task
+
+
+def main():
+    ap = argparse.ArgumentParser()
+    ap.add_argument("--audio", required=True)
+    ap.add_argument("--text", required=True)
+    ap.add_argument("--lang", required=True)
+    ap.add_argument("--output", required=True)
+    args = ap.parse_args()
+
+    task = Task(
+        config_string="task_language=%s|is_text_type=plain|os_task_file_format=json" % args.lang
+    )
+    task.audio_file_path_absolute = args.audio
+    task.text_file_path_absolute = args.text
+    ExecuteTask(task).execute()
+
+    fragments = [
+        {
+            "begin_ms": int(round(float(leaf.begin) * 1000)),
+            "end_ms": int(round(float(leaf.end) * 1000)),
+        }
+        for leaf in task.sync_map_leaves(SyncMapFragment.REGULAR)
+    ]
+    with open(args.output, "w", encoding="utf-8") as f:
+        json.dump(fragments, f)
+
+
+if __name__ == "__main__":
+    main()
+"#;
+            std::fs::write(&script_path, script)?;
+
+            let aeneas_lang = aeneas_language_code(&source_track.lang);
+            let mut py_cmd = cmd::sanitized_command(&venv_python);
+            py_cmd.arg(&script_path);
+            py_cmd.arg("--audio").arg(&audio_path);
+            py_cmd.arg("--text").arg(&text_path);
+            py_cmd.arg("--lang").arg(&aeneas_lang);
+            py_cmd.arg("--output").arg(&fragments_path);
+            py_cmd.env("PYTHONNOUSERSITE", "1");
+            let output =
+                run_python_tool(py_cmd, &JobType::SyncSubtitlesV1, "subtitle sync script")?;
             if !output.status.success() {
-                let stderr = String::from_utf8_lossy(&output.stderr);
-                return Err(EngineError::InstallFailed(format!(
-                    "demucs failed (code={:?}): {}",
-                    output.status.code(),
-                    stderr.trim()
-                )));
+                return Err(python_tool_failed(
+                    paths,
+                    job_id,
+                    "subtitle sync script",
+                    &output,
+                ));
             }
+            set_progress(paths, job_id, 0.80)?;
 
-            let mut vocals_src: Option<PathBuf> = None;
-            let mut background_src: Option<PathBuf> = None;
-            let mut stack: Vec<PathBuf> = vec![raw_dir.clone()];
-            while let Some(dir) = stack.pop() {
-                let entries = match std::fs::read_dir(&dir) {
-                    Ok(v) => v,
-                    Err(_) => continue,
-                };
-                for entry in entries.flatten() {
-                    let path = entry.path();
-                    if path.is_dir() {
-                        stack.push(path);
-                        continue;
-                    }
-                    let name = path
-                        .file_name()
-                        .and_then(|n| n.to_str())
-                        .unwrap_or("")
-                        .to_lowercase();
-                    if name == "vocals.wav" {
-                        vocals_src = Some(path);
-                    } else if name == "no_vocals.wav" || name == "accompaniment.wav" {
-                        background_src = Some(path);
-                    }
-                    if vocals_src.is_some() && background_src.is_some() {
-                        break;
-                    }
-                }
-                if vocals_src.is_some() && background_src.is_some() {
-                    break;
-                }
-            }
+            let fragments: Vec<AlignedFragment> =
+                serde_json::from_slice(&std::fs::read(&fragments_path)?)?;
+            let aligned = apply_aligned_fragments(&source_doc, &fragments)?;
 
-            let vocals_src = vocals_src.ok_or_else(|| {
-                EngineError::InstallFailed("demucs output not found (vocals.wav)".to_string())
-            })?;
-            let background_src = background_src.ok_or_else(|| {
-                EngineError::InstallFailed("demucs output not found (no_vocals.wav)".to_string())
-            })?;
+            let conn = db::open(paths)?;
+            db::migrate(&conn)?;
+            let max_version: Option<i64> = conn.query_row(
+                r#"
+SELECT MAX(version)
+FROM subtitle_track
+WHERE item_id=?1 AND kind=?2 AND lang=?3 AND format=?4
+"#,
+                params![
+                    &item.id,
+                    &source_track.kind,
+                    &source_track.lang,
+                    &source_track.format
+                ],
+                |row| row.get(0),
+            )?;
+            let next_version = max_version.unwrap_or(0) + 1;
 
-            if vocals_dst.exists() {
-                let _ = std::fs::remove_file(&vocals_dst);
-            }
-            if background_dst.exists() {
-                let _ = std::fs::remove_file(&background_dst);
-            }
-            if std::fs::rename(&vocals_src, &vocals_dst).is_err() {
-                std::fs::copy(&vocals_src, &vocals_dst)?;
-            }
-            if std::fs::rename(&background_src, &background_dst).is_err() {
-                std::fs::copy(&background_src, &background_dst)?;
-            }
+            let sync_dir = paths.derived_item_dir(&item.id).join("sync");
+            let stem = format!(
+                "{}_{}.v{next_version}",
+                sanitize_filename_component(&source_track.kind),
+                sanitize_filename_component(&source_track.lang)
+            );
+            let json_path = sync_dir.join(format!("{stem}.json"));
+            subtitles::write_artifacts(
+                &aligned,
+                &json_path,
+                &sync_dir.join(format!("{stem}.srt")),
+                &sync_dir.join(format!("{stem}.vtt")),
+            )?;
+
+            let track_id = Uuid::new_v4().to_string();
+            conn.execute(
+                r#"
+INSERT INTO subtitle_track (
+  id,
+  item_id,
+  kind,
+  lang,
+  format,
+  path,
+  created_by,
+  version,
+  parent_track_id
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9)
+"#,
+                params![
+                    &track_id,
+                    &item.id,
+                    &source_track.kind,
+                    &source_track.lang,
+                    &source_track.format,
+                    json_path.to_string_lossy().to_string(),
+                    "sync:aeneas_v1",
+                    next_version,
+                    &source_track.id,
+                ],
+            )?;
 
             set_progress(paths, job_id, 0.95)?;
             log_line(
                 paths,
                 job_id,
                 "info",
-                "separate_done",
-                serde_json::json!({ "vocals_path": &vocals_dst, "background_path": &background_dst }),
+                "sync_subtitles_done",
+                serde_json::json!({
+                    "track_id": track_id,
+                    "json_path": json_path,
+                    "segments": aligned.segments.len(),
+                    "aeneas_lang": aeneas_lang,
+                }),
             )?;
-
-            if p.batch_on_import {
-                let rules = config::load_batch_on_import_rules(paths).unwrap_or_default();
-                if rules.auto_dub_preview
-                    && tts_manifest_exists(paths, &item.id)
-                    && !mix_output_exists(paths, &item.id)
-                    && !item_has_active_job(paths, &item.id, JobType::MixDubPreviewV1.as_str())
-                        .unwrap_or(false)
-                {
-                    let batch_id = job_batch_id(paths, job_id).ok().flatten();
-                    let params_json = serde_json::to_string(&MixDubPreviewV1Params {
-                        item_id: item.id.clone(),
-                        ducking_strength: None,
-                        loudness_target_lufs: None,
-                        timing_fit_enabled: None,
-                        timing_fit_min_factor: None,
-                        timing_fit_max_factor: None,
-                        batch_on_import: true,
-                        pipeline: None,
-                    })?;
-                    let _ = enqueue_with_type_item_and_batch_id(
-                        paths,
-                        JobType::MixDubPreviewV1,
-                        params_json,
-                        Some(item.id.clone()),
-                        batch_id,
-                    )?;
-                }
-            }
         }
-        JobType::CleanVocalsV1 => {
+        JobType::FullPipelineV1 => {
             set_progress(paths, job_id, 0.05)?;
-            let p: CleanVocalsV1Params = serde_json::from_str(params_json)?;
+            let p: FullPipelineV1Params = serde_json::from_str(params_json)?;
 
             if is_canceled(paths, job_id)? {
                 log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
                 return Ok(());
             }
 
+            let item = library::get_item_by_id(paths, &p.item_id)?;
+            let rules = batch_on_import_rules_for_job(paths, job_id);
             log_line(
                 paths,
                 job_id,
                 "info",
-                "clean_vocals_begin",
-                serde_json::json!({ "item_id": &p.item_id }),
+                "full_pipeline_begin",
+                serde_json::json!({
+                    "item_id": &item.id,
+                    "asr_lang": &p.asr_lang,
+                    "model_id": &p.model_id,
+                    "tts_job_type": batch_on_import_tts_job_type(paths, &rules).as_str(),
+                    "ducking_strength": p.ducking_strength,
+                }),
             )?;
 
-            let item = library::get_item_by_id(paths, &p.item_id)?;
-            let vocals_src =
-                separation_vocals_path_best_effort(paths, &item.id).ok_or_else(|| {
-                    EngineError::InstallFailed(
-                        "vocals stem not found; run Separate first (Spleeter or Demucs)"
-                            .to_string(),
-                    )
+            // Separation runs alongside ASR; the mix stage waits for both the TTS manifest and
+            // the background stem before it is queued.
+            if !separation_background_exists(paths, &item.id)
+                && !item_has_active_job(paths, &item.id, JobType::SeparateAudioDemucsV1.as_str())
+                    .unwrap_or(false)
+            {
+                let params_json = serde_json::to_string(&SeparateAudioDemucsV1Params {
+                    item_id: item.id.clone(),
+                    batch_on_import: true,
+                    chunk_duration_secs: None,
+                    audio_path_override: None,
                 })?;
-
-            let out_dir = paths.derived_item_dir(&item.id).join("cleanup");
-            std::fs::create_dir_all(&out_dir)?;
-            let out_path = out_dir.join("vocals_clean_v1.wav");
-
-            if out_path.exists() && std::fs::metadata(&out_path).map(|m| m.len()).unwrap_or(0) > 0 {
-                set_progress(paths, job_id, 1.0)?;
-                log_line(
+                chain_on_complete(
                     paths,
                     job_id,
-                    "info",
-                    "clean_vocals_resume_skip_existing",
-                    serde_json::json!({ "out_path": &out_path }),
+                    JobDependency::on_success(JobType::SeparateAudioDemucsV1, params_json),
                 )?;
-                return Ok(());
-            }
-
-            let filter = "highpass=f=80,lowpass=f=12000,afftdn=nf=-25";
-            let output = cmd::command(paths.ffmpeg_cmd())
-                .args(["-nostdin", "-y"])
-                .arg("-i")
-                .arg(&vocals_src)
-                .args(["-af", filter])
-                .args(["-c:a", "pcm_s16le", "-ar", "44100", "-ac", "2"])
-                .arg(&out_path)
-                .output()
-                .map_err(|e| match e.kind() {
-                    std::io::ErrorKind::NotFound => EngineError::ExternalToolMissing {
-                        tool: "ffmpeg".to_string(),
-                    },
-                    _ => EngineError::Io(e),
-                })?;
-
-            if !output.status.success() {
-                return Err(EngineError::ExternalToolFailed {
-                    tool: "ffmpeg".to_string(),
-                    code: output.status.code(),
-                    stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
-                });
             }
 
-            set_progress(paths, job_id, 0.95)?;
-            log_line(
+            let params_json = serde_json::to_string(&AsrLocalParams {
+                item_id: item.id.clone(),
+                lang: p.asr_lang.clone(),
+                model_id: p.model_id.clone(),
+                batch_on_import: true,
+                pipeline: None,
+                task: None,
+            })?;
+            chain_on_complete(
                 paths,
                 job_id,
-                "info",
-                "clean_vocals_done",
-                serde_json::json!({ "out_path": &out_path, "filter": filter }),
+                JobDependency::on_success(JobType::AsrLocal, params_json),
             )?;
+            set_progress(paths, job_id, 1.0)?;
         }
         JobType::QcReportV1 => {
             set_progress(paths, job_id, 0.05)?;
@@ -9083,6 +14331,18 @@ if __name__ == "__main__":
                 }
             }
 
+            let source = qc_source_document(paths, &track)?;
+            let source_text_by_index: HashMap<u32, &str> = source
+                .as_ref()
+                .map(|(_, source_doc)| {
+                    source_doc
+                        .segments
+                        .iter()
+                        .map(|seg| (seg.index, seg.text.trim()))
+                        .collect()
+                })
+                .unwrap_or_default();
+
             let thresholds = QcThresholds {
                 cps_warn: 17.0,
                 cps_fail: 23.0,
@@ -9092,6 +14352,7 @@ if __name__ == "__main__":
             };
 
             let mut issues: Vec<QcIssueRecord> = Vec::new();
+            let mut segment_reports: Vec<QcSegmentReport> = Vec::with_capacity(doc.segments.len());
             let mut prev_end_ms: Option<i64> = None;
 
             for seg in &doc.segments {
@@ -9198,7 +14459,17 @@ if __name__ == "__main__":
                 }
                 prev_end_ms = Some(seg.end_ms);
 
-                if let Some(tts_ms) = tts_duration_by_index.get(&seg.index).copied() {
+                let tts_duration_ms = tts_duration_by_index.get(&seg.index).copied();
+                segment_reports.push(qc_segment_report(
+                    seg.index,
+                    seg.start_ms,
+                    seg.end_ms,
+                    text,
+                    source_text_by_index.get(&seg.index).copied(),
+                    tts_duration_ms,
+                ));
+
+                if let Some(tts_ms) = tts_duration_ms {
                     if window_ms > 0 && tts_ms > window_ms + 120 {
                         issues.push(QcIssueRecord {
                             kind: "tts_timing".to_string(),
@@ -9241,14 +14512,10 @@ if __name__ == "__main__":
             issues.extend(voice_issues);
             let _ = std::fs::remove_dir_all(&qc_temp_dir);
 
-            let mut by_kind: std::collections::BTreeMap<String, usize> =
-                std::collections::BTreeMap::new();
-            for issue in &issues {
-                *by_kind.entry(issue.kind.clone()).or_insert(0) += 1;
-            }
-
-            let report = QcReportV1 {
-                schema_version: 1,
+            let summary = qc_summary(doc.segments.len(), &issues, &segment_reports);
+            let qc_overall_score = qc_overall_score(&summary);
+            let report = QcReportV1Result {
+                schema_version: QC_REPORT_V1_SCHEMA_VERSION,
                 generated_at_ms: now_ms(),
                 item_id: item.id.clone(),
                 track_id: track.id.clone(),
@@ -9257,13 +14524,12 @@ if __name__ == "__main__":
                 thresholds,
                 tts_backend,
                 tts_manifest_path: tts_manifest_file_path,
-                issues: issues.clone(),
+                issues,
                 voice: voice_report,
-                summary: QcSummary {
-                    total_segments: doc.segments.len(),
-                    issues_total: issues.len(),
-                    issues_by_kind: by_kind,
-                },
+                segments: segment_reports,
+                summary,
+                source_track_id: source.as_ref().map(|(id, _)| id.clone()),
+                qc_overall_score,
             };
 
             let json = serde_json::to_string_pretty(&report)?;
@@ -9278,6 +14544,7 @@ if __name__ == "__main__":
                 serde_json::json!({
                     "out_path": &out_path,
                     "issues": report.summary.issues_total,
+                    "qc_overall_score": report.qc_overall_score,
                     "variant_label": variant_label
                 }),
             )?;
@@ -9316,195 +14583,97 @@ if __name__ == "__main__":
                 let _ = std::fs::remove_file(&tmp_path);
             }
 
-            #[derive(Debug, Clone, Serialize)]
-            struct ExportEntry {
-                zip_path: String,
-                src_path: String,
-                bytes: u64,
-            }
-
-            #[derive(Debug, Clone, Serialize)]
-            struct ExportProvenance {
-                schema_version: u32,
-                generated_at_ms: i64,
-                engine_version: String,
-                item_id: String,
-                item_title: String,
-                source_type: String,
-                source_uri: String,
-                media_path: String,
-                included: Vec<ExportEntry>,
-                jobs: Vec<serde_json::Value>,
-            }
+            let render_dir = job_artifacts_dir_for_id(paths, job_id)?.join("subtitles");
+            let contents = collect_export_pack_contents(
+                paths,
+                job_id,
+                &item,
+                selected_variant.as_deref(),
+                p.include_alternates,
+                p.include_subtitles,
+                &render_dir,
+            )?;
 
-            let mut files: Vec<(PathBuf, String)> = Vec::new();
+            let file = std::fs::File::create(&tmp_path)?;
+            let mut zip = zip::ZipWriter::new(file);
+            let options = zip::write::FileOptions::default()
+                .compression_method(zip::CompressionMethod::Deflated);
+            write_export_pack_item(&mut zip, options, &item, &contents, "")?;
 
-            let mut push_dub_artifacts = |variant_label: Option<&str>, zip_root: String| {
-                let dub_dir = dub_variant_dir(&item_dir, variant_label);
-                let mix_wav = dub_dir.join("mix_dub_preview_v1.wav");
-                if mix_wav.exists() {
-                    files.push((mix_wav, format!("{zip_root}/mix_dub_preview_v1.wav")));
-                }
-                let speech_stem = dub_dir.join("speech_dub_preview_v1.wav");
-                if speech_stem.exists() {
-                    files.push((speech_stem, format!("{zip_root}/speech_dub_preview_v1.wav")));
-                }
-                let mux_mp4 = dub_dir.join("mux_dub_preview_v1.mp4");
-                let mux_mkv = dub_dir.join("mux_dub_preview_v1.mkv");
-                if mux_mp4.exists() {
-                    files.push((mux_mp4, format!("{zip_root}/mux_dub_preview_v1.mp4")));
-                } else if mux_mkv.exists() {
-                    files.push((mux_mkv, format!("{zip_root}/mux_dub_preview_v1.mkv")));
-                }
-            };
-            push_dub_artifacts(
-                selected_variant.as_deref(),
-                match selected_variant.as_deref() {
-                    Some(label) => format!("alternates/{label}"),
-                    None => "dub_preview".to_string(),
-                },
-            );
-            if selected_variant.is_none() && p.include_alternates {
-                let alternates_dir = item_dir.join("dub_preview").join("alternates");
-                if alternates_dir.exists() {
-                    if let Ok(entries) = std::fs::read_dir(&alternates_dir) {
-                        for entry in entries.flatten() {
-                            let path = entry.path();
-                            if !path.is_dir() {
-                                continue;
-                            }
-                            let Some(label) = path.file_name().and_then(|value| value.to_str())
-                            else {
-                                continue;
-                            };
-                            push_dub_artifacts(Some(label), format!("alternates/{label}"));
-                        }
-                    }
-                }
-            }
+            zip.finish()
+                .map_err(|e| EngineError::InstallFailed(format!("zip finish failed: {e}")))?;
 
-            if let Some(bg) = separation_background_path_best_effort(paths, &item.id) {
-                files.push((bg, "separation/background.wav".to_string()));
-            }
-            if let Some(vocals) = separation_vocals_path_best_effort(paths, &item.id) {
-                files.push((vocals, "separation/vocals.wav".to_string()));
+            if out_path.exists() {
+                let _ = std::fs::remove_file(&out_path);
             }
-
-            let cleaned = item_dir.join("cleanup").join("vocals_clean_v1.wav");
-            if cleaned.exists() {
-                files.push((cleaned, "cleanup/vocals_clean_v1.wav".to_string()));
+            if std::fs::rename(&tmp_path, &out_path).is_err() {
+                std::fs::copy(&tmp_path, &out_path)?;
+                let _ = std::fs::remove_file(&tmp_path);
             }
 
-            // Include latest subtitle tracks (best-effort).
-            let tracks = subtitle_tracks::list_tracks(paths, &item.id)?;
-            let mut latest: HashMap<(String, String, String), subtitle_tracks::SubtitleTrackRow> =
-                HashMap::new();
-            for t in tracks {
-                let key = (t.kind.clone(), t.lang.clone(), t.format.clone());
-                let replace = match latest.get(&key) {
-                    Some(existing) => t.version > existing.version,
-                    None => true,
-                };
-                if replace {
-                    latest.insert(key, t);
-                }
-            }
-            for (_k, t) in latest {
-                let src = PathBuf::from(&t.path);
-                if !src.exists() {
-                    continue;
-                }
-                let base = format!(
-                    "subtitles/{kind}.{lang}.v{version}.json",
-                    kind = t.kind,
-                    lang = t.lang,
-                    version = t.version
-                );
-                files.push((src.clone(), base.clone()));
+            let bytes = std::fs::metadata(&out_path).map(|m| m.len()).unwrap_or(0);
+            let result = ExportPackV1Result {
+                zip_path: out_path.to_string_lossy().to_string(),
+                zip_bytes: bytes,
+                included_subtitle_tracks: contents.subtitle_tracks,
+            };
+            persistence::atomic_write_text(
+                &job_artifacts_dir_for_id(paths, job_id)?.join("export_pack_result.json"),
+                &format!("{}\n", serde_json::to_string_pretty(&result)?),
+            )?;
+            set_progress(paths, job_id, 0.95)?;
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "export_pack_done",
+                serde_json::json!({
+                    "out_path": &out_path,
+                    "bytes": bytes,
+                    "subtitle_tracks": result.included_subtitle_tracks.len(),
+                }),
+            )?;
+            let _ = library::record_item_history(
+                paths,
+                &item.id,
+                "export_done",
+                Some(job_id),
+                None,
+                serde_json::json!({ "out_path": &out_path }),
+            );
+        }
+        JobType::ExportMultiPackV1 => {
+            set_progress(paths, job_id, 0.02)?;
+            let p: ExportMultiPackV1Params = serde_json::from_str(params_json)?;
 
-                let srt = src.with_extension("srt");
-                if srt.exists() {
-                    files.push((srt, base.replace(".json", ".srt")));
-                }
-                let vtt = src.with_extension("vtt");
-                if vtt.exists() {
-                    files.push((vtt, base.replace(".json", ".vtt")));
-                }
+            if is_canceled(paths, job_id)? {
+                log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
+                return Ok(());
             }
 
-            let integrity_path = crate::tools::pack_integrity_manifest_status(paths).manifest_path;
-            let integrity_path = PathBuf::from(integrity_path);
-            if integrity_path.exists() {
-                files.push((
-                    integrity_path,
-                    "integrity/pack_integrity_manifest.json".to_string(),
-                ));
-            }
+            let items = p
+                .item_ids
+                .iter()
+                .map(|item_id| library::get_item_by_id(paths, item_id))
+                .collect::<Result<Vec<_>>>()?;
 
-            // Best-effort include QC reports and timing-fit artifacts.
-            let qc_dir = item_dir.join("qc");
-            if qc_dir.exists() {
-                if let Ok(entries) = std::fs::read_dir(&qc_dir) {
-                    for entry in entries.flatten() {
-                        let path = entry.path();
-                        if !path.is_file() {
-                            continue;
-                        }
-                        let name = path
-                            .file_name()
-                            .and_then(|n| n.to_str())
-                            .unwrap_or("")
-                            .to_string();
-                        if name.to_lowercase().ends_with(".json") {
-                            files.push((path, format!("qc/{name}")));
-                        }
-                    }
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "export_multi_pack_begin",
+                serde_json::json!({ "item_count": items.len(), "out_path": &p.out_path }),
+            )?;
+
+            let out_path = PathBuf::from(&p.out_path);
+            if let Some(parent) = out_path.parent() {
+                if !parent.as_os_str().is_empty() {
+                    std::fs::create_dir_all(parent)?;
                 }
             }
-            let timing_fit_report = paths
-                .job_artifacts_dir(job_id)
-                .join("timing_fit_report.json");
-            if timing_fit_report.exists() {
-                files.push((
-                    timing_fit_report,
-                    "dub_preview/timing_fit_report.json".to_string(),
-                ));
-            }
-
-            // Collect relevant job rows for provenance (best-effort).
-            let conn = db::open(paths)?;
-            db::migrate(&conn)?;
-            let mut jobs_json: Vec<serde_json::Value> = Vec::new();
-            let mut stmt = conn.prepare(
-                r#"
-SELECT id, type, status, progress, error, created_at_ms, started_at_ms, finished_at_ms, params_json
-FROM job
-WHERE item_id=?1
-ORDER BY created_at_ms ASC
-"#,
-            )?;
-            let mut rows = stmt.query(params![&item.id])?;
-            while let Some(row) = rows.next()? {
-                let id: String = row.get(0)?;
-                let ty: String = row.get(1)?;
-                let status: String = row.get(2)?;
-                let progress: f32 = row.get(3)?;
-                let error: Option<String> = row.get(4)?;
-                let created_at_ms: i64 = row.get(5)?;
-                let started_at_ms: Option<i64> = row.get(6)?;
-                let finished_at_ms: Option<i64> = row.get(7)?;
-                let params_json_str: String = row.get(8)?;
-                jobs_json.push(serde_json::json!({
-                    "id": id,
-                    "type": ty,
-                    "status": status,
-                    "progress": progress,
-                    "error": error,
-                    "created_at_ms": created_at_ms,
-                    "started_at_ms": started_at_ms,
-                    "finished_at_ms": finished_at_ms,
-                    "params_json": params_json_str,
-                }));
+            let tmp_path = PathBuf::from(format!("{}.{job_id}.tmp", p.out_path));
+            if tmp_path.exists() {
+                let _ = std::fs::remove_file(&tmp_path);
             }
 
             let file = std::fs::File::create(&tmp_path)?;
@@ -9512,45 +14681,53 @@ ORDER BY created_at_ms ASC
             let options = zip::write::FileOptions::default()
                 .compression_method(zip::CompressionMethod::Deflated);
 
-            let mut included: Vec<ExportEntry> = Vec::new();
-            for (src, zip_path) in &files {
-                if !src.exists() {
-                    continue;
+            let mut index_items: Vec<serde_json::Value> = Vec::new();
+            for (idx, item) in items.iter().enumerate() {
+                if is_canceled(paths, job_id)? {
+                    drop(zip);
+                    let _ = std::fs::remove_file(&tmp_path);
+                    log_line(paths, job_id, "info", "job_canceled", serde_json::json!({}))?;
+                    return Ok(());
                 }
-                let bytes = std::fs::metadata(src).map(|m| m.len()).unwrap_or(0);
-                let zip_path = zip_path.replace('\\', "/");
-                zip.start_file(&zip_path, options).map_err(|e| {
-                    EngineError::InstallFailed(format!("zip start file failed ({zip_path}): {e}"))
-                })?;
-                let mut f = std::fs::File::open(src)?;
-                std::io::copy(&mut f, &mut zip)?;
-                included.push(ExportEntry {
-                    zip_path,
-                    src_path: src.to_string_lossy().to_string(),
-                    bytes,
-                });
+
+                let render_dir = job_artifacts_dir_for_id(paths, job_id)?
+                    .join("subtitles")
+                    .join(sanitize_filename_component(&item.id));
+                let contents = collect_export_pack_contents(
+                    paths,
+                    job_id,
+                    item,
+                    None,
+                    true,
+                    true,
+                    &render_dir,
+                )?;
+                let prefix = format!("{}/", item.id);
+                write_export_pack_item(&mut zip, options, item, &contents, &prefix)?;
+                index_items.push(serde_json::json!({
+                    "item_id": &item.id,
+                    "title": &item.title,
+                    "prefix": prefix,
+                    "subtitle_tracks": contents.subtitle_tracks.len(),
+                }));
+
+                set_progress(
+                    paths,
+                    job_id,
+                    0.05 + 0.85 * ((idx + 1) as f32 / items.len() as f32),
+                )?;
             }
 
-            let provenance = ExportProvenance {
-                schema_version: 1,
-                generated_at_ms: now_ms(),
-                engine_version: crate::diagnostics::engine_version().to_string(),
-                item_id: item.id.clone(),
-                item_title: item.title.clone(),
-                source_type: item.source_type.clone(),
-                source_uri: item.source_uri.clone(),
-                media_path: item.media_path.clone(),
-                included: included.clone(),
-                jobs: jobs_json,
-            };
-            let prov_json = serde_json::to_string_pretty(&provenance)?;
-            zip.start_file("provenance/manifest.json", options)
-                .map_err(|e| {
-                    EngineError::InstallFailed(format!(
-                        "zip start file failed (provenance/manifest.json): {e}"
-                    ))
-                })?;
-            zip.write_all(prov_json.as_bytes())?;
+            let index = serde_json::json!({
+                "schema_version": 1,
+                "generated_at_ms": now_ms(),
+                "engine_version": crate::diagnostics::engine_version(),
+                "items": index_items,
+            });
+            zip.start_file("index.json", options).map_err(|e| {
+                EngineError::InstallFailed(format!("zip start file failed (index.json): {e}"))
+            })?;
+            zip.write_all(serde_json::to_string_pretty(&index)?.as_bytes())?;
             zip.write_all(b"\n")?;
 
             zip.finish()
@@ -9570,9 +14747,19 @@ ORDER BY created_at_ms ASC
                 paths,
                 job_id,
                 "info",
-                "export_pack_done",
-                serde_json::json!({ "out_path": &out_path, "bytes": bytes }),
+                "export_multi_pack_done",
+                serde_json::json!({ "out_path": &out_path, "bytes": bytes, "item_count": items.len() }),
             )?;
+            for item in &items {
+                let _ = library::record_item_history(
+                    paths,
+                    &item.id,
+                    "export_done",
+                    Some(job_id),
+                    None,
+                    serde_json::json!({ "out_path": &out_path, "multi_pack": true }),
+                );
+            }
         }
         JobType::InstallPhase2PacksV1 => {
             let p: InstallPhase2PacksV1Params =
@@ -9639,6 +14826,24 @@ ORDER BY created_at_ms ASC
                 }
             }
 
+            let snapshot = tools::write_phase2_pre_install_snapshot(paths)?;
+            let run_guard = Phase2InstallRunGuard {
+                paths,
+                job_id,
+                rollback_on_failure: p.rollback_on_failure,
+                finished: false,
+            };
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "phase2_pre_install_snapshot",
+                serde_json::json!({
+                    "snapshot_path": tools::phase2_pre_install_snapshot_path(paths),
+                    "package_count": snapshot.packages.len(),
+                }),
+            )?;
+
             let started_at_ms = now_ms();
             let plan = tools::phase2_packs_install_plan();
             let mut steps: Vec<Phase2InstallStep> = Vec::new();
@@ -9687,16 +14892,18 @@ ORDER BY created_at_ms ASC
                     continue;
                 }
 
+                let step_started_at_ms = now_ms();
                 let (step_id, step_title, step_log_path) = {
                     let step = &mut state.steps[step_index];
                     step.status = "running".to_string();
-                    step.started_at_ms = Some(now_ms());
+                    step.started_at_ms = Some(step_started_at_ms);
                     step.error = None;
                     state.updated_at_ms = now_ms();
                     (step.id.clone(), step.title.clone(), step.log_path.clone())
                 };
 
                 write_state(&state_path, &latest_path, &state)?;
+                report_phase2_install_progress(completed_steps, total_steps, &step_id, "running");
 
                 let log_path = PathBuf::from(&step_log_path);
                 append_log_line(
@@ -9715,39 +14922,32 @@ ORDER BY created_at_ms ASC
                             Ok(())
                         } else {
                             append_log_line(&log_path, "install: portable python");
-                            let _ = tools::install_portable_python(paths)?;
-                            Ok(())
+                            tools::install_portable_python(paths).map(|_| ())
                         }
                     }
                     "python_toolchain" => {
                         append_log_line(&log_path, "install: python toolchain");
-                        let _ = tools::install_python_toolchain(paths)?;
-                        Ok(())
+                        tools::install_python_toolchain(paths, false).map(|_| ())
                     }
                     "spleeter" => {
                         append_log_line(&log_path, "install: spleeter pack");
-                        let _ = tools::install_spleeter_pack(paths)?;
-                        Ok(())
+                        tools::install_spleeter_pack(paths).map(|_| ())
                     }
                     "diarization" => {
                         append_log_line(&log_path, "install: diarization pack");
-                        let _ = tools::install_diarization_pack(paths)?;
-                        Ok(())
+                        tools::install_diarization_pack(paths).map(|_| ())
                     }
                     "tts_preview" => {
                         append_log_line(&log_path, "install: tts preview pack");
-                        let _ = tools::install_tts_preview_pack(paths)?;
-                        Ok(())
+                        tools::install_tts_preview_pack(paths).map(|_| ())
                     }
                     "tts_neural_local_v1" => {
                         append_log_line(&log_path, "install: neural tts local v1 pack");
-                        let _ = tools::install_tts_neural_local_v1_pack(paths)?;
-                        Ok(())
+                        tools::install_tts_neural_local_v1_pack(paths).map(|_| ())
                     }
                     "tts_voice_preserving_local_v1" => {
                         append_log_line(&log_path, "install: voice-preserving dub pack");
-                        let _ = tools::install_tts_voice_preserving_local_v1_pack(paths)?;
-                        Ok(())
+                        tools::install_tts_voice_preserving_local_v1_pack(paths).map(|_| ())
                     }
                     other => Err(EngineError::InstallFailed(format!(
                         "unknown phase2 pack step id: {other}"
@@ -9770,6 +14970,24 @@ ORDER BY created_at_ms ASC
                         }
                         append_log_line(&log_path, "done");
                         completed_steps += 1;
+                        log_line(
+                            paths,
+                            job_id,
+                            "info",
+                            "phase2_step_done",
+                            serde_json::json!({
+                                "step_name": &step_id,
+                                "step_index": completed_steps - 1,
+                                "total_steps": total_steps,
+                                "elapsed_ms": finished_at_ms - step_started_at_ms,
+                            }),
+                        )?;
+                        report_phase2_install_progress(
+                            completed_steps - 1,
+                            total_steps,
+                            &step_id,
+                            "done",
+                        );
                     }
                     Err(err) => {
                         {
@@ -9779,10 +14997,28 @@ ORDER BY created_at_ms ASC
                             step.finished_at_ms = Some(finished_at_ms);
                             step.error = Some(err.to_string());
                         }
-                        append_log_line(&log_path, &format!("failed: {}", err.to_string()));
+                        append_log_line(&log_path, &format!("failed: {err}"));
                         state.updated_at_ms = now_ms();
                         write_state(&state_path, &latest_path, &state)?;
-                        return Err(err);
+                        log_line(
+                            paths,
+                            job_id,
+                            "error",
+                            "phase2_step_failed",
+                            serde_json::json!({
+                                "failed_step": &step_id,
+                                "error": err.to_string(),
+                            }),
+                        )?;
+                        report_phase2_install_progress(
+                            completed_steps,
+                            total_steps,
+                            &step_id,
+                            "failed",
+                        );
+                        return Err(EngineError::InstallFailed(format!(
+                            "phase2 step {step_id} failed: {err}"
+                        )));
                     }
                 }
 
@@ -9793,6 +15029,7 @@ ORDER BY created_at_ms ASC
                 set_progress(paths, job_id, progress)?;
             }
 
+            run_guard.succeeded();
             set_progress(paths, job_id, 0.98)?;
             log_line(
                 paths,
@@ -9841,6 +15078,42 @@ ORDER BY created_at_ms ASC
                 )?;
             }
         }
+        JobType::InstallModelV1 => {
+            let p: InstallModelV1Params = serde_json::from_str(params_json)?;
+            set_progress(paths, job_id, 0.02)?;
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "model_install_begin",
+                serde_json::json!({ "model_id": &p.model_id }),
+            )?;
+
+            let last_percent = std::cell::Cell::new(0u32);
+            let store = models::ModelStore::new(paths.clone());
+            store.install_model_with_progress(&p.model_id, &|event| {
+                let Some(total) = event.total_bytes.filter(|total| *total > 0) else {
+                    return;
+                };
+                let fraction = event.downloaded_bytes as f32 / total as f32;
+                let percent = (fraction * 100.0) as u32;
+                if percent > last_percent.get() {
+                    last_percent.set(percent);
+                    let _ = set_progress(paths, job_id, 0.02 + fraction * 0.96);
+                }
+            })?;
+            log_line(
+                paths,
+                job_id,
+                "info",
+                "model_install_done",
+                serde_json::json!({ "model_id": &p.model_id }),
+            )?;
+        }
+        JobType::WatchDirectory => {
+            let p: WatchDirectoryParams = serde_json::from_str(params_json)?;
+            run_watch_directory(paths, job_id, &p)?;
+        }
         JobType::DummySleep => {
             let p: DummySleepParams = serde_json::from_str(params_json)?;
             let total = p.seconds.max(1);
@@ -9874,47 +15147,156 @@ ORDER BY created_at_ms ASC
 }
 
 fn set_progress(paths: &AppPaths, job_id: &str, progress: f32) -> Result<()> {
+    let progress = progress.clamp(0.0, 1.0);
+    CURRENT_PROGRESS.with(|current| current.store(progress.to_bits(), Ordering::Relaxed));
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
     conn.execute(
         "UPDATE job SET progress=?1 WHERE id=?2 AND status=?3",
-        params![
-            progress.clamp(0.0, 1.0),
-            job_id,
-            JobStatus::Running.as_str()
-        ],
+        params![progress, job_id, JobStatus::Running.as_str()],
     )?;
     Ok(())
 }
 
-fn set_succeeded(paths: &AppPaths, job_id: &str) -> Result<()> {
+/// Marks `step_name` running; restarting a step (e.g. after a requeue) resets its timestamps.
+pub fn record_step_start(paths: &AppPaths, job_id: &str, step_name: &str) -> Result<()> {
+    update_progress_steps(paths, job_id, |steps| {
+        let step = ProgressStep {
+            name: step_name.to_string(),
+            status: PROGRESS_STEP_STATUS_RUNNING.to_string(),
+            started_at_ms: Some(now_ms()),
+            finished_at_ms: None,
+        };
+        match steps.iter_mut().find(|existing| existing.name == step_name) {
+            Some(existing) => *existing = step,
+            None => steps.push(step),
+        }
+    })
+}
+
+/// Closes every still-running step with `status` (failed or canceled) when the job ends early.
+fn close_open_progress_steps(paths: &AppPaths, job_id: &str, status: &str) -> Result<()> {
+    let finished_at_ms = now_ms();
+    update_progress_steps(paths, job_id, |steps| {
+        for step in steps
+            .iter_mut()
+            .filter(|step| step.status == PROGRESS_STEP_STATUS_RUNNING)
+        {
+            step.status = status.to_string();
+            step.finished_at_ms = Some(finished_at_ms);
+        }
+    })
+}
+
+/// Marks `step_name` done, adding it when it was never started.
+pub fn record_step_done(paths: &AppPaths, job_id: &str, step_name: &str) -> Result<()> {
+    update_progress_steps(paths, job_id, |steps| {
+        let index = match steps.iter().position(|existing| existing.name == step_name) {
+            Some(index) => index,
+            None => {
+                steps.push(ProgressStep {
+                    name: step_name.to_string(),
+                    status: PROGRESS_STEP_STATUS_RUNNING.to_string(),
+                    started_at_ms: None,
+                    finished_at_ms: None,
+                });
+                steps.len() - 1
+            }
+        };
+        steps[index].status = PROGRESS_STEP_STATUS_DONE.to_string();
+        steps[index].finished_at_ms = Some(now_ms());
+    })
+}
+
+fn update_progress_steps(
+    paths: &AppPaths,
+    job_id: &str,
+    update: impl FnOnce(&mut Vec<ProgressStep>),
+) -> Result<()> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
+    let raw: Option<String> = conn
+        .query_row(
+            "SELECT progress_steps_json FROM job WHERE id=?1",
+            [job_id],
+            |row| row.get(0),
+        )
+        .optional()?
+        .ok_or_else(|| EngineError::not_found("job", job_id))?;
+    let mut steps: Vec<ProgressStep> = raw
+        .and_then(|raw| serde_json::from_str(&raw).ok())
+        .unwrap_or_default();
+    update(&mut steps);
     conn.execute(
-        "UPDATE job SET status=?1, progress=1.0, finished_at_ms=?2, error=NULL WHERE id=?3 AND status=?4",
+        "UPDATE job SET progress_steps_json=?1 WHERE id=?2",
+        params![serde_json::to_string(&steps)?, job_id],
+    )?;
+    Ok(())
+}
+
+fn set_succeeded(paths: &AppPaths, job_id: &str) -> Result<()> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let updated = conn.execute(
+        "UPDATE job SET status=?1, progress=1.0, finished_at_ms=?2, error=NULL, worker_id=?5 WHERE id=?3 AND status=?4",
         params![
             JobStatus::Succeeded.as_str(),
             now_ms(),
             job_id,
-            JobStatus::Running.as_str()
+            JobStatus::Running.as_str(),
+            current_worker_id(),
         ],
     )?;
+    if updated > 0 {
+        report_job_status_changed(job_id, JobStatus::Succeeded);
+        let _ = update_avg_job_duration(&conn, job_id);
+        enqueue_on_complete_jobs_or_log(&conn, paths, job_id, true);
+        let _ = report_batch_completion(paths, job_id);
+    }
     Ok(())
 }
 
-fn set_failed(paths: &AppPaths, job_id: &str, error: &str) -> Result<()> {
+/// `final_progress` overwrites the stored progress when given; otherwise it is left as is.
+fn set_failed(
+    paths: &AppPaths,
+    job_id: &str,
+    error: &str,
+    final_progress: Option<f32>,
+) -> Result<()> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
-    conn.execute(
-        "UPDATE job SET status=?1, finished_at_ms=?2, error=?3 WHERE id=?4 AND status=?5",
+    let final_progress = final_progress.map(|progress| progress.clamp(0.0, 1.0));
+    let updated = conn.execute(
+        "UPDATE job SET status=?1, finished_at_ms=?2, error=?3, progress=COALESCE(?4, progress), worker_id=?7 WHERE id=?5 AND status=?6",
         params![
             JobStatus::Failed.as_str(),
             now_ms(),
             error,
+            final_progress,
             job_id,
-            JobStatus::Running.as_str()
+            JobStatus::Running.as_str(),
+            current_worker_id(),
         ],
     )?;
+    if updated > 0 {
+        report_job_status_changed(job_id, JobStatus::Failed);
+        if let Some(hook) = JOB_FAILED_HOOK.get() {
+            let final_progress = conn
+                .query_row("SELECT progress FROM job WHERE id=?1", [job_id], |row| {
+                    row.get::<_, f32>(0)
+                })
+                .ok()
+                .or(final_progress);
+            hook(&JobFailedEvent {
+                job_id: job_id.to_string(),
+                error: error.to_string(),
+                final_progress,
+            });
+        }
+        let _ = close_open_progress_steps(paths, job_id, PROGRESS_STEP_STATUS_FAILED);
+        enqueue_on_complete_jobs_or_log(&conn, paths, job_id, false);
+        let _ = report_batch_completion(paths, job_id);
+    }
     Ok(())
 }
 
@@ -9933,12 +15315,6 @@ fn is_queue_paused(paths: &AppPaths) -> Result<bool> {
     is_queue_paused_conn(&conn)
 }
 
-fn get_max_concurrency(paths: &AppPaths) -> Result<usize> {
-    let conn = db::open(paths)?;
-    db::migrate(&conn)?;
-    get_max_concurrency_conn(&conn)
-}
-
 fn get_max_concurrency_conn(conn: &rusqlite::Connection) -> Result<usize> {
     let value: std::result::Result<String, rusqlite::Error> = conn.query_row(
         "SELECT value FROM meta WHERE key=?1",
@@ -9992,16 +15368,21 @@ fn remove_job_log_files_detailed(
     failures: &mut Vec<JobCleanupFailure>,
     failed_job_ids: &mut HashSet<String>,
     job_id: Option<&str>,
-) -> usize {
+) -> (usize, u64) {
     let mut removed = 0_usize;
-    for path in std::iter::once(base_path.to_path_buf())
-        .chain((1..=JOB_LOG_MAX_BACKUPS).map(|i| path_with_suffix(base_path, &format!(".{i}"))))
-    {
+    let mut removed_bytes = 0_u64;
+    for path in std::iter::once(base_path.to_path_buf()).chain(
+        (1..=JOB_LOG_MAX_BACKUPS_LIMIT).map(|i| path_with_suffix(base_path, &format!(".{i}"))),
+    ) {
         if !path.exists() {
             continue;
         }
+        let bytes = path_size_bytes_best_effort(&path);
         match std::fs::remove_file(&path) {
-            Ok(_) => removed += 1,
+            Ok(_) => {
+                removed += 1;
+                removed_bytes += bytes;
+            }
             Err(err) => {
                 failures.push(JobCleanupFailure {
                     scope: "job_log".to_string(),
@@ -10014,7 +15395,15 @@ fn remove_job_log_files_detailed(
             }
         }
     }
-    removed
+    (removed, removed_bytes)
+}
+
+fn path_size_bytes_best_effort(path: &Path) -> u64 {
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.is_dir() => diagnostics::directory_size_bytes_best_effort(path),
+        Ok(meta) => meta.len(),
+        Err(_) => 0,
+    }
 }
 
 fn clear_dir_entries(dir: &Path) -> Result<usize> {
@@ -10045,12 +15434,13 @@ fn clear_dir_entries_detailed(
     dir: &Path,
     scope: &str,
     failures: &mut Vec<JobCleanupFailure>,
-) -> Result<usize> {
+) -> Result<(usize, u64)> {
     if !dir.exists() {
-        return Ok(0);
+        return Ok((0, 0));
     }
 
     let mut removed = 0_usize;
+    let mut removed_bytes = 0_u64;
     for entry in std::fs::read_dir(dir)? {
         let entry = match entry {
             Ok(v) => v,
@@ -10064,11 +15454,13 @@ fn clear_dir_entries_detailed(
             }
         };
         let path = entry.path();
+        let bytes = path_size_bytes_best_effort(&path);
         if remove_path_recursively(&path, scope, failures).is_ok() {
             removed += 1;
+            removed_bytes += bytes;
         }
     }
-    Ok(removed)
+    Ok((removed, removed_bytes))
 }
 
 fn remove_output_dir_targets(
@@ -10076,8 +15468,9 @@ fn remove_output_dir_targets(
     scope: &str,
     failures: &mut Vec<JobCleanupFailure>,
     failed_job_ids: &mut HashSet<String>,
-) -> usize {
+) -> (usize, u64) {
     let mut removed = 0_usize;
+    let mut removed_bytes = 0_u64;
     for target in targets {
         if !target.path.exists() {
             continue;
@@ -10103,13 +15496,15 @@ fn remove_output_dir_targets(
             failed_job_ids.extend(target.source_job_ids.iter().cloned());
             continue;
         }
+        let bytes = diagnostics::directory_size_bytes_best_effort(&target.path);
         if remove_path_recursively(&target.path, scope, failures).is_ok() {
             removed += 1;
+            removed_bytes += bytes;
         } else {
             failed_job_ids.extend(target.source_job_ids.iter().cloned());
         }
     }
-    removed
+    (removed, removed_bytes)
 }
 
 fn remove_path_recursively(
@@ -10150,7 +15545,7 @@ fn count_job_log_files(base_path: &Path) -> usize {
     if base_path.exists() {
         count += 1;
     }
-    for i in 1..=JOB_LOG_MAX_BACKUPS {
+    for i in 1..=JOB_LOG_MAX_BACKUPS_LIMIT {
         if path_with_suffix(base_path, &format!(".{i}")).exists() {
             count += 1;
         }
@@ -10158,6 +15553,16 @@ fn count_job_log_files(base_path: &Path) -> usize {
     count
 }
 
+fn job_log_files_size_bytes(base_path: &Path) -> u64 {
+    std::iter::once(base_path.to_path_buf())
+        .chain(
+            (1..=JOB_LOG_MAX_BACKUPS_LIMIT).map(|i| path_with_suffix(base_path, &format!(".{i}"))),
+        )
+        .filter_map(|path| std::fs::metadata(path).ok())
+        .map(|meta| meta.len())
+        .sum()
+}
+
 fn count_dir_entries(dir: &Path) -> Result<usize> {
     if !dir.exists() {
         return Ok(0);
@@ -10273,15 +15678,17 @@ fn log_line(
     let line = serde_json::json!({
         "ts_ms": now_ms(),
         "job_id": job_id,
+        "worker_id": current_worker_id(),
+        "thread_id": format!("{:?}", thread::current().id()),
         "level": level,
         "event": event,
         "data": data
     })
     .to_string();
 
-    let path = paths.job_logs_dir().join(format!("{job_id}.jsonl"));
+    let path = paths.job_log_path(job_id);
     std::fs::create_dir_all(paths.job_logs_dir())?;
-    rotate_job_log_if_needed(&path)?;
+    rotate_job_log_if_needed(paths, &path)?;
     std::fs::OpenOptions::new()
         .create(true)
         .append(true)
@@ -10290,17 +15697,22 @@ fn log_line(
     Ok(())
 }
 
-fn rotate_job_log_if_needed(path: &Path) -> Result<()> {
+fn rotate_job_log_if_needed(paths: &AppPaths, path: &Path) -> Result<()> {
     let len = match std::fs::metadata(path) {
         Ok(m) => m.len(),
         Err(_) => return Ok(()),
     };
 
-    if len < JOB_LOG_ROTATE_BYTES {
+    // No valid policy rotates below the minimum, so skip the meta lookup for small logs.
+    if len < JOB_LOG_MIN_ROTATE_BYTES {
+        return Ok(());
+    }
+    let policy = load_job_log_retention_policy(paths);
+    if len < policy.rotate_bytes {
         return Ok(());
     }
 
-    rotate_file_backups(path, JOB_LOG_MAX_BACKUPS)?;
+    rotate_file_backups(path, policy.max_backups)?;
     Ok(())
 }
 
@@ -10338,16 +15750,57 @@ fn path_with_suffix(path: &Path, suffix: &str) -> PathBuf {
     path.with_file_name(format!("{file_name}{suffix}"))
 }
 
-fn prune_job_logs(paths: &AppPaths) -> Result<()> {
+/// Maps job log file names (as written by `log_line`) to the type of the job that owns them.
+fn job_log_file_types(paths: &AppPaths) -> Result<HashMap<String, String>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut stmt = conn.prepare("SELECT type, logs_path FROM job")?;
+    let rows = stmt.query_map([], |row| {
+        Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+    })?;
+    let mut out = HashMap::new();
+    for row in rows.flatten() {
+        let (job_type, logs_path) = row;
+        if let Some(name) = Path::new(&logs_path).file_name() {
+            out.insert(name.to_string_lossy().to_string(), job_type);
+        }
+    }
+    Ok(out)
+}
+
+/// Splits a rotated log name like `job.jsonl.2` into `("job.jsonl", Some(2))`.
+fn split_job_log_backup_suffix(file_name: &str) -> (&str, Option<usize>) {
+    if let Some((base, suffix)) = file_name.rsplit_once('.') {
+        if let Ok(index) = suffix.parse::<usize>() {
+            return (base, Some(index));
+        }
+    }
+    (file_name, None)
+}
+
+fn prune_job_logs(paths: &AppPaths) -> Result<JobLogPruneSummary> {
+    let mut summary = JobLogPruneSummary {
+        pruned_orphan_artifact_dirs: prune_orphan_job_artifact_dirs(paths)?,
+        ..Default::default()
+    };
     let dir = paths.job_logs_dir();
     if !dir.exists() {
-        return Ok(());
+        return Ok(summary);
     }
 
     let now = SystemTime::now();
-    let cutoff = now
-        .checked_sub(Duration::from_secs(JOB_LOG_MAX_AGE_DAYS * 24 * 60 * 60))
-        .unwrap_or(SystemTime::UNIX_EPOCH);
+    let cutoff_for = |days: u64| {
+        now.checked_sub(Duration::from_secs(days * 24 * 60 * 60))
+            .unwrap_or(SystemTime::UNIX_EPOCH)
+    };
+    let policy = load_job_log_retention_policy(paths);
+    let cutoff = cutoff_for(policy.max_age_days);
+    let overrides = policy.overrides;
+    let log_job_types = if overrides.is_empty() {
+        HashMap::new()
+    } else {
+        job_log_file_types(paths).unwrap_or_default()
+    };
 
     let mut candidates: Vec<(PathBuf, SystemTime, u64)> = Vec::new();
     for entry in std::fs::read_dir(&dir)? {
@@ -10366,8 +15819,23 @@ fn prune_job_logs(paths: &AppPaths) -> Result<()> {
         let path = entry.path();
         let size = meta.len();
 
-        if modified < cutoff {
-            let _ = std::fs::remove_file(&path);
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        let (base_name, backup_index) = split_job_log_backup_suffix(&file_name);
+        let type_override = log_job_types
+            .get(base_name)
+            .and_then(|job_type| overrides.iter().find(|o| &o.job_type == job_type));
+        let file_cutoff = type_override
+            .and_then(|o| o.max_age_days)
+            .map(cutoff_for)
+            .unwrap_or(cutoff);
+        let backup_limit = type_override
+            .and_then(|o| o.max_backups)
+            .unwrap_or(policy.max_backups);
+
+        if modified < file_cutoff || backup_index.is_some_and(|i| i > backup_limit) {
+            if std::fs::remove_file(&path).is_ok() {
+                summary.pruned_log_files += 1;
+            }
             continue;
         }
 
@@ -10377,14 +15845,65 @@ fn prune_job_logs(paths: &AppPaths) -> Result<()> {
     candidates.sort_by_key(|(_, modified, _)| *modified);
     let mut total: u64 = candidates.iter().map(|(_, _, size)| *size).sum();
     for (path, _modified, size) in candidates {
-        if total <= JOB_LOG_TOTAL_CAP_BYTES {
+        if total <= policy.total_cap_bytes {
             break;
         }
-        let _ = std::fs::remove_file(&path);
+        if std::fs::remove_file(&path).is_ok() {
+            summary.pruned_log_files += 1;
+        }
         total = total.saturating_sub(size);
     }
 
-    Ok(())
+    Ok(summary)
+}
+
+/// Removes `derived/jobs` subdirs named after a job id or content key that no job row still
+/// references. `invalid-*` dirs and other names are left alone.
+fn prune_orphan_job_artifact_dirs(paths: &AppPaths) -> Result<usize> {
+    let root = paths.derived_jobs_dir();
+    let entries = match std::fs::read_dir(&root) {
+        Ok(entries) => entries,
+        Err(_) => return Ok(0),
+    };
+    let names: Vec<String> = entries
+        .flatten()
+        .filter(|entry| entry.file_type().map(|t| t.is_dir()).unwrap_or(false))
+        .filter_map(|entry| entry.file_name().to_str().map(str::to_string))
+        .filter(|name| {
+            crate::paths::validate_item_id(name).is_ok() || crate::paths::is_content_key(name)
+        })
+        .collect();
+    if names.is_empty() {
+        return Ok(0);
+    }
+
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut pruned = 0usize;
+    for chunk in names.chunks(ORPHAN_ARTIFACT_SCAN_BATCH) {
+        let placeholders = (1..=chunk.len())
+            .map(|i| format!("?{i}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        let mut stmt = conn.prepare(&format!(
+            "SELECT id, content_key FROM job WHERE id IN ({placeholders}) OR content_key IN ({placeholders})"
+        ))?;
+        let mut referenced: HashSet<String> = HashSet::new();
+        let rows = stmt.query_map(rusqlite::params_from_iter(chunk.iter()), |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, Option<String>>(1)?))
+        })?;
+        for row in rows {
+            let (id, content_key) = row?;
+            referenced.insert(id);
+            referenced.extend(content_key);
+        }
+        for name in chunk {
+            if !referenced.contains(name) && std::fs::remove_dir_all(root.join(name)).is_ok() {
+                pruned += 1;
+            }
+        }
+    }
+    Ok(pruned)
 }
 
 fn normalize_and_expand_download_targets(
@@ -10406,26 +15925,30 @@ fn normalize_and_expand_download_targets(
                 )));
             }
 
-            let expanded =
-                match expand_instagram_profile_media_targets(&url, remaining + 1, auth_cookie) {
-                    Ok(values) if !values.is_empty() => values,
-                    Ok(_) | Err(_) => {
-                        let fallback_urls = expand_yt_dlp_urls(
-                            paths,
-                            &url,
-                            remaining + 1,
-                            auth_cookie,
-                            use_browser_cookies_for_url(&url, use_browser_cookies),
-                        )?;
-                        fallback_urls
-                            .into_iter()
-                            .map(|value| DownloadTarget {
-                                url: value,
-                                provider: DOWNLOAD_PROVIDER_YOUTUBE_YT_DLP,
-                            })
-                            .collect()
-                    }
-                };
+            let expanded = match expand_instagram_profile_media_targets(
+                paths,
+                &url,
+                remaining + 1,
+                auth_cookie,
+            ) {
+                Ok(values) if !values.is_empty() => values,
+                Ok(_) | Err(_) => {
+                    let fallback_urls = expand_yt_dlp_urls(
+                        paths,
+                        &url,
+                        remaining + 1,
+                        auth_cookie,
+                        use_browser_cookies_for_url(&url, use_browser_cookies),
+                    )?;
+                    fallback_urls
+                        .into_iter()
+                        .map(|value| DownloadTarget {
+                            url: value,
+                            provider: DOWNLOAD_PROVIDER_YOUTUBE_YT_DLP,
+                        })
+                        .collect()
+                }
+            };
 
             if expanded.is_empty() {
                 return Err(EngineError::InstallFailed(format!(
@@ -10460,7 +15983,7 @@ fn normalize_and_expand_download_targets(
                 )));
             }
 
-            if let Ok(expanded) = expand_instagram_post_media_targets(&url, auth_cookie) {
+            if let Ok(expanded) = expand_instagram_post_media_targets(paths, &url, auth_cookie) {
                 if !expanded.is_empty() {
                     for candidate in expanded {
                         let normalized = normalize_direct_url(&candidate.url)?;
@@ -10545,20 +16068,28 @@ fn normalize_and_expand_download_targets(
     Ok(targets)
 }
 
-fn normalize_direct_urls(inputs: Vec<String>) -> Result<Vec<String>> {
+/// Splits pasted URL lists on whitespace, commas, and semicolons, dropping blanks and repeats.
+fn split_url_inputs(inputs: Vec<String>) -> Vec<String> {
     let mut output: Vec<String> = Vec::new();
     for input in inputs {
         for part in input.split(|ch| matches!(ch, '\n' | '\r' | '\t' | ',' | ';' | ' ')) {
             let trimmed = part.trim();
-            if trimmed.is_empty() {
-                continue;
-            }
-            let normalized = normalize_direct_url(trimmed)?;
-            if !output.iter().any(|existing| existing == &normalized) {
-                output.push(normalized);
+            if !trimmed.is_empty() && !output.iter().any(|existing| existing == trimmed) {
+                output.push(trimmed.to_string());
             }
         }
     }
+    output
+}
+
+fn normalize_direct_urls(inputs: Vec<String>) -> Result<Vec<String>> {
+    let mut output: Vec<String> = Vec::new();
+    for part in split_url_inputs(inputs) {
+        let normalized = normalize_direct_url(&part)?;
+        if !output.iter().any(|existing| existing == &normalized) {
+            output.push(normalized);
+        }
+    }
     Ok(output)
 }
 
@@ -10598,13 +16129,57 @@ pub(crate) fn normalize_auth_cookie(value: Option<String>) -> Result<Option<Stri
         )));
     }
 
-    if parse_cookie_header_pairs(trimmed).is_empty() {
-        return Err(EngineError::InstallFailed(
-            "session input must be a cookie header, browser-export JSON, Netscape cookie text, or an existing cookie-file path".to_string(),
+    if parse_cookie_header_pairs(trimmed).is_empty() {
+        return Err(EngineError::InstallFailed(
+            "session input must be a cookie header, browser-export JSON, Netscape cookie text, or an existing cookie-file path".to_string(),
+        ));
+    }
+
+    Ok(Some(trimmed.to_string()))
+}
+
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct CookieValidation {
+    pub is_netscape_format: bool,
+    pub is_header_format: bool,
+    pub warnings: Vec<String>,
+}
+
+/// Heuristic shape check for an already-normalized cookie. Only produces warnings: users may
+/// paste formats this does not recognise that yt-dlp still accepts.
+pub fn validate_cookie_format(cookie: &str) -> CookieValidation {
+    let trimmed = cookie.trim();
+    let is_netscape_format = trimmed.starts_with(NETSCAPE_COOKIE_FILE_HEADER);
+    let is_header_format = !is_netscape_format
+        && trimmed.contains('=')
+        && trimmed
+            .split(';')
+            .map(str::trim)
+            .filter(|pair| !pair.is_empty())
+            .all(|pair| {
+                pair.split_once('=')
+                    .is_some_and(|(name, _)| !name.trim().is_empty())
+            });
+
+    let mut warnings = Vec::new();
+    if trimmed.chars().count() < COOKIE_SUSPICIOUSLY_SHORT_CHARS {
+        warnings.push(format!(
+            "cookie is suspiciously short ({} chars)",
+            trimmed.chars().count()
         ));
     }
+    if !is_netscape_format && (trimmed.contains('\n') || trimmed.contains('\r')) {
+        warnings.push("cookie header contains newlines".to_string());
+    }
+    if !is_netscape_format && !is_header_format {
+        warnings.push("cookie is neither Netscape cookie text nor name=value pairs".to_string());
+    }
 
-    Ok(Some(trimmed.to_string()))
+    CookieValidation {
+        is_netscape_format,
+        is_header_format,
+        warnings,
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq)]
@@ -10733,7 +16308,7 @@ fn format_netscape_cookie_records(records: &[NetscapeCookieRecord]) -> Option<St
     }
     dedup_records.reverse();
 
-    let mut contents = String::from("# Netscape HTTP Cookie File\n");
+    let mut contents = format!("{NETSCAPE_COOKIE_FILE_HEADER}\n");
     for record in dedup_records {
         let line_domain = if record.http_only {
             format!("#HttpOnly_{}", record.domain)
@@ -10875,6 +16450,15 @@ fn normalize_output_dir(value: Option<String>) -> Option<String> {
     }
 }
 
+fn normalize_schedule_after_ms(value: Option<i64>) -> Result<Option<i64>> {
+    match value {
+        Some(ms) if ms <= 0 => Err(EngineError::InstallFailed(format!(
+            "schedule_after_ms must be a positive epoch timestamp (got {ms})"
+        ))),
+        other => Ok(other),
+    }
+}
+
 fn parse_cookie_header_pairs(cookie_header: &str) -> Vec<(String, String)> {
     let mut pairs: Vec<(String, String)> = Vec::new();
     for part in cookie_header.split(';') {
@@ -10955,7 +16539,7 @@ fn write_auth_cookie_as_netscape_file(
     url: &str,
     auth_cookie: &str,
 ) -> Result<PathBuf> {
-    let artifacts_dir = paths.job_artifacts_dir(job_id);
+    let artifacts_dir = job_artifacts_dir_for_id(paths, job_id)?;
     std::fs::create_dir_all(&artifacts_dir)?;
     let cookie_path = artifacts_dir.join("yt_dlp_cookies.txt");
     let contents = auth_cookie_to_netscape_text(url, auth_cookie)?;
@@ -11180,7 +16764,7 @@ fn strip_range_query_params(raw_url: &str) -> String {
     parsed.to_string()
 }
 
-fn normalize_direct_url(value: &str) -> Result<String> {
+pub(crate) fn normalize_direct_url(value: &str) -> Result<String> {
     let trimmed = value.trim();
     if trimmed.is_empty() {
         return Err(EngineError::InstallFailed("empty URL provided".to_string()));
@@ -11272,6 +16856,25 @@ fn is_instagram_url(url: &str) -> bool {
     host == "instagram.com" || host == "www.instagram.com" || host.ends_with(".instagram.com")
 }
 
+fn is_supported_ytdlp_site_url(url: &str) -> bool {
+    let Ok(parsed) = Url::parse(url.trim()) else {
+        return false;
+    };
+    if parsed.scheme() != "http" && parsed.scheme() != "https" {
+        return false;
+    }
+    let Some(host) = parsed.host_str().map(|value| value.to_ascii_lowercase()) else {
+        return false;
+    };
+    let host = host.trim_end_matches('.');
+    SUPPORTED_YTDLP_DOMAINS.iter().any(|domain| {
+        host == *domain
+            || host
+                .strip_suffix(domain)
+                .is_some_and(|prefix| prefix.ends_with('.'))
+    })
+}
+
 fn is_instagram_media_asset_url(url: &str) -> bool {
     let parsed = match url.parse::<ureq::http::Uri>() {
         Ok(value) => value,
@@ -11375,6 +16978,72 @@ fn instagram_shortcode_to_media_id(shortcode: &str) -> Option<String> {
     Some(value.to_string())
 }
 
+fn instagram_media_id_from_url(url: &str) -> Option<String> {
+    instagram_shortcode_from_url(url).and_then(|code| instagram_shortcode_to_media_id(&code))
+}
+
+fn instagram_thumbnail_url_from_info(payload: &serde_json::Value) -> Option<String> {
+    payload
+        .get("items")
+        .and_then(|v| v.as_array())
+        .and_then(|items| items.first())
+        .and_then(|item| item.get("thumbnail_url"))
+        .or_else(|| payload.get("thumbnail_url"))
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
+/// Best-effort metadata fetch ahead of yt-dlp, which can fail on private Reels. Saves the post
+/// thumbnail as `thumbnail.jpg` in the job artifacts dir.
+fn instagram_pre_fetch_thumbnail(
+    paths: &AppPaths,
+    job_id: &str,
+    post_url: &str,
+    media_id: &str,
+    auth_cookie: Option<&str>,
+) -> Result<PathBuf> {
+    let info_url = format!("https://www.instagram.com/api/v1/media/{media_id}/info/");
+    let payload = download_instagram_json(paths, &info_url, auth_cookie, Some(post_url))?;
+    let thumbnail_url = instagram_thumbnail_url_from_info(&payload).ok_or_else(|| {
+        EngineError::InstallFailed("instagram media info has no thumbnail_url".to_string())
+    })?;
+
+    let agent = build_http_agent(paths, 25);
+    let mut response = call_get_with_cookie(&agent, &thumbnail_url, None).map_err(|err| {
+        EngineError::InstallFailed(format!(
+            "thumbnail request failed for {}: {err}",
+            redact_url_for_log(&thumbnail_url)
+        ))
+    })?;
+    let status = response.status().as_u16();
+    if status >= 400 {
+        return Err(EngineError::InstallFailed(format!(
+            "thumbnail http {status} for {}",
+            redact_url_for_log(&thumbnail_url)
+        )));
+    }
+    let mut bytes = Vec::new();
+    response
+        .body_mut()
+        .as_reader()
+        .take(INSTAGRAM_THUMBNAIL_MAX_BYTES)
+        .read_to_end(&mut bytes)?;
+    if image_batch::sniff_image_format(&bytes).is_none() {
+        return Err(EngineError::InstallFailed(format!(
+            "thumbnail is not an image: {}",
+            redact_url_for_log(&thumbnail_url)
+        )));
+    }
+
+    let artifacts_dir = job_artifacts_dir_for_id(paths, job_id)?;
+    std::fs::create_dir_all(&artifacts_dir)?;
+    let path = artifacts_dir.join("thumbnail.jpg");
+    persistence::atomic_write_bytes(&path, &bytes)?;
+    Ok(path)
+}
+
 fn is_likely_youtube_video_url(url: &str) -> bool {
     let uri = match url.parse::<ureq::http::Uri>() {
         Ok(v) => v,
@@ -11403,6 +17072,7 @@ fn effective_download_provider(provider: &str, url: &str) -> &'static str {
     if normalized == DOWNLOAD_PROVIDER_YOUTUBE_YT_DLP
         || is_youtube_url(url)
         || is_instagram_url(url)
+        || is_supported_ytdlp_site_url(url)
     {
         DOWNLOAD_PROVIDER_YOUTUBE_YT_DLP
     } else {
@@ -11630,17 +17300,83 @@ enum CommandRunError {
     TimedOut(u64),
 }
 
-fn kill_child_process_tree(child: &mut std::process::Child) {
-    #[cfg(windows)]
-    {
-        let pid = child.id().to_string();
-        let _ = cmd::command("taskkill")
-            .args(["/PID", &pid, "/T", "/F"])
-            .status();
+/// Upper bound on a single Python helper run for each job type. Separation and neural TTS scale
+/// with media length, so they get the longest budgets.
+fn python_tool_timeout(job_type: &JobType) -> Duration {
+    let secs = match job_type {
+        JobType::DubVoicePreservingV1
+        | JobType::TtsNeuralLocalV1
+        | JobType::SeparateAudioSpleeter
+        | JobType::SeparateAudioDemucsV1
+        | JobType::SeparateAudioDemucs4StemsV1 => 7200,
+        JobType::DiarizeLocalV1 => 3600,
+        JobType::TtsPreviewPyttsx3V1 | JobType::CleanVocalsV1 | JobType::SyncSubtitlesV1 => 1800,
+        _ => PYTHON_TOOL_DEFAULT_TIMEOUT_SECS,
+    };
+    Duration::from_secs(secs)
+}
+
+/// Builds the job error for a failed Python script, keeping the full stderr in
+/// `{job_id}_stderr.txt` under the job's artifacts dir.
+fn python_tool_failed(
+    paths: &AppPaths,
+    job_id: &str,
+    label: &str,
+    output: &std::process::Output,
+) -> EngineError {
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    if let Ok(dir) = job_artifacts_dir_for_id(paths, job_id) {
+        if std::fs::create_dir_all(&dir).is_ok() {
+            let _ = std::fs::write(dir.join(format!("{job_id}_stderr.txt")), stderr.as_bytes());
+        }
     }
+    EngineError::InstallFailed(format!(
+        "{label} failed (code={:?}): {}",
+        output.status.code(),
+        format_python_stderr(&stderr, PYTHON_STDERR_EXCERPT_LINES)
+    ))
+}
+
+/// Condenses Python stderr for job errors: drops progress-bar lines, keeps the last traceback
+/// (or the last `max_lines` lines) and caps the result at [`PYTHON_STDERR_EXCERPT_MAX_CHARS`].
+pub(crate) fn format_python_stderr(stderr: &str, max_lines: usize) -> String {
+    let lines: Vec<&str> = stderr
+        .trim_end()
+        .split('\n')
+        .filter(|line| !line.starts_with('\r') && !line.contains('%'))
+        .map(|line| line.trim_end_matches('\r'))
+        .collect();
+    let start = lines
+        .iter()
+        .rposition(|line| line.starts_with("Traceback (most recent call last)"))
+        .unwrap_or_else(|| lines.len().saturating_sub(max_lines));
+    let excerpt = lines[start..].join("\n");
+    let excerpt = excerpt.trim();
+    if excerpt.chars().count() <= PYTHON_STDERR_EXCERPT_MAX_CHARS {
+        return excerpt.to_string();
+    }
+    let tail: String = excerpt
+        .chars()
+        .rev()
+        .take(PYTHON_STDERR_EXCERPT_MAX_CHARS)
+        .collect::<Vec<_>>()
+        .into_iter()
+        .rev()
+        .collect();
+    format!("[truncated] {tail}")
+}
 
-    let _ = child.kill();
-    let _ = child.wait();
+/// Runs a Python helper under [`python_tool_timeout`]. Spawn failures keep the
+/// `failed to run <label>` wording; timeouts surface as `ExternalToolFailed`.
+fn run_python_tool(
+    py_cmd: std::process::Command,
+    job_type: &JobType,
+    label: &str,
+) -> Result<std::process::Output> {
+    cmd::run_with_timeout(py_cmd, python_tool_timeout(job_type)).map_err(|e| match e {
+        EngineError::Io(e) => EngineError::InstallFailed(format!("failed to run {label}: {e}")),
+        other => other,
+    })
 }
 
 fn run_command_output_with_control(
@@ -11683,7 +17419,7 @@ fn run_command_output_with_control(
         if abort_reason.is_none() {
             if let Some(id) = job_id {
                 if is_canceled(paths, id).unwrap_or(false) {
-                    kill_child_process_tree(&mut child);
+                    cmd::kill_process_tree(&mut child);
                     abort_reason = Some(CommandRunError::Canceled);
                 }
             }
@@ -11692,7 +17428,7 @@ fn run_command_output_with_control(
             && timeout_secs > 0
             && started.elapsed() >= Duration::from_secs(timeout_secs)
         {
-            kill_child_process_tree(&mut child);
+            cmd::kill_process_tree(&mut child);
             abort_reason = Some(CommandRunError::TimedOut(timeout_secs));
         }
 
@@ -11713,7 +17449,7 @@ fn run_command_output_with_control(
                 thread::sleep(Duration::from_millis(EXTERNAL_CMD_POLL_INTERVAL_MS));
             }
             Err(err) => {
-                kill_child_process_tree(&mut child);
+                cmd::kill_process_tree(&mut child);
                 let _ = stdout_handle.join();
                 let _ = stderr_handle.join();
                 return Err(CommandRunError::Wait(err));
@@ -11748,6 +17484,9 @@ fn run_yt_dlp(
 ) -> Result<std::process::Output> {
     let mut failures: Vec<String> = Vec::new();
     let mut candidates: Vec<(String, Vec<String>)> = Vec::new();
+    if let Some(custom) = paths.custom_ytdlp_path() {
+        candidates.push((custom.to_string_lossy().to_string(), Vec::new()));
+    }
     match ensure_bundled_yt_dlp(paths) {
         Ok(Some(bundled)) if bundled.exists() => {
             candidates.push((bundled.to_string_lossy().to_string(), Vec::new()));
@@ -11766,10 +17505,15 @@ fn run_yt_dlp(
         "python3".to_string(),
         vec!["-m".to_string(), "yt_dlp".to_string()],
     ));
+    let proxy_url =
+        config::active_proxy_config(paths).and_then(|proxy| proxy.url_with_credentials());
 
     for (program, prefix) in candidates {
         let mut cmd = cmd::command(&program);
         cmd.args(prefix);
+        if let Some(proxy_url) = proxy_url.as_deref() {
+            cmd.arg("--proxy").arg(proxy_url);
+        }
         cmd.args(args);
         match run_command_output_with_control(paths, &mut cmd, job_id, timeout_secs) {
             Ok(output) => {
@@ -11845,6 +17589,54 @@ fn expand_yt_dlp_urls(
     limit: usize,
     auth_cookie: Option<&str>,
     use_browser_cookies: bool,
+) -> Result<Vec<String>> {
+    let mut urls = expand_yt_dlp_print_lines(
+        paths,
+        url,
+        limit,
+        auth_cookie,
+        use_browser_cookies,
+        "webpage_url",
+    )?;
+
+    if urls.is_empty() && is_likely_youtube_video_url(url) {
+        urls.push(url.to_string());
+    }
+
+    Ok(urls)
+}
+
+/// Maps video id to title for the first `limit` entries of a playlist or channel URL.
+fn expand_yt_dlp_titles(
+    paths: &AppPaths,
+    url: &str,
+    limit: usize,
+    auth_cookie: Option<&str>,
+    use_browser_cookies: bool,
+) -> Result<HashMap<String, String>> {
+    let lines = expand_yt_dlp_print_lines(
+        paths,
+        url,
+        limit,
+        auth_cookie,
+        use_browser_cookies,
+        "%(title)s %(id)s",
+    )?;
+    Ok(lines
+        .iter()
+        .filter_map(|line| line.rsplit_once(' '))
+        .map(|(title, id)| (id.to_string(), title.trim().to_string()))
+        .collect())
+}
+
+/// Runs a flat-playlist yt-dlp pass and returns the deduplicated non-empty `--print` lines.
+fn expand_yt_dlp_print_lines(
+    paths: &AppPaths,
+    url: &str,
+    limit: usize,
+    auth_cookie: Option<&str>,
+    use_browser_cookies: bool,
+    print_template: &str,
 ) -> Result<Vec<String>> {
     let limit = limit.max(1);
     let mut args = vec![
@@ -11855,7 +17647,7 @@ fn expand_yt_dlp_urls(
         "--ignore-errors".to_string(),
         "--no-warnings".to_string(),
         "--print".to_string(),
-        "webpage_url".to_string(),
+        print_template.to_string(),
         "--playlist-end".to_string(),
         limit.to_string(),
         url.to_string(),
@@ -11904,25 +17696,21 @@ fn expand_yt_dlp_urls(
         )
     })?;
     let mut seen: HashSet<String> = HashSet::new();
-    let mut urls: Vec<String> = Vec::new();
+    let mut lines: Vec<String> = Vec::new();
     for line in String::from_utf8_lossy(&output.stdout).lines() {
         let trimmed = line.trim();
         if trimmed.is_empty() {
             continue;
         }
         if seen.insert(trimmed.to_string()) {
-            urls.push(trimmed.to_string());
+            lines.push(trimmed.to_string());
         }
     }
-
-    if urls.is_empty() && is_likely_youtube_video_url(url) {
-        urls.push(url.to_string());
-    }
-
-    Ok(urls)
+    Ok(lines)
 }
 
 fn expand_instagram_profile_media_targets(
+    paths: &AppPaths,
     profile_url: &str,
     limit: usize,
     auth_cookie: Option<&str>,
@@ -11937,8 +17725,12 @@ fn expand_instagram_profile_media_targets(
     let profile_info_url =
         format!("https://i.instagram.com/api/v1/users/web_profile_info/?username={username}");
 
-    let profile_info =
-        download_instagram_json(&profile_info_url, auth_cookie, Some(&profile_page_url))?;
+    let profile_info = download_instagram_json(
+        paths,
+        &profile_info_url,
+        auth_cookie,
+        Some(&profile_page_url),
+    )?;
     let user_id = profile_info
         .get("data")
         .and_then(|v| v.get("user"))
@@ -11966,7 +17758,8 @@ fn expand_instagram_profile_media_targets(
             }
         }
 
-        let feed_json = download_instagram_json(&feed_url, auth_cookie, Some(&profile_page_url))?;
+        let feed_json =
+            download_instagram_json(paths, &feed_url, auth_cookie, Some(&profile_page_url))?;
         let items = feed_json
             .get("items")
             .and_then(|v| v.as_array())
@@ -12015,6 +17808,7 @@ fn expand_instagram_profile_media_targets(
 }
 
 fn expand_instagram_post_media_targets(
+    paths: &AppPaths,
     post_url: &str,
     auth_cookie: Option<&str>,
 ) -> Result<Vec<DownloadTarget>> {
@@ -12031,7 +17825,7 @@ fn expand_instagram_post_media_targets(
         ))
     })?;
     let info_url = format!("https://i.instagram.com/api/v1/media/{media_id}/info/");
-    let payload = download_instagram_json(&info_url, auth_cookie, Some(post_url))?;
+    let payload = download_instagram_json(paths, &info_url, auth_cookie, Some(post_url))?;
 
     let items = payload
         .get("items")
@@ -12126,11 +17920,12 @@ fn instagram_candidate_score(candidate: &serde_json::Value) -> i64 {
 }
 
 fn download_instagram_json(
+    paths: &AppPaths,
     url: &str,
     auth_cookie: Option<&str>,
     referer: Option<&str>,
 ) -> Result<serde_json::Value> {
-    let agent = build_http_agent(25);
+    let agent = build_http_agent(paths, 25);
     let mut request = agent
         .get(url)
         .header("X-IG-App-ID", INSTAGRAM_API_APP_ID)
@@ -12184,71 +17979,57 @@ fn download_instagram_json(
     })
 }
 
+/// Per-download settings for [`download_url_to_library`] and the yt-dlp path behind it.
+#[derive(Debug, Clone, Copy, Default)]
+struct UrlDownloadOptions<'a> {
+    auth_cookie: Option<&'a str>,
+    output_dir: Option<&'a str>,
+    output_subdir: Option<&'a str>,
+    use_browser_cookies: bool,
+    output_path_template: Option<&'a str>,
+    filename_template: Option<&'a str>,
+    format_preference: Option<&'a str>,
+    quality_preference: Option<&'a str>,
+    subtitle_mode: Option<&'a str>,
+    output_filename_template: Option<&'a str>,
+    /// yt-dlp `--download-archive` file.
+    download_archive: Option<&'a str>,
+    write_info_json: bool,
+}
+
+/// `Ok(None)` when yt-dlp skipped the URL because `download_archive` already lists it.
 fn download_url_to_library(
     paths: &AppPaths,
     url: &str,
     job_id: &str,
     provider: &str,
-    auth_cookie: Option<&str>,
-    output_dir: Option<&str>,
-    output_subdir: Option<&str>,
-    use_browser_cookies: bool,
-    output_path_template: Option<&str>,
-    filename_template: Option<&str>,
-    format_preference: Option<&str>,
-    quality_preference: Option<&str>,
-    subtitle_mode: Option<&str>,
-) -> Result<PathBuf> {
+    options: &UrlDownloadOptions<'_>,
+) -> Result<Option<PathBuf>> {
     if provider == DOWNLOAD_PROVIDER_YOUTUBE_YT_DLP {
-        return download_yt_dlp_url_to_library(
-            paths,
-            url,
-            job_id,
-            auth_cookie,
-            output_dir,
-            output_subdir,
-            use_browser_cookies,
-            output_path_template,
-            filename_template,
-            format_preference,
-            quality_preference,
-            subtitle_mode,
-        );
+        return download_yt_dlp_url_to_library(paths, url, job_id, options);
     }
 
     match download_direct_http_url_to_library(
         paths,
         url,
         job_id,
-        auth_cookie,
-        output_dir,
-        output_subdir,
-        output_path_template,
-        filename_template,
-        format_preference,
-        quality_preference,
-        subtitle_mode,
+        options.auth_cookie,
+        options.output_dir,
+        options.output_subdir,
+        options.output_path_template,
+        options.filename_template,
+        options.format_preference,
+        options.quality_preference,
+        options.subtitle_mode,
+        options.output_filename_template,
     ) {
-        Ok(path) => Ok(path),
+        Ok(path) => Ok(Some(path)),
         Err(direct_err) => {
             if is_canceled(paths, job_id).unwrap_or(false) {
                 return Err(EngineError::InstallFailed("job canceled".to_string()));
             }
             // Fallback for webpage URLs and hosts that need extractor logic.
-            match download_yt_dlp_url_to_library(
-                paths,
-                url,
-                job_id,
-                auth_cookie,
-                output_dir,
-                output_subdir,
-                use_browser_cookies,
-                output_path_template,
-                filename_template,
-                format_preference,
-                quality_preference,
-                subtitle_mode,
-            ) {
+            match download_yt_dlp_url_to_library(paths, url, job_id, options) {
                 Ok(path) => Ok(path),
                 Err(yt_err) => Err(EngineError::InstallFailed(format!(
                     "direct download failed for {} ({direct_err}); yt-dlp fallback failed ({yt_err})",
@@ -12259,6 +18040,37 @@ fn download_url_to_library(
     }
 }
 
+/// Logs `low_disk_space_warning` when the volume holding `dir` (or its nearest existing
+/// ancestor) is below the configured `warn_below_free_gb` threshold.
+fn warn_if_download_disk_space_low(paths: &AppPaths, job_id: &str, dir: &Path) -> Result<()> {
+    let Some(threshold_gb) = config::load_download_disk_space_config(paths)
+        .ok()
+        .and_then(|config| config.warn_below_free_gb)
+    else {
+        return Ok(());
+    };
+    let Some(existing) = dir.ancestors().find(|candidate| candidate.exists()) else {
+        return Ok(());
+    };
+    let Ok(free_gb) = diagnostics::disk_free_gb(existing) else {
+        return Ok(());
+    };
+    if free_gb < f64::from(threshold_gb) {
+        log_line(
+            paths,
+            job_id,
+            "warning",
+            "low_disk_space_warning",
+            serde_json::json!({
+                "dir": existing.to_string_lossy().to_string(),
+                "free_gb": free_gb,
+                "warn_below_free_gb": threshold_gb,
+            }),
+        )?;
+    }
+    Ok(())
+}
+
 fn resolve_downloads_dir(paths: &AppPaths, output_subdir: Option<&str>) -> Result<PathBuf> {
     resolve_downloads_dir_with_override(paths, None, output_subdir)
 }
@@ -12404,6 +18216,52 @@ fn build_yt_dlp_output_template(
     format!("{path_template}/{file_template}_{suffix}.%(ext)s")
 }
 
+const YT_DLP_OUTPUT_TEMPLATE_FIELDS: &[&str] = &[
+    "%(title)s",
+    "%(id)s",
+    "%(ext)s",
+    "%(upload_date)s",
+    "%(uploader)s",
+    "%(playlist_title)s",
+];
+
+/// Accepts a yt-dlp `--output` template built only from the supported fields, relative to the
+/// download dir and free of shell metacharacters.
+fn validate_yt_dlp_output_template(template: &str) -> Result<()> {
+    let bytes = template.as_bytes();
+    // Drive paths (`C:\x`, `C:x`) and `..` are checked by hand so they are caught on every OS.
+    let has_drive_prefix = bytes.len() >= 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':';
+    let has_parent_dir = Path::new(template)
+        .components()
+        .any(|c| matches!(c, std::path::Component::ParentDir))
+        || template.split(['/', '\\']).any(|part| part == "..");
+    if Path::new(template).is_absolute()
+        || template.starts_with(['/', '\\'])
+        || has_drive_prefix
+        || has_parent_dir
+    {
+        return Err(EngineError::InstallFailed(format!(
+            "output filename template must stay inside the download folder: {template}"
+        )));
+    }
+    let mut literal = template.to_string();
+    for field in YT_DLP_OUTPUT_TEMPLATE_FIELDS {
+        literal = literal.replace(field, "");
+    }
+    if literal.contains("%(") {
+        return Err(EngineError::InstallFailed(format!(
+            "output filename template uses an unsupported field (allowed: {}): {template}",
+            YT_DLP_OUTPUT_TEMPLATE_FIELDS.join(", ")
+        )));
+    }
+    if let Some(bad) = literal.chars().find(|c| "`$\\|;&><()".contains(*c)) {
+        return Err(EngineError::InstallFailed(format!(
+            "output filename template contains forbidden character '{bad}': {template}"
+        )));
+    }
+    Ok(())
+}
+
 fn resolve_download_preset(
     paths: &AppPaths,
     requested_preset_id: Option<&str>,
@@ -12470,6 +18328,7 @@ fn download_direct_http_url_to_library(
     format_preference: Option<&str>,
     quality_preference: Option<&str>,
     subtitle_mode: Option<&str>,
+    output_filename_template: Option<&str>,
 ) -> Result<PathBuf> {
     let mut last_err = match download_direct_media_asset(
         paths,
@@ -12510,21 +18369,23 @@ fn download_direct_http_url_to_library(
         }
 
         if should_try_yt_dlp_candidate(&candidate) {
-            match download_yt_dlp_url_to_library(
-                paths,
-                &candidate,
-                job_id,
+            let options = UrlDownloadOptions {
                 auth_cookie,
                 output_dir,
                 output_subdir,
-                use_browser_cookies_for_url(&candidate, false),
+                use_browser_cookies: use_browser_cookies_for_url(&candidate, false),
                 output_path_template,
                 filename_template,
                 format_preference,
                 quality_preference,
                 subtitle_mode,
-            ) {
-                Ok(path) => return Ok(path),
+                output_filename_template,
+                ..Default::default()
+            };
+            match download_yt_dlp_url_to_library(paths, &candidate, job_id, &options) {
+                Ok(Some(path)) => return Ok(path),
+                // Only an archive skip yields no path, and no archive is passed here.
+                Ok(None) => {}
                 Err(e) => last_err = Some(e.to_string()),
             }
         }
@@ -12537,12 +18398,13 @@ fn download_direct_http_url_to_library(
     )))
 }
 
-fn build_http_agent(timeout_secs: u64) -> ureq::Agent {
+fn build_http_agent(paths: &AppPaths, timeout_secs: u64) -> ureq::Agent {
     let mut config = ureq::Agent::config_builder();
     config = config
         .http_status_as_error(false)
         .timeout_global(Some(Duration::from_secs(timeout_secs.max(1))))
-        .user_agent(DEFAULT_HTTP_USER_AGENT);
+        .user_agent(DEFAULT_HTTP_USER_AGENT)
+        .proxy(config::active_proxy_config(paths).and_then(|proxy| proxy.ureq_proxy()));
     config.build().into()
 }
 
@@ -12577,7 +18439,7 @@ fn download_direct_media_asset(
     let downloads_dir = resolve_downloads_dir_with_override(paths, output_dir, output_subdir)?;
     std::fs::create_dir_all(&downloads_dir)?;
 
-    let agent = build_http_agent(60);
+    let agent = build_http_agent(paths, 60);
     let mut response = call_get_with_cookie(&agent, &request_url, auth_cookie).map_err(|err| {
         EngineError::InstallFailed(format!(
             "request failed for {}: {err}",
@@ -12594,7 +18456,24 @@ fn download_direct_media_asset(
     }
 
     let content_type = header_string(&response, "content-type");
-    let filename = suggested_download_filename(&request_url, job_id);
+    let content_disposition = response
+        .headers()
+        .get("content-disposition")
+        .and_then(|v| v.to_str().ok())
+        .and_then(parse_content_disposition_filename);
+    let (filename, filename_source) =
+        resolve_download_filename(&request_url, content_disposition.as_deref(), job_id);
+    log_line(
+        paths,
+        job_id,
+        "info",
+        "download_begin",
+        serde_json::json!({
+            "url": redact_url_for_log(url),
+            "filename": &filename,
+            "source": filename_source,
+        }),
+    )?;
     let final_path = downloads_dir.join(filename);
     let temp_name = format!(
         "{}.part",
@@ -12687,7 +18566,7 @@ fn discover_embedded_media_urls(
     auth_cookie: Option<&str>,
 ) -> Result<Vec<String>> {
     let start_url = normalize_direct_url(start_url)?;
-    let agent = build_http_agent(25);
+    let agent = build_http_agent(paths, 25);
 
     let mut queue: VecDeque<String> = VecDeque::new();
     queue.push_back(start_url.clone());
@@ -13078,22 +18957,37 @@ fn header_string(response: &ureq::http::Response<ureq::Body>, key: &str) -> Stri
         .to_ascii_lowercase()
 }
 
+/// `.info.json` sidecar yt-dlp writes next to `media_path` (same stem).
+fn yt_dlp_info_json_path(media_path: &Path) -> PathBuf {
+    media_path.with_extension("info.json")
+}
+
+/// `Ok(None)` when `options.download_archive` already lists the video and yt-dlp skipped it.
 fn download_yt_dlp_url_to_library(
     paths: &AppPaths,
     url: &str,
     job_id: &str,
-    auth_cookie: Option<&str>,
-    output_dir: Option<&str>,
-    output_subdir: Option<&str>,
-    use_browser_cookies: bool,
-    output_path_template: Option<&str>,
-    filename_template: Option<&str>,
-    format_preference: Option<&str>,
-    quality_preference: Option<&str>,
-    subtitle_mode: Option<&str>,
-) -> Result<PathBuf> {
+    options: &UrlDownloadOptions<'_>,
+) -> Result<Option<PathBuf>> {
+    let UrlDownloadOptions {
+        auth_cookie,
+        output_dir,
+        output_subdir,
+        use_browser_cookies,
+        output_path_template,
+        filename_template,
+        format_preference,
+        quality_preference,
+        subtitle_mode,
+        output_filename_template,
+        download_archive,
+        write_info_json,
+    } = *options;
     let downloads_dir = resolve_downloads_dir_with_override(paths, output_dir, output_subdir)?;
-    let template = build_yt_dlp_output_template(job_id, output_path_template, filename_template);
+    let template = match normalize_non_empty(output_filename_template) {
+        Some(custom) => custom,
+        None => build_yt_dlp_output_template(job_id, output_path_template, filename_template),
+    };
 
     let mut args = vec![
         "--socket-timeout".to_string(),
@@ -13140,6 +19034,20 @@ fn download_yt_dlp_url_to_library(
         args.push("--write-auto-subs".to_string());
     }
 
+    if write_info_json {
+        args.push("--write-info-json".to_string());
+        args.push("--no-write-playlist-metafiles".to_string());
+    }
+
+    let download_archive = normalize_non_empty(download_archive);
+    if let Some(archive) = download_archive.as_deref() {
+        if let Some(parent) = Path::new(archive).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        args.push("--download-archive".to_string());
+        args.push(archive.to_string());
+    }
+
     if !is_playlist_candidate_url(url) {
         args.insert(0, "--no-playlist".to_string());
     }
@@ -13217,18 +19125,22 @@ fn download_yt_dlp_url_to_library(
             js_runtime_available,
         )
     })?;
-    let downloaded = String::from_utf8_lossy(&output.stdout)
+    let Some(downloaded) = String::from_utf8_lossy(&output.stdout)
         .lines()
         .map(str::trim)
         .filter(|line| !line.is_empty())
         .last()
         .map(PathBuf::from)
-        .ok_or_else(|| {
-            EngineError::InstallFailed(format!(
-                "yt-dlp did not report an output file for {}",
-                redact_url_for_log(url)
-            ))
-        })?;
+    else {
+        // yt-dlp exits cleanly without output when the archive already lists the video.
+        if download_archive.is_some() {
+            return Ok(None);
+        }
+        return Err(EngineError::InstallFailed(format!(
+            "yt-dlp did not report an output file for {}",
+            redact_url_for_log(url)
+        )));
+    };
 
     let downloaded = if downloaded.is_absolute() {
         downloaded
@@ -13248,7 +19160,7 @@ fn download_yt_dlp_url_to_library(
         )));
     }
 
-    Ok(downloaded)
+    Ok(Some(downloaded))
 }
 
 pub(crate) fn write_auth_cookie_secret_path(path: &Path, cookie_input: &str) -> Result<()> {
@@ -13280,13 +19192,51 @@ pub(crate) fn remove_auth_cookie_secret_path(path: &Path) {
     let _ = std::fs::remove_file(path);
 }
 
+/// Job cookie secrets are stored encrypted with [`crypto::generate_machine_key`].
 fn write_job_cookie_secret(paths: &AppPaths, job_id: &str, cookie_header: &str) -> Result<()> {
     paths.ensure_dirs()?;
-    write_auth_cookie_secret_path(&paths.job_cookie_secret_path(job_id), cookie_header)
+    let path = paths.job_cookie_secret_path(job_id);
+    let Some(cookie_header) = normalize_auth_cookie(Some(cookie_header.to_string()))? else {
+        remove_auth_cookie_secret_path(&path);
+        return Ok(());
+    };
+    let key = crypto::generate_machine_key(paths)?;
+    persistence::atomic_write_bytes(
+        &path,
+        &crypto::encrypt_secret(cookie_header.as_bytes(), &key),
+    )?;
+    Ok(())
 }
 
-fn read_job_cookie_secret(paths: &AppPaths, job_id: &str) -> Option<String> {
-    read_auth_cookie_secret_path(&paths.job_cookie_secret_path(job_id))
+/// `Ok(None)` when the job has no cookie secret. A secret that cannot be decrypted is an error
+/// rather than a silent run without the cookie.
+pub(crate) fn read_job_cookie_secret(paths: &AppPaths, job_id: &str) -> Result<Option<String>> {
+    let path = paths.job_cookie_secret_path(job_id);
+    let bytes = match std::fs::read(&path) {
+        Ok(bytes) => bytes,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err.into()),
+    };
+    if !crypto::is_encrypted_secret(&bytes) {
+        // Written before secrets were encrypted.
+        return Ok(read_auth_cookie_secret_path(&path));
+    }
+    let key = crypto::generate_machine_key(paths)?;
+    let plain = crypto::decrypt_secret(&bytes, &key).map_err(|_| EngineError::InvalidInput {
+        message: format!(
+            "job cookie secret {} could not be decrypted; re-enter the cookie and queue the job again",
+            path.display()
+        ),
+    })?;
+    let text = String::from_utf8(plain).map_err(|_| EngineError::InvalidInput {
+        message: format!("job cookie secret {} is not valid UTF-8", path.display()),
+    })?;
+    let trimmed = text.trim();
+    if trimmed.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(trimmed.to_string()))
+    }
 }
 
 fn remove_job_cookie_secret(paths: &AppPaths, job_id: &str) {
@@ -13360,25 +19310,125 @@ fn looks_like_textual_error_payload(sniff_prefix: &[u8]) -> bool {
         || trimmed.starts_with("[")
 }
 
-fn suggested_download_filename(url: &str, job_id: &str) -> String {
-    let raw_name = url
+/// Common filesystem limit for one path component; applied after the job suffix is added.
+const DOWNLOAD_FILENAME_MAX_BYTES: usize = 255;
+const DOWNLOAD_FILENAME_EXT_MAX_BYTES: usize = 16;
+
+/// Extracts the `filename*` (preferred) or `filename` parameter from a `Content-Disposition`
+/// header. Names containing path separators or `..` are rejected.
+fn parse_content_disposition_filename(header_value: &str) -> Option<String> {
+    let mut current = String::new();
+    let mut in_quotes = false;
+    let mut escaped = false;
+    let mut raw_parts: Vec<String> = Vec::new();
+    for ch in header_value.chars() {
+        if escaped {
+            current.push(ch);
+            escaped = false;
+        } else if in_quotes && ch == '\\' {
+            escaped = true;
+        } else if ch == '"' {
+            in_quotes = !in_quotes;
+        } else if ch == ';' && !in_quotes {
+            raw_parts.push(std::mem::take(&mut current));
+        } else {
+            current.push(ch);
+        }
+    }
+    raw_parts.push(current);
+    let params: Vec<(String, String)> = raw_parts
+        .iter()
+        .skip(1)
+        .filter_map(|part| part.split_once('='))
+        .map(|(key, value)| (key.trim().to_ascii_lowercase(), value.trim().to_string()))
+        .collect();
+
+    let extended = params
+        .iter()
+        .find(|(key, _)| key == "filename*")
+        .and_then(|(_, value)| {
+            let (charset, rest) = value.split_once('\'')?;
+            let (_, encoded) = rest.split_once('\'')?;
+            if !charset.eq_ignore_ascii_case("utf-8") {
+                return None;
+            }
+            percent_decode_utf8(encoded)
+        });
+    let name = extended.or_else(|| {
+        params
+            .iter()
+            .find(|(key, _)| key == "filename")
+            .map(|(_, value)| value.clone())
+    })?;
+
+    let name = name.trim();
+    if name.is_empty() || name == "." || name == ".." || name.contains(['/', '\\']) {
+        return None;
+    }
+    let cleaned: String = name
+        .chars()
+        .map(|ch| {
+            if ch.is_control() || matches!(ch, '<' | '>' | ':' | '"' | '|' | '?' | '*') {
+                '_'
+            } else {
+                ch
+            }
+        })
+        .collect();
+    let cleaned = cleaned.trim().trim_end_matches('.').to_string();
+    (!cleaned.is_empty()).then_some(cleaned)
+}
+
+fn percent_decode_utf8(value: &str) -> Option<String> {
+    let bytes = value.as_bytes();
+    let mut out = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let hex = value.get(i + 1..i + 3)?;
+            out.push(u8::from_str_radix(hex, 16).ok()?);
+            i += 3;
+        } else {
+            out.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+/// Picks the output filename for a direct download and reports where it came from.
+fn resolve_download_filename(
+    url: &str,
+    content_disposition_filename: Option<&str>,
+    job_id: &str,
+) -> (String, &'static str) {
+    if let Some(name) = content_disposition_filename {
+        return (
+            download_filename_with_job_suffix(name, job_id),
+            "content-disposition",
+        );
+    }
+    let url_name = url
         .parse::<ureq::http::Uri>()
         .ok()
         .and_then(|uri| {
             uri.path()
                 .rsplit('/')
                 .next()
-                .map(|segment| segment.to_string())
+                .map(sanitize_filename_component)
         })
-        .filter(|name| !name.trim().is_empty())
-        .unwrap_or_else(|| "download.mp4".to_string());
-
-    let mut safe_name = sanitize_filename_component(&raw_name);
-    if safe_name.is_empty() {
-        safe_name = "download.mp4".to_string();
+        .filter(|name| !name.is_empty());
+    match url_name {
+        Some(name) => (download_filename_with_job_suffix(&name, job_id), "url_path"),
+        None => (
+            download_filename_with_job_suffix("download.mp4", job_id),
+            "uuid_fallback",
+        ),
     }
+}
 
-    let mut path = PathBuf::from(&safe_name);
+fn download_filename_with_job_suffix(safe_name: &str, job_id: &str) -> String {
+    let mut path = PathBuf::from(safe_name);
     if path.extension().is_none() {
         path.set_extension("mp4");
     }
@@ -13393,10 +19443,345 @@ fn suggested_download_filename(url: &str, job_id: &str) -> String {
         .and_then(|s| s.to_str())
         .filter(|s| !s.is_empty())
         .unwrap_or("mp4");
-    let suffix = &job_id[..job_id.len().min(8)];
+    let ext = truncate_to_char_boundary(ext, DOWNLOAD_FILENAME_EXT_MAX_BYTES);
+    let suffix = truncate_to_char_boundary(job_id, 8);
+    // The stem absorbs all truncation so the suffix and extension always survive.
+    let budget = DOWNLOAD_FILENAME_MAX_BYTES.saturating_sub(suffix.len() + ext.len() + 2);
+    let stem = truncate_to_char_boundary(stem, budget);
     format!("{stem}_{suffix}.{ext}")
 }
 
+/// Longest prefix of `value` that fits in `max_bytes` without splitting a character.
+fn truncate_to_char_boundary(value: &str, max_bytes: usize) -> &str {
+    if value.len() <= max_bytes {
+        return value;
+    }
+    let mut end = max_bytes;
+    while !value.is_char_boundary(end) {
+        end -= 1;
+    }
+    &value[..end]
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ExportEntry {
+    zip_path: String,
+    src_path: String,
+    bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct ExportProvenance {
+    schema_version: u32,
+    generated_at_ms: i64,
+    engine_version: String,
+    item_id: String,
+    item_title: String,
+    source_type: String,
+    source_uri: String,
+    media_path: String,
+    included: Vec<ExportEntry>,
+    jobs: Vec<serde_json::Value>,
+}
+
+struct ExportPackContents {
+    files: Vec<(PathBuf, String)>,
+    subtitle_tracks: Vec<ExportPackSubtitleTrack>,
+    jobs: Vec<serde_json::Value>,
+}
+
+/// Gathers the files (source path, zip path) and provenance rows for one item's export pack.
+fn collect_export_pack_contents(
+    paths: &AppPaths,
+    job_id: &str,
+    item: &library::LibraryItem,
+    selected_variant: Option<&str>,
+    include_alternates: bool,
+    include_subtitles: bool,
+    render_dir: &Path,
+) -> Result<ExportPackContents> {
+    let item_dir = paths.derived_item_dir(&item.id);
+    let mut files: Vec<(PathBuf, String)> = Vec::new();
+
+    let mut push_dub_artifacts = |variant_label: Option<&str>, zip_root: String| {
+        let dub_dir = dub_variant_dir(&item_dir, variant_label);
+        let mix_wav = dub_dir.join("mix_dub_preview_v1.wav");
+        if mix_wav.exists() {
+            files.push((mix_wav, format!("{zip_root}/mix_dub_preview_v1.wav")));
+        }
+        let speech_stem = dub_dir.join("speech_dub_preview_v1.wav");
+        if speech_stem.exists() {
+            files.push((speech_stem, format!("{zip_root}/speech_dub_preview_v1.wav")));
+        }
+        let mux_mp4 = dub_dir.join("mux_dub_preview_v1.mp4");
+        let mux_mkv = dub_dir.join("mux_dub_preview_v1.mkv");
+        if mux_mp4.exists() {
+            files.push((mux_mp4, format!("{zip_root}/mux_dub_preview_v1.mp4")));
+        } else if mux_mkv.exists() {
+            files.push((mux_mkv, format!("{zip_root}/mux_dub_preview_v1.mkv")));
+        }
+    };
+    push_dub_artifacts(
+        selected_variant,
+        match selected_variant {
+            Some(label) => format!("alternates/{label}"),
+            None => "dub_preview".to_string(),
+        },
+    );
+    if selected_variant.is_none() && include_alternates {
+        let alternates_dir = item_dir.join("dub_preview").join("alternates");
+        if alternates_dir.exists() {
+            if let Ok(entries) = std::fs::read_dir(&alternates_dir) {
+                for entry in entries.flatten() {
+                    let path = entry.path();
+                    if !path.is_dir() {
+                        continue;
+                    }
+                    let Some(label) = path.file_name().and_then(|value| value.to_str()) else {
+                        continue;
+                    };
+                    push_dub_artifacts(Some(label), format!("alternates/{label}"));
+                }
+            }
+        }
+    }
+
+    if let Some(bg) = separation_background_path_best_effort(paths, &item.id) {
+        files.push((bg, "separation/background.wav".to_string()));
+    }
+    if let Some(vocals) = separation_vocals_path_best_effort(paths, &item.id) {
+        files.push((vocals, "separation/vocals.wav".to_string()));
+    }
+
+    let cleaned = item_dir.join("cleanup").join("vocals_clean_v1.wav");
+    if cleaned.exists() {
+        files.push((cleaned, "cleanup/vocals_clean_v1.wav".to_string()));
+    }
+
+    // Include latest subtitle documents plus SRT/VTT renders of every track.
+    let mut included_subtitle_tracks: Vec<ExportPackSubtitleTrack> = Vec::new();
+    if include_subtitles {
+        let tracks = subtitle_tracks::list_tracks(paths, &item.id)?;
+        let mut latest: HashMap<(String, String, String), subtitle_tracks::SubtitleTrackRow> =
+            HashMap::new();
+        for t in &tracks {
+            let key = (t.kind.clone(), t.lang.clone(), t.format.clone());
+            let replace = match latest.get(&key) {
+                Some(existing) => t.version > existing.version,
+                None => true,
+            };
+            if replace {
+                latest.insert(key, t.clone());
+            }
+        }
+        for (_k, t) in latest {
+            let src = PathBuf::from(&t.path);
+            if !src.exists() {
+                continue;
+            }
+            files.push((
+                src,
+                format!(
+                    "subtitles/{kind}.{lang}.v{version}.json",
+                    kind = t.kind,
+                    lang = t.lang,
+                    version = t.version
+                ),
+            ));
+        }
+
+        included_subtitle_tracks =
+            render_export_pack_subtitles(&tracks, render_dir, "subtitles", &mut files)?;
+    }
+
+    let integrity_path = crate::tools::pack_integrity_manifest_status(paths).manifest_path;
+    let integrity_path = PathBuf::from(integrity_path);
+    if integrity_path.exists() {
+        files.push((
+            integrity_path,
+            "integrity/pack_integrity_manifest.json".to_string(),
+        ));
+    }
+
+    // Best-effort include QC reports and timing-fit artifacts.
+    let qc_dir = item_dir.join("qc");
+    if qc_dir.exists() {
+        if let Ok(entries) = std::fs::read_dir(&qc_dir) {
+            for entry in entries.flatten() {
+                let path = entry.path();
+                if !path.is_file() {
+                    continue;
+                }
+                let name = path
+                    .file_name()
+                    .and_then(|n| n.to_str())
+                    .unwrap_or("")
+                    .to_string();
+                if name.to_lowercase().ends_with(".json") {
+                    files.push((path, format!("qc/{name}")));
+                }
+            }
+        }
+    }
+    let timing_fit_report = job_artifacts_dir_for_id(paths, job_id)?.join("timing_fit_report.json");
+    if timing_fit_report.exists() {
+        files.push((
+            timing_fit_report,
+            "dub_preview/timing_fit_report.json".to_string(),
+        ));
+    }
+
+    // Collect relevant job rows for provenance (best-effort).
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut jobs_json: Vec<serde_json::Value> = Vec::new();
+    let mut stmt = conn.prepare(
+        r#"
+SELECT id, type, status, progress, error, created_at_ms, started_at_ms, finished_at_ms, params_json
+FROM job
+WHERE item_id=?1
+ORDER BY created_at_ms ASC
+"#,
+    )?;
+    let mut rows = stmt.query(params![&item.id])?;
+    while let Some(row) = rows.next()? {
+        let id: String = row.get(0)?;
+        let ty: String = row.get(1)?;
+        let status: String = row.get(2)?;
+        let progress: f32 = row.get(3)?;
+        let error: Option<String> = row.get(4)?;
+        let created_at_ms: i64 = row.get(5)?;
+        let started_at_ms: Option<i64> = row.get(6)?;
+        let finished_at_ms: Option<i64> = row.get(7)?;
+        let params_json_str: String = row.get(8)?;
+        jobs_json.push(serde_json::json!({
+            "id": id,
+            "type": ty,
+            "status": status,
+            "progress": progress,
+            "error": error,
+            "created_at_ms": created_at_ms,
+            "started_at_ms": started_at_ms,
+            "finished_at_ms": finished_at_ms,
+            "params_json": params_json_str,
+        }));
+    }
+
+    Ok(ExportPackContents {
+        files,
+        subtitle_tracks: included_subtitle_tracks,
+        jobs: jobs_json,
+    })
+}
+
+/// Writes one item's files, provenance, and export manifest into `zip`, with every path
+/// placed under `prefix` (empty for single-item packs, `{item_id}/` for multi-item packs).
+fn write_export_pack_item<W: Write + std::io::Seek>(
+    zip: &mut zip::ZipWriter<W>,
+    options: zip::write::FileOptions,
+    item: &library::LibraryItem,
+    contents: &ExportPackContents,
+    prefix: &str,
+) -> Result<()> {
+    let start_file = |zip: &mut zip::ZipWriter<W>, zip_path: &str| {
+        zip.start_file(zip_path, options).map_err(|e| {
+            EngineError::InstallFailed(format!("zip start file failed ({zip_path}): {e}"))
+        })
+    };
+
+    let mut included: Vec<ExportEntry> = Vec::new();
+    for (src, zip_path) in &contents.files {
+        if !src.exists() {
+            continue;
+        }
+        let bytes = std::fs::metadata(src).map(|m| m.len()).unwrap_or(0);
+        let zip_path = format!("{prefix}{}", zip_path.replace('\\', "/"));
+        start_file(zip, &zip_path)?;
+        let mut f = std::fs::File::open(src)?;
+        std::io::copy(&mut f, zip)?;
+        included.push(ExportEntry {
+            zip_path,
+            src_path: src.to_string_lossy().to_string(),
+            bytes,
+        });
+    }
+
+    let provenance = ExportProvenance {
+        schema_version: 1,
+        generated_at_ms: now_ms(),
+        engine_version: crate::diagnostics::engine_version().to_string(),
+        item_id: item.id.clone(),
+        item_title: item.title.clone(),
+        source_type: item.source_type.clone(),
+        source_uri: item.source_uri.clone(),
+        media_path: item.media_path.clone(),
+        included,
+        jobs: contents.jobs.clone(),
+    };
+    start_file(zip, &format!("{prefix}provenance/manifest.json"))?;
+    zip.write_all(serde_json::to_string_pretty(&provenance)?.as_bytes())?;
+    zip.write_all(b"\n")?;
+
+    let export_manifest = serde_json::json!({
+        "schema_version": 1,
+        "generated_at_ms": now_ms(),
+        "engine_version": crate::diagnostics::engine_version(),
+        "item": {
+            "id": &item.id,
+            "title": &item.title,
+            "source_type": &item.source_type,
+            "source_uri": &item.source_uri,
+        },
+        "subtitle_tracks": &contents.subtitle_tracks,
+    });
+    start_file(zip, &format!("{prefix}export_manifest.json"))?;
+    zip.write_all(serde_json::to_string_pretty(&export_manifest)?.as_bytes())?;
+    zip.write_all(b"\n")?;
+    Ok(())
+}
+
+/// Renders SRT and VTT files for every track into `render_dir`, queues them in `files` under
+/// `zip_root`, and returns a summary of the included tracks.
+fn render_export_pack_subtitles(
+    tracks: &[subtitle_tracks::SubtitleTrackRow],
+    render_dir: &Path,
+    zip_root: &str,
+    files: &mut Vec<(PathBuf, String)>,
+) -> Result<Vec<ExportPackSubtitleTrack>> {
+    let mut summary: Vec<ExportPackSubtitleTrack> = Vec::new();
+    for track in tracks {
+        let doc = match subtitle_tracks::load_document_from_path(Path::new(&track.path)) {
+            Ok(doc) => doc,
+            Err(_) => continue,
+        };
+        let stem = format!(
+            "{}_{}_{}_v{}",
+            sanitize_filename_component(&track.id),
+            sanitize_filename_component(&track.kind),
+            sanitize_filename_component(&track.lang),
+            track.version
+        );
+        let srt_path = render_dir.join(format!("{stem}.srt"));
+        let vtt_path = render_dir.join(format!("{stem}.vtt"));
+        subtitle_tracks::export_document_srt(&doc, &srt_path)?;
+        subtitle_tracks::export_document_vtt(&doc, &vtt_path)?;
+
+        let srt_zip_path = format!("{zip_root}/{stem}.srt");
+        let vtt_zip_path = format!("{zip_root}/{stem}.vtt");
+        files.push((srt_path, srt_zip_path.clone()));
+        files.push((vtt_path, vtt_zip_path.clone()));
+        summary.push(ExportPackSubtitleTrack {
+            track_id: track.id.clone(),
+            kind: track.kind.clone(),
+            lang: track.lang.clone(),
+            version: track.version,
+            srt_zip_path,
+            vtt_zip_path,
+        });
+    }
+    Ok(summary)
+}
+
 fn sanitize_filename_component(input: &str) -> String {
     let mut out = String::with_capacity(input.len());
     for ch in input.chars() {
@@ -13419,11 +19804,74 @@ fn sanitize_filename_component(input: &str) -> String {
     limited
 }
 
-fn atempo_chain_for_factor(factor: f32) -> String {
-    let mut remaining = factor.max(0.0001) as f64;
-    let mut parts: Vec<f64> = Vec::new();
+const ATEMPO_MIN_FACTOR: f32 = 0.1;
+const ATEMPO_MAX_FACTOR: f32 = 8.0;
+
+/// True when `path` starts with a RIFF/WAVE header whose chunk size matches the file length, so
+/// a WAV cut short by a crash is rejected.
+fn is_riff_wav_file(path: &Path) -> bool {
+    let mut head = [0_u8; 12];
+    let Ok(mut file) = std::fs::File::open(path) else {
+        return false;
+    };
+    if std::io::Read::read_exact(&mut file, &mut head).is_err()
+        || !head.starts_with(b"RIFF")
+        || &head[8..12] != b"WAVE"
+    {
+        return false;
+    }
+    let riff_size = u32::from_le_bytes([head[4], head[5], head[6], head[7]]) as u64;
+    file.metadata()
+        .is_ok_and(|meta| meta.len() == riff_size + 8)
+}
+
+/// Finds the highest `mix_step_NNNN.wav` of an interrupted legacy mix that can be resumed. Step
+/// `n` covers `background` plus the first `n` of `inputs`; it must be a valid WAV and no older
+/// than any of those sources, otherwise a lower step is tried.
+fn resumable_mix_step(
+    artifacts_dir: &Path,
+    background: &Path,
+    inputs: &[&Path],
+) -> Option<(usize, PathBuf)> {
+    let modified = |p: &Path| std::fs::metadata(p).and_then(|m| m.modified()).ok();
+    let mut steps: Vec<(usize, PathBuf)> = std::fs::read_dir(artifacts_dir)
+        .ok()?
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter_map(|path| {
+            let name = path.file_name()?.to_str()?;
+            let step = name
+                .strip_prefix("mix_step_")?
+                .strip_suffix(".wav")?
+                .parse::<usize>()
+                .ok()?;
+            (1..=inputs.len()).contains(&step).then_some((step, path))
+        })
+        .collect();
+    steps.sort_by_key(|(step, _)| std::cmp::Reverse(*step));
+    steps.into_iter().find(|(step, path)| {
+        let Some(step_mtime) = modified(path) else {
+            return false;
+        };
+        is_riff_wav_file(path)
+            && std::iter::once(background)
+                .chain(inputs[..*step].iter().copied())
+                .filter_map(modified)
+                .all(|source_mtime| source_mtime <= step_mtime)
+    })
+}
+
+/// Builds an `atempo` filter chain for `factor`. A single `atempo` only accepts [0.5, 2.0], so
+/// larger changes are split into at most five chained stages. A factor of 1.0 yields an empty
+/// chain.
+fn atempo_chain_for_factor(factor: f32) -> Result<String> {
+    if !factor.is_finite() || !(ATEMPO_MIN_FACTOR..=ATEMPO_MAX_FACTOR).contains(&factor) {
+        return Err(EngineError::InstallFailed(format!(
+            "atempo factor {factor} is outside {ATEMPO_MIN_FACTOR}..={ATEMPO_MAX_FACTOR}"
+        )));
+    }
 
-    // FFmpeg atempo supports [0.5, 2.0]. Chain filters if needed.
+    let mut remaining = factor as f64;
+    let mut parts: Vec<f64> = Vec::new();
     while remaining > 2.0 {
         parts.push(2.0);
         remaining /= 2.0;
@@ -13432,27 +19880,350 @@ fn atempo_chain_for_factor(factor: f32) -> String {
         parts.push(0.5);
         remaining /= 0.5;
     }
-    parts.push(remaining);
+    if (remaining - 1.0).abs() > 1e-6 {
+        parts.push(remaining);
+    }
+
+    Ok(parts
+        .into_iter()
+        .map(|v| {
+            let formatted = format!("{v:.6}");
+            let trimmed = formatted.trim_end_matches('0');
+            if trimmed.ends_with('.') {
+                format!("atempo={trimmed}0")
+            } else {
+                format!("atempo={trimmed}")
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(","))
+}
+
+/// Measures each mix input with loudnorm and returns the gain (dB) that levels it to
+/// [`MIX_SPEECH_NORMALIZE_TARGET_LUFS`]. Measurements are cached in `speech_levels.json` under
+/// `artifacts_dir`, keyed by [`speech_level_cache_key`], so a resumed job does not measure the
+/// same segments again while a re-rendered segment is measured afresh.
+fn measure_speech_gains_db(
+    paths: &AppPaths,
+    job_id: &str,
+    artifacts_dir: &Path,
+    inputs: &[(TtsPreviewManifestSegment, PathBuf)],
+) -> Result<HashMap<u32, f32>> {
+    let cache_path = artifacts_dir.join("speech_levels.json");
+    let mut levels: std::collections::BTreeMap<String, ffmpeg::LoudnormMeasurement> =
+        std::fs::read(&cache_path)
+            .ok()
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+
+    let keys = inputs
+        .iter()
+        .map(|(_, audio_path)| speech_level_cache_key(audio_path))
+        .collect::<Vec<_>>();
+    let stale_before = levels.len();
+    levels.retain(|key, _| keys.contains(key));
+    let mut changed = levels.len() != stale_before;
+    for ((_, audio_path), key) in inputs.iter().zip(&keys) {
+        if levels.contains_key(key) {
+            continue;
+        }
+        match ffmpeg::measure_loudnorm(
+            paths,
+            audio_path,
+            MIX_SPEECH_NORMALIZE_TARGET_LUFS,
+            MIX_SPEECH_NORMALIZE_TRUE_PEAK_DB,
+            MIX_SPEECH_NORMALIZE_LRA,
+        ) {
+            Ok(measurement) => {
+                levels.insert(key.clone(), measurement);
+                changed = true;
+            }
+            Err(e) => {
+                log_line(
+                    paths,
+                    job_id,
+                    "warn",
+                    "mix_dub_preview_speech_level_failed",
+                    serde_json::json!({ "path": audio_path, "error": e.to_string() }),
+                )?;
+            }
+        }
+    }
+    if changed {
+        std::fs::create_dir_all(artifacts_dir)?;
+        persistence::atomic_write_text(&cache_path, &serde_json::to_string_pretty(&levels)?)?;
+    }
+
+    let mut gains = HashMap::new();
+    for ((seg, _), key) in inputs.iter().zip(&keys) {
+        let gain = levels.get(key).and_then(|m| m.input_i).map(|input_i| {
+            (MIX_SPEECH_NORMALIZE_TARGET_LUFS - input_i).clamp(
+                -MIX_SPEECH_NORMALIZE_MAX_GAIN_DB,
+                MIX_SPEECH_NORMALIZE_MAX_GAIN_DB,
+            )
+        });
+        if let Some(gain) = gain.filter(|g| g.abs() >= 0.1) {
+            gains.insert(seg.index, gain);
+        }
+    }
+    Ok(gains)
+}
+
+/// Cache key for one speech segment's loudness measurement: the path plus its modification time
+/// and size, so a segment re-rendered in place does not reuse the old measurement.
+fn speech_level_cache_key(audio_path: &Path) -> String {
+    let (modified_ms, len) = std::fs::metadata(audio_path)
+        .map(|meta| {
+            let modified_ms = meta
+                .modified()
+                .ok()
+                .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                .map(|d| d.as_millis())
+                .unwrap_or(0);
+            (modified_ms, meta.len())
+        })
+        .unwrap_or((0, 0));
+    format!("{}|{modified_ms}|{len}", audio_path.to_string_lossy())
+}
+
+fn normalize_lang_tag(raw: Option<&str>) -> Option<&'static str> {
+    let v = raw?.trim().to_lowercase();
+    if v.is_empty() {
+        return None;
+    }
+    match v.as_str() {
+        "en" | "eng" | "english" => Some("eng"),
+        "ja" | "jpn" | "japanese" => Some("jpn"),
+        "ko" | "kor" | "korean" => Some("kor"),
+        "und" | "unknown" => Some("und"),
+        _ => None,
+    }
+}
+
+const QC_REPORT_V1_SCHEMA_VERSION: u32 = 3;
+const QC_TIMING_SHORT_RATIO: f64 = 0.8;
+const QC_TIMING_LONG_RATIO: f64 = 1.2;
+const QC_GAP_MIN_MS: i64 = 2_000;
+const QC_SCORE_WEIGHT_TIMING: f64 = 0.4;
+const QC_SCORE_WEIGHT_TEXT: f64 = 0.3;
+const QC_SCORE_WEIGHT_COMPRESSION: f64 = 0.3;
+
+fn qc_text_chars(text: &str) -> usize {
+    text.chars().filter(|c| !c.is_whitespace()).count()
+}
+
+fn qc_segment_report(
+    segment_index: u32,
+    start_ms: i64,
+    end_ms: i64,
+    text: &str,
+    source_text: Option<&str>,
+    tts_duration_ms: Option<i64>,
+) -> QcSegmentReport {
+    let window_ms = (end_ms - start_ms).max(0);
+    let word_count = text.split_whitespace().count();
+    let timing_ratio = tts_duration_ms
+        .filter(|_| window_ms > 0)
+        .map(|tts_ms| tts_ms as f64 / window_ms as f64);
+    let coverage = timing_ratio.map(|ratio| ratio.clamp(0.0, 1.0));
+    let timing_bucket = timing_ratio.map(|ratio| {
+        if ratio < QC_TIMING_SHORT_RATIO {
+            "short"
+        } else if ratio > QC_TIMING_LONG_RATIO {
+            "long"
+        } else {
+            "on_time"
+        }
+        .to_string()
+    });
+    let spoken_ms = tts_duration_ms.unwrap_or(window_ms);
+    let word_rate_wpm =
+        (spoken_ms > 0 && word_count > 0).then(|| word_count as f64 * 60_000.0 / spoken_ms as f64);
+
+    QcSegmentReport {
+        segment_index,
+        start_ms,
+        end_ms,
+        window_ms,
+        word_count,
+        text_chars: qc_text_chars(text),
+        source_text_chars: source_text.map(qc_text_chars),
+        tts_duration_ms,
+        coverage,
+        timing_ratio,
+        timing_bucket,
+        word_rate_wpm,
+    }
+}
+
+/// Nearest-rank percentile of an ascending slice.
+fn qc_percentile(sorted: &[f64], pct: f64) -> Option<f64> {
+    if sorted.is_empty() {
+        return None;
+    }
+    let rank = ((pct / 100.0) * sorted.len() as f64).ceil() as usize;
+    Some(sorted[rank.clamp(1, sorted.len()) - 1])
+}
+
+fn qc_summary(
+    total_segments: usize,
+    issues: &[QcIssueRecord],
+    segments: &[QcSegmentReport],
+) -> QcSummary {
+    let mut issues_by_kind: std::collections::BTreeMap<String, usize> =
+        std::collections::BTreeMap::new();
+    for issue in issues {
+        *issues_by_kind.entry(issue.kind.clone()).or_insert(0) += 1;
+    }
+
+    let count_bucket = |bucket: &str| {
+        segments
+            .iter()
+            .filter(|seg| seg.timing_bucket.as_deref() == Some(bucket))
+            .count()
+    };
+    let timed_segments = segments
+        .iter()
+        .filter(|seg| seg.timing_bucket.is_some())
+        .count();
+    let percent = |count: usize| {
+        if timed_segments == 0 {
+            0.0
+        } else {
+            count as f64 * 100.0 / timed_segments as f64
+        }
+    };
+    let on_time = count_bucket("on_time");
+
+    let mut ratios: Vec<f64> = segments.iter().filter_map(|seg| seg.timing_ratio).collect();
+    ratios.sort_by(f64::total_cmp);
+
+    let total_words: usize = segments.iter().map(|seg| seg.word_count).sum();
+    let spoken_ms: i64 = segments
+        .iter()
+        .map(|seg| seg.tts_duration_ms.unwrap_or(seg.window_ms))
+        .sum();
+    let words_per_minute = (spoken_ms > 0 && total_words > 0)
+        .then(|| total_words as f64 * 60_000.0 / spoken_ms as f64);
+
+    let gap_count = segments
+        .windows(2)
+        .filter(|pair| pair[1].start_ms - pair[0].end_ms >= QC_GAP_MIN_MS)
+        .count();
+
+    let timed_window_ms: i64 = segments
+        .iter()
+        .filter(|seg| seg.tts_duration_ms.is_some())
+        .map(|seg| seg.window_ms)
+        .sum();
+    let covered_ms: i64 = segments
+        .iter()
+        .filter_map(|seg| {
+            seg.tts_duration_ms
+                .map(|tts_ms| tts_ms.clamp(0, seg.window_ms))
+        })
+        .sum();
+    let timing_coverage = (timed_window_ms > 0).then(|| covered_ms as f64 / timed_window_ms as f64);
+
+    let text_coverage = (!segments.is_empty()).then(|| {
+        segments.iter().filter(|seg| seg.text_chars > 0).count() as f64 / segments.len() as f64
+    });
+
+    let (translated_chars, source_chars) = segments
+        .iter()
+        .filter_map(|seg| match seg.source_text_chars {
+            Some(source) if source > 0 => Some((seg.text_chars, source)),
+            _ => None,
+        })
+        .fold((0usize, 0usize), |(t, s), (seg_t, seg_s)| {
+            (t + seg_t, s + seg_s)
+        });
+    let compression_ratio =
+        (source_chars > 0).then(|| translated_chars as f64 / source_chars as f64);
+
+    QcSummary {
+        total_segments,
+        issues_total: issues.len(),
+        issues_by_kind,
+        timed_segments,
+        percent_on_time: percent(on_time),
+        percent_short: percent(count_bucket("short")),
+        percent_long: percent(count_bucket("long")),
+        timing_accuracy_score: (timed_segments > 0).then(|| on_time as f64 / timed_segments as f64),
+        timing_ratio_p50: qc_percentile(&ratios, 50.0),
+        timing_ratio_p90: qc_percentile(&ratios, 90.0),
+        words_per_minute,
+        gap_count,
+        timing_coverage,
+        text_coverage,
+        compression_ratio,
+    }
+}
+
+/// Weighted average of the summary components that could be measured; a report without TTS
+/// audio or a source track is scored on the remaining components only.
+fn qc_overall_score(summary: &QcSummary) -> f32 {
+    let components = [
+        (summary.timing_coverage, QC_SCORE_WEIGHT_TIMING),
+        (summary.text_coverage, QC_SCORE_WEIGHT_TEXT),
+        (
+            summary
+                .compression_ratio
+                .map(|ratio| (1.0 - (ratio - 1.0).abs()).clamp(0.0, 1.0)),
+            QC_SCORE_WEIGHT_COMPRESSION,
+        ),
+    ];
+    let (weighted, weights) = components
+        .iter()
+        .filter_map(|(value, weight)| value.map(|v| (v.clamp(0.0, 1.0) * weight, *weight)))
+        .fold((0.0, 0.0), |(acc, total), (v, w)| (acc + v, total + w));
+    if weights > 0.0 {
+        (weighted / weights) as f32
+    } else {
+        0.0
+    }
+}
 
-    parts
+/// Source document a QC'd track is compared against: the nearest `source` ancestor, else the
+/// item's latest source track. `None` when the track itself is a source track.
+fn qc_source_document(
+    paths: &AppPaths,
+    track: &subtitle_tracks::SubtitleTrackRow,
+) -> Result<Option<(String, subtitles::SubtitleDocument)>> {
+    if track.kind == "source" {
+        return Ok(None);
+    }
+    let source_track = match subtitle_tracks::get_lineage(paths, &track.id)?
         .into_iter()
-        .map(|v| format!("atempo={:.6}", v))
-        .collect::<Vec<_>>()
-        .join(",")
+        .find(|ancestor| ancestor.kind == "source")
+    {
+        Some(ancestor) => Some(ancestor),
+        None => latest_source_track(paths, &track.item_id)?,
+    };
+    let Some(source_track) = source_track else {
+        return Ok(None);
+    };
+    let doc = subtitle_tracks::load_document(paths, &source_track.id)?;
+    Ok(Some((source_track.id, doc)))
 }
 
-fn normalize_lang_tag(raw: Option<&str>) -> Option<&'static str> {
-    let v = raw?.trim().to_lowercase();
-    if v.is_empty() {
-        return None;
-    }
-    match v.as_str() {
-        "en" | "eng" | "english" => Some("eng"),
-        "ja" | "jpn" | "japanese" => Some("jpn"),
-        "ko" | "kor" | "korean" => Some("kor"),
-        "und" | "unknown" => Some("und"),
-        _ => None,
+/// Loads a previously generated QC report, or `None` when the job has not produced one yet.
+pub fn load_qc_report_v1(
+    paths: &AppPaths,
+    item_id: &str,
+    track_id: &str,
+    variant_label: Option<&str>,
+) -> Result<Option<QcReportV1Result>> {
+    let file_name = match normalize_variant_label(variant_label) {
+        Some(label) => format!("qc_report_v1_{track_id}_{label}.json"),
+        None => format!("qc_report_v1_{track_id}.json"),
+    };
+    let path = paths.derived_item_dir(item_id).join("qc").join(file_name);
+    if !path.exists() {
+        return Ok(None);
     }
+    let bytes = std::fs::read(&path)?;
+    Ok(Some(serde_json::from_slice(&bytes)?))
 }
 
 fn normalize_variant_label(raw: Option<&str>) -> Option<String> {
@@ -13515,10 +20286,90 @@ fn dub_variant_dir(item_dir: &Path, variant_label: Option<&str>) -> PathBuf {
     dir
 }
 
+/// Resolves a user-chosen TTS manifest, absolute or relative to the item's derived dir, and
+/// rejects files outside that dir.
+fn resolve_item_tts_manifest_path(paths: &AppPaths, item_id: &str, raw: &str) -> Result<PathBuf> {
+    let item_dir = paths.derived_item_dir(item_id);
+    let raw_path = Path::new(raw.trim());
+    let relative = if raw_path.is_absolute() {
+        raw_path.strip_prefix(&item_dir).map_err(|_| {
+            EngineError::InstallFailed(format!(
+                "TTS manifest must be inside the item's derived folder: {}",
+                raw_path.display()
+            ))
+        })?
+    } else {
+        raw_path
+    };
+    let traversal = relative.components().any(|c| {
+        matches!(
+            c,
+            std::path::Component::ParentDir
+                | std::path::Component::RootDir
+                | std::path::Component::Prefix(_)
+        )
+    });
+    if traversal || relative.as_os_str().is_empty() {
+        return Err(EngineError::InstallFailed(format!(
+            "TTS manifest path is not a safe relative path: {}",
+            relative.display()
+        )));
+    }
+    let resolved = item_dir.join(relative);
+    if !resolved.is_file() {
+        return Err(EngineError::InstallFailed(format!(
+            "TTS manifest not found: {}",
+            resolved.display()
+        )));
+    }
+    Ok(resolved)
+}
+
+/// Adds fallback-manifest audio for segments the preferred manifest left unsynthesized.
+fn fill_missing_tts_inputs_from_fallback(
+    inputs: &mut Vec<(TtsPreviewManifestSegment, PathBuf)>,
+    fallback: &TtsPreviewManifest,
+) -> usize {
+    let present: HashSet<u32> = inputs.iter().map(|(seg, _)| seg.index).collect();
+    let mut filled = 0usize;
+    for seg in &fallback.segments {
+        if present.contains(&seg.index) || !seg.audio_exists {
+            continue;
+        }
+        let Some(audio_path) = seg
+            .audio_path
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(PathBuf::from)
+        else {
+            continue;
+        };
+        if !audio_path.exists() {
+            continue;
+        }
+        inputs.push((seg.clone(), audio_path));
+        filled += 1;
+    }
+    if filled > 0 {
+        inputs.sort_by_key(|(seg, _)| seg.index);
+    }
+    filled
+}
+
 fn tts_manifest_path(item_dir: &Path, backend_dir: &str, variant_label: Option<&str>) -> PathBuf {
     tts_variant_dir(item_dir, backend_dir, variant_label).join("manifest.json")
 }
 
+/// Where neural TTS writes the pyttsx3 stand-ins for segments it failed to render: next to the
+/// neural manifest, so each variant keeps its own and the standalone pyttsx3 preview is untouched.
+fn pyttsx3_fallback_dir(neural_manifest_path: &Path) -> PathBuf {
+    neural_manifest_path
+        .parent()
+        .unwrap_or(Path::new("."))
+        .join("pyttsx3_fallback")
+}
+
 #[derive(Debug, Clone)]
 struct TtsManifestCandidateRef {
     backend_id: String,
@@ -13759,7 +20610,6 @@ fn queue_experimental_pipeline_followups(
         return Ok(());
     }
 
-    let batch_id = job_batch_id(paths, job_id).ok().flatten();
     let has_mix_source = library::get_item_by_id(paths, item_id)
         .ok()
         .and_then(|item| mix_background_audio_source(paths, &item))
@@ -13774,6 +20624,7 @@ fn queue_experimental_pipeline_followups(
                 timing_fit_enabled: None,
                 timing_fit_min_factor: None,
                 timing_fit_max_factor: None,
+                normalize_speech: None,
                 batch_on_import: false,
                 pipeline: Some(LocalizationPipelineOptions {
                     source_track_id: Some(source_track_id.to_string()),
@@ -13781,13 +20632,12 @@ fn queue_experimental_pipeline_followups(
                     tts_backend_id: pipeline.tts_backend_id.clone(),
                     ..pipeline.clone()
                 }),
+                tts_manifest_path: None,
             })?;
-            let _ = enqueue_with_type_item_and_batch_id(
+            chain_on_complete(
                 paths,
-                JobType::MixDubPreviewV1,
-                params_json,
-                Some(item_id.to_string()),
-                batch_id,
+                job_id,
+                JobDependency::on_success(JobType::MixDubPreviewV1, params_json),
             )?;
         }
     } else {
@@ -15092,10 +21942,43 @@ fn now_ms() -> i64 {
 #[cfg(test)]
 mod tests {
     use super::*;
+
     use crate::subtitles::{SubtitleDocument, SubtitleSegment, SUBTITLE_JSON_SCHEMA_VERSION};
     use rusqlite::params;
     use std::path::Path;
 
+    /// Enqueues a dummy job without the one-active-job guard, for tests needing several.
+    fn queue_dummy_sleep(paths: &AppPaths, seconds: u64) -> Result<JobRow> {
+        let params_json = serde_json::to_string(&DummySleepParams { seconds })?;
+        enqueue(paths, JobType::DummySleep, params_json)
+    }
+
+    #[test]
+    fn enqueue_install_and_dummy_jobs_reuse_active_job_of_same_type() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+
+        let install = enqueue_install_phase2_packs_v1(&paths).expect("install");
+        let again = enqueue_install_phase2_packs_v1(&paths).expect("install again");
+        assert_eq!(install.id, again.id);
+        assert!(
+            has_active_job_of_type(&paths, JobType::InstallPhase2PacksV1.as_str())
+                .expect("active install")
+        );
+
+        let dummy = enqueue_dummy_sleep(&paths, 1).expect("dummy");
+        assert_eq!(
+            enqueue_dummy_sleep(&paths, 5).expect("dummy again").id,
+            dummy.id
+        );
+        cancel_job(&paths, &dummy.id).expect("cancel");
+        assert_ne!(
+            enqueue_dummy_sleep(&paths, 1).expect("fresh dummy").id,
+            dummy.id
+        );
+    }
+
     fn seed_item_and_track(paths: &AppPaths) {
         seed_item_and_track_named(paths, "item-1", "track-1", "Item 1");
     }
@@ -15140,7 +22023,9 @@ mod tests {
                 end_ms: 1200,
                 text: "Hello world".to_string(),
                 speaker: speakers.first().map(|value| value.to_string()),
+                confidence: None,
             }],
+            metadata: Default::default(),
         };
         let track_path = paths
             .derived_item_dir(item_id)
@@ -15189,6 +22074,7 @@ mod tests {
             kind: kind.to_string(),
             lang: lang.to_string(),
             segments: Vec::new(),
+            metadata: Default::default(),
         };
         let track_path = paths
             .derived_item_dir(item_id)
@@ -15263,6 +22149,7 @@ mod tests {
                     end_ms: 500,
                     text: "   ".to_string(),
                     speaker: None,
+                    confidence: None,
                 },
                 SubtitleSegment {
                     index: 1,
@@ -15270,8 +22157,10 @@ mod tests {
                     end_ms: 1000,
                     text: "hello".to_string(),
                     speaker: None,
+                    confidence: None,
                 },
             ],
+            metadata: Default::default(),
         };
 
         let stats = subtitle_document_segment_stats(&doc);
@@ -15284,6 +22173,470 @@ mod tests {
         );
     }
 
+    #[test]
+    fn export_multi_pack_v1_prefixes_each_item_and_writes_index() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().join("app"));
+        seed_item_only(&paths, "item-1", "Item 1");
+        seed_item_only(&paths, "item-2", "Item 2");
+        seed_subtitle_track_named(&paths, "item-2", "track-en", "translated", "en", 1, &["S1"]);
+
+        let out_path = dir.path().join("out").join("multi.zip");
+        let out_path_str = out_path.to_string_lossy().to_string();
+        assert!(enqueue_export_multi_pack_v1(
+            &paths,
+            vec!["item-1".to_string(), "missing".to_string()],
+            out_path_str.clone(),
+        )
+        .is_err());
+        assert!(enqueue_export_multi_pack_v1(
+            &paths,
+            (0..=EXPORT_MULTI_PACK_MAX_ITEMS)
+                .map(|i| format!("item-{i}"))
+                .collect(),
+            out_path_str.clone(),
+        )
+        .is_err());
+
+        let job = enqueue_export_multi_pack_v1(
+            &paths,
+            vec![
+                "item-1".to_string(),
+                "item-2".to_string(),
+                "item-1".to_string(),
+            ],
+            out_path_str,
+        )
+        .expect("enqueue");
+        execute_job(&paths, &job.id, &job.job_type, &job.params_json).expect("execute");
+
+        let mut archive =
+            zip::ZipArchive::new(std::fs::File::open(&out_path).expect("zip")).expect("archive");
+        let names = archive.file_names().map(String::from).collect::<Vec<_>>();
+        assert!(names.contains(&"item-1/export_manifest.json".to_string()));
+        assert!(names.contains(&"item-2/provenance/manifest.json".to_string()));
+        assert!(names.contains(&"item-2/subtitles/track-en_translated_en_v1.srt".to_string()));
+
+        let mut index_json = String::new();
+        archive
+            .by_name("index.json")
+            .expect("index")
+            .read_to_string(&mut index_json)
+            .expect("read index");
+        let index: serde_json::Value = serde_json::from_str(&index_json).expect("index json");
+        let items = index["items"].as_array().expect("items");
+        assert_eq!(items.len(), 2);
+        assert_eq!(items[1]["item_id"], "item-2");
+        assert_eq!(items[1]["title"], "Item 2");
+    }
+
+    #[test]
+    fn render_export_pack_subtitles_writes_srt_and_vtt_per_track() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        seed_item_only(&paths, "item-1", "Item 1");
+        seed_subtitle_track_named(&paths, "item-1", "track-ja", "source", "ja", 1, &["S1"]);
+        seed_subtitle_track_named(&paths, "item-1", "track-en", "translated", "en", 2, &["S1"]);
+
+        let tracks = subtitle_tracks::list_tracks(&paths, "item-1").expect("tracks");
+        let render_dir = dir.path().join("render");
+        let mut files = Vec::new();
+        let summary = render_export_pack_subtitles(&tracks, &render_dir, "subtitles", &mut files)
+            .expect("render");
+
+        assert_eq!(summary.len(), 2);
+        assert_eq!(files.len(), 4);
+        let en = summary
+            .iter()
+            .find(|track| track.track_id == "track-en")
+            .expect("en track");
+        assert_eq!(en.srt_zip_path, "subtitles/track-en_translated_en_v2.srt");
+        assert_eq!(en.vtt_zip_path, "subtitles/track-en_translated_en_v2.vtt");
+        let vtt =
+            std::fs::read_to_string(render_dir.join("track-en_translated_en_v2.vtt")).expect("vtt");
+        assert!(vtt.starts_with("WEBVTT"));
+        assert!(vtt.contains("Hello world"));
+    }
+
+    #[test]
+    fn enqueue_extract_speaker_voice_profile_v1_validates_clip_duration() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        seed_item_only(&paths, "item-1", "Item 1");
+
+        let err = enqueue_extract_speaker_voice_profile_v1(
+            &paths,
+            "item-1".to_string(),
+            "S1".to_string(),
+            1_000,
+            2_000,
+        )
+        .expect_err("too short");
+        assert!(err.to_string().contains("between 3000 and 30000 ms"));
+        assert!(enqueue_extract_speaker_voice_profile_v1(
+            &paths,
+            "item-1".to_string(),
+            "S1".to_string(),
+            0,
+            30_001,
+        )
+        .is_err());
+
+        let job = enqueue_extract_speaker_voice_profile_v1(
+            &paths,
+            "item-1".to_string(),
+            " S1 ".to_string(),
+            1_000,
+            6_000,
+        )
+        .expect("enqueue");
+        assert_eq!(job.job_type, "extract_speaker_voice_profile_v1");
+        let params: ExtractSpeakerVoiceProfileV1Params =
+            serde_json::from_str(&job.params_json).expect("params");
+        assert_eq!(params.speaker_key, "S1");
+    }
+
+    #[test]
+    fn apply_aligned_fragments_keeps_source_segment_count() {
+        let seg = |index: u32, start_ms: i64, text: &str| subtitles::SubtitleSegment {
+            index,
+            start_ms,
+            end_ms: start_ms + 500,
+            text: text.to_string(),
+            speaker: None,
+            confidence: None,
+        };
+        let source = subtitles::SubtitleDocument {
+            schema_version: subtitles::SUBTITLE_JSON_SCHEMA_VERSION,
+            kind: "source".to_string(),
+            lang: "ja".to_string(),
+            segments: vec![seg(0, 0, "a"), seg(1, 1000, " "), seg(2, 2000, "b")],
+            metadata: Default::default(),
+        };
+        let fragments = vec![
+            AlignedFragment {
+                begin_ms: 100,
+                end_ms: 900,
+            },
+            AlignedFragment {
+                begin_ms: 2500,
+                end_ms: 2400,
+            },
+        ];
+
+        let aligned = apply_aligned_fragments(&source, &fragments).expect("aligned");
+        assert_eq!(aligned.segments.len(), source.segments.len());
+        assert_eq!(
+            (aligned.segments[0].start_ms, aligned.segments[0].end_ms),
+            (100, 900)
+        );
+        assert_eq!(aligned.segments[1].start_ms, 1000);
+        assert_eq!(
+            (aligned.segments[2].start_ms, aligned.segments[2].end_ms),
+            (2500, 2500)
+        );
+        assert!(apply_aligned_fragments(&source, &fragments[..1]).is_err());
+        assert_eq!(aeneas_language_code("ja-JP"), "jpn");
+    }
+
+    #[test]
+    fn clean_vocals_source_selects_requested_separation_stem() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let separation = paths.derived_item_dir("item-1").join("separation");
+        let spleeter = separation.join("spleeter_2stems");
+        std::fs::create_dir_all(&spleeter).expect("mkdir");
+        std::fs::write(spleeter.join("vocals.wav"), b"RIFF").expect("write");
+
+        assert!(clean_vocals_source_path(&paths, "item-1", Some("demucs")).is_none());
+        assert_eq!(
+            clean_vocals_source_path(&paths, "item-1", Some("spleeter")),
+            Some(spleeter.join("vocals.wav"))
+        );
+        assert_eq!(
+            normalize_clean_vocals_source(Some(" Demucs ")).expect("demucs"),
+            Some("demucs".to_string())
+        );
+        assert!(normalize_clean_vocals_source(Some("rnnoise")).is_err());
+
+        let cleaned = paths.derived_item_dir("item-1").join("cleanup");
+        std::fs::create_dir_all(&cleaned).expect("mkdir");
+        std::fs::write(cleaned.join("vocals_clean_v1.wav"), b"RIFF").expect("write");
+        assert_eq!(
+            preferred_vocals_path_best_effort(&paths, "item-1"),
+            Some(cleaned.join("vocals_clean_v1.wav"))
+        );
+    }
+
+    #[test]
+    fn separation_paths_fall_back_to_demucs_4stems_outputs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        assert!(separation_background_path_best_effort(&paths, "item-1").is_none());
+
+        let four_stems = paths
+            .derived_item_dir("item-1")
+            .join("separation")
+            .join("demucs_4stems_v1");
+        std::fs::create_dir_all(&four_stems).expect("mkdir");
+        for stem in DEMUCS_4STEMS {
+            std::fs::write(four_stems.join(format!("{stem}.wav")), b"RIFF").expect("write");
+        }
+        assert_eq!(
+            separation_background_path_best_effort(&paths, "item-1"),
+            Some(four_stems.join("other.wav"))
+        );
+        assert_eq!(
+            separation_vocals_path_best_effort(&paths, "item-1"),
+            Some(four_stems.join("vocals.wav"))
+        );
+
+        let two_stems = paths
+            .derived_item_dir("item-1")
+            .join("separation")
+            .join("demucs_two_stems_v1");
+        std::fs::create_dir_all(&two_stems).expect("mkdir");
+        std::fs::write(two_stems.join("background.wav"), b"RIFF").expect("write");
+        assert_eq!(
+            separation_background_path_best_effort(&paths, "item-1"),
+            Some(two_stems.join("background.wav"))
+        );
+    }
+
+    #[test]
+    fn watch_directory_imports_stable_new_or_changed_files_once() {
+        use filetime::{set_file_mtime, FileTime};
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let existing = dir.path().join("old.mp4");
+        std::fs::write(&existing, b"old").expect("write");
+        std::fs::write(dir.path().join("notes.txt"), b"skip").expect("write");
+        let exts = vec!["mp4".to_string()];
+        let mut known = std::collections::BTreeMap::new();
+
+        let scan = |known: &mut std::collections::BTreeMap<String, WatchedFileState>| {
+            scan_watch_directory(dir.path(), &exts, known, false).expect("scan")
+        };
+        assert!(scan_watch_directory(dir.path(), &exts, &mut known, true)
+            .expect("baseline")
+            .is_empty());
+        assert_eq!(known.len(), 1);
+
+        let recorded = dir.path().join("new.mp4");
+        std::fs::write(&recorded, b"partial").expect("write");
+        assert!(
+            scan(&mut known).is_empty(),
+            "waits one scan for a stable size"
+        );
+        assert_eq!(scan(&mut known), vec![recorded.clone()]);
+        assert!(scan(&mut known).is_empty());
+
+        set_file_mtime(&recorded, FileTime::from_unix_time(1_000_000, 0)).expect("mtime");
+        assert!(scan(&mut known).is_empty());
+        assert!(scan(&mut known).is_empty(), "same hash is not re-imported");
+
+        std::fs::write(&existing, b"edited").expect("write");
+        assert!(scan(&mut known).is_empty());
+        assert_eq!(scan(&mut known), vec![existing.clone()]);
+
+        std::fs::remove_file(&existing).expect("remove");
+        scan(&mut known);
+        assert_eq!(known.len(), 1);
+    }
+
+    #[test]
+    fn enqueue_watch_directory_reuses_active_job_for_same_dir() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().join("app"));
+        let watched = dir.path().join("recordings");
+        std::fs::create_dir_all(&watched).expect("mkdir");
+        let watched = watched.to_string_lossy().to_string();
+
+        let job = enqueue_watch_directory(&paths, watched.clone(), vec![".MP4".to_string()], 1)
+            .expect("enqueue");
+        assert_eq!(job.job_type, "watch_directory");
+        let again = enqueue_watch_directory(&paths, watched, vec![], 60).expect("again");
+        assert_eq!(again.id, job.id);
+        assert!(enqueue_watch_directory(
+            &paths,
+            dir.path().to_string_lossy().to_string(),
+            vec!["exe".to_string()],
+            60,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn enqueue_tts_neural_local_v1_validates_lang_code() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        seed_item_and_track(&paths);
+
+        assert!(enqueue_tts_neural_local_v1(
+            &paths,
+            "item-1".to_string(),
+            "track-1".to_string(),
+            Some("xx".to_string()),
+            None,
+        )
+        .is_err());
+        let job = enqueue_tts_neural_local_v1(
+            &paths,
+            "item-1".to_string(),
+            "track-1".to_string(),
+            Some(" B ".to_string()),
+            None,
+        )
+        .expect("enqueue");
+        let conn = db::open(&paths).expect("open db");
+        let params_json: String = conn
+            .query_row(
+                "SELECT params_json FROM job WHERE id=?1",
+                params![job.id],
+                |row| row.get(0),
+            )
+            .expect("params");
+        let params: TtsNeuralLocalV1Params = serde_json::from_str(&params_json).expect("params");
+        assert_eq!(params.lang_code.as_deref(), Some("b"));
+        assert!(params.fallback_to_pyttsx3);
+    }
+
+    #[test]
+    fn resolve_item_tts_manifest_path_stays_inside_item_dir() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let manifest = tts_manifest_path(&paths.derived_item_dir("item-1"), "pyttsx3_v1", None);
+        std::fs::create_dir_all(manifest.parent().expect("parent")).expect("mkdir");
+        std::fs::write(&manifest, "{\"segments\":[]}").expect("write");
+
+        let relative = resolve_item_tts_manifest_path(
+            &paths,
+            "item-1",
+            "tts_preview/pyttsx3_v1/manifest.json",
+        )
+        .expect("relative");
+        assert_eq!(relative, manifest);
+        let absolute =
+            resolve_item_tts_manifest_path(&paths, "item-1", &manifest.to_string_lossy())
+                .expect("absolute");
+        assert_eq!(absolute, manifest);
+
+        let other = paths.derived_item_dir("item-2").join("manifest.json");
+        assert!(
+            resolve_item_tts_manifest_path(&paths, "item-1", &other.to_string_lossy()).is_err()
+        );
+        assert!(resolve_item_tts_manifest_path(
+            &paths,
+            "item-1",
+            "../item-2/tts_preview/pyttsx3_v1/manifest.json"
+        )
+        .is_err());
+        assert!(
+            resolve_item_tts_manifest_path(&paths, "item-1", "tts_preview/missing.json").is_err()
+        );
+    }
+
+    #[test]
+    fn fill_missing_tts_inputs_from_fallback_adds_only_missing_segments() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let neural = dir.path().join("neural_0001.wav");
+        let fallback_1 = dir.path().join("fallback_0001.wav");
+        let fallback_2 = dir.path().join("fallback_0002.wav");
+        for path in [&neural, &fallback_1, &fallback_2] {
+            std::fs::write(path, b"RIFF").expect("write wav");
+        }
+        let seg = |index: u32, path: &Path| -> serde_json::Value {
+            serde_json::json!({
+                "index": index,
+                "start_ms": index as i64 * 1000,
+                "end_ms": index as i64 * 1000 + 900,
+                "audio_path": path.to_string_lossy(),
+                "audio_exists": true,
+            })
+        };
+        let neural_seg: TtsPreviewManifestSegment =
+            serde_json::from_value(seg(1, &neural)).expect("segment");
+        let mut inputs = vec![(neural_seg, neural.clone())];
+        let fallback: TtsPreviewManifest = serde_json::from_value(serde_json::json!({
+            "segments": [
+                seg(1, &fallback_1),
+                seg(2, &fallback_2),
+                seg(3, &dir.path().join("missing.wav")),
+            ],
+        }))
+        .expect("manifest");
+
+        assert_eq!(
+            fill_missing_tts_inputs_from_fallback(&mut inputs, &fallback),
+            1
+        );
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[0].1, neural);
+        assert_eq!(inputs[1].1, fallback_2);
+    }
+
+    #[test]
+    fn separation_paths_fall_back_to_spleeter_5stems_outputs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let five_stems = paths
+            .derived_item_dir("item-1")
+            .join("separation")
+            .join("spleeter_5stems");
+        std::fs::create_dir_all(&five_stems).expect("mkdir");
+        for stem in SPLEETER_5STEMS {
+            std::fs::write(five_stems.join(format!("{stem}.wav")), b"RIFF").expect("write");
+        }
+        assert_eq!(
+            separation_background_path_best_effort(&paths, "item-1"),
+            Some(five_stems.join("other.wav"))
+        );
+        assert_eq!(
+            separation_vocals_path_best_effort(&paths, "item-1"),
+            Some(five_stems.join("vocals.wav"))
+        );
+
+        let params: SeparateAudioSpleeterParams =
+            serde_json::from_str(r#"{"item_id":"item-1"}"#).expect("legacy params");
+        assert_eq!(params.stems, 2);
+    }
+
+    #[test]
+    fn separation_audio_override_requires_existing_absolute_audio_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().join("app"));
+        db::ensure_schema(&paths).expect("schema");
+        let audio = dir.path().join("cleaned.flac");
+        std::fs::write(&audio, b"fLaC").expect("write");
+        let video = dir.path().join("clip.mp4");
+        std::fs::write(&video, b"mp4").expect("write");
+
+        assert!(resolve_separation_audio_override(Some("  "))
+            .expect("blank")
+            .is_none());
+        assert!(resolve_separation_audio_override(Some("cleaned.flac")).is_err());
+        assert!(resolve_separation_audio_override(video.to_str()).is_err());
+        assert!(resolve_separation_audio_override(dir.path().join("gone.wav").to_str()).is_err());
+
+        let job = enqueue_separate_audio_demucs_v1_with_options(
+            &paths,
+            "item-1".to_string(),
+            None,
+            audio.to_str().map(str::to_string),
+        )
+        .expect("enqueue");
+        let params: SeparateAudioDemucsV1Params =
+            serde_json::from_str(&job.params_json).expect("params");
+        assert_eq!(params.audio_path_override.as_deref(), audio.to_str());
+        assert!(enqueue_separate_audio_spleeter_with_options(
+            &paths,
+            "item-1".to_string(),
+            2,
+            video.to_str().map(str::to_string),
+        )
+        .is_err());
+    }
+
     #[test]
     fn enqueue_localization_run_v1_queues_asr_when_no_tracks_exist() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -15317,6 +22670,49 @@ mod tests {
         assert!(pipeline.queue_export_pack);
     }
 
+    #[test]
+    fn enqueue_full_pipeline_v1_overrides_batch_rules_for_its_batch() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        seed_item_only(&paths, "item-1", "Item 1");
+
+        assert!(enqueue_full_pipeline_v1(
+            &paths,
+            "item-1".to_string(),
+            None,
+            None,
+            Some("espeak".to_string()),
+            None,
+        )
+        .is_err());
+
+        let result = enqueue_full_pipeline_v1(
+            &paths,
+            "item-1".to_string(),
+            Some("auto".to_string()),
+            Some("whispercpp-base".to_string()),
+            Some(config::BATCH_ON_IMPORT_TTS_BACKEND_PYTTSX3_V1.to_string()),
+            Some(0.3),
+        )
+        .expect("queue");
+        assert_eq!(result.jobs.len(), 1);
+        assert_eq!(result.jobs[0].job_type, "full_pipeline_v1");
+        let params: FullPipelineV1Params =
+            serde_json::from_str(&result.jobs[0].params_json).expect("params");
+        assert_eq!(params.asr_lang, None);
+
+        let rules = batch_on_import_rules_for_job(&paths, &result.jobs[0].id);
+        assert!(rules.auto_translate && rules.auto_dub_preview && rules.auto_mux);
+        assert_eq!(rules.asr_model_id.as_deref(), Some("whispercpp-base"));
+        assert_eq!(
+            batch_on_import_tts_job_type(&paths, &rules).as_str(),
+            "tts_preview_pyttsx3_v1"
+        );
+
+        let other = enqueue_mix_dub_preview_v1(&paths, "item-1".to_string()).expect("mix");
+        assert!(!batch_on_import_rules_for_job(&paths, &other.id).auto_mux);
+    }
+
     #[test]
     fn enqueue_localization_run_v1_blocks_empty_source_track() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -15482,6 +22878,93 @@ mod tests {
         );
     }
 
+    #[test]
+    fn qc_timing_buckets_and_summary_percentages() {
+        let segments = vec![
+            qc_segment_report(0, 0, 2_000, "one two three four", None, Some(1_000)),
+            qc_segment_report(1, 2_000, 4_000, "one two", None, Some(2_100)),
+            qc_segment_report(2, 4_000, 5_000, "one two", None, Some(1_500)),
+            qc_segment_report(3, 5_000, 6_000, "no audio yet", None, None),
+        ];
+        assert_eq!(segments[0].timing_bucket.as_deref(), Some("short"));
+        assert_eq!(segments[0].word_rate_wpm, Some(240.0));
+        assert_eq!(segments[1].timing_bucket.as_deref(), Some("on_time"));
+        assert_eq!(segments[2].timing_bucket.as_deref(), Some("long"));
+        assert!(segments[3].timing_bucket.is_none());
+        assert_eq!(segments[3].word_rate_wpm, Some(180.0));
+
+        let summary = qc_summary(4, &[], &segments);
+        assert_eq!(summary.timed_segments, 3);
+        assert!((summary.percent_on_time - 100.0 / 3.0).abs() < 1e-9);
+        assert!((summary.percent_short - 100.0 / 3.0).abs() < 1e-9);
+        assert_eq!(summary.timing_accuracy_score, Some(1.0 / 3.0));
+        assert_eq!(summary.timing_ratio_p50, Some(1.05));
+        assert_eq!(summary.timing_ratio_p90, Some(1.5));
+        assert_eq!(summary.gap_count, 0);
+        assert!(summary.compression_ratio.is_none());
+    }
+
+    #[test]
+    fn qc_summary_reports_coverage_compression_and_overall_score() {
+        let segments = vec![
+            qc_segment_report(0, 0, 1_000, "hello there", Some("こんにちは"), Some(500)),
+            qc_segment_report(1, 1_000, 2_000, "", Some("はい"), Some(1_000)),
+            qc_segment_report(2, 5_000, 6_000, "ok", Some("わかった"), None),
+        ];
+        assert_eq!(segments[0].text_chars, 10);
+        assert_eq!(segments[0].source_text_chars, Some(5));
+        assert_eq!(segments[0].coverage, Some(0.5));
+
+        let summary = qc_summary(3, &[], &segments);
+        assert_eq!(summary.gap_count, 1);
+        assert_eq!(summary.timing_coverage, Some(0.75));
+        assert_eq!(summary.text_coverage, Some(2.0 / 3.0));
+        assert_eq!(summary.compression_ratio, Some(12.0 / 11.0));
+        assert_eq!(summary.words_per_minute, Some(3.0 * 60_000.0 / 2_500.0));
+
+        let expected = (0.75 * QC_SCORE_WEIGHT_TIMING
+            + (2.0 / 3.0) * QC_SCORE_WEIGHT_TEXT
+            + (1.0 - 1.0 / 11.0) * QC_SCORE_WEIGHT_COMPRESSION)
+            / (QC_SCORE_WEIGHT_TIMING + QC_SCORE_WEIGHT_TEXT + QC_SCORE_WEIGHT_COMPRESSION);
+        assert!((qc_overall_score(&summary) - expected as f32).abs() < 1e-5);
+
+        let untimed = qc_summary(1, &[], &[qc_segment_report(0, 0, 1_000, "hi", None, None)]);
+        assert_eq!(qc_overall_score(&untimed), 1.0);
+    }
+
+    #[test]
+    fn enqueue_asr_local_validates_and_stores_task() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        seed_item_only(&paths, "item-asr-task", "ASR Task");
+
+        let job = enqueue_asr_local(
+            &paths,
+            "item-asr-task".to_string(),
+            Some("ja".to_string()),
+            Some(" Translate ".to_string()),
+        )
+        .expect("enqueue translate");
+        let conn = db::open(&paths).expect("open");
+        let params_json: String = conn
+            .query_row(
+                "SELECT params_json FROM job WHERE id=?1",
+                params![&job.id],
+                |row| row.get(0),
+            )
+            .expect("params");
+        let stored: AsrLocalParams = serde_json::from_str(&params_json).expect("parse");
+        assert_eq!(stored.task.as_deref(), Some(ASR_TASK_TRANSLATE));
+
+        assert!(enqueue_asr_local(
+            &paths,
+            "item-asr-task".to_string(),
+            None,
+            Some("summarize".to_string()),
+        )
+        .is_err());
+    }
+
     #[test]
     fn enqueue_localization_run_v1_auto_generates_source_reference_before_voice_setup() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -15612,6 +23095,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn demucs_chunk_plan_overlaps_chunks_and_folds_short_tails() {
+        assert_eq!(demucs_chunk_duration_ms(None, Some(1_200_000)), None);
+        assert_eq!(
+            demucs_chunk_duration_ms(None, Some(1_500_000)),
+            Some(600_000)
+        );
+        assert_eq!(demucs_chunk_duration_ms(Some(0), Some(5_000_000)), None);
+        assert_eq!(demucs_chunk_duration_ms(Some(5), None), Some(30_000));
+
+        let plan = demucs_chunk_plan(1_500_000, 600_000, 5_000);
+        assert_eq!(
+            plan,
+            vec![(0, 605_000), (600_000, 605_000), (1_200_000, 300_000)]
+        );
+        let stitched: i64 = plan.iter().map(|(_, duration)| duration).sum::<i64>()
+            - (plan.len() as i64 - 1) * 5_000;
+        assert_eq!(stitched, 1_500_000);
+
+        assert_eq!(
+            demucs_chunk_plan(1_205_000, 600_000, 5_000),
+            vec![(0, 605_000), (600_000, 605_000)]
+        );
+        assert_eq!(
+            demucs_chunk_plan(400_000, 600_000, 5_000),
+            vec![(0, 400_000)]
+        );
+    }
+
+    #[test]
+    fn tts_manifest_coverage_counts_missing_segments() {
+        let manifest: TtsPreviewManifest = serde_json::from_value(serde_json::json!({
+            "segments": [
+                { "index": 1, "start_ms": 0, "end_ms": 900, "audio_exists": true },
+                { "index": 2, "start_ms": 1000, "end_ms": 1100, "audio_exists": false }
+            ]
+        }))
+        .expect("manifest");
+        let coverage = manifest.coverage();
+        assert_eq!(coverage.total_duration_ms, 1000);
+        assert_eq!(coverage.synthesized_duration_ms, 900);
+        assert_eq!(coverage.missing_segment_count, 1);
+        assert!((coverage.coverage_ratio - 0.9).abs() < 1e-6);
+
+        let written = serde_json::to_value(coverage).expect("serialize");
+        let stored: TtsPreviewManifest = serde_json::from_value(serde_json::json!({
+            "total_duration_ms": written["total_duration_ms"],
+            "synthesized_duration_ms": written["synthesized_duration_ms"],
+            "coverage_ratio": written["coverage_ratio"],
+            "missing_segment_count": written["missing_segment_count"],
+            "segments": []
+        }))
+        .expect("stored manifest");
+        assert_eq!(stored.coverage(), coverage);
+        assert_eq!(TtsManifestCoverage::from_spans([]).coverage_ratio, 1.0);
+    }
+
     #[test]
     fn select_tts_manifest_candidate_prefers_requested_backend() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -15903,6 +23443,48 @@ EOF
         assert!(out_dir.join("segments").join("seg_0001.wav").exists());
     }
 
+    #[test]
+    fn enqueue_asr_local_batch_skips_transcribed_items_and_shares_batch_id() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        seed_item_only(&paths, "item-1", "Item 1");
+        seed_item_only(&paths, "item-2", "Item 2");
+        seed_item_only(&paths, "item-3", "Item 3");
+        seed_subtitle_track_named(&paths, "item-2", "track-2", "source", "ja", 1, &[]);
+
+        let result = enqueue_asr_local_batch(
+            &paths,
+            vec![
+                "item-1".to_string(),
+                " item-2 ".to_string(),
+                "item-3".to_string(),
+                "item-1".to_string(),
+            ],
+            Some("ja".to_string()),
+            Some("whispercpp-base".to_string()),
+        )
+        .expect("batch");
+
+        assert_eq!(
+            result.skipped_already_transcribed,
+            vec!["item-2".to_string()]
+        );
+        assert_eq!(result.enqueued.len(), 2);
+        let batch_id = result.enqueued[0].batch_id.clone().expect("batch id");
+        for job in &result.enqueued {
+            assert_eq!(job.job_type, "asr_local");
+            assert_eq!(job.batch_id.as_deref(), Some(batch_id.as_str()));
+            let params: AsrLocalParams = serde_json::from_str(&job.params_json).expect("params");
+            assert_eq!(params.model_id, "whispercpp-base");
+            assert_eq!(params.lang.as_deref(), Some("ja"));
+        }
+
+        let too_many = (0..=ASR_BATCH_MAX_ITEMS)
+            .map(|i| format!("item-{i}"))
+            .collect();
+        assert!(enqueue_asr_local_batch(&paths, too_many, None, None).is_err());
+    }
+
     #[test]
     fn experimental_backend_batch_queue_uses_shared_batch_id_and_ready_backend() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -16032,7 +23614,7 @@ EOF
         .expect("second import");
 
         assert_eq!(first.id, second.id);
-        let jobs = list_jobs(&paths, 20, 0).expect("jobs");
+        let jobs = list_jobs(&paths, 20, 0, None, None).expect("jobs");
         assert_eq!(jobs.len(), 1);
     }
 
@@ -16102,15 +23684,17 @@ EOF
                 model_id: "whispercpp-tiny".to_string(),
                 batch_on_import: true,
                 pipeline: None,
+                task: None,
             })
             .expect("params"),
             Some("item-1".to_string()),
             Some(batch_id),
+            None,
         )
         .expect("child");
 
         cancel_job(&paths, &import.id).expect("cancel");
-        let jobs = list_jobs(&paths, 20, 0).expect("jobs");
+        let jobs = list_jobs(&paths, 20, 0, None, None).expect("jobs");
         let child_status = jobs
             .iter()
             .find(|job| job.id == child.id)
@@ -16120,40 +23704,475 @@ EOF
     }
 
     #[test]
-    fn running_jobs_are_marked_failed_after_restart_recovery() {
+    fn worker_id_is_logged_and_stored_on_completion() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+        let job = enqueue(&paths, JobType::ImportLocal, "{}".to_string()).expect("enqueue");
+        let conn = db::open(&paths).expect("db");
+        conn.execute(
+            "UPDATE job SET status=?1",
+            params![JobStatus::Running.as_str()],
+        )
+        .expect("running");
+
+        CURRENT_WORKER_ID.with(|current| current.set(Some(7)));
+        log_line(&paths, &job.id, "info", "probe", serde_json::json!({})).expect("log");
+        set_succeeded(&paths, &job.id).expect("succeed");
+        CURRENT_WORKER_ID.with(|current| current.set(None));
+
+        let row = get_job(&paths, &job.id).expect("get").expect("row");
+        assert_eq!(row.worker_id, Some(7));
+        let log = std::fs::read_to_string(paths.job_log_path(&job.id)).expect("log file");
+        let probe: serde_json::Value = log
+            .lines()
+            .filter_map(|line| serde_json::from_str(line).ok())
+            .find(|v: &serde_json::Value| v["event"] == "probe")
+            .expect("probe line");
+        assert_eq!(probe["worker_id"], 7);
+        assert!(probe["thread_id"]
+            .as_str()
+            .is_some_and(|id| id.starts_with("ThreadId(")));
+    }
+
+    #[test]
+    fn set_failed_keeps_reported_progress() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+        let kept = enqueue(&paths, JobType::ImportLocal, "{}".to_string()).expect("kept");
+        let overwritten = enqueue(&paths, JobType::ImportLocal, "{}".to_string()).expect("over");
+        let conn = db::open(&paths).expect("db");
+        conn.execute(
+            "UPDATE job SET status=?1, progress=0.4",
+            params![JobStatus::Running.as_str()],
+        )
+        .expect("running");
+
+        assert_eq!(current_progress(), None);
+        set_progress(&paths, &overwritten.id, 0.73).expect("progress");
+        assert_eq!(current_progress(), Some(0.73));
+
+        set_failed(&paths, &kept.id, "boom", None).expect("fail kept");
+        set_failed(&paths, &overwritten.id, "boom", current_progress()).expect("fail over");
+        let progress_of = |id: &str| -> f32 {
+            conn.query_row("SELECT progress FROM job WHERE id=?1", [id], |row| {
+                row.get(0)
+            })
+            .expect("progress")
+        };
+        assert!((progress_of(&kept.id) - 0.4).abs() < 1e-6);
+        assert!((progress_of(&overwritten.id) - 0.73).abs() < 1e-6);
+    }
+
+    #[test]
+    fn check_batch_complete_waits_for_every_batch_job() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+        let batch_id = "batch-1".to_string();
+        let mut job_ids = Vec::new();
+        for _ in 0..3 {
+            let job = enqueue_with_type_item_and_batch_id(
+                &paths,
+                JobType::ImportLocal,
+                "{}".to_string(),
+                None,
+                Some(batch_id.clone()),
+                None,
+            )
+            .expect("enqueue");
+            job_ids.push(job.id);
+        }
+        let conn = db::open(&paths).expect("db");
+        conn.execute(
+            "UPDATE job SET status=?1 WHERE batch_id=?2",
+            params![JobStatus::Running.as_str(), batch_id],
+        )
+        .expect("running");
+
+        set_succeeded(&paths, &job_ids[0]).expect("succeeded");
+        set_failed(&paths, &job_ids[1], "boom", None).expect("failed");
+        assert!(!check_batch_complete(&paths, &batch_id).expect("check"));
+
+        cancel_job(&paths, &job_ids[2]).expect("cancel");
+        assert!(check_batch_complete(&paths, &batch_id).expect("check"));
+        assert!(claim_batch_completion(&paths, &batch_id).expect("claim"));
+        assert!(!claim_batch_completion(&paths, &batch_id).expect("claim again"));
+        let expires_at_ms: Option<i64> = conn
+            .query_row(
+                "SELECT expires_at_ms FROM meta WHERE key=?1",
+                [batch_completed_meta_key(&batch_id)],
+                |row| row.get(0),
+            )
+            .expect("claim row");
+        assert!(expires_at_ms.is_some_and(|expires| expires > now_ms()));
+        assert_eq!(
+            batch_completed_event(&paths, &batch_id).expect("event"),
+            BatchCompletedEvent {
+                batch_id: batch_id.clone(),
+                total_jobs: 3,
+                succeeded: 1,
+                failed: 1,
+                canceled: 1,
+            }
+        );
+
+        let reopened = enqueue_with_type_item_and_batch_id(
+            &paths,
+            JobType::ImportLocal,
+            "{}".to_string(),
+            None,
+            Some(batch_id.clone()),
+            None,
+        )
+        .expect("enqueue again");
+        assert_eq!(cancel_all_jobs(&paths).expect("cancel all"), 1);
+        assert_eq!(
+            get_job(&paths, &reopened.id)
+                .expect("get")
+                .expect("job row")
+                .status,
+            JobStatus::Canceled
+        );
+        assert!(claim_batch_completion(&paths, &batch_id).expect("claim reopened"));
+    }
+
+    #[test]
+    fn search_job_logs_filters_by_job_event_level_and_text() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+
+        log_line(&paths, "job-a", "info", "begin", serde_json::json!({})).expect("log");
+        log_line(
+            &paths,
+            "job-a",
+            "error",
+            "failed",
+            serde_json::json!({ "msg": "Disk Full" }),
+        )
+        .expect("log");
+        log_line(
+            &paths,
+            "job-b",
+            "error",
+            "failed",
+            serde_json::json!({ "msg": "timeout" }),
+        )
+        .expect("log");
+
+        let errors = search_job_logs(
+            &paths,
+            &JobLogQuery {
+                level: Some("ERROR".to_string()),
+                ..JobLogQuery::default()
+            },
+        )
+        .expect("search errors");
+        assert_eq!(errors.len(), 2);
+
+        let disk = search_job_logs(
+            &paths,
+            &JobLogQuery {
+                text_contains: Some("disk full".to_string()),
+                ..JobLogQuery::default()
+            },
+        )
+        .expect("search text");
+        assert_eq!(disk.len(), 1);
+        assert_eq!(disk[0]["job_id"], "job-a");
+
+        let single = search_job_logs(
+            &paths,
+            &JobLogQuery {
+                job_id: Some("job-b".to_string()),
+                event: Some("begin".to_string()),
+                ..JobLogQuery::default()
+            },
+        )
+        .expect("search job");
+        assert!(single.is_empty());
+
+        assert!(search_job_logs(
+            &paths,
+            &JobLogQuery {
+                job_id: Some("../job-a".to_string()),
+                ..JobLogQuery::default()
+            },
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn list_jobs_estimates_remaining_time_from_recent_durations() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+        let conn = db::open(&paths).expect("open");
+        let start_running = |job_id: &str, started_at_ms: i64, progress: f32| {
+            conn.execute(
+                "UPDATE job SET status=?1, started_at_ms=?2, progress=?3 WHERE id=?4",
+                params![JobStatus::Running.as_str(), started_at_ms, progress, job_id],
+            )
+            .expect("force running");
+        };
+
+        let done = enqueue_dummy_sleep(&paths, 10).expect("enqueue done");
+        start_running(&done.id, now_ms() - 60_000, 0.0);
+        set_succeeded(&paths, &done.id).expect("succeed");
+        let avg = load_avg_job_duration_ms(&conn, &done.job_type).expect("avg cached");
+        assert!((60_000..65_000).contains(&avg), "{avg}");
+
+        let running = enqueue(&paths, JobType::DummySleep, r#"{"seconds":1}"#.to_string())
+            .expect("enqueue running");
+        start_running(&running.id, now_ms() - 10_000, 0.5);
+        let queued = enqueue(&paths, JobType::DummySleep, r#"{"seconds":1}"#.to_string())
+            .expect("enqueue queued");
+
+        let jobs = list_jobs(&paths, 10, 0, None, None).expect("list");
+        let find = |id: &str| jobs.iter().find(|j| j.id == id).expect("job");
+        let remaining = find(&running.id).estimate_remaining_ms.expect("estimate");
+        assert!((29_000..=33_000).contains(&remaining), "{remaining}");
+        assert_eq!(find(&queued.id).estimate_remaining_ms, None);
+        assert_eq!(find(&done.id).estimate_remaining_ms, None);
+
+        assert_eq!(estimate_remaining_ms(60_000, 0.1, 58_000), 2_000);
+        assert_eq!(estimate_remaining_ms(60_000, 0.5, 90_000), 0);
+    }
+
+    #[test]
+    fn format_python_stderr_keeps_traceback_and_drops_progress() {
+        let noisy: String = (0..50).map(|i| format!("warning line {i}\n")).collect();
+        let stderr = format!(
+            "{noisy}\r 45%|####      | 45/100\nTraceback (most recent call last):\n  File \"x.py\", line 3, in <module>\nValueError: bad input\n"
+        );
+        assert_eq!(
+            format_python_stderr(&stderr, 20),
+            "Traceback (most recent call last):\n  File \"x.py\", line 3, in <module>\nValueError: bad input"
+        );
+
+        let tail = format_python_stderr(&noisy, 2);
+        assert_eq!(tail, "warning line 48\nwarning line 49");
+
+        let long = format!("Traceback (most recent call last):\n{}", "x".repeat(5000));
+        let truncated = format_python_stderr(&long, 20);
+        assert!(truncated.starts_with("[truncated] "));
+        assert_eq!(
+            truncated.chars().count(),
+            "[truncated] ".len() + PYTHON_STDERR_EXCERPT_MAX_CHARS
+        );
+    }
+
+    #[test]
+    fn prune_job_logs_applies_per_type_overrides() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+        let conn = db::open(&paths).expect("open db");
+        db::migrate(&conn).expect("migrate");
+        for (id, job_type) in [("sleepy", "dummy_sleep"), ("install", "install_tools")] {
+            let logs_path = paths.job_logs_dir().join(format!("{id}.jsonl"));
+            conn.execute(
+                "INSERT INTO job (id, type, status, progress, params_json, created_at_ms, logs_path) VALUES (?1, ?2, 'succeeded', 1, '{}', 1, ?3)",
+                params![id, job_type, logs_path.to_string_lossy().to_string()],
+            )
+            .expect("insert job");
+            log_line(&paths, id, "info", "done", serde_json::json!({})).expect("log");
+            std::fs::write(path_with_suffix(&logs_path, ".1"), "{}\n").expect("backup");
+        }
+        drop(conn);
+
+        let stored = set_job_log_retention_overrides(
+            &paths,
+            vec![
+                JobTypeRetentionOverride {
+                    job_type: " dummy_sleep ".to_string(),
+                    max_age_days: Some(0),
+                    max_backups: None,
+                },
+                JobTypeRetentionOverride {
+                    job_type: "install_tools".to_string(),
+                    max_age_days: None,
+                    max_backups: Some(0),
+                },
+                JobTypeRetentionOverride {
+                    job_type: "noop".to_string(),
+                    max_age_days: None,
+                    max_backups: None,
+                },
+            ],
+        )
+        .expect("set overrides");
+        assert_eq!(stored.len(), 2);
+        assert_eq!(stored[0].job_type, "dummy_sleep");
+        assert_eq!(stored[0].max_age_days, Some(1));
+        assert_eq!(load_job_log_retention_policy(&paths).overrides, stored);
+
+        let two_days_ago = SystemTime::now() - Duration::from_secs(2 * 24 * 60 * 60);
+        let sleepy_log = paths.job_logs_dir().join("sleepy.jsonl");
+        std::fs::File::options()
+            .write(true)
+            .open(&sleepy_log)
+            .expect("open log")
+            .set_modified(two_days_ago)
+            .expect("set mtime");
+
+        prune_job_logs_now(&paths).expect("prune");
+        let logs = paths.job_logs_dir();
+        assert!(!sleepy_log.exists());
+        assert!(logs.join("sleepy.jsonl.1").exists());
+        assert!(logs.join("install.jsonl").exists());
+        assert!(!logs.join("install.jsonl.1").exists());
+    }
+
+    #[test]
+    fn prune_job_logs_removes_orphaned_artifact_dirs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+        let live = enqueue_dummy_sleep(&paths, 1).expect("enqueue");
+        let conn = db::open(&paths).expect("open db");
+        let content_key = "0123456789abcdef0123456789abcdef";
+        conn.execute(
+            "UPDATE job SET content_key=?1 WHERE id=?2",
+            params![content_key, &live.id],
+        )
+        .expect("set content key");
+        drop(conn);
+
+        let orphan_id = "00000000-0000-0000-0000-0000000000ff";
+        let orphan_key = "fedcba9876543210fedcba9876543210";
+        for name in [
+            live.id.as_str(),
+            content_key,
+            orphan_id,
+            orphan_key,
+            "keep-me",
+        ] {
+            std::fs::create_dir_all(paths.derived_jobs_dir().join(name)).expect("mkdir");
+        }
+
+        let summary = prune_job_logs_now(&paths).expect("prune");
+        assert_eq!(summary.pruned_orphan_artifact_dirs, 2);
+        let root = paths.derived_jobs_dir();
+        assert!(root.join(&live.id).exists());
+        assert!(root.join(content_key).exists());
+        assert!(root.join("keep-me").exists());
+        assert!(!root.join(orphan_id).exists());
+        assert!(!root.join(orphan_key).exists());
+    }
+
+    #[test]
+    fn job_log_retention_policy_is_validated_stored_and_used_for_rotation() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+        assert_eq!(
+            load_job_log_retention_policy(&paths),
+            JobLogRetentionPolicy::default()
+        );
+
+        let too_small = JobLogRetentionPolicy {
+            rotate_bytes: 1024,
+            ..JobLogRetentionPolicy::default()
+        };
+        assert!(set_job_log_retention_policy(&paths, too_small).is_err());
+        let too_many_backups = JobLogRetentionPolicy {
+            max_backups: JOB_LOG_MAX_BACKUPS_LIMIT + 1,
+            ..JobLogRetentionPolicy::default()
+        };
+        assert!(set_job_log_retention_policy(&paths, too_many_backups).is_err());
+
+        let stored = set_job_log_retention_policy(
+            &paths,
+            JobLogRetentionPolicy {
+                rotate_bytes: JOB_LOG_MIN_ROTATE_BYTES,
+                max_backups: 1,
+                max_age_days: 7,
+                total_cap_bytes: 64 * JOB_LOG_MIN_ROTATE_BYTES,
+                overrides: Vec::new(),
+            },
+        )
+        .expect("set policy");
+        assert_eq!(stored.max_backups, 1);
+        assert_eq!(load_job_log_retention_policy(&paths), stored);
+
+        let log_path = paths.job_logs_dir().join("big.jsonl");
+        std::fs::write(&log_path, vec![b' '; JOB_LOG_MIN_ROTATE_BYTES as usize]).expect("fill");
+        std::fs::write(path_with_suffix(&log_path, ".1"), "old\n").expect("backup");
+        log_line(&paths, "big", "info", "next", serde_json::json!({})).expect("log");
+        let backup = std::fs::metadata(path_with_suffix(&log_path, ".1")).expect("backup meta");
+        assert_eq!(backup.len(), JOB_LOG_MIN_ROTATE_BYTES);
+        assert!(!path_with_suffix(&log_path, ".2").exists());
+        assert!(std::fs::metadata(&log_path).expect("log meta").len() < 1024);
+    }
+
+    #[test]
+    fn running_jobs_are_requeued_after_restart_until_attempts_run_out() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let job = enqueue_dummy_sleep(&paths, 10).expect("enqueue");
+
+        let conn = db::open(&paths).expect("open");
+        db::migrate(&conn).expect("migrate");
+        let force_running = || {
+            conn.execute(
+                "UPDATE job SET status=?1, started_at_ms=?2 WHERE id=?3",
+                params![JobStatus::Running.as_str(), now_ms(), &job.id],
+            )
+            .expect("force running");
+        };
+
+        for attempt in 1..=MAX_REQUEUE_ATTEMPTS {
+            force_running();
+            let updated = requeue_orphaned_running_jobs(&paths, &conn).expect("requeue");
+            assert_eq!(updated, 1);
+            let row = get_job(&paths, &job.id).expect("get").expect("job row");
+            assert_eq!(row.status, JobStatus::Queued);
+            assert!(row.started_at_ms.is_none());
+            assert_eq!(row.requeue_count, attempt);
+        }
+
+        force_running();
+        requeue_orphaned_running_jobs(&paths, &conn).expect("requeue");
+        let row = get_job(&paths, &job.id).expect("get").expect("job row");
+        assert_eq!(row.status, JobStatus::Failed);
+        assert!(row.finished_at_ms.is_some());
+        assert_eq!(row.error.as_deref(), Some("interrupted by app shutdown"));
+    }
+
+    #[test]
+    fn watchers_are_requeued_after_every_restart_and_skip_the_worker_pool() {
         let dir = tempfile::tempdir().expect("tempdir");
         let paths = AppPaths::new(dir.path().to_path_buf());
         db::ensure_schema(&paths).expect("schema");
 
-        let job = enqueue_dummy_sleep(&paths, 10).expect("enqueue");
+        let watch_dir = dir.path().join("inbox");
+        std::fs::create_dir_all(&watch_dir).expect("inbox");
+        let watcher =
+            enqueue_watch_directory(&paths, watch_dir.to_string_lossy().to_string(), vec![], 60)
+                .expect("enqueue watcher");
+        assert!(fetch_queued_jobs(&paths, 10).expect("fetch").is_empty());
+        let watchers = fetch_queued_watchers(&paths).expect("fetch watchers");
+        assert_eq!(watchers.len(), 1);
+        assert_eq!(watchers[0].0, watcher.id);
 
         let conn = db::open(&paths).expect("open");
         db::migrate(&conn).expect("migrate");
-        conn.execute(
-            "UPDATE job SET status=?1, started_at_ms=?2 WHERE id=?3",
-            params![JobStatus::Running.as_str(), now_ms(), job.id],
-        )
-        .expect("force running");
-
-        let updated = requeue_orphaned_running_jobs(&conn).expect("requeue");
-        assert_eq!(updated, 1);
-
-        let (status, started_at_ms, finished_at_ms, error): (
-            String,
-            Option<i64>,
-            Option<i64>,
-            Option<String>,
-        ) = conn
-            .query_row(
-                "SELECT status, started_at_ms, finished_at_ms, error FROM job WHERE id=?1",
-                [job.id],
-                |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?)),
+        for _ in 0..=MAX_REQUEUE_ATTEMPTS {
+            conn.execute(
+                "UPDATE job SET status=?1, started_at_ms=?2 WHERE id=?3",
+                params![JobStatus::Running.as_str(), now_ms(), &watcher.id],
             )
-            .expect("select");
-        assert_eq!(status, JobStatus::Failed.as_str());
-        assert!(started_at_ms.is_none());
-        assert!(finished_at_ms.is_some());
-        assert_eq!(error.as_deref(), Some("interrupted by app shutdown"));
+            .expect("force running");
+            requeue_orphaned_running_jobs(&paths, &conn).expect("requeue");
+            let row = get_job(&paths, &watcher.id).expect("get").expect("job row");
+            assert_eq!(row.status, JobStatus::Queued);
+            assert_eq!(row.requeue_count, 0);
+        }
     }
 
     #[test]
@@ -16245,6 +24264,40 @@ EOF
         );
     }
 
+    #[test]
+    fn effective_provider_routes_supported_sites_through_yt_dlp() {
+        for url in [
+            "https://vimeo.com/123456",
+            "https://player.vimeo.com/video/123456",
+            "https://soundcloud.com/artist/track",
+            "https://www.dailymotion.com/video/x8abc",
+            "https://www.tiktok.com/@user/video/123",
+            "https://twitter.com/user/status/123",
+            "https://x.com/user/status/123",
+            "https://VIMEO.com./123",
+        ] {
+            assert_eq!(
+                effective_download_provider(DOWNLOAD_PROVIDER_DIRECT_HTTP, url),
+                DOWNLOAD_PROVIDER_YOUTUBE_YT_DLP,
+                "{url}"
+            );
+        }
+        for url in [
+            "https://myvimeo.com/123456",
+            "https://vimeo.com.evil.com/123456",
+            "https://example.com/?u=https://vimeo.com/1",
+            "https://notx.com/user/status/123",
+            "ftp://vimeo.com/123456",
+            "not a url",
+        ] {
+            assert_eq!(
+                effective_download_provider(DOWNLOAD_PROVIDER_DIRECT_HTTP, url),
+                DOWNLOAD_PROVIDER_DIRECT_HTTP,
+                "{url}"
+            );
+        }
+    }
+
     #[test]
     fn normalize_and_expand_enforces_batch_cap_for_direct_urls() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -16337,6 +24390,61 @@ EOF
         );
     }
 
+    #[test]
+    fn download_jobs_warn_when_output_drive_is_below_threshold() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+        let out_dir = dir.path().join("not-yet-created");
+        let log_path = paths.job_logs_dir().join("disk-job.jsonl");
+
+        warn_if_download_disk_space_low(&paths, "disk-job", &out_dir).expect("no threshold");
+        assert!(!log_path.exists());
+
+        config::save_download_disk_space_config(
+            &paths,
+            &config::DownloadDiskSpaceConfig {
+                warn_below_free_gb: Some(0.000_001),
+            },
+        )
+        .expect("save low threshold");
+        warn_if_download_disk_space_low(&paths, "disk-job", &out_dir).expect("plenty free");
+        assert!(!log_path.exists());
+
+        config::save_download_disk_space_config(
+            &paths,
+            &config::DownloadDiskSpaceConfig {
+                warn_below_free_gb: Some(1.0e9),
+            },
+        )
+        .expect("save high threshold");
+        warn_if_download_disk_space_low(&paths, "disk-job", &out_dir).expect("warn");
+        let log = std::fs::read_to_string(&log_path).expect("log");
+        assert!(log.contains("low_disk_space_warning"));
+        assert!(diagnostics::disk_free_gb(dir.path()).expect("free") > 0.0);
+    }
+
+    #[test]
+    fn validate_cookie_format_warns_without_rejecting() {
+        let netscape = validate_cookie_format(
+            "# Netscape HTTP Cookie File\n.instagram.com\tTRUE\t/\tTRUE\t2147483647\tsessionid\tabc123\n",
+        );
+        assert!(netscape.is_netscape_format);
+        assert!(netscape.warnings.is_empty());
+
+        let header = validate_cookie_format("sessionid=abc123; csrftoken=xyz789");
+        assert!(header.is_header_format);
+        assert!(header.warnings.is_empty());
+
+        let short = validate_cookie_format("sessionid=abc");
+        assert!(short.is_header_format);
+        assert_eq!(short.warnings.len(), 1);
+
+        let odd = validate_cookie_format("sessionid=abc123456789;\ncsrftoken");
+        assert!(!odd.is_header_format && !odd.is_netscape_format);
+        assert_eq!(odd.warnings.len(), 2);
+    }
+
     #[test]
     fn netscape_cookie_text_to_header_keeps_http_only_entries() {
         let header = netscape_cookie_text_to_header(
@@ -16481,8 +24589,8 @@ EOF
         let paths = AppPaths::new(dir.path().to_path_buf());
         db::ensure_schema(&paths).expect("schema");
 
-        let queued = enqueue_dummy_sleep(&paths, 3).expect("enqueue queued");
-        let running = enqueue_dummy_sleep(&paths, 3).expect("enqueue running");
+        let queued = queue_dummy_sleep(&paths, 3).expect("enqueue queued");
+        let running = queue_dummy_sleep(&paths, 3).expect("enqueue running");
 
         let conn = db::open(&paths).expect("open");
         db::migrate(&conn).expect("migrate");
@@ -16515,9 +24623,9 @@ EOF
         let paths = AppPaths::new(dir.path().to_path_buf());
         db::ensure_schema(&paths).expect("schema");
 
-        let succeeded = enqueue_dummy_sleep(&paths, 1).expect("enqueue succeeded");
-        let failed = enqueue_dummy_sleep(&paths, 1).expect("enqueue failed");
-        let queued = enqueue_dummy_sleep(&paths, 1).expect("enqueue queued");
+        let succeeded = queue_dummy_sleep(&paths, 1).expect("enqueue succeeded");
+        let failed = queue_dummy_sleep(&paths, 1).expect("enqueue failed");
+        let queued = queue_dummy_sleep(&paths, 1).expect("enqueue queued");
 
         let conn = db::open(&paths).expect("open");
         db::migrate(&conn).expect("migrate");
@@ -16545,8 +24653,8 @@ EOF
             .expect("write succeeded backup");
         std::fs::write(&failed_log, "failed").expect("write failed log");
 
-        let succeeded_artifacts = paths.job_artifacts_dir(&succeeded.id);
-        let failed_artifacts = paths.job_artifacts_dir(&failed.id);
+        let succeeded_artifacts = paths.job_artifacts_dir(&succeeded.id, None);
+        let failed_artifacts = paths.job_artifacts_dir(&failed.id, None);
         std::fs::create_dir_all(&succeeded_artifacts).expect("succeeded artifacts");
         std::fs::create_dir_all(&failed_artifacts).expect("failed artifacts");
         std::fs::write(succeeded_artifacts.join("a.txt"), "a").expect("artifact file");
@@ -16575,8 +24683,16 @@ EOF
         assert_eq!(summary.skipped_external_output_dirs, 0);
         assert!(summary.removed_cache_entries >= 2);
         assert!(summary.failed_paths.is_empty());
+        assert_eq!(summary.freed_log_bytes, 17);
+        assert_eq!(summary.freed_artifact_bytes, 2);
+        assert_eq!(summary.freed_output_bytes, 0);
+        assert!(summary.freed_cache_bytes >= 1);
+        assert_eq!(
+            summary.freed_bytes,
+            summary.freed_log_bytes + summary.freed_artifact_bytes + summary.freed_cache_bytes
+        );
 
-        let remaining = list_jobs(&paths, 20, 0).expect("list");
+        let remaining = list_jobs(&paths, 20, 0, None, None).expect("list");
         assert_eq!(remaining.len(), 1);
         assert_eq!(remaining[0].id, queued.id);
         assert_eq!(remaining[0].status.as_str(), JobStatus::Queued.as_str());
@@ -16586,6 +24702,515 @@ EOF
         assert!(!failed_artifacts.exists());
     }
 
+    #[test]
+    fn get_jobs_by_ids_keeps_request_order_and_skips_unknown() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let a = queue_dummy_sleep(&paths, 1).expect("enqueue a");
+        let b = queue_dummy_sleep(&paths, 1).expect("enqueue b");
+        let rows = get_jobs_by_ids(
+            &paths,
+            &[
+                b.id.clone(),
+                "missing".to_string(),
+                a.id.clone(),
+                b.id.clone(),
+            ],
+        )
+        .expect("batch");
+        assert_eq!(
+            rows.iter().map(|j| j.id.as_str()).collect::<Vec<_>>(),
+            vec![b.id.as_str(), a.id.as_str()]
+        );
+
+        let too_many: Vec<String> = (0..=JOBS_GET_BATCH_MAX).map(|i| format!("j{i}")).collect();
+        assert!(get_jobs_by_ids(&paths, &too_many).is_err());
+
+        assert_eq!(get_job_by_id(&paths, &a.id).expect("get").id, a.id);
+        assert!(matches!(
+            get_job_by_id(&paths, "missing"),
+            Err(EngineError::NotFound { .. })
+        ));
+    }
+
+    #[test]
+    fn retried_job_reuses_content_keyed_artifacts_dir() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+        seed_item_only(&paths, "item-A", "Item A");
+
+        let params_json = serde_json::json!({
+            "item_id": "item-A",
+            "lang": "ja",
+            "model_id": "whisper-base",
+        })
+        .to_string();
+        let first = enqueue_with_type_and_item_id(
+            &paths,
+            JobType::AsrLocal,
+            params_json,
+            Some("item-A".to_string()),
+        )
+        .expect("enqueue");
+        let key = first.content_key.clone().expect("content key");
+        assert_eq!(key.len(), 32);
+
+        let conn = db::open(&paths).expect("open");
+        conn.execute(
+            "UPDATE job SET status=?1, finished_at_ms=?2 WHERE id=?3",
+            params![JobStatus::Failed.as_str(), now_ms(), &first.id],
+        )
+        .expect("mark failed");
+        drop(conn);
+
+        let retried = retry_job(&paths, &first.id).expect("retry");
+        assert_ne!(retried.id, first.id);
+        assert_eq!(retried.content_key.as_deref(), Some(key.as_str()));
+        let artifacts_dir = job_artifacts_dir_for_id(&paths, &retried.id).expect("dir");
+        assert_eq!(artifacts_dir, paths.derived_jobs_dir().join(&key));
+        std::fs::create_dir_all(&artifacts_dir).expect("artifacts dir");
+
+        let preview = preview_jobs_cleanup(&paths).expect("preview");
+        assert_eq!(preview.artifact_dir_count, 0);
+        let summary = flush_jobs_cache(&paths, None).expect("flush");
+        assert_eq!(summary.removed_jobs, 1);
+        assert_eq!(summary.removed_artifact_dirs, 0);
+        assert!(artifacts_dir.exists());
+
+        let dummy = enqueue_dummy_sleep(&paths, 1).expect("dummy");
+        assert!(dummy.content_key.is_none());
+    }
+
+    #[test]
+    fn on_complete_jobs_follow_the_parent_outcome() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let on_complete = vec![
+            JobDependency::on_success(JobType::DummySleep, r#"{"seconds":2}"#.to_string()),
+            JobDependency::always(JobType::DummySleep, r#"{"seconds":3}"#.to_string()),
+        ];
+        let bad = JobDependency {
+            condition: "sometimes".to_string(),
+            ..on_complete[0].clone()
+        };
+        assert!(enqueue_with_on_complete(
+            &paths,
+            JobType::DummySleep,
+            r#"{"seconds":1}"#.to_string(),
+            None,
+            None,
+            vec![bad],
+        )
+        .is_err());
+
+        let mut parents = Vec::new();
+        for seconds in [1, 4] {
+            let parent = enqueue_with_on_complete(
+                &paths,
+                JobType::DummySleep,
+                format!(r#"{{"seconds":{seconds}}}"#),
+                None,
+                Some(format!("batch-{seconds}")),
+                on_complete.clone(),
+            )
+            .expect("enqueue");
+            assert_eq!(
+                get_job_by_id(&paths, &parent.id)
+                    .expect("job")
+                    .on_complete_jobs,
+                on_complete
+            );
+            let conn = db::open(&paths).expect("open");
+            conn.execute(
+                "UPDATE job SET status=?1 WHERE id=?2",
+                params![JobStatus::Running.as_str(), &parent.id],
+            )
+            .expect("running");
+            parents.push(parent);
+        }
+        set_failed(&paths, &parents[0].id, "boom", None).expect("failed");
+        set_succeeded(&paths, &parents[1].id).expect("succeeded");
+
+        let queued_params = |batch_id: &str| {
+            let conn = db::open(&paths).expect("open");
+            let mut stmt = conn
+                .prepare("SELECT params_json FROM job WHERE batch_id=?1 AND status=?2 ORDER BY params_json")
+                .expect("prepare");
+            stmt.query_map(params![batch_id, JobStatus::Queued.as_str()], |row| {
+                row.get::<_, String>(0)
+            })
+            .expect("query")
+            .collect::<rusqlite::Result<Vec<_>>>()
+            .expect("rows")
+        };
+        assert_eq!(queued_params("batch-1"), vec![r#"{"seconds":3}"#]);
+        assert_eq!(
+            queued_params("batch-4"),
+            vec![r#"{"seconds":2}"#, r#"{"seconds":3}"#]
+        );
+    }
+
+    #[test]
+    fn unreadable_on_complete_jobs_are_logged_on_the_parent() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let parent = enqueue_with_on_complete(
+            &paths,
+            JobType::DummySleep,
+            r#"{"seconds":1}"#.to_string(),
+            None,
+            None,
+            Vec::new(),
+        )
+        .expect("enqueue");
+        let conn = db::open(&paths).expect("open");
+        conn.execute(
+            "UPDATE job SET status=?1, on_complete_json='not json' WHERE id=?2",
+            params![JobStatus::Running.as_str(), &parent.id],
+        )
+        .expect("running");
+
+        set_succeeded(&paths, &parent.id).expect("succeeded");
+        assert_eq!(
+            get_job_by_id(&paths, &parent.id).expect("job").status,
+            JobStatus::Succeeded
+        );
+        let log = std::fs::read_to_string(paths.job_log_path(&parent.id)).expect("log file");
+        assert!(log.contains("on_complete_jobs_failed"));
+    }
+
+    #[test]
+    fn chained_jobs_record_their_parent() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let parent = enqueue_with_on_complete(
+            &paths,
+            JobType::DummySleep,
+            r#"{"seconds":1}"#.to_string(),
+            None,
+            None,
+            vec![JobDependency::always(
+                JobType::DummySleep,
+                r#"{"seconds":2}"#.to_string(),
+            )],
+        )
+        .expect("enqueue");
+        assert!(parent.parent_job_id.is_none());
+        let child = enqueue_with_type_item_and_batch_id(
+            &paths,
+            JobType::DummySleep,
+            r#"{"seconds":3}"#.to_string(),
+            None,
+            None,
+            Some(parent.id.clone()),
+        )
+        .expect("child");
+        assert_eq!(child.parent_job_id.as_deref(), Some(parent.id.as_str()));
+
+        let conn = db::open(&paths).expect("open");
+        conn.execute(
+            "UPDATE job SET status=?1 WHERE id=?2",
+            params![JobStatus::Running.as_str(), &parent.id],
+        )
+        .expect("running");
+        set_succeeded(&paths, &parent.id).expect("succeeded");
+
+        let children = list_child_jobs(&paths, &parent.id, 10, 0).expect("children");
+        assert_eq!(children.len(), 2);
+        assert_eq!(children[0].id, child.id);
+        assert!(children
+            .iter()
+            .all(|row| row.parent_job_id.as_deref() == Some(parent.id.as_str())));
+        assert_eq!(
+            list_child_jobs(&paths, &parent.id, 10, 1)
+                .expect("page")
+                .len(),
+            1
+        );
+
+        set_failed(&paths, &child.id, "boom", None).expect("failed");
+        let retry = retry_job(&paths, &child.id).expect("retry");
+        assert_eq!(retry.parent_job_id.as_deref(), Some(parent.id.as_str()));
+        assert_eq!(
+            get_job_by_id(&paths, &retry.id)
+                .expect("job")
+                .parent_job_id
+                .as_deref(),
+            Some(parent.id.as_str())
+        );
+    }
+
+    #[test]
+    fn chained_follow_ups_wait_for_the_job_and_survive_retries() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let job = enqueue_dummy_sleep(&paths, 1).expect("enqueue");
+        let follow_up =
+            JobDependency::on_success(JobType::DummySleep, r#"{"seconds":2}"#.to_string())
+                .with_on_complete(vec![JobDependency::always(
+                    JobType::DummySleep,
+                    r#"{"seconds":3}"#.to_string(),
+                )]);
+        chain_on_complete(&paths, &job.id, follow_up.clone()).expect("chain");
+        chain_on_complete(&paths, &job.id, follow_up.clone()).expect("chain again");
+        assert_eq!(
+            get_job_by_id(&paths, &job.id)
+                .expect("job")
+                .on_complete_jobs,
+            vec![follow_up.clone()]
+        );
+        assert!(list_child_jobs(&paths, &job.id, 10, 0)
+            .expect("children")
+            .is_empty());
+
+        let retry = retry_job(&paths, &job.id).expect("retry");
+        assert_eq!(retry.on_complete_jobs, vec![follow_up.clone()]);
+
+        let conn = db::open(&paths).expect("open");
+        conn.execute(
+            "UPDATE job SET status=?1 WHERE id=?2",
+            params![JobStatus::Running.as_str(), &retry.id],
+        )
+        .expect("running");
+        set_succeeded(&paths, &retry.id).expect("succeeded");
+        let children = list_child_jobs(&paths, &retry.id, 10, 0).expect("children");
+        assert_eq!(children.len(), 1);
+        assert_eq!(children[0].params_json, r#"{"seconds":2}"#);
+        assert_eq!(children[0].on_complete_jobs, follow_up.on_complete);
+    }
+
+    #[test]
+    fn progress_steps_round_trip_through_job_rows() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let job = enqueue_dummy_sleep(&paths, 1).expect("enqueue");
+        assert!(job.progress_steps.is_empty());
+        record_step_start(&paths, &job.id, "extract_audio").expect("start");
+        record_step_done(&paths, &job.id, "extract_audio").expect("done");
+        record_step_start(&paths, &job.id, "transcribe").expect("start");
+        record_step_done(&paths, &job.id, "save_track").expect("done unstarted");
+
+        let steps = get_job_by_id(&paths, &job.id).expect("job").progress_steps;
+        let summary: Vec<_> = steps
+            .iter()
+            .map(|step| (step.name.as_str(), step.status.as_str()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("extract_audio", PROGRESS_STEP_STATUS_DONE),
+                ("transcribe", PROGRESS_STEP_STATUS_RUNNING),
+                ("save_track", PROGRESS_STEP_STATUS_DONE),
+            ]
+        );
+        assert!(steps[0].started_at_ms.is_some() && steps[0].finished_at_ms.is_some());
+        assert!(steps[1].finished_at_ms.is_none());
+        assert!(steps[2].started_at_ms.is_none());
+
+        record_step_start(&paths, &job.id, "extract_audio").expect("restart");
+        let steps = get_job_by_id(&paths, &job.id).expect("job").progress_steps;
+        assert_eq!(steps.len(), 3);
+        assert_eq!(steps[0].status, PROGRESS_STEP_STATUS_RUNNING);
+        assert!(steps[0].finished_at_ms.is_none());
+
+        assert!(record_step_start(&paths, "missing", "extract_audio").is_err());
+
+        let conn = db::open(&paths).expect("open");
+        conn.execute(
+            "UPDATE job SET status=?1 WHERE id=?2",
+            params![JobStatus::Running.as_str(), &job.id],
+        )
+        .expect("running");
+        set_failed(&paths, &job.id, "boom", None).expect("failed");
+        let steps = get_job_by_id(&paths, &job.id).expect("job").progress_steps;
+        assert_eq!(steps[0].status, PROGRESS_STEP_STATUS_FAILED);
+        assert_eq!(steps[1].status, PROGRESS_STEP_STATUS_FAILED);
+        assert!(steps[1].finished_at_ms.is_some());
+        assert_eq!(steps[2].status, PROGRESS_STEP_STATUS_DONE);
+
+        let running = enqueue_dummy_sleep(&paths, 1).expect("enqueue");
+        record_step_start(&paths, &running.id, "extract_audio").expect("start");
+        cancel_job(&paths, &running.id).expect("cancel");
+        let steps = get_job_by_id(&paths, &running.id)
+            .expect("job")
+            .progress_steps;
+        assert_eq!(steps[0].status, PROGRESS_STEP_STATUS_CANCELED);
+    }
+
+    #[test]
+    fn retry_batch_requeues_latest_failed_attempts_once() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let batch_id = Uuid::new_v4().to_string();
+        let mut originals = Vec::new();
+        for seconds in [1, 2, 3] {
+            originals.push(
+                enqueue_with_type_item_and_batch_id(
+                    &paths,
+                    JobType::DummySleep,
+                    format!(r#"{{"seconds":{seconds}}}"#),
+                    None,
+                    Some(batch_id.clone()),
+                    None,
+                )
+                .expect("enqueue"),
+            );
+        }
+        let conn = db::open(&paths).expect("open");
+        for (job, status) in
+            originals
+                .iter()
+                .zip([JobStatus::Failed, JobStatus::Canceled, JobStatus::Succeeded])
+        {
+            conn.execute(
+                "UPDATE job SET status=?1 WHERE id=?2",
+                params![status.as_str(), &job.id],
+            )
+            .expect("status");
+        }
+        drop(conn);
+
+        let retried = retry_batch(&paths, &batch_id, true, None).expect("retry batch");
+        assert_eq!(retried.len(), 2);
+        assert!(retried
+            .iter()
+            .all(|row| row.batch_id.as_deref() == Some(batch_id.as_str())));
+        assert!(retry_batch(&paths, &batch_id, true, None)
+            .expect("retry again")
+            .is_empty());
+
+        let conn = db::open(&paths).expect("open");
+        conn.execute(
+            "UPDATE job SET status=?1 WHERE id=?2",
+            params![JobStatus::Failed.as_str(), &retried[0].id],
+        )
+        .expect("fail retry");
+        drop(conn);
+        let again = retry_batch(&paths, &batch_id, false, Some(60_000)).expect("new batch");
+        assert_eq!(again.len(), 1);
+        assert_ne!(again[0].batch_id.as_deref(), Some(batch_id.as_str()));
+        assert!(again[0].not_before_ms.expect("delayed") > now_ms());
+
+        let single = retry_job_with_options(&paths, &originals[2].id, false, None).expect("retry");
+        assert_eq!(single.batch_id, None);
+    }
+
+    #[test]
+    fn flush_jobs_cache_dry_run_and_filters_keep_unmatched_jobs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let old = queue_dummy_sleep(&paths, 1).expect("enqueue old");
+        let recent = queue_dummy_sleep(&paths, 1).expect("enqueue recent");
+        let conn = db::open(&paths).expect("open");
+        db::migrate(&conn).expect("migrate");
+        let ten_days_ago = now_ms() - 10 * 86_400_000;
+        for (id, finished) in [(&old.id, ten_days_ago), (&recent.id, now_ms())] {
+            conn.execute(
+                "UPDATE job SET status=?1, finished_at_ms=?2 WHERE id=?3",
+                params![JobStatus::Succeeded.as_str(), finished, id],
+            )
+            .expect("mark succeeded");
+        }
+        drop(conn);
+        std::fs::create_dir_all(paths.job_logs_dir()).expect("job logs dir");
+        std::fs::write(&old.logs_path, "12345").expect("old log");
+        std::fs::write(&recent.logs_path, "123").expect("recent log");
+        std::fs::create_dir_all(paths.cache_dir()).expect("cache dir");
+        std::fs::write(paths.cache_dir().join("tmp.bin"), "xx").expect("cache file");
+
+        let dry = flush_jobs_cache(
+            &paths,
+            Some(JobCleanupOptions {
+                dry_run: true,
+                ..Default::default()
+            }),
+        )
+        .expect("dry run");
+        assert!(dry.dry_run);
+        assert_eq!(dry.removed_jobs, 2);
+        assert_eq!(dry.would_remove_bytes, 10);
+        assert_eq!(dry.available_to_remove_bytes, 10);
+        assert!(Path::new(&old.logs_path).exists());
+        assert!(paths.cache_dir().join("tmp.bin").exists());
+
+        let unmatched = flush_jobs_cache(
+            &paths,
+            Some(JobCleanupOptions {
+                job_types: Some(vec![JobType::ImportLocal.as_str().to_string()]),
+                ..Default::default()
+            }),
+        )
+        .expect("type filter");
+        assert_eq!(unmatched.removed_jobs, 0);
+        assert_eq!(unmatched.removed_cache_entries, 0);
+
+        let summary = flush_jobs_cache(
+            &paths,
+            Some(JobCleanupOptions {
+                older_than_days: Some(7),
+                ..Default::default()
+            }),
+        )
+        .expect("age filter");
+        assert_eq!(summary.removed_jobs, 1);
+        assert_eq!(summary.would_remove_bytes, 5);
+        assert!(!Path::new(&old.logs_path).exists());
+        assert!(Path::new(&recent.logs_path).exists());
+        assert!(paths.cache_dir().join("tmp.bin").exists());
+        assert!(get_job(&paths, &recent.id).expect("get job").is_some());
+    }
+
+    #[test]
+    fn flush_jobs_cache_dry_run_counts_shared_content_key_dirs_once() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let key = "0123456789abcdef0123456789abcdef";
+        let first = queue_dummy_sleep(&paths, 1).expect("enqueue first");
+        let second = queue_dummy_sleep(&paths, 1).expect("enqueue second");
+        let conn = db::open(&paths).expect("open");
+        for id in [&first.id, &second.id] {
+            conn.execute(
+                "UPDATE job SET status=?1, finished_at_ms=?2, content_key=?3 WHERE id=?4",
+                params![JobStatus::Succeeded.as_str(), now_ms(), key, id],
+            )
+            .expect("mark succeeded");
+        }
+        drop(conn);
+        let shared = paths.job_artifacts_dir(&first.id, Some(key));
+        std::fs::create_dir_all(&shared).expect("artifacts dir");
+        std::fs::write(shared.join("out.bin"), "1234").expect("artifact");
+
+        let dry = flush_jobs_cache(
+            &paths,
+            Some(JobCleanupOptions {
+                dry_run: true,
+                ..Default::default()
+            }),
+        )
+        .expect("dry run");
+        assert_eq!(dry.removed_jobs, 2);
+        assert_eq!(dry.would_remove_bytes, 4);
+    }
+
     #[test]
     fn flush_jobs_cache_does_not_remove_output_dirs_without_opt_in() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -16601,14 +25226,14 @@ EOF
         let job = enqueue_download_image_batch(
             &paths,
             vec!["https://example.com/forum".to_string()],
-            Some(2),
-            Some(0),
-            Some(false),
-            Some(false),
-            vec![],
-            Some("wipe_me".to_string()),
-            None,
-            None,
+            DownloadImageBatchOptions {
+                max_pages: Some(2),
+                delay_ms: Some(0),
+                allow_cross_domain: Some(false),
+                follow_content_links: Some(false),
+                output_subdir: Some("wipe_me".to_string()),
+                ..Default::default()
+            },
         )
         .expect("enqueue image batch");
 
@@ -16649,14 +25274,14 @@ EOF
         let job = enqueue_download_image_batch(
             &paths,
             vec!["https://example.com/forum".to_string()],
-            Some(2),
-            Some(0),
-            Some(false),
-            Some(false),
-            vec![],
-            Some("wipe_me".to_string()),
-            None,
-            None,
+            DownloadImageBatchOptions {
+                max_pages: Some(2),
+                delay_ms: Some(0),
+                allow_cross_domain: Some(false),
+                follow_content_links: Some(false),
+                output_subdir: Some("wipe_me".to_string()),
+                ..Default::default()
+            },
         )
         .expect("enqueue image batch");
 
@@ -16680,6 +25305,7 @@ EOF
             Some(JobCleanupOptions {
                 remove_managed_output_dirs: true,
                 remove_external_output_dirs: false,
+                ..Default::default()
             }),
         )
         .expect("flush");
@@ -16699,14 +25325,14 @@ EOF
         let job = enqueue_download_image_batch(
             &paths,
             vec!["https://example.com/forum".to_string()],
-            Some(2),
-            Some(0),
-            Some(false),
-            Some(false),
-            vec![],
-            None,
-            Some(external_output_dir.to_string_lossy().to_string()),
-            None,
+            DownloadImageBatchOptions {
+                max_pages: Some(2),
+                delay_ms: Some(0),
+                allow_cross_domain: Some(false),
+                follow_content_links: Some(false),
+                output_dir: Some(external_output_dir.to_string_lossy().to_string()),
+                ..Default::default()
+            },
         )
         .expect("enqueue image batch");
 
@@ -16732,14 +25358,14 @@ EOF
         let external_job = enqueue_download_image_batch(
             &paths,
             vec!["https://example.com/forum2".to_string()],
-            Some(2),
-            Some(0),
-            Some(false),
-            Some(false),
-            vec![],
-            None,
-            Some(external_output_dir.to_string_lossy().to_string()),
-            None,
+            DownloadImageBatchOptions {
+                max_pages: Some(2),
+                delay_ms: Some(0),
+                allow_cross_domain: Some(false),
+                follow_content_links: Some(false),
+                output_dir: Some(external_output_dir.to_string_lossy().to_string()),
+                ..Default::default()
+            },
         )
         .expect("enqueue image batch again");
         let conn = db::open(&paths).expect("reopen");
@@ -16760,6 +25386,7 @@ EOF
             Some(JobCleanupOptions {
                 remove_managed_output_dirs: false,
                 remove_external_output_dirs: true,
+                ..Default::default()
             }),
         )
         .expect("destructive flush");
@@ -16782,14 +25409,14 @@ EOF
         let job = enqueue_download_image_batch(
             &paths,
             vec!["https://example.com/forum".to_string()],
-            Some(2),
-            Some(0),
-            Some(false),
-            Some(false),
-            vec![],
-            Some("broken_target".to_string()),
-            None,
-            None,
+            DownloadImageBatchOptions {
+                max_pages: Some(2),
+                delay_ms: Some(0),
+                allow_cross_domain: Some(false),
+                follow_content_links: Some(false),
+                output_subdir: Some("broken_target".to_string()),
+                ..Default::default()
+            },
         )
         .expect("enqueue image batch");
 
@@ -16812,6 +25439,7 @@ EOF
             Some(JobCleanupOptions {
                 remove_managed_output_dirs: true,
                 remove_external_output_dirs: false,
+                ..Default::default()
             }),
         )
         .expect("flush with failure");
@@ -16820,7 +25448,7 @@ EOF
         assert_eq!(summary.removed_managed_output_dirs, 0);
         assert!(!summary.failed_paths.is_empty());
 
-        let remaining = list_jobs(&paths, 20, 0).expect("list");
+        let remaining = list_jobs(&paths, 20, 0, None, None).expect("list");
         assert_eq!(remaining.len(), 1);
         assert_eq!(remaining[0].id, job.id);
     }
@@ -16834,14 +25462,16 @@ EOF
         let job = enqueue_download_image_batch(
             &paths,
             vec!["https://example.com/blog".to_string()],
-            Some(25),
-            Some(100),
-            Some(false),
-            Some(true),
-            vec!["avatar".to_string()],
-            Some("dad-images".to_string()),
-            None,
-            Some("session=abc123".to_string()),
+            DownloadImageBatchOptions {
+                max_pages: Some(25),
+                delay_ms: Some(100),
+                allow_cross_domain: Some(false),
+                follow_content_links: Some(true),
+                skip_url_keywords: vec!["avatar".to_string()],
+                output_subdir: Some("dad-images".to_string()),
+                auth_cookie: Some("session=abc123".to_string()),
+                ..Default::default()
+            },
         )
         .expect("enqueue image batch");
         assert_eq!(job.job_type, "download_image_batch");
@@ -16866,8 +25496,224 @@ EOF
 
         let cookie_path = paths.job_cookie_secret_path(&job.id);
         assert!(cookie_path.exists(), "cookie secret should exist on disk");
-        let stored = std::fs::read_to_string(cookie_path).expect("read cookie secret");
-        assert_eq!(stored.trim(), "session=abc123");
+        let raw = std::fs::read(cookie_path).expect("read cookie secret");
+        assert!(!raw.windows(14).any(|w| w == b"session=abc123"));
+        let stored = read_job_cookie_secret(&paths, &job.id)
+            .expect("decrypt cookie secret")
+            .expect("cookie secret");
+        assert_eq!(stored, "session=abc123");
+
+        let mut tampered = raw;
+        *tampered.last_mut().expect("tag") ^= 1;
+        std::fs::write(paths.job_cookie_secret_path(&job.id), tampered).expect("tamper");
+        assert!(matches!(
+            read_job_cookie_secret(&paths, &job.id),
+            Err(EngineError::InvalidInput { .. })
+        ));
+    }
+
+    #[test]
+    fn scheduled_download_batch_is_not_fetched_before_its_start_time() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let later = now_ms() + 60 * 60 * 1000;
+        let scheduled = enqueue_download_image_batch(
+            &paths,
+            vec!["https://example.com/blog".to_string()],
+            DownloadImageBatchOptions {
+                schedule_after_ms: Some(later),
+                ..Default::default()
+            },
+        )
+        .expect("enqueue scheduled");
+        assert_eq!(scheduled.not_before_ms, Some(later));
+        let immediate = enqueue_download_direct_url_batch(
+            &paths,
+            vec!["https://example.com/file.mp4".to_string()],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .expect("enqueue immediate");
+        assert_eq!(immediate.queued[0].not_before_ms, None);
+
+        let queued = fetch_queued_jobs(&paths, 10).expect("fetch");
+        assert_eq!(queued.len(), 1);
+        assert_eq!(queued[0].0, immediate.queued[0].id);
+
+        let conn = db::open(&paths).expect("open");
+        conn.execute(
+            "UPDATE job SET not_before_ms=?1 WHERE id=?2",
+            params![now_ms() - 1, &scheduled.id],
+        )
+        .expect("move schedule");
+        drop(conn);
+        assert_eq!(fetch_queued_jobs(&paths, 10).expect("fetch").len(), 2);
+
+        assert!(enqueue_download_direct_url_batch(
+            &paths,
+            vec!["https://example.com/file.mp4".to_string()],
+            None,
+            None,
+            None,
+            None,
+            None,
+            Some(-5),
+            None,
+            None,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn enqueue_download_direct_url_batch_skips_invalid_urls() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let checked = validate_urls(vec![
+            "https://example.com/a.mp4 ftp://example.com/b.mp4".to_string(),
+            "https://example.com/a.mp4".to_string(),
+        ]);
+        assert_eq!(checked.len(), 2);
+        assert!(checked[0].valid);
+        assert_eq!(
+            checked[0].normalized.as_deref(),
+            Some("https://example.com/a.mp4")
+        );
+        assert!(!checked[1].valid);
+        assert!(checked[1]
+            .error
+            .as_deref()
+            .unwrap_or_default()
+            .contains("scheme"));
+
+        let result = enqueue_download_direct_url_batch(
+            &paths,
+            vec!["https://example.com/a.mp4\nftp://example.com/b.mp4\nhttp://".to_string()],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .expect("enqueue");
+        assert_eq!(result.queued.len(), 1);
+        let skipped: Vec<&str> = result
+            .skipped_invalid
+            .iter()
+            .map(|v| v.url.as_str())
+            .collect();
+        assert_eq!(skipped, vec!["ftp://example.com/b.mp4", "http://"]);
+
+        let all_invalid = enqueue_download_direct_url_batch(
+            &paths,
+            vec!["not a url".to_string()],
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .expect("all invalid");
+        assert!(all_invalid.queued.is_empty());
+        assert_eq!(all_invalid.skipped_invalid.len(), 3);
+    }
+
+    #[test]
+    fn enqueue_translate_batch_selects_untranslated_items_with_source_tracks() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+        seed_item_only(&paths, "item-a", "A");
+        seed_subtitle_track_named(&paths, "item-a", "track-a", "source", "ja", 1, &["S1"]);
+        seed_item_only(&paths, "item-b", "B");
+        seed_subtitle_track_named(&paths, "item-b", "track-b", "source", "ja", 1, &["S1"]);
+        seed_subtitle_track_named(
+            &paths,
+            "item-b",
+            "track-b-en",
+            "translated",
+            "en",
+            1,
+            &["S1"],
+        );
+        seed_item_only(&paths, "item-c", "C");
+        seed_item_only(&paths, "item-d", "D");
+        seed_subtitle_track_named(&paths, "item-d", "track-d", "source", "ko", 1, &["S1"]);
+
+        let preview = enqueue_translate_batch(&paths, None, true, Some(1)).expect("dry run");
+        assert!(preview.enqueued.is_empty());
+        assert_eq!(preview.eligible_item_ids, vec!["item-a".to_string()]);
+        assert_eq!(preview.skipped_over_limit, 1);
+        assert_eq!(
+            preview.skipped_already_translated,
+            vec!["item-b".to_string()]
+        );
+        assert_eq!(preview.skipped_no_source_track, vec!["item-c".to_string()]);
+
+        let queued = enqueue_translate_batch(&paths, None, false, None).expect("enqueue");
+        assert_eq!(queued.enqueued.len(), 2);
+        assert!(queued
+            .enqueued
+            .iter()
+            .all(|job| job.batch_id == queued.batch_id && job.job_type == "translate_local"));
+
+        let again = enqueue_translate_batch(&paths, None, false, None).expect("enqueue again");
+        assert!(again.enqueued.is_empty());
+        assert_eq!(again.skipped_already_translated.len(), 3);
+    }
+
+    #[test]
+    fn enqueue_install_model_v1_reuses_active_job_for_same_model() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        assert!(enqueue_install_model_v1(&paths, "no-such-model").is_err());
+        let first = enqueue_install_model_v1(&paths, "demo-ja-asr").expect("enqueue");
+        let second = enqueue_install_model_v1(&paths, " demo-ja-asr ").expect("enqueue again");
+        assert_eq!(first.id, second.id);
+        assert_eq!(first.job_type, "install_model_v1");
+    }
+
+    #[test]
+    fn skip_urls_in_download_archive_filters_recorded_ids() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let archive = dir.path().join("archive.txt");
+        assert!(skip_urls_in_download_archive(vec![], &archive).is_err());
+
+        std::fs::write(&archive, "youtube dQw4w9WgXcQ\ninstagram Cabc123\n").expect("write");
+        let kept = skip_urls_in_download_archive(
+            vec![
+                "https://www.youtube.com/watch?v=dQw4w9WgXcQ".to_string(),
+                "https://www.youtube.com/watch?v=aaaaaaaaaaa".to_string(),
+                "https://www.instagram.com/p/Cabc123/".to_string(),
+                "https://example.com/file.mp4".to_string(),
+            ],
+            &archive,
+        )
+        .expect("filter");
+        assert_eq!(
+            kept,
+            vec![
+                "https://www.youtube.com/watch?v=aaaaaaaaaaa".to_string(),
+                "https://example.com/file.mp4".to_string(),
+            ]
+        );
     }
 
     #[test]
@@ -16902,6 +25748,62 @@ EOF
         assert!(!params.use_browser_cookies);
     }
 
+    #[test]
+    fn enqueue_batch_in_transaction_rolls_back_on_insert_failure() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let conn = db::open(&paths).expect("open");
+        db::migrate(&conn).expect("migrate");
+        let jobs = enqueue_batch_in_transaction(
+            &conn,
+            &paths,
+            vec![
+                (JobType::DummySleep, "{}".to_string(), None, None),
+                (JobType::DummySleep, "{}".to_string(), None, None),
+            ],
+            "batch-ok",
+            None,
+        )
+        .expect("enqueue batch");
+        assert_eq!(jobs.len(), 2);
+        assert!(jobs
+            .iter()
+            .all(|job| job.batch_id.as_deref() == Some("batch-ok")));
+
+        conn.execute_batch(
+            "CREATE TRIGGER reject_marked_job BEFORE INSERT ON job
+             WHEN NEW.params_json = '{\"reject\":true}'
+             BEGIN SELECT RAISE(ABORT, 'rejected'); END;",
+        )
+        .expect("trigger");
+        let err = enqueue_batch_in_transaction(
+            &conn,
+            &paths,
+            vec![
+                (JobType::DummySleep, "{}".to_string(), None, None),
+                (
+                    JobType::DummySleep,
+                    r#"{"reject":true}"#.to_string(),
+                    None,
+                    None,
+                ),
+            ],
+            "batch-fail",
+            None,
+        );
+        assert!(err.is_err());
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM job WHERE batch_id='batch-fail'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("count");
+        assert_eq!(count, 0);
+    }
+
     #[test]
     fn default_direct_job_output_dir_routes_instagram_cdn_to_instagram_folder() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -16927,9 +25829,67 @@ EOF
 
     #[test]
     fn suggested_download_filename_has_suffix_and_extension() {
-        let name = suggested_download_filename("https://example.com/video", "12345678-abcd");
+        let (name, source) =
+            resolve_download_filename("https://example.com/video", None, "12345678-abcd");
         assert!(name.starts_with("video_12345678."));
         assert!(name.ends_with(".mp4"));
+        assert_eq!(source, "url_path");
+
+        let (name, source) = resolve_download_filename(
+            "https://cdn.example.com/3f9a2c",
+            Some("My Clip.webm"),
+            "12345678-abcd",
+        );
+        assert_eq!(name, "My Clip_12345678.webm");
+        assert_eq!(source, "content-disposition");
+    }
+
+    #[test]
+    fn parse_content_disposition_filename_handles_quoted_and_extended_forms() {
+        assert_eq!(
+            parse_content_disposition_filename(r#"attachment; filename="clip; part 1.mp4""#),
+            Some("clip; part 1.mp4".to_string())
+        );
+        assert_eq!(
+            parse_content_disposition_filename(
+                "attachment; filename=fallback.mp4; filename*=UTF-8''caf%C3%A9.mp4"
+            ),
+            Some("café.mp4".to_string())
+        );
+        assert_eq!(
+            parse_content_disposition_filename("inline; filename=video.mp4"),
+            Some("video.mp4".to_string())
+        );
+        for bad in [
+            r#"attachment; filename="../evil.mp4""#,
+            r#"attachment; filename="a\\b.mp4""#,
+            "attachment; filename=..",
+            "attachment",
+        ] {
+            assert_eq!(parse_content_disposition_filename(bad), None, "{bad}");
+        }
+        let long = format!("attachment; filename={}.mp4", "a".repeat(400));
+        assert_eq!(
+            parse_content_disposition_filename(&long).map(|v| v.ends_with(".mp4")),
+            Some(true)
+        );
+    }
+
+    #[test]
+    fn download_filename_with_job_suffix_keeps_suffix_and_extension_within_limit() {
+        let job_id = "0123456789abcdef";
+        assert_eq!(
+            download_filename_with_job_suffix("clip.webm", job_id),
+            "clip_01234567.webm"
+        );
+        for stem in ["a".repeat(400), "é".repeat(300)] {
+            let name = download_filename_with_job_suffix(&format!("{stem}.mp4"), job_id);
+            assert!(name.len() <= DOWNLOAD_FILENAME_MAX_BYTES, "{}", name.len());
+            assert!(name.ends_with("_01234567.mp4"), "{name}");
+        }
+        let long_ext = download_filename_with_job_suffix(&format!("a.{}", "x".repeat(300)), job_id);
+        assert!(long_ext.len() <= DOWNLOAD_FILENAME_MAX_BYTES);
+        assert!(long_ext.starts_with("a_01234567."));
     }
 
     #[test]
@@ -16956,6 +25916,27 @@ EOF
         );
     }
 
+    #[test]
+    fn validate_yt_dlp_output_template_rejects_unsafe_templates() {
+        validate_yt_dlp_output_template("%(uploader)s/%(upload_date)s_%(title)s [%(id)s].%(ext)s")
+            .expect("supported fields");
+        for bad in [
+            "/tmp/%(title)s.%(ext)s",
+            "../%(title)s.%(ext)s",
+            "%(title)s/..",
+            "%(uploader)s/../../%(title)s.%(ext)s",
+            "C:\\Users\\%(title)s.%(ext)s",
+            "C:/Users/%(title)s.%(ext)s",
+            "C:%(title)s.%(ext)s",
+            "\\\\server\\share\\%(title)s.%(ext)s",
+            "%(title)s;rm.%(ext)s",
+            "$(whoami).%(ext)s",
+            "%(channel)s.%(ext)s",
+        ] {
+            assert!(validate_yt_dlp_output_template(bad).is_err(), "{bad}");
+        }
+    }
+
     #[test]
     fn convert_download_template_to_ytdlp_sanitizes_unsafe_literals() {
         let rendered = convert_download_template_to_ytdlp("{title}:*?");
@@ -17061,6 +26042,29 @@ EOF
         assert_eq!(code, "Cx4Qd9vIBTh");
     }
 
+    #[test]
+    fn instagram_pre_fetch_helpers_read_media_id_and_thumbnail() {
+        assert_eq!(
+            instagram_media_id_from_url("https://www.instagram.com/reel/Cx4Qd9vIBTh/"),
+            instagram_shortcode_to_media_id("Cx4Qd9vIBTh")
+        );
+        assert_eq!(
+            instagram_media_id_from_url("https://www.instagram.com/someone/"),
+            None
+        );
+        let payload = serde_json::json!({
+            "items": [{ "thumbnail_url": " https://cdn.example/thumb.jpg " }]
+        });
+        assert_eq!(
+            instagram_thumbnail_url_from_info(&payload).as_deref(),
+            Some("https://cdn.example/thumb.jpg")
+        );
+        assert_eq!(
+            instagram_thumbnail_url_from_info(&serde_json::json!({ "items": [] })),
+            None
+        );
+    }
+
     fn seed_job_row(paths: &AppPaths, id: &str, item_id: &str, status: JobStatus) {
         let logs_path = paths
             .job_logs_dir()
@@ -17085,6 +26089,45 @@ EOF
         .expect("insert job");
     }
 
+    #[test]
+    fn list_jobs_filters_by_item_id_and_job_type() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+        seed_item_only(&paths, "item-A", "Item A");
+        seed_item_only(&paths, "item-B", "Item B");
+        seed_job_row(&paths, "a-asr", "item-A", JobStatus::Succeeded);
+        seed_job_row(&paths, "a-translate", "item-A", JobStatus::Succeeded);
+        seed_job_row(&paths, "b-asr", "item-B", JobStatus::Succeeded);
+        db::open(&paths)
+            .expect("db")
+            .execute(
+                "UPDATE job SET type=?1 WHERE id='a-translate'",
+                params![JobType::TranslateLocal.as_str()],
+            )
+            .expect("type");
+
+        assert_eq!(list_jobs(&paths, 20, 0, None, None).expect("all").len(), 3);
+        let a_jobs = list_jobs(&paths, 20, 0, Some("item-A"), None).expect("item");
+        assert_eq!(a_jobs.len(), 2);
+        assert!(a_jobs
+            .iter()
+            .all(|job| job.item_id.as_deref() == Some("item-A")));
+        let asr_jobs =
+            list_jobs(&paths, 20, 0, None, Some(JobType::AsrLocal.as_str())).expect("type");
+        assert_eq!(asr_jobs.len(), 2);
+        let a_asr = list_jobs(
+            &paths,
+            20,
+            0,
+            Some("item-A"),
+            Some(JobType::AsrLocal.as_str()),
+        )
+        .expect("both");
+        assert_eq!(a_asr.len(), 1);
+        assert_eq!(a_asr[0].id, "a-asr");
+    }
+
     #[test]
     fn clear_failed_jobs_for_item_only_removes_failed_for_that_item() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -17135,8 +26178,8 @@ EOF
         seed_job_row(&paths, "a-fail-1", "item-A", JobStatus::Failed);
         seed_job_row(&paths, "a-ok-1", "item-A", JobStatus::Succeeded);
 
-        let failed_artifacts = paths.job_artifacts_dir("a-fail-1");
-        let ok_artifacts = paths.job_artifacts_dir("a-ok-1");
+        let failed_artifacts = paths.job_artifacts_dir("a-fail-1", None);
+        let ok_artifacts = paths.job_artifacts_dir("a-ok-1", None);
         std::fs::create_dir_all(&failed_artifacts).expect("failed artifacts dir");
         std::fs::create_dir_all(&ok_artifacts).expect("ok artifacts dir");
         std::fs::write(failed_artifacts.join("trace.txt"), "x").expect("write fail artifact");
@@ -17156,4 +26199,127 @@ EOF
         assert!(!failed_artifacts.exists());
         assert!(ok_artifacts.exists());
     }
+
+    #[test]
+    fn python_missing_module_is_parsed_from_import_errors() {
+        assert_eq!(
+            python_missing_module_from_error(
+                "diarization failed: ModuleNotFoundError: No module named 'resemblyzer'"
+            )
+            .as_deref(),
+            Some("resemblyzer")
+        );
+        assert_eq!(
+            python_missing_module_from_error(
+                "ImportError: cannot import name 'VoiceEncoder' from 'resemblyzer' (unknown location)"
+            )
+            .as_deref(),
+            Some("resemblyzer")
+        );
+        assert_eq!(python_missing_module_from_error("ffmpeg failed"), None);
+    }
+
+    #[test]
+    fn mux_container_override_switches_incompatible_mp4_to_mkv() {
+        assert_eq!(mux_container_override("mp4", Some("vp9")), Some("mkv"));
+        assert_eq!(mux_container_override("mp4", Some("H264")), None);
+        assert_eq!(mux_container_override("mkv", Some("vp9")), None);
+        assert_eq!(mux_container_override("mp4", None), None);
+
+        let p: MuxDubPreviewV1Params =
+            serde_json::from_str(r#"{"item_id":"item-1"}"#).expect("params");
+        assert!(p.allow_container_override);
+    }
+
+    #[test]
+    fn resumable_mix_step_picks_highest_valid_step_wav() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let background = dir.path().join("bg.wav");
+        let inputs: Vec<PathBuf> = (0..4)
+            .map(|i| dir.path().join(format!("tts_{i}.wav")))
+            .collect();
+        write_sine_wav(&background, 44_100, 50);
+        for input in &inputs {
+            write_sine_wav(input, 44_100, 50);
+        }
+        let input_refs: Vec<&Path> = inputs.iter().map(|p| p.as_path()).collect();
+        assert_eq!(
+            resumable_mix_step(dir.path(), &background, &input_refs),
+            None
+        );
+
+        write_sine_wav(&dir.path().join("mix_step_0001.wav"), 44_100, 50);
+        write_sine_wav(&dir.path().join("mix_step_0002.wav"), 44_100, 50);
+        std::fs::write(dir.path().join("mix_step_0003.wav"), b"").expect("empty");
+        std::fs::write(dir.path().join("mix_step_0004.wav"), b"not a wav file").expect("junk");
+        assert_eq!(
+            resumable_mix_step(dir.path(), &background, &input_refs),
+            Some((2, dir.path().join("mix_step_0002.wav")))
+        );
+
+        let truncated = dir.path().join("mix_step_0003.wav");
+        write_sine_wav(&truncated, 44_100, 50);
+        let full_len = std::fs::metadata(&truncated).expect("meta").len();
+        std::fs::OpenOptions::new()
+            .write(true)
+            .open(&truncated)
+            .and_then(|f| f.set_len(full_len / 2))
+            .expect("truncate");
+        assert!(!is_riff_wav_file(&truncated));
+        assert_eq!(
+            resumable_mix_step(dir.path(), &background, &input_refs),
+            Some((2, dir.path().join("mix_step_0002.wav")))
+        );
+
+        let later = filetime::FileTime::from_unix_time(4_000_000_000, 0);
+        filetime::set_file_mtime(&inputs[1], later).expect("mtime");
+        assert_eq!(
+            resumable_mix_step(dir.path(), &background, &input_refs),
+            Some((1, dir.path().join("mix_step_0001.wav")))
+        );
+    }
+
+    #[test]
+    fn atempo_chain_for_factor_splits_into_supported_stages() {
+        assert_eq!(atempo_chain_for_factor(1.0).expect("1.0"), "");
+        assert_eq!(atempo_chain_for_factor(1.25).expect("1.25"), "atempo=1.25");
+        assert_eq!(
+            atempo_chain_for_factor(0.25).expect("0.25"),
+            "atempo=0.5,atempo=0.5"
+        );
+        assert_eq!(
+            atempo_chain_for_factor(2.5).expect("2.5"),
+            "atempo=2.0,atempo=1.25"
+        );
+        assert_eq!(
+            atempo_chain_for_factor(3.0).expect("3.0"),
+            "atempo=2.0,atempo=1.5"
+        );
+        assert_eq!(
+            atempo_chain_for_factor(4.0).expect("4.0"),
+            "atempo=2.0,atempo=2.0"
+        );
+        assert_eq!(
+            atempo_chain_for_factor(0.1).expect("0.1"),
+            "atempo=0.5,atempo=0.5,atempo=0.5,atempo=0.8"
+        );
+        assert_eq!(
+            atempo_chain_for_factor(8.0).expect("8.0"),
+            "atempo=2.0,atempo=2.0,atempo=2.0"
+        );
+        assert!(atempo_chain_for_factor(0.09).is_err());
+        assert!(atempo_chain_for_factor(8.5).is_err());
+        assert!(atempo_chain_for_factor(f32::NAN).is_err());
+    }
+
+    #[test]
+    fn speech_level_cache_key_changes_when_segment_is_rewritten() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let wav = dir.path().join("seg_0001.wav");
+        std::fs::write(&wav, b"short").expect("write");
+        let first = speech_level_cache_key(&wav);
+        assert_eq!(first, speech_level_cache_key(&wav));
+        std::fs::write(&wav, b"longer render").expect("rewrite");
+        assert_ne!(first, speech_level_cache_key(&wav));
+    }
 }
@@ -1,6 +1,8 @@
 pub mod asr;
 pub mod cmd;
+pub mod collections;
 pub mod config;
+pub mod crypto;
 pub mod db;
 pub mod diagnostics;
 mod error;
@@ -9,6 +11,7 @@ pub mod image_batch;
 pub mod instagram_subscriptions;
 pub mod jobs;
 pub mod library;
+pub mod migration;
 pub mod models;
 pub mod paths;
 pub mod persistence;
@@ -19,6 +22,7 @@ pub mod subtitle_tracks;
 pub mod subtitles;
 pub mod tools;
 pub mod translate;
+pub mod util;
 pub mod vendor_patches;
 pub mod voice_backend_adapters;
 pub mod voice_backends;
@@ -32,3 +36,6 @@ pub mod voice_reference_curation;
 pub mod voice_templates;
 
 pub use error::{EngineError, Result};
+
+/// Version of the engine crate, reported to the desktop shell at startup.
+pub const ENGINE_VERSION: &str = env!("CARGO_PKG_VERSION");
@@ -1,14 +1,42 @@
 use crate::ffmpeg;
 use crate::paths::AppPaths;
-use crate::{db, Result};
+use crate::{db, subtitles, util, Result};
 use rusqlite::{params, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 use uuid::Uuid;
 
 const THUMB_CACHE_MAX_BYTES: u64 = 512 * 1024 * 1024;
 const THUMB_CACHE_MAX_AGE_DAYS: i64 = 45;
+/// Leading bytes hashed to recognise the same media downloaded from different URLs.
+const CONTENT_HASH_PREFIX_BYTES: usize = 64 * 1024;
+/// yt-dlp `.info.json` fields copied into `library_item_metadata` after a download.
+const YT_DLP_INFO_METADATA_KEYS: &[&str] = &[
+    "title",
+    "uploader",
+    "upload_date",
+    "description",
+    "view_count",
+    "like_count",
+    "duration",
+    "tags",
+];
+/// Container tags copied into `library_item_metadata` on import.
+const EMBEDDED_METADATA_KEYS: &[&str] = &["artist", "album"];
+/// Extensions treated as audio-only when no probe result says otherwise.
+pub(crate) const AUDIO_ONLY_EXTENSIONS: &[&str] = &[
+    "aac", "aiff", "flac", "m4a", "mp3", "oga", "ogg", "opus", "wav", "wma",
+];
+/// Extensions accepted by local import (audio-only ones are in `AUDIO_ONLY_EXTENSIONS`).
+const VIDEO_IMPORT_EXTENSIONS: &[&str] = &[
+    "avi", "flv", "m2ts", "m4v", "mkv", "mov", "mp4", "mpeg", "mpg", "ts", "webm", "wmv",
+];
+/// Newest `library_item_history` rows kept per item; older ones are pruned on insert.
+pub(crate) const ITEM_HISTORY_MAX_ROWS: usize = 1000;
+/// Values stored in `library_item.source_platform`.
+pub const SOURCE_PLATFORMS: &[&str] = &["youtube", "instagram", "direct_http", "local_file"];
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct LibraryItem {
@@ -25,9 +53,42 @@ pub struct LibraryItem {
     pub video_codec: Option<String>,
     pub audio_codec: Option<String>,
     pub thumbnail_path: Option<String>,
+    pub file_size_bytes: Option<i64>,
+    /// `"video"`, `"audio"` or `"unknown"`, derived from the probed codecs.
+    #[serde(default = "unknown_media_type")]
+    pub media_type: String,
+    /// One of [`SOURCE_PLATFORMS`]; `None` for items imported before the column existed and not
+    /// recognised by the backfill.
+    #[serde(default)]
+    pub source_platform: Option<String>,
+}
+
+fn unknown_media_type() -> String {
+    "unknown".to_string()
+}
+
+/// Cover art shows up as a single-frame video stream in audio files, so it does not make the item
+/// a video.
+fn media_type_for_codecs(video_codec: Option<&str>, audio_codec: Option<&str>) -> String {
+    let has_video = video_codec.is_some_and(|codec| {
+        !matches!(
+            codec.to_ascii_lowercase().as_str(),
+            "mjpeg" | "png" | "bmp" | "gif"
+        )
+    });
+    if has_video {
+        "video".to_string()
+    } else if audio_codec.is_some() {
+        "audio".to_string()
+    } else {
+        unknown_media_type()
+    }
 }
 
 fn library_item_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<LibraryItem> {
+    let video_codec: Option<String> = row.get(10)?;
+    let audio_codec: Option<String> = row.get(11)?;
+    let media_type = media_type_for_codecs(video_codec.as_deref(), audio_codec.as_deref());
     Ok(LibraryItem {
         id: row.get(0)?,
         created_at_ms: row.get(1)?,
@@ -39,12 +100,23 @@ fn library_item_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<LibraryIte
         width: row.get(7)?,
         height: row.get(8)?,
         container: row.get(9)?,
-        video_codec: row.get(10)?,
-        audio_codec: row.get(11)?,
+        video_codec,
+        audio_codec,
         thumbnail_path: row.get(12)?,
+        file_size_bytes: row.get(13)?,
+        media_type,
+        source_platform: row.get(14)?,
     })
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct DownloadedFileImport {
+    #[serde(flatten)]
+    pub item: LibraryItem,
+    /// The file matched an existing item by content hash, which is returned instead.
+    pub was_duplicate: bool,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ThumbnailCacheStatus {
     pub cache_dir: String,
@@ -76,6 +148,18 @@ fn thumbnail_timestamp_seconds(duration_ms: Option<i64>) -> f64 {
     }
 }
 
+/// Whether a frame can be extracted. `has_video` comes from ffprobe when available; without it the
+/// file extension decides.
+fn thumbnail_expected(media_path: &Path, has_video: Option<bool>) -> bool {
+    has_video.unwrap_or_else(|| {
+        let ext = media_path
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        !AUDIO_ONLY_EXTENSIONS.contains(&ext.as_str())
+    })
+}
+
 fn set_item_thumbnail_path(
     paths: &AppPaths,
     item_id: &str,
@@ -111,7 +195,12 @@ pub fn ensure_thumbnail_path(paths: &AppPaths, item_id: &str) -> Result<Option<P
     }
 
     let media_path = PathBuf::from(item.media_path.trim());
-    if !media_path.is_file() {
+    let has_video = if item.video_codec.is_some() {
+        Some(true)
+    } else {
+        item.audio_codec.as_ref().map(|_| false)
+    };
+    if !media_path.is_file() || !thumbnail_expected(&media_path, has_video) {
         if item.thumbnail_path.is_some() {
             set_item_thumbnail_path(paths, item_id, None)?;
         }
@@ -151,10 +240,139 @@ pub fn ensure_thumbnail_path(paths: &AppPaths, item_id: &str) -> Result<Option<P
     }
 }
 
+/// One page of [`list_items`] together with the total item count.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryPage {
+    pub items: Vec<LibraryItem>,
+    pub total_count: usize,
+    pub offset: usize,
+    pub limit: usize,
+}
+
 pub fn list_items(paths: &AppPaths, limit: usize, offset: usize) -> Result<Vec<LibraryItem>> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
+    query_items(&conn, limit, offset)
+}
+
+/// Counts and lists in one deferred transaction so the total matches the page.
+pub fn list_items_page(paths: &AppPaths, limit: usize, offset: usize) -> Result<LibraryPage> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    let tx = conn.unchecked_transaction()?;
+    let total_count = count_items_in(&tx)?;
+    let items = query_items(&tx, limit, offset)?;
+    tx.commit()?;
+
+    Ok(LibraryPage {
+        items,
+        total_count,
+        offset,
+        limit,
+    })
+}
 
+/// One page of items whose `source_platform` equals `platform`.
+pub fn list_items_by_platform(
+    paths: &AppPaths,
+    platform: &str,
+    limit: usize,
+    offset: usize,
+) -> Result<LibraryPage> {
+    let platform = platform.trim();
+    if !SOURCE_PLATFORMS.contains(&platform) {
+        return Err(invalid_input(format!(
+            "unknown source platform: {platform} (expected one of {})",
+            SOURCE_PLATFORMS.join(", ")
+        )));
+    }
+
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    let tx = conn.unchecked_transaction()?;
+    let total_count: i64 = tx.query_row(
+        "SELECT COUNT(*) FROM library_item WHERE source_platform = ?1",
+        params![platform],
+        |row| row.get(0),
+    )?;
+    let items = {
+        let mut stmt = tx.prepare(
+            r#"
+SELECT
+  id,
+  created_at_ms,
+  source_type,
+  source_uri,
+  title,
+  media_path,
+  duration_ms,
+  width,
+  height,
+  container,
+  video_codec,
+  audio_codec,
+  thumbnail_path,
+  file_size_bytes,
+  source_platform
+FROM library_item
+WHERE source_platform = ?1
+ORDER BY created_at_ms DESC
+LIMIT ?2 OFFSET ?3
+"#,
+        )?;
+        let rows = stmt
+            .query_map(
+                params![platform, limit as i64, offset as i64],
+                library_item_from_row,
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows
+    };
+    tx.commit()?;
+
+    Ok(LibraryPage {
+        items,
+        total_count: total_count.max(0) as usize,
+        offset,
+        limit,
+    })
+}
+
+pub fn count_items(paths: &AppPaths) -> Result<usize> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    count_items_in(&conn)
+}
+
+/// Items carrying the tag named `tag` (exact name match, surrounding whitespace ignored).
+pub fn count_items_by_tag(paths: &AppPaths, tag: &str) -> Result<usize> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let count: i64 = conn.query_row(
+        r#"
+SELECT COUNT(DISTINCT lit.item_id)
+FROM library_item_tag lit
+JOIN tag t ON t.id = lit.tag_id
+WHERE t.name = ?1
+"#,
+        params![tag.trim()],
+        |row| row.get(0),
+    )?;
+    Ok(count.max(0) as usize)
+}
+
+fn count_items_in(conn: &rusqlite::Connection) -> Result<usize> {
+    let count: i64 = conn.query_row("SELECT COUNT(*) FROM library_item", [], |row| row.get(0))?;
+    Ok(count.max(0) as usize)
+}
+
+fn query_items(
+    conn: &rusqlite::Connection,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<LibraryItem>> {
     let mut stmt = conn.prepare(
         r#"
 SELECT
@@ -170,7 +388,9 @@ SELECT
   container,
   video_codec,
   audio_codec,
-  thumbnail_path
+  thumbnail_path,
+  file_size_bytes,
+  source_platform
 FROM library_item
 ORDER BY created_at_ms DESC
 LIMIT ?1 OFFSET ?2
@@ -207,7 +427,9 @@ SELECT
   library_item.container,
   library_item.video_codec,
   library_item.audio_codec,
-  library_item.thumbnail_path
+  library_item.thumbnail_path,
+  library_item.file_size_bytes,
+  library_item.source_platform
 FROM localization_workspace_item
 JOIN library_item ON library_item.id = localization_workspace_item.item_id
 ORDER BY localization_workspace_item.selected_at_ms DESC, library_item.created_at_ms DESC
@@ -222,6 +444,66 @@ LIMIT ?1 OFFSET ?2
     Ok(items)
 }
 
+/// One page of the items filed directly in `collection_id` (sub-collections are not included).
+pub fn list_items_in_collection(
+    paths: &AppPaths,
+    collection_id: &str,
+    limit: usize,
+    offset: usize,
+) -> Result<LibraryPage> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    let tx = conn.unchecked_transaction()?;
+    let total_count: i64 = tx.query_row(
+        "SELECT COUNT(*) FROM collection_item WHERE collection_id = ?1",
+        params![collection_id],
+        |row| row.get(0),
+    )?;
+    let items = {
+        let mut stmt = tx.prepare(
+            r#"
+SELECT
+  library_item.id,
+  library_item.created_at_ms,
+  library_item.source_type,
+  library_item.source_uri,
+  library_item.title,
+  library_item.media_path,
+  library_item.duration_ms,
+  library_item.width,
+  library_item.height,
+  library_item.container,
+  library_item.video_codec,
+  library_item.audio_codec,
+  library_item.thumbnail_path,
+  library_item.file_size_bytes,
+  library_item.source_platform
+FROM collection_item
+JOIN library_item ON library_item.id = collection_item.item_id
+WHERE collection_item.collection_id = ?1
+ORDER BY library_item.created_at_ms DESC
+LIMIT ?2 OFFSET ?3
+"#,
+        )?;
+        let rows = stmt
+            .query_map(
+                params![collection_id, limit as i64, offset as i64],
+                library_item_from_row,
+            )?
+            .collect::<rusqlite::Result<Vec<_>>>()?;
+        rows
+    };
+    tx.commit()?;
+
+    Ok(LibraryPage {
+        items,
+        total_count: total_count.max(0) as usize,
+        offset,
+        limit,
+    })
+}
+
 pub fn get_item_by_id(paths: &AppPaths, item_id: &str) -> Result<LibraryItem> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
@@ -241,7 +523,9 @@ SELECT
   container,
   video_codec,
   audio_codec,
-  thumbnail_path
+  thumbnail_path,
+  file_size_bytes,
+  source_platform
 FROM library_item
 WHERE id=?1
 "#,
@@ -250,7 +534,7 @@ WHERE id=?1
     )
     .map_err(|e| match e {
         rusqlite::Error::QueryReturnedNoRows => {
-            crate::EngineError::InstallFailed(format!("library item not found: {item_id}"))
+            crate::EngineError::not_found("library item", item_id)
         }
         other => crate::EngineError::Database(other),
     })
@@ -282,7 +566,9 @@ SELECT
   container,
   video_codec,
   audio_codec,
-  thumbnail_path
+  thumbnail_path,
+  file_size_bytes,
+  source_platform
 FROM library_item
 WHERE media_path=?1
 ORDER BY created_at_ms DESC
@@ -342,10 +628,183 @@ ON CONFLICT(item_id) DO UPDATE SET
     Ok(())
 }
 
-pub fn import_local_file(paths: &AppPaths, input_path: &Path) -> Result<LibraryItem> {
+/// Imports `input_path` and any sidecar subtitles beside it; see [`import_sidecar_subtitles`].
+pub fn import_local_file(paths: &AppPaths, input_path: &Path) -> Result<LocalFileImport> {
     let input_path = input_path.canonicalize()?;
     let source_uri = input_path.to_string_lossy().to_string();
-    import_media_file(paths, &input_path, "local_file", &source_uri, None)
+    let content_hash =
+        util::compute_file_content_hash_prefix(&input_path, CONTENT_HASH_PREFIX_BYTES).ok();
+    let item = import_media_file(
+        paths,
+        &input_path,
+        "local_file",
+        &source_uri,
+        None,
+        content_hash.as_deref(),
+        "local_file",
+    )?;
+    let sidecar_subtitles = import_sidecar_subtitles(paths, &item.id, &input_path);
+    Ok(LocalFileImport {
+        item,
+        sidecar_subtitles,
+    })
+}
+
+/// Subtitle extensions picked up next to imported media.
+const SIDECAR_SUBTITLE_EXTENSIONS: &[&str] = &["srt", "vtt"];
+/// Language recorded for sidecars named without a `{stem}.{lang}.{ext}` language part.
+const SIDECAR_UNKNOWN_LANG: &str = "und";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SidecarSubtitle {
+    pub path: PathBuf,
+    /// Lowercase 2-3 letter code from `{stem}.{lang}.{ext}`; `None` for `{stem}.{ext}`.
+    pub lang: Option<String>,
+    /// `"srt"` or `"vtt"`.
+    pub format: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SidecarSubtitleImport {
+    pub sidecar: SidecarSubtitle,
+    pub track_id: Option<String>,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct LocalFileImport {
+    #[serde(flatten)]
+    pub item: LibraryItem,
+    pub sidecar_subtitles: Vec<SidecarSubtitleImport>,
+}
+
+/// Finds `{stem}.{ext}` and `{stem}.{lang}.{ext}` subtitle files beside `media_path`.
+pub fn discover_sidecar_subtitles(media_path: &Path) -> Vec<SidecarSubtitle> {
+    let (Some(dir), Some(stem)) = (
+        media_path.parent(),
+        media_path.file_stem().and_then(|s| s.to_str()),
+    ) else {
+        return Vec::new();
+    };
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut out: Vec<SidecarSubtitle> = Vec::new();
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if !path.is_file() {
+            continue;
+        }
+        let Some(format) = path
+            .extension()
+            .and_then(|e| e.to_str())
+            .map(str::to_ascii_lowercase)
+            .filter(|ext| SIDECAR_SUBTITLE_EXTENSIONS.contains(&ext.as_str()))
+        else {
+            continue;
+        };
+        let Some(sidecar_stem) = path.file_stem().and_then(|s| s.to_str()) else {
+            continue;
+        };
+        let lang = if sidecar_stem == stem {
+            None
+        } else {
+            let Some(lang) = sidecar_stem
+                .strip_prefix(stem)
+                .and_then(|rest| rest.strip_prefix('.'))
+                .map(str::to_ascii_lowercase)
+                .filter(|lang| {
+                    (2..=3).contains(&lang.len()) && lang.chars().all(|c| c.is_ascii_lowercase())
+                })
+            else {
+                continue;
+            };
+            Some(lang)
+        };
+        out.push(SidecarSubtitle { path, lang, format });
+    }
+    out.sort_by(|a, b| a.path.cmp(&b.path));
+    out
+}
+
+/// Imports each sidecar beside `media_path` as a `source` subtitle track of `item_id`. Failures
+/// are reported per sidecar and never fail the media import.
+fn import_sidecar_subtitles(
+    paths: &AppPaths,
+    item_id: &str,
+    media_path: &Path,
+) -> Vec<SidecarSubtitleImport> {
+    discover_sidecar_subtitles(media_path)
+        .into_iter()
+        .map(
+            |sidecar| match import_sidecar_subtitle(paths, item_id, &sidecar) {
+                Ok(track_id) => SidecarSubtitleImport {
+                    sidecar,
+                    track_id: Some(track_id),
+                    error: None,
+                },
+                Err(err) => SidecarSubtitleImport {
+                    sidecar,
+                    track_id: None,
+                    error: Some(err.to_string()),
+                },
+            },
+        )
+        .collect()
+}
+
+fn import_sidecar_subtitle(
+    paths: &AppPaths,
+    item_id: &str,
+    sidecar: &SidecarSubtitle,
+) -> Result<String> {
+    let bytes = std::fs::read(&sidecar.path)?;
+    let text = String::from_utf8_lossy(&bytes);
+    let lang = sidecar.lang.as_deref().unwrap_or(SIDECAR_UNKNOWN_LANG);
+    let doc = if sidecar.format == "vtt" {
+        subtitles::parse_vtt(&text, lang)?
+    } else {
+        subtitles::parse_srt(&text, lang)?
+    };
+
+    let track_id = Uuid::new_v4().to_string();
+    let out_dir = paths.derived_item_dir(item_id).join("sidecar_subtitles");
+    let json_path = out_dir.join(format!("{track_id}.json"));
+    subtitles::write_artifacts(
+        &doc,
+        &json_path,
+        &out_dir.join(format!("{track_id}.srt")),
+        &out_dir.join(format!("{track_id}.vtt")),
+    )?;
+
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    conn.execute(
+        r#"
+INSERT INTO subtitle_track (
+  id,
+  item_id,
+  kind,
+  lang,
+  format,
+  path,
+  created_by,
+  version
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+"#,
+        params![
+            &track_id,
+            item_id,
+            &doc.kind,
+            &doc.lang,
+            "ytfetch_subtitle_json_v1",
+            json_path.to_string_lossy().to_string(),
+            "sidecar:import",
+            1_i64
+        ],
+    )?;
+    Ok(track_id)
 }
 
 pub fn import_downloaded_file(
@@ -355,15 +814,90 @@ pub fn import_downloaded_file(
     rights_note: &str,
     provider: &str,
     attested_at_ms: i64,
-) -> Result<LibraryItem> {
+) -> Result<DownloadedFileImport> {
     let downloaded_path = downloaded_path.canonicalize()?;
     let source_url = source_url.trim();
     let rights_note = rights_note.trim();
     let provider = provider.trim();
-    let item = import_media_file(paths, &downloaded_path, "url_direct", source_url, None)?;
 
+    let content_hash =
+        util::compute_file_content_hash_prefix(&downloaded_path, CONTENT_HASH_PREFIX_BYTES)?;
+    let file_size_bytes = std::fs::metadata(&downloaded_path)?.len() as i64;
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
+    let candidates: Vec<(String, String)> = {
+        let mut stmt = conn.prepare(
+            "SELECT id, media_path FROM library_item WHERE content_hash=?1 AND file_size_bytes=?2 ORDER BY created_at_ms ASC",
+        )?;
+        let rows = stmt.query_map(params![&content_hash, file_size_bytes], |row| {
+            Ok((row.get(0)?, row.get(1)?))
+        })?;
+        rows.collect::<std::result::Result<_, _>>()?
+    };
+    // The hash only covers a prefix, so a match is confirmed byte-for-byte before the fresh
+    // download is discarded.
+    let mut duplicate: Option<(String, bool)> = None;
+    for (id, media_path) in candidates {
+        let Ok(media_path) = Path::new(&media_path).canonicalize() else {
+            continue;
+        };
+        if media_path == downloaded_path {
+            duplicate = Some((id, true));
+            break;
+        }
+        if util::files_have_same_contents(&media_path, &downloaded_path)? {
+            duplicate = Some((id, false));
+            break;
+        }
+    }
+    if let Some((existing_id, is_item_media)) = duplicate {
+        let item = get_item_by_id(paths, &existing_id)?;
+        if !is_item_media {
+            let _ = std::fs::remove_file(&downloaded_path);
+        }
+        conn.execute(
+            r#"
+INSERT OR IGNORE INTO ingest_provenance_source (
+  item_id,
+  provider,
+  source_url,
+  rights_note,
+  attested_at_ms,
+  created_at_ms
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+"#,
+            params![
+                &item.id,
+                provider,
+                source_url,
+                rights_note,
+                attested_at_ms,
+                now_ms(),
+            ],
+        )?;
+        record_item_history(
+            paths,
+            &item.id,
+            "duplicate_download",
+            None,
+            None,
+            serde_json::json!({ "source_url": source_url, "provider": provider }),
+        )?;
+        return Ok(DownloadedFileImport {
+            item,
+            was_duplicate: true,
+        });
+    }
+
+    let item = import_media_file(
+        paths,
+        &downloaded_path,
+        "url_direct",
+        source_url,
+        None,
+        Some(&content_hash),
+        classify_source_platform(provider, source_url),
+    )?;
     conn.execute(
         r#"
 INSERT INTO ingest_provenance (
@@ -385,7 +919,29 @@ INSERT INTO ingest_provenance (
         ],
     )?;
 
-    Ok(item)
+    Ok(DownloadedFileImport {
+        item,
+        was_duplicate: false,
+    })
+}
+
+/// Source URLs an item was downloaded from: the original provenance first, then any later
+/// downloads that turned out to be duplicates.
+pub fn list_item_source_urls(paths: &AppPaths, item_id: &str) -> Result<Vec<String>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut stmt = conn.prepare(
+        r#"
+SELECT source_url FROM (
+  SELECT source_url, 0 AS ord, created_at_ms FROM ingest_provenance WHERE item_id=?1
+  UNION ALL
+  SELECT source_url, 1 AS ord, created_at_ms FROM ingest_provenance_source WHERE item_id=?1
+)
+ORDER BY ord ASC, created_at_ms ASC
+"#,
+    )?;
+    let rows = stmt.query_map([item_id], |row| row.get::<_, String>(0))?;
+    Ok(rows.collect::<std::result::Result<_, _>>()?)
 }
 
 fn import_media_file(
@@ -394,12 +950,19 @@ fn import_media_file(
     source_type: &str,
     source_uri: &str,
     title_hint: Option<&str>,
+    content_hash: Option<&str>,
+    source_platform: &str,
 ) -> Result<LibraryItem> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
 
     let id = Uuid::new_v4().to_string();
     let created_at_ms = now_ms();
+
+    // Import should remain possible even when ffmpeg/ffprobe is not installed. Metadata and
+    // thumbnails are best-effort.
+    let probe = probe_best_effort(paths, media_path)?;
+
     let title = title_hint
         .and_then(|s| {
             let trimmed = s.trim();
@@ -409,6 +972,7 @@ fn import_media_file(
                 Some(trimmed.to_string())
             }
         })
+        .or_else(|| embedded_title(probe.format_tags.get("title"), media_path))
         .or_else(|| {
             media_path
                 .file_stem()
@@ -421,39 +985,25 @@ fn import_media_file(
     let derived_dir = paths.derived_item_dir(&id);
     std::fs::create_dir_all(&derived_dir)?;
 
-    // Import should remain possible even when ffmpeg/ffprobe is not installed. Metadata and
-    // thumbnails are best-effort.
-    let probe = match ffmpeg::probe(paths, media_path) {
-        Ok(v) => v,
-        Err(crate::EngineError::ExternalToolMissing { .. }) => ffmpeg::MediaProbe {
-            duration_ms: None,
-            container: None,
-            video_codec: None,
-            audio_codec: None,
-            width: None,
-            height: None,
-        },
-        Err(crate::EngineError::ExternalToolFailed { .. }) => ffmpeg::MediaProbe {
-            duration_ms: None,
-            container: None,
-            video_codec: None,
-            audio_codec: None,
-            width: None,
-            height: None,
-        },
-        Err(e) => return Err(e),
-    };
+    let file_size_bytes = std::fs::metadata(media_path)
+        .ok()
+        .map(|meta| meta.len() as i64);
 
     let thumbnail_path = thumbnail_cache_path(paths, &id);
     let timestamp_seconds = thumbnail_timestamp_seconds(probe.duration_ms);
 
-    let thumbnail_path_str =
+    let probed_has_video = probe.container.is_some().then_some(probe.has_video);
+
+    let thumbnail_path_str = if thumbnail_expected(media_path, probed_has_video) {
         match ffmpeg::generate_thumbnail(paths, media_path, &thumbnail_path, timestamp_seconds) {
             Ok(()) => Some(thumbnail_path.to_string_lossy().to_string()),
             Err(crate::EngineError::ExternalToolMissing { .. }) => None,
             Err(crate::EngineError::ExternalToolFailed { .. }) => None,
             Err(_) => None,
-        };
+        }
+    } else {
+        None
+    };
     prune_thumbnail_cache(paths, THUMB_CACHE_MAX_BYTES, THUMB_CACHE_MAX_AGE_DAYS);
 
     conn.execute(
@@ -471,8 +1021,11 @@ INSERT INTO library_item (
   container,
   video_codec,
   audio_codec,
-  thumbnail_path
-) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)
+  thumbnail_path,
+  file_size_bytes,
+  content_hash,
+  source_platform
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)
 "#,
         params![
             &id,
@@ -488,9 +1041,22 @@ INSERT INTO library_item (
             probe.video_codec,
             probe.audio_codec,
             thumbnail_path_str,
+            file_size_bytes,
+            content_hash,
+            source_platform,
         ],
     )?;
+    for key in EMBEDDED_METADATA_KEYS {
+        if let Some(value) = probe.format_tags.get(*key) {
+            conn.execute(
+                "INSERT OR REPLACE INTO library_item_metadata (item_id, key, value) VALUES (?1, ?2, ?3)",
+                params![&id, key, value],
+            )?;
+        }
+    }
 
+    let media_type =
+        media_type_for_codecs(probe.video_codec.as_deref(), probe.audio_codec.as_deref());
     Ok(LibraryItem {
         id,
         created_at_ms,
@@ -505,9 +1071,298 @@ INSERT INTO library_item (
         video_codec: probe.video_codec,
         audio_codec: probe.audio_codec,
         thumbnail_path: thumbnail_path_str,
+        file_size_bytes,
+        media_type,
+        source_platform: Some(source_platform.to_string()),
+    })
+}
+
+/// Maps a download to a [`SOURCE_PLATFORMS`] value. The URL host wins; the provider only decides
+/// when the URL has no usable host.
+pub(crate) fn classify_source_platform(provider: &str, url: &str) -> &'static str {
+    let host = url::Url::parse(url.trim())
+        .ok()
+        .and_then(|parsed| parsed.host_str().map(|host| host.to_ascii_lowercase()));
+    let host_matches = |domain: &str| {
+        host.as_deref()
+            .is_some_and(|host| host == domain || host.ends_with(&format!(".{domain}")))
+    };
+    if host_matches("youtube.com") || host_matches("youtu.be") {
+        "youtube"
+    } else if host_matches("instagram.com") {
+        "instagram"
+    } else if host.is_none() && provider.trim().starts_with("youtube") {
+        "youtube"
+    } else {
+        "direct_http"
+    }
+}
+
+/// Uses an embedded `title` tag unless it is empty or just repeats a file name.
+fn embedded_title(tag: Option<&String>, media_path: &Path) -> Option<String> {
+    let title = tag?.trim();
+    if title.is_empty() {
+        return None;
+    }
+    let file_name = media_path.file_name().and_then(|s| s.to_str());
+    let file_stem = media_path.file_stem().and_then(|s| s.to_str());
+    if Some(title) == file_name || Some(title) == file_stem {
+        return None;
+    }
+    let looks_like_file_name = Path::new(title)
+        .extension()
+        .and_then(|e| e.to_str())
+        .is_some_and(|ext| {
+            let ext = ext.to_ascii_lowercase();
+            AUDIO_ONLY_EXTENSIONS.contains(&ext.as_str())
+                || matches!(ext.as_str(), "mp4" | "mkv" | "mov" | "webm" | "avi" | "m4v")
+        });
+    if looks_like_file_name {
+        return None;
+    }
+    Some(title.to_string())
+}
+
+/// Metadata stored per item: container tags from import and yt-dlp `.info.json` fields.
+pub fn get_item_metadata(paths: &AppPaths, item_id: &str) -> Result<HashMap<String, String>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut stmt = conn.prepare("SELECT key, value FROM library_item_metadata WHERE item_id=?1")?;
+    let rows = stmt
+        .query_map(params![item_id], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<HashMap<_, _>>>()?;
+    Ok(rows)
+}
+
+/// Summary of [`import_yt_dlp_info_json`] for the download log.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct YtDlpInfoImport {
+    pub title: Option<String>,
+    pub uploader: Option<String>,
+    pub upload_date: Option<String>,
+    pub title_updated: bool,
+}
+
+/// Stores selected `.info.json` fields as item metadata and replaces an item title that is just
+/// the media file name. Returns `None` when the file does not exist.
+pub fn import_yt_dlp_info_json(
+    paths: &AppPaths,
+    item_id: &str,
+    info_json_path: &Path,
+) -> Result<Option<YtDlpInfoImport>> {
+    let Ok(raw) = std::fs::read_to_string(info_json_path) else {
+        return Ok(None);
+    };
+    let info: serde_json::Value = serde_json::from_str(&raw)?;
+    let mut fields: HashMap<&str, String> = HashMap::new();
+    for key in YT_DLP_INFO_METADATA_KEYS {
+        let value = match info.get(*key) {
+            Some(serde_json::Value::String(v)) => v.trim().to_string(),
+            Some(serde_json::Value::Number(v)) => v.to_string(),
+            Some(serde_json::Value::Array(values)) => values
+                .iter()
+                .filter_map(|v| v.as_str().map(str::trim))
+                .filter(|v| !v.is_empty())
+                .collect::<Vec<_>>()
+                .join(", "),
+            _ => continue,
+        };
+        if !value.is_empty() {
+            fields.insert(key, value);
+        }
+    }
+
+    let item = get_item_by_id(paths, item_id)?;
+    let media_path = Path::new(&item.media_path);
+    let title_is_file_name = [media_path.file_stem(), media_path.file_name()]
+        .iter()
+        .any(|name| name.and_then(|n| n.to_str()) == Some(item.title.as_str()));
+    let new_title = fields
+        .get("title")
+        .filter(|title| title_is_file_name && **title != item.title);
+
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    for (key, value) in &fields {
+        conn.execute(
+            "INSERT OR REPLACE INTO library_item_metadata (item_id, key, value) VALUES (?1, ?2, ?3)",
+            params![item_id, key, value],
+        )?;
+    }
+    if let Some(title) = new_title {
+        conn.execute(
+            "UPDATE library_item SET title=?1 WHERE id=?2",
+            params![title, item_id],
+        )?;
+    }
+
+    Ok(Some(YtDlpInfoImport {
+        title: fields.get("title").cloned(),
+        uploader: fields.get("uploader").cloned(),
+        upload_date: fields.get("upload_date").cloned(),
+        title_updated: new_title.is_some(),
+    }))
+}
+
+/// Result of `validate_importable_media`. `probed` is false when ffprobe is not installed, in
+/// which case only the file and its extension were checked.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MediaValidationReport {
+    pub has_video: bool,
+    pub has_audio: bool,
+    pub duration_ms: Option<i64>,
+    pub codec: Option<String>,
+    pub probed: bool,
+}
+
+pub(crate) fn supported_import_extensions() -> Vec<&'static str> {
+    let mut exts: Vec<&str> = AUDIO_ONLY_EXTENSIONS
+        .iter()
+        .chain(VIDEO_IMPORT_EXTENSIONS)
+        .copied()
+        .collect();
+    exts.sort_unstable();
+    exts
+}
+
+fn invalid_input(message: String) -> crate::EngineError {
+    crate::EngineError::InvalidInput { message }
+}
+
+/// Checks that `file_path` is a non-empty file with a supported extension and, when ffprobe is
+/// available, at least one audio or video stream.
+pub fn validate_importable_media(
+    paths: &AppPaths,
+    file_path: &Path,
+) -> Result<MediaValidationReport> {
+    let display = file_path.display();
+    let meta = std::fs::metadata(file_path)
+        .map_err(|_| invalid_input(format!("file not found: {display}")))?;
+    if !meta.is_file() {
+        return Err(invalid_input(format!("not a file: {display}")));
+    }
+    if meta.len() == 0 {
+        return Err(invalid_input(format!("file is empty: {display}")));
+    }
+
+    let ext = file_path
+        .extension()
+        .map(|e| e.to_string_lossy().to_ascii_lowercase())
+        .unwrap_or_default();
+    let supported = supported_import_extensions();
+    if !supported.contains(&ext.as_str()) {
+        let shown = if ext.is_empty() {
+            "(no extension)".to_string()
+        } else {
+            format!(".{ext}")
+        };
+        return Err(invalid_input(format!(
+            "unsupported file type {shown}; supported types: {}",
+            supported.join(", ")
+        )));
+    }
+
+    let probe = match ffmpeg::probe(paths, file_path) {
+        Ok(v) => v,
+        Err(crate::EngineError::ExternalToolMissing { .. }) => {
+            return Ok(MediaValidationReport {
+                has_video: false,
+                has_audio: false,
+                duration_ms: None,
+                codec: None,
+                probed: false,
+            });
+        }
+        Err(crate::EngineError::ExternalToolFailed { stderr, .. }) => {
+            return Err(invalid_input(format!(
+                "could not read media from {display}: {}",
+                stderr.lines().next().unwrap_or("unrecognized format")
+            )));
+        }
+        Err(e) => return Err(e),
+    };
+    if !probe.has_video && !probe.has_audio {
+        return Err(invalid_input(format!(
+            "no audio or video stream found in {display}"
+        )));
+    }
+    Ok(MediaValidationReport {
+        has_video: probe.has_video,
+        has_audio: probe.has_audio,
+        duration_ms: probe.duration_ms,
+        codec: probe.video_codec.or(probe.audio_codec),
+        probed: true,
     })
 }
 
+fn probe_best_effort(paths: &AppPaths, media_path: &Path) -> Result<ffmpeg::MediaProbe> {
+    match ffmpeg::probe(paths, media_path) {
+        Ok(v) => Ok(v),
+        Err(crate::EngineError::ExternalToolMissing { .. }) => Ok(ffmpeg::MediaProbe::empty()),
+        Err(crate::EngineError::ExternalToolFailed { .. }) => Ok(ffmpeg::MediaProbe::empty()),
+        Err(e) => Err(e),
+    }
+}
+
+/// Re-probes items with missing duration or file size and fills in whatever can be recovered.
+/// Existing values are never overwritten. Returns the number of items updated.
+pub fn repair_item_metadata(paths: &AppPaths) -> Result<usize> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    let mut stmt = conn.prepare(
+        "SELECT id, media_path FROM library_item WHERE duration_ms IS NULL OR file_size_bytes IS NULL",
+    )?;
+    let rows = stmt
+        .query_map([], |row| {
+            Ok((row.get::<_, String>(0)?, row.get::<_, String>(1)?))
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    let mut repaired = 0usize;
+    for (item_id, media_path) in rows {
+        let media_path = PathBuf::from(media_path);
+        let Ok(meta) = std::fs::metadata(&media_path) else {
+            continue;
+        };
+        let probe = probe_best_effort(paths, &media_path)?;
+        let changed = conn.execute(
+            r#"
+UPDATE library_item SET
+  file_size_bytes = COALESCE(file_size_bytes, ?2),
+  duration_ms = COALESCE(duration_ms, ?3),
+  width = COALESCE(width, ?4),
+  height = COALESCE(height, ?5),
+  container = COALESCE(container, ?6),
+  video_codec = COALESCE(video_codec, ?7),
+  audio_codec = COALESCE(audio_codec, ?8)
+WHERE id = ?1
+  AND (
+    (file_size_bytes IS NULL AND ?2 IS NOT NULL)
+    OR (duration_ms IS NULL AND ?3 IS NOT NULL)
+    OR (width IS NULL AND ?4 IS NOT NULL)
+    OR (height IS NULL AND ?5 IS NOT NULL)
+  )
+"#,
+            params![
+                item_id,
+                meta.len() as i64,
+                probe.duration_ms,
+                probe.width,
+                probe.height,
+                probe.container,
+                probe.video_codec,
+                probe.audio_codec,
+            ],
+        )?;
+        repaired += changed;
+    }
+
+    Ok(repaired)
+}
+
 fn now_ms() -> i64 {
     SystemTime::now()
         .duration_since(UNIX_EPOCH)
@@ -515,6 +1370,83 @@ fn now_ms() -> i64 {
         .as_millis() as i64
 }
 
+/// One audit entry of an operation performed on a library item.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LibraryItemHistoryRow {
+    pub id: String,
+    pub item_id: String,
+    pub event_type: String,
+    pub job_id: Option<String>,
+    pub track_id: Option<String>,
+    pub details: serde_json::Value,
+    pub created_at_ms: i64,
+}
+
+/// Appends a history entry for `item_id` and prunes it to `ITEM_HISTORY_MAX_ROWS` entries.
+pub fn record_item_history(
+    paths: &AppPaths,
+    item_id: &str,
+    event_type: &str,
+    job_id: Option<&str>,
+    track_id: Option<&str>,
+    details: serde_json::Value,
+) -> Result<()> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    conn.execute(
+        "INSERT INTO library_item_history (id, item_id, event_type, job_id, track_id, details_json, created_at_ms)
+         VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+        params![
+            Uuid::new_v4().to_string(),
+            item_id,
+            event_type,
+            job_id,
+            track_id,
+            details.to_string(),
+            now_ms(),
+        ],
+    )?;
+    conn.execute(
+        "DELETE FROM library_item_history WHERE item_id=?1 AND id NOT IN (
+           SELECT id FROM library_item_history WHERE item_id=?1
+           ORDER BY created_at_ms DESC, rowid DESC LIMIT ?2
+         )",
+        params![item_id, ITEM_HISTORY_MAX_ROWS as i64],
+    )?;
+    Ok(())
+}
+
+/// History of `item_id`, newest first.
+pub fn list_item_history(
+    paths: &AppPaths,
+    item_id: &str,
+    limit: usize,
+    offset: usize,
+) -> Result<Vec<LibraryItemHistoryRow>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut stmt = conn.prepare(
+        "SELECT id, item_id, event_type, job_id, track_id, details_json, created_at_ms
+         FROM library_item_history WHERE item_id=?1
+         ORDER BY created_at_ms DESC, rowid DESC LIMIT ?2 OFFSET ?3",
+    )?;
+    let rows = stmt
+        .query_map(params![item_id, limit as i64, offset as i64], |row| {
+            Ok(LibraryItemHistoryRow {
+                id: row.get(0)?,
+                item_id: row.get(1)?,
+                event_type: row.get(2)?,
+                job_id: row.get(3)?,
+                track_id: row.get(4)?,
+                details: serde_json::from_str(&row.get::<_, String>(5)?)
+                    .unwrap_or(serde_json::Value::Null),
+                created_at_ms: row.get(6)?,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+    Ok(rows)
+}
+
 pub fn derived_dir_for_item(paths: &AppPaths, item_id: &str) -> PathBuf {
     paths.derived_item_dir(item_id)
 }
@@ -678,6 +1610,92 @@ mod tests {
     use filetime::{set_file_mtime, FileTime};
     use rusqlite::params;
 
+    #[test]
+    fn validate_importable_media_rejects_missing_empty_and_unsupported_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().join("app"));
+        let is_invalid = |path: &Path| {
+            matches!(
+                validate_importable_media(&paths, path),
+                Err(crate::EngineError::InvalidInput { .. })
+            )
+        };
+
+        assert!(is_invalid(&dir.path().join("missing.mp4")));
+        let empty = dir.path().join("empty.mp4");
+        std::fs::write(&empty, b"").expect("write");
+        assert!(is_invalid(&empty));
+
+        let notes = dir.path().join("notes.txt");
+        std::fs::write(&notes, b"hello").expect("write");
+        let err = validate_importable_media(&paths, &notes).expect_err("unsupported");
+        let message = err.to_string();
+        assert!(message.contains("unsupported file type .txt"), "{message}");
+        assert!(
+            message.contains("mp4") && message.contains("wav"),
+            "{message}"
+        );
+    }
+
+    #[test]
+    fn sidecar_subtitles_are_discovered_and_imported_as_tracks() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().join("app"));
+        db::ensure_schema(&paths).expect("schema");
+        let media_dir = dir.path().join("media");
+        std::fs::create_dir_all(&media_dir).expect("mkdir");
+        let media = media_dir.join("movie.mp4");
+        std::fs::write(&media, b"not really media").expect("write");
+        std::fs::write(
+            media_dir.join("movie.srt"),
+            "1\r\n00:00:01,000 --> 00:00:02,500\r\nHello\r\n\r\n",
+        )
+        .expect("write");
+        std::fs::write(
+            media_dir.join("movie.FR.vtt"),
+            "WEBVTT\n\n00:03.000 --> 00:04.000 align:start\nBonjour\nmonde\n",
+        )
+        .expect("write");
+        std::fs::write(media_dir.join("movie.english.srt"), "x").expect("write");
+        std::fs::write(media_dir.join("other.srt"), "x").expect("write");
+
+        let found = discover_sidecar_subtitles(&media);
+        let summary: Vec<(String, Option<String>)> = found
+            .iter()
+            .map(|s| (s.format.clone(), s.lang.clone()))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("vtt".to_string(), Some("fr".to_string())),
+                ("srt".to_string(), None),
+            ]
+        );
+
+        let imported = import_local_file(&paths, &media).expect("import");
+        assert_eq!(imported.sidecar_subtitles.len(), 2);
+        assert!(
+            imported.sidecar_subtitles.iter().all(|i| i.error.is_none()),
+            "{imported:?}"
+        );
+        let tracks =
+            crate::subtitle_tracks::list_tracks(&paths, &imported.item.id).expect("tracks");
+        assert_eq!(tracks.len(), 2);
+        assert!(tracks.iter().all(|t| t.created_by == "sidecar:import"));
+        let french = tracks.iter().find(|t| t.lang == "fr").expect("fr track");
+        let doc = crate::subtitle_tracks::load_document(&paths, &french.id).expect("doc");
+        assert_eq!(doc.segments[0].start_ms, 3000);
+        assert_eq!(doc.segments[0].text, "Bonjour\nmonde");
+    }
+
+    #[test]
+    fn thumbnail_expected_prefers_probe_over_extension() {
+        assert!(!thumbnail_expected(Path::new("a/song.MP3"), None));
+        assert!(thumbnail_expected(Path::new("a/song.mp3"), Some(true)));
+        assert!(thumbnail_expected(Path::new("a/clip.mkv"), None));
+        assert!(!thumbnail_expected(Path::new("a/clip.mkv"), Some(false)));
+    }
+
     #[test]
     fn thumbnail_cache_file_name_is_sanitized() {
         let key = thumbnail_cache_file_name("  ab/cd:ef?gh  ");
@@ -734,6 +1752,47 @@ mod tests {
         assert!(fresh.exists(), "newest file should remain");
     }
 
+    #[test]
+    fn list_items_page_reports_total_and_tag_counts() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+
+        let conn = db::open(&paths).expect("db");
+        for idx in 0..3_i64 {
+            conn.execute(
+                r#"
+INSERT INTO library_item (id, created_at_ms, source_type, source_uri, title, media_path)
+VALUES (?1, ?2, 'local_file', ?3, ?4, ?3)
+"#,
+                params![
+                    format!("item-{idx}"),
+                    idx,
+                    format!("D:/media/{idx}.mp4"),
+                    "Clip"
+                ],
+            )
+            .expect("insert item");
+        }
+        conn.execute("INSERT INTO tag (id, name) VALUES ('t1', 'music')", [])
+            .expect("insert tag");
+        conn.execute(
+            "INSERT INTO library_item_tag (item_id, tag_id) VALUES ('item-0', 't1'), ('item-2', 't1')",
+            [],
+        )
+        .expect("tag items");
+        drop(conn);
+
+        let page = list_items_page(&paths, 2, 1).expect("page");
+        assert_eq!(page.total_count, 3);
+        assert_eq!((page.offset, page.limit), (1, 2));
+        assert_eq!(page.items.len(), 2);
+        assert_eq!(page.items[0].id, "item-1");
+        assert_eq!(count_items(&paths).expect("count"), 3);
+        assert_eq!(count_items_by_tag(&paths, " music ").expect("tag count"), 2);
+        assert_eq!(count_items_by_tag(&paths, "none").expect("tag count"), 0);
+    }
+
     #[test]
     fn ensure_thumbnail_path_reuses_cached_file_and_updates_db() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -785,6 +1844,262 @@ INSERT INTO library_item (
         );
     }
 
+    #[test]
+    fn import_downloaded_file_reuses_item_with_matching_content_hash() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+        db::ensure_schema(&paths).expect("schema");
+
+        let first_path = dir.path().join("direct.mp4");
+        let second_path = dir.path().join("yt-dlp.mp4");
+        std::fs::write(&first_path, b"same-bytes").expect("first");
+        std::fs::write(&second_path, b"same-bytes").expect("second");
+
+        let first = import_downloaded_file(&paths, &first_path, "https://a/x.mp4", "", "direct", 1)
+            .expect("first import");
+        assert!(!first.was_duplicate);
+        let second =
+            import_downloaded_file(&paths, &second_path, "https://b/watch", "", "yt-dlp", 2)
+                .expect("second import");
+        assert!(second.was_duplicate);
+        assert_eq!(second.item.id, first.item.id);
+        assert!(!second_path.exists());
+        assert!(first_path.exists());
+        let history = list_item_history(&paths, &first.item.id, 10, 0).expect("history");
+        assert!(history
+            .iter()
+            .any(|row| row.event_type == "duplicate_download"
+                && row.details["source_url"] == "https://b/watch"));
+
+        assert_eq!(
+            list_item_source_urls(&paths, &first.item.id).expect("sources"),
+            vec!["https://a/x.mp4".to_string(), "https://b/watch".to_string()]
+        );
+
+        let conn = db::open(&paths).expect("db");
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM library_item", [], |row| row.get(0))
+            .expect("count");
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn import_downloaded_file_keeps_file_when_only_the_hashed_prefix_matches() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+        db::ensure_schema(&paths).expect("schema");
+
+        let prefix = vec![7u8; CONTENT_HASH_PREFIX_BYTES];
+        let first_path = dir.path().join("first.mp4");
+        let second_path = dir.path().join("second.mp4");
+        std::fs::write(&first_path, [prefix.as_slice(), b"tail-a"].concat()).expect("first");
+        std::fs::write(&second_path, [prefix.as_slice(), b"tail-b"].concat()).expect("second");
+
+        let first = import_downloaded_file(&paths, &first_path, "https://a/1", "", "direct", 1)
+            .expect("first import");
+        let second = import_downloaded_file(&paths, &second_path, "https://a/2", "", "direct", 2)
+            .expect("second import");
+        assert!(!second.was_duplicate);
+        assert_ne!(second.item.id, first.item.id);
+        assert!(second_path.exists());
+    }
+
+    #[test]
+    fn yt_dlp_info_json_fills_metadata_and_replaces_file_name_title() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+        db::ensure_schema(&paths).expect("schema");
+        let media = dir.path().join("My_Clip-abc123.mp4");
+        std::fs::write(&media, b"clip").expect("media");
+        let item = import_downloaded_file(&paths, &media, "https://youtu.be/abc123", "", "yt", 1)
+            .expect("import")
+            .item;
+        assert_eq!(item.title, "My_Clip-abc123");
+
+        let info = dir.path().join("My_Clip-abc123.info.json");
+        assert_eq!(
+            import_yt_dlp_info_json(&paths, &item.id, &info).expect("missing"),
+            None
+        );
+        std::fs::write(
+            &info,
+            r#"{"title":"My Clip","uploader":"Chan","upload_date":"20240102","view_count":42,"tags":["a","b"],"description":null}"#,
+        )
+        .expect("info");
+        let imported = import_yt_dlp_info_json(&paths, &item.id, &info)
+            .expect("import info")
+            .expect("info present");
+        assert!(imported.title_updated);
+        assert_eq!(imported.uploader.as_deref(), Some("Chan"));
+        assert_eq!(
+            get_item_by_id(&paths, &item.id).expect("item").title,
+            "My Clip"
+        );
+        let metadata = get_item_metadata(&paths, &item.id).expect("metadata");
+        assert_eq!(metadata.get("view_count").map(String::as_str), Some("42"));
+        assert_eq!(metadata.get("tags").map(String::as_str), Some("a, b"));
+        assert!(!metadata.contains_key("description"));
+
+        std::fs::write(&info, r#"{"title":"Other"}"#).expect("info");
+        let again = import_yt_dlp_info_json(&paths, &item.id, &info)
+            .expect("import info")
+            .expect("info present");
+        assert!(!again.title_updated);
+    }
+
+    #[test]
+    fn downloaded_items_are_listed_by_source_platform() {
+        assert_eq!(
+            classify_source_platform("direct_http_v1", "https://www.youtube.com/watch?v=x"),
+            "youtube"
+        );
+        assert_eq!(
+            classify_source_platform("youtube_yt_dlp_v1", "https://youtu.be/x"),
+            "youtube"
+        );
+        assert_eq!(
+            classify_source_platform("youtube_yt_dlp_v1", "https://www.instagram.com/p/x/"),
+            "instagram"
+        );
+        assert_eq!(
+            classify_source_platform("direct_http_v1", "https://notyoutube.com/a.mp4"),
+            "direct_http"
+        );
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("ensure dirs");
+        let clip = dir.path().join("clip.mp4");
+        std::fs::write(&clip, b"youtube-bytes").expect("clip");
+        let imported = import_downloaded_file(
+            &paths,
+            &clip,
+            "https://www.youtube.com/watch?v=x",
+            "",
+            "youtube_yt_dlp_v1",
+            1,
+        )
+        .expect("import");
+        assert_eq!(imported.item.source_platform.as_deref(), Some("youtube"));
+
+        let page = list_items_by_platform(&paths, "youtube", 10, 0).expect("youtube page");
+        assert_eq!(page.total_count, 1);
+        assert_eq!(page.items[0].id, imported.item.id);
+        assert_eq!(page.items[0].source_platform.as_deref(), Some("youtube"));
+        assert_eq!(
+            list_items_by_platform(&paths, "instagram", 10, 0)
+                .expect("instagram page")
+                .total_count,
+            0
+        );
+        assert!(list_items_by_platform(&paths, "vimeo", 10, 0).is_err());
+    }
+
+    #[test]
+    fn get_item_by_id_reports_missing_item_as_not_found() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+
+        let err = get_item_by_id(&paths, "gone").expect_err("missing item");
+        assert!(matches!(
+            &err,
+            crate::EngineError::NotFound { entity, id } if entity == "library item" && id == "gone"
+        ));
+        assert_eq!(err.to_string(), "library item not found: gone");
+    }
+
+    #[test]
+    fn embedded_title_skips_file_names_and_media_type_ignores_cover_art() {
+        let path = Path::new("/usb/track01.mp3");
+        let tag = |v: &str| Some(v.to_string());
+        assert_eq!(
+            embedded_title(tag("Morning Song").as_ref(), path).as_deref(),
+            Some("Morning Song")
+        );
+        assert_eq!(embedded_title(tag("track01").as_ref(), path), None);
+        assert_eq!(embedded_title(tag("other.mp4").as_ref(), path), None);
+        assert_eq!(embedded_title(tag("  ").as_ref(), path), None);
+        assert_eq!(embedded_title(None, path), None);
+
+        assert_eq!(media_type_for_codecs(Some("h264"), Some("aac")), "video");
+        assert_eq!(media_type_for_codecs(Some("mjpeg"), Some("mp3")), "audio");
+        assert_eq!(media_type_for_codecs(None, None), "unknown");
+    }
+
+    #[test]
+    fn record_item_history_lists_newest_first_and_caps_rows() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+        let conn = db::open(&paths).expect("db");
+        conn.execute(
+            "INSERT INTO library_item (id, created_at_ms, source_type, source_uri, title, media_path) VALUES ('item-h', 1, 'local_file', '/m.mp4', 'H', '/m.mp4')",
+            [],
+        )
+        .expect("insert");
+        for i in 0..ITEM_HISTORY_MAX_ROWS {
+            conn.execute(
+                "INSERT INTO library_item_history (id, item_id, event_type, details_json, created_at_ms) VALUES (?1, 'item-h', 'asr_done', '{}', ?2)",
+                params![format!("old-{i}"), i as i64],
+            )
+            .expect("seed");
+        }
+
+        record_item_history(
+            &paths,
+            "item-h",
+            "mux_done",
+            Some("job-1"),
+            None,
+            serde_json::json!({ "out_path": "/out.mp4" }),
+        )
+        .expect("record");
+
+        let rows = list_item_history(&paths, "item-h", 2, 0).expect("list");
+        assert_eq!(rows[0].event_type, "mux_done");
+        assert_eq!(rows[0].job_id.as_deref(), Some("job-1"));
+        assert_eq!(rows[0].details["out_path"], "/out.mp4");
+        assert_eq!(rows[1].id, format!("old-{}", ITEM_HISTORY_MAX_ROWS - 1));
+        let count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM library_item_history WHERE item_id='item-h'",
+                [],
+                |row| row.get(0),
+            )
+            .expect("count");
+        assert_eq!(count, ITEM_HISTORY_MAX_ROWS as i64);
+        assert!(
+            list_item_history(&paths, "item-h", 1, ITEM_HISTORY_MAX_ROWS)
+                .expect("tail")
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn repair_item_metadata_fills_missing_file_size_once() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+        db::ensure_schema(&paths).expect("schema");
+
+        let media_path = dir.path().join("sample.mp4");
+        std::fs::write(&media_path, b"not-a-real-video").expect("media");
+        let conn = db::open(&paths).expect("db");
+        conn.execute(
+            "INSERT INTO library_item (id, created_at_ms, source_type, source_uri, title, media_path) VALUES ('item-repair', 1, 'local_file', ?1, 'Sample', ?1)",
+            params![media_path.to_string_lossy().to_string()],
+        )
+        .expect("insert");
+
+        assert_eq!(repair_item_metadata(&paths).expect("repair"), 1);
+        let item = get_item_by_id(&paths, "item-repair").expect("item");
+        assert_eq!(item.file_size_bytes, Some(16));
+        assert_eq!(repair_item_metadata(&paths).expect("repair again"), 0);
+    }
+
     #[test]
     fn ensure_thumbnail_path_clears_stale_reference_when_media_missing() {
         let dir = tempfile::tempdir().expect("tempdir");
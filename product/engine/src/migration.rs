@@ -0,0 +1,445 @@
+use std::path::{Path, PathBuf};
+
+use rusqlite::params;
+use serde::Serialize;
+
+use crate::paths::{AppPaths, BASE_DIR_OVERRIDE_FILE_NAME};
+use crate::{db, diagnostics, persistence, EngineError, Result};
+
+/// Extra headroom required on the destination volume beyond the size of the copied tree.
+const STORAGE_MIGRATION_FREE_SPACE_MARGIN_BYTES: u64 = 256 * 1024 * 1024;
+/// Left in the new base dir when the old one should be emptied on the next launch; holds the
+/// old dir path.
+pub const OLD_BASE_DIR_CLEANUP_FILE_NAME: &str = "old_base_dir_cleanup.txt";
+const DB_FILE_NAME: &str = "app.sqlite";
+
+#[derive(Debug, Default, Clone)]
+pub struct CopySummary {
+    pub copied_files: u64,
+    pub skipped_files: u64,
+    pub copied_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct StorageMigrationResult {
+    pub copied_files: u64,
+    pub copied_bytes: u64,
+    pub old_dir: String,
+    pub new_dir: String,
+    /// The old dir is emptied on the next launch, once the pointer to the new dir was followed.
+    pub old_dir_removal_scheduled: bool,
+}
+
+/// Copies every regular file under `src_root` into `dst_root`, skipping files that already exist
+/// with the same non-zero size and are not older than the source. `force_overwrite` copies every
+/// file regardless. Missing sources are treated as empty.
+pub fn copy_tree_best_effort(
+    src_root: &Path,
+    dst_root: &Path,
+    force_overwrite: bool,
+) -> Result<CopySummary> {
+    copy_tree_best_effort_with_progress(src_root, dst_root, force_overwrite, |_| {})
+}
+
+/// Same as [`copy_tree_best_effort`], calling `on_file_bytes` with the size of each file handled.
+pub fn copy_tree_best_effort_with_progress(
+    src_root: &Path,
+    dst_root: &Path,
+    force_overwrite: bool,
+    mut on_file_bytes: impl FnMut(u64),
+) -> Result<CopySummary> {
+    if !src_root.exists() {
+        return Ok(CopySummary::default());
+    }
+
+    let mut summary = CopySummary::default();
+    let mut stack: Vec<PathBuf> = vec![src_root.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let entries = std::fs::read_dir(&dir).map_err(|e| {
+            EngineError::InstallFailed(format!("failed to read dir {}: {e}", dir.display()))
+        })?;
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let file_type = match entry.file_type() {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+
+            let rel = match path.strip_prefix(src_root) {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+            let dst = dst_root.join(rel);
+
+            if file_type.is_dir() {
+                std::fs::create_dir_all(&dst).map_err(|e| {
+                    EngineError::InstallFailed(format!(
+                        "failed to create dir {}: {e}",
+                        dst.display()
+                    ))
+                })?;
+                stack.push(path);
+                continue;
+            }
+
+            if !file_type.is_file() {
+                continue;
+            }
+
+            let src_meta = match std::fs::metadata(&path) {
+                Ok(v) => v,
+                Err(_) => continue,
+            };
+
+            if !force_overwrite {
+                if let Ok(dst_meta) = std::fs::metadata(&dst) {
+                    if copy_is_up_to_date(&src_meta, &dst_meta) {
+                        summary.skipped_files += 1;
+                        on_file_bytes(src_meta.len());
+                        continue;
+                    }
+                }
+            }
+
+            if let Some(parent) = dst.parent() {
+                std::fs::create_dir_all(parent).map_err(|e| {
+                    EngineError::InstallFailed(format!(
+                        "failed to create dir {}: {e}",
+                        parent.display()
+                    ))
+                })?;
+            }
+
+            let tmp = dst.with_extension("copying");
+            let _ = std::fs::remove_file(&tmp);
+
+            std::fs::copy(&path, &tmp).map_err(|e| {
+                EngineError::InstallFailed(format!(
+                    "failed to copy {} -> {}: {e}",
+                    path.display(),
+                    tmp.display()
+                ))
+            })?;
+
+            if dst.exists() {
+                let _ = std::fs::remove_file(&dst);
+            }
+            if std::fs::rename(&tmp, &dst).is_err() {
+                std::fs::copy(&tmp, &dst).map_err(|e| {
+                    EngineError::InstallFailed(format!(
+                        "failed to finalize copy {} -> {}: {e}",
+                        tmp.display(),
+                        dst.display()
+                    ))
+                })?;
+                let _ = std::fs::remove_file(&tmp);
+            }
+
+            summary.copied_files += 1;
+            summary.copied_bytes += src_meta.len();
+            on_file_bytes(src_meta.len());
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Same non-zero size, and the destination is not older than the source. When either mtime is
+/// unavailable the size match alone decides.
+fn copy_is_up_to_date(src_meta: &std::fs::Metadata, dst_meta: &std::fs::Metadata) -> bool {
+    if dst_meta.len() != src_meta.len() || src_meta.len() == 0 {
+        return false;
+    }
+    match (src_meta.modified(), dst_meta.modified()) {
+        (Ok(src_mtime), Ok(dst_mtime)) => dst_mtime >= src_mtime,
+        _ => true,
+    }
+}
+
+fn tree_size_bytes(root: &Path) -> u64 {
+    let mut total = 0u64;
+    let mut stack = vec![root.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in entries.flatten() {
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                stack.push(entry.path());
+            } else if file_type.is_file() {
+                total += entry.metadata().map(|m| m.len()).unwrap_or(0);
+            }
+        }
+    }
+    total
+}
+
+fn running_job_count(paths: &AppPaths) -> Result<i64> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let count = conn.query_row(
+        "SELECT COUNT(*) FROM job WHERE status = ?1",
+        params!["running"],
+        |row| row.get(0),
+    )?;
+    let _ = db::wal_checkpoint_truncate(&conn);
+    Ok(count)
+}
+
+/// Writes a consistent snapshot of the live database to `dst_db_dir`, replacing any copy of the
+/// raw file (and its WAL/SHM sidecars) that the tree copy picked up mid-write.
+fn snapshot_db_into(paths: &AppPaths, dst_db_dir: &Path) -> Result<()> {
+    std::fs::create_dir_all(dst_db_dir)?;
+    for suffix in ["", "-wal", "-shm"] {
+        let _ = std::fs::remove_file(dst_db_dir.join(format!("{DB_FILE_NAME}{suffix}")));
+    }
+    let conn = db::open(paths)?;
+    conn.execute(
+        "VACUUM INTO ?1",
+        params![dst_db_dir.join(DB_FILE_NAME).to_string_lossy().to_string()],
+    )?;
+    Ok(())
+}
+
+/// Removes everything in the old base dir except the override pointer, so the next launch can
+/// still find the new location.
+fn remove_old_base_dir_contents(old_dir: &Path) -> bool {
+    let Ok(entries) = std::fs::read_dir(old_dir) else {
+        return false;
+    };
+    let mut ok = true;
+    for entry in entries.flatten() {
+        if entry.file_name() == BASE_DIR_OVERRIDE_FILE_NAME {
+            continue;
+        }
+        let path = entry.path();
+        let removed = match entry.file_type() {
+            Ok(t) if t.is_dir() => std::fs::remove_dir_all(&path),
+            _ => std::fs::remove_file(&path),
+        };
+        ok &= removed.is_ok();
+    }
+    ok
+}
+
+/// Empties the old base dir recorded by [`migrate_storage`] once this launch is running from the
+/// dir its pointer leads to, i.e. nothing has the old database open anymore. Returns whether a
+/// pending cleanup finished.
+pub fn finish_pending_old_dir_cleanup(paths: &AppPaths) -> bool {
+    let marker = paths.base_dir.join(OLD_BASE_DIR_CLEANUP_FILE_NAME);
+    let Ok(raw) = std::fs::read_to_string(&marker) else {
+        return false;
+    };
+    let old_dir = PathBuf::from(raw.trim());
+    if raw.trim().is_empty() || AppPaths::resolve_base_dir(&old_dir) != paths.base_dir {
+        let _ = std::fs::remove_file(&marker);
+        return false;
+    }
+    if !remove_old_base_dir_contents(&old_dir) {
+        return false;
+    }
+    let _ = std::fs::remove_file(&marker);
+    true
+}
+
+/// Copies the app data tree to `new_base_dir` and points future launches at it through the base
+/// dir override file. The running app keeps using the old dir until restarted; with
+/// `remove_old_dir` the old dir is emptied by [`finish_pending_old_dir_cleanup`] on that restart.
+pub fn migrate_storage(
+    paths: &AppPaths,
+    new_base_dir: &Path,
+    remove_old_dir: bool,
+    progress_cb: impl Fn(f32),
+) -> Result<StorageMigrationResult> {
+    let old_dir = paths.base_dir.clone();
+    let new_dir = AppPaths::normalize_base_dir(new_base_dir);
+    if !new_dir.is_absolute() {
+        return Err(EngineError::InstallFailed(format!(
+            "storage migration target must be an absolute path: {}",
+            new_dir.display()
+        )));
+    }
+
+    let old_canonical = std::fs::canonicalize(&old_dir).unwrap_or_else(|_| old_dir.clone());
+    std::fs::create_dir_all(&new_dir)?;
+    let new_canonical = std::fs::canonicalize(&new_dir)?;
+    if new_canonical.starts_with(&old_canonical) || old_canonical.starts_with(&new_canonical) {
+        return Err(EngineError::InstallFailed(format!(
+            "storage migration target must not overlap the current data dir: {}",
+            new_dir.display()
+        )));
+    }
+    if std::fs::read_dir(&new_dir)?.next().is_some() {
+        return Err(EngineError::InstallFailed(format!(
+            "storage migration target must be empty: {}",
+            new_dir.display()
+        )));
+    }
+    if !diagnostics::dir_is_writable(&new_dir) {
+        return Err(EngineError::InstallFailed(format!(
+            "storage migration target is not writable: {}",
+            new_dir.display()
+        )));
+    }
+
+    let running = running_job_count(paths)?;
+    if running > 0 {
+        return Err(EngineError::InstallFailed(format!(
+            "cannot migrate storage while {running} job(s) are running"
+        )));
+    }
+
+    let total_bytes = tree_size_bytes(&old_dir);
+    if let Some(free) = diagnostics::disk_free_bytes(&new_dir) {
+        let required = total_bytes.saturating_add(STORAGE_MIGRATION_FREE_SPACE_MARGIN_BYTES);
+        if free < required {
+            return Err(EngineError::InstallFailed(format!(
+                "not enough free space at {}: need {required} bytes, have {free} bytes",
+                new_dir.display()
+            )));
+        }
+    }
+
+    progress_cb(0.0);
+    let mut done_bytes = 0u64;
+    let summary = copy_tree_best_effort_with_progress(&old_dir, &new_dir, false, |bytes| {
+        done_bytes = done_bytes.saturating_add(bytes);
+        if total_bytes > 0 {
+            progress_cb((done_bytes as f32 / total_bytes as f32).min(1.0));
+        }
+    })?;
+    // A stale pointer copied along with the tree would send the new dir somewhere else.
+    let _ = std::fs::remove_file(new_dir.join(BASE_DIR_OVERRIDE_FILE_NAME));
+    let _ = std::fs::remove_file(new_dir.join(OLD_BASE_DIR_CLEANUP_FILE_NAME));
+    snapshot_db_into(paths, &AppPaths::new(new_dir.clone()).db_dir())?;
+
+    if remove_old_dir {
+        persistence::atomic_write_text(
+            &new_dir.join(OLD_BASE_DIR_CLEANUP_FILE_NAME),
+            &old_dir.to_string_lossy(),
+        )?;
+    }
+    persistence::atomic_write_text(&paths.base_dir_override_path(), &new_dir.to_string_lossy())?;
+    progress_cb(1.0);
+
+    Ok(StorageMigrationResult {
+        copied_files: summary.copied_files,
+        copied_bytes: summary.copied_bytes,
+        old_dir: old_dir.to_string_lossy().to_string(),
+        new_dir: new_dir.to_string_lossy().to_string(),
+        old_dir_removal_scheduled: remove_old_dir,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use filetime::{set_file_mtime, FileTime};
+
+    #[test]
+    fn copy_tree_recopies_older_same_size_files_and_honors_force() {
+        let src = tempfile::tempdir().expect("src tempdir");
+        let dst = tempfile::tempdir().expect("dst tempdir");
+        let src_file = src.path().join("a.bin");
+        let dst_file = dst.path().join("a.bin");
+        std::fs::write(&src_file, b"fresh").expect("write src");
+        std::fs::write(&dst_file, b"stale").expect("write dst");
+        set_file_mtime(&src_file, FileTime::from_unix_time(2_000_000, 0)).expect("src mtime");
+        set_file_mtime(&dst_file, FileTime::from_unix_time(1_000_000, 0)).expect("dst mtime");
+
+        let summary = copy_tree_best_effort(src.path(), dst.path(), false).expect("copy");
+        assert_eq!(summary.copied_files, 1);
+        assert_eq!(std::fs::read(&dst_file).expect("read"), b"fresh");
+
+        std::fs::write(&dst_file, b"other").expect("write dst");
+        set_file_mtime(&dst_file, FileTime::from_unix_time(3_000_000, 0)).expect("dst mtime");
+        let summary = copy_tree_best_effort(src.path(), dst.path(), false).expect("copy");
+        assert_eq!(summary.skipped_files, 1);
+        assert_eq!(std::fs::read(&dst_file).expect("read"), b"other");
+
+        let summary = copy_tree_best_effort(src.path(), dst.path(), true).expect("force copy");
+        assert_eq!(summary.copied_files, 1);
+        assert_eq!(std::fs::read(&dst_file).expect("read"), b"fresh");
+    }
+
+    #[test]
+    fn migrate_storage_copies_tree_and_writes_override() {
+        let old = tempfile::tempdir().expect("old tempdir");
+        let new_root = tempfile::tempdir().expect("new tempdir");
+        let paths = AppPaths::new(old.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+        std::fs::write(paths.config_dir().join("download_dir.txt"), "D:/downloads")
+            .expect("config");
+
+        let new_dir = new_root.path().join("voxvulgi");
+        let conn = db::open(&paths).expect("open db");
+        db::migrate(&conn).expect("migrate");
+        conn.execute(
+            "INSERT INTO job (id, type, status, progress, params_json, created_at_ms, logs_path) VALUES ('j1', 'import_local', 'running', 0, '{}', 1, 'x')",
+            [],
+        )
+        .expect("insert job");
+        let err = migrate_storage(&paths, &new_dir, false, |_| {}).expect_err("running job");
+        assert!(err.to_string().contains("running"));
+        conn.execute("UPDATE job SET status = 'succeeded'", [])
+            .expect("finish job");
+        drop(conn);
+
+        let last = std::cell::Cell::new(0.0f32);
+        let result =
+            migrate_storage(&paths, &new_dir, false, |p| last.set(p)).expect("migrate storage");
+        assert!(result.copied_files >= 2);
+        assert_eq!(last.get(), 1.0);
+        assert_eq!(
+            std::fs::read_to_string(new_dir.join("config").join("download_dir.txt"))
+                .expect("copied config"),
+            "D:/downloads"
+        );
+        assert_eq!(AppPaths::resolve_base_dir(old.path()), new_dir);
+        assert!(paths.config_dir().exists());
+
+        let err = migrate_storage(&paths, &new_dir, false, |_| {}).expect_err("non-empty");
+        assert!(err.to_string().contains("empty"));
+    }
+
+    #[test]
+    fn migrate_storage_snapshots_db_and_defers_old_dir_removal() {
+        let old = tempfile::tempdir().expect("old tempdir");
+        let new_root = tempfile::tempdir().expect("new tempdir");
+        let paths = AppPaths::new(old.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+        let conn = db::open(&paths).expect("open db");
+        db::migrate(&conn).expect("migrate");
+        conn.execute(
+            "INSERT INTO job (id, type, status, progress, params_json, created_at_ms, logs_path) VALUES ('j1', 'import_local', 'succeeded', 1, '{}', 1, 'x')",
+            [],
+        )
+        .expect("insert job");
+
+        let new_dir = new_root.path().join("voxvulgi");
+        let result = migrate_storage(&paths, &new_dir, true, |_| {}).expect("migrate storage");
+        assert!(result.old_dir_removal_scheduled);
+        // The live database stays in place until the next launch.
+        assert!(paths.db_dir().join(DB_FILE_NAME).exists());
+        drop(conn);
+
+        let new_paths = AppPaths::new(AppPaths::resolve_base_dir(old.path()));
+        assert_eq!(new_paths.base_dir, new_dir);
+        let copied = db::open(&new_paths).expect("open copy");
+        let jobs: i64 = copied
+            .query_row("SELECT COUNT(*) FROM job", [], |row| row.get(0))
+            .expect("count");
+        assert_eq!(jobs, 1);
+
+        assert!(!finish_pending_old_dir_cleanup(&paths));
+        assert!(finish_pending_old_dir_cleanup(&new_paths));
+        assert!(!paths.db_dir().exists());
+        assert!(paths.base_dir_override_path().exists());
+        assert!(!new_dir.join(OLD_BASE_DIR_CLEANUP_FILE_NAME).exists());
+    }
+}
@@ -1,12 +1,33 @@
 use crate::paths::AppPaths;
-use crate::{EngineError, Result};
+use crate::{db, diagnostics, EngineError, Result};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
 use std::path::Path;
+use std::sync::OnceLock;
 use std::{io::Read, io::Write, path::PathBuf};
 
 const BUNDLED_MANIFEST_JSON: &str = include_str!("../resources/models/manifest.json");
 const BUNDLED_DEMO_FILE: &[u8] = include_bytes!("../resources/models/bundled/demo/demo.txt");
+/// Minimum bytes between download progress events so large models do not flood listeners.
+const MODEL_DOWNLOAD_PROGRESS_STEP_BYTES: u64 = 1024 * 1024;
+
+type ModelDownloadHook = Box<dyn Fn(&ModelDownloadEvent) + Send + Sync>;
+static MODEL_DOWNLOAD_HOOK: OnceLock<ModelDownloadHook> = OnceLock::new();
+
+/// Install progress of one model; byte counts cover all of the model's files.
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelDownloadEvent {
+    pub model_id: String,
+    pub downloaded_bytes: u64,
+    pub total_bytes: Option<u64>,
+    /// `downloading`, `copying`, `writing`, `verifying` or `installed`.
+    pub stage: String,
+}
+
+/// Registers the observer for model install progress; later registrations are ignored.
+pub fn set_model_download_hook(hook: impl Fn(&ModelDownloadEvent) + Send + Sync + 'static) {
+    let _ = MODEL_DOWNLOAD_HOOK.set(Box::new(hook));
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ModelManifest {
@@ -85,6 +106,28 @@ pub struct ModelInventoryItem {
     pub features: Vec<String>,
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelInventoryWithSizes {
+    pub models_dir: String,
+    pub total_installed_bytes: u64,
+    pub total_disk_bytes: u64,
+    pub models: Vec<ModelInventorySizedItem>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct ModelInventorySizedItem {
+    #[serde(flatten)]
+    pub item: ModelInventoryItem,
+    /// Bytes actually on disk under `install_dir`, including partial or unverified installs.
+    pub disk_bytes: u64,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DeleteModelResult {
+    pub removed_files: usize,
+    pub removed_bytes: u64,
+}
+
 #[derive(Debug, Clone)]
 pub struct ModelStore {
     paths: AppPaths,
@@ -161,13 +204,59 @@ impl ModelStore {
         })
     }
 
+    pub fn inventory_with_sizes(&self) -> Result<ModelInventoryWithSizes> {
+        let inventory = self.inventory()?;
+        let models: Vec<ModelInventorySizedItem> = inventory
+            .models
+            .into_iter()
+            .map(|item| ModelInventorySizedItem {
+                disk_bytes: diagnostics::directory_size_bytes_best_effort(Path::new(
+                    &item.install_dir,
+                )),
+                item,
+            })
+            .collect();
+        let total_disk_bytes = models.iter().map(|m| m.disk_bytes).sum();
+        Ok(ModelInventoryWithSizes {
+            models_dir: inventory.models_dir,
+            total_installed_bytes: inventory.total_installed_bytes,
+            total_disk_bytes,
+            models,
+        })
+    }
+
     pub fn install_model(&self, model_id: &str) -> Result<()> {
+        self.install_model_with_progress(model_id, &|_| {})
+    }
+
+    /// Installs `model_id`, reporting progress to `on_progress` and the registered model
+    /// download hook.
+    pub fn install_model_with_progress(
+        &self,
+        model_id: &str,
+        on_progress: &dyn Fn(&ModelDownloadEvent),
+    ) -> Result<()> {
         self.paths.ensure_dirs()?;
 
         let model = self.model_spec_by_id(model_id)?;
         let install_root = self.paths.model_install_dir(&model.id, &model.version);
         std::fs::create_dir_all(&install_root)?;
 
+        let total_bytes: u64 = model.files.iter().map(|f| f.size_bytes).sum();
+        let emit = |stage: &str, downloaded_bytes: u64| {
+            let event = ModelDownloadEvent {
+                model_id: model.id.clone(),
+                downloaded_bytes: downloaded_bytes.min(total_bytes),
+                total_bytes: (total_bytes > 0).then_some(total_bytes),
+                stage: stage.to_string(),
+            };
+            on_progress(&event);
+            if let Some(hook) = MODEL_DOWNLOAD_HOOK.get() {
+                hook(&event);
+            }
+        };
+
+        let mut completed_bytes = 0_u64;
         for file in &model.files {
             let out_path = install_root.join(&file.path);
             if let Some(parent) = out_path.parent() {
@@ -178,20 +267,27 @@ impl ModelStore {
                 ModelFileSource::Bundled { resource_id } => {
                     let bytes = bundled_resource(resource_id)?;
                     write_atomic(&out_path, bytes)?;
+                    emit("writing", completed_bytes + file.size_bytes);
                 }
                 ModelFileSource::Url { url } => {
-                    download_atomic(url, &out_path, file.size_bytes, &file.sha256)?;
+                    download_atomic(url, &out_path, file.size_bytes, &file.sha256, &|bytes| {
+                        emit("downloading", completed_bytes + bytes)
+                    })?;
                 }
                 ModelFileSource::LocalPath { path } => {
                     copy_atomic(Path::new(path), &out_path)?;
+                    emit("copying", completed_bytes + file.size_bytes);
                 }
             }
 
             verify_file(&out_path, file.size_bytes, &file.sha256)?;
+            completed_bytes += file.size_bytes;
         }
 
         // Full model verification after install.
+        emit("verifying", completed_bytes);
         self.verify_model(&model)?;
+        emit("installed", completed_bytes);
         Ok(())
     }
 
@@ -224,6 +320,80 @@ impl ModelStore {
     }
 }
 
+/// Removes an installed model from disk. Refuses while a queued or running job references it.
+pub fn delete_model(paths: &AppPaths, model_id: &str) -> Result<DeleteModelResult> {
+    let store = ModelStore::new(paths.clone());
+    let inventory = store.inventory()?;
+    let item = inventory
+        .models
+        .iter()
+        .find(|m| m.id == model_id)
+        .ok_or_else(|| EngineError::UnknownModel(model_id.to_string()))?;
+
+    let active_jobs = active_jobs_referencing_model(paths, model_id)?;
+    if active_jobs > 0 {
+        return Err(EngineError::InvalidInput {
+            message: format!(
+                "model {model_id} is used by {active_jobs} queued or running job(s); cancel them before deleting"
+            ),
+        });
+    }
+
+    let install_dir = PathBuf::from(&item.install_dir);
+    if !install_dir.exists() {
+        return Ok(DeleteModelResult {
+            removed_files: 0,
+            removed_bytes: 0,
+        });
+    }
+    let (removed_files, removed_bytes) = count_files_and_bytes(&install_dir)?;
+    std::fs::remove_dir_all(&install_dir)?;
+    if let Some(parent) = install_dir.parent() {
+        // Drop the per-model folder once its last version is gone.
+        if parent != paths.models_dir() {
+            let _ = std::fs::remove_dir(parent);
+        }
+    }
+
+    Ok(DeleteModelResult {
+        removed_files,
+        removed_bytes,
+    })
+}
+
+fn active_jobs_referencing_model(paths: &AppPaths, model_id: &str) -> Result<usize> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let mut stmt =
+        conn.prepare("SELECT params_json FROM job WHERE status IN ('queued', 'running')")?;
+    let params = stmt
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<std::result::Result<Vec<_>, _>>()?;
+    Ok(params
+        .iter()
+        .filter_map(|raw| serde_json::from_str::<serde_json::Value>(raw).ok())
+        .filter(|value| value.get("model_id").and_then(|v| v.as_str()) == Some(model_id))
+        .count())
+}
+
+fn count_files_and_bytes(path: &Path) -> std::io::Result<(usize, u64)> {
+    let mut files = 0_usize;
+    let mut bytes = 0_u64;
+    for entry in std::fs::read_dir(path)? {
+        let entry = entry?;
+        let meta = entry.metadata()?;
+        if meta.is_file() {
+            files += 1;
+            bytes += meta.len();
+        } else if meta.is_dir() {
+            let (f, b) = count_files_and_bytes(&entry.path())?;
+            files += f;
+            bytes += b;
+        }
+    }
+    Ok((files, bytes))
+}
+
 #[derive(Debug, Clone, Copy)]
 struct ModelInventoryMeta {
     role: ModelInventoryRole,
@@ -323,19 +493,30 @@ fn copy_atomic(src: &Path, dst: &Path) -> Result<()> {
     Ok(())
 }
 
-fn download_atomic(url: &str, dst: &Path, expected_size: u64, expected_sha256: &str) -> Result<()> {
+fn download_atomic(
+    url: &str,
+    dst: &Path,
+    expected_size: u64,
+    expected_sha256: &str,
+    on_bytes: &dyn Fn(u64),
+) -> Result<()> {
     let tmp_path = dst.with_extension("download");
 
     let mut last_error: Option<String> = None;
     for attempt in 1..=5 {
         let _ = std::fs::remove_file(&tmp_path);
 
-        let download_result = download_atomic_ureq(url, &tmp_path).or_else(|primary_err| {
-            download_atomic_curl(url, &tmp_path).map_err(|fallback_err| {
-                EngineError::InstallFailed(format!(
-                    "model download failed: {url} ({primary_err}); curl fallback failed ({fallback_err})"
-                ))
-            })
+        let download_result = download_atomic_ureq(url, &tmp_path, on_bytes).or_else(|primary_err| {
+            download_atomic_curl(url, &tmp_path)
+                .map(|()| {
+                    // curl runs silently, so report the finished file in one step.
+                    on_bytes(std::fs::metadata(&tmp_path).map(|m| m.len()).unwrap_or(0));
+                })
+                .map_err(|fallback_err| {
+                    EngineError::InstallFailed(format!(
+                        "model download failed: {url} ({primary_err}); curl fallback failed ({fallback_err})"
+                    ))
+                })
         });
 
         let result = download_result.and_then(|_| {
@@ -359,7 +540,7 @@ fn download_atomic(url: &str, dst: &Path, expected_size: u64, expected_sha256: &
     )))
 }
 
-fn download_atomic_ureq(url: &str, tmp_path: &Path) -> Result<()> {
+fn download_atomic_ureq(url: &str, tmp_path: &Path, on_bytes: &dyn Fn(u64)) -> Result<()> {
     let resp = ureq::get(url)
         .call()
         .map_err(|e| EngineError::InstallFailed(format!("model download failed: {url} ({e})")))?;
@@ -375,14 +556,24 @@ fn download_atomic_ureq(url: &str, tmp_path: &Path) -> Result<()> {
     let mut reader = resp.into_body().into_reader();
     let mut file = std::fs::File::create(&tmp_path)?;
     let mut buf = [0u8; 1024 * 64];
+    let mut downloaded = 0_u64;
+    let mut last_reported = 0_u64;
     loop {
         let n = reader.read(&mut buf)?;
         if n == 0 {
             break;
         }
         file.write_all(&buf[..n])?;
+        downloaded += n as u64;
+        if downloaded - last_reported >= MODEL_DOWNLOAD_PROGRESS_STEP_BYTES {
+            on_bytes(downloaded);
+            last_reported = downloaded;
+        }
     }
     file.flush()?;
+    if downloaded != last_reported {
+        on_bytes(downloaded);
+    }
     Ok(())
 }
 
@@ -501,6 +692,62 @@ mod tests {
         store.verify_model_by_id("demo-ja-asr").expect("verify");
     }
 
+    #[test]
+    fn install_model_with_progress_reports_stages_through_installed() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let store = ModelStore::new(AppPaths::new(dir.path().to_path_buf()));
+        let events = std::cell::RefCell::new(Vec::<ModelDownloadEvent>::new());
+
+        store
+            .install_model_with_progress("demo-ja-asr", &|event| {
+                events.borrow_mut().push(event.clone())
+            })
+            .expect("install");
+
+        let events = events.into_inner();
+        let stages: Vec<&str> = events.iter().map(|e| e.stage.as_str()).collect();
+        assert_eq!(stages.first(), Some(&"writing"));
+        assert_eq!(stages.last(), Some(&"installed"));
+        let last = events.last().expect("event");
+        assert_eq!(Some(last.downloaded_bytes), last.total_bytes);
+    }
+
+    #[test]
+    fn delete_model_removes_files_unless_an_active_job_uses_it() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        db::ensure_schema(&paths).expect("schema");
+        let store = ModelStore::new(paths.clone());
+        store.install_bundled_model("demo-ja-asr").expect("install");
+
+        let sized = store.inventory_with_sizes().expect("sized inventory");
+        let demo = sized
+            .models
+            .iter()
+            .find(|m| m.item.id == "demo-ja-asr")
+            .expect("demo model");
+        assert!(demo.disk_bytes > 0);
+
+        let conn = db::open(&paths).expect("open");
+        conn.execute(
+            "INSERT INTO job(id, type, status, progress, params_json, created_at_ms, logs_path)
+             VALUES ('j1', 'asr_local', 'queued', 0.0, '{\"model_id\":\"demo-ja-asr\"}', 0, '')",
+            [],
+        )
+        .expect("insert job");
+        assert!(matches!(
+            delete_model(&paths, "demo-ja-asr"),
+            Err(EngineError::InvalidInput { .. })
+        ));
+
+        conn.execute("UPDATE job SET status='succeeded' WHERE id='j1'", [])
+            .expect("finish job");
+        let result = delete_model(&paths, "demo-ja-asr").expect("delete");
+        assert!(result.removed_files > 0);
+        assert_eq!(result.removed_bytes, demo.disk_bytes);
+        assert!(store.verify_model_by_id("demo-ja-asr").is_err());
+    }
+
     #[test]
     fn inventory_marks_required_and_demo_models_explicitly() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -1,14 +1,111 @@
 use crate::persistence;
+use crate::{EngineError, Result};
+use sha2::{Digest, Sha256};
+use std::borrow::Cow;
 use std::path::{Path, PathBuf};
 
+/// Checks that an item or job id has the canonical UUID shape (`^[0-9a-f-]{36}$`), so it is
+/// safe to use as a single path component.
+pub fn validate_item_id(id: &str) -> Result<()> {
+    let valid = id.len() == 36
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b) || b == b'-');
+    if valid {
+        Ok(())
+    } else {
+        Err(EngineError::InvalidInput {
+            message: format!("invalid id {id:?}: expected a lowercase UUID"),
+        })
+    }
+}
+
+pub(crate) fn is_content_key(key: &str) -> bool {
+    key.len() == 32 && key.bytes().all(|b| b.is_ascii_hexdigit())
+}
+
+/// Path component for an item or job id or a content key. UUIDs, content keys and other plain
+/// `[A-Za-z0-9_-]` ids (older data and fixtures) are used as is; anything else maps to a
+/// deterministic `invalid-<hash>` name, so it can never escape its parent dir or collide with a
+/// plain id.
+fn id_path_component(id: &str) -> Cow<'_, str> {
+    let plain = !id.is_empty()
+        && id.len() <= 128
+        && !id.starts_with("invalid-")
+        && id
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric() || b == b'-' || b == b'_');
+    if plain {
+        return Cow::Borrowed(id);
+    }
+    let digest = Sha256::digest(id.as_bytes());
+    Cow::Owned(format!("invalid-{}", hex::encode(&digest[..8])))
+}
+
+/// Outcome of [`AppPaths::ensure_dirs`], split by whether each directory had to be created.
+#[derive(Debug, Clone, Default)]
+pub struct EnsureDirsResult {
+    pub created: Vec<PathBuf>,
+    pub already_existed: Vec<PathBuf>,
+}
+
+/// How the base dir was chosen at startup.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum PathSource {
+    #[default]
+    OsAppData,
+    /// `VOXVULGI_DATA_DIR`, see [`AppPaths::from_env`].
+    Environment,
+    Portable,
+}
+
+impl PathSource {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            PathSource::OsAppData => "os_app_data",
+            PathSource::Environment => "environment",
+            PathSource::Portable => "portable",
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct AppPaths {
     pub base_dir: PathBuf,
+    pub source: PathSource,
 }
 
 impl AppPaths {
     pub fn new(base_dir: PathBuf) -> Self {
-        Self { base_dir }
+        Self {
+            base_dir,
+            source: PathSource::default(),
+        }
+    }
+
+    pub fn with_source(mut self, source: PathSource) -> Self {
+        self.source = source;
+        self
+    }
+
+    /// Base dir from `VOXVULGI_DATA_DIR`, for CI, tests and containers that must not touch the
+    /// OS app data dir. `None` when the variable is unset; a blank or relative value is ignored
+    /// with a warning so the app still starts with its default data dir.
+    pub fn from_env() -> Option<AppPaths> {
+        let raw = std::env::var_os(DATA_DIR_ENV_VAR)?;
+        let paths = Self::from_env_value(&raw.to_string_lossy());
+        if paths.is_none() {
+            eprintln!("from_env: ignoring {DATA_DIR_ENV_VAR}={raw:?}; it must be an absolute path");
+        }
+        paths
+    }
+
+    fn from_env_value(raw: &str) -> Option<AppPaths> {
+        let raw = raw.trim();
+        if raw.is_empty() || !Path::new(raw).is_absolute() {
+            return None;
+        }
+        Some(AppPaths::new(PathBuf::from(raw)).with_source(PathSource::Environment))
     }
 
     pub fn config_dir(&self) -> PathBuf {
@@ -69,15 +166,22 @@ impl AppPaths {
     }
 
     pub fn derived_item_dir(&self, item_id: &str) -> PathBuf {
-        self.derived_items_dir().join(item_id)
+        self.derived_items_dir()
+            .join(id_path_component(item_id).as_ref())
     }
 
     pub fn derived_item_voice_dir(&self, item_id: &str) -> PathBuf {
         self.derived_item_dir(item_id).join("voice")
     }
 
-    pub fn job_artifacts_dir(&self, job_id: &str) -> PathBuf {
-        self.derived_jobs_dir().join(job_id)
+    /// Artifacts dir for a job. Jobs with a content key share one dir across retries; the rest
+    /// fall back to their job id.
+    pub fn job_artifacts_dir(&self, job_id: &str, content_key: Option<&str>) -> PathBuf {
+        let name = match content_key.map(str::trim).filter(|key| !key.is_empty()) {
+            Some(key) => id_path_component(key),
+            None => id_path_component(job_id),
+        };
+        self.derived_jobs_dir().join(name.as_ref())
     }
 
     pub fn db_dir(&self) -> PathBuf {
@@ -92,6 +196,11 @@ impl AppPaths {
         self.logs_dir().join("jobs")
     }
 
+    pub fn job_log_path(&self, job_id: &str) -> PathBuf {
+        self.job_logs_dir()
+            .join(format!("{}.jsonl", id_path_component(job_id)))
+    }
+
     pub fn cache_dir(&self) -> PathBuf {
         self.base_dir.join("cache")
     }
@@ -104,6 +213,11 @@ impl AppPaths {
         self.base_dir.join("secrets")
     }
 
+    /// Random key that encrypts the files under [`Self::secrets_dir`].
+    pub fn secret_key_path(&self) -> PathBuf {
+        self.config_dir().join("secret.key")
+    }
+
     pub fn job_secrets_dir(&self) -> PathBuf {
         self.secrets_dir().join("jobs")
     }
@@ -343,6 +457,11 @@ impl AppPaths {
         self.python_toolchain_dir().join("models")
     }
 
+    /// Drop-in location for model files users download themselves (see `tools::scan_user_models`).
+    pub fn user_models_dir(&self) -> PathBuf {
+        self.base_dir.join("user_models")
+    }
+
     pub fn batch_on_import_rules_path(&self) -> PathBuf {
         self.config_dir().join("batch_on_import_rules.json")
     }
@@ -351,6 +470,14 @@ impl AppPaths {
         self.config_dir().join("safe_mode.json")
     }
 
+    pub fn pack_integrity_config_path(&self) -> PathBuf {
+        self.config_dir().join("pack_integrity.json")
+    }
+
+    pub fn download_disk_space_config_path(&self) -> PathBuf {
+        self.config_dir().join("download_disk_space.json")
+    }
+
     pub fn download_presets_config_path(&self) -> PathBuf {
         self.config_dir().join("download_presets.json")
     }
@@ -396,7 +523,47 @@ impl AppPaths {
         path
     }
 
+    pub fn custom_tool_paths_config_path(&self) -> PathBuf {
+        self.config_dir().join("custom_tool_paths_v1.json")
+    }
+
+    pub fn proxy_config_path(&self) -> PathBuf {
+        self.config_dir().join("proxy_v1.json")
+    }
+
+    pub fn tts_defaults_config_path(&self) -> PathBuf {
+        self.config_dir().join("tts_defaults_v1.json")
+    }
+
+    pub fn diagnostics_trace_config_path(&self) -> PathBuf {
+        self.config_dir().join("diagnostics_trace_config_v1.json")
+    }
+
+    pub fn custom_ffmpeg_path(&self) -> Option<PathBuf> {
+        crate::config::load_custom_tool_paths(self)
+            .ok()
+            .and_then(|config| config.ffmpeg)
+            .map(PathBuf::from)
+    }
+
+    pub fn custom_ffprobe_path(&self) -> Option<PathBuf> {
+        crate::config::load_custom_tool_paths(self)
+            .ok()
+            .and_then(|config| config.ffprobe)
+            .map(PathBuf::from)
+    }
+
+    pub fn custom_ytdlp_path(&self) -> Option<PathBuf> {
+        crate::config::load_custom_tool_paths(self)
+            .ok()
+            .and_then(|config| config.ytdlp)
+            .map(PathBuf::from)
+    }
+
     pub fn ffmpeg_cmd(&self) -> PathBuf {
+        if let Some(custom) = self.custom_ffmpeg_path() {
+            return custom;
+        }
         let path = self.ffmpeg_bin_path();
         if path.exists() {
             path
@@ -406,6 +573,9 @@ impl AppPaths {
     }
 
     pub fn ffprobe_cmd(&self) -> PathBuf {
+        if let Some(custom) = self.custom_ffprobe_path() {
+            return custom;
+        }
         let path = self.ffprobe_bin_path();
         if path.exists() {
             path
@@ -418,28 +588,211 @@ impl AppPaths {
         self.models_dir().join(model_id).join(version)
     }
 
-    pub fn ensure_dirs(&self) -> std::io::Result<()> {
-        std::fs::create_dir_all(self.config_dir())?;
-        std::fs::create_dir_all(self.library_dir())?;
-        std::fs::create_dir_all(self.derived_items_dir())?;
-        std::fs::create_dir_all(self.derived_jobs_dir())?;
-        std::fs::create_dir_all(self.voice_templates_dir())?;
-        std::fs::create_dir_all(self.voice_library_dir())?;
-        std::fs::create_dir_all(self.db_dir())?;
-        std::fs::create_dir_all(self.logs_dir())?;
-        std::fs::create_dir_all(self.job_logs_dir())?;
-        std::fs::create_dir_all(self.youtube_subscription_state_dir())?;
-        std::fs::create_dir_all(self.default_diagnostics_trace_dir())?;
-        std::fs::create_dir_all(self.cache_dir())?;
-        std::fs::create_dir_all(self.thumbnail_cache_dir())?;
-        std::fs::create_dir_all(self.job_secrets_dir())?;
-        std::fs::create_dir_all(self.models_dir())?;
-        std::fs::create_dir_all(self.ffmpeg_dir())?;
-        Ok(())
+    pub fn ensure_dirs(&self) -> std::io::Result<EnsureDirsResult> {
+        let mut result = EnsureDirsResult::default();
+        for dir in [
+            self.config_dir(),
+            self.library_dir(),
+            self.derived_items_dir(),
+            self.derived_jobs_dir(),
+            self.voice_templates_dir(),
+            self.voice_library_dir(),
+            self.db_dir(),
+            self.logs_dir(),
+            self.job_logs_dir(),
+            self.youtube_subscription_state_dir(),
+            self.default_diagnostics_trace_dir(),
+            self.cache_dir(),
+            self.thumbnail_cache_dir(),
+            self.job_secrets_dir(),
+            self.models_dir(),
+            self.user_models_dir(),
+            self.ffmpeg_dir(),
+        ] {
+            if dir.is_dir() {
+                result.already_existed.push(dir);
+                continue;
+            }
+            std::fs::create_dir_all(&dir)?;
+            // Logged so first-launch permission problems show which directory failed next.
+            eprintln!("ensure_dirs: created {}", dir.to_string_lossy());
+            result.created.push(dir);
+        }
+        Ok(result)
     }
 
     pub fn normalize_base_dir(base_dir: &Path) -> PathBuf {
         // Keep it simple for now; callers should provide an app-specific directory.
         base_dir.to_path_buf()
     }
+
+    /// Portable mode is requested by the `--portable` CLI flag or by a `portable.txt` marker
+    /// file placed directly in the app resource directory (next to the executable on Windows
+    /// installs, e.g. `<install dir>/portable.txt`).
+    pub fn portable_mode_requested<I, S>(args: I, resource_dir: Option<&Path>) -> bool
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        args.into_iter()
+            .any(|value| value.as_ref().trim() == PORTABLE_CLI_FLAG)
+            || resource_dir
+                .map(|dir| dir.join(PORTABLE_MARKER_FILE_NAME).is_file())
+                .unwrap_or(false)
+    }
+
+    /// Base dir used in portable mode: `<exe dir>/data`.
+    pub fn portable_base_dir() -> std::io::Result<PathBuf> {
+        let exe = std::env::current_exe()?;
+        let exe_dir = exe.parent().ok_or_else(|| {
+            std::io::Error::new(
+                std::io::ErrorKind::NotFound,
+                "executable has no parent directory",
+            )
+        })?;
+        Ok(exe_dir.join("data"))
+    }
+
+    /// Pointer file left in a base dir after `migration::migrate_storage` moved app data elsewhere.
+    pub fn base_dir_override_path(&self) -> PathBuf {
+        self.base_dir.join(BASE_DIR_OVERRIDE_FILE_NAME)
+    }
+
+    pub fn base_dir_override(&self) -> std::io::Result<Option<PathBuf>> {
+        let path = self.base_dir_override_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let raw = std::fs::read_to_string(path)?;
+        let trimmed = raw.trim();
+        if trimmed.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(PathBuf::from(trimmed)))
+    }
+
+    /// Follows base dir override pointers (bounded, to survive accidental cycles) and returns the
+    /// directory app data should be loaded from.
+    pub fn resolve_base_dir(base_dir: &Path) -> PathBuf {
+        let mut current = base_dir.to_path_buf();
+        for _ in 0..MAX_BASE_DIR_OVERRIDE_HOPS {
+            match AppPaths::new(current.clone()).base_dir_override() {
+                Ok(Some(next)) if next.is_dir() && next != current => current = next,
+                _ => break,
+            }
+        }
+        current
+    }
+
+    pub fn is_portable(&self) -> bool {
+        Self::portable_base_dir()
+            .map(|dir| dir == self.base_dir)
+            .unwrap_or(false)
+    }
+}
+
+pub const DATA_DIR_ENV_VAR: &str = "VOXVULGI_DATA_DIR";
+pub const PORTABLE_CLI_FLAG: &str = "--portable";
+pub const PORTABLE_MARKER_FILE_NAME: &str = "portable.txt";
+pub const BASE_DIR_OVERRIDE_FILE_NAME: &str = "base_dir_override.txt";
+const MAX_BASE_DIR_OVERRIDE_HOPS: usize = 4;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn invalid_ids_never_escape_their_parent_dir() {
+        let paths = AppPaths::new(PathBuf::from("/app"));
+        let valid = "00000000-0000-0000-0000-000000000000";
+        assert!(validate_item_id(valid).is_ok());
+        assert_eq!(
+            paths.derived_item_dir(valid),
+            paths.derived_items_dir().join(valid)
+        );
+        for id in ["", "../../../etc", "abc def", "a\0b", "invalid-0011"] {
+            assert!(validate_item_id(id).is_err(), "{id:?}");
+            for path in [
+                paths.derived_item_dir(id),
+                paths.job_artifacts_dir(id, None),
+                paths.job_artifacts_dir("", Some(id)),
+            ] {
+                let name = path.file_name().and_then(|n| n.to_str()).expect("name");
+                assert!(name.starts_with("invalid-"), "{id:?} -> {name}");
+                assert_eq!(path.components().count(), 5, "{id:?}");
+            }
+            let log = paths.job_log_path(id);
+            assert_eq!(log.parent(), Some(paths.job_logs_dir().as_path()));
+        }
+        assert_ne!(
+            paths.derived_item_dir(""),
+            paths.derived_item_dir("abc def")
+        );
+
+        // Plain non-UUID ids from older data are not valid item ids but keep their dir names.
+        assert!(validate_item_id("legacy_item-1").is_err());
+        assert_eq!(
+            paths.derived_item_dir("legacy_item-1"),
+            paths.derived_items_dir().join("legacy_item-1")
+        );
+    }
+
+    #[test]
+    fn ensure_dirs_reports_created_then_existing_dirs() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().join("app"));
+
+        let first = paths.ensure_dirs().expect("first");
+        assert!(!first.created.is_empty());
+        assert!(first.created.iter().all(|path| path.is_dir()));
+
+        let second = paths.ensure_dirs().expect("second");
+        assert!(second.created.is_empty());
+        assert_eq!(
+            second.already_existed.len(),
+            first.created.len() + first.already_existed.len()
+        );
+    }
+
+    #[test]
+    fn portable_mode_is_requested_by_flag_or_marker_file() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        assert!(!AppPaths::portable_mode_requested(
+            ["voxvulgi", "--safe-mode"],
+            Some(dir.path())
+        ));
+        assert!(AppPaths::portable_mode_requested(
+            ["voxvulgi", "--portable"],
+            None
+        ));
+
+        std::fs::write(dir.path().join(PORTABLE_MARKER_FILE_NAME), "").expect("marker");
+        assert!(AppPaths::portable_mode_requested(
+            ["voxvulgi"],
+            Some(dir.path())
+        ));
+
+        let base_dir = AppPaths::portable_base_dir().expect("portable base");
+        assert!(base_dir.ends_with("data"));
+        assert!(AppPaths::new(base_dir).is_portable());
+    }
+
+    #[test]
+    fn env_data_dir_ignores_blank_or_relative_values() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let raw = format!(" {} ", dir.path().to_string_lossy());
+        let paths = AppPaths::from_env_value(&raw).expect("absolute dir");
+        assert_eq!(paths.base_dir, dir.path());
+        assert_eq!(paths.source, PathSource::Environment);
+
+        assert!(AppPaths::from_env_value("").is_none());
+        assert!(AppPaths::from_env_value("   ").is_none());
+        assert!(AppPaths::from_env_value("relative/data").is_none());
+        assert_eq!(
+            AppPaths::new(dir.path().to_path_buf()).source,
+            PathSource::OsAppData
+        );
+    }
 }
@@ -246,6 +246,97 @@ WHERE item_id=?1 AND speaker_key=?2
     .map_err(|e| EngineError::Database(e))
 }
 
+pub fn delete_item_speaker_setting(
+    paths: &AppPaths,
+    item_id: &str,
+    speaker_key: &str,
+) -> Result<()> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    conn.execute(
+        "DELETE FROM item_speaker WHERE item_id=?1 AND speaker_key=?2",
+        params![item_id.trim(), speaker_key.trim()],
+    )?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GlobalSpeakerSummary {
+    pub speaker_key: String,
+    pub item_count: usize,
+    pub has_voice_id: bool,
+    pub has_voice_profile: bool,
+}
+
+pub fn list_all_speaker_keys(paths: &AppPaths) -> Result<Vec<GlobalSpeakerSummary>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    let mut stmt = conn.prepare(
+        r#"
+SELECT
+  speaker_key,
+  COUNT(DISTINCT item_id),
+  MAX(CASE WHEN tts_voice_id IS NOT NULL AND TRIM(tts_voice_id) <> '' THEN 1 ELSE 0 END),
+  MAX(CASE WHEN tts_voice_profile_path IS NOT NULL AND TRIM(tts_voice_profile_path) <> '' THEN 1 ELSE 0 END)
+FROM item_speaker
+GROUP BY speaker_key
+ORDER BY speaker_key ASC
+"#,
+    )?;
+
+    let rows = stmt
+        .query_map([], |row| {
+            Ok(GlobalSpeakerSummary {
+                speaker_key: row.get(0)?,
+                item_count: row.get::<_, i64>(1)?.max(0) as usize,
+                has_voice_id: row.get::<_, i64>(2)? != 0,
+                has_voice_profile: row.get::<_, i64>(3)? != 0,
+            })
+        })?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(rows)
+}
+
+/// Sets `tts_voice_id` on the `speaker_key` row of each listed item. Items without that
+/// speaker are left untouched. Returns the number of rows updated.
+pub fn bulk_set_speaker_voice(
+    paths: &AppPaths,
+    speaker_key: &str,
+    voice_id: Option<String>,
+    item_ids: &[String],
+) -> Result<usize> {
+    let speaker_key = speaker_key.trim();
+    if speaker_key.is_empty() {
+        return Err(EngineError::InstallFailed(
+            "speaker_key is empty".to_string(),
+        ));
+    }
+    let voice_id = normalize_optional_string(voice_id);
+
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    let tx = conn.unchecked_transaction()?;
+    let now = now_ms();
+    let mut updated = 0_usize;
+    {
+        let mut stmt = tx.prepare(
+            "UPDATE item_speaker SET tts_voice_id=?1, updated_at_ms=?2 WHERE item_id=?3 AND speaker_key=?4",
+        )?;
+        for item_id in item_ids {
+            let item_id = item_id.trim();
+            if item_id.is_empty() {
+                continue;
+            }
+            updated += stmt.execute(params![voice_id, now, item_id, speaker_key])?;
+        }
+    }
+    tx.commit()?;
+    Ok(updated)
+}
+
 fn normalize_optional_string(value: Option<String>) -> Option<String> {
     value.and_then(|v| {
         let t = v.trim().to_string();
@@ -326,3 +417,74 @@ fn now_ms() -> i64 {
         .unwrap_or_default()
         .as_millis() as i64
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn seed_item(paths: &AppPaths, item_id: &str) {
+        let conn = db::open(paths).expect("open db");
+        db::migrate(&conn).expect("migrate");
+        conn.execute(
+            "INSERT INTO library_item (id, created_at_ms, source_type, source_uri, title, media_path) VALUES (?1, 1, 'file', ?2, ?1, ?3)",
+            params![item_id, format!("file://{item_id}"), format!("D:/media/{item_id}.mp4")],
+        )
+        .expect("insert item");
+    }
+
+    fn upsert(paths: &AppPaths, item_id: &str, speaker_key: &str, profile: Option<&str>) {
+        upsert_item_speaker_setting(
+            paths,
+            item_id,
+            speaker_key,
+            None,
+            None,
+            None,
+            profile.map(ToString::to_string),
+            None,
+            None,
+            None,
+            None,
+            None,
+            None,
+        )
+        .expect("upsert");
+    }
+
+    #[test]
+    fn speaker_keys_aggregate_across_items_and_support_bulk_voice_and_delete() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        for item_id in ["item-1", "item-2", "item-3"] {
+            seed_item(&paths, item_id);
+        }
+        upsert(&paths, "item-1", "S1", Some("D:/voices/s1.wav"));
+        upsert(&paths, "item-2", "S1", None);
+        upsert(&paths, "item-3", "S2", None);
+
+        let updated = bulk_set_speaker_voice(
+            &paths,
+            "S1",
+            Some("voice-a".to_string()),
+            &["item-2".to_string(), "item-3".to_string()],
+        )
+        .expect("bulk set");
+        assert_eq!(updated, 1);
+
+        let all = list_all_speaker_keys(&paths).expect("list all");
+        assert_eq!(all.len(), 2);
+        assert_eq!(all[0].speaker_key, "S1");
+        assert_eq!(all[0].item_count, 2);
+        assert!(all[0].has_voice_id);
+        assert!(all[0].has_voice_profile);
+        assert!(!all[1].has_voice_id);
+        assert!(!all[1].has_voice_profile);
+
+        delete_item_speaker_setting(&paths, "item-3", "S2").expect("delete");
+        let all = list_all_speaker_keys(&paths).expect("list all");
+        assert_eq!(all.len(), 1);
+        assert!(list_item_speaker_settings(&paths, "item-3")
+            .expect("list")
+            .is_empty());
+    }
+}
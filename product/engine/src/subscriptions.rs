@@ -14,9 +14,20 @@ use uuid::Uuid;
 
 const EXPORT_SCHEMA_VERSION: u32 = 1;
 const DEFAULT_SUBSCRIPTION_MAP: &str = "subscription";
+const IMPORT_JSON_MAX_SUBSCRIPTIONS: usize = 500;
 const DEFAULT_REFRESH_INTERVAL_MINUTES: i64 = 60;
 const MIN_REFRESH_INTERVAL_MINUTES: i64 = 5;
 const MAX_REFRESH_INTERVAL_MINUTES: i64 = 10080;
+pub const AUTO_MAX_ITEMS_CAP: usize = 500;
+/// Lowest auto `max_items`, so a channel that was dormant for a while still catches a new burst.
+const AUTO_MAX_ITEMS_FLOOR: usize = 10;
+/// Rate multiplier for a refresh that filled `max_items`; the real rate is at least that high.
+const AUTO_MAX_ITEMS_SATURATED_GROWTH: f64 = 2.0;
+const AUTO_MAX_ITEMS_WINDOW_DAYS: f64 = 7.0;
+/// Floor for the refresh gap so back-to-back refreshes do not inflate the post rate.
+const AUTO_MAX_ITEMS_MIN_DAYS: f64 = 1.0 / 24.0;
+const AUTO_MAX_ITEMS_EMA_KEEP: f64 = 0.8;
+const MS_PER_DAY: f64 = 24.0 * 60.0 * 60.0 * 1000.0;
 const FOURKVDP_SUBSCRIPTIONS_JSON_FILENAME: &str = "subscriptions.json";
 const FOURKVDP_SUBSCRIPTION_ENTRIES_CSV_FILENAME: &str = "subscription_entries.csv";
 const YT_DLP_ARCHIVE_FILENAME: &str = "voxvulgi_youtube_archive.txt";
@@ -46,12 +57,30 @@ pub struct YoutubeSubscriptionRow {
     pub refresh_interval_minutes: i64,
     pub last_queued_at_ms: Option<i64>,
     pub last_error_at_ms: Option<i64>,
+    /// Error message of the most recent failed refresh; cleared on success.
+    #[serde(default)]
+    pub last_error: Option<String>,
     pub consecutive_failures: i64,
     pub next_allowed_refresh_at_ms: Option<i64>,
     pub created_at_ms: i64,
     pub updated_at_ms: i64,
     #[serde(default)]
     pub group_ids: Vec<String>,
+    #[serde(default)]
+    pub filter_keywords_exclude: Vec<String>,
+    #[serde(default)]
+    pub filter_keywords_include: Option<Vec<String>>,
+    /// Overrides the refresh job's `max_items` for this subscription.
+    #[serde(default)]
+    pub max_items_per_refresh: Option<usize>,
+    /// Derive `max_items` from `avg_posts_per_day` instead of a fixed count.
+    #[serde(default)]
+    pub auto_max_items: bool,
+    /// Exponential moving average of new posts per day, updated after each refresh.
+    #[serde(default)]
+    pub avg_posts_per_day: Option<f64>,
+    #[serde(default)]
+    pub last_refreshed_at_ms: Option<i64>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -71,6 +100,15 @@ pub struct YoutubeSubscriptionUpsert {
     #[serde(default)]
     pub group_ids: Vec<String>,
     pub refresh_interval_minutes: Option<i64>,
+    #[serde(default)]
+    pub filter_keywords_exclude: Vec<String>,
+    #[serde(default)]
+    pub filter_keywords_include: Option<Vec<String>>,
+    /// Must be within `1..=MAX_DOWNLOAD_BATCH_URLS` when set.
+    #[serde(default)]
+    pub max_items_per_refresh: Option<usize>,
+    #[serde(default)]
+    pub auto_max_items: bool,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -162,7 +200,9 @@ pub struct YoutubeSubscriptionsExportSummary {
 pub struct YoutubeSubscriptionsImportSummary {
     pub total_in_file: usize,
     pub inserted: usize,
-    pub updated: usize,
+    pub skipped_duplicates: usize,
+    pub skipped_invalid: usize,
+    pub validation_errors: Vec<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -206,6 +246,13 @@ struct YoutubeSubscriptionsExportFile {
     subscriptions: Vec<YoutubeSubscriptionsExportEntry>,
 }
 
+/// Import side of [`YoutubeSubscriptionsExportFile`]; rows are validated one by one.
+#[derive(Debug, Clone, Deserialize)]
+struct YoutubeSubscriptionsImportFile {
+    schema_version: u32,
+    subscriptions: Vec<serde_json::Value>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct YoutubeSubscriptionsExportEntry {
     title: String,
@@ -220,6 +267,14 @@ struct YoutubeSubscriptionsExportEntry {
     group_ids: Vec<String>,
     #[serde(default)]
     refresh_interval_minutes: Option<i64>,
+    #[serde(default)]
+    filter_keywords_exclude: Vec<String>,
+    #[serde(default)]
+    filter_keywords_include: Option<Vec<String>>,
+    #[serde(default)]
+    max_items_per_refresh: Option<usize>,
+    #[serde(default)]
+    auto_max_items: bool,
 }
 
 pub fn list_youtube_subscriptions(paths: &AppPaths) -> Result<Vec<YoutubeSubscriptionRow>> {
@@ -243,7 +298,14 @@ SELECT
   consecutive_failures,
   next_allowed_refresh_at_ms,
   created_at_ms,
-  updated_at_ms
+  updated_at_ms,
+  filter_keywords_exclude,
+  filter_keywords_include,
+  last_error,
+  max_items_per_refresh,
+  auto_max_items,
+  avg_posts_per_day,
+  last_refreshed_at_ms
 FROM youtube_subscription
 ORDER BY active DESC, updated_at_ms DESC, created_at_ms DESC
 "#,
@@ -256,6 +318,16 @@ ORDER BY active DESC, updated_at_ms DESC, created_at_ms DESC
     Ok(hydrate_auth_session_flags(paths, rows))
 }
 
+/// Subscriptions whose most recent refresh failed.
+pub fn list_youtube_subscriptions_with_errors(
+    paths: &AppPaths,
+) -> Result<Vec<YoutubeSubscriptionRow>> {
+    Ok(list_youtube_subscriptions(paths)?
+        .into_iter()
+        .filter(|sub| sub.last_error.is_some())
+        .collect())
+}
+
 pub fn upsert_youtube_subscription(
     paths: &AppPaths,
     req: YoutubeSubscriptionUpsert,
@@ -281,7 +353,11 @@ SET
   active = ?6,
   preset_id = ?7,
   refresh_interval_minutes = ?8,
-  updated_at_ms = ?9
+  updated_at_ms = ?9,
+  filter_keywords_exclude = ?11,
+  filter_keywords_include = ?12,
+  max_items_per_refresh = ?13,
+  auto_max_items = ?14
 WHERE id = ?10
 "#,
             params![
@@ -295,6 +371,10 @@ WHERE id = ?10
                 normalized.refresh_interval_minutes,
                 now,
                 id,
+                keywords_to_column(Some(&normalized.filter_keywords_exclude)),
+                keywords_to_column(normalized.filter_keywords_include.as_deref()),
+                normalized.max_items_per_refresh,
+                bool_to_i64(normalized.auto_max_items),
             ],
         )?;
         if changed > 0 {
@@ -321,8 +401,12 @@ INSERT INTO youtube_subscription (
   consecutive_failures,
   next_allowed_refresh_at_ms,
   created_at_ms,
-  updated_at_ms
-) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, NULL, NULL, 0, NULL, ?10, ?10)
+  updated_at_ms,
+  filter_keywords_exclude,
+  filter_keywords_include,
+  max_items_per_refresh,
+  auto_max_items
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, NULL, NULL, 0, NULL, ?10, ?10, ?11, ?12, ?13, ?14)
 ON CONFLICT(source_url) DO UPDATE SET
   title = excluded.title,
   folder_map = excluded.folder_map,
@@ -331,7 +415,11 @@ ON CONFLICT(source_url) DO UPDATE SET
   active = excluded.active,
   preset_id = excluded.preset_id,
   refresh_interval_minutes = excluded.refresh_interval_minutes,
-  updated_at_ms = excluded.updated_at_ms
+  updated_at_ms = excluded.updated_at_ms,
+  filter_keywords_exclude = excluded.filter_keywords_exclude,
+  filter_keywords_include = excluded.filter_keywords_include,
+  max_items_per_refresh = excluded.max_items_per_refresh,
+  auto_max_items = excluded.auto_max_items
 "#,
             params![
                 id,
@@ -344,6 +432,10 @@ ON CONFLICT(source_url) DO UPDATE SET
                 &normalized.preset_id,
                 normalized.refresh_interval_minutes,
                 now,
+                keywords_to_column(Some(&normalized.filter_keywords_exclude)),
+                keywords_to_column(normalized.filter_keywords_include.as_deref()),
+                normalized.max_items_per_refresh,
+                bool_to_i64(normalized.auto_max_items),
             ],
         )?;
     }
@@ -394,7 +486,7 @@ pub fn queue_youtube_subscription(paths: &AppPaths, id: &str) -> Result<Vec<jobs
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
     let sub = subscription_by_id_conn(&conn, id)?
-        .ok_or_else(|| EngineError::InstallFailed(format!("subscription not found: {id}")))?;
+        .ok_or_else(|| EngineError::not_found("subscription", id))?;
     drop(conn);
     queue_subscription_internal(paths, &sub, Some(Uuid::new_v4().to_string()))
 }
@@ -419,7 +511,14 @@ SELECT
   consecutive_failures,
   next_allowed_refresh_at_ms,
   created_at_ms,
-  updated_at_ms
+  updated_at_ms,
+  filter_keywords_exclude,
+  filter_keywords_include,
+  last_error,
+  max_items_per_refresh,
+  auto_max_items,
+  avg_posts_per_day,
+  last_refreshed_at_ms
 FROM youtube_subscription
 WHERE active = 1
 ORDER BY updated_at_ms DESC, created_at_ms DESC
@@ -568,7 +667,14 @@ SELECT
   sub.consecutive_failures,
   sub.next_allowed_refresh_at_ms,
   sub.created_at_ms,
-  sub.updated_at_ms
+  sub.updated_at_ms,
+  sub.filter_keywords_exclude,
+  sub.filter_keywords_include,
+  sub.last_error,
+  sub.max_items_per_refresh,
+  sub.auto_max_items,
+  sub.avg_posts_per_day,
+  sub.last_refreshed_at_ms
 FROM youtube_subscription sub
 JOIN youtube_subscription_group_member gm ON gm.subscription_id = sub.id
 WHERE gm.group_id = ?1 AND sub.active = 1
@@ -597,25 +703,139 @@ ORDER BY sub.updated_at_ms DESC, sub.created_at_ms DESC
     Ok(queued_jobs)
 }
 
-pub fn record_subscription_refresh_success(paths: &AppPaths, subscription_id: &str) -> Result<()> {
+/// New items a refresh found, and the `max_items` it was allowed to list.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RefreshItemsSeen {
+    pub new_items: usize,
+    pub max_items: usize,
+}
+
+impl RefreshItemsSeen {
+    /// True when every listed item was new, so more may have been cut off by `max_items`.
+    fn saturated(&self) -> bool {
+        self.new_items >= self.max_items
+    }
+}
+
+/// Clears failure backoff and, when the refresh listed items, folds the observed post rate into
+/// `avg_posts_per_day`.
+pub fn record_subscription_refresh_success(
+    paths: &AppPaths,
+    subscription_id: &str,
+    seen: Option<RefreshItemsSeen>,
+) -> Result<()> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
+    let now = now_ms();
+    let previous: Option<(Option<f64>, Option<i64>)> = conn
+        .query_row(
+            "SELECT avg_posts_per_day, last_refreshed_at_ms FROM youtube_subscription WHERE id = ?1",
+            params![subscription_id],
+            |row| Ok((row.get(0)?, row.get(1)?)),
+        )
+        .optional()?;
+    let Some((avg_posts_per_day, last_refreshed_at_ms)) = previous else {
+        return Ok(());
+    };
+    let (avg_posts_per_day, last_refreshed_at_ms) = match seen {
+        Some(seen) => (
+            next_avg_posts_per_day(avg_posts_per_day, last_refreshed_at_ms, seen, now),
+            Some(now),
+        ),
+        None => (avg_posts_per_day, last_refreshed_at_ms),
+    };
     conn.execute(
         r#"
 UPDATE youtube_subscription
 SET
   consecutive_failures = 0,
   last_error_at_ms = NULL,
+  last_error = NULL,
   next_allowed_refresh_at_ms = NULL,
+  avg_posts_per_day = ?2,
+  last_refreshed_at_ms = ?3,
   updated_at_ms = ?1
-WHERE id = ?2
+WHERE id = ?4
 "#,
-        params![now_ms(), subscription_id],
+        params![
+            now,
+            avg_posts_per_day,
+            last_refreshed_at_ms,
+            subscription_id
+        ],
     )?;
     Ok(())
 }
 
-pub fn record_subscription_refresh_failure(paths: &AppPaths, subscription_id: &str) -> Result<()> {
+fn days_since_last_refresh(last_refreshed_at_ms: Option<i64>, now_ms: i64) -> Option<f64> {
+    let last = last_refreshed_at_ms?;
+    Some((now_ms.saturating_sub(last) as f64 / MS_PER_DAY).max(AUTO_MAX_ITEMS_MIN_DAYS))
+}
+
+/// Folds the refresh into the moving average. A saturated refresh only saw a lower bound of the
+/// real rate, so it is scaled up and never lowers the average; otherwise `max_items` could only
+/// ever shrink.
+fn next_avg_posts_per_day(
+    current: Option<f64>,
+    last_refreshed_at_ms: Option<i64>,
+    seen: RefreshItemsSeen,
+    now_ms: i64,
+) -> Option<f64> {
+    let Some(days) = days_since_last_refresh(last_refreshed_at_ms, now_ms) else {
+        return current;
+    };
+    let mut observed = seen.new_items as f64 / days;
+    if seen.saturated() {
+        observed *= AUTO_MAX_ITEMS_SATURATED_GROWTH;
+    }
+    let next = match current {
+        Some(old) => AUTO_MAX_ITEMS_EMA_KEEP * old + (1.0 - AUTO_MAX_ITEMS_EMA_KEEP) * observed,
+        None => observed,
+    };
+    Some(match current {
+        Some(old) if seen.saturated() => next.max(old),
+        _ => next,
+    })
+}
+
+/// Result of sizing a refresh from the subscription's posting history.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct AutoMaxItems {
+    pub value: usize,
+    pub avg_posts_per_day: Option<f64>,
+    pub days_since_last: Option<f64>,
+}
+
+/// Sizes `max_items` to cover the posts expected since the last refresh (at least a week's
+/// worth), kept within `AUTO_MAX_ITEMS_FLOOR..=AUTO_MAX_ITEMS_CAP`. Without history, falls back
+/// to `fallback`.
+pub fn compute_auto_max_items(
+    sub: &YoutubeSubscriptionRow,
+    fallback: usize,
+    now_ms: i64,
+) -> AutoMaxItems {
+    let days_since_last = days_since_last_refresh(sub.last_refreshed_at_ms, now_ms);
+    let value = match sub.avg_posts_per_day {
+        Some(avg) if avg.is_finite() && avg >= 0.0 => {
+            let window = days_since_last
+                .unwrap_or(AUTO_MAX_ITEMS_WINDOW_DAYS)
+                .max(AUTO_MAX_ITEMS_WINDOW_DAYS);
+            (avg * window).ceil() as usize
+        }
+        _ => fallback,
+    };
+    AutoMaxItems {
+        value: value.clamp(AUTO_MAX_ITEMS_FLOOR, AUTO_MAX_ITEMS_CAP),
+        avg_posts_per_day: sub.avg_posts_per_day,
+        days_since_last,
+    }
+}
+
+pub fn record_subscription_refresh_failure(
+    paths: &AppPaths,
+    subscription_id: &str,
+    error: &str,
+) -> Result<()> {
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
     let now = now_ms();
@@ -643,14 +863,16 @@ SET
   consecutive_failures = ?1,
   last_error_at_ms = ?2,
   next_allowed_refresh_at_ms = ?3,
-  updated_at_ms = ?2
+  updated_at_ms = ?2,
+  last_error = ?5
 WHERE id = ?4
 "#,
         params![
             next_failures,
             now,
             now.saturating_add(delay_ms),
-            subscription_id
+            subscription_id,
+            error
         ],
     )?;
     Ok(())
@@ -1217,6 +1439,10 @@ pub fn export_youtube_subscriptions_json(
                 preset_id: row.preset_id.clone(),
                 group_ids: row.group_ids.clone(),
                 refresh_interval_minutes: Some(row.refresh_interval_minutes),
+                filter_keywords_exclude: row.filter_keywords_exclude.clone(),
+                filter_keywords_include: row.filter_keywords_include.clone(),
+                max_items_per_refresh: row.max_items_per_refresh,
+                auto_max_items: row.auto_max_items,
             })
             .collect(),
     };
@@ -1235,27 +1461,116 @@ pub fn export_youtube_subscriptions_json(
     })
 }
 
+/// Writes subscriptions as an OPML 2.0 feed list for other readers. Channel URLs become RSS
+/// outlines (`xmlUrl`); handles, playlists and videos are kept as `type="link"` outlines.
+pub fn export_youtube_subscriptions_opml(
+    paths: &AppPaths,
+    out_path: &Path,
+) -> Result<YoutubeSubscriptionsExportSummary> {
+    let rows = list_youtube_subscriptions(paths)?;
+    let mut out = String::new();
+    out.push_str("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+    out.push_str("<opml version=\"2.0\">\n");
+    out.push_str("  <head>\n");
+    out.push_str("    <title>VoxVulgi YouTube subscriptions</title>\n");
+    out.push_str("  </head>\n");
+    out.push_str("  <body>\n");
+    for row in &rows {
+        let title = opml_escape(&row.title);
+        let html_url = opml_escape(&row.source_url);
+        match youtube_channel_id_from_url(&row.source_url) {
+            Some(channel_id) => {
+                let xml_url = opml_escape(&format!(
+                    "https://www.youtube.com/feeds/videos.xml?channel_id={channel_id}"
+                ));
+                out.push_str(&format!(
+                    "    <outline type=\"rss\" text=\"{title}\" title=\"{title}\" xmlUrl=\"{xml_url}\" htmlUrl=\"{html_url}\"/>\n"
+                ));
+            }
+            None => {
+                out.push_str(&format!(
+                    "    <outline type=\"link\" text=\"{title}\" title=\"{title}\" htmlUrl=\"{html_url}\"/>\n"
+                ));
+            }
+        }
+    }
+    out.push_str("  </body>\n");
+    out.push_str("</opml>\n");
+
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(out_path, out)?;
+
+    Ok(YoutubeSubscriptionsExportSummary {
+        out_path: out_path.to_string_lossy().to_string(),
+        count: rows.len(),
+    })
+}
+
+fn opml_escape(value: &str) -> String {
+    let mut out = String::with_capacity(value.len());
+    for ch in value.chars() {
+        match ch {
+            '&' => out.push_str("&amp;"),
+            '<' => out.push_str("&lt;"),
+            '>' => out.push_str("&gt;"),
+            '"' => out.push_str("&quot;"),
+            '\'' => out.push_str("&apos;"),
+            c if c.is_control() => out.push(' '),
+            c => out.push(c),
+        }
+    }
+    out
+}
+
+/// Imports subscriptions exported by [`export_youtube_subscriptions_json`]. Rows whose
+/// `source_url` already exists (in the library or earlier in the file) are skipped, and invalid
+/// rows are reported in `validation_errors` instead of failing the whole import.
 pub fn import_youtube_subscriptions_json(
     paths: &AppPaths,
     in_path: &Path,
 ) -> Result<YoutubeSubscriptionsImportSummary> {
     let bytes = std::fs::read(in_path)?;
-    let payload: YoutubeSubscriptionsExportFile = serde_json::from_slice(&bytes)?;
+    let payload: YoutubeSubscriptionsImportFile = serde_json::from_slice(&bytes)?;
     if payload.schema_version != EXPORT_SCHEMA_VERSION {
         return Err(EngineError::InstallFailed(format!(
             "unsupported subscriptions export schema_version: {}",
             payload.schema_version
         )));
     }
+    if payload.subscriptions.len() > IMPORT_JSON_MAX_SUBSCRIPTIONS {
+        return Err(EngineError::InstallFailed(format!(
+            "subscriptions import has {} entries (max {IMPORT_JSON_MAX_SUBSCRIPTIONS})",
+            payload.subscriptions.len()
+        )));
+    }
 
-    let conn = db::open(paths)?;
+    let mut conn = db::open(paths)?;
     db::migrate(&conn)?;
 
+    let mut seen: HashSet<String> = conn
+        .prepare("SELECT source_url FROM youtube_subscription")?
+        .query_map([], |row| row.get::<_, String>(0))?
+        .collect::<rusqlite::Result<_>>()?;
     let mut inserted = 0_usize;
-    let mut updated = 0_usize;
+    let mut skipped_duplicates = 0_usize;
+    let mut validation_errors = Vec::new();
     let now = now_ms();
-    for raw in &payload.subscriptions {
-        let normalized = normalize_upsert(YoutubeSubscriptionUpsert {
+    let tx = conn.transaction()?;
+    for (index, value) in payload.subscriptions.iter().enumerate() {
+        let raw: YoutubeSubscriptionsExportEntry = match serde_json::from_value(value.clone()) {
+            Ok(v) => v,
+            Err(e) => {
+                validation_errors.push(format!("entry {index}: {e}"));
+                continue;
+            }
+        };
+        if let Err(e) = jobs::normalize_direct_url(&raw.source_url) {
+            validation_errors.push(format!("entry {index}: {e}"));
+            continue;
+        }
+        let normalized = match normalize_upsert(YoutubeSubscriptionUpsert {
             id: None,
             title: raw.title.clone(),
             source_url: raw.source_url.clone(),
@@ -1268,11 +1583,24 @@ pub fn import_youtube_subscriptions_json(
             preset_id: raw.preset_id.clone(),
             group_ids: raw.group_ids.clone(),
             refresh_interval_minutes: raw.refresh_interval_minutes,
-        })?;
+            filter_keywords_exclude: raw.filter_keywords_exclude.clone(),
+            filter_keywords_include: raw.filter_keywords_include.clone(),
+            max_items_per_refresh: raw.max_items_per_refresh,
+            auto_max_items: raw.auto_max_items,
+        }) {
+            Ok(v) => v,
+            Err(e) => {
+                validation_errors.push(format!("entry {index}: {e}"));
+                continue;
+            }
+        };
+        if !seen.insert(normalized.source_url.clone()) {
+            skipped_duplicates += 1;
+            continue;
+        }
 
-        let existed =
-            subscription_by_source_url_conn(&conn, normalized.source_url.as_str())?.is_some();
-        conn.execute(
+        let id = Uuid::new_v4().to_string();
+        tx.execute(
             r#"
 INSERT INTO youtube_subscription (
   id,
@@ -1289,20 +1617,15 @@ INSERT INTO youtube_subscription (
   consecutive_failures,
   next_allowed_refresh_at_ms,
   created_at_ms,
-  updated_at_ms
-) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, NULL, NULL, 0, NULL, ?10, ?10)
-ON CONFLICT(source_url) DO UPDATE SET
-  title = excluded.title,
-  folder_map = excluded.folder_map,
-  output_dir_override = excluded.output_dir_override,
-  use_browser_cookies = excluded.use_browser_cookies,
-  active = excluded.active,
-  preset_id = excluded.preset_id,
-  refresh_interval_minutes = excluded.refresh_interval_minutes,
-  updated_at_ms = excluded.updated_at_ms
+  updated_at_ms,
+  filter_keywords_exclude,
+  filter_keywords_include,
+  max_items_per_refresh,
+  auto_max_items
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, NULL, NULL, 0, NULL, ?10, ?10, ?11, ?12, ?13, ?14)
 "#,
             params![
-                Uuid::new_v4().to_string(),
+                id,
                 normalized.title,
                 normalized.source_url,
                 normalized.folder_map,
@@ -1312,24 +1635,23 @@ ON CONFLICT(source_url) DO UPDATE SET
                 normalized.preset_id,
                 normalized.refresh_interval_minutes,
                 now,
+                keywords_to_column(Some(&normalized.filter_keywords_exclude)),
+                keywords_to_column(normalized.filter_keywords_include.as_deref()),
+                normalized.max_items_per_refresh,
+                bool_to_i64(normalized.auto_max_items),
             ],
         )?;
-        if let Some(saved) = subscription_by_source_url_conn(&conn, normalized.source_url.as_str())?
-        {
-            set_subscription_group_memberships_conn(&conn, &saved.id, &normalized.group_ids)?;
-        }
-
-        if existed {
-            updated += 1;
-        } else {
-            inserted += 1;
-        }
+        set_subscription_group_memberships_conn(&tx, &id, &normalized.group_ids)?;
+        inserted += 1;
     }
+    tx.commit()?;
 
     Ok(YoutubeSubscriptionsImportSummary {
         total_in_file: payload.subscriptions.len(),
         inserted,
-        updated,
+        skipped_duplicates,
+        skipped_invalid: validation_errors.len(),
+        validation_errors,
     })
 }
 
@@ -1454,6 +1776,10 @@ pub fn import_youtube_subscriptions_4kvdp_dir(
             preset_id: None,
             group_ids: Vec::new(),
             refresh_interval_minutes: Some(DEFAULT_REFRESH_INTERVAL_MINUTES),
+            filter_keywords_exclude: Vec::new(),
+            filter_keywords_include: None,
+            max_items_per_refresh: None,
+            auto_max_items: false,
         })?;
 
         let existed =
@@ -1632,6 +1958,10 @@ pub fn import_youtube_subscriptions_4kvdp_state(
             preset_id: None,
             group_ids: Vec::new(),
             refresh_interval_minutes: Some(DEFAULT_REFRESH_INTERVAL_MINUTES),
+            filter_keywords_exclude: Vec::new(),
+            filter_keywords_include: None,
+            max_items_per_refresh: None,
+            auto_max_items: false,
         })?;
 
         let existed =
@@ -2088,7 +2418,7 @@ fn merge_archive_file(path: &Path, video_ids: &HashSet<String>) -> std::io::Resu
     Ok((appended, skipped_existing))
 }
 
-fn read_archive_file_ids(path: &Path) -> std::io::Result<HashSet<String>> {
+pub(crate) fn read_archive_file_ids(path: &Path) -> std::io::Result<HashSet<String>> {
     let mut out: HashSet<String> = HashSet::new();
     if !path.exists() {
         return Ok(out);
@@ -2233,6 +2563,27 @@ fn fourkvd_normalize_dirname(dirname: &str) -> String {
     trimmed.to_string()
 }
 
+/// Returns the `UC...` id of `/channel/{id}` URLs. Handles (`/@name`) and legacy `/c/` or
+/// `/user/` paths cannot be resolved without a network lookup and yield `None`.
+pub(crate) fn youtube_channel_id_from_url(url: &str) -> Option<String> {
+    let parsed = Url::parse(url).ok()?;
+    let host = parsed.host_str()?.to_ascii_lowercase();
+    if host != "youtube.com" && host != "www.youtube.com" && !host.ends_with(".youtube.com") {
+        return None;
+    }
+    if let Some(id) = parsed.path().strip_prefix("/channel/") {
+        let out = id.split('/').next().unwrap_or("").trim().to_string();
+        if out.starts_with("UC")
+            && out
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+        {
+            return Some(out);
+        }
+    }
+    None
+}
+
 pub(crate) fn youtube_video_id_from_url(url: &str) -> Option<String> {
     let parsed = Url::parse(url).ok()?;
     let host = parsed.host_str()?.to_ascii_lowercase();
@@ -2443,9 +2794,7 @@ fn resolve_seed_target_subscriptions(
         if let Some(sub) = get_youtube_subscription_by_id(paths, id)? {
             return Ok(vec![sub]);
         }
-        return Err(EngineError::InstallFailed(format!(
-            "subscription not found: {id}"
-        )));
+        return Err(EngineError::not_found("subscription", id));
     }
 
     let mut subs = list_youtube_subscriptions(paths)?
@@ -2604,7 +2953,14 @@ SELECT
   consecutive_failures,
   next_allowed_refresh_at_ms,
   created_at_ms,
-  updated_at_ms
+  updated_at_ms,
+  filter_keywords_exclude,
+  filter_keywords_include,
+  last_error,
+  max_items_per_refresh,
+  auto_max_items,
+  avg_posts_per_day,
+  last_refreshed_at_ms
 FROM youtube_subscription
 WHERE id = ?1
 "#,
@@ -2635,7 +2991,14 @@ SELECT
   consecutive_failures,
   next_allowed_refresh_at_ms,
   created_at_ms,
-  updated_at_ms
+  updated_at_ms,
+  filter_keywords_exclude,
+  filter_keywords_include,
+  last_error,
+  max_items_per_refresh,
+  auto_max_items,
+  avg_posts_per_day,
+  last_refreshed_at_ms
 FROM youtube_subscription
 WHERE source_url = ?1
 "#,
@@ -2687,9 +3050,74 @@ fn normalize_upsert(req: YoutubeSubscriptionUpsert) -> Result<NormalizedSubscrip
         preset_id,
         group_ids,
         refresh_interval_minutes: normalize_refresh_interval_minutes(req.refresh_interval_minutes),
+        filter_keywords_exclude: normalize_filter_keywords(req.filter_keywords_exclude),
+        filter_keywords_include: req
+            .filter_keywords_include
+            .map(normalize_filter_keywords)
+            .filter(|v| !v.is_empty()),
+        max_items_per_refresh: normalize_max_items_per_refresh(req.max_items_per_refresh)?,
+        auto_max_items: req.auto_max_items,
     })
 }
 
+fn normalize_max_items_per_refresh(value: Option<usize>) -> Result<Option<usize>> {
+    match value {
+        Some(v) if !(1..=jobs::MAX_DOWNLOAD_BATCH_URLS).contains(&v) => {
+            Err(EngineError::InstallFailed(format!(
+                "max_items_per_refresh must be between 1 and {}",
+                jobs::MAX_DOWNLOAD_BATCH_URLS
+            )))
+        }
+        other => Ok(other),
+    }
+}
+
+fn normalize_filter_keywords(values: Vec<String>) -> Vec<String> {
+    let mut out: Vec<String> = Vec::new();
+    for value in values {
+        let trimmed = value.trim();
+        if trimmed.is_empty() || out.iter().any(|v| v.eq_ignore_ascii_case(trimmed)) {
+            continue;
+        }
+        out.push(trimmed.to_string());
+    }
+    out
+}
+
+fn keywords_to_column(values: Option<&[String]>) -> Option<String> {
+    values
+        .filter(|v| !v.is_empty())
+        .and_then(|v| serde_json::to_string(v).ok())
+}
+
+fn keywords_from_column(value: Option<String>) -> Option<Vec<String>> {
+    value
+        .and_then(|v| serde_json::from_str::<Vec<String>>(&v).ok())
+        .filter(|v| !v.is_empty())
+}
+
+/// Returns why a refreshed video should be skipped under the subscription's title keyword
+/// filters, or `None` when it passes. Matching is a case-insensitive substring check.
+pub fn title_filter_skip_reason(sub: &YoutubeSubscriptionRow, title: &str) -> Option<String> {
+    let title = title.to_lowercase();
+    if let Some(keyword) = sub
+        .filter_keywords_exclude
+        .iter()
+        .find(|k| title.contains(&k.to_lowercase()))
+    {
+        return Some(format!("title matches exclude keyword \"{keyword}\""));
+    }
+    match sub.filter_keywords_include.as_deref() {
+        Some(include)
+            if !include.is_empty()
+                && !include.iter().any(|k| title.contains(&k.to_lowercase())) =>
+        {
+            Some("title matches no include keyword".to_string())
+        }
+        _ => None,
+    }
+}
+
 fn normalize_refresh_interval_minutes(value: Option<i64>) -> i64 {
     value
         .unwrap_or(DEFAULT_REFRESH_INTERVAL_MINUTES)
@@ -2797,11 +3225,18 @@ fn row_to_subscription(row: &rusqlite::Row<'_>) -> rusqlite::Result<YoutubeSubsc
         refresh_interval_minutes: row.get(8)?,
         last_queued_at_ms: row.get(9)?,
         last_error_at_ms: row.get(10)?,
+        last_error: row.get(17)?,
         consecutive_failures: row.get(11)?,
         next_allowed_refresh_at_ms: row.get(12)?,
         created_at_ms: row.get(13)?,
         updated_at_ms: row.get(14)?,
         group_ids: Vec::new(),
+        filter_keywords_exclude: keywords_from_column(row.get(15)?).unwrap_or_default(),
+        filter_keywords_include: keywords_from_column(row.get(16)?),
+        max_items_per_refresh: row.get(18)?,
+        auto_max_items: i64_to_bool(row.get::<_, i64>(19)?),
+        avg_posts_per_day: row.get(20)?,
+        last_refreshed_at_ms: row.get(21)?,
     })
 }
 
@@ -2838,6 +3273,10 @@ struct NormalizedSubscriptionInput {
     preset_id: Option<String>,
     group_ids: Vec<String>,
     refresh_interval_minutes: i64,
+    filter_keywords_exclude: Vec<String>,
+    filter_keywords_include: Option<Vec<String>>,
+    max_items_per_refresh: Option<usize>,
+    auto_max_items: bool,
 }
 
 trait OptionalRowExt<T> {
@@ -2860,7 +3299,63 @@ mod tests {
     use crate::paths::AppPaths;
 
     #[test]
-    fn import_upserts_by_source_url() {
+    fn opml_export_uses_channel_feeds_and_links_for_other_urls() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        crate::db::ensure_schema(&paths).expect("schema");
+
+        for (title, url) in [
+            (
+                "Chan & Co",
+                "https://www.youtube.com/channel/UCi_YgCDnd1bz70I6YgBi1rw/videos",
+            ),
+            ("List", "https://www.youtube.com/playlist?list=PL123456"),
+        ] {
+            upsert_youtube_subscription(
+                &paths,
+                YoutubeSubscriptionUpsert {
+                    id: None,
+                    title: title.to_string(),
+                    source_url: url.to_string(),
+                    folder_map: None,
+                    output_dir_override: None,
+                    use_browser_cookies: false,
+                    auth_session_input: None,
+                    clear_auth_session: false,
+                    active: true,
+                    preset_id: None,
+                    group_ids: Vec::new(),
+                    refresh_interval_minutes: None,
+                    filter_keywords_exclude: Vec::new(),
+                    filter_keywords_include: None,
+                    max_items_per_refresh: None,
+                    auto_max_items: false,
+                },
+            )
+            .expect("seed");
+        }
+
+        let out_path = dir.path().join("subs.opml");
+        let summary = export_youtube_subscriptions_opml(&paths, &out_path).expect("export");
+        assert_eq!(summary.count, 2);
+        let text = std::fs::read_to_string(&out_path).expect("read");
+        assert!(text.starts_with("<?xml"));
+        assert!(text.contains("<opml version=\"2.0\">"));
+        assert!(text.contains(
+            "xmlUrl=\"https://www.youtube.com/feeds/videos.xml?channel_id=UCi_YgCDnd1bz70I6YgBi1rw\""
+        ));
+        assert!(text.contains("text=\"Chan &amp; Co\""));
+        assert!(text.contains(
+            "type=\"link\" text=\"List\" title=\"List\" htmlUrl=\"https://www.youtube.com/playlist?list=PL123456\""
+        ));
+        assert_eq!(
+            youtube_channel_id_from_url("https://www.youtube.com/@example/videos"),
+            None
+        );
+    }
+
+    #[test]
+    fn import_skips_existing_and_invalid_source_urls() {
         let dir = tempfile::tempdir().expect("tempdir");
         let paths = AppPaths::new(dir.path().to_path_buf());
         crate::db::ensure_schema(&paths).expect("schema");
@@ -2880,6 +3375,10 @@ mod tests {
                 preset_id: None,
                 group_ids: Vec::new(),
                 refresh_interval_minutes: Some(DEFAULT_REFRESH_INTERVAL_MINUTES),
+                filter_keywords_exclude: Vec::new(),
+                filter_keywords_include: None,
+                max_items_per_refresh: None,
+                auto_max_items: false,
             },
         )
         .expect("seed");
@@ -2907,6 +3406,29 @@ mod tests {
                     "use_browser_cookies": false,
                     "active": true,
                     "refresh_interval_minutes": 30
+                },
+                {
+                    "title": "Second again",
+                    "source_url": "https://www.youtube.com/playlist?list=PL123456",
+                    "folder_map": null,
+                    "output_dir_override": null,
+                    "use_browser_cookies": false,
+                    "active": true
+                },
+                {
+                    "title": "Missing url",
+                    "folder_map": null,
+                    "output_dir_override": null,
+                    "use_browser_cookies": false,
+                    "active": true
+                },
+                {
+                    "title": "Bad scheme",
+                    "source_url": "ftp://www.youtube.com/@other",
+                    "folder_map": null,
+                    "output_dir_override": null,
+                    "use_browser_cookies": false,
+                    "active": true
                 }
             ]
         });
@@ -2920,20 +3442,26 @@ mod tests {
         .expect("write import");
 
         let summary = import_youtube_subscriptions_json(&paths, &import_path).expect("import");
-        assert_eq!(summary.total_in_file, 2);
+        assert_eq!(summary.total_in_file, 5);
         assert_eq!(summary.inserted, 1);
-        assert_eq!(summary.updated, 1);
+        assert_eq!(summary.skipped_duplicates, 2);
+        assert_eq!(summary.skipped_invalid, 2);
+        assert_eq!(summary.validation_errors.len(), 2);
+        assert!(summary.validation_errors[0].starts_with("entry 3:"));
 
         let rows = list_youtube_subscriptions(&paths).expect("list");
         assert_eq!(rows.len(), 2);
-        let updated = rows
+        let existing = rows
             .iter()
             .find(|row| row.source_url.contains("@example"))
-            .expect("updated row");
-        assert_eq!(updated.title, "Updated title");
-        assert_eq!(updated.folder_map, "updated_map");
-        assert!(updated.use_browser_cookies);
-        assert_eq!(updated.refresh_interval_minutes, 90);
+            .expect("existing row");
+        assert_eq!(existing.title, "Original");
+        let second = rows
+            .iter()
+            .find(|row| row.source_url.contains("PL123456"))
+            .expect("second row");
+        assert_eq!(second.title, "Second");
+        assert_eq!(second.refresh_interval_minutes, 30);
     }
 
     #[test]
@@ -2960,6 +3488,10 @@ mod tests {
                 preset_id: None,
                 group_ids: Vec::new(),
                 refresh_interval_minutes: Some(DEFAULT_REFRESH_INTERVAL_MINUTES),
+                filter_keywords_exclude: Vec::new(),
+                filter_keywords_include: None,
+                max_items_per_refresh: None,
+                auto_max_items: false,
             },
         )
         .expect("upsert");
@@ -3011,6 +3543,10 @@ mod tests {
                 preset_id: None,
                 group_ids: Vec::new(),
                 refresh_interval_minutes: Some(DEFAULT_REFRESH_INTERVAL_MINUTES),
+                filter_keywords_exclude: Vec::new(),
+                filter_keywords_include: None,
+                max_items_per_refresh: None,
+                auto_max_items: false,
             },
         )
         .expect("upsert");
@@ -3026,9 +3562,9 @@ mod tests {
         assert_eq!(stored, "SAPISID=cookie123");
 
         let queued = queue_youtube_subscription(&paths, &sub.id).expect("queue");
-        let job_secret =
-            jobs::read_auth_cookie_secret_path(&paths.job_cookie_secret_path(&queued[0].id))
-                .expect("job auth secret");
+        let job_secret = jobs::read_job_cookie_secret(&paths, &queued[0].id)
+            .expect("read job auth secret")
+            .expect("job auth secret");
         assert_eq!(job_secret, "SAPISID=cookie123");
     }
 
@@ -3053,6 +3589,10 @@ mod tests {
                 preset_id: None,
                 group_ids: Vec::new(),
                 refresh_interval_minutes: Some(1),
+                filter_keywords_exclude: Vec::new(),
+                filter_keywords_include: None,
+                max_items_per_refresh: None,
+                auto_max_items: false,
             },
         )
         .expect("upsert low");
@@ -3073,12 +3613,191 @@ mod tests {
                 preset_id: None,
                 group_ids: Vec::new(),
                 refresh_interval_minutes: Some(999999),
+                filter_keywords_exclude: Vec::new(),
+                filter_keywords_include: None,
+                max_items_per_refresh: None,
+                auto_max_items: false,
             },
         )
         .expect("upsert high");
         assert_eq!(high.refresh_interval_minutes, MAX_REFRESH_INTERVAL_MINUTES);
     }
 
+    #[test]
+    fn upsert_validates_and_persists_max_items_per_refresh() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        crate::db::ensure_schema(&paths).expect("schema");
+        let req = |max_items_per_refresh| YoutubeSubscriptionUpsert {
+            id: None,
+            title: "Fast channel".to_string(),
+            source_url: "https://www.youtube.com/@fast/videos".to_string(),
+            folder_map: None,
+            output_dir_override: None,
+            use_browser_cookies: false,
+            auth_session_input: None,
+            clear_auth_session: false,
+            active: true,
+            preset_id: None,
+            group_ids: Vec::new(),
+            refresh_interval_minutes: None,
+            filter_keywords_exclude: Vec::new(),
+            filter_keywords_include: None,
+            max_items_per_refresh,
+            auto_max_items: false,
+        };
+
+        assert!(upsert_youtube_subscription(&paths, req(Some(0))).is_err());
+        assert!(
+            upsert_youtube_subscription(&paths, req(Some(jobs::MAX_DOWNLOAD_BATCH_URLS + 1)))
+                .is_err()
+        );
+
+        let row = upsert_youtube_subscription(&paths, req(Some(10))).expect("upsert");
+        assert_eq!(row.max_items_per_refresh, Some(10));
+        let listed = list_youtube_subscriptions(&paths).expect("list");
+        assert_eq!(listed[0].max_items_per_refresh, Some(10));
+
+        let cleared = upsert_youtube_subscription(&paths, req(None)).expect("clear");
+        assert_eq!(cleared.max_items_per_refresh, None);
+    }
+
+    #[test]
+    fn auto_max_items_tracks_average_posts_per_day() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        crate::db::ensure_schema(&paths).expect("schema");
+        let sub = upsert_youtube_subscription(
+            &paths,
+            YoutubeSubscriptionUpsert {
+                id: None,
+                title: "Daily channel".to_string(),
+                source_url: "https://www.youtube.com/@daily/videos".to_string(),
+                folder_map: None,
+                output_dir_override: None,
+                use_browser_cookies: false,
+                auth_session_input: None,
+                clear_auth_session: false,
+                active: true,
+                preset_id: None,
+                group_ids: Vec::new(),
+                refresh_interval_minutes: None,
+                filter_keywords_exclude: Vec::new(),
+                filter_keywords_include: None,
+                max_items_per_refresh: None,
+                auto_max_items: true,
+            },
+        )
+        .expect("upsert");
+        assert!(sub.auto_max_items);
+        let now = now_ms();
+        let no_history = compute_auto_max_items(&sub, 200, now);
+        assert_eq!(no_history.value, 200);
+        assert_eq!(no_history.avg_posts_per_day, None);
+
+        // First refresh only establishes the baseline timestamp.
+        record_subscription_refresh_success(
+            &paths,
+            &sub.id,
+            Some(RefreshItemsSeen {
+                new_items: 30,
+                max_items: 200,
+            }),
+        )
+        .expect("first");
+        let row = get_youtube_subscription_by_id(&paths, &sub.id)
+            .expect("get")
+            .expect("row");
+        assert_eq!(row.avg_posts_per_day, None);
+        assert!(row.last_refreshed_at_ms.is_some());
+
+        let day_ms = MS_PER_DAY as i64;
+        let seen = |new_items| RefreshItemsSeen {
+            new_items,
+            max_items: 200,
+        };
+        assert_eq!(
+            next_avg_posts_per_day(None, Some(now - 2 * day_ms), seen(6), now),
+            Some(3.0)
+        );
+        let ema = next_avg_posts_per_day(Some(3.0), Some(now - day_ms), seen(8), now).expect("ema");
+        assert!((ema - 4.0).abs() < 1e-9);
+        // A refresh that filled max_items grows the estimate instead of pulling it down.
+        let saturated = RefreshItemsSeen {
+            new_items: 5,
+            max_items: 5,
+        };
+        let grown =
+            next_avg_posts_per_day(Some(3.0), Some(now - day_ms), saturated, now).expect("grown");
+        assert!((grown - 4.4).abs() < 1e-9);
+        let kept =
+            next_avg_posts_per_day(Some(30.0), Some(now - day_ms), saturated, now).expect("kept");
+        assert!((kept - 30.0).abs() < 1e-9);
+
+        let busy = YoutubeSubscriptionRow {
+            avg_posts_per_day: Some(4.0),
+            last_refreshed_at_ms: Some(now - day_ms),
+            ..row.clone()
+        };
+        assert_eq!(compute_auto_max_items(&busy, 200, now).value, 28);
+        let dormant = YoutubeSubscriptionRow {
+            avg_posts_per_day: Some(0.0),
+            ..busy.clone()
+        };
+        assert_eq!(
+            compute_auto_max_items(&dormant, 200, now).value,
+            AUTO_MAX_ITEMS_FLOOR
+        );
+        let flood = YoutubeSubscriptionRow {
+            avg_posts_per_day: Some(1000.0),
+            ..busy
+        };
+        assert_eq!(
+            compute_auto_max_items(&flood, 200, now).value,
+            AUTO_MAX_ITEMS_CAP
+        );
+    }
+
+    #[test]
+    fn upsert_persists_title_keyword_filters() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        crate::db::ensure_schema(&paths).expect("schema");
+
+        let row = upsert_youtube_subscription(
+            &paths,
+            YoutubeSubscriptionUpsert {
+                id: None,
+                title: "Filtered".to_string(),
+                source_url: "https://www.youtube.com/@filtered/videos".to_string(),
+                folder_map: None,
+                output_dir_override: None,
+                use_browser_cookies: false,
+                auth_session_input: None,
+                clear_auth_session: false,
+                active: true,
+                preset_id: None,
+                group_ids: Vec::new(),
+                refresh_interval_minutes: None,
+                filter_keywords_exclude: vec![" #shorts ".to_string(), "#Shorts".to_string()],
+                filter_keywords_include: Some(vec!["Live".to_string(), " ".to_string()]),
+                max_items_per_refresh: None,
+                auto_max_items: false,
+            },
+        )
+        .expect("upsert");
+        assert_eq!(row.filter_keywords_exclude, vec!["#shorts".to_string()]);
+        assert_eq!(row.filter_keywords_include, Some(vec!["Live".to_string()]));
+
+        assert!(title_filter_skip_reason(&row, "Live stream #SHORTS").is_some());
+        assert!(title_filter_skip_reason(&row, "Studio session").is_some());
+        assert_eq!(title_filter_skip_reason(&row, "LIVE at the arena"), None);
+
+        let mut open = row.clone();
+        open.filter_keywords_include = None;
+        assert_eq!(title_filter_skip_reason(&open, "Studio session"), None);
+    }
+
     #[test]
     fn queue_all_active_respects_refresh_interval() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -3100,6 +3819,10 @@ mod tests {
                 preset_id: None,
                 group_ids: Vec::new(),
                 refresh_interval_minutes: Some(5),
+                filter_keywords_exclude: Vec::new(),
+                filter_keywords_include: None,
+                max_items_per_refresh: None,
+                auto_max_items: false,
             },
         )
         .expect("upsert due");
@@ -3118,6 +3841,10 @@ mod tests {
                 preset_id: None,
                 group_ids: Vec::new(),
                 refresh_interval_minutes: Some(60),
+                filter_keywords_exclude: Vec::new(),
+                filter_keywords_include: None,
+                max_items_per_refresh: None,
+                auto_max_items: false,
             },
         )
         .expect("upsert not due");
@@ -3479,6 +4206,10 @@ CREATE TABLE subscription_entries (
                 preset_id: None,
                 group_ids: Vec::new(),
                 refresh_interval_minutes: Some(DEFAULT_REFRESH_INTERVAL_MINUTES),
+                filter_keywords_exclude: Vec::new(),
+                filter_keywords_include: None,
+                max_items_per_refresh: None,
+                auto_max_items: false,
             },
         )
         .expect("upsert sub");
@@ -3534,11 +4265,20 @@ CREATE TABLE subscription_entries (
                 preset_id: None,
                 group_ids: Vec::new(),
                 refresh_interval_minutes: Some(MIN_REFRESH_INTERVAL_MINUTES),
+                filter_keywords_exclude: Vec::new(),
+                filter_keywords_include: None,
+                max_items_per_refresh: None,
+                auto_max_items: false,
             },
         )
         .expect("upsert");
 
-        record_subscription_refresh_failure(&paths, &sub.id).expect("record failure");
+        record_subscription_refresh_failure(&paths, &sub.id, "video is private")
+            .expect("record failure");
+        let failing = list_youtube_subscriptions_with_errors(&paths).expect("with errors");
+        assert_eq!(failing.len(), 1);
+        assert_eq!(failing[0].last_error.as_deref(), Some("video is private"));
+        assert!(failing[0].last_error_at_ms.is_some());
         let blocked = queue_all_active_youtube_subscriptions(&paths).expect("queue blocked");
         assert!(
             blocked.is_empty(),
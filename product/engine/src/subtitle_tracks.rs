@@ -1,11 +1,102 @@
 use crate::paths::AppPaths;
-use crate::subtitles::{SubtitleDocument, SUBTITLE_JSON_SCHEMA_VERSION};
-use crate::{db, EngineError, Result};
-use rusqlite::params;
+use crate::subtitles::{ExportVttOptions, SubtitleDocument, SUBTITLE_JSON_SCHEMA_VERSION};
+use crate::{db, persistence, EngineError, Result};
+use rusqlite::{params, OptionalExtension};
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::Path;
+use std::sync::{Mutex, OnceLock};
+use std::time::UNIX_EPOCH;
 use uuid::Uuid;
 
+const DOCUMENT_CACHE_CAPACITY: usize = 32;
+const SUBTITLE_TRACK_NOTES_MAX_CHARS: usize = 4096;
+
+/// Track id + file mtime (whole seconds).
+type DocumentCacheKey = (String, u64);
+
+/// Identifies the exact file a cached document was parsed from; the length catches rewrites
+/// within the same mtime second.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct DocumentFileStamp {
+    path: String,
+    len: u64,
+}
+
+/// Parsed track documents keyed by file mtime, so UI polling while editing skips re-parsing.
+struct DocumentCache {
+    entries: HashMap<DocumentCacheKey, (DocumentFileStamp, SubtitleDocument)>,
+    order: VecDeque<DocumentCacheKey>,
+    capacity: usize,
+}
+
+impl DocumentCache {
+    fn new(capacity: usize) -> Self {
+        Self {
+            entries: HashMap::new(),
+            order: VecDeque::new(),
+            capacity,
+        }
+    }
+
+    fn touch(&mut self, key: &DocumentCacheKey) {
+        if let Some(pos) = self.order.iter().position(|k| k == key) {
+            if let Some(key) = self.order.remove(pos) {
+                self.order.push_back(key);
+            }
+        }
+    }
+
+    fn get(
+        &mut self,
+        key: &DocumentCacheKey,
+        stamp: &DocumentFileStamp,
+    ) -> Option<SubtitleDocument> {
+        let hit = match self.entries.get(key) {
+            Some((cached, doc)) if cached == stamp => doc.clone(),
+            Some(_) => {
+                self.remove_track(&key.0);
+                return None;
+            }
+            None => return None,
+        };
+        self.touch(key);
+        Some(hit)
+    }
+
+    fn insert(&mut self, key: DocumentCacheKey, stamp: DocumentFileStamp, doc: SubtitleDocument) {
+        // An older mtime for the same track is stale now.
+        self.remove_track(&key.0);
+        while self.order.len() >= self.capacity {
+            match self.order.pop_front() {
+                Some(oldest) => {
+                    self.entries.remove(&oldest);
+                }
+                None => break,
+            }
+        }
+        self.order.push_back(key.clone());
+        self.entries.insert(key, (stamp, doc));
+    }
+
+    fn remove_track(&mut self, track_id: &str) {
+        self.entries.retain(|(id, _), _| id != track_id);
+        self.order.retain(|(id, _)| id != track_id);
+    }
+}
+
+fn document_cache() -> &'static Mutex<DocumentCache> {
+    static CACHE: OnceLock<Mutex<DocumentCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(DocumentCache::new(DOCUMENT_CACHE_CAPACITY)))
+}
+
+/// Drops the cached document for `track_id`, e.g. after its file was rewritten.
+pub fn invalidate_document_cache(track_id: &str) {
+    if let Ok(mut cache) = document_cache().lock() {
+        cache.remove_track(track_id);
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubtitleTrackRow {
     pub id: String,
@@ -16,6 +107,35 @@ pub struct SubtitleTrackRow {
     pub path: String,
     pub created_by: String,
     pub version: i64,
+    /// Free-text annotation for this version, set via [`annotate_version`].
+    #[serde(default)]
+    pub notes: Option<String>,
+    /// Track this one was derived from (translation, diarization, sync).
+    #[serde(default)]
+    pub parent_track_id: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubtitleSaveResult {
+    #[serde(flatten)]
+    pub track: SubtitleTrackRow,
+    /// True when the document matched the latest version and nothing was written.
+    pub was_no_op: bool,
+}
+
+fn track_from_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<SubtitleTrackRow> {
+    Ok(SubtitleTrackRow {
+        id: row.get(0)?,
+        item_id: row.get(1)?,
+        kind: row.get(2)?,
+        lang: row.get(3)?,
+        format: row.get(4)?,
+        path: row.get(5)?,
+        created_by: row.get(6)?,
+        version: row.get(7)?,
+        notes: row.get(8)?,
+        parent_track_id: row.get(9)?,
+    })
 }
 
 pub fn list_tracks(paths: &AppPaths, item_id: &str) -> Result<Vec<SubtitleTrackRow>> {
@@ -32,7 +152,9 @@ SELECT
   format,
   path,
   created_by,
-  version
+  version,
+  notes,
+  parent_track_id
 FROM subtitle_track
 WHERE item_id=?1
 ORDER BY kind ASC, lang ASC, version DESC
@@ -40,18 +162,145 @@ ORDER BY kind ASC, lang ASC, version DESC
     )?;
 
     let rows = stmt
-        .query_map(params![item_id], |row| {
-            Ok(SubtitleTrackRow {
-                id: row.get(0)?,
-                item_id: row.get(1)?,
-                kind: row.get(2)?,
-                lang: row.get(3)?,
-                format: row.get(4)?,
-                path: row.get(5)?,
-                created_by: row.get(6)?,
-                version: row.get(7)?,
-            })
-        })?
+        .query_map(params![item_id], track_from_row)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(rows)
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TrackSortField {
+    /// Insertion order; `subtitle_track` has no timestamp column, so this sorts by rowid.
+    #[default]
+    CreatedAt,
+    Version,
+    Kind,
+    Lang,
+}
+
+impl TrackSortField {
+    fn order_column(self) -> &'static str {
+        match self {
+            Self::CreatedAt => "rowid",
+            Self::Version => "version",
+            Self::Kind => "kind",
+            Self::Lang => "lang",
+        }
+    }
+}
+
+/// Sort and filter for [`list_tracks_filtered`]; blank filters are ignored.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct ListTracksOptions {
+    pub sort_by: TrackSortField,
+    pub sort_desc: bool,
+    pub kind_filter: Option<String>,
+    pub lang_filter: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SubtitleTracksPage {
+    pub tracks: Vec<SubtitleTrackRow>,
+    /// Tracks matching the filters; nothing is paged, so this equals `tracks.len()`.
+    pub total_count: usize,
+}
+
+/// Like [`list_tracks`] with an explicit order and optional kind/language filters. Ties fall
+/// back to insertion order.
+pub fn list_tracks_filtered(
+    paths: &AppPaths,
+    item_id: &str,
+    options: &ListTracksOptions,
+) -> Result<SubtitleTracksPage> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    let mut filters: Vec<&str> = vec!["item_id=?"];
+    let mut args: Vec<rusqlite::types::Value> = vec![item_id.to_string().into()];
+    if let Some(kind) = options
+        .kind_filter
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    {
+        filters.push("kind=?");
+        args.push(kind.to_string().into());
+    }
+    if let Some(lang) = options
+        .lang_filter
+        .as_deref()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    {
+        filters.push("lang=?");
+        args.push(lang.to_string().into());
+    }
+    let direction = if options.sort_desc { "DESC" } else { "ASC" };
+    let order_column = options.sort_by.order_column();
+
+    let mut stmt = conn.prepare(&format!(
+        r#"
+SELECT
+  id,
+  item_id,
+  kind,
+  lang,
+  format,
+  path,
+  created_by,
+  version,
+  notes,
+  parent_track_id
+FROM subtitle_track
+WHERE {}
+ORDER BY {order_column} {direction}, rowid {direction}
+"#,
+        filters.join(" AND ")
+    ))?;
+
+    let tracks = stmt
+        .query_map(rusqlite::params_from_iter(args), track_from_row)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(SubtitleTracksPage {
+        total_count: tracks.len(),
+        tracks,
+    })
+}
+
+/// All versions of an item's track with the given kind and language, newest first.
+pub fn list_track_versions(
+    paths: &AppPaths,
+    item_id: &str,
+    kind: &str,
+    lang: &str,
+) -> Result<Vec<SubtitleTrackRow>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    let mut stmt = conn.prepare(
+        r#"
+SELECT
+  id,
+  item_id,
+  kind,
+  lang,
+  format,
+  path,
+  created_by,
+  version,
+  notes,
+  parent_track_id
+FROM subtitle_track
+WHERE item_id=?1 AND kind=?2 AND lang=?3
+ORDER BY version DESC
+"#,
+    )?;
+
+    let rows = stmt
+        .query_map(params![item_id, kind, lang], track_from_row)?
         .collect::<rusqlite::Result<Vec<_>>>()?;
 
     Ok(rows)
@@ -71,35 +320,103 @@ SELECT
   format,
   path,
   created_by,
-  version
+  version,
+  notes,
+  parent_track_id
 FROM subtitle_track
 WHERE id=?1
 "#,
         params![track_id],
-        |row| {
-            Ok(SubtitleTrackRow {
-                id: row.get(0)?,
-                item_id: row.get(1)?,
-                kind: row.get(2)?,
-                lang: row.get(3)?,
-                format: row.get(4)?,
-                path: row.get(5)?,
-                created_by: row.get(6)?,
-                version: row.get(7)?,
-            })
-        },
+        track_from_row,
     )
     .map_err(|e| match e {
-        rusqlite::Error::QueryReturnedNoRows => {
-            EngineError::InstallFailed(format!("subtitle track not found: {track_id}"))
-        }
+        rusqlite::Error::QueryReturnedNoRows => EngineError::not_found("subtitle track", track_id),
         other => EngineError::Database(other),
     })
 }
 
+/// Ancestors of `track_id` following `parent_track_id`, nearest parent first. Stops at a missing
+/// parent or a cycle.
+pub fn get_lineage(paths: &AppPaths, track_id: &str) -> Result<Vec<SubtitleTrackRow>> {
+    let mut next = get_track(paths, track_id)?.parent_track_id;
+    let mut seen: HashSet<String> = HashSet::from([track_id.to_string()]);
+    let mut ancestors = Vec::new();
+    while let Some(parent_id) = next {
+        if !seen.insert(parent_id.clone()) {
+            break;
+        }
+        let parent = match get_track(paths, &parent_id) {
+            Ok(v) => v,
+            Err(EngineError::NotFound { .. }) => break,
+            Err(e) => return Err(e),
+        };
+        next = parent.parent_track_id.clone();
+        ancestors.push(parent);
+    }
+    Ok(ancestors)
+}
+
+/// Tracks directly derived from `track_id`.
+pub fn list_children(paths: &AppPaths, track_id: &str) -> Result<Vec<SubtitleTrackRow>> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+
+    let mut stmt = conn.prepare(
+        r#"
+SELECT
+  id,
+  item_id,
+  kind,
+  lang,
+  format,
+  path,
+  created_by,
+  version,
+  notes,
+  parent_track_id
+FROM subtitle_track
+WHERE parent_track_id=?1
+ORDER BY kind ASC, lang ASC, version DESC
+"#,
+    )?;
+
+    let rows = stmt
+        .query_map(params![track_id], track_from_row)?
+        .collect::<rusqlite::Result<Vec<_>>>()?;
+
+    Ok(rows)
+}
+
 pub fn load_document(paths: &AppPaths, track_id: &str) -> Result<SubtitleDocument> {
     let track = get_track(paths, track_id)?;
-    let doc = load_document_from_path(Path::new(&track.path))?;
+    let path = Path::new(&track.path);
+    let fingerprint = std::fs::metadata(path).ok().and_then(|meta| {
+        let mtime_secs = meta
+            .modified()
+            .ok()?
+            .duration_since(UNIX_EPOCH)
+            .ok()?
+            .as_secs();
+        let stamp = DocumentFileStamp {
+            path: track.path.clone(),
+            len: meta.len(),
+        };
+        Some(((track_id.to_string(), mtime_secs), stamp))
+    });
+    if let Some((key, stamp)) = fingerprint.as_ref() {
+        if let Ok(mut cache) = document_cache().lock() {
+            if let Some(hit) = cache.get(key, stamp) {
+                return Ok(hit);
+            }
+        }
+    }
+
+    let doc = load_document_from_path(path)?;
+    if let Some((key, stamp)) = fingerprint {
+        if let Ok(mut cache) = document_cache().lock() {
+            cache.insert(key, stamp, doc.clone());
+        }
+    }
     Ok(doc)
 }
 
@@ -115,11 +432,14 @@ pub fn load_document_from_path(path: &Path) -> Result<SubtitleDocument> {
     Ok(doc)
 }
 
+/// Saves `doc` as the next version of the base track. `version_metadata` is merged into the
+/// document metadata first (a `null` value removes the key) to record what produced the version.
 pub fn save_new_version(
     paths: &AppPaths,
     base_track_id: &str,
     mut doc: SubtitleDocument,
-) -> Result<SubtitleTrackRow> {
+    version_metadata: Option<HashMap<String, serde_json::Value>>,
+) -> Result<SubtitleSaveResult> {
     let base = get_track(paths, base_track_id)?;
     if doc.schema_version != SUBTITLE_JSON_SCHEMA_VERSION {
         return Err(EngineError::InstallFailed(format!(
@@ -128,15 +448,38 @@ pub fn save_new_version(
         )));
     }
 
+    for (key, value) in version_metadata.unwrap_or_default() {
+        let key = key.trim();
+        if key.is_empty() {
+            continue;
+        }
+        if value.is_null() {
+            doc.metadata.remove(key);
+        } else {
+            doc.metadata.insert(key.to_string(), value);
+        }
+    }
+
     // Ensure doc kind/lang align with the track metadata.
     doc.kind = base.kind.clone();
     if doc.lang.trim().is_empty() {
         doc.lang = base.lang.clone();
     }
 
+    invalidate_document_cache(base_track_id);
     let conn = db::open(paths)?;
     db::migrate(&conn)?;
 
+    let content_hash = crate::subtitles::content_hash(&doc);
+    if let Some(latest) = latest_version(&conn, &base)? {
+        if latest_content_hash(&conn, &latest)?.as_deref() == Some(content_hash.as_str()) {
+            return Ok(SubtitleSaveResult {
+                track: latest,
+                was_no_op: true,
+            });
+        }
+    }
+
     let max_version: Option<i64> = conn.query_row(
         r#"
 SELECT MAX(version)
@@ -171,8 +514,10 @@ INSERT INTO subtitle_track (
   format,
   path,
   created_by,
-  version
-) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+  version,
+  content_hash,
+  parent_track_id
+) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10)
 "#,
         params![
             &id,
@@ -182,35 +527,182 @@ INSERT INTO subtitle_track (
             &base.format,
             json_path.to_string_lossy().to_string(),
             "user",
-            next_version
+            next_version,
+            &content_hash,
+            &base.parent_track_id
         ],
     )?;
 
-    Ok(SubtitleTrackRow {
-        id,
-        item_id: base.item_id,
-        kind: base.kind,
-        lang: base.lang,
-        format: base.format,
-        path: json_path.to_string_lossy().to_string(),
-        created_by: "user".to_string(),
-        version: next_version,
+    Ok(SubtitleSaveResult {
+        track: SubtitleTrackRow {
+            id,
+            item_id: base.item_id,
+            kind: base.kind,
+            lang: base.lang,
+            format: base.format,
+            path: json_path.to_string_lossy().to_string(),
+            created_by: "user".to_string(),
+            version: next_version,
+            notes: None,
+            parent_track_id: base.parent_track_id,
+        },
+        was_no_op: false,
     })
 }
 
-pub fn export_document_srt(doc: &SubtitleDocument, out_path: &Path) -> Result<()> {
-    let text = crate::subtitles::render_srt(doc)?;
-    if let Some(parent) = out_path.parent() {
-        if !parent.as_os_str().is_empty() {
-            std::fs::create_dir_all(parent)?;
+fn latest_version(
+    conn: &rusqlite::Connection,
+    base: &SubtitleTrackRow,
+) -> Result<Option<SubtitleTrackRow>> {
+    let row = conn
+        .query_row(
+            r#"
+SELECT
+  id,
+  item_id,
+  kind,
+  lang,
+  format,
+  path,
+  created_by,
+  version,
+  notes,
+  parent_track_id
+FROM subtitle_track
+WHERE item_id=?1 AND kind=?2 AND lang=?3 AND format=?4
+ORDER BY version DESC
+LIMIT 1
+"#,
+            params![&base.item_id, &base.kind, &base.lang, &base.format],
+            track_from_row,
+        )
+        .optional()?;
+    Ok(row)
+}
+
+/// Stored hash of a track, or the hash of its file for rows written before hashes were recorded.
+fn latest_content_hash(
+    conn: &rusqlite::Connection,
+    track: &SubtitleTrackRow,
+) -> Result<Option<String>> {
+    let stored: Option<String> = conn.query_row(
+        "SELECT content_hash FROM subtitle_track WHERE id=?1",
+        params![&track.id],
+        |row| row.get(0),
+    )?;
+    if stored.is_some() {
+        return Ok(stored);
+    }
+    Ok(load_document_from_path(Path::new(&track.path))
+        .ok()
+        .map(|doc| crate::subtitles::content_hash(&doc)))
+}
+
+pub fn get_document_metadata(
+    paths: &AppPaths,
+    track_id: &str,
+) -> Result<HashMap<String, serde_json::Value>> {
+    Ok(load_document(paths, track_id)?.metadata)
+}
+
+/// Sets one metadata key on the track's JSON file in place (a `null` value removes the key).
+/// Metadata is not a content edit, so no new version is created.
+pub fn set_document_metadata(
+    paths: &AppPaths,
+    track_id: &str,
+    key: &str,
+    value: serde_json::Value,
+) -> Result<HashMap<String, serde_json::Value>> {
+    let key = key.trim();
+    if key.is_empty() {
+        return Err(EngineError::InstallFailed(
+            "metadata key must not be empty".to_string(),
+        ));
+    }
+    let track = get_track(paths, track_id)?;
+    let path = Path::new(&track.path);
+    let mut doc = load_document_from_path(path)?;
+    if value.is_null() {
+        doc.metadata.remove(key);
+    } else {
+        doc.metadata.insert(key.to_string(), value);
+    }
+
+    let json = serde_json::to_string_pretty(&doc)?;
+    persistence::atomic_write_text(path, &format!("{json}\n"))?;
+    invalidate_document_cache(track_id);
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    conn.execute(
+        "UPDATE subtitle_track SET content_hash=?1 WHERE id=?2",
+        params![crate::subtitles::content_hash(&doc), track_id],
+    )?;
+    Ok(doc.metadata)
+}
+
+/// Sets or clears (`None`/blank) the free-text notes of one track version.
+pub fn annotate_version(
+    paths: &AppPaths,
+    track_id: &str,
+    notes: Option<String>,
+) -> Result<SubtitleTrackRow> {
+    let notes = notes
+        .map(|v| v.trim().to_string())
+        .filter(|v| !v.is_empty());
+    if let Some(notes) = notes.as_deref() {
+        let len = notes.chars().count();
+        if len > SUBTITLE_TRACK_NOTES_MAX_CHARS {
+            return Err(EngineError::InstallFailed(format!(
+                "notes must be at most {SUBTITLE_TRACK_NOTES_MAX_CHARS} characters (got {len})"
+            )));
         }
     }
-    std::fs::write(out_path, text)?;
-    Ok(())
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let changed = conn.execute(
+        "UPDATE subtitle_track SET notes=?1 WHERE id=?2",
+        params![notes, track_id],
+    )?;
+    if changed == 0 {
+        return Err(EngineError::not_found("subtitle track", track_id));
+    }
+    get_track(paths, track_id)
+}
+
+/// Saves a historical version's document as the new latest version of its track.
+pub fn restore_version(paths: &AppPaths, track_id: &str) -> Result<SubtitleTrackRow> {
+    let doc = load_document(paths, track_id)?;
+    Ok(save_new_version(paths, track_id, doc, None)?.track)
+}
+
+pub fn export_document_srt(doc: &SubtitleDocument, out_path: &Path) -> Result<()> {
+    export_document_srt_with_options(doc, out_path, false)
+}
+
+/// Writes SRT, optionally prefixing each cue with its speaker in brackets.
+pub fn export_document_srt_with_options(
+    doc: &SubtitleDocument,
+    out_path: &Path,
+    include_speaker_labels: bool,
+) -> Result<()> {
+    let text = crate::subtitles::render_srt_with_options(doc, include_speaker_labels)?;
+    write_export_text(out_path, &text)
 }
 
 pub fn export_document_vtt(doc: &SubtitleDocument, out_path: &Path) -> Result<()> {
-    let text = crate::subtitles::render_vtt(doc)?;
+    export_document_vtt_with_options(doc, out_path, &ExportVttOptions::default())
+}
+
+pub fn export_document_vtt_with_options(
+    doc: &SubtitleDocument,
+    out_path: &Path,
+    options: &ExportVttOptions,
+) -> Result<()> {
+    let text = crate::subtitles::render_vtt_with_options(doc, options)?;
+    write_export_text(out_path, &text)
+}
+
+fn write_export_text(out_path: &Path, text: &str) -> Result<()> {
     if let Some(parent) = out_path.parent() {
         if !parent.as_os_str().is_empty() {
             std::fs::create_dir_all(parent)?;
@@ -238,6 +730,124 @@ mod tests {
     use crate::subtitles::{SubtitleDocument, SubtitleSegment};
     use std::time::{SystemTime, UNIX_EPOCH};
 
+    #[test]
+    fn lineage_walks_parents_and_children() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let conn = db::open(&paths).expect("open");
+        db::migrate(&conn).expect("migrate");
+        conn.execute(
+            "INSERT INTO library_item (id, created_at_ms, source_type, source_uri, title, media_path) VALUES ('item-1', 1, 'local', 'file:///a.mp4', 'A', '/a.mp4')",
+            [],
+        )
+        .expect("insert item");
+        for (id, kind, parent) in [
+            ("asr", "source", None),
+            ("translated", "translated", Some("asr")),
+            ("diarized", "translated", Some("translated")),
+            ("synced", "source", Some("asr")),
+        ] {
+            conn.execute(
+                "INSERT INTO subtitle_track (id, item_id, kind, lang, format, path, created_by, version, parent_track_id) VALUES (?1, 'item-1', ?2, 'en', 'ytfetch_subtitle_json_v1', ?3, 'test', 1, ?4)",
+                params![id, kind, format!("/{id}.json"), parent],
+            )
+            .expect("insert track");
+        }
+        drop(conn);
+
+        let lineage: Vec<String> = get_lineage(&paths, "diarized")
+            .expect("lineage")
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(lineage, vec!["translated", "asr"]);
+        assert!(get_lineage(&paths, "asr").expect("root").is_empty());
+        assert!(get_lineage(&paths, "missing").is_err());
+
+        let children: Vec<String> = list_children(&paths, "asr")
+            .expect("children")
+            .into_iter()
+            .map(|t| t.id)
+            .collect();
+        assert_eq!(children, vec!["synced", "translated"]);
+    }
+
+    #[test]
+    fn list_tracks_filtered_sorts_and_filters() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let conn = db::open(&paths).expect("open");
+        db::migrate(&conn).expect("migrate");
+        conn.execute(
+            "INSERT INTO library_item (id, created_at_ms, source_type, source_uri, title, media_path) VALUES ('item-1', 1, 'local', 'file:///a.mp4', 'A', '/a.mp4')",
+            [],
+        )
+        .expect("insert item");
+        for (id, kind, lang, version) in [
+            ("ja-1", "source", "ja", 1_i64),
+            ("en-1", "translated", "en", 1),
+            ("ja-2", "source", "ja", 2),
+            ("de-1", "translated", "de", 1),
+        ] {
+            conn.execute(
+                "INSERT INTO subtitle_track (id, item_id, kind, lang, format, path, created_by, version) VALUES (?1, 'item-1', ?2, ?3, 'ytfetch_subtitle_json_v1', ?4, 'test', ?5)",
+                params![id, kind, lang, format!("/{id}.json"), version],
+            )
+            .expect("insert track");
+        }
+        drop(conn);
+
+        let ids = |options: ListTracksOptions| -> (Vec<String>, usize) {
+            let page = list_tracks_filtered(&paths, "item-1", &options).expect("list");
+            (
+                page.tracks.into_iter().map(|t| t.id).collect(),
+                page.total_count,
+            )
+        };
+
+        assert_eq!(
+            ids(ListTracksOptions::default()),
+            (
+                vec!["ja-1".into(), "en-1".into(), "ja-2".into(), "de-1".into()],
+                4
+            )
+        );
+        assert_eq!(
+            ids(ListTracksOptions {
+                sort_by: TrackSortField::Version,
+                sort_desc: true,
+                ..Default::default()
+            })
+            .0,
+            vec!["ja-2", "de-1", "en-1", "ja-1"]
+        );
+        assert_eq!(
+            ids(ListTracksOptions {
+                sort_by: TrackSortField::Lang,
+                kind_filter: Some(" translated ".to_string()),
+                ..Default::default()
+            }),
+            (vec!["de-1".into(), "en-1".into()], 2)
+        );
+        assert_eq!(
+            ids(ListTracksOptions {
+                lang_filter: Some("ja".to_string()),
+                sort_desc: true,
+                ..Default::default()
+            })
+            .0,
+            vec!["ja-2", "ja-1"]
+        );
+        assert_eq!(
+            ids(ListTracksOptions {
+                kind_filter: Some("source".to_string()),
+                lang_filter: Some("en".to_string()),
+                ..Default::default()
+            }),
+            (Vec::new(), 0)
+        );
+    }
+
     #[test]
     fn versionless_stem_strips_trailing_version_suffix() {
         assert_eq!(
@@ -250,6 +860,33 @@ mod tests {
         );
     }
 
+    #[test]
+    fn document_cache_evicts_oldest_and_rejects_changed_files() {
+        let doc = SubtitleDocument {
+            schema_version: SUBTITLE_JSON_SCHEMA_VERSION,
+            kind: "source".to_string(),
+            lang: "ja".to_string(),
+            segments: Vec::new(),
+            metadata: Default::default(),
+        };
+        let stamp = |len| DocumentFileStamp {
+            path: "a.json".to_string(),
+            len,
+        };
+        let key = |id: &str, secs| (id.to_string(), secs);
+        let mut cache = DocumentCache::new(2);
+        cache.insert(key("a", 1), stamp(10), doc.clone());
+        cache.insert(key("b", 1), stamp(10), doc.clone());
+        assert!(cache.get(&key("a", 1), &stamp(10)).is_some());
+        cache.insert(key("c", 1), stamp(10), doc.clone());
+        assert!(cache.get(&key("b", 1), &stamp(10)).is_none());
+        assert!(cache.get(&key("a", 1), &stamp(11)).is_none());
+        assert!(cache.get(&key("a", 1), &stamp(10)).is_none());
+        cache.insert(key("c", 2), stamp(10), doc);
+        assert!(cache.get(&key("c", 1), &stamp(10)).is_none());
+        assert!(cache.get(&key("c", 2), &stamp(10)).is_some());
+    }
+
     #[test]
     fn save_new_version_creates_new_file_and_row() {
         let dir = tempfile::tempdir().expect("tempdir");
@@ -297,7 +934,9 @@ INSERT INTO library_item (
                 end_ms: 1000,
                 text: "hello".to_string(),
                 speaker: None,
+                confidence: None,
             }],
+            metadata: Default::default(),
         };
         crate::subtitles::write_artifacts(
             &base_doc,
@@ -337,13 +976,93 @@ INSERT INTO subtitle_track (
         let mut edited = base_doc.clone();
         edited.segments[0].text = "edited".to_string();
 
-        let saved = save_new_version(&paths, base_track_id, edited).expect("save");
-        assert_eq!(saved.version, 2);
-        assert!(Path::new(&saved.path).exists());
+        let unchanged =
+            save_new_version(&paths, base_track_id, base_doc.clone(), None).expect("no-op");
+        assert!(unchanged.was_no_op);
+        assert_eq!(unchanged.track.id, base_track_id);
+
+        let saved = save_new_version(&paths, base_track_id, edited.clone(), None).expect("save");
+        assert!(!saved.was_no_op);
+        assert_eq!(saved.track.version, 2);
+        assert!(Path::new(&saved.track.path).exists());
         assert!(base_json_path.exists());
 
+        let again = save_new_version(&paths, base_track_id, edited, None).expect("save again");
+        assert!(again.was_no_op);
+        assert_eq!(again.track.id, saved.track.id);
+
         let all = list_tracks(&paths, item_id).expect("list");
         assert_eq!(all.len(), 2);
+
+        let versions = list_track_versions(&paths, item_id, "source", "ja").expect("versions");
+        assert_eq!(
+            versions.iter().map(|t| t.version).collect::<Vec<_>>(),
+            vec![2, 1]
+        );
+
+        let restored = restore_version(&paths, base_track_id).expect("restore");
+        assert_eq!(restored.version, 3);
+        let restored_doc = load_document(&paths, &restored.id).expect("load restored");
+        assert_eq!(restored_doc.segments[0].text, "hello");
+
+        let metadata = set_document_metadata(
+            &paths,
+            &restored.id,
+            " asr_model_id ",
+            serde_json::json!("whispercpp-small"),
+        )
+        .expect("set metadata");
+        assert_eq!(metadata["asr_model_id"], "whispercpp-small");
+        let unchanged = save_new_version(
+            &paths,
+            &restored.id,
+            load_document(&paths, &restored.id).expect("reload"),
+            None,
+        )
+        .expect("no-op after metadata");
+        assert!(unchanged.was_no_op);
+        let cleared = set_document_metadata(
+            &paths,
+            &restored.id,
+            "asr_model_id",
+            serde_json::Value::Null,
+        )
+        .expect("clear metadata");
+        assert!(cleared.is_empty());
+        assert!(get_document_metadata(&paths, &restored.id)
+            .expect("get metadata")
+            .is_empty());
+
+        let mut translated = load_document(&paths, &restored.id).expect("reload");
+        translated.segments[0].text = "bonjour".to_string();
+        let annotated = save_new_version(
+            &paths,
+            &restored.id,
+            translated,
+            Some(HashMap::from([(
+                "produced_by".to_string(),
+                serde_json::json!("translate:test"),
+            )])),
+        )
+        .expect("save with metadata");
+        assert_eq!(
+            get_document_metadata(&paths, &annotated.track.id).expect("metadata")["produced_by"],
+            "translate:test"
+        );
+
+        let noted = annotate_version(&paths, &annotated.track.id, Some(" checked ".to_string()))
+            .expect("annotate");
+        assert_eq!(noted.notes.as_deref(), Some("checked"));
+        let versions = list_track_versions(&paths, item_id, "source", "ja").expect("versions");
+        assert_eq!(versions[0].notes.as_deref(), Some("checked"));
+        assert!(annotate_version(
+            &paths,
+            &annotated.track.id,
+            Some("x".repeat(SUBTITLE_TRACK_NOTES_MAX_CHARS + 1))
+        )
+        .is_err());
+        let cleared = annotate_version(&paths, &annotated.track.id, None).expect("clear notes");
+        assert_eq!(cleared.notes, None);
     }
 
     fn now_ms_test() -> i64 {
@@ -1,15 +1,26 @@
 use crate::{EngineError, Result};
+use regex::{NoExpand, Regex, RegexBuilder};
 use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
 use std::path::Path;
 
 pub const SUBTITLE_JSON_SCHEMA_VERSION: u32 = 1;
 
+const FIND_REPLACE_PREVIEW_SAMPLES: usize = 5;
+const FIND_REPLACE_REGEX_PREFIX: &str = "regex:";
+pub const LOW_CONFIDENCE_FILTER_DEFAULT_THRESHOLD: f32 = -0.9;
+const LOW_CONFIDENCE_PLACEHOLDER: &str = "[inaudible]";
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SubtitleDocument {
     pub schema_version: u32,
     pub kind: String,
     pub lang: String,
     pub segments: Vec<SubtitleSegment>,
+    /// Free-form document-level data such as the ASR model or detected language.
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, serde_json::Value>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -20,6 +31,9 @@ pub struct SubtitleSegment {
     pub text: String,
     #[serde(default)]
     pub speaker: Option<String>,
+    /// Whisper's mean token log probability for this segment, when known (0 is most certain).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub confidence: Option<f32>,
 }
 
 pub fn usable_segment_count(doc: &SubtitleDocument) -> usize {
@@ -29,6 +43,36 @@ pub fn usable_segment_count(doc: &SubtitleDocument) -> usize {
         .count()
 }
 
+/// Compact JSON with object keys sorted, so equal documents always serialize identically.
+pub fn canonical_json(doc: &SubtitleDocument) -> String {
+    fn sorted(value: serde_json::Value) -> serde_json::Value {
+        match value {
+            serde_json::Value::Object(map) => {
+                let mut entries = map.into_iter().collect::<Vec<_>>();
+                entries.sort_by(|a, b| a.0.cmp(&b.0));
+                serde_json::Value::Object(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (key, sorted(value)))
+                        .collect(),
+                )
+            }
+            serde_json::Value::Array(items) => {
+                serde_json::Value::Array(items.into_iter().map(sorted).collect())
+            }
+            other => other,
+        }
+    }
+
+    let value = serde_json::to_value(doc).unwrap_or(serde_json::Value::Null);
+    sorted(value).to_string()
+}
+
+/// SHA-256 (hex) of [`canonical_json`].
+pub fn content_hash(doc: &SubtitleDocument) -> String {
+    hex::encode(Sha256::digest(canonical_json(doc).as_bytes()))
+}
+
 pub fn write_artifacts(
     doc: &SubtitleDocument,
     json_path: &Path,
@@ -51,37 +95,197 @@ pub fn write_artifacts(
     Ok(())
 }
 
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct SrtValidationWarning {
+    /// 1-based cue number as written in the SRT.
+    pub cue: usize,
+    pub message: String,
+}
+
+/// Cue times for export. A cue whose times exactly repeat the previous segment's is shifted 1 ms
+/// past the previous written cue, since some players drop cues with duplicate timestamps.
+fn export_cue_times(segments: &[SubtitleSegment]) -> Vec<(i64, i64)> {
+    let mut out: Vec<(i64, i64)> = Vec::with_capacity(segments.len());
+    let mut prev_source: Option<(i64, i64)> = None;
+    for seg in segments {
+        let source = (seg.start_ms, seg.end_ms);
+        let times = match (prev_source, out.last()) {
+            (Some(prev), Some(&(prev_start, prev_end))) if prev == source => {
+                (prev_start + 1, prev_end + 1)
+            }
+            _ => source,
+        };
+        prev_source = Some(source);
+        out.push(times);
+    }
+    out
+}
+
+/// Reports cues in rendered SRT text whose timestamp line repeats an earlier cue's.
+pub fn validate_srt_output(srt_str: &str) -> Vec<SrtValidationWarning> {
+    let mut warnings = Vec::new();
+    let mut seen: HashMap<&str, usize> = HashMap::new();
+    let mut cue = 0usize;
+    for line in srt_str.lines() {
+        let line = line.trim();
+        if !line.contains(" --> ") {
+            continue;
+        }
+        cue += 1;
+        if let Some(first) = seen.get(line) {
+            warnings.push(SrtValidationWarning {
+                cue,
+                message: format!("duplicate timestamps of cue {first}: {line}"),
+            });
+        } else {
+            seen.insert(line, cue);
+        }
+    }
+    warnings
+}
+
+/// Cue settings and speaker labelling for WebVTT export.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ExportVttOptions {
+    /// Prefix cues with `<v Speaker>` and place each distinct speaker in its own `REGION`.
+    #[serde(default)]
+    pub include_speaker_labels: bool,
+    /// Horizontal cue position as a percentage (`"10"` or `"10%"`).
+    #[serde(default)]
+    pub position: Option<String>,
+    /// One of `start`, `center`, `end`, `left` or `right`.
+    #[serde(default)]
+    pub align: Option<String>,
+}
+
 pub fn render_srt(doc: &SubtitleDocument) -> Result<String> {
+    render_srt_with_options(doc, false)
+}
+
+/// Like [`render_srt`], optionally prefixing each cue with `[Speaker]`.
+pub fn render_srt_with_options(
+    doc: &SubtitleDocument,
+    include_speaker_labels: bool,
+) -> Result<String> {
     let mut out = String::new();
-    for (idx, seg) in doc.segments.iter().enumerate() {
+    let times = export_cue_times(&doc.segments);
+    for (idx, (seg, (start_ms, end_ms))) in doc.segments.iter().zip(times).enumerate() {
         let n = idx + 1;
         out.push_str(&format!("{n}\n"));
         out.push_str(&format!(
             "{} --> {}\n",
-            format_srt_ts(seg.start_ms),
-            format_srt_ts(seg.end_ms)
+            format_srt_ts(start_ms),
+            format_srt_ts(end_ms)
         ));
-        out.push_str(&sanitize_text(&seg.text));
+        match segment_speaker(seg).filter(|_| include_speaker_labels) {
+            Some(speaker) => out.push_str(&format!("[{speaker}] {}", sanitize_text(&seg.text))),
+            None => out.push_str(&sanitize_text(&seg.text)),
+        }
         out.push_str("\n\n");
     }
     Ok(out)
 }
 
 pub fn render_vtt(doc: &SubtitleDocument) -> Result<String> {
+    render_vtt_with_options(doc, &ExportVttOptions::default())
+}
+
+pub fn render_vtt_with_options(
+    doc: &SubtitleDocument,
+    options: &ExportVttOptions,
+) -> Result<String> {
+    let position = options
+        .position
+        .as_deref()
+        .map(normalize_vtt_position)
+        .transpose()?;
+    let align = options
+        .align
+        .as_deref()
+        .map(normalize_vtt_align)
+        .transpose()?;
+
+    let mut regions: Vec<&str> = Vec::new();
+    if options.include_speaker_labels {
+        for speaker in doc.segments.iter().filter_map(segment_speaker) {
+            if !regions.contains(&speaker) {
+                regions.push(speaker);
+            }
+        }
+    }
+
     let mut out = String::new();
     out.push_str("WEBVTT\n\n");
-    for seg in &doc.segments {
+    for (idx, _) in regions.iter().enumerate() {
+        // Alternate speakers between the lower left and lower right of the frame.
+        let (region_x, viewport_x) = if idx % 2 == 0 { (0, 5) } else { (100, 95) };
         out.push_str(&format!(
-            "{} --> {}\n",
-            format_vtt_ts(seg.start_ms),
-            format_vtt_ts(seg.end_ms)
+            "REGION\nid:speaker{}\nwidth:40%\nlines:3\nregionanchor:{region_x}%,100%\nviewportanchor:{viewport_x}%,90%\nscroll:up\n\n",
+            idx + 1
+        ));
+    }
+
+    let times = export_cue_times(&doc.segments);
+    for (seg, (start_ms, end_ms)) in doc.segments.iter().zip(times) {
+        out.push_str(&format!(
+            "{} --> {}",
+            format_vtt_ts(start_ms),
+            format_vtt_ts(end_ms)
         ));
+        let speaker = segment_speaker(seg).filter(|_| options.include_speaker_labels);
+        if let Some(region) = speaker.and_then(|s| regions.iter().position(|r| *r == s)) {
+            out.push_str(&format!(" region:speaker{}", region + 1));
+        }
+        if let Some(position) = position {
+            out.push_str(&format!(" position:{position}%"));
+        }
+        if let Some(align) = align {
+            out.push_str(&format!(" align:{align}"));
+        }
+        out.push('\n');
+        if let Some(speaker) = speaker {
+            out.push_str(&format!("<v {}>", speaker.replace(['<', '>'], "")));
+        }
         out.push_str(&sanitize_text(&seg.text));
         out.push_str("\n\n");
     }
     Ok(out)
 }
 
+fn segment_speaker(seg: &SubtitleSegment) -> Option<&str> {
+    seg.speaker
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+}
+
+fn normalize_vtt_position(raw: &str) -> Result<f64> {
+    let trimmed = raw.trim();
+    trimmed
+        .strip_suffix('%')
+        .unwrap_or(trimmed)
+        .trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|v| (0.0..=100.0).contains(v))
+        .ok_or_else(|| EngineError::InvalidInput {
+            message: format!("VTT position must be a percentage between 0 and 100, got {raw:?}"),
+        })
+}
+
+fn normalize_vtt_align(raw: &str) -> Result<&'static str> {
+    match raw.trim().to_ascii_lowercase().as_str() {
+        "start" => Ok("start"),
+        "center" => Ok("center"),
+        "end" => Ok("end"),
+        "left" => Ok("left"),
+        "right" => Ok("right"),
+        _ => Err(EngineError::InvalidInput {
+            message: format!("VTT align must be start, center, end, left or right, got {raw:?}"),
+        }),
+    }
+}
+
 fn sanitize_text(text: &str) -> String {
     text.replace('\r', "").trim().to_string()
 }
@@ -106,6 +310,78 @@ fn format_vtt_ts(ms: i64) -> String {
     format!("{hours:02}:{minutes:02}:{seconds:02}.{millis:03}")
 }
 
+/// Parses SRT text into a `source` document in `lang`.
+pub fn parse_srt(text: &str, lang: &str) -> Result<SubtitleDocument> {
+    parse_cue_blocks(text, lang, "SRT")
+}
+
+/// Parses WebVTT text into a `source` document in `lang`; header, NOTE, STYLE and REGION
+/// blocks are skipped and cue settings are dropped.
+pub fn parse_vtt(text: &str, lang: &str) -> Result<SubtitleDocument> {
+    parse_cue_blocks(text, lang, "VTT")
+}
+
+fn parse_cue_blocks(text: &str, lang: &str, format_label: &str) -> Result<SubtitleDocument> {
+    let text = text.trim_start_matches('\u{feff}').replace('\r', "");
+    let mut segments: Vec<SubtitleSegment> = Vec::new();
+    for block in text.split("\n\n") {
+        let lines: Vec<&str> = block.lines().filter(|l| !l.trim().is_empty()).collect();
+        let Some(timing_pos) = lines.iter().position(|l| l.contains("-->")) else {
+            continue;
+        };
+        let (start_raw, end_raw) = lines[timing_pos].split_once("-->").unwrap_or_default();
+        let end_raw = end_raw.split_whitespace().next().unwrap_or("");
+        let (Some(start_ms), Some(end_ms)) =
+            (parse_cue_timestamp(start_raw), parse_cue_timestamp(end_raw))
+        else {
+            return Err(EngineError::InvalidInput {
+                message: format!(
+                    "{format_label} cue has an invalid timestamp line: {:?}",
+                    lines[timing_pos]
+                ),
+            });
+        };
+        let cue_text = lines[timing_pos + 1..].join("\n");
+        segments.push(SubtitleSegment {
+            index: segments.len() as u32,
+            start_ms,
+            end_ms: end_ms.max(start_ms),
+            text: sanitize_text(&cue_text),
+            speaker: None,
+            confidence: None,
+        });
+    }
+    if segments.is_empty() {
+        return Err(EngineError::InvalidInput {
+            message: format!("{format_label} file contains no cues"),
+        });
+    }
+    Ok(SubtitleDocument {
+        schema_version: SUBTITLE_JSON_SCHEMA_VERSION,
+        kind: "source".to_string(),
+        lang: lang.to_string(),
+        segments,
+        metadata: HashMap::new(),
+    })
+}
+
+/// `HH:MM:SS,mmm`, `HH:MM:SS.mmm` or `MM:SS.mmm` to milliseconds.
+fn parse_cue_timestamp(raw: &str) -> Option<i64> {
+    let raw = raw.trim().replace(',', ".");
+    let (clock, millis) = raw.split_once('.').unwrap_or((raw.as_str(), "0"));
+    let millis: i64 = format!("{millis:0<3}").get(..3)?.parse().ok()?;
+    let parts = clock
+        .split(':')
+        .map(|part| part.parse::<i64>().ok())
+        .collect::<Option<Vec<i64>>>()?;
+    let (hours, minutes, seconds) = match parts.as_slice() {
+        [h, m, s] => (*h, *m, *s),
+        [m, s] => (0, *m, *s),
+        _ => return None,
+    };
+    Some(((hours * 60 + minutes) * 60 + seconds) * 1000 + millis)
+}
+
 pub fn validate_document(doc: &SubtitleDocument) -> Result<()> {
     if doc.schema_version != SUBTITLE_JSON_SCHEMA_VERSION {
         return Err(EngineError::InstallFailed(format!(
@@ -115,3 +391,241 @@ pub fn validate_document(doc: &SubtitleDocument) -> Result<()> {
     }
     Ok(())
 }
+
+#[derive(Debug, Clone, Serialize)]
+pub struct FindReplacePreview {
+    pub matches: usize,
+    pub sample_before: Vec<String>,
+    pub sample_after: Vec<String>,
+}
+
+/// Replaces `pattern` in every segment text, returning the edited copy and the match count.
+/// A `regex:` prefix switches to a regex pattern, whose replacement may use `$1`-style groups;
+/// plain patterns and replacements are taken literally.
+pub fn find_and_replace(
+    doc: &SubtitleDocument,
+    pattern: &str,
+    replacement: &str,
+    case_sensitive: bool,
+    whole_word: bool,
+) -> Result<(SubtitleDocument, usize)> {
+    let (re, is_regex) = find_replace_regex(pattern, case_sensitive, whole_word)?;
+    let mut out = doc.clone();
+    let mut matches = 0_usize;
+    for seg in &mut out.segments {
+        let count = re.find_iter(&seg.text).count();
+        if count == 0 {
+            continue;
+        }
+        matches += count;
+        seg.text = if is_regex {
+            re.replace_all(&seg.text, replacement).into_owned()
+        } else {
+            re.replace_all(&seg.text, NoExpand(replacement))
+                .into_owned()
+        };
+    }
+    Ok((out, matches))
+}
+
+/// Dry run of [`find_and_replace`] with the first few changed segment texts.
+pub fn find_and_replace_preview(
+    doc: &SubtitleDocument,
+    pattern: &str,
+    replacement: &str,
+    case_sensitive: bool,
+    whole_word: bool,
+) -> Result<FindReplacePreview> {
+    let (replaced, matches) =
+        find_and_replace(doc, pattern, replacement, case_sensitive, whole_word)?;
+    let (sample_before, sample_after) = doc
+        .segments
+        .iter()
+        .zip(&replaced.segments)
+        .filter(|(before, after)| before.text != after.text)
+        .take(FIND_REPLACE_PREVIEW_SAMPLES)
+        .map(|(before, after)| (before.text.clone(), after.text.clone()))
+        .unzip();
+    Ok(FindReplacePreview {
+        matches,
+        sample_before,
+        sample_after,
+    })
+}
+
+/// Replaces the text of segments whose confidence is below `threshold` (clamped to
+/// `-2.0..=0.0`) with a placeholder. Segments without a confidence are kept.
+pub fn replace_low_confidence_segments(
+    doc: &SubtitleDocument,
+    threshold: f32,
+) -> (SubtitleDocument, usize) {
+    let threshold = if threshold.is_finite() {
+        threshold.clamp(-2.0, 0.0)
+    } else {
+        LOW_CONFIDENCE_FILTER_DEFAULT_THRESHOLD
+    };
+    let mut out = doc.clone();
+    let mut replaced = 0_usize;
+    for seg in &mut out.segments {
+        if seg.confidence.is_some_and(|c| c < threshold) && seg.text != LOW_CONFIDENCE_PLACEHOLDER {
+            seg.text = LOW_CONFIDENCE_PLACEHOLDER.to_string();
+            replaced += 1;
+        }
+    }
+    (out, replaced)
+}
+
+fn find_replace_regex(
+    pattern: &str,
+    case_sensitive: bool,
+    whole_word: bool,
+) -> Result<(Regex, bool)> {
+    let (body, is_regex) = match pattern.strip_prefix(FIND_REPLACE_REGEX_PREFIX) {
+        Some(raw) => (raw.to_string(), true),
+        None => (regex::escape(pattern), false),
+    };
+    if body.is_empty() {
+        return Err(EngineError::InstallFailed(
+            "find pattern is empty".to_string(),
+        ));
+    }
+    let body = if whole_word {
+        format!(r"\b(?:{body})\b")
+    } else {
+        body
+    };
+    let re = RegexBuilder::new(&body)
+        .case_insensitive(!case_sensitive)
+        .build()
+        .map_err(|e| EngineError::InstallFailed(format!("invalid find pattern: {e}")))?;
+    Ok((re, is_regex))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn doc(texts: &[&str]) -> SubtitleDocument {
+        SubtitleDocument {
+            schema_version: SUBTITLE_JSON_SCHEMA_VERSION,
+            kind: "translated".to_string(),
+            lang: "en".to_string(),
+            segments: texts
+                .iter()
+                .enumerate()
+                .map(|(i, text)| SubtitleSegment {
+                    index: i as u32,
+                    start_ms: i as i64 * 1000,
+                    end_ms: i as i64 * 1000 + 900,
+                    text: text.to_string(),
+                    speaker: None,
+                    confidence: None,
+                })
+                .collect(),
+            metadata: Default::default(),
+        }
+    }
+
+    #[test]
+    fn vtt_options_add_speaker_regions_and_cue_settings() {
+        let mut source = doc(&["Hi", "Hello", "Bye"]);
+        source.segments[0].speaker = Some("Alice".to_string());
+        source.segments[1].speaker = Some("Bob".to_string());
+        source.segments[2].speaker = Some("Alice".to_string());
+
+        assert_eq!(
+            render_vtt(&source).expect("plain"),
+            "WEBVTT\n\n00:00:00.000 --> 00:00:00.900\nHi\n\n00:00:01.000 --> 00:00:01.900\nHello\n\n00:00:02.000 --> 00:00:02.900\nBye\n\n"
+        );
+
+        let options = ExportVttOptions {
+            include_speaker_labels: true,
+            position: Some("10%".to_string()),
+            align: Some("Start".to_string()),
+        };
+        let vtt = render_vtt_with_options(&source, &options).expect("vtt");
+        assert_eq!(vtt.matches("REGION\n").count(), 2);
+        assert!(vtt.contains("id:speaker2\n"));
+        assert!(vtt.contains(
+            "00:00:01.000 --> 00:00:01.900 region:speaker2 position:10% align:start\n<v Bob>Hello"
+        ));
+        assert!(vtt.contains("00:00:02.000 --> 00:00:02.900 region:speaker1 "));
+
+        let bad = ExportVttOptions {
+            position: Some("120".to_string()),
+            ..ExportVttOptions::default()
+        };
+        assert!(render_vtt_with_options(&source, &bad).is_err());
+
+        let srt = render_srt_with_options(&source, true).expect("srt");
+        assert!(srt.contains("[Bob] Hello\n"));
+        assert!(!render_srt(&source).expect("plain srt").contains("[Bob]"));
+    }
+
+    #[test]
+    fn replace_low_confidence_segments_uses_clamped_threshold() {
+        let mut source = doc(&["clear", "mumbled", "unknown"]);
+        source.segments[0].confidence = Some(-0.2);
+        source.segments[1].confidence = Some(-1.4);
+
+        let (out, replaced) = replace_low_confidence_segments(&source, -0.9);
+        assert_eq!(replaced, 1);
+        assert_eq!(out.segments[0].text, "clear");
+        assert_eq!(out.segments[1].text, "[inaudible]");
+        assert_eq!(out.segments[2].text, "unknown");
+
+        let (_, replaced) = replace_low_confidence_segments(&source, -5.0);
+        assert_eq!(replaced, 0);
+        let (_, replaced) = replace_low_confidence_segments(&source, 1.0);
+        assert_eq!(replaced, 2);
+    }
+
+    #[test]
+    fn find_and_replace_supports_literal_whole_word_and_regex_patterns() {
+        let source = doc(&["Tanaka-san said hi", "tanaka and Tanakas", "$5 for Tanaka"]);
+
+        let (out, matches) = find_and_replace(&source, "tanaka", "Tanaka", false, true).unwrap();
+        assert_eq!(matches, 3);
+        assert_eq!(out.segments[1].text, "Tanaka and Tanakas");
+
+        let (out, matches) = find_and_replace(&source, "$5", "$6", true, false).unwrap();
+        assert_eq!(matches, 1);
+        assert_eq!(out.segments[2].text, "$6 for Tanaka");
+
+        let (out, matches) =
+            find_and_replace(&source, r"regex:(\w+)-san", "Mr. $1", true, false).unwrap();
+        assert_eq!(matches, 1);
+        assert_eq!(out.segments[0].text, "Mr. Tanaka said hi");
+
+        let preview = find_and_replace_preview(&source, "Tanaka", "Sato", true, false).unwrap();
+        assert_eq!(preview.matches, 3);
+        assert_eq!(preview.sample_before.len(), 3);
+        assert_eq!(preview.sample_after[0], "Sato-san said hi");
+
+        assert!(find_and_replace(&source, "regex:(", "", true, false).is_err());
+    }
+
+    #[test]
+    fn render_shifts_repeated_zero_duration_cues_apart() {
+        let mut source = doc(&["a", "b", "c"]);
+        for seg in &mut source.segments {
+            seg.start_ms = 1000;
+            seg.end_ms = 1000;
+        }
+        let raw: String = source
+            .segments
+            .iter()
+            .map(|_| "1\n00:00:01,000 --> 00:00:01,000\nx\n\n")
+            .collect();
+        assert_eq!(validate_srt_output(&raw).len(), 2);
+
+        let srt = render_srt(&source).unwrap();
+        assert!(validate_srt_output(&srt).is_empty(), "{srt}");
+        assert!(srt.contains("00:00:01,001 --> 00:00:01,001"));
+        assert!(srt.contains("00:00:01,002 --> 00:00:01,002"));
+
+        let vtt = render_vtt(&source).unwrap();
+        assert!(vtt.contains("00:00:01.000 --> 00:00:01.000"));
+        assert!(vtt.contains("00:00:01.002 --> 00:00:01.002"));
+    }
+}
@@ -1,7 +1,8 @@
 use crate::paths::AppPaths;
-use crate::{pinned_dependency_manifest, vendor_patches};
+use crate::{db, pinned_dependency_manifest, vendor_patches};
 use crate::{EngineError, Result};
 use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap};
 use std::io::Write;
 use std::path::{Path, PathBuf};
 
@@ -12,13 +13,16 @@ pub struct FfmpegToolsStatus {
     pub ffprobe_path: String,
     pub ffmpeg_version: Option<String>,
     pub ffprobe_version: Option<String>,
+    pub is_custom_path: bool,
+    pub resolved_path: String,
 }
 
 pub fn ffmpeg_tools_status(paths: &AppPaths) -> FfmpegToolsStatus {
     let ffmpeg_path = paths.ffmpeg_bin_path();
     let ffprobe_path = paths.ffprobe_bin_path();
     let installed = ffmpeg_path.exists() && ffprobe_path.exists();
-    let ffmpeg_version = tool_version_first_line(paths.ffmpeg_cmd());
+    let resolved = paths.ffmpeg_cmd();
+    let ffmpeg_version = tool_version_first_line(&resolved);
     let ffprobe_version = tool_version_first_line(paths.ffprobe_cmd());
 
     FfmpegToolsStatus {
@@ -27,6 +31,8 @@ pub fn ffmpeg_tools_status(paths: &AppPaths) -> FfmpegToolsStatus {
         ffprobe_path: ffprobe_path.to_string_lossy().to_string(),
         ffmpeg_version,
         ffprobe_version,
+        is_custom_path: paths.custom_ffmpeg_path().is_some(),
+        resolved_path: resolved.to_string_lossy().to_string(),
     }
 }
 
@@ -136,6 +142,76 @@ fn tool_version_first_line(program: impl AsRef<std::ffi::OsStr>) -> Option<Strin
     Some(first.to_string())
 }
 
+pub fn probe_ffmpeg_version(paths: &AppPaths) -> Result<String> {
+    probe_tool_version(paths.ffmpeg_cmd(), "-version", "ffmpeg")
+}
+
+pub fn probe_ffprobe_version(paths: &AppPaths) -> Result<String> {
+    probe_tool_version(paths.ffprobe_cmd(), "-version", "ffprobe")
+}
+
+pub fn probe_ytdlp_version(paths: &AppPaths) -> Result<String> {
+    let bundled = bundled_ytdlp_path(paths);
+    let program = if let Some(custom) = paths.custom_ytdlp_path() {
+        custom
+    } else if bundled.exists() {
+        bundled
+    } else {
+        std::path::PathBuf::from("yt-dlp")
+    };
+    probe_tool_version(program, "--version", "yt-dlp")
+}
+
+/// Unlike `tool_version_first_line`, keeps the failure reason (missing binary or stderr)
+/// so the UI can show why a tool is unusable.
+fn probe_tool_version(
+    program: impl AsRef<std::ffi::OsStr>,
+    arg: &str,
+    tool: &str,
+) -> Result<String> {
+    let output = crate::cmd::command(program)
+        .arg(arg)
+        .output()
+        .map_err(|e| match e.kind() {
+            std::io::ErrorKind::NotFound => EngineError::ExternalToolMissing {
+                tool: tool.to_string(),
+            },
+            _ => EngineError::Io(e),
+        })?;
+    if !output.status.success() {
+        return Err(EngineError::ExternalToolFailed {
+            tool: tool.to_string(),
+            code: output.status.code(),
+            stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
+        });
+    }
+    let text = String::from_utf8_lossy(&output.stdout);
+    let first = text.lines().next().unwrap_or("").trim();
+    if first.is_empty() {
+        return Err(EngineError::ExternalToolFailed {
+            tool: tool.to_string(),
+            code: output.status.code(),
+            stderr: format!(
+                "empty version output; stderr: {}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ),
+        });
+    }
+    Ok(parse_version_line(first))
+}
+
+fn parse_version_line(line: &str) -> String {
+    let mut tokens = line.split_whitespace();
+    while let Some(token) = tokens.next() {
+        if token.eq_ignore_ascii_case("version") {
+            if let Some(version) = tokens.next() {
+                return version.to_string();
+            }
+        }
+    }
+    line.trim().to_string()
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct YtDlpToolsStatus {
     pub available: bool,
@@ -179,6 +255,9 @@ pub fn ytdlp_tools_status(paths: &AppPaths) -> YtDlpToolsStatus {
     let mut available = false;
 
     let mut candidates: Vec<std::path::PathBuf> = Vec::new();
+    if let Some(custom) = paths.custom_ytdlp_path() {
+        candidates.push(custom);
+    }
     if bundled_installed {
         candidates.push(bundled.clone());
     }
@@ -208,10 +287,13 @@ pub fn install_ytdlp_tools(paths: &AppPaths) -> Result<YtDlpToolsStatus> {
 
     #[cfg(not(windows))]
     {
-        let _ = paths;
-        return Err(EngineError::InstallFailed(
-            "automatic yt-dlp install is only supported on Windows for now".to_string(),
-        ));
+        install_ytdlp_release_asset(
+            paths,
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            download_ytdlp_release_file,
+        )?;
+        Ok(ytdlp_tools_status(paths))
     }
 
     #[cfg(windows)]
@@ -287,6 +369,242 @@ pub fn install_ytdlp_tools(paths: &AppPaths) -> Result<YtDlpToolsStatus> {
     }
 }
 
+const YT_DLP_LATEST_RELEASE_API_URL: &str =
+    "https://api.github.com/repos/yt-dlp/yt-dlp/releases/latest";
+const YT_DLP_UPDATE_CHECK_TIMEOUT_SECS: u64 = 5;
+const YT_DLP_UPDATE_CHECK_TTL_MS: u64 = 24 * 60 * 60 * 1000;
+const META_KEY_YT_DLP_UPDATE_CHECK: &str = "ytdlp_update_check_v1";
+
+#[derive(Debug, Clone, Serialize)]
+pub struct YtDlpUpdateInfo {
+    pub installed_version: Option<String>,
+    pub latest_version: Option<String>,
+    pub update_available: bool,
+    pub checked_at_ms: i64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct YtDlpUpdateCheckCache {
+    latest_version: String,
+    checked_at_ms: i64,
+}
+
+/// Compares the resolved yt-dlp against the latest GitHub release. The release lookup is cached
+/// in `meta` for a day unless `force_refresh` is set.
+pub fn check_ytdlp_update(paths: &AppPaths, force_refresh: bool) -> Result<YtDlpUpdateInfo> {
+    let conn = db::open(paths)?;
+    db::migrate(&conn)?;
+    let cached: Option<YtDlpUpdateCheckCache> = db::get_meta(&conn, META_KEY_YT_DLP_UPDATE_CHECK)?
+        .and_then(|raw| serde_json::from_str(&raw).ok());
+    let cache = match cached {
+        Some(cache) if !force_refresh => cache,
+        _ => {
+            let cache = YtDlpUpdateCheckCache {
+                latest_version: fetch_latest_ytdlp_version()?,
+                checked_at_ms: now_ms(),
+            };
+            db::set_meta_with_ttl(
+                &conn,
+                META_KEY_YT_DLP_UPDATE_CHECK,
+                &serde_json::to_string(&cache)?,
+                YT_DLP_UPDATE_CHECK_TTL_MS,
+            )?;
+            cache
+        }
+    };
+
+    let installed_version = ytdlp_tools_status(paths).ytdlp_version;
+    let update_available = match installed_version.as_deref() {
+        Some(installed) => ytdlp_version_is_newer(&cache.latest_version, installed),
+        None => true,
+    };
+    Ok(YtDlpUpdateInfo {
+        installed_version,
+        latest_version: Some(cache.latest_version),
+        update_available,
+        checked_at_ms: cache.checked_at_ms,
+    })
+}
+
+/// Replaces the bundled yt-dlp with the latest release when a newer one is available.
+pub fn update_ytdlp_tools(paths: &AppPaths) -> Result<YtDlpToolsStatus> {
+    if paths.custom_ytdlp_path().is_some() {
+        return Err(EngineError::InstallFailed(
+            "a custom yt-dlp path is configured; update that binary manually".to_string(),
+        ));
+    }
+    let info = check_ytdlp_update(paths, false)?;
+    if !info.update_available {
+        return Ok(ytdlp_tools_status(paths));
+    }
+    paths.ensure_dirs()?;
+    install_ytdlp_release_asset(
+        paths,
+        std::env::consts::OS,
+        std::env::consts::ARCH,
+        download_ytdlp_release_file,
+    )?;
+    Ok(ytdlp_tools_status(paths))
+}
+
+fn fetch_latest_ytdlp_version() -> Result<String> {
+    let mut config = ureq::Agent::config_builder();
+    config = config
+        .http_status_as_error(false)
+        .timeout_global(Some(std::time::Duration::from_secs(
+            YT_DLP_UPDATE_CHECK_TIMEOUT_SECS,
+        )))
+        .user_agent("VoxVulgi");
+    let agent: ureq::Agent = config.build().into();
+    let mut resp = agent
+        .get(YT_DLP_LATEST_RELEASE_API_URL)
+        .header("Accept", "application/vnd.github+json")
+        .call()
+        .map_err(|e| EngineError::InstallFailed(format!("yt-dlp release check failed: {e}")))?;
+    let status = resp.status();
+    if status.as_u16() >= 400 {
+        return Err(EngineError::InstallFailed(format!(
+            "yt-dlp release check failed (status={status})"
+        )));
+    }
+    let body = resp
+        .body_mut()
+        .read_to_string()
+        .map_err(|e| EngineError::InstallFailed(format!("yt-dlp release check failed: {e}")))?;
+    let release: serde_json::Value = serde_json::from_str(&body)?;
+    release
+        .get("tag_name")
+        .and_then(|v| v.as_str())
+        .map(|v| v.trim().trim_start_matches('v').to_string())
+        .filter(|v| !v.is_empty())
+        .ok_or_else(|| {
+            EngineError::InstallFailed("yt-dlp release check returned no tag_name".to_string())
+        })
+}
+
+/// yt-dlp versions are dotted dates (`2025.01.15`, optionally `.1` for same-day rebuilds).
+fn ytdlp_version_is_newer(latest: &str, installed: &str) -> bool {
+    let parts = |v: &str| -> Vec<u64> {
+        v.trim()
+            .trim_start_matches('v')
+            .split('.')
+            .map(|p| p.parse().unwrap_or(0))
+            .collect()
+    };
+    parts(latest) > parts(installed)
+}
+
+const YT_DLP_RELEASE_DOWNLOAD_BASE_URL: &str =
+    "https://github.com/yt-dlp/yt-dlp/releases/latest/download";
+const YT_DLP_RELEASE_CHECKSUMS_ASSET: &str = "SHA2-256SUMS";
+const YT_DLP_LINUX_X86_64_ASSET: &str = "yt-dlp_linux";
+const YT_DLP_LINUX_AARCH64_ASSET: &str = "yt-dlp_linux_aarch64";
+/// The macOS build is a universal2 binary covering both x86_64 and arm64.
+const YT_DLP_MACOS_ASSET: &str = "yt-dlp_macos";
+const YT_DLP_WINDOWS_X86_64_ASSET: &str = "yt-dlp.exe";
+
+/// Standalone yt-dlp release asset for a `std::env::consts` OS/arch pair.
+fn ytdlp_release_asset_name(os: &str, arch: &str) -> Option<&'static str> {
+    match (os, arch) {
+        ("linux", "x86_64") => Some(YT_DLP_LINUX_X86_64_ASSET),
+        ("linux", "aarch64") => Some(YT_DLP_LINUX_AARCH64_ASSET),
+        ("macos", "x86_64") | ("macos", "aarch64") => Some(YT_DLP_MACOS_ASSET),
+        ("windows", "x86_64") => Some(YT_DLP_WINDOWS_X86_64_ASSET),
+        _ => None,
+    }
+}
+
+fn download_ytdlp_release_file(url: &str, output_path: &Path) -> Result<()> {
+    let primary = (|| -> Result<()> {
+        let resp = ureq::get(url)
+            .call()
+            .map_err(|e| EngineError::InstallFailed(format!("download failed: {e}")))?;
+        let mut reader = resp.into_body().into_reader();
+        let mut file = std::fs::File::create(output_path)?;
+        std::io::copy(&mut reader, &mut file)?;
+        file.flush()?;
+        Ok(())
+    })();
+    if let Err(primary_err) = primary {
+        download_url_to_file_with_curl(url, output_path, "yt-dlp release file").map_err(
+            |fallback_err| {
+                EngineError::InstallFailed(format!(
+                    "{primary_err}; curl fallback failed: {fallback_err}"
+                ))
+            },
+        )?;
+    }
+    Ok(())
+}
+
+/// Downloads the latest yt-dlp release for `os`/`arch` into the bundled tools dir. The binary is
+/// checked against the release's `SHA2-256SUMS` file before it replaces an existing install.
+fn install_ytdlp_release_asset(
+    paths: &AppPaths,
+    os: &str,
+    arch: &str,
+    fetch: impl Fn(&str, &Path) -> Result<()>,
+) -> Result<PathBuf> {
+    let asset = ytdlp_release_asset_name(os, arch).ok_or_else(|| {
+        EngineError::InstallFailed(format!(
+            "automatic yt-dlp install is not supported on {os}/{arch}; install yt-dlp manually or set a custom path"
+        ))
+    })?;
+
+    let destination = bundled_ytdlp_path(paths);
+    if let Some(parent) = destination.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let tmp_path = destination.with_extension("download");
+    let sums_path = destination.with_extension("sha256sums");
+
+    let result = (|| -> Result<()> {
+        fetch(
+            &format!("{YT_DLP_RELEASE_DOWNLOAD_BASE_URL}/{YT_DLP_RELEASE_CHECKSUMS_ASSET}"),
+            &sums_path,
+        )?;
+        let sums = std::fs::read_to_string(&sums_path)?;
+        let expected = sums
+            .lines()
+            .filter_map(|line| line.split_once(char::is_whitespace))
+            .find(|(_, name)| name.trim().trim_start_matches('*') == asset)
+            .map(|(hash, _)| hash.trim().to_ascii_lowercase())
+            .ok_or_else(|| {
+                EngineError::InstallFailed(format!("yt-dlp checksum list has no entry for {asset}"))
+            })?;
+
+        fetch(
+            &format!("{YT_DLP_RELEASE_DOWNLOAD_BASE_URL}/{asset}"),
+            &tmp_path,
+        )?;
+        let actual = hex::encode(sha256_file(&tmp_path)?);
+        if actual != expected {
+            return Err(EngineError::HashMismatch {
+                path: tmp_path.clone(),
+                expected,
+                actual,
+            });
+        }
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&tmp_path, std::fs::Permissions::from_mode(0o755))?;
+        }
+
+        if destination.exists() {
+            let _ = std::fs::remove_file(&destination);
+        }
+        std::fs::rename(&tmp_path, &destination)?;
+        Ok(())
+    })();
+    let _ = std::fs::remove_file(&sums_path);
+    if result.is_err() {
+        let _ = std::fs::remove_file(&tmp_path);
+    }
+    result.map(|()| destination)
+}
+
 fn bundled_ytdlp_path(paths: &AppPaths) -> std::path::PathBuf {
     let mut path = paths.tools_dir().join("yt-dlp").join("yt-dlp");
     if cfg!(windows) {
@@ -520,6 +838,19 @@ fn tool_version_first_line_with_arg(
     Some(first.to_string())
 }
 
+const SYSTEM_PYTHON_MIN_VERSION: (u32, u32) = (3, 9);
+/// Written into the venv when it was created from a system Python.
+const SYSTEM_PYTHON_VENV_MARKER: &str = ".voxvulgi_system_python";
+
+/// Python interpreter found on PATH, independent of the portable install and config override.
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct SystemPythonInfo {
+    pub path: String,
+    pub version: String,
+    /// The interpreter itself runs inside a virtual environment.
+    pub is_venv: bool,
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct PythonToolchainStatus {
     pub base_available: bool,
@@ -630,6 +961,251 @@ pub fn phase2_packs_install_plan() -> Vec<Phase2PackPlanItem> {
     ]
 }
 
+/// Packages the venv needs to function; never removed by a phase-2 rollback.
+const PHASE2_ROLLBACK_PROTECTED_PACKAGES: &[&str] = &["pip", "setuptools", "wheel"];
+
+pub const PHASE2_RUN_STATUS_RUNNING: &str = "running";
+pub const PHASE2_RUN_STATUS_SUCCEEDED: &str = "succeeded";
+pub const PHASE2_RUN_STATUS_FAILED: &str = "failed";
+pub const PHASE2_RUN_STATUS_ROLLED_BACK: &str = "rolled_back";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Phase2PreInstallSnapshot {
+    pub captured_at_ms: i64,
+    /// Normalized package name -> version.
+    pub packages: BTreeMap<String, String>,
+    /// Outcome of the install run that took this snapshot; `None` for snapshots written before
+    /// the status was recorded.
+    #[serde(default)]
+    pub last_run_status: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Phase2RollbackStep {
+    pub package: String,
+    /// `"uninstall"` for packages the install added, `"reinstall"` for packages it changed.
+    pub action: String,
+    pub ok: bool,
+    pub error: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Phase2RollbackSummary {
+    pub snapshot_path: String,
+    pub steps: Vec<Phase2RollbackStep>,
+}
+
+pub fn phase2_pre_install_snapshot_path(paths: &AppPaths) -> PathBuf {
+    paths
+        .install_logs_dir()
+        .join("phase2")
+        .join("pre_install_snapshot.json")
+}
+
+fn normalize_pip_package_name(name: &str) -> String {
+    name.trim().to_ascii_lowercase().replace(['_', '.'], "-")
+}
+
+/// Installed venv packages keyed by normalized name; empty when the venv does not exist yet.
+fn pip_installed_packages(paths: &AppPaths) -> Result<BTreeMap<String, String>> {
+    let Ok(venv_python) = python_venv_python_path(paths) else {
+        return Ok(BTreeMap::new());
+    };
+    let output = crate::cmd::command(&venv_python)
+        .args([
+            "-m",
+            "pip",
+            "list",
+            "--format=json",
+            "--disable-pip-version-check",
+        ])
+        .env("PYTHONNOUSERSITE", "1")
+        .output()
+        .map_err(|e| EngineError::InstallFailed(format!("failed to run pip list: {e}")))?;
+    if !output.status.success() {
+        return Err(EngineError::InstallFailed(format!(
+            "pip list failed (code={:?}): {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+
+    #[derive(Deserialize)]
+    struct PipListEntry {
+        name: String,
+        version: String,
+    }
+
+    let entries: Vec<PipListEntry> = serde_json::from_slice(&output.stdout)
+        .map_err(|e| EngineError::InstallFailed(format!("failed to parse pip list JSON: {e}")))?;
+    Ok(entries
+        .into_iter()
+        .map(|entry| (normalize_pip_package_name(&entry.name), entry.version))
+        .collect())
+}
+
+fn read_phase2_pre_install_snapshot(paths: &AppPaths) -> Option<Phase2PreInstallSnapshot> {
+    let bytes = std::fs::read(phase2_pre_install_snapshot_path(paths)).ok()?;
+    serde_json::from_slice(&bytes).ok()
+}
+
+fn write_phase2_snapshot_file(paths: &AppPaths, snapshot: &Phase2PreInstallSnapshot) -> Result<()> {
+    let path = phase2_pre_install_snapshot_path(paths);
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(snapshot)?;
+    std::fs::write(&path, format!("{json}\n"))?;
+    Ok(())
+}
+
+/// Records the venv package list before a phase-2 install so a failed run can be rolled back.
+/// A snapshot left by a run that failed (or never finished) and was not rolled back is kept, so
+/// it still describes the venv before that run.
+pub fn write_phase2_pre_install_snapshot(paths: &AppPaths) -> Result<Phase2PreInstallSnapshot> {
+    let previous = read_phase2_pre_install_snapshot(paths).filter(|snapshot| {
+        matches!(
+            snapshot.last_run_status.as_deref(),
+            Some(PHASE2_RUN_STATUS_RUNNING | PHASE2_RUN_STATUS_FAILED)
+        )
+    });
+    let snapshot = match previous {
+        Some(previous) => Phase2PreInstallSnapshot {
+            last_run_status: Some(PHASE2_RUN_STATUS_RUNNING.to_string()),
+            ..previous
+        },
+        None => Phase2PreInstallSnapshot {
+            captured_at_ms: now_ms(),
+            packages: pip_installed_packages(paths)?,
+            last_run_status: Some(PHASE2_RUN_STATUS_RUNNING.to_string()),
+        },
+    };
+    write_phase2_snapshot_file(paths, &snapshot)?;
+    Ok(snapshot)
+}
+
+/// Stores the outcome of the install run that took the current snapshot.
+pub fn set_phase2_last_run_status(paths: &AppPaths, status: &str) -> Result<()> {
+    let Some(mut snapshot) = read_phase2_pre_install_snapshot(paths) else {
+        return Ok(());
+    };
+    snapshot.last_run_status = Some(status.to_string());
+    write_phase2_snapshot_file(paths, &snapshot)
+}
+
+/// Packages present now that were not in the snapshot.
+fn phase2_rollback_delta(
+    snapshot: &BTreeMap<String, String>,
+    current: &BTreeMap<String, String>,
+) -> Vec<String> {
+    current
+        .keys()
+        .filter(|name| !snapshot.contains_key(*name))
+        .filter(|name| !PHASE2_ROLLBACK_PROTECTED_PACKAGES.contains(&name.as_str()))
+        .cloned()
+        .collect()
+}
+
+/// `name==version` pins for snapshot packages whose installed version changed or that went
+/// missing since the snapshot.
+fn phase2_rollback_pins(
+    snapshot: &BTreeMap<String, String>,
+    current: &BTreeMap<String, String>,
+) -> Vec<String> {
+    snapshot
+        .iter()
+        .filter(|(name, version)| current.get(*name) != Some(*version))
+        .filter(|(name, _)| !PHASE2_ROLLBACK_PROTECTED_PACKAGES.contains(&name.as_str()))
+        .map(|(name, version)| format!("{name}=={version}"))
+        .collect()
+}
+
+fn run_phase2_rollback_pip(
+    venv_python: &Path,
+    args: &[&str],
+    package: String,
+    action: &str,
+) -> Phase2RollbackStep {
+    let result = crate::cmd::command(venv_python)
+        .args(["-m", "pip"])
+        .args(args)
+        .arg("--disable-pip-version-check")
+        .arg(&package)
+        .env("PYTHONNOUSERSITE", "1")
+        .output();
+    let error = match result {
+        Ok(output) if output.status.success() => None,
+        Ok(output) => Some(format!(
+            "pip {action} failed (code={:?}): {}",
+            output.status.code(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        )),
+        Err(err) => Some(format!("failed to run pip {action}: {err}")),
+    };
+    Phase2RollbackStep {
+        package,
+        action: action.to_string(),
+        ok: error.is_none(),
+        error,
+    }
+}
+
+/// Uninstalls every package added since the pre-install snapshot and reinstalls the snapshot's
+/// version of every package the install changed. Refuses when the last install succeeded or the
+/// snapshot is empty (there was no venv to return to). Individual pip failures are reported in
+/// the summary rather than aborting the rollback.
+pub fn rollback_phase2_packs(paths: &AppPaths) -> Result<Phase2RollbackSummary> {
+    let snapshot_path = phase2_pre_install_snapshot_path(paths);
+    let bytes = std::fs::read(&snapshot_path).map_err(|_| {
+        EngineError::InstallFailed(format!(
+            "no phase2 pre-install snapshot at {}",
+            snapshot_path.display()
+        ))
+    })?;
+    let snapshot: Phase2PreInstallSnapshot = serde_json::from_slice(&bytes)?;
+    if snapshot.last_run_status.as_deref() == Some(PHASE2_RUN_STATUS_SUCCEEDED) {
+        return Err(EngineError::InstallFailed(
+            "the last phase2 install succeeded; there is nothing to roll back".to_string(),
+        ));
+    }
+    if snapshot.packages.is_empty() {
+        return Err(EngineError::InstallFailed(
+            "the phase2 pre-install snapshot is empty (no venv before the install); refusing to remove every package".to_string(),
+        ));
+    }
+    let current = pip_installed_packages(paths)?;
+    let delta = phase2_rollback_delta(&snapshot.packages, &current);
+    let pins = phase2_rollback_pins(&snapshot.packages, &current);
+
+    let mut steps = Vec::with_capacity(delta.len() + pins.len());
+    if !delta.is_empty() || !pins.is_empty() {
+        let venv_python = python_venv_python_path(paths)?;
+        for package in delta {
+            steps.push(run_phase2_rollback_pip(
+                &venv_python,
+                &["uninstall", "-y"],
+                package,
+                "uninstall",
+            ));
+        }
+        for pin in pins {
+            steps.push(run_phase2_rollback_pip(
+                &venv_python,
+                &["install", "--no-deps"],
+                pin,
+                "reinstall",
+            ));
+        }
+    }
+    let _ = generate_pack_integrity_manifest(paths);
+    let _ = set_phase2_last_run_status(paths, PHASE2_RUN_STATUS_ROLLED_BACK);
+
+    Ok(Phase2RollbackSummary {
+        snapshot_path: snapshot_path.to_string_lossy().to_string(),
+        steps,
+    })
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct PackIntegrityManifestStatus {
     pub exists: bool,
@@ -692,6 +1268,7 @@ pub fn generate_pack_integrity_manifest(paths: &AppPaths) -> Result<PackIntegrit
         allow_unpinned_fallback_enabled: bool,
         packs: PackIntegrityPacks,
         model_manifests: PackIntegrityModelManifests,
+        pack_files: BTreeMap<String, BTreeMap<String, String>>,
     }
 
     let generated_at_ms = now_ms();
@@ -710,8 +1287,13 @@ pub fn generate_pack_integrity_manifest(paths: &AppPaths) -> Result<PackIntegrit
         .join("openvoice_v2")
         .join("voxvulgi_openvoicev2_manifest.json");
 
+    let mut pack_files = BTreeMap::new();
+    for (pack_id, _, _) in PACK_INTEGRITY_FILE_ROOTS {
+        pack_files.insert(pack_id.to_string(), hash_pack_files(paths, pack_id)?);
+    }
+
     let manifest = PackIntegrityManifest {
-        schema_version: 1,
+        schema_version: 2,
         generated_at_ms,
         portable_python: portable_python_status(paths),
         python_toolchain: python_toolchain_status(paths),
@@ -731,6 +1313,7 @@ pub fn generate_pack_integrity_manifest(paths: &AppPaths) -> Result<PackIntegrit
             spleeter_2stems: read_json_value_best_effort(&spleeter_manifest_path),
             openvoice_v2: read_json_value_best_effort(&openvoice_manifest_path),
         },
+        pack_files,
     };
 
     let json = serde_json::to_string_pretty(&manifest)?;
@@ -744,25 +1327,260 @@ pub fn generate_pack_integrity_manifest(paths: &AppPaths) -> Result<PackIntegrit
     })
 }
 
+/// `(pack id, models subdir, site-packages module)` trees hashed into the integrity manifest.
+const PACK_INTEGRITY_FILE_ROOTS: &[(&str, Option<&str>, &str)] = &[
+    ("spleeter", Some("spleeter"), "spleeter"),
+    ("demucs", Some("demucs"), "demucs_infer"),
+    ("diarization", None, "resemblyzer"),
+    ("tts_preview", None, "pyttsx3"),
+    ("tts_neural_local_v1", Some("kokoro"), "kokoro"),
+    (
+        "tts_voice_preserving_local_v1",
+        Some("openvoice_v2"),
+        "openvoice",
+    ),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PackIntegrityVerifyResult {
+    pub ok: bool,
+    pub missing_files: Vec<String>,
+    pub corrupted_files: Vec<String>,
+}
+
+/// Checks the files recorded for `pack_id` in the integrity manifest against their stored
+/// SHA-256. Paths in the manifest are relative to the app base dir.
+pub fn verify_pack_integrity(paths: &AppPaths, pack_id: &str) -> Result<PackIntegrityVerifyResult> {
+    let manifest_path = pack_integrity_manifest_path(paths);
+    let manifest = read_json_value_best_effort(&manifest_path).ok_or_else(|| {
+        EngineError::InstallFailed(format!(
+            "pack integrity manifest not found at {}",
+            manifest_path.display()
+        ))
+    })?;
+    let files = manifest
+        .get("pack_files")
+        .and_then(|v| v.get(pack_id))
+        .and_then(|v| v.as_object())
+        .ok_or_else(|| {
+            EngineError::InstallFailed(format!(
+                "pack integrity manifest has no file hashes for {pack_id}; regenerate it"
+            ))
+        })?;
+
+    let mut missing_files = Vec::new();
+    let mut corrupted_files = Vec::new();
+    for (rel, expected) in files {
+        let path = paths.base_dir.join(rel);
+        if !path.is_file() {
+            missing_files.push(rel.clone());
+            continue;
+        }
+        let expected = expected.as_str().unwrap_or_default();
+        match compute_sha256(&path) {
+            Ok(actual) if actual.eq_ignore_ascii_case(expected) => {}
+            _ => corrupted_files.push(rel.clone()),
+        }
+    }
+
+    Ok(PackIntegrityVerifyResult {
+        ok: missing_files.is_empty() && corrupted_files.is_empty(),
+        missing_files,
+        corrupted_files,
+    })
+}
+
+fn hash_pack_files(paths: &AppPaths, pack_id: &str) -> Result<BTreeMap<String, String>> {
+    let mut roots = Vec::new();
+    if let Some((_, models_subdir, module)) = PACK_INTEGRITY_FILE_ROOTS
+        .iter()
+        .find(|(id, _, _)| *id == pack_id)
+    {
+        if let Some(subdir) = models_subdir {
+            roots.push(paths.python_models_dir().join(subdir));
+        }
+        for site_packages in venv_site_packages_dirs(&paths.python_venv_dir()) {
+            roots.push(site_packages.join(module));
+        }
+    }
+
+    let mut hashes = BTreeMap::new();
+    let mut stack: Vec<PathBuf> = roots.into_iter().filter(|root| root.is_dir()).collect();
+    while let Some(dir) = stack.pop() {
+        for entry in std::fs::read_dir(&dir)?.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                // Bytecode caches are rewritten by the interpreter and would always mismatch.
+                if entry.file_name() != "__pycache__" {
+                    stack.push(path);
+                }
+                continue;
+            }
+            if !file_type.is_file() {
+                continue;
+            }
+            let rel = path
+                .strip_prefix(&paths.base_dir)
+                .unwrap_or(&path)
+                .to_string_lossy()
+                .replace('\\', "/");
+            hashes.insert(rel, compute_sha256(&path)?);
+        }
+    }
+    Ok(hashes)
+}
+
+fn venv_site_packages_dirs(venv_dir: &Path) -> Vec<PathBuf> {
+    if cfg!(windows) {
+        return vec![venv_dir.join("Lib").join("site-packages")];
+    }
+    let Ok(entries) = std::fs::read_dir(venv_dir.join("lib")) else {
+        return Vec::new();
+    };
+    entries
+        .flatten()
+        .filter(|entry| entry.file_name().to_string_lossy().starts_with("python"))
+        .map(|entry| entry.path().join("site-packages"))
+        .collect()
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct OfflineBundleManifestV2Result {
+    pub out_path: String,
+    pub bundle_id: String,
+    pub file_count: usize,
+    pub total_bytes: u64,
+}
+
+/// Lowercase hex SHA-256 of a file's contents.
+pub fn compute_sha256(path: &Path) -> Result<String> {
+    Ok(hex::encode(sha256_file(path)?))
+}
+
+/// Writes a schema v2 offline bundle manifest with per-file checksums for the staged
+/// `tools/`, `models/`, and `cache/huggingface/` trees of `paths`. Fields of an
+/// existing `bundle_root/manifest.json` (bundle id, payload zip metadata) are carried over.
+pub fn tools_generate_offline_bundle_manifest_v2(
+    paths: &AppPaths,
+    bundle_root: &Path,
+    out_path: &Path,
+) -> Result<OfflineBundleManifestV2Result> {
+    let mut manifest = match read_json_value_best_effort(&bundle_root.join("manifest.json")) {
+        Some(serde_json::Value::Object(map)) => map,
+        _ => serde_json::Map::new(),
+    };
+
+    let bundle_id = manifest
+        .get("bundle_id")
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(ToString::to_string)
+        .unwrap_or_else(|| format!("offline_bundle_{}", now_ms()));
+
+    let mut checksums: std::collections::BTreeMap<String, String> = Default::default();
+    let mut total_bytes = 0_u64;
+    let payload_roots = [
+        ("tools", paths.tools_dir()),
+        ("models", paths.models_dir()),
+        ("cache/huggingface", paths.cache_dir().join("huggingface")),
+    ];
+    for (root, src_root) in payload_roots {
+        if !src_root.is_dir() {
+            continue;
+        }
+        let mut stack: Vec<PathBuf> = vec![src_root.clone()];
+        while let Some(dir) = stack.pop() {
+            for entry in std::fs::read_dir(&dir)?.flatten() {
+                let path = entry.path();
+                let Ok(file_type) = entry.file_type() else {
+                    continue;
+                };
+                if file_type.is_dir() {
+                    stack.push(path);
+                    continue;
+                }
+                if !file_type.is_file() {
+                    continue;
+                }
+                let Ok(rel) = path.strip_prefix(&src_root) else {
+                    continue;
+                };
+                let rel = rel.to_string_lossy().replace('\\', "/");
+                total_bytes += entry.metadata().map(|m| m.len()).unwrap_or(0);
+                checksums.insert(format!("{root}/{rel}"), compute_sha256(&path)?);
+            }
+        }
+    }
+
+    let file_count = checksums.len();
+    manifest.insert("schema_version".to_string(), serde_json::json!(2));
+    manifest.insert("bundle_id".to_string(), serde_json::json!(bundle_id));
+    manifest
+        .entry("created_at_ms".to_string())
+        .or_insert_with(|| serde_json::json!(now_ms()));
+    manifest.insert(
+        "checksums_algorithm".to_string(),
+        serde_json::json!("sha256"),
+    );
+    manifest.insert("checksums".to_string(), serde_json::json!(checksums));
+
+    if let Some(parent) = out_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string_pretty(&serde_json::Value::Object(manifest))?;
+    crate::persistence::atomic_write_text(out_path, &format!("{json}\n"))?;
+
+    Ok(OfflineBundleManifestV2Result {
+        out_path: out_path.to_string_lossy().to_string(),
+        bundle_id,
+        file_count,
+        total_bytes,
+    })
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct PerformanceTierStatus {
     pub tier: String,
     pub gpu_names: Vec<String>,
+    /// First detected GPU (NVIDIA first, then the macOS Metal device).
+    pub gpu_name: Option<String>,
+    pub gpu_vram_mb: Option<u64>,
     pub torch_cuda_available: Option<bool>,
+    /// True when `nvidia-smi` reports a GPU or torch in the managed venv sees CUDA.
+    pub cuda_available: bool,
+    pub metal_available: bool,
+    pub cpu_cores: usize,
+    pub recommended_max_concurrency: usize,
     pub recommended_separation_backend: String,
     pub recommended_diarization_backend: String,
     pub recommended_tts_vc_device: String,
 }
 
 pub fn performance_tier_status(paths: &AppPaths) -> PerformanceTierStatus {
-    let gpu_names = detect_gpu_names_best_effort();
+    let nvidia_gpus = detect_nvidia_gpus_best_effort();
+    let gpu_names: Vec<String> = nvidia_gpus.iter().map(|(name, _)| name.clone()).collect();
     let torch_cuda_available = detect_torch_cuda_best_effort(paths);
+    let (metal_available, metal_gpu_name) = detect_metal_best_effort();
+    let cuda_available = torch_cuda_available.unwrap_or(false) || !nvidia_gpus.is_empty();
+    let cpu_cores = std::thread::available_parallelism()
+        .map(|n| n.get())
+        .unwrap_or(1);
 
-    let tier = if torch_cuda_available.unwrap_or(false) || !gpu_names.is_empty() {
+    let tier = if cuda_available {
         "gpu".to_string()
     } else {
         "cpu".to_string()
     };
+    let gpu_name = nvidia_gpus
+        .first()
+        .map(|(name, _)| name.clone())
+        .or(metal_gpu_name);
+    let gpu_vram_mb = nvidia_gpus.first().and_then(|(_, vram)| *vram);
+    let recommended_max_concurrency = recommended_max_concurrency(&tier, cpu_cores, gpu_vram_mb);
 
     // Defaults remain CPU-safe and deterministic.
     let recommended_separation_backend = if tier == "gpu" {
@@ -781,33 +1599,106 @@ pub fn performance_tier_status(paths: &AppPaths) -> PerformanceTierStatus {
     PerformanceTierStatus {
         tier,
         gpu_names,
+        gpu_name,
+        gpu_vram_mb,
         torch_cuda_available,
+        cuda_available,
+        metal_available,
+        cpu_cores,
+        recommended_max_concurrency,
         recommended_separation_backend,
         recommended_diarization_backend,
         recommended_tts_vc_device,
     }
 }
 
-fn detect_gpu_names_best_effort() -> Vec<String> {
-    // Best-effort, cross-platform-ish detection.
-    let mut out: Vec<String> = Vec::new();
+/// CPU-tier machines get one job per four cores; GPU-tier machines are bounded by VRAM since
+/// model jobs share the card.
+fn recommended_max_concurrency(tier: &str, cpu_cores: usize, gpu_vram_mb: Option<u64>) -> usize {
+    let cpu_bound = (cpu_cores / 4).clamp(1, 4);
+    if tier != "gpu" {
+        return cpu_bound;
+    }
+    let gpu_bound = match gpu_vram_mb {
+        Some(vram) if vram >= 16_000 => 4,
+        Some(vram) if vram >= 8_000 => 3,
+        _ => 2,
+    };
+    gpu_bound.min(cpu_cores.max(1))
+}
 
-    if let Ok(output) = crate::cmd::command("nvidia-smi")
-        .args(["--query-gpu=name", "--format=csv,noheader"])
+/// NVIDIA GPUs with their total memory in MiB, as reported by `nvidia-smi`.
+fn detect_nvidia_gpus_best_effort() -> Vec<(String, Option<u64>)> {
+    let Ok(output) = crate::cmd::command("nvidia-smi")
+        .args(["--query-gpu=name,memory.total", "--format=csv,noheader"])
         .output()
-    {
-        if output.status.success() {
-            let text = String::from_utf8_lossy(&output.stdout);
-            for line in text.lines() {
-                let name = line.trim();
-                if !name.is_empty() {
-                    out.push(name.to_string());
-                }
+    else {
+        return Vec::new();
+    };
+    if !output.status.success() {
+        return Vec::new();
+    }
+    parse_nvidia_smi_gpus(&String::from_utf8_lossy(&output.stdout))
+}
+
+fn parse_nvidia_smi_gpus(text: &str) -> Vec<(String, Option<u64>)> {
+    text.lines()
+        .filter_map(|line| {
+            let (name, memory) = match line.rsplit_once(',') {
+                Some((name, memory)) => (name.trim(), Some(memory.trim())),
+                None => (line.trim(), None),
+            };
+            if name.is_empty() {
+                return None;
             }
-        }
+            let vram_mb = memory
+                .and_then(|m| m.split_whitespace().next())
+                .and_then(|m| m.parse::<u64>().ok());
+            Some((name.to_string(), vram_mb))
+        })
+        .collect()
+}
+
+/// Metal support and GPU model on macOS via `system_profiler`; `(false, None)` elsewhere.
+fn detect_metal_best_effort() -> (bool, Option<String>) {
+    if !cfg!(target_os = "macos") {
+        return (false, None);
     }
+    let Ok(output) = crate::cmd::command("system_profiler")
+        .args(["SPDisplaysDataType", "-json"])
+        .output()
+    else {
+        return (false, None);
+    };
+    if !output.status.success() {
+        return (false, None);
+    }
+    serde_json::from_slice::<serde_json::Value>(&output.stdout)
+        .map(|v| parse_metal_displays(&v))
+        .unwrap_or((false, None))
+}
 
-    out
+fn parse_metal_displays(value: &serde_json::Value) -> (bool, Option<String>) {
+    let Some(displays) = value.get("SPDisplaysDataType").and_then(|v| v.as_array()) else {
+        return (false, None);
+    };
+    for display in displays {
+        let Some(obj) = display.as_object() else {
+            continue;
+        };
+        let metal = obj.iter().any(|(key, value)| {
+            (key.starts_with("spdisplays_mtlgpufamilysupport") || key == "spdisplays_metal")
+                && value.as_str().is_some_and(|v| !v.is_empty())
+        });
+        if metal {
+            let name = obj
+                .get("sppci_model")
+                .and_then(|v| v.as_str())
+                .map(ToString::to_string);
+            return (true, name);
+        }
+    }
+    (false, None)
 }
 
 fn detect_torch_cuda_best_effort(paths: &AppPaths) -> Option<bool> {
@@ -934,17 +1825,44 @@ pub fn install_portable_python(paths: &AppPaths) -> Result<PortablePythonStatus>
     }
 }
 
-pub fn install_python_toolchain(paths: &AppPaths) -> Result<PythonToolchainStatus> {
+/// Creates the app venv. With `use_system_python` the venv is based on [`detect_system_python`]
+/// instead of the config override or portable Python. An existing venv is kept, but asking for a
+/// system Python venv when the existing one was built from another interpreter is an error.
+pub fn install_python_toolchain(
+    paths: &AppPaths,
+    use_system_python: bool,
+) -> Result<PythonToolchainStatus> {
     paths.ensure_dirs()?;
 
-    let resolved = resolve_base_python(paths).ok_or_else(|| {
-        EngineError::InstallFailed(
-            "Python was not found. Install Python 3 and ensure it is on PATH, install the optional portable Python in Diagnostics, or set a Python override in app config (config/python_exe.txt)."
-                .to_string(),
-        )
-    })?;
-
     let venv_dir = paths.python_venv_dir();
+    if use_system_python && venv_dir.exists() && !venv_uses_system_python(paths) {
+        return Err(EngineError::InstallFailed(format!(
+            "the app venv at {} was not created from the system Python; remove it to rebuild it from the system Python",
+            venv_dir.display()
+        )));
+    }
+
+    let resolved = if use_system_python {
+        let system = detect_system_python().ok_or_else(|| {
+            EngineError::InstallFailed(format!(
+                "no Python {}.{}+ was found on PATH",
+                SYSTEM_PYTHON_MIN_VERSION.0, SYSTEM_PYTHON_MIN_VERSION.1
+            ))
+        })?;
+        ResolvedPython {
+            program: std::path::PathBuf::from(system.path),
+            args: Vec::new(),
+            version: system.version,
+        }
+    } else {
+        resolve_base_python(paths).ok_or_else(|| {
+            EngineError::InstallFailed(
+                "Python was not found. Install Python 3 and ensure it is on PATH, install the optional portable Python in Diagnostics, or set a Python override in app config (config/python_exe.txt)."
+                    .to_string(),
+            )
+        })?
+    };
+
     if !venv_dir.exists() {
         if let Some(parent) = venv_dir.parent() {
             std::fs::create_dir_all(parent)?;
@@ -967,6 +1885,12 @@ pub fn install_python_toolchain(paths: &AppPaths) -> Result<PythonToolchainStatu
                 stderr.trim()
             )));
         }
+        if use_system_python {
+            std::fs::write(
+                venv_dir.join(SYSTEM_PYTHON_VENV_MARKER),
+                format!("{}\n", resolved.program.to_string_lossy()),
+            )?;
+        }
     }
 
     let venv_python = venv_python_path(&venv_dir);
@@ -992,6 +1916,59 @@ pub fn python_venv_python_path(paths: &AppPaths) -> Result<std::path::PathBuf> {
     Ok(venv_python)
 }
 
+/// True when the app venv was created from a system Python by [`install_python_toolchain`].
+pub fn venv_uses_system_python(paths: &AppPaths) -> bool {
+    paths
+        .python_venv_dir()
+        .join(SYSTEM_PYTHON_VENV_MARKER)
+        .is_file()
+}
+
+/// Looks for `python3`, then `python`, on PATH and returns the first one that is at least
+/// [`SYSTEM_PYTHON_MIN_VERSION`].
+pub fn detect_system_python() -> Option<SystemPythonInfo> {
+    ["python3", "python"].into_iter().find_map(|program| {
+        let version = python_version(std::path::Path::new(program), &[])?;
+        if parse_python_version(&version)? < SYSTEM_PYTHON_MIN_VERSION {
+            return None;
+        }
+        let probe = crate::cmd::command(program)
+            .args([
+                "-c",
+                "import sys; print(sys.executable); print(int(sys.prefix != sys.base_prefix))",
+            ])
+            .output()
+            .ok()
+            .filter(|o| o.status.success())
+            .map(|o| String::from_utf8_lossy(&o.stdout).to_string())
+            .unwrap_or_default();
+        let mut lines = probe.lines().map(str::trim);
+        let path = lines
+            .next()
+            .filter(|v| !v.is_empty())
+            .unwrap_or(program)
+            .to_string();
+        let is_venv = lines.next() == Some("1");
+        Some(SystemPythonInfo {
+            path,
+            version,
+            is_venv,
+        })
+    })
+}
+
+/// Parses `major.minor` out of `python --version` output such as `Python 3.11.4`.
+fn parse_python_version(text: &str) -> Option<(u32, u32)> {
+    let mut parts = text.trim().strip_prefix("Python")?.trim().split('.');
+    let major = parts.next()?.parse().ok()?;
+    let minor: String = parts
+        .next()?
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    Some((major, minor.parse().ok()?))
+}
+
 #[derive(Debug, Clone)]
 struct ResolvedPython {
     program: std::path::PathBuf,
@@ -1231,7 +2208,7 @@ pub fn spleeter_pack_status(paths: &AppPaths) -> SpleeterPackStatus {
 
 pub fn install_spleeter_pack(paths: &AppPaths) -> Result<SpleeterPackStatus> {
     // Ensure venv exists first.
-    let _ = install_python_toolchain(paths)?;
+    let _ = install_python_toolchain(paths, false)?;
     let venv_python = python_venv_python_path(paths)?;
     let py_version = python_version(&venv_python, &[]).unwrap_or_else(|| "unknown".to_string());
     let candidates = spleeter_install_candidates(&py_version);
@@ -1897,7 +2874,7 @@ pub fn demucs_pack_status(paths: &AppPaths) -> DemucsPackStatus {
 
 pub fn install_demucs_pack(paths: &AppPaths) -> Result<DemucsPackStatus> {
     // Ensure venv exists first.
-    let _ = install_python_toolchain(paths)?;
+    let _ = install_python_toolchain(paths, false)?;
     let venv_python = python_venv_python_path(paths)?;
     let pin = &pinned_dependency_manifest::manifest().demucs;
 
@@ -1961,6 +2938,54 @@ pub fn install_demucs_pack(paths: &AppPaths) -> Result<DemucsPackStatus> {
     Ok(status)
 }
 
+/// Optional vocals cleanup pack; without it `clean_vocals_v1` falls back to ffmpeg filters.
+#[derive(Debug, Clone, Serialize)]
+pub struct CleanupPackStatus {
+    pub installed: bool,
+    pub noisereduce_version: Option<String>,
+}
+
+pub fn cleanup_pack_status(paths: &AppPaths) -> CleanupPackStatus {
+    let venv_dir = paths.python_venv_dir();
+    let venv_python = venv_python_path(&venv_dir);
+    if !venv_python.exists() {
+        return CleanupPackStatus {
+            installed: false,
+            noisereduce_version: None,
+        };
+    }
+
+    let noisereduce_version = python_module_version(&venv_python, "noisereduce");
+    CleanupPackStatus {
+        installed: noisereduce_version.is_some(),
+        noisereduce_version,
+    }
+}
+
+/// Forced-alignment pack used by `sync_subtitles_v1`.
+#[derive(Debug, Clone, Serialize)]
+pub struct SyncPackStatus {
+    pub installed: bool,
+    pub aeneas_version: Option<String>,
+}
+
+pub fn sync_pack_status(paths: &AppPaths) -> SyncPackStatus {
+    let venv_dir = paths.python_venv_dir();
+    let venv_python = venv_python_path(&venv_dir);
+    if !venv_python.exists() {
+        return SyncPackStatus {
+            installed: false,
+            aeneas_version: None,
+        };
+    }
+
+    let aeneas_version = python_module_version(&venv_python, "aeneas");
+    SyncPackStatus {
+        installed: aeneas_version.is_some(),
+        aeneas_version,
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct DiarizationPackStatus {
     pub installed: bool,
@@ -2099,7 +3124,7 @@ pub fn diarization_pack_status(paths: &AppPaths) -> DiarizationPackStatus {
 
 pub fn install_diarization_pack(paths: &AppPaths) -> Result<DiarizationPackStatus> {
     // Ensure venv exists first.
-    let _ = install_python_toolchain(paths)?;
+    let _ = install_python_toolchain(paths, false)?;
     let venv_python = python_venv_python_path(paths)?;
     let pin = &pinned_dependency_manifest::manifest().diarization;
 
@@ -2183,7 +3208,7 @@ pub fn tts_preview_pack_status(paths: &AppPaths) -> TtsPreviewPackStatus {
 
 pub fn install_tts_preview_pack(paths: &AppPaths) -> Result<TtsPreviewPackStatus> {
     // Ensure venv exists first.
-    let _ = install_python_toolchain(paths)?;
+    let _ = install_python_toolchain(paths, false)?;
     let venv_python = python_venv_python_path(paths)?;
     let pin = &pinned_dependency_manifest::manifest().tts_preview;
 
@@ -2246,7 +3271,7 @@ pub fn tts_neural_local_v1_pack_status(paths: &AppPaths) -> TtsNeuralLocalV1Pack
 
 pub fn install_tts_neural_local_v1_pack(paths: &AppPaths) -> Result<TtsNeuralLocalV1PackStatus> {
     // Ensure venv exists first.
-    let _ = install_python_toolchain(paths)?;
+    let _ = install_python_toolchain(paths, false)?;
     let venv_python = python_venv_python_path(paths)?;
     let pin = &pinned_dependency_manifest::manifest().tts_neural_local_v1;
 
@@ -2323,6 +3348,79 @@ pub fn install_tts_neural_local_v1_pack(paths: &AppPaths) -> Result<TtsNeuralLoc
     Ok(status)
 }
 
+/// Model folders recognised under `user_models_dir`, with the file that marks each one.
+const USER_MODEL_PATTERNS: [(&str, &str); 2] = [
+    ("openvoice_v2", "openvoice_v2/converter/config.json"),
+    ("kokoro", "kokoro/config.json"),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UserModelEntry {
+    pub name: String,
+    pub path: String,
+    pub valid: bool,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct UserModelsScanResult {
+    pub user_models_dir: String,
+    pub found_models: Vec<UserModelEntry>,
+}
+
+/// Lists known model folders under `user_models_dir`. A folder is valid when its marker config
+/// parses as JSON (and, for OpenVoice, the converter checkpoint is present).
+pub fn scan_user_models(paths: &AppPaths) -> UserModelsScanResult {
+    let root = paths.user_models_dir();
+    let found_models = USER_MODEL_PATTERNS
+        .iter()
+        .filter_map(|(name, marker)| {
+            let dir = root.join(name);
+            if !dir.is_dir() {
+                return None;
+            }
+            Some(UserModelEntry {
+                name: name.to_string(),
+                path: dir.to_string_lossy().to_string(),
+                valid: user_model_is_valid(name, &root.join(marker)),
+            })
+        })
+        .collect();
+    UserModelsScanResult {
+        user_models_dir: root.to_string_lossy().to_string(),
+        found_models,
+    }
+}
+
+fn user_model_is_valid(name: &str, marker: &Path) -> bool {
+    let config_ok = std::fs::read(marker)
+        .ok()
+        .and_then(|bytes| serde_json::from_slice::<serde_json::Value>(&bytes).ok())
+        .is_some_and(|value| value.is_object());
+    match name {
+        "openvoice_v2" => {
+            config_ok
+                && marker
+                    .parent()
+                    .is_some_and(|dir| dir.join("checkpoint.pth").is_file())
+        }
+        _ => config_ok,
+    }
+}
+
+/// OpenVoice models dir for the voice-preserving TTS script: a valid user-provided copy wins over
+/// the pack-installed one.
+pub fn openvoice_models_dir(paths: &AppPaths) -> PathBuf {
+    let user_dir = paths.user_models_dir().join("openvoice_v2");
+    if user_model_is_valid(
+        "openvoice_v2",
+        &user_dir.join("converter").join("config.json"),
+    ) {
+        user_dir
+    } else {
+        paths.python_models_dir().join("openvoice_v2")
+    }
+}
+
 #[derive(Debug, Clone, Serialize)]
 pub struct TtsVoicePreservingLocalV1PackStatus {
     pub installed: bool,
@@ -2337,11 +3435,7 @@ pub struct TtsVoicePreservingLocalV1PackStatus {
 pub fn tts_voice_preserving_local_v1_pack_status(
     paths: &AppPaths,
 ) -> TtsVoicePreservingLocalV1PackStatus {
-    let openvoice_models_dir = paths
-        .python_models_dir()
-        .join("openvoice_v2")
-        .to_string_lossy()
-        .to_string();
+    let openvoice_models_dir = openvoice_models_dir(paths).to_string_lossy().to_string();
 
     let venv_dir = paths.python_venv_dir();
     let venv_python = venv_python_path(&venv_dir);
@@ -2385,7 +3479,7 @@ pub fn tts_voice_preserving_local_v1_pack_status(
 pub fn install_tts_voice_preserving_local_v1_pack(
     paths: &AppPaths,
 ) -> Result<TtsVoicePreservingLocalV1PackStatus> {
-    let _ = install_python_toolchain(paths)?;
+    let _ = install_python_toolchain(paths, false)?;
     let venv_python = python_venv_python_path(paths)?;
     let pin = &pinned_dependency_manifest::manifest().tts_voice_preserving_local_v1;
 
@@ -2577,13 +3671,73 @@ print("openvoice_converter_warmup_ok")
     Ok(status)
 }
 
+#[derive(Debug, Clone, Serialize)]
+pub struct AllToolsStatus {
+    pub ffmpeg: FfmpegToolsStatus,
+    pub ytdlp: YtDlpToolsStatus,
+    pub python: PythonToolchainStatus,
+    pub portable_python: PortablePythonStatus,
+    pub spleeter: SpleeterPackStatus,
+    pub demucs: DemucsPackStatus,
+    pub diarization: DiarizationPackStatus,
+    pub tts_preview: TtsPreviewPackStatus,
+    pub tts_neural: TtsNeuralLocalV1PackStatus,
+    pub tts_voice_preserving: TtsVoicePreservingLocalV1PackStatus,
+}
+
+/// Collects every tool/pack status in one call so the UI does not need a round-trip per tool.
+pub fn all_tools_status(paths: &AppPaths) -> AllToolsStatus {
+    AllToolsStatus {
+        ffmpeg: ffmpeg_tools_status(paths),
+        ytdlp: ytdlp_tools_status(paths),
+        python: python_toolchain_status(paths),
+        portable_python: portable_python_status(paths),
+        spleeter: spleeter_pack_status(paths),
+        demucs: demucs_pack_status(paths),
+        diarization: diarization_pack_status(paths),
+        tts_preview: tts_preview_pack_status(paths),
+        tts_neural: tts_neural_local_v1_pack_status(paths),
+        tts_voice_preserving: tts_voice_preserving_local_v1_pack_status(paths),
+    }
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Pyttsx3Voice {
     pub id: String,
     pub name: String,
+    /// First language the driver reports, e.g. `en-gb`.
+    #[serde(default)]
+    pub lang: Option<String>,
+    #[serde(default)]
+    pub age: Option<String>,
+    /// Lowercased, e.g. `male` or `female`.
+    #[serde(default)]
+    pub gender: Option<String>,
+    /// Base64 WAV of a short sample phrase, when requested.
+    #[serde(default)]
+    pub sample_text_wav: Option<String>,
+    #[serde(default, skip_serializing)]
+    sample_path: Option<String>,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct Pyttsx3VoiceQuery {
+    /// Language prefix such as `en` or `en-US`; voices without a reported language are dropped.
+    #[serde(default)]
+    pub language_filter: Option<String>,
+    #[serde(default)]
+    pub gender_filter: Option<String>,
+    #[serde(default)]
+    pub include_sample: bool,
 }
 
-pub fn tts_preview_pyttsx3_list_voices(paths: &AppPaths) -> Result<Vec<Pyttsx3Voice>> {
+const PYTTSX3_SAMPLE_TEXT: &str = "Hello, this is a voice sample";
+const PYTTSX3_SAMPLE_MAX_MS: u64 = 5_000;
+
+pub fn tts_preview_pyttsx3_list_voices(
+    paths: &AppPaths,
+    query: &Pyttsx3VoiceQuery,
+) -> Result<Vec<Pyttsx3Voice>> {
     let pack = tts_preview_pack_status(paths);
     if !pack.installed {
         return Err(EngineError::InstallFailed(
@@ -2599,11 +3753,55 @@ pub fn tts_preview_pyttsx3_list_voices(paths: &AppPaths) -> Result<Vec<Pyttsx3Vo
         )
     })?;
 
+    let sample_dir = paths
+        .cache_dir()
+        .join("python")
+        .join("pyttsx3_samples")
+        .join(uuid::Uuid::new_v4().to_string());
+    let normalize = |value: &Option<String>| {
+        value
+            .as_deref()
+            .map(str::trim)
+            .filter(|value| !value.is_empty())
+            .map(str::to_string)
+    };
+    let args = serde_json::json!({
+        "language": normalize(&query.language_filter),
+        "gender": normalize(&query.gender_filter),
+        "sample_dir": if query.include_sample {
+            std::fs::create_dir_all(&sample_dir)?;
+            Some(sample_dir.to_string_lossy().to_string())
+        } else {
+            None
+        },
+        "sample_text": PYTTSX3_SAMPLE_TEXT,
+    });
+
     // Emit a single JSON line so we can parse the final non-empty stdout line robustly.
     let code = r#"
 import json
+import os
+import sys
 import pyttsx3
 
+args = json.loads(sys.argv[1])
+
+def norm_lang(value):
+    if isinstance(value, bytes):
+        value = value.decode("utf-8", errors="ignore")
+    value = str(value or "").strip().lstrip("\x00\x01\x02\x03\x04\x05\x06\x07\x08")
+    return value.replace("_", "-").lower() or None
+
+def norm_gender(value):
+    value = str(value or "").strip().lower()
+    if value.startswith("voicegender"):
+        value = value[len("voicegender"):]
+    return value or None
+
+language = norm_lang(args.get("language"))
+gender = norm_gender(args.get("gender"))
+sample_dir = args.get("sample_dir")
+
 engine = pyttsx3.init()
 voices = []
 for v in (engine.getProperty("voices") or []):
@@ -2613,13 +3811,40 @@ for v in (engine.getProperty("voices") or []):
     if not vid:
         continue
     name = (str(name).strip() if name else vid)
-    voices.append({"id": vid, "name": name})
+    langs = [norm_lang(l) for l in (getattr(v, "languages", None) or [])]
+    langs = [l for l in langs if l]
+    v_gender = norm_gender(getattr(v, "gender", None))
+    age = getattr(v, "age", None)
+    if language and not any(l == language or l.startswith(language + "-") for l in langs):
+        continue
+    if gender and v_gender != gender:
+        continue
+    voices.append({
+        "id": vid,
+        "name": name,
+        "lang": langs[0] if langs else None,
+        "age": str(age) if age not in (None, "") else None,
+        "gender": v_gender,
+    })
+
+if sample_dir:
+    for index, voice in enumerate(voices):
+        path = os.path.join(sample_dir, "voice_%04d.wav" % index)
+        try:
+            engine.setProperty("voice", voice["id"])
+            engine.save_to_file(args.get("sample_text") or "", path)
+            engine.runAndWait()
+        except Exception:
+            continue
+        if os.path.exists(path):
+            voice["sample_path"] = path
 
 print(json.dumps(voices, ensure_ascii=False))
 "#;
 
     let mut cmd = crate::cmd::command(&venv_python);
     cmd.args(["-c", code]);
+    cmd.arg(args.to_string());
     cmd.env("PYTHONNOUSERSITE", "1");
     cmd.env(
         "XDG_CACHE_HOME",
@@ -2630,9 +3855,29 @@ print(json.dumps(voices, ensure_ascii=False))
             .to_string(),
     );
 
-    let output = cmd
-        .output()
-        .map_err(|e| EngineError::InstallFailed(format!("failed to list pyttsx3 voices: {e}")))?;
+    let output = cmd.output();
+    let voices = output
+        .map_err(|e| EngineError::InstallFailed(format!("failed to list pyttsx3 voices: {e}")))
+        .and_then(|output| parse_pyttsx3_voices_output(&output));
+    let voices = voices.map(|voices| {
+        voices
+            .into_iter()
+            .map(|mut voice| {
+                if let Some(sample_path) = voice.sample_path.take() {
+                    voice.sample_text_wav =
+                        wav_sample_base64(Path::new(&sample_path), PYTTSX3_SAMPLE_MAX_MS).ok();
+                }
+                voice
+            })
+            .collect()
+    });
+    if query.include_sample {
+        let _ = std::fs::remove_dir_all(&sample_dir);
+    }
+    voices
+}
+
+fn parse_pyttsx3_voices_output(output: &std::process::Output) -> Result<Vec<Pyttsx3Voice>> {
     if !output.status.success() {
         let stderr = String::from_utf8_lossy(&output.stderr);
         return Err(EngineError::InstallFailed(format!(
@@ -2659,6 +3904,94 @@ print(json.dumps(voices, ensure_ascii=False))
     Ok(voices)
 }
 
+/// Re-encodes at most `max_ms` of a WAV file as 16-bit PCM and returns it base64-encoded.
+fn wav_sample_base64(path: &Path, max_ms: u64) -> Result<String> {
+    use base64::Engine as _;
+
+    let mut reader = hound::WavReader::open(path)
+        .map_err(|e| EngineError::InstallFailed(format!("failed to read sample wav: {e}")))?;
+    let spec = reader.spec();
+    let max_samples =
+        (u64::from(spec.sample_rate) * max_ms / 1000) as usize * usize::from(spec.channels.max(1));
+    let samples: Vec<i16> = match spec.sample_format {
+        hound::SampleFormat::Float => reader
+            .samples::<f32>()
+            .take(max_samples)
+            .map(|sample| sample.map(|v| (v.clamp(-1.0, 1.0) * f32::from(i16::MAX)) as i16))
+            .collect::<std::result::Result<_, _>>(),
+        hound::SampleFormat::Int => {
+            let shift = i32::from(spec.bits_per_sample).saturating_sub(16).max(0) as u32;
+            reader
+                .samples::<i32>()
+                .take(max_samples)
+                .map(|sample| sample.map(|v| (v >> shift) as i16))
+                .collect::<std::result::Result<_, _>>()
+        }
+    }
+    .map_err(|e| EngineError::InstallFailed(format!("failed to read sample wav: {e}")))?;
+
+    let out_spec = hound::WavSpec {
+        channels: spec.channels,
+        sample_rate: spec.sample_rate,
+        bits_per_sample: 16,
+        sample_format: hound::SampleFormat::Int,
+    };
+    let mut cursor = std::io::Cursor::new(Vec::new());
+    {
+        let mut writer = hound::WavWriter::new(&mut cursor, out_spec)
+            .map_err(|e| EngineError::InstallFailed(format!("failed to encode sample wav: {e}")))?;
+        for sample in samples {
+            writer.write_sample(sample).map_err(|e| {
+                EngineError::InstallFailed(format!("failed to encode sample wav: {e}"))
+            })?;
+        }
+        writer
+            .finalize()
+            .map_err(|e| EngineError::InstallFailed(format!("failed to encode sample wav: {e}")))?;
+    }
+    Ok(base64::engine::general_purpose::STANDARD.encode(cursor.into_inner()))
+}
+
+const SPLEETER_REQUIRED_PACKAGES: &[&str] = &["spleeter", "tensorflow", "numpy"];
+const DIARIZATION_REQUIRED_PACKAGES: &[&str] = &[
+    "Resemblyzer",
+    "numpy",
+    "scikit-learn",
+    "librosa",
+    "numba",
+    "llvmlite",
+    "webrtcvad",
+    "soundfile",
+];
+
+/// Reports the installed distribution version (or `None`) for each requested venv package.
+pub fn check_python_venv_packages(
+    paths: &AppPaths,
+    required: &[&str],
+) -> Result<HashMap<String, Option<String>>> {
+    let venv_python = python_venv_python_path(paths)?;
+    let mut out = HashMap::new();
+    for package in required {
+        let package = package.trim();
+        if package.is_empty() {
+            continue;
+        }
+        out.insert(
+            package.to_string(),
+            python_distribution_version(&venv_python, package),
+        );
+    }
+    Ok(out)
+}
+
+pub fn check_spleeter_packages(paths: &AppPaths) -> Result<HashMap<String, Option<String>>> {
+    check_python_venv_packages(paths, SPLEETER_REQUIRED_PACKAGES)
+}
+
+pub fn check_diarization_packages(paths: &AppPaths) -> Result<HashMap<String, Option<String>>> {
+    check_python_venv_packages(paths, DIARIZATION_REQUIRED_PACKAGES)
+}
+
 fn python_module_version(python: &std::path::Path, module: &str) -> Option<String> {
     let code = format!(
         "import importlib\nm=importlib.import_module({module:?})\nprint(getattr(m,'__version__', 'installed') or 'installed')\n"
@@ -2744,6 +4077,9 @@ fn run_python_checked(
             .to_string(),
     );
     cmd.env("HF_HUB_DISABLE_XET", "1");
+    if let Some(proxy) = crate::config::active_proxy_config(paths) {
+        proxy.apply_env(&mut cmd);
+    }
     cmd.env("HF_HUB_DOWNLOAD_TIMEOUT", "300");
     cmd.env("HF_HUB_ETAG_TIMEOUT", "30");
 
@@ -2791,6 +4127,29 @@ fn run_python_checked_with_retries(
 mod tests {
     use super::*;
 
+    #[test]
+    fn verify_pack_integrity_reports_missing_and_corrupted_files() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let models_dir = paths.python_models_dir().join("spleeter").join("2stems");
+        std::fs::create_dir_all(&models_dir).expect("models dir");
+        std::fs::write(models_dir.join("model.index"), "index").expect("index");
+        std::fs::write(models_dir.join("model.meta"), "meta").expect("meta");
+
+        generate_pack_integrity_manifest(&paths).expect("manifest");
+        let result = verify_pack_integrity(&paths, "spleeter").expect("verify");
+        assert!(result.ok);
+
+        std::fs::write(models_dir.join("model.index"), "tampered").expect("tamper");
+        std::fs::remove_file(models_dir.join("model.meta")).expect("remove");
+        let result = verify_pack_integrity(&paths, "spleeter").expect("verify");
+        assert!(!result.ok);
+        assert_eq!(result.corrupted_files.len(), 1);
+        assert!(result.corrupted_files[0].ends_with("2stems/model.index"));
+        assert_eq!(result.missing_files.len(), 1);
+        assert!(result.missing_files[0].ends_with("2stems/model.meta"));
+    }
+
     #[test]
     fn diarization_runtime_validation_exercises_runtime_dependency_chain() {
         let code = diarization_runtime_validation_code();
@@ -2810,4 +4169,275 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn ytdlp_release_install_selects_asset_per_platform_and_verifies_checksum() {
+        let binary = b"#!/bin/sh\necho 2026.03.17\n";
+        let digest = hex::encode(<sha2::Sha256 as sha2::Digest>::digest(binary));
+
+        for (os, arch, asset) in [
+            ("linux", "x86_64", "yt-dlp_linux"),
+            ("linux", "aarch64", "yt-dlp_linux_aarch64"),
+            ("macos", "x86_64", "yt-dlp_macos"),
+            ("macos", "aarch64", "yt-dlp_macos"),
+        ] {
+            let dir = tempfile::tempdir().expect("tempdir");
+            let paths = AppPaths::new(dir.path().to_path_buf());
+            let fetched = std::cell::RefCell::new(Vec::new());
+            let installed = install_ytdlp_release_asset(&paths, os, arch, |url, out| {
+                fetched.borrow_mut().push(url.to_string());
+                if url.ends_with(YT_DLP_RELEASE_CHECKSUMS_ASSET) {
+                    std::fs::write(out, format!("{digest}  {asset}\nffff  yt-dlp.exe\n"))?;
+                } else {
+                    std::fs::write(out, binary)?;
+                }
+                Ok(())
+            })
+            .expect("install");
+            assert_eq!(std::fs::read(&installed).expect("binary"), binary);
+            assert!(fetched.borrow()[1].ends_with(&format!("/{asset}")));
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mode = std::fs::metadata(&installed).unwrap().permissions().mode();
+                assert_eq!(mode & 0o777, 0o755);
+            }
+        }
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        let err = install_ytdlp_release_asset(&paths, "linux", "x86_64", |url, out| {
+            if url.ends_with(YT_DLP_RELEASE_CHECKSUMS_ASSET) {
+                std::fs::write(out, format!("{digest}  yt-dlp_linux\n"))?;
+            } else {
+                std::fs::write(out, b"tampered")?;
+            }
+            Ok(())
+        })
+        .expect_err("checksum mismatch");
+        assert!(matches!(err, EngineError::HashMismatch { .. }));
+        assert!(!bundled_ytdlp_path(&paths).exists());
+
+        assert!(install_ytdlp_release_asset(&paths, "freebsd", "x86_64", |_, _| Ok(())).is_err());
+    }
+
+    #[test]
+    fn ytdlp_version_is_newer_compares_dotted_dates() {
+        assert!(ytdlp_version_is_newer("2025.02.01", "2025.01.15"));
+        assert!(ytdlp_version_is_newer("2025.01.15.1", "2025.01.15"));
+        assert!(ytdlp_version_is_newer("2025.10.01", "2025.9.30"));
+        assert!(!ytdlp_version_is_newer("2025.01.15", "2025.01.15"));
+        assert!(!ytdlp_version_is_newer("2024.12.31", "2025.01.01"));
+    }
+
+    #[test]
+    fn scan_user_models_prefers_valid_user_openvoice_dir() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        paths.ensure_dirs().expect("dirs");
+        assert!(scan_user_models(&paths).found_models.is_empty());
+
+        let converter = paths
+            .user_models_dir()
+            .join("openvoice_v2")
+            .join("converter");
+        std::fs::create_dir_all(&converter).expect("converter dir");
+        std::fs::write(converter.join("config.json"), "{}").expect("config");
+        std::fs::create_dir_all(paths.user_models_dir().join("kokoro")).expect("kokoro dir");
+        let scan = scan_user_models(&paths);
+        assert_eq!(scan.found_models.len(), 2);
+        assert!(scan.found_models.iter().all(|m| !m.valid));
+        assert_eq!(
+            openvoice_models_dir(&paths),
+            paths.python_models_dir().join("openvoice_v2")
+        );
+
+        std::fs::write(converter.join("checkpoint.pth"), "ckpt").expect("checkpoint");
+        let scan = scan_user_models(&paths);
+        assert!(scan.found_models[0].valid);
+        assert_eq!(
+            openvoice_models_dir(&paths),
+            paths.user_models_dir().join("openvoice_v2")
+        );
+    }
+
+    #[test]
+    fn offline_bundle_manifest_v2_lists_payload_checksums() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().join("stage"));
+        let model_file = paths.models_dir().join("whispercpp").join("tiny.bin");
+        std::fs::create_dir_all(model_file.parent().unwrap()).unwrap();
+        std::fs::write(&model_file, b"abc").unwrap();
+        std::fs::create_dir_all(paths.tools_dir()).unwrap();
+        std::fs::write(paths.tools_dir().join("ffmpeg"), b"").unwrap();
+
+        let bundle_root = dir.path().join("bundle");
+        std::fs::create_dir_all(&bundle_root).unwrap();
+        std::fs::write(
+            bundle_root.join("manifest.json"),
+            r#"{"schema_version":1,"bundle_id":"b1","payload_zip":"payload.zip"}"#,
+        )
+        .unwrap();
+
+        let out_path = bundle_root.join("manifest.json");
+        let result =
+            tools_generate_offline_bundle_manifest_v2(&paths, &bundle_root, &out_path).unwrap();
+        assert_eq!(result.bundle_id, "b1");
+        assert_eq!(result.file_count, 2);
+        assert_eq!(result.total_bytes, 3);
+
+        let v: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&out_path).unwrap()).unwrap();
+        assert_eq!(v["schema_version"], 2);
+        assert_eq!(v["payload_zip"], "payload.zip");
+        assert_eq!(
+            v["checksums"]["models/whispercpp/tiny.bin"],
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_eq!(
+            compute_sha256(&model_file).unwrap(),
+            v["checksums"]["models/whispercpp/tiny.bin"]
+        );
+    }
+
+    #[test]
+    fn parse_version_line_extracts_version_token() {
+        assert_eq!(
+            parse_version_line(
+                "ffmpeg version 7.1-full_build-www.gyan.dev Copyright (c) 2000-2024"
+            ),
+            "7.1-full_build-www.gyan.dev"
+        );
+        assert_eq!(parse_version_line("2025.01.15"), "2025.01.15");
+    }
+
+    #[test]
+    fn parse_python_version_reads_major_minor() {
+        assert_eq!(parse_python_version("Python 3.11.4"), Some((3, 11)));
+        assert_eq!(parse_python_version("Python 3.13.0rc1"), Some((3, 13)));
+        assert_eq!(parse_python_version("Python 2.7.18"), Some((2, 7)));
+        assert!(parse_python_version("Python 3.8.10").unwrap() < SYSTEM_PYTHON_MIN_VERSION);
+        assert_eq!(parse_python_version("not python"), None);
+    }
+
+    #[test]
+    fn gpu_detection_parsers_and_concurrency_recommendation() {
+        assert_eq!(
+            parse_nvidia_smi_gpus("NVIDIA GeForce RTX 3080, 10240 MiB\n\n"),
+            vec![("NVIDIA GeForce RTX 3080".to_string(), Some(10240))]
+        );
+        let profiler = serde_json::json!({
+            "SPDisplaysDataType": [
+                { "sppci_model": "Apple M2", "spdisplays_mtlgpufamilysupport": "spdisplays_metal3" }
+            ]
+        });
+        assert_eq!(
+            parse_metal_displays(&profiler),
+            (true, Some("Apple M2".to_string()))
+        );
+
+        assert_eq!(recommended_max_concurrency("cpu", 2, None), 1);
+        assert_eq!(recommended_max_concurrency("cpu", 12, None), 3);
+        assert_eq!(recommended_max_concurrency("gpu", 16, Some(24_576)), 4);
+        assert_eq!(recommended_max_concurrency("gpu", 16, Some(6_144)), 2);
+    }
+
+    #[test]
+    fn phase2_rollback_delta_skips_existing_and_protected_packages() {
+        let snapshot: BTreeMap<String, String> = [("numpy", "1.26.4"), ("pip", "24.0")]
+            .into_iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        let current: BTreeMap<String, String> = [
+            ("numpy", "2.0.0"),
+            ("pip", "24.0"),
+            ("setuptools", "70.0"),
+            ("spleeter", "2.4.0"),
+        ]
+        .into_iter()
+        .map(|(k, v)| (k.to_string(), v.to_string()))
+        .collect();
+        assert_eq!(
+            phase2_rollback_delta(&snapshot, &current),
+            vec!["spleeter".to_string()]
+        );
+        assert_eq!(
+            phase2_rollback_pins(&snapshot, &current),
+            vec!["numpy==1.26.4".to_string()]
+        );
+        assert_eq!(
+            normalize_pip_package_name("Typing_Extensions"),
+            "typing-extensions"
+        );
+    }
+
+    #[test]
+    fn phase2_snapshot_survives_failed_runs_and_guards_rollback() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+
+        let snapshot = write_phase2_pre_install_snapshot(&paths).expect("snapshot");
+        assert!(snapshot.packages.is_empty());
+        assert!(rollback_phase2_packs(&paths).is_err());
+
+        let before = Phase2PreInstallSnapshot {
+            captured_at_ms: 1,
+            packages: [("numpy".to_string(), "1.26.4".to_string())]
+                .into_iter()
+                .collect(),
+            last_run_status: Some(PHASE2_RUN_STATUS_FAILED.to_string()),
+        };
+        write_phase2_snapshot_file(&paths, &before).expect("write");
+        let kept = write_phase2_pre_install_snapshot(&paths).expect("snapshot");
+        assert_eq!(kept.captured_at_ms, 1);
+        assert_eq!(kept.packages, before.packages);
+        assert_eq!(
+            kept.last_run_status.as_deref(),
+            Some(PHASE2_RUN_STATUS_RUNNING)
+        );
+
+        set_phase2_last_run_status(&paths, PHASE2_RUN_STATUS_SUCCEEDED).expect("status");
+        assert!(rollback_phase2_packs(&paths).is_err());
+        let fresh = write_phase2_pre_install_snapshot(&paths).expect("snapshot");
+        assert!(fresh.packages.is_empty());
+    }
+
+    #[test]
+    fn wav_sample_base64_truncates_to_max_duration() {
+        use base64::Engine as _;
+
+        let dir = tempfile::tempdir().expect("tempdir");
+        let path = dir.path().join("sample.wav");
+        let spec = hound::WavSpec {
+            channels: 1,
+            sample_rate: 8_000,
+            bits_per_sample: 16,
+            sample_format: hound::SampleFormat::Int,
+        };
+        let mut writer = hound::WavWriter::create(&path, spec).expect("create wav");
+        for i in 0..(8_000 * 8) {
+            writer.write_sample((i % 100) as i16).expect("write sample");
+        }
+        writer.finalize().expect("finalize wav");
+
+        let encoded = wav_sample_base64(&path, PYTTSX3_SAMPLE_MAX_MS).expect("encode sample");
+        let bytes = base64::engine::general_purpose::STANDARD
+            .decode(encoded)
+            .expect("decode base64");
+        let reader = hound::WavReader::new(std::io::Cursor::new(bytes)).expect("read wav");
+        assert_eq!(reader.spec().sample_rate, 8_000);
+        assert_eq!(reader.duration(), 8_000 * 5);
+    }
+
+    #[test]
+    fn system_python_install_refuses_to_reuse_a_non_system_venv() {
+        let dir = tempfile::tempdir().expect("tempdir");
+        let paths = AppPaths::new(dir.path().to_path_buf());
+        std::fs::create_dir_all(paths.python_venv_dir()).expect("venv dir");
+
+        let err = install_python_toolchain(&paths, true).expect_err("mismatched venv");
+        assert!(err
+            .to_string()
+            .contains("not created from the system Python"));
+    }
 }
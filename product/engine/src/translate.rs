@@ -6,6 +6,9 @@ use serde::Serialize;
 use std::collections::BTreeMap;
 use std::path::Path;
 
+/// Translated segments with a mean log probability below this count as low confidence.
+const LOW_CONFIDENCE_AVG_LOGPROB: f32 = -0.7;
+
 #[derive(Debug, Clone)]
 pub struct TranslateOptions {
     pub max_line_chars: usize,
@@ -43,6 +46,7 @@ pub struct TranslateReport {
     pub translated_raw_segment_count: usize,
     pub translated_usable_segment_count: usize,
     pub aligned_usable_segment_count: usize,
+    pub low_confidence_segments: usize,
     pub warnings: Vec<TranslateQcWarning>,
 }
 
@@ -91,7 +95,7 @@ pub fn translate_doc_whisper_to_en(
     let mut warnings: Vec<TranslateQcWarning> = Vec::new();
 
     for (i, src) in source_doc.segments.iter().enumerate() {
-        let mut text = aligned_texts.get(i).cloned().unwrap_or_default();
+        let (mut text, confidence) = aligned_texts.get(i).cloned().unwrap_or_default();
         text = apply_glossary(&text, &glossary_entries_sorted);
         let qc = qc_format_and_warn(i as u32, src.start_ms, src.end_ms, &text, &options);
         text = qc.text;
@@ -103,16 +107,37 @@ pub fn translate_doc_whisper_to_en(
             end_ms: src.end_ms,
             text,
             speaker: src.speaker.clone(),
+            confidence,
         });
     }
 
-    let doc = SubtitleDocument {
+    let mut doc = SubtitleDocument {
         schema_version: SUBTITLE_JSON_SCHEMA_VERSION,
         kind: "translated".to_string(),
         lang: "en".to_string(),
         segments: out_segments,
+        metadata: Default::default(),
     };
+    let word_count: usize = doc
+        .segments
+        .iter()
+        .map(|seg| seg.text.split_whitespace().count())
+        .sum();
+    doc.metadata
+        .insert("source_lang".to_string(), source_doc.lang.clone().into());
+    doc.metadata
+        .insert("target_lang".to_string(), doc.lang.clone().into());
+    doc.metadata
+        .insert("word_count".to_string(), word_count.into());
     let aligned_usable_segment_count = crate::subtitles::usable_segment_count(&doc);
+    let low_confidence_segments = doc
+        .segments
+        .iter()
+        .filter(|seg| {
+            seg.confidence
+                .is_some_and(|c| c < LOW_CONFIDENCE_AVG_LOGPROB)
+        })
+        .count();
 
     let report = TranslateReport {
         engine: "whispercpp_translate".to_string(),
@@ -124,6 +149,7 @@ pub fn translate_doc_whisper_to_en(
         translated_raw_segment_count: translated_raw.stats.raw_segment_count,
         translated_usable_segment_count: translated_raw.stats.usable_segment_count,
         aligned_usable_segment_count,
+        low_confidence_segments,
         warnings,
     };
 
@@ -222,10 +248,12 @@ pub fn glossary_import_csv(paths: &AppPaths, csv_path: &Path) -> Result<usize> {
     Ok(count)
 }
 
+/// Joins the translated segments whose midpoint falls in each source window. The confidence of a
+/// window is that of its least certain translated segment.
 fn align_translated_to_source(
     source: &SubtitleDocument,
     translated: &SubtitleDocument,
-) -> Vec<String> {
+) -> Vec<(String, Option<f32>)> {
     let n = source.segments.len();
     if n == 0 {
         return Vec::new();
@@ -242,6 +270,7 @@ fn align_translated_to_source(
     });
 
     let mut buckets_sorted: Vec<Vec<String>> = vec![Vec::new(); n];
+    let mut confidence_sorted: Vec<Option<f32>> = vec![None; n];
     let mut j = 0_usize;
     for seg in &translated.segments {
         let mid = (seg.start_ms + seg.end_ms) / 2;
@@ -260,15 +289,18 @@ fn align_translated_to_source(
             let t = seg.text.trim();
             if !t.is_empty() {
                 buckets_sorted[j].push(t.to_string());
+                if let Some(c) = seg.confidence {
+                    confidence_sorted[j] = Some(confidence_sorted[j].map_or(c, |prev| prev.min(c)));
+                }
             }
         }
     }
 
-    let mut out: Vec<String> = vec![String::new(); n];
+    let mut out: Vec<(String, Option<f32>)> = vec![(String::new(), None); n];
     for sorted_idx in 0..n {
         let orig_idx = order[sorted_idx];
         let joined = buckets_sorted[sorted_idx].join(" ").trim().to_string();
-        out[orig_idx] = joined;
+        out[orig_idx] = (joined, confidence_sorted[sorted_idx]);
     }
     out
 }
@@ -453,6 +485,7 @@ mod tests {
                     end_ms: 1000,
                     text: "a".to_string(),
                     speaker: None,
+                    confidence: None,
                 },
                 SubtitleSegment {
                     index: 1,
@@ -460,8 +493,10 @@ mod tests {
                     end_ms: 2000,
                     text: "b".to_string(),
                     speaker: None,
+                    confidence: None,
                 },
             ],
+            metadata: Default::default(),
         };
 
         let translated = SubtitleDocument {
@@ -475,6 +510,7 @@ mod tests {
                     end_ms: 900,
                     text: "A".to_string(),
                     speaker: None,
+                    confidence: Some(-0.2),
                 },
                 SubtitleSegment {
                     index: 1,
@@ -482,13 +518,15 @@ mod tests {
                     end_ms: 1900,
                     text: "B".to_string(),
                     speaker: None,
+                    confidence: None,
                 },
             ],
+            metadata: Default::default(),
         };
 
         let aligned = align_translated_to_source(&source, &translated);
         assert_eq!(aligned.len(), 2);
-        assert_eq!(aligned[0], "A");
-        assert_eq!(aligned[1], "B");
+        assert_eq!(aligned[0], ("A".to_string(), Some(-0.2)));
+        assert_eq!(aligned[1], ("B".to_string(), None));
     }
 }
@@ -0,0 +1,33 @@
+use crate::Result;
+use sha2::{Digest, Sha256};
+use std::io::Read;
+use std::path::Path;
+
+/// Hex SHA-256 of the first `prefix_bytes` of `path` (the whole file when it is shorter).
+pub fn compute_file_content_hash_prefix(path: &Path, prefix_bytes: usize) -> Result<String> {
+    let file = std::fs::File::open(path)?;
+    let mut buf = Vec::with_capacity(prefix_bytes.min(1024 * 1024));
+    file.take(prefix_bytes as u64).read_to_end(&mut buf)?;
+    Ok(hex::encode(Sha256::digest(&buf)))
+}
+
+/// True when both files have the same length and bytes.
+pub fn files_have_same_contents(a: &Path, b: &Path) -> Result<bool> {
+    if std::fs::metadata(a)?.len() != std::fs::metadata(b)?.len() {
+        return Ok(false);
+    }
+    let mut a = std::io::BufReader::new(std::fs::File::open(a)?);
+    let mut b = std::io::BufReader::new(std::fs::File::open(b)?);
+    let mut buf_a = vec![0u8; 64 * 1024];
+    let mut buf_b = vec![0u8; 64 * 1024];
+    loop {
+        let read = a.read(&mut buf_a)?;
+        if read == 0 {
+            return Ok(b.read(&mut buf_b[..1])? == 0);
+        }
+        b.read_exact(&mut buf_b[..read])?;
+        if buf_a[..read] != buf_b[..read] {
+            return Ok(false);
+        }
+    }
+}
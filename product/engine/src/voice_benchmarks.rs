@@ -1777,7 +1777,9 @@ mod tests {
                 end_ms: 1200,
                 text: "Hello world".to_string(),
                 speaker: Some("S1".to_string()),
+                confidence: None,
             }],
+            metadata: Default::default(),
         };
         let track_path = paths
             .derived_item_dir("item-1")
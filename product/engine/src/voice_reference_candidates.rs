@@ -488,6 +488,7 @@ fn prepare_candidate_segment(segment: &SubtitleSegment) -> Option<SubtitleSegmen
         end_ms: capped_end_ms,
         text: text.to_string(),
         speaker: segment.speaker.clone(),
+        confidence: segment.confidence,
     })
 }
 
@@ -618,6 +619,7 @@ mod tests {
                     end_ms: 2200,
                     text: "First speaker sentence".to_string(),
                     speaker: Some("S1".to_string()),
+                    confidence: None,
                 },
                 SubtitleSegment {
                     index: 2,
@@ -625,6 +627,7 @@ mod tests {
                     end_ms: 4300,
                     text: "Second speaker sentence".to_string(),
                     speaker: Some("S2".to_string()),
+                    confidence: None,
                 },
                 SubtitleSegment {
                     index: 3,
@@ -632,8 +635,10 @@ mod tests {
                     end_ms: 6700,
                     text: "First speaker follow up".to_string(),
                     speaker: Some("S1".to_string()),
+                    confidence: None,
                 },
             ],
+            metadata: Default::default(),
         };
         let track_path = paths
             .derived_item_dir(item_id)
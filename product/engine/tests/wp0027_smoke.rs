@@ -64,7 +64,8 @@ fn wait_for_job_done(
             }));
         }
 
-        let jobs = jobs::list_jobs(paths, 200, 0).map_err(|e| format!("list_jobs failed: {e}"))?;
+        let jobs = jobs::list_jobs(paths, 200, 0, None, None)
+            .map_err(|e| format!("list_jobs failed: {e}"))?;
         let target = jobs.into_iter().find(|j| j.id == job_id);
         if let Some(job) = target {
             if matches!(job.status, jobs::JobStatus::Succeeded) {
@@ -131,11 +132,12 @@ fn wp_0027_phase2_smoke_chain_on_sample() -> SmokeResult<()> {
         .map_err(|e| format!("install whisper model failed: {e}"))?;
 
     let item = library::import_local_file(&paths, &sample)
-        .map_err(|e| format!("import local failed: {e}"))?;
+        .map_err(|e| format!("import local failed: {e}"))?
+        .item;
 
     let runner = jobs::start_runner(paths.clone())?;
 
-    let install_py = tools::install_python_toolchain(&paths)
+    let install_py = tools::install_python_toolchain(&paths, false)
         .map_err(|e| format!("install python toolchain failed: {e}"))?;
     assert!(install_py.venv_exists, "python venv should exist");
 
@@ -154,7 +156,7 @@ fn wp_0027_phase2_smoke_chain_on_sample() -> SmokeResult<()> {
         .map_err(|e| format!("install tts preview failed: {e}"))?;
     assert!(tts_status.installed, "tts preview pack should be installed");
 
-    let asr_job = jobs::enqueue_asr_local(&paths, item.id.clone(), Some("ja".to_string()))
+    let asr_job = jobs::enqueue_asr_local(&paths, item.id.clone(), Some("ja".to_string()), None)
         .map_err(|e| format!("enqueue asr failed: {e}"))?;
     let _ = wait_for_job_done(
         &paths,